@@ -1,8 +1,24 @@
 // list_agents command
 // 对应 CLI: detectInstalledAgents + getAgentConfig
 
-use crate::core::agents::{AgentInfo, AgentType};
+use crate::core::agents::{AgentInfo, AgentPathInfo, AgentType};
 use crate::error::AppError;
+use serde::Serialize;
+use specta::Type;
+use std::path::PathBuf;
+
+/// 重新检测 agents 的结果
+#[derive(Debug, Clone, Serialize, Type)]
+#[serde(rename_all = "camelCase")]
+#[specta(rename_all = "camelCase")]
+pub struct AgentRefresh {
+    /// 所有 agents（与 list_agents 相同）
+    pub agents: Vec<AgentInfo>,
+    /// 本次检测到、但不在 previous_detected 中的 agents
+    pub newly_detected: Vec<AgentType>,
+    /// 在 previous_detected 中、但本次未检测到的 agents
+    pub newly_missing: Vec<AgentType>,
+}
 
 /// 列出所有 Agents（包括未安装的）
 /// 返回完整信息供前端使用，前端无需额外计算
@@ -16,3 +32,84 @@ pub fn list_agents() -> Result<Vec<AgentInfo>, AppError> {
 
     Ok(agents)
 }
+
+/// 重新检测 agents，并与调用方之前的检测结果对比
+///
+/// 前端缓存 list_agents 结果时，可用此命令发现运行期间新安装/卸载的 agent，
+/// 只更新变化的部分。
+///
+/// # Arguments
+/// * `previous_detected` - 之前检测到的 agent id 列表（None 视为空）
+#[tauri::command]
+#[specta::specta]
+pub fn refresh_agents(previous_detected: Option<Vec<AgentType>>) -> Result<AgentRefresh, AppError> {
+    let agents = list_agents()?;
+    Ok(diff_detected(agents, &previous_detected.unwrap_or_default()))
+}
+
+/// 按 AgentType 顺序计算新增/消失的 agents
+fn diff_detected(agents: Vec<AgentInfo>, previous: &[AgentType]) -> AgentRefresh {
+    let newly_detected = agents
+        .iter()
+        .filter(|a| a.detected && !previous.contains(&a.id))
+        .map(|a| a.id)
+        .collect();
+    let newly_missing = agents
+        .iter()
+        .filter(|a| !a.detected && previous.contains(&a.id))
+        .map(|a| a.id)
+        .collect();
+
+    AgentRefresh {
+        agents,
+        newly_detected,
+        newly_missing,
+    }
+}
+
+/// 列出所有 Agents 实际解析出的 skills 目录及其是否存在（诊断用）
+///
+/// # Arguments
+/// * `project_path` - 解析项目级目录的基准路径，默认当前工作目录
+#[tauri::command]
+#[specta::specta]
+pub fn get_agent_paths(project_path: Option<String>) -> Result<Vec<AgentPathInfo>, AppError> {
+    let cwd = match project_path {
+        Some(p) => PathBuf::from(p),
+        None => std::env::current_dir()?,
+    };
+
+    let paths: Vec<AgentPathInfo> = AgentType::all()
+        .map(|agent| agent.to_agent_path_info(&cwd))
+        .collect();
+
+    Ok(paths)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn agent(id: AgentType, detected: bool) -> AgentInfo {
+        AgentInfo {
+            detected,
+            ..id.to_agent_info()
+        }
+    }
+
+    #[test]
+    fn test_diff_detected() {
+        let agents = vec![
+            agent(AgentType::ClaudeCode, true),
+            agent(AgentType::Cursor, true),
+            agent(AgentType::Windsurf, false),
+            agent(AgentType::Amp, false),
+        ];
+        let previous = [AgentType::ClaudeCode, AgentType::Windsurf];
+
+        let refresh = diff_detected(agents, &previous);
+        assert_eq!(refresh.agents.len(), 4);
+        assert_eq!(refresh.newly_detected, vec![AgentType::Cursor]);
+        assert_eq!(refresh.newly_missing, vec![AgentType::Windsurf]);
+    }
+}
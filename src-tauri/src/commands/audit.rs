@@ -3,6 +3,8 @@ use crate::error::AppError;
 use std::collections::HashMap;
 
 /// 检查 skill 的安全审计数据
+///
+/// 审计已关闭（disableAudit / SKILL_DECK_DISABLE_AUDIT）时直接返回 None
 #[tauri::command]
 #[specta::specta]
 pub async fn check_skill_audit(
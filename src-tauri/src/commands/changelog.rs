@@ -0,0 +1,74 @@
+//! Skill 变更历史命令
+//!
+//! 提供命令：
+//! - skill_changelog: 列出来源仓库中自安装以来修改过该 skill 的提交
+
+use crate::commands::install::clone_with_events;
+use crate::commands::operation::{Operation, OperationKind};
+use crate::commands::update::{read_locked_source, skill_folder_from_path};
+use crate::core::changelog::{self, SkillChangelog, CHANGELOG_CLONE_DEPTH};
+use crate::core::{parse_source, CloneDepth};
+use crate::error::AppError;
+use crate::models::{Scope, SourceType};
+
+/// 列出已安装 skill 自安装以来的提交
+///
+/// 按 lock 中记录的来源克隆较深的历史（最多 500 个提交），用 lock 中的目录 tree hash
+/// 定位已安装的版本；只支持 git 来源（GitHub / GitLab / 其他 git 仓库）。
+///
+/// # Arguments
+/// * `scope` - 安装范围
+/// * `name` - skill 名称
+/// * `project_path` - Project scope 时的项目路径
+/// * `op_id` - 前端指定的操作 id（用于 operation-event、clone-progress 和 cancel_clone），未传时自动生成
+#[tauri::command]
+#[specta::specta]
+pub async fn skill_changelog(
+    app: tauri::AppHandle,
+    scope: Scope,
+    name: String,
+    project_path: Option<String>,
+    op_id: Option<String>,
+) -> Result<SkillChangelog, AppError> {
+    let op = Operation::start(&app, OperationKind::Changelog, op_id);
+    let result = skill_changelog_inner(&app, &scope, &name, project_path.as_deref(), op.id());
+    op.finish(result, |changelog| format!("{} commits", changelog.commits.len()))
+}
+
+fn skill_changelog_inner(
+    app: &tauri::AppHandle,
+    scope: &Scope,
+    name: &str,
+    project_path: Option<&str>,
+    op_id: &str,
+) -> Result<SkillChangelog, AppError> {
+    let locked = read_locked_source(scope, name, project_path)?;
+    let parsed = parse_source(&locked.source_url)?;
+    if !matches!(
+        parsed.source_type,
+        SourceType::GitHub | SourceType::GitLab | SourceType::Git
+    ) {
+        return Err(AppError::InvalidSource {
+            value: format!("Changelog requires a git source: {}", locked.source),
+        });
+    }
+
+    let (repo_path, _temp_dir) = clone_with_events(
+        app,
+        &parsed.url,
+        parsed.git_ref.as_deref(),
+        CloneDepth::Depth(CHANGELOG_CLONE_DEPTH),
+        Some(op_id),
+    )?;
+    let folder = locked
+        .skill_path
+        .as_deref()
+        .map(skill_folder_from_path)
+        .unwrap_or_default();
+    changelog::skill_changelog(
+        &repo_path,
+        &folder,
+        locked.folder_hash.as_deref(),
+        locked.updated_at.as_deref(),
+    )
+}
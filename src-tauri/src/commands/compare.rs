@@ -0,0 +1,213 @@
+//! 来源对比命令
+//!
+//! 提供命令：
+//! - compare_sources: 并发获取两个来源的 skills 列表，按名称对比差异
+//!
+//! 两个来源各在独立线程中获取（克隆为阻塞操作）。任一来源失败时不中断命令，
+//! 在结果中记录该侧的错误，另一侧的 skills 全部列入其独有列表。
+
+use crate::commands::install::fetch_available_inner;
+use crate::error::AppError;
+use crate::models::FetchResult;
+use serde::Serialize;
+use specta::Type;
+use std::collections::BTreeMap;
+use tauri::AppHandle;
+
+/// 只存在于一侧的 skill
+#[derive(Debug, Clone, Serialize, Type)]
+#[serde(rename_all = "camelCase")]
+#[specta(rename_all = "camelCase")]
+pub struct ComparedSkill {
+    pub name: String,
+    pub description: String,
+}
+
+/// 两侧都存在的 skill
+#[derive(Debug, Clone, Serialize, Type)]
+#[serde(rename_all = "camelCase")]
+#[specta(rename_all = "camelCase")]
+pub struct CommonSkill {
+    pub name: String,
+    /// source_a 中的描述
+    pub description_a: String,
+    /// source_b 中的描述
+    pub description_b: String,
+}
+
+/// 来源对比结果（以 source_a 为基准）
+#[derive(Debug, Clone, Serialize, Type)]
+#[serde(rename_all = "camelCase")]
+#[specta(rename_all = "camelCase")]
+pub struct SourceComparison {
+    /// 只在 source_b 中存在
+    pub added: Vec<ComparedSkill>,
+    /// 只在 source_a 中存在
+    pub removed: Vec<ComparedSkill>,
+    /// 两侧都存在
+    pub common: Vec<CommonSkill>,
+    /// source_a 获取失败时的错误信息
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error_a: Option<String>,
+    /// source_b 获取失败时的错误信息
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error_b: Option<String>,
+}
+
+/// 对比两个来源的 skills 列表
+///
+/// 按 skill 名称匹配，各列表按名称排序。
+///
+/// # Arguments
+/// * `source_a` - 基准来源（格式同 fetch_available）
+/// * `source_b` - 对比来源
+#[tauri::command]
+#[specta::specta]
+pub async fn compare_sources(
+    app: AppHandle,
+    source_a: String,
+    source_b: String,
+) -> Result<SourceComparison, AppError> {
+    let (result_a, result_b) = tauri::async_runtime::spawn_blocking(move || {
+        let fetch = |source: &str| {
+            tauri::async_runtime::block_on(fetch_available_inner(&app, source, None, None, None))
+        };
+        std::thread::scope(|scope| {
+            let handle_a = scope.spawn(|| fetch(&source_a));
+            let result_b = fetch(&source_b);
+            (join_fetch(handle_a.join()), result_b)
+        })
+    })
+    .await
+    .map_err(|e| AppError::Custom { message: format!("Compare task failed: {}", e) })?;
+
+    Ok(compare_fetch_results(result_a, result_b))
+}
+
+/// 获取线程 panic 时转为错误
+fn join_fetch(
+    joined: std::thread::Result<Result<FetchResult, AppError>>,
+) -> Result<FetchResult, AppError> {
+    joined.unwrap_or_else(|_| {
+        Err(AppError::Custom {
+            message: "Fetch task panicked".to_string(),
+        })
+    })
+}
+
+/// 按名称对比两侧的获取结果
+fn compare_fetch_results(
+    result_a: Result<FetchResult, AppError>,
+    result_b: Result<FetchResult, AppError>,
+) -> SourceComparison {
+    let (skills_a, error_a) = split_result(result_a);
+    let (mut skills_b, error_b) = split_result(result_b);
+
+    let mut removed = Vec::new();
+    let mut common = Vec::new();
+    for (name, description_a) in skills_a {
+        match skills_b.remove(&name) {
+            Some(description_b) => common.push(CommonSkill {
+                name,
+                description_a,
+                description_b,
+            }),
+            None => removed.push(ComparedSkill {
+                name,
+                description: description_a,
+            }),
+        }
+    }
+    let added = skills_b
+        .into_iter()
+        .map(|(name, description)| ComparedSkill { name, description })
+        .collect();
+
+    SourceComparison {
+        added,
+        removed,
+        common,
+        error_a,
+        error_b,
+    }
+}
+
+/// 拆分为「名称 → 描述」（按名称排序）和错误信息
+fn split_result(result: Result<FetchResult, AppError>) -> (BTreeMap<String, String>, Option<String>) {
+    match result {
+        Ok(fetched) => (
+            fetched
+                .skills
+                .into_iter()
+                .map(|s| (s.name, s.description))
+                .collect(),
+            None,
+        ),
+        Err(e) => (BTreeMap::new(), Some(e.to_string())),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::AvailableSkill;
+
+    fn fetch_result(skills: &[(&str, &str)]) -> FetchResult {
+        FetchResult {
+            source_type: "github".to_string(),
+            source_url: "https://github.com/owner/repo".to_string(),
+            skill_filter: None,
+            skills: skills
+                .iter()
+                .map(|(name, description)| AvailableSkill {
+                    name: name.to_string(),
+                    description: description.to_string(),
+                    relative_path: format!("skills/{}", name),
+                    plugin_name: None,
+                    version: None,
+                    author: None,
+                    license: None,
+                    tags: Vec::new(),
+                    category: None,
+                    icon_url: None,
+                    locales: Vec::new(),
+                    audit: None,
+                    content_hash: None,
+                })
+                .collect(),
+            warnings: Vec::new(),
+        }
+    }
+
+    fn names(skills: &[ComparedSkill]) -> Vec<&str> {
+        skills.iter().map(|s| s.name.as_str()).collect()
+    }
+
+    #[test]
+    fn test_compare_fetch_results() {
+        let comparison = compare_fetch_results(
+            Ok(fetch_result(&[("pdf", "Old PDF"), ("docx", "Word"), ("xlsx", "Excel")])),
+            Ok(fetch_result(&[("pdf", "New PDF"), ("pptx", "Slides"), ("csv", "CSV")])),
+        );
+
+        assert_eq!(names(&comparison.added), vec!["csv", "pptx"]);
+        assert_eq!(names(&comparison.removed), vec!["docx", "xlsx"]);
+        assert_eq!(comparison.common.len(), 1);
+        assert_eq!(comparison.common[0].description_a, "Old PDF");
+        assert_eq!(comparison.common[0].description_b, "New PDF");
+        assert!(comparison.error_a.is_none() && comparison.error_b.is_none());
+    }
+
+    #[test]
+    fn test_compare_with_failed_side() {
+        let comparison = compare_fetch_results(
+            Ok(fetch_result(&[("pdf", "PDF")])),
+            Err(AppError::GitRepoNotFound { repo: "owner/missing".to_string() }),
+        );
+
+        assert_eq!(names(&comparison.removed), vec!["pdf"]);
+        assert!(comparison.added.is_empty() && comparison.common.is_empty());
+        assert!(comparison.error_a.is_none());
+        assert!(comparison.error_b.unwrap().contains("owner/missing"));
+    }
+}
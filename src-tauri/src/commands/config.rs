@@ -1,13 +1,28 @@
+use crate::core::agents::{set_agent_dir_overrides, validate_agent_dir_override};
+use crate::core::atomic_write::{with_locked_file, write_atomic};
+use crate::core::audit::set_audit_disabled;
+use crate::core::clone_cache::set_clone_retention;
+use crate::core::git::set_max_concurrent_clones;
+use crate::core::git_binary::{set_git_path, validate_git_path};
+use crate::core::http_client::{set_api_headers, validate_header_value};
+use crate::core::installer::{set_copy_excludes, validate_copy_excludes};
+use crate::core::offline::set_offline;
+use crate::core::paths::{
+    set_canonical_dir_override, skill_deck_config_path, validate_canonical_dir_override,
+};
 use crate::core::skill_lock;
+use crate::core::source_parser::set_gitlab_hosts;
 use crate::error::AppError;
-use crate::models::SkillDeckConfig;
+use crate::models::{SkillDeckConfig, SourceHistoryEntry};
 use std::fs;
 use std::path::PathBuf;
 
+/// 来源历史最多保留的条数
+const MAX_SOURCE_HISTORY: usize = 20;
+
 /// 获取配置文件路径: ~/.skill-deck/config.json
 fn get_config_path() -> Result<PathBuf, AppError> {
-    let home = dirs::home_dir().ok_or(AppError::Path { message: "无法获取用户主目录".to_string() })?;
-    Ok(home.join(".skill-deck").join("config.json"))
+    Ok(skill_deck_config_path())
 }
 
 /// 获取配置
@@ -38,42 +53,180 @@ pub fn get_config() -> Result<SkillDeckConfig, AppError> {
 }
 
 /// 保存配置
-/// 目录不存在时自动创建
+/// 目录不存在时自动创建；原子写入并持有与 record_source_usage 相同的文件锁
+/// canonicalDirOverride 和 gitPath 必须为绝对路径，agentDirOverrides 中全局目录必须为绝对路径、项目目录必须为相对路径，
+/// 保存后立即生效（gitlabHosts、disableAudit、maxConcurrentClones、offline、userAgent、githubAccept 同样立即生效）
 #[tauri::command]
 #[specta::specta]
 pub fn save_config(config: SkillDeckConfig) -> Result<(), AppError> {
     let path = get_config_path()?;
 
-    // 确保目录存在
-    if let Some(parent) = path.parent() {
-        fs::create_dir_all(parent)?;
+    // 校验 canonical 目录覆盖
+    let canonical_override = match config.canonical_dir_override.as_deref() {
+        Some(value) => Some(validate_canonical_dir_override(value)?),
+        None => None,
+    };
+
+    // 校验 agent 目录覆盖
+    for (agent, dir_override) in &config.agent_dir_overrides {
+        validate_agent_dir_override(agent, dir_override)?;
+    }
+
+    // 校验 git 路径
+    let git_path = match config.git_path.as_deref() {
+        Some(value) => validate_git_path(value)?,
+        None => None,
+    };
+
+    // 校验复制排除项
+    validate_copy_excludes(&config.copy_exclude_files)?;
+    validate_copy_excludes(&config.copy_exclude_dirs)?;
+
+    // 校验请求头
+    if let Some(value) = config.user_agent.as_deref() {
+        validate_header_value("User-Agent", value)?;
+    }
+    if let Some(value) = config.github_accept.as_deref() {
+        validate_header_value("Accept", value)?;
     }
 
     let content = serde_json::to_string_pretty(&config)?;
 
-    fs::write(&path, content)?;
+    write_atomic(&path, content.as_bytes())?;
+    set_canonical_dir_override(canonical_override);
+    set_gitlab_hosts(&config.gitlab_hosts);
+    set_audit_disabled(config.disable_audit);
+    set_max_concurrent_clones(config.max_concurrent_clones);
+    set_offline(config.offline);
+    set_agent_dir_overrides(&config.agent_dir_overrides);
+    set_git_path(git_path);
+    set_clone_retention(config.retain_clones, config.max_retained_clones);
+    set_copy_excludes(
+        &config.copy_exclude_files,
+        &config.copy_exclude_dirs,
+        config.replace_copy_excludes,
+    );
+    set_api_headers(config.user_agent.as_deref(), config.github_accept.as_deref());
 
     log::info!("配置已保存到: {:?}", path);
     Ok(())
 }
 
 /// 获取上次选择的 agents
-/// 读取 ~/.agents/.skill-lock.json 中的 lastSelectedAgents
+/// 提供 project_path 且该项目有记录时返回项目级默认 agents，
+/// 否则读取 ~/.agents/.skill-lock.json 中的 lastSelectedAgents
 #[tauri::command]
 #[specta::specta]
-pub fn get_last_selected_agents() -> Vec<String> {
-    skill_lock::get_last_selected_agents().unwrap_or_default()
+pub fn get_last_selected_agents(project_path: Option<String>) -> Vec<String> {
+    let config = match project_path {
+        Some(_) => get_config().unwrap_or_default(),
+        None => SkillDeckConfig::default(),
+    };
+    resolve_selected_agents(
+        &config,
+        project_path.as_deref(),
+        skill_lock::get_last_selected_agents(),
+    )
 }
 
 /// 保存选择的 agents
-/// 写入 ~/.agents/.skill-lock.json 中的 lastSelectedAgents
+/// 提供 project_path 时写入 config.json 的 projectDefaultAgents，
+/// 否则写入 ~/.agents/.skill-lock.json 中的 lastSelectedAgents
 #[tauri::command]
 #[specta::specta]
-pub fn save_last_selected_agents(agents: Vec<String>) -> Result<(), AppError> {
-    skill_lock::save_selected_agents(&agents)?;
+pub fn save_last_selected_agents(
+    agents: Vec<String>,
+    project_path: Option<String>,
+) -> Result<(), AppError> {
+    match project_path {
+        Some(path) => {
+            let mut config = get_config()?;
+            config.project_default_agents.insert(path, agents);
+            save_config(config)?;
+        }
+        None => skill_lock::save_selected_agents(&agents)?,
+    }
     Ok(())
 }
 
+/// 获取最近安装过的来源（最近使用的在前），供安装向导快速选择
+#[tauri::command]
+#[specta::specta]
+pub fn get_source_history() -> Result<Vec<SourceHistoryEntry>, AppError> {
+    Ok(get_config()?.sources)
+}
+
+/// 记录一次来源使用（install_skills 成功安装后调用）
+///
+/// 在 config.json 的文件锁内只改写 `sources`，其他字段原样保留，
+/// 不会覆盖同时保存的设置，也不会重新应用其余配置
+pub fn record_source_usage(source: &str, skill_count: usize) -> Result<(), AppError> {
+    let now = chrono::Utc::now().format("%Y-%m-%dT%H:%M:%S%.3fZ").to_string();
+    with_locked_file(&get_config_path()?, |content| {
+        Ok((update_source_history(content.as_deref(), source, skill_count, now), ()))
+    })
+}
+
+/// 返回只更新了 `sources` 的 config.json 内容
+///
+/// 现有内容不是 JSON 对象时返回 None（不写入，避免覆盖用户无法解析的配置）
+fn update_source_history(
+    content: Option<&str>,
+    source: &str,
+    skill_count: usize,
+    now: String,
+) -> Option<String> {
+    let mut config = match content {
+        Some(content) => match serde_json::from_str::<serde_json::Value>(content) {
+            Ok(serde_json::Value::Object(map)) => map,
+            _ => {
+                log::warn!("配置文件无法解析，跳过记录来源历史");
+                return None;
+            }
+        },
+        None => serde_json::Map::new(),
+    };
+    let mut history: Vec<SourceHistoryEntry> = config
+        .get("sources")
+        .and_then(|v| serde_json::from_value(v.clone()).ok())
+        .unwrap_or_default();
+    push_source_history(&mut history, source, skill_count, now);
+    config.insert("sources".to_string(), serde_json::to_value(history).ok()?);
+    serde_json::to_string_pretty(&config).ok()
+}
+
+/// 将来源移到历史最前面（同一来源只保留一条），超出上限时丢弃最旧的
+fn push_source_history(history: &mut Vec<SourceHistoryEntry>, source: &str, skill_count: usize, now: String) {
+    let source = source.trim();
+    if source.is_empty() {
+        return;
+    }
+    history.retain(|entry| entry.source != source);
+    history.insert(
+        0,
+        SourceHistoryEntry {
+            source: source.to_string(),
+            last_used_at: now,
+            skill_count: skill_count as u32,
+        },
+    );
+    history.truncate(MAX_SOURCE_HISTORY);
+}
+
+/// 项目级记录优先，未记录时回退到全局列表
+///
+/// 旧版本只有全局列表，项目首次保存前沿用它，因此无需迁移
+fn resolve_selected_agents(
+    config: &SkillDeckConfig,
+    project_path: Option<&str>,
+    global: Option<Vec<String>>,
+) -> Vec<String> {
+    project_path
+        .and_then(|p| config.project_default_agents.get(p).cloned())
+        .or(global)
+        .unwrap_or_default()
+}
+
 /// 添加项目路径
 /// 已存在则忽略，返回更新后的 projects 列表
 #[tauri::command]
@@ -129,3 +282,74 @@ pub fn open_in_explorer(path: String) -> Result<(), AppError> {
     }
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_resolve_selected_agents() {
+        let mut config = SkillDeckConfig::default();
+        config
+            .project_default_agents
+            .insert("/work/a".to_string(), vec!["cursor".to_string()]);
+        let global = Some(vec!["claude-code".to_string()]);
+
+        // 项目有记录：使用项目级
+        assert_eq!(
+            resolve_selected_agents(&config, Some("/work/a"), global.clone()),
+            vec!["cursor".to_string()]
+        );
+        // 项目无记录：回退全局
+        assert_eq!(
+            resolve_selected_agents(&config, Some("/work/b"), global.clone()),
+            vec!["claude-code".to_string()]
+        );
+        // 未提供项目路径：全局
+        assert_eq!(
+            resolve_selected_agents(&config, None, global),
+            vec!["claude-code".to_string()]
+        );
+        assert!(resolve_selected_agents(&config, None, None).is_empty());
+    }
+
+    #[test]
+    fn test_push_source_history() {
+        let mut history = Vec::new();
+        push_source_history(&mut history, "owner/a", 2, "t1".to_string());
+        push_source_history(&mut history, " owner/b ", 1, "t2".to_string());
+        push_source_history(&mut history, "", 1, "t3".to_string());
+        // 再次使用的来源移到最前面并更新记录
+        push_source_history(&mut history, "owner/a", 3, "t4".to_string());
+        let sources: Vec<&str> = history.iter().map(|e| e.source.as_str()).collect();
+        assert_eq!(sources, vec!["owner/a", "owner/b"]);
+        assert_eq!(history[0].last_used_at, "t4");
+        assert_eq!(history[0].skill_count, 3);
+
+        for i in 0..30 {
+            push_source_history(&mut history, &format!("owner/repo-{}", i), 1, "t".to_string());
+        }
+        assert_eq!(history.len(), MAX_SOURCE_HISTORY);
+        assert_eq!(history[0].source, "owner/repo-29");
+    }
+
+    #[test]
+    fn test_update_source_history_keeps_other_fields() {
+        let content = r#"{"projects":["/work/a"],"offline":true,"sources":[{"source":"owner/a","lastUsedAt":"t1","skillCount":1}]}"#;
+        let updated = update_source_history(Some(content), "owner/b", 2, "t2".to_string()).unwrap();
+        let config: SkillDeckConfig = serde_json::from_str(&updated).unwrap();
+        assert_eq!(config.projects, vec!["/work/a"]);
+        assert!(config.offline);
+        let sources: Vec<&str> = config.sources.iter().map(|e| e.source.as_str()).collect();
+        assert_eq!(sources, vec!["owner/b", "owner/a"]);
+
+        assert!(update_source_history(None, "owner/a", 1, "t".to_string()).is_some());
+        assert!(update_source_history(Some("not json"), "owner/a", 1, "t".to_string()).is_none());
+    }
+
+    #[test]
+    fn test_config_without_project_defaults_deserializes() {
+        let config: SkillDeckConfig = serde_json::from_str(r#"{"projects":["/work/a"]}"#).unwrap();
+        assert!(config.project_default_agents.is_empty());
+    }
+}
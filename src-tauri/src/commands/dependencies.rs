@@ -0,0 +1,74 @@
+//! 依赖树分析命令
+//!
+//! 提供命令：
+//! - resolve_dependencies: 解析 skill 的完整依赖图（只读，不安装）
+
+use crate::commands::install::prepare_skills_dir;
+use crate::commands::operation::{Operation, OperationKind};
+use crate::core::dependencies::{resolve_dependency_graph, SourceSkill, DEFAULT_MAX_DEPENDENCY_DEPTH};
+use crate::core::skill::parse_skill_md;
+use crate::core::{discover_skills_in_subpaths, parse_source, DiscoverOptions};
+use crate::error::AppError;
+use crate::models::{DependencyGraph, ParsedSource};
+use tauri::AppHandle;
+
+/// 解析 skill 的依赖图
+///
+/// 克隆（或读取）来源，按 SKILL.md frontmatter 的 `dependencies` 逐层加载依赖所在的来源，
+/// 返回节点、边、依赖环和无法解析的依赖，供安装前展示。
+///
+/// # Arguments
+/// * `source` - 来源字符串
+/// * `skill_name` - 根 skill 名称
+/// * `max_depth` - 最大依赖深度（默认 5）
+/// * `op_id` - 前端指定的操作 id（用于 operation-event、clone-progress 和 cancel_clone），未传时自动生成
+#[tauri::command]
+#[specta::specta]
+pub async fn resolve_dependencies(
+    app: AppHandle,
+    source: String,
+    skill_name: String,
+    max_depth: Option<u32>,
+    op_id: Option<String>,
+) -> Result<DependencyGraph, AppError> {
+    let op = Operation::start(&app, OperationKind::ResolveDependencies, op_id);
+    let max_depth = max_depth.unwrap_or(DEFAULT_MAX_DEPENDENCY_DEPTH);
+    let result = match parse_source(&source) {
+        Ok(parsed) => {
+            resolve_dependency_graph(parsed, &skill_name, max_depth, |parsed| {
+                let app = app.clone();
+                let op_id = op.id().to_string();
+                async move { load_source_skills(&app, &parsed, &op_id).await }
+            })
+            .await
+        }
+        Err(e) => Err(e),
+    };
+    op.finish(result, |graph| {
+        format!("{} skills, {} unresolved", graph.nodes.len(), graph.unresolved.len())
+    })
+}
+
+/// 克隆或下载来源，列出其中的 skills 及其依赖声明（临时目录在返回时删除）
+async fn load_source_skills(
+    app: &AppHandle,
+    parsed: &ParsedSource,
+    op_id: &str,
+) -> Result<Vec<SourceSkill>, AppError> {
+    let (skills_dir, _temp_dir) = prepare_skills_dir(app, parsed, Some(op_id)).await?;
+    let options = DiscoverOptions {
+        include_internal: true,
+        full_depth: false,
+        max_depth: None,
+    };
+    let discovered = discover_skills_in_subpaths(&skills_dir, &parsed.search_subpaths(), options)?;
+    Ok(discovered
+        .into_iter()
+        .map(|skill| SourceSkill {
+            dependencies: parse_skill_md(&skill.path.join("SKILL.md"))
+                .map(|frontmatter| frontmatter.dependencies)
+                .unwrap_or_default(),
+            name: skill.name,
+        })
+        .collect())
+}
@@ -0,0 +1,32 @@
+//! 脱离 canonical 目录的 Tauri Command
+//!
+//! 提供命令：
+//! - detach_skill: 将 symlink 模式的 agent 目录转为独立副本
+//!
+//! GUI 增强：CLI 无对应命令，用于单独修改某个 agent 下的 skill 而不影响 canonical 来源
+
+use crate::core::agents::AgentType;
+use crate::core::detacher;
+use crate::error::AppError;
+use crate::models::{DetachResult, Scope};
+
+/// 将 skill 在指定 agents 下的 symlink 替换为 canonical 目录的独立副本
+///
+/// 脱离的 agents 记录在 lock 中，之后 update_skill / reinstall_skill 不会覆盖这些副本。
+///
+/// # Arguments
+/// * `scope` - 范围（global/project）
+/// * `name` - skill 名称
+/// * `project_path` - Project scope 时的项目路径
+/// * `agents` - 目标 agents（None 或空列表 = 所有以 symlink 安装了该 skill 的 agents）
+#[tauri::command]
+#[specta::specta]
+pub async fn detach_skill(
+    scope: Scope,
+    name: String,
+    project_path: Option<String>,
+    agents: Option<Vec<AgentType>>,
+) -> Result<DetachResult, AppError> {
+    let agents = agents.filter(|a| !a.is_empty());
+    detacher::detach_skill(&name, &scope, project_path.as_deref(), agents.as_deref())
+}
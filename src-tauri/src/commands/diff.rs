@@ -0,0 +1,212 @@
+//! Skill 差异对比命令
+//!
+//! 提供命令：
+//! - diff_skill: 对比已安装的 skill 与来源中的最新版本，便于更新前审阅改动
+//!
+//! 来源内容先按安装规则复制到临时目录，避免 metadata.json、.skillignore
+//! 排除的文件等被误报为改动。
+
+use crate::commands::update::{fetch_locked_skill, read_locked_source};
+use crate::core::installer::stage_installable;
+use crate::core::local_lock::HASH_SKIP_DIRS;
+use crate::core::paths::canonical_skills_dir;
+use crate::core::skill::skill_dir_name;
+use crate::error::AppError;
+use crate::models::Scope;
+use serde::Serialize;
+use similar::TextDiff;
+use specta::Type;
+use std::collections::BTreeSet;
+use std::fs;
+use std::path::Path;
+use walkdir::WalkDir;
+
+/// 超过该大小的文件不生成文本 diff（字节）
+const MAX_TEXT_DIFF_BYTES: u64 = 512 * 1024;
+
+/// unified diff 的上下文行数
+const DIFF_CONTEXT_LINES: usize = 3;
+
+/// 文件改动类型
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Type)]
+#[serde(rename_all = "lowercase")]
+#[specta(rename_all = "lowercase")]
+pub enum FileChangeKind {
+    Added,
+    Removed,
+    Modified,
+}
+
+/// 单个文件的差异
+#[derive(Debug, Clone, Serialize, Type)]
+#[serde(rename_all = "camelCase")]
+#[specta(rename_all = "camelCase")]
+pub struct SkillFileDiff {
+    /// 相对 skill 目录的路径（`/` 分隔）
+    pub path: String,
+    pub change: FileChangeKind,
+    /// 是否为二进制或超出大小上限的文件（此时不提供 diff 文本）
+    pub binary: bool,
+    /// unified diff 文本（installed → source）
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub diff: Option<String>,
+}
+
+/// 对比已安装 skill 与来源中的最新内容
+///
+/// 按 lock 中记录的来源重新克隆/下载，定位 skill 后与 canonical 目录逐文件比较。
+/// 内容相同的文件不出现在结果中；二进制或超过 `MAX_TEXT_DIFF_BYTES` 的文件
+/// 只报告 `binary: true`。
+///
+/// # Arguments
+/// * `scope` - 安装范围
+/// * `name` - skill 名称
+/// * `project_path` - Project scope 时的项目路径
+#[tauri::command]
+#[specta::specta]
+pub async fn diff_skill(
+    app: tauri::AppHandle,
+    scope: Scope,
+    name: String,
+    project_path: Option<String>,
+) -> Result<Vec<SkillFileDiff>, AppError> {
+    let is_global = matches!(scope, Scope::Global);
+    let cwd = project_path.as_deref().unwrap_or(".");
+    let installed_dir = canonical_skills_dir(is_global, cwd).join(skill_dir_name(&name, is_global, cwd));
+    if !installed_dir.is_dir() {
+        return Err(AppError::PathNotFound {
+            path: installed_dir.to_string_lossy().to_string(),
+        });
+    }
+
+    let locked = read_locked_source(&scope, &name, project_path.as_deref())?;
+    let (skill, _temp_dir) = fetch_locked_skill(&app, &locked, &name, None).await?;
+    let staged = stage_installable(&skill.path)?;
+
+    diff_dirs(&installed_dir, staged.path())
+}
+
+/// 逐文件比较两个目录（old → new），按路径排序返回有差异的文件
+fn diff_dirs(old_dir: &Path, new_dir: &Path) -> Result<Vec<SkillFileDiff>, AppError> {
+    let old_files = collect_relative_files(old_dir);
+    let new_files = collect_relative_files(new_dir);
+
+    let mut diffs = Vec::new();
+    for path in old_files.union(&new_files) {
+        let change = match (old_files.contains(path), new_files.contains(path)) {
+            (true, true) => FileChangeKind::Modified,
+            (false, true) => FileChangeKind::Added,
+            _ => FileChangeKind::Removed,
+        };
+
+        let old = read_if(old_dir, path, change != FileChangeKind::Added)?;
+        let new = read_if(new_dir, path, change != FileChangeKind::Removed)?;
+        if change == FileChangeKind::Modified && old == new {
+            continue;
+        }
+
+        let diff = match (as_diffable_text(&old), as_diffable_text(&new)) {
+            (Some(old_text), Some(new_text)) => Some(
+                TextDiff::from_lines(old_text, new_text)
+                    .unified_diff()
+                    .context_radius(DIFF_CONTEXT_LINES)
+                    .header(&format!("a/{}", path), &format!("b/{}", path))
+                    .to_string(),
+            ),
+            _ => None,
+        };
+
+        diffs.push(SkillFileDiff {
+            path: path.clone(),
+            change,
+            binary: diff.is_none(),
+            diff,
+        });
+    }
+
+    Ok(diffs)
+}
+
+/// 收集目录下所有文件的相对路径（跳过 .git、node_modules）
+fn collect_relative_files(dir: &Path) -> BTreeSet<String> {
+    WalkDir::new(dir)
+        .into_iter()
+        .filter_entry(|e| {
+            !(e.file_type().is_dir()
+                && HASH_SKIP_DIRS.contains(&e.file_name().to_string_lossy().as_ref()))
+        })
+        .filter_map(|e| e.ok())
+        .filter(|e| e.file_type().is_file())
+        .filter_map(|e| {
+            e.path()
+                .strip_prefix(dir)
+                .ok()
+                .map(|p| p.to_string_lossy().replace('\\', "/"))
+        })
+        .collect()
+}
+
+/// 读取文件内容；`exists` 为 false 时视为空文件
+fn read_if(dir: &Path, relative: &str, exists: bool) -> Result<Vec<u8>, AppError> {
+    if !exists {
+        return Ok(Vec::new());
+    }
+    Ok(fs::read(dir.join(relative))?)
+}
+
+/// 可生成文本 diff 时返回 UTF-8 文本（不含 NUL 且不超过大小上限）
+fn as_diffable_text(content: &[u8]) -> Option<&str> {
+    if content.len() as u64 > MAX_TEXT_DIFF_BYTES || content.contains(&0) {
+        return None;
+    }
+    std::str::from_utf8(content).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_diff_dirs() {
+        let old = tempdir().unwrap();
+        let new = tempdir().unwrap();
+
+        fs::write(old.path().join("SKILL.md"), "# Demo\nversion 1\n").unwrap();
+        fs::write(new.path().join("SKILL.md"), "# Demo\nversion 2\n").unwrap();
+        fs::write(old.path().join("same.md"), "unchanged").unwrap();
+        fs::write(new.path().join("same.md"), "unchanged").unwrap();
+        fs::write(old.path().join("old.md"), "removed\n").unwrap();
+        fs::create_dir_all(new.path().join("scripts")).unwrap();
+        fs::write(new.path().join("scripts/run.sh"), "echo hi\n").unwrap();
+        fs::write(old.path().join("logo.png"), [0u8, 1, 2]).unwrap();
+        fs::write(new.path().join("logo.png"), [0u8, 1, 3]).unwrap();
+        // node_modules 不参与比较
+        fs::create_dir_all(new.path().join("node_modules")).unwrap();
+        fs::write(new.path().join("node_modules/x.js"), "x").unwrap();
+
+        let diffs = diff_dirs(old.path(), new.path()).unwrap();
+        let paths: Vec<_> = diffs.iter().map(|d| d.path.as_str()).collect();
+        assert_eq!(paths, vec!["SKILL.md", "logo.png", "old.md", "scripts/run.sh"]);
+
+        let skill_md = &diffs[0];
+        assert_eq!(skill_md.change, FileChangeKind::Modified);
+        let text = skill_md.diff.as_deref().unwrap();
+        assert!(text.contains("--- a/SKILL.md"));
+        assert!(text.contains("-version 1"));
+        assert!(text.contains("+version 2"));
+
+        assert!(diffs[1].binary);
+        assert!(diffs[1].diff.is_none());
+        assert_eq!(diffs[2].change, FileChangeKind::Removed);
+        assert_eq!(diffs[3].change, FileChangeKind::Added);
+        assert!(diffs[3].diff.as_deref().unwrap().contains("+echo hi"));
+    }
+
+    #[test]
+    fn test_large_file_reported_as_binary() {
+        let large = vec![b'a'; MAX_TEXT_DIFF_BYTES as usize + 1];
+        assert!(as_diffable_text(&large).is_none());
+        assert_eq!(as_diffable_text(b"text"), Some("text"));
+    }
+}
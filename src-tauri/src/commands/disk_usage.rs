@@ -0,0 +1,18 @@
+//! 磁盘占用命令
+//!
+//! 提供命令：
+//! - disk_usage: 统计 skill-deck 使用的磁盘空间（global / 克隆缓存 / 各项目）
+
+use crate::commands::config::get_config;
+use crate::core::disk_usage::{self, DiskUsage};
+use crate::error::AppError;
+
+/// 统计 skill-deck 使用的磁盘空间
+///
+/// 汇总 `~/.agents/skills`、克隆缓存目录（开启 retainClones 时）和已保存项目的
+/// canonical 目录，返回各项大小及无法读取而跳过的路径。
+#[tauri::command]
+#[specta::specta]
+pub fn disk_usage() -> Result<DiskUsage, AppError> {
+    Ok(disk_usage::disk_usage(&get_config()?.projects))
+}
@@ -0,0 +1,17 @@
+//! 环境健康检查命令
+//!
+//! 提供命令：
+//! - doctor: 汇总 git、GitHub token、路径、symlink、网络和 agent 检测结果，供排查问题
+
+use crate::core::doctor::{self, DoctorReport};
+use crate::error::AppError;
+
+/// 运行环境健康检查
+///
+/// 每项检查返回 ok / warn / fail 及说明；检查本身失败不会导致命令失败。
+/// 网络检查各有 5 秒超时，离线模式下跳过。
+#[tauri::command]
+#[specta::specta]
+pub async fn doctor() -> Result<DoctorReport, AppError> {
+    Ok(doctor::run_doctor().await)
+}
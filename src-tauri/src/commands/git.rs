@@ -0,0 +1,17 @@
+//! git 相关命令
+//!
+//! 提供命令：
+//! - detect_git: 检测 git 的位置和版本（gitPath 配置、PATH、常见安装位置）
+
+use crate::core::git_binary::{self, GitInfo};
+use crate::error::AppError;
+
+/// 检测 git
+///
+/// 配置了 gitPath 时只检查该路径；否则依次查找 PATH 和常见安装位置，
+/// 返回第一个能运行 `git --version` 的 git。未找到时 `found` 为 false，原因见 `error`。
+#[tauri::command]
+#[specta::specta]
+pub fn detect_git() -> Result<GitInfo, AppError> {
+    Ok(git_binary::detect_git())
+}
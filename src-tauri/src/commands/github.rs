@@ -0,0 +1,17 @@
+//! GitHub 相关命令
+//!
+//! 提供命令：
+//! - github_rate_limit: 查询 GitHub API 剩余额度（更新检测和 hash 计算依赖 GitHub API）
+
+use crate::core::github_api::{fetch_rate_limit, GitHubRateLimit};
+use crate::error::AppError;
+
+/// 查询 GitHub API 限流额度
+///
+/// 返回 core（REST API）和 search 两个桶的 limit / remaining / resetAt，以及是否找到 token。
+/// 未认证时按 IP 计算额度（core 每小时 60 次），配置 token 后提升到 5000 次。
+#[tauri::command]
+#[specta::specta]
+pub async fn github_rate_limit() -> Result<GitHubRateLimit, AppError> {
+    fetch_rate_limit().await
+}
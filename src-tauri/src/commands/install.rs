@@ -1,20 +1,41 @@
 //! 安装相关的 Tauri Commands
 //!
-//! 提供两个命令：
+//! 提供命令：
 //! - fetch_available: 从来源获取可用的 skills 列表
 //! - install_skills: 安装选中的 skills
+//! - cancel_clone: 取消进行中的克隆
+//! - plan_install: 安装前预览安装计划（不克隆）
+//! - preview_skill: 安装前预览单个 skill 的 SKILL.md
+//! - check_symlink_support: 检测 canonical 目录能否创建 symlink
 
+use crate::commands::config::{record_source_usage, save_last_selected_agents};
+use crate::commands::operation::{Operation, OperationKind};
 use crate::core::agents::AgentType;
-use crate::core::local_lock::{add_skill_to_local_lock, compute_skill_folder_hash, LocalSkillLockEntry};
-use crate::core::skill_lock::{add_skill_to_lock, save_selected_agents};
+use crate::core::audit::{fetch_audit_data, SkillAuditData};
+use crate::core::clone_cache::CheckoutDir;
+use crate::core::http_source::{fetch_direct_url_skill, fetch_well_known_skills};
+use crate::core::installer::compute_installable_hash;
+use crate::core::local_lock::{
+    add_skill_to_local_lock, compute_skill_folder_hash, recorded_source_url, LocalSkillLockEntry,
+};
+use crate::core::local_source::{extract_archive, local_source_kind, LocalSourceKind};
+use crate::core::preview::{self, SkillPreview};
+use crate::core::remote_hash::RemoteHashHost;
+use crate::core::skill::sanitize_name;
+use crate::core::skill_lock::{add_skill_to_lock, save_selected_agents, SkillLockEntry};
 use crate::core::{
-    clone_repo_with_progress, discover_skills, fetch_skill_folder_hash, get_owner_repo,
-    install_skill_for_agent, parse_source, CloneProgress, DiscoverOptions,
+    active_clone_operations, begin_clone_operation, cancel_clones, clone_repo_with_progress,
+    discover_skills_in_subpaths, discover_skills_in_subpaths_with_warnings, finish_clone_operation, get_owner_repo,
+    can_symlink, install_skill_for_agent_in_dir, name_glob_matcher, parse_source, DiscoveredSkill,
+    resolve_install_dir_name, stage_localized, write_canonical_dir, CloneDepth, CloneProgress, DiscoverOptions,
 };
 use crate::error::AppError;
 use crate::models::{
-    AvailableSkill, FetchResult, InstallParams, InstallResults, SourceType,
+    AvailableSkill, FetchResult, InstallMode, InstallParams, InstallPlan, InstallResults, ParsedSource,
+    Scope, SourceType,
 };
+use std::collections::{HashMap, HashSet};
+use std::path::PathBuf;
 use tauri::{AppHandle, Emitter};
 
 /// 安装进度事件（发送到前端）
@@ -30,84 +51,280 @@ struct InstallProgress {
     total: usize,
 }
 
+/// clone-progress 事件：克隆进度加所属操作的 id
+#[derive(serde::Serialize)]
+struct CloneProgressEvent<'a> {
+    #[serde(flatten)]
+    progress: &'a CloneProgress,
+    /// 所属操作的 id（来自 Operation，不属于任何操作时为 None）
+    #[serde(rename = "opId", skip_serializing_if = "Option::is_none")]
+    op_id: Option<&'a str>,
+}
+
 /// 从来源获取可用的 skills 列表
 ///
 /// # Arguments
 /// * `source` - 来源字符串（支持 9 种格式）
+/// * `with_audit` - 是否同时获取安全审计数据（默认 false，避免离线时等待 3 秒超时）
+/// * `max_depth` - 递归搜索的最大深度（默认 5，不影响优先目录扫描）
+/// * `git_ref` - 覆盖来源中的分支/tag（来自 list_refs 的选择）
+/// * `name_glob` - 按名称过滤 skills 的 glob（如 `python-*`，忽略大小写）；
+///   与 `@skill` 同时使用时，`@skill` 只在过滤后的结果中生效；无匹配时返回空列表，glob 无效时返回错误
+/// * `op_id` - 前端指定的操作 id（用于 operation-event、clone-progress 和 cancel_clone），未传时自动生成
 ///
 /// # Returns
 /// * `FetchResult` - 包含来源信息和可用 skills 列表
 #[tauri::command]
 #[specta::specta]
-pub async fn fetch_available(app: AppHandle, source: String) -> Result<FetchResult, AppError> {
-    fetch_available_inner(&app, &source)
+pub async fn fetch_available(
+    app: AppHandle,
+    source: String,
+    with_audit: Option<bool>,
+    max_depth: Option<u32>,
+    git_ref: Option<String>,
+    name_glob: Option<String>,
+    op_id: Option<String>,
+) -> Result<FetchResult, AppError> {
+    let op = Operation::start(&app, OperationKind::Fetch, op_id);
+    let max_depth = max_depth.map(|d| d as usize);
+    let result = async {
+        let mut result = fetch_available_inner(&app, &source, max_depth, git_ref, Some(op.id())).await?;
+        filter_by_name_glob(&mut result, name_glob.as_deref())?;
+
+        if with_audit.unwrap_or(false) && !result.skills.is_empty() {
+            let names: Vec<String> = result.skills.iter().map(|s| s.name.clone()).collect();
+            // 审计失败返回 None，不影响 skills 列表
+            let audit = fetch_audit_data(&source, &names).await;
+            attach_audit(&mut result, audit);
+        }
+
+        Ok::<_, AppError>(result)
+    }
+    .await;
+    op.finish(result, |r| format!("{} skills found", r.skills.len()))
+}
+
+/// 只保留名称匹配 glob 的 skills（glob 为空时不过滤，glob 无效时返回错误）
+fn filter_by_name_glob(result: &mut FetchResult, name_glob: Option<&str>) -> Result<(), AppError> {
+    if let Some(pattern) = name_glob.map(str::trim).filter(|p| !p.is_empty()) {
+        let matcher = name_glob_matcher(pattern)?;
+        result.skills.retain(|skill| matcher.is_match(&skill.name));
+    }
+    Ok(())
 }
 
-fn fetch_available_inner(app: &AppHandle, source: &str) -> Result<FetchResult, AppError> {
-    // 1. 解析来源
-    let parsed = parse_source(source)?;
+/// 将审计数据按 skill 名称合并到 FetchResult
+fn attach_audit(result: &mut FetchResult, audit: Option<HashMap<String, SkillAuditData>>) {
+    if let Some(mut audit) = audit {
+        for skill in &mut result.skills {
+            skill.audit = audit.remove(&skill.name);
+        }
+    }
+}
+
+pub(crate) async fn fetch_available_inner(
+    app: &AppHandle,
+    source: &str,
+    max_depth: Option<usize>,
+    git_ref: Option<String>,
+    op_id: Option<&str>,
+) -> Result<FetchResult, AppError> {
+    // 1. 解析来源（用户选择的 ref 优先于来源中的 ref）
+    let mut parsed = parse_source(source)?;
+    if let Some(r) = git_ref {
+        parsed = parsed.with_ref(r);
+    }
+
+    // 2. 确定 skills 目录（临时目录在函数结束前保持存活）
+    let (skills_dir, _temp_dir) = prepare_skills_dir(app, &parsed, op_id).await?;
 
-    // 2. 确定 skills 目录
-    let (skills_dir, _clone_result) = match parsed.source_type {
+    // 3. 发现并构建结果（复用纯逻辑函数）
+    discover_and_build_result(&parsed, &skills_dir, max_depth)
+}
+
+/// 取消克隆的结果
+#[derive(Debug, Clone, serde::Serialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+#[specta(rename_all = "camelCase")]
+pub struct CancelCloneResult {
+    /// 已发出取消信号的克隆数（0 表示没有匹配的进行中克隆）
+    pub cancelled: u32,
+    /// 取消后仍在进行中的克隆所属的操作 id（可据此改为取消其他操作）
+    pub active_op_ids: Vec<String>,
+}
+
+/// 取消进行中的克隆
+///
+/// 终止对应的 git clone 进程，所属操作（fetch_available / install_skills 等）返回 `AppError::Cancelled`
+///
+/// # Arguments
+/// * `op_id` - 只取消该操作的克隆（与发起命令时传入或 operation-event 中的 opId 相同）；
+///   未传时取消所有进行中的克隆
+#[tauri::command]
+#[specta::specta]
+pub fn cancel_clone(op_id: Option<String>) -> CancelCloneResult {
+    let cancelled = cancel_clones(op_id.as_deref());
+    let active_op_ids = active_clone_operations()
+        .into_iter()
+        .filter(|id| cancelled == 0 || op_id.as_deref().is_some_and(|op_id| op_id != id))
+        .collect();
+    CancelCloneResult {
+        cancelled: cancelled as u32,
+        active_op_ids,
+    }
+}
+
+/// 安装前预览某个 skill 的 SKILL.md
+///
+/// 只获取单个文件（GitHub raw / GitLab Files API / `git archive --remote`），
+/// 不完整克隆仓库。内容超过上限时截断，`truncated` 为 true。
+///
+/// # Arguments
+/// * `source` - 来源（同 fetch_available）
+/// * `relative_path` - skill 目录相对来源根目录的路径（AvailableSkill.relativePath）
+/// * `git_ref` - 可选的分支/标签/提交，优先于来源中的 ref
+#[tauri::command]
+#[specta::specta]
+pub async fn preview_skill(
+    source: String,
+    relative_path: String,
+    git_ref: Option<String>,
+) -> Result<SkillPreview, AppError> {
+    let mut parsed = parse_source(&source)?;
+    if let Some(r) = git_ref {
+        parsed = parsed.with_ref(r);
+    }
+    preview::preview_skill(&parsed, &relative_path).await
+}
+
+/// 检测指定范围的 canonical 目录能否创建 symlink
+///
+/// 结果在进程内缓存。返回 false 时 Symlink 模式安装会直接复制到各 agent 目录，
+/// 前端可据此提示或默认选择 Copy 模式。
+///
+/// # Arguments
+/// * `scope` - 安装范围
+/// * `project_path` - Project scope 时的项目路径
+#[tauri::command]
+#[specta::specta]
+pub fn check_symlink_support(scope: Scope, project_path: Option<String>) -> bool {
+    let is_global = matches!(scope, Scope::Global);
+    let cwd = project_path.as_deref().unwrap_or(".");
+    can_symlink(&crate::core::paths::canonical_skills_dir(is_global, cwd))
+}
+
+/// 获取来源对应的 skills 目录
+///
+/// - Local: 直接使用本地路径
+/// - GitHub/GitLab/Git: 克隆到临时目录（发送 clone-progress 事件）
+/// - WellKnown/DirectUrl: 下载 SKILL.md 到临时目录（DirectUrl 带 sha256 时校验内容）
+///
+/// 返回的 CheckoutDir 需要在使用 skills 目录期间保持存活。
+/// `op_id` 为所属操作的 id，随 clone-progress 事件发送，cancel_clone 按它取消克隆。
+pub(crate) async fn prepare_skills_dir(
+    app: &AppHandle,
+    parsed: &ParsedSource,
+    op_id: Option<&str>,
+) -> Result<(PathBuf, Option<CheckoutDir>), AppError> {
+    match parsed.source_type {
         SourceType::Local => {
             let path = parsed
                 .local_path
                 .as_ref()
                 .ok_or_else(|| AppError::InvalidSource { value: "Missing local path".to_string() })?;
-            (path.clone(), None)
+            match local_source_kind(path) {
+                LocalSourceKind::Directory => Ok((path.clone(), None)),
+                // git bundle 与远程仓库走同一克隆流程
+                LocalSourceKind::GitBundle => {
+                    clone_with_events(app, &path.to_string_lossy(), parsed.git_ref.as_deref(), CloneDepth::Shallow, op_id)
+                }
+                LocalSourceKind::Archive(format) => {
+                    let extracted = extract_archive(path, format)?;
+                    Ok((extracted.root, Some(extracted.temp_dir.into())))
+                }
+            }
         }
         SourceType::GitHub | SourceType::GitLab | SourceType::Git => {
-            // 克隆仓库（带进度事件）
-            let app_clone = app.clone();
-            let clone_result = clone_repo_with_progress(
-                &parsed.url,
-                parsed.git_ref.as_deref(),
-                move |progress: CloneProgress| {
-                    // 发送进度事件到前端
-                    let _ = app_clone.emit("clone-progress", &progress);
-                },
-            )?;
-            let repo_path = clone_result.repo_path.clone();
-            (repo_path, Some(clone_result))
+            clone_with_events(app, &parsed.url, parsed.git_ref.as_deref(), CloneDepth::Shallow, op_id)
         }
-        SourceType::DirectUrl | SourceType::WellKnown => {
-            // 这些类型需要特殊处理，暂时返回空列表
-            return Ok(FetchResult {
-                source_type: parsed.source_type.to_string(),
-                source_url: parsed.url.clone(),
-                skill_filter: parsed.skill_filter.clone(),
-                skills: vec![],
-            });
+        SourceType::WellKnown => {
+            // 下载 .well-known/skills.json 中列出的 SKILL.md
+            let download = fetch_well_known_skills(&parsed.url).await?;
+            Ok((download.skills_dir, Some(download.temp_dir.into())))
         }
-    };
+        SourceType::DirectUrl => {
+            let download = fetch_direct_url_skill(&parsed.url, parsed.sha256.as_deref()).await?;
+            Ok((download.skills_dir, Some(download.temp_dir.into())))
+        }
+    }
+}
 
-    // 3. 发现并构建结果（复用纯逻辑函数）
-    discover_and_build_result(&parsed, &skills_dir)
+/// 克隆仓库到临时目录（带进度事件，可通过 cancel_clone 取消）
+pub(crate) fn clone_with_events(
+    app: &AppHandle,
+    url: &str,
+    git_ref: Option<&str>,
+    depth: CloneDepth,
+    op_id: Option<&str>,
+) -> Result<(PathBuf, Option<CheckoutDir>), AppError> {
+    let app_clone = app.clone();
+    let event_op_id = op_id.map(str::to_string);
+    let cancel = begin_clone_operation(op_id);
+    let clone_result = clone_repo_with_progress(
+        url,
+        git_ref,
+        depth,
+        Some(&cancel),
+        move |progress: CloneProgress| {
+            // 发送进度事件到前端
+            let _ = app_clone.emit("clone-progress", &CloneProgressEvent {
+                progress: &progress,
+                op_id: event_op_id.as_deref(),
+            });
+        },
+    );
+    finish_clone_operation(&cancel);
+    let clone_result = clone_result?;
+    Ok((clone_result.repo_path, Some(clone_result.temp_dir)))
 }
 
 /// 从已有的 skills 目录发现 skills 并构建 FetchResult
 ///
 /// 抽取为独立函数，不依赖 AppHandle，便于单元测试
 fn discover_and_build_result(
-    parsed: &crate::models::ParsedSource,
+    parsed: &ParsedSource,
     skills_dir: &std::path::Path,
+    max_depth: Option<usize>,
 ) -> Result<FetchResult, AppError> {
     // 如果有 @skill 语法，包含 internal skills（用户明确请求）
     let include_internal = parsed.skill_filter.is_some();
     let options = DiscoverOptions {
         include_internal,
         full_depth: false,
+        max_depth,
     };
 
-    let discovered = discover_skills(skills_dir, parsed.subpath.as_deref(), options)?;
+    let (discovered, warnings) =
+        discover_skills_in_subpaths_with_warnings(skills_dir, &parsed.search_subpaths(), options)?;
 
-    let skills: Vec<AvailableSkill> = discovered.into_iter().map(|s| s.into()).collect();
+    // 内容 hash 只能在克隆目录存活时计算；失败时为 None（覆盖检测退化为 Modified）
+    let skills: Vec<AvailableSkill> = discovered
+        .into_iter()
+        .map(|s| {
+            let content_hash = compute_installable_hash(&s.path).ok();
+            AvailableSkill {
+                content_hash,
+                ..s.into()
+            }
+        })
+        .collect();
 
     Ok(FetchResult {
         source_type: parsed.source_type.to_string(),
         source_url: parsed.url.clone(),
         skill_filter: parsed.skill_filter.clone(),
         skills,
+        warnings,
     })
 }
 
@@ -115,48 +332,47 @@ fn discover_and_build_result(
 ///
 /// # Arguments
 /// * `params` - 安装参数（来源、选中的 skills、agents、scope、mode）
+/// * `op_id` - 前端指定的操作 id（用于 operation-event、clone-progress 和 cancel_clone），未传时自动生成
 ///
 /// # Returns
 /// * `InstallResults` - 安装结果汇总
 #[tauri::command]
 #[specta::specta]
-pub async fn install_skills(app: AppHandle, params: InstallParams) -> Result<InstallResults, AppError> {
-    install_skills_inner(&app, params).await
+pub async fn install_skills(
+    app: AppHandle,
+    params: InstallParams,
+    op_id: Option<String>,
+) -> Result<InstallResults, AppError> {
+    let op = Operation::start(&app, OperationKind::Install, op_id);
+    let result = install_skills_inner(&app, params, &op).await;
+    op.finish(result, |r| {
+        format!("{} installed, {} failed", r.successful.len(), r.failed.len())
+    })
 }
 
-async fn install_skills_inner(app: &AppHandle, params: InstallParams) -> Result<InstallResults, AppError> {
-    // 1. 解析来源
-    let parsed = parse_source(&params.source)?;
+async fn install_skills_inner(
+    app: &AppHandle,
+    params: InstallParams,
+    op: &Operation,
+) -> Result<InstallResults, AppError> {
+    // 1. 解析来源（用户选择的 ref 优先于来源中的 ref）
+    let mut parsed = parse_source(&params.source)?;
+    if let Some(ref r) = params.git_ref {
+        parsed = parsed.with_ref(r.clone());
+    }
 
-    // 2. 克隆或获取本地路径
-    let (skills_dir, _clone_result) = match parsed.source_type {
-        SourceType::Local => {
-            let path = parsed
-                .local_path
-                .as_ref()
-                .ok_or_else(|| AppError::InvalidSource { value: "Missing local path".to_string() })?;
-            (path.clone(), None)
-        }
-        _ => {
-            let app_clone = app.clone();
-            let clone_result = clone_repo_with_progress(
-                &parsed.url,
-                parsed.git_ref.as_deref(),
-                move |progress: CloneProgress| {
-                    let _ = app_clone.emit("clone-progress", &progress);
-                },
-            )?;
-            let repo_path = clone_result.repo_path.clone();
-            (repo_path, Some(clone_result))
-        }
-    };
+    // 2. 克隆、下载或获取本地路径
+    op.progress("fetching", 0, params.skills.len());
+    let (skills_dir, _temp_dir) = prepare_skills_dir(app, &parsed, Some(op.id())).await?;
 
     // 3. 发现所有 skills
     let options = DiscoverOptions {
         include_internal: true, // 安装时包含 internal（用户已明确选择）
         full_depth: false,
+        max_depth: params.max_depth.map(|d| d as usize),
     };
-    let discovered = discover_skills(&skills_dir, parsed.subpath.as_deref(), options)?;
+    let discovered =
+        discover_skills_in_subpaths(&skills_dir, &parsed.search_subpaths(), options)?;
 
     // 4. 过滤用户选择的 skills
     let selected_skills: Vec<_> = discovered
@@ -168,107 +384,108 @@ async fn install_skills_inner(app: &AppHandle, params: InstallParams) -> Result<
         return Err(AppError::NoSkillsFound);
     }
 
-    // 5. 确保包含 Universal Agents（动态获取）
-    let mut target_agents = params.agents.clone();
-    let universal_agents = AgentType::get_universal_agents();
-
-    for ua in universal_agents {
-        let ua_str = ua.to_string();
-        if !target_agents.contains(&ua_str) {
-            target_agents.push(ua_str);
-        }
-    }
+    // 5. 确定目标 agents（合并强制安装的 agents，默认追加 Universal Agents）
+    let target_agents = resolve_target_agents(&explicit_agents(&params), params.skip_universal);
 
     // 6. 执行安装
-    let mut successful = Vec::new();
-    let mut failed = Vec::new();
-    let mut symlink_fallback_agents = Vec::new();
     let total_skills = selected_skills.len();
-
-    for (idx, skill) in selected_skills.iter().enumerate() {
+    let batch = install_selected(&selected_skills, &target_agents, &params, |idx, skill_name| {
         // 发送安装进度事件
         let _ = app.emit("install-progress", &InstallProgress {
             phase: "installing".to_string(),
-            current_skill: skill.name.clone(),
+            current_skill: skill_name.to_string(),
             completed: idx,
             total: total_skills,
         });
-
-        for agent_str in &target_agents {
-            let agent: AgentType = agent_str
-                .parse()
-                .map_err(|_| AppError::InvalidAgent { agent: agent_str.clone() })?;
-
-            let result = install_skill_for_agent(
-                &skill.path,
-                &skill.name,
-                &agent,
-                &params.scope,
-                params.project_path.as_deref(),
-                &params.mode,
-            );
-
-            if result.success {
-                if result.symlink_failed && !symlink_fallback_agents.contains(agent_str) {
-                    symlink_fallback_agents.push(agent_str.clone());
-                }
-                successful.push(result);
-            } else {
-                failed.push(result);
-            }
-        }
-    }
+        op.progress("installing", idx, total_skills);
+    })?;
+    let InstalledBatch { results, dir_names, locales } = batch;
+    let InstallResults {
+        successful,
+        failed,
+        symlink_fallback_agents,
+        symlink_fallback_reasons,
+    } = results;
 
     // 7. 写入 lock 文件
     if !successful.is_empty() {
-        let _ = app.emit("install-progress", &InstallProgress {
-            phase: "writing_lock".to_string(),
-            current_skill: String::new(),
-            completed: total_skills,
-            total: total_skills,
-        });
-
         let owner_repo = get_owner_repo(&parsed);
+        let installed_skills: Vec<_> = selected_skills
+            .iter()
+            .filter(|skill| successful.iter().any(|r| r.skill_name == skill.name))
+            .collect();
+        let lock_total = installed_skills.len();
 
-        for skill in &selected_skills {
-            let installed = successful.iter().any(|r| r.skill_name == skill.name);
-            if !installed {
-                continue;
+        // 一次性获取全部 skill folder hash（仅支持 GitHub / GitLab 来源），远程目录只请求一次，失败时记为空
+        let host = RemoteHashHost::from_source(&parsed.source_type.to_string(), &parsed.url);
+        let folder_hashes = match (&host, &owner_repo) {
+            (Some(host), Some(repo)) => {
+                let skill_paths: Vec<&str> =
+                    installed_skills.iter().map(|s| s.relative_path.as_str()).collect();
+                host.fetch_folder_hashes(repo, &skill_paths, None).await.unwrap_or_default()
             }
+            _ => Vec::new(),
+        };
 
-            // 获取 skill folder hash（仅 GitHub 来源）
-            let skill_folder_hash = if parsed.source_type == SourceType::GitHub {
-                if let Some(ref repo) = owner_repo {
-                    fetch_skill_folder_hash(repo, &skill.relative_path, None)
-                        .await
-                        .unwrap_or(None)
-                        .unwrap_or_default()
-                } else {
-                    String::new()
-                }
-            } else {
-                String::new()
-            };
+        for (idx, skill) in installed_skills.into_iter().enumerate() {
+            let _ = app.emit("install-progress", &InstallProgress {
+                phase: "writing_lock".to_string(),
+                current_skill: skill.name.clone(),
+                completed: idx,
+                total: lock_total,
+            });
+            op.progress("writing_lock", idx, lock_total);
+
+            let skill_folder_hash = folder_hashes.get(idx).cloned().flatten().unwrap_or_default();
 
             let source = owner_repo.as_deref().unwrap_or(&params.source);
             let source_type_str = &parsed.source_type.to_string();
             let source_url = &parsed.url;
-            let skill_path = Some(skill.relative_path.as_str());
+            // HTTP 来源的 skill 路径是临时目录内的路径，没有意义
+            // 不记录 skill_path，更新时直接用 source_url 重新下载
+            let skill_path = match parsed.source_type {
+                SourceType::WellKnown | SourceType::DirectUrl => None,
+                _ => Some(skill.relative_path.as_str()),
+            };
+            let dir_name = &dir_names[&skill.name];
+            // 只记录与默认目录名不同的目录名
+            let recorded_dir_name =
+                (*dir_name != sanitize_name(&skill.name)).then(|| dir_name.clone());
 
             // 根据 scope 写入对应的 lock 文件
             match params.scope {
                 crate::models::Scope::Global => {
-                    let _ = add_skill_to_lock(
-                        &skill.name, source, source_type_str, source_url,
-                        skill_path, &skill_folder_hash,
-                        skill.plugin_name.as_deref(),
-                    );
+                    let entry = SkillLockEntry {
+                        source: source.to_string(),
+                        source_type: source_type_str.to_string(),
+                        source_url: source_url.to_string(),
+                        skill_path: skill_path.map(|s| s.to_string()),
+                        skill_folder_hash: skill_folder_hash.clone(),
+                        installed_at: String::new(),
+                        updated_at: String::new(),
+                        plugin_name: skill.plugin_name.clone(),
+                        version: skill.version.clone(),
+                        dir_name: recorded_dir_name,
+                        locale: locales.get(&skill.name).cloned(),
+                        detached_agents: Vec::new(),
+                    };
+                    let _ = add_skill_to_lock(&skill.name, entry);
                 }
                 crate::models::Scope::Project => {
                     if let Some(ref project_path) = params.project_path {
                         // 计算安装后的本地文件 SHA-256
-                        let install_dir = crate::core::paths::canonical_skills_dir(false, project_path)
-                            .join(crate::core::skill::sanitize_name(&skill.name));
+                        // Copy 模式 + skip_universal 时没有 canonical 目录，改用实际安装位置
+                        let canonical_dir = crate::core::paths::canonical_skills_dir(false, project_path)
+                            .join(dir_name);
+                        let install_dir = if canonical_dir.exists() {
+                            canonical_dir
+                        } else {
+                            successful
+                                .iter()
+                                .find(|r| r.skill_name == skill.name)
+                                .map(|r| r.path.clone())
+                                .unwrap_or(canonical_dir)
+                        };
                         let computed_hash = compute_skill_folder_hash(&install_dir)
                             .unwrap_or_default();
 
@@ -283,6 +500,13 @@ async fn install_skills_inner(app: &AppHandle, params: InstallParams) -> Result<
                             },
                             skill_path: skill_path.map(|s| s.to_string()),
                             plugin_name: skill.plugin_name.clone(),
+                            version: skill.version.clone(),
+                            installed_at: None,
+                            updated_at: None,
+                            dir_name: recorded_dir_name,
+                            locale: locales.get(&skill.name).cloned(),
+                            detached_agents: Vec::new(),
+                            source_url: recorded_source_url(source, source_type_str, source_url),
                         };
                         let _ = add_skill_to_local_lock(&skill.name, entry, project_path);
                     }
@@ -291,19 +515,251 @@ async fn install_skills_inner(app: &AppHandle, params: InstallParams) -> Result<
         }
     }
 
-    // 8. 保存选择的 agents
-    let _ = save_selected_agents(&target_agents);
+    // 8. 保存选择的 agents（project scope 保存为该项目的默认 agents）
+    let _ = match (&params.scope, &params.project_path) {
+        (crate::models::Scope::Project, Some(project_path)) => {
+            save_last_selected_agents(target_agents.clone(), Some(project_path.clone()))
+        }
+        _ => save_selected_agents(&target_agents),
+    };
+
+    // 9. 记录来源历史（按成功安装的 skill 数）
+    if !successful.is_empty() {
+        let mut installed: Vec<&str> = successful.iter().map(|r| r.skill_name.as_str()).collect();
+        installed.sort_unstable();
+        installed.dedup();
+        if let Err(e) = record_source_usage(&params.source, installed.len()) {
+            log::warn!("Failed to record source history: {}", e);
+        }
+    }
 
     Ok(InstallResults {
         successful,
         failed,
         symlink_fallback_agents,
+        symlink_fallback_reasons,
     })
 }
 
+/// 一批 skill 的安装结果
+struct InstalledBatch {
+    results: InstallResults,
+    /// skill name → 安装目录名（目录被其他 skill 占用时可能带数字后缀）
+    dir_names: HashMap<String, String>,
+    /// skill name → 实际安装的本地化版本
+    locales: HashMap<String, String>,
+}
+
+/// 将选中的 skills 安装到目标 agents（不写 lock）
+///
+/// 目录名按顺序分配，同一批次中 sanitize 后同名的 skills 不会互相覆盖。
+/// `on_skill` 在每个 skill 开始安装前以 (序号, skill name) 调用。
+fn install_selected(
+    selected_skills: &[DiscoveredSkill],
+    target_agents: &[String],
+    params: &InstallParams,
+    mut on_skill: impl FnMut(usize, &str),
+) -> Result<InstalledBatch, AppError> {
+    let mut successful = Vec::new();
+    let mut failed = Vec::new();
+    let mut symlink_fallback_agents = Vec::new();
+    let mut symlink_fallback_reasons = Vec::new();
+    let is_global = matches!(params.scope, crate::models::Scope::Global);
+    let cwd = params.project_path.as_deref().unwrap_or(".");
+    let target_agent_types: Vec<AgentType> =
+        target_agents.iter().filter_map(|a| a.parse().ok()).collect();
+    let policy = params.name_collision.unwrap_or_default();
+    let mut claimed = HashSet::new();
+    let mut dir_names = HashMap::new();
+    for skill in selected_skills {
+        if dir_names.contains_key(&skill.name) {
+            continue;
+        }
+        let dir_name = resolve_install_dir_name(
+            &skill.name,
+            &target_agent_types,
+            is_global,
+            cwd,
+            policy,
+            &claimed,
+        );
+        claimed.insert(dir_name.clone());
+        dir_names.insert(skill.name.clone(), dir_name);
+    }
+
+    let mut locales: HashMap<String, String> = HashMap::new();
+    for (idx, skill) in selected_skills.iter().enumerate() {
+        on_skill(idx, &skill.name);
+
+        // 选择了本地化版本时从暂存目录安装（其中的 SKILL.md 已替换为对应 locale）
+        let localized = match params.locale.as_deref() {
+            Some(locale) => stage_localized(&skill.path, locale)?,
+            None => None,
+        };
+        let source_path = localized.as_ref().map_or(skill.path.as_path(), |(_, staging)| staging.path());
+        if let Some((locale, _)) = &localized {
+            locales.insert(skill.name.clone(), locale.clone());
+        }
+
+        // Hardlink：canonical 目录每个 skill 只写入一次，各 agent 都链接到同一份文件
+        if params.mode == InstallMode::Hardlink {
+            write_canonical_dir(
+                source_path,
+                &dir_names[&skill.name],
+                &params.scope,
+                params.project_path.as_deref(),
+                false,
+            )?;
+        }
+
+        for agent_str in target_agents {
+            let agent: AgentType = agent_str
+                .parse()
+                .map_err(|_| AppError::InvalidAgent { agent: agent_str.clone() })?;
+
+            let result = install_skill_for_agent_in_dir(
+                source_path,
+                &skill.name,
+                &dir_names[&skill.name],
+                &agent,
+                &params.scope,
+                params.project_path.as_deref(),
+                &params.mode,
+            );
+
+            if result.success {
+                if result.symlink_failed && !symlink_fallback_agents.contains(agent_str) {
+                    symlink_fallback_agents.push(agent_str.clone());
+                    if let Some(reason) = &result.symlink_error {
+                        symlink_fallback_reasons.push((agent_str.clone(), reason.clone()));
+                    }
+                }
+                successful.push(result);
+            } else {
+                failed.push(result);
+            }
+        }
+    }
+
+    Ok(InstalledBatch {
+        results: InstallResults {
+            successful,
+            failed,
+            symlink_fallback_agents,
+            symlink_fallback_reasons,
+        },
+        dir_names,
+        locales,
+    })
+}
+
+/// 预览安装计划
+///
+/// 解析来源并确定最终的目标 agents（含 Universal Agents 自动追加和 skip_universal 逻辑），
+/// 不会克隆仓库或下载文件。远程来源在克隆前无法知道 skill 数量，此时只返回来源、
+/// agent 和 scope 信息（`skills` 为 None）；本地来源会做一次快速发现。
+///
+/// # Arguments
+/// * `params` - 与 install_skills 相同的安装参数
+#[tauri::command]
+#[specta::specta]
+pub async fn plan_install(params: InstallParams) -> Result<InstallPlan, AppError> {
+    // 1. 解析来源（用户选择的 ref 优先于来源中的 ref）
+    let mut parsed = parse_source(&params.source)?;
+    if let Some(ref r) = params.git_ref {
+        parsed = parsed.with_ref(r.clone());
+    }
+
+    // 2. 确定目标 agents
+    let explicit = explicit_agents(&params);
+    let agents = resolve_target_agents(&explicit, params.skip_universal);
+    for agent_str in &agents {
+        agent_str
+            .parse::<AgentType>()
+            .map_err(|_| AppError::InvalidAgent { agent: agent_str.clone() })?;
+    }
+    let universal_agents_added = agents[explicit.len()..].to_vec();
+
+    // 3. 本地目录来源快速发现选中的 skills（bundle / 压缩包需要克隆或解压，不在预览中处理）
+    let skills = match (&parsed.source_type, parsed.local_path.as_ref()) {
+        (SourceType::Local, Some(path)) if local_source_kind(path) == LocalSourceKind::Directory => {
+            let options = DiscoverOptions {
+                include_internal: true,
+                full_depth: false,
+                max_depth: params.max_depth.map(|d| d as usize),
+            };
+            let discovered =
+                discover_skills_in_subpaths(path, &parsed.search_subpaths(), options)?;
+            Some(
+                discovered
+                    .into_iter()
+                    .map(|s| s.name)
+                    .filter(|name| params.skills.contains(name))
+                    .collect(),
+            )
+        }
+        _ => None,
+    };
+
+    Ok(InstallPlan {
+        source_type: parsed.source_type.to_string(),
+        requires_clone: match (&parsed.source_type, parsed.local_path.as_ref()) {
+            (SourceType::Local, Some(path)) => local_source_kind(path) == LocalSourceKind::GitBundle,
+            (source_type, _) => matches!(
+                source_type,
+                SourceType::GitHub | SourceType::GitLab | SourceType::Git
+            ),
+        },
+        source_url: parsed.url,
+        git_ref: parsed.git_ref,
+        subpath: parsed.subpath,
+        subpaths: parsed.subpaths,
+        scope: params.scope,
+        mode: params.mode,
+        agents,
+        universal_agents_added,
+        skills,
+    })
+}
+
+/// 用户显式指定的 agents：选中的 agents 加上 `force_agents`（去重，保持顺序）
+///
+/// 安装本身不检查 agent 是否已检测到，force_agents 只是不经过向导的检测过滤
+fn explicit_agents(params: &InstallParams) -> Vec<String> {
+    let mut agents = params.agents.clone();
+    for agent in &params.force_agents {
+        let id = agent.to_string();
+        if !agents.contains(&id) {
+            agents.push(id);
+        }
+    }
+    agents
+}
+
+/// 确定实际安装的目标 agents
+///
+/// 默认在用户选择的基础上追加所有 Universal Agents（动态获取，去重）；
+/// `skip_universal` 为 true 时只保留用户显式选择的 agents。
+fn resolve_target_agents(selected: &[String], skip_universal: bool) -> Vec<String> {
+    let mut target_agents = selected.to_vec();
+    if skip_universal {
+        return target_agents;
+    }
+
+    for ua in AgentType::get_universal_agents() {
+        let ua_str = ua.to_string();
+        if !target_agents.contains(&ua_str) {
+            target_agents.push(ua_str);
+        }
+    }
+
+    target_agents
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::core::install_skill_for_agent;
     use std::fs;
     use tempfile::tempdir;
 
@@ -322,11 +778,16 @@ mod tests {
 
         let source = temp.path().to_string_lossy().to_string();
         let parsed = parse_source(&source).unwrap();
-        let result = discover_and_build_result(&parsed, temp.path()).unwrap();
+        let result = discover_and_build_result(&parsed, temp.path(), None).unwrap();
 
         assert_eq!(result.source_type, "local");
         assert_eq!(result.skills.len(), 1);
         assert_eq!(result.skills[0].name, "test-skill");
+        // 内容 hash 与安装后目录的 hash 一致，供 check_overwrites 直接比对
+        assert_eq!(
+            result.skills[0].content_hash,
+            Some(compute_skill_folder_hash(&skill_dir).unwrap())
+        );
     }
 
     #[test]
@@ -354,8 +815,271 @@ mod tests {
         // 不带 @skill 语法，不应包含 internal
         let source = temp.path().to_string_lossy().to_string();
         let parsed = parse_source(&source).unwrap();
-        let result = discover_and_build_result(&parsed, temp.path()).unwrap();
+        let result = discover_and_build_result(&parsed, temp.path(), None).unwrap();
         assert_eq!(result.skills.len(), 1);
         assert_eq!(result.skills[0].name, "normal");
     }
+
+    #[test]
+    fn test_fetch_available_with_name_glob() {
+        let temp = tempdir().unwrap();
+        for name in ["python-lint", "python-test", "rust-fmt", "pdf"] {
+            let skill_dir = temp.path().join("skills").join(name);
+            fs::create_dir_all(&skill_dir).unwrap();
+            fs::write(
+                skill_dir.join("SKILL.md"),
+                format!("---\nname: {}\ndescription: {}\n---\n", name, name),
+            )
+            .unwrap();
+        }
+
+        let source = temp.path().to_string_lossy().to_string();
+        let parsed = parse_source(&source).unwrap();
+        let names = |result: &FetchResult| {
+            let mut names: Vec<String> = result.skills.iter().map(|s| s.name.clone()).collect();
+            names.sort();
+            names
+        };
+
+        let mut result = discover_and_build_result(&parsed, temp.path(), None).unwrap();
+        filter_by_name_glob(&mut result, Some("python-*")).unwrap();
+        assert_eq!(names(&result), vec!["python-lint", "python-test"]);
+
+        // 空 glob 不过滤，无匹配返回空列表
+        let mut result = discover_and_build_result(&parsed, temp.path(), None).unwrap();
+        filter_by_name_glob(&mut result, Some(" ")).unwrap();
+        assert_eq!(result.skills.len(), 4);
+        filter_by_name_glob(&mut result, Some("go-*")).unwrap();
+        assert!(result.skills.is_empty());
+
+        // 与 @skill 同时使用：skill_filter 保留，skills 为 glob 过滤后的结果
+        let parsed = parse_source(&source)
+            .unwrap()
+            .with_skill_filter("python-lint".to_string());
+        let mut result = discover_and_build_result(&parsed, temp.path(), None).unwrap();
+        filter_by_name_glob(&mut result, Some("*-test")).unwrap();
+        assert_eq!(result.skill_filter.as_deref(), Some("python-lint"));
+        assert_eq!(names(&result), vec!["python-test"]);
+    }
+
+    #[test]
+    fn test_resolve_target_agents() {
+        let selected = vec!["claude-code".to_string()];
+        let universal: Vec<String> = AgentType::get_universal_agents()
+            .iter()
+            .map(|a| a.to_string())
+            .collect();
+
+        // 默认：追加 Universal Agents，保留用户选择在前
+        let agents = resolve_target_agents(&selected, false);
+        assert_eq!(agents[0], "claude-code");
+        assert!(universal.iter().all(|ua| agents.contains(ua)));
+
+        // 已选中的 Universal Agent 不重复
+        let with_universal = vec![universal[0].clone()];
+        let agents = resolve_target_agents(&with_universal, false);
+        assert_eq!(agents.iter().filter(|a| **a == universal[0]).count(), 1);
+
+        // skip_universal：只保留显式选择
+        assert_eq!(resolve_target_agents(&selected, true), selected);
+    }
+
+    #[test]
+    fn test_skip_universal_symlink_still_writes_canonical() {
+        let src = tempdir().unwrap();
+        fs::write(src.path().join("SKILL.md"), "---\nname: demo\ndescription: Demo\n---\n").unwrap();
+        let project = tempdir().unwrap();
+        let project_path = project.path().to_string_lossy().to_string();
+        let canonical = project.path().join(".agents/skills/demo");
+
+        // Symlink 模式：即使只选非 Universal agent，canonical 目录仍作为 symlink 目标写入
+        for agent_str in resolve_target_agents(&["claude-code".to_string()], true) {
+            let agent: AgentType = agent_str.parse().unwrap();
+            let result = install_skill_for_agent(
+                src.path(),
+                "demo",
+                &agent,
+                &crate::models::Scope::Project,
+                Some(&project_path),
+                &crate::models::InstallMode::Symlink,
+            );
+            assert!(result.success);
+            assert_eq!(result.canonical_path.as_deref(), Some(canonical.as_path()));
+        }
+        assert!(canonical.join("SKILL.md").exists());
+        assert!(project.path().join(".claude/skills/demo/SKILL.md").exists());
+
+        // Copy 模式：不写 canonical 目录
+        let project = tempdir().unwrap();
+        let project_path = project.path().to_string_lossy().to_string();
+        let result = install_skill_for_agent(
+            src.path(),
+            "demo",
+            &AgentType::ClaudeCode,
+            &crate::models::Scope::Project,
+            Some(&project_path),
+            &crate::models::InstallMode::Copy,
+        );
+        assert!(result.success);
+        assert!(result.canonical_path.is_none());
+        assert!(!project.path().join(".agents").exists());
+    }
+
+    fn plan_params(source: &str, skills: &[&str], skip_universal: bool) -> InstallParams {
+        InstallParams {
+            source: source.to_string(),
+            skills: skills.iter().map(|s| s.to_string()).collect(),
+            agents: vec!["claude-code".to_string()],
+            scope: crate::models::Scope::Project,
+            project_path: Some("/work/project".to_string()),
+            mode: crate::models::InstallMode::Symlink,
+            skip_universal,
+            max_depth: None,
+            git_ref: None,
+            name_collision: None,
+            force_agents: Vec::new(),
+            locale: None,
+        }
+    }
+
+    #[test]
+    fn test_install_selected_colliding_names_in_one_batch() {
+        let src = tempdir().unwrap();
+        for (dir, name) in [("a", "My Skill!"), ("b", "my skill")] {
+            let skill_dir = src.path().join(dir);
+            fs::create_dir_all(&skill_dir).unwrap();
+            fs::write(
+                skill_dir.join("SKILL.md"),
+                format!("---\nname: {}\ndescription: Test\n---\n", name),
+            )
+            .unwrap();
+        }
+        let selected = crate::core::discover_skills(src.path(), None, DiscoverOptions::default()).unwrap();
+        assert_eq!(selected.len(), 2);
+
+        let project = tempdir().unwrap();
+        let mut params = plan_params("unused", &["My Skill!", "my skill"], true);
+        params.project_path = Some(project.path().to_string_lossy().to_string());
+        // 默认 Overwrite 策略下同批次的 skill 也不能互相覆盖
+        let mut progress = Vec::new();
+        let batch = install_selected(&selected, &["claude-code".to_string()], &params, |idx, name| {
+            progress.push((idx, name.to_string()))
+        })
+        .unwrap();
+
+        assert_eq!(progress.len(), 2);
+        assert_eq!(batch.results.successful.len(), 2);
+        assert!(batch.results.failed.is_empty());
+        let mut dir_names: Vec<&String> = batch.dir_names.values().collect();
+        dir_names.sort();
+        assert_eq!(dir_names, vec!["my-skill", "my-skill-2"]);
+        for skill in &selected {
+            let md = fs::read_to_string(
+                project
+                    .path()
+                    .join(".claude/skills")
+                    .join(&batch.dir_names[&skill.name])
+                    .join("SKILL.md"),
+            )
+            .unwrap();
+            assert!(md.contains(&format!("name: {}", skill.name)));
+        }
+    }
+
+    #[test]
+    fn test_plan_install_remote_does_not_clone() {
+        let plan = tauri::async_runtime::block_on(plan_install(plan_params(
+            "https://github.com/owner/repo/tree/dev/skills",
+            &["demo"],
+            false,
+        )))
+        .unwrap();
+
+        assert_eq!(plan.source_type, "github");
+        assert_eq!(plan.source_url, "https://github.com/owner/repo");
+        assert_eq!(plan.git_ref.as_deref(), Some("dev"));
+        assert_eq!(plan.subpath.as_deref(), Some("skills"));
+        assert!(plan.requires_clone);
+        assert!(plan.skills.is_none());
+        assert_eq!(plan.agents[0], "claude-code");
+        assert_eq!(plan.universal_agents_added, plan.agents[1..].to_vec());
+        assert!(!plan.universal_agents_added.is_empty());
+    }
+
+    #[test]
+    fn test_plan_install_local_discovers_selected_skills() {
+        let temp = tempdir().unwrap();
+        for name in ["alpha", "beta"] {
+            let skill_dir = temp.path().join(name);
+            fs::create_dir_all(&skill_dir).unwrap();
+            fs::write(
+                skill_dir.join("SKILL.md"),
+                format!("---\nname: {}\ndescription: Test\n---\n", name),
+            )
+            .unwrap();
+        }
+
+        let source = temp.path().to_string_lossy().to_string();
+        let plan =
+            tauri::async_runtime::block_on(plan_install(plan_params(&source, &["beta"], true)))
+                .unwrap();
+
+        assert!(!plan.requires_clone);
+        assert_eq!(plan.skills, Some(vec!["beta".to_string()]));
+        assert_eq!(plan.agents, vec!["claude-code".to_string()]);
+        assert!(plan.universal_agents_added.is_empty());
+    }
+
+    #[test]
+    fn test_plan_install_force_agents() {
+        let mut params = plan_params("https://github.com/owner/repo", &["demo"], false);
+        params.force_agents = vec![AgentType::Cursor, AgentType::ClaudeCode];
+        let plan = tauri::async_runtime::block_on(plan_install(params)).unwrap();
+
+        // 强制安装的 agent 排在显式选择之后、Universal Agents 之前，已选中的不重复
+        assert_eq!(plan.agents[..2], ["claude-code".to_string(), "cursor".to_string()]);
+        assert_eq!(plan.universal_agents_added, plan.agents[2..].to_vec());
+    }
+
+    #[test]
+    fn test_attach_audit() {
+        use crate::core::audit::RiskLevel;
+
+        let temp = tempdir().unwrap();
+        for name in ["alpha", "beta"] {
+            let skill_dir = temp.path().join(name);
+            fs::create_dir_all(&skill_dir).unwrap();
+            fs::write(
+                skill_dir.join("SKILL.md"),
+                format!("---\nname: {}\ndescription: Test\n---\n", name),
+            )
+            .unwrap();
+        }
+
+        let source = temp.path().to_string_lossy().to_string();
+        let parsed = parse_source(&source).unwrap();
+        let mut result = discover_and_build_result(&parsed, temp.path(), None).unwrap();
+
+        // 审计失败：所有 audit 保持 None
+        attach_audit(&mut result, None);
+        assert!(result.skills.iter().all(|s| s.audit.is_none()));
+
+        // 仅 alpha 有审计数据
+        let mut audit = HashMap::new();
+        audit.insert(
+            "alpha".to_string(),
+            SkillAuditData {
+                risk: RiskLevel::Low,
+                alerts: Some(1),
+                score: Some(0.9),
+                analyzed_at: "2024-01-01T00:00:00Z".to_string(),
+            },
+        );
+        attach_audit(&mut result, Some(audit));
+
+        let alpha = result.skills.iter().find(|s| s.name == "alpha").unwrap();
+        let beta = result.skills.iter().find(|s| s.name == "beta").unwrap();
+        assert!(matches!(alpha.audit.as_ref().unwrap().risk, RiskLevel::Low));
+        assert!(beta.audit.is_none());
+    }
 }
@@ -0,0 +1,86 @@
+//! Lock 文件相关命令
+//!
+//! 提供命令：
+//! - migrate_project_lock: 将旧版 `.agents/.skill-lock.json` 迁移为 `skills-lock.json`
+//! - check_lock_integrity: 检查 lock 文件能否解析，损坏时备份并从 canonical 目录重建
+//!
+//! read_local_lock 会静默回退读取旧版文件，但不会写出新格式；
+//! 此命令让用户显式完成迁移，并可选择删除旧版文件。
+
+use crate::core::local_lock::migrate_legacy_lock;
+use crate::core::lock_integrity;
+use crate::error::AppError;
+use crate::models::Scope;
+use serde::Serialize;
+use specta::Type;
+
+/// 迁移结果
+#[derive(Debug, Clone, Serialize, Type)]
+#[serde(rename_all = "camelCase")]
+#[specta(rename_all = "camelCase")]
+pub struct LockMigrationResult {
+    /// 写入 skills-lock.json 的条目数（已存在的条目不计入）
+    pub migrated: u32,
+    /// 是否已删除旧版 lock 文件
+    pub legacy_deleted: bool,
+}
+
+/// 将项目的旧版 lock 文件迁移为 skills-lock.json
+///
+/// # Arguments
+/// * `project_path` - 项目路径
+/// * `delete_legacy` - 迁移成功后是否删除旧版文件（默认 false，保留以便回退）
+#[tauri::command]
+#[specta::specta]
+pub fn migrate_project_lock(
+    project_path: String,
+    delete_legacy: Option<bool>,
+) -> Result<LockMigrationResult, AppError> {
+    let result = migrate_legacy_lock(&project_path, delete_legacy.unwrap_or(false))?;
+    Ok(LockMigrationResult {
+        migrated: result.migrated as u32,
+        legacy_deleted: result.legacy_deleted,
+    })
+}
+
+/// lock 完整性检查结果
+#[derive(Debug, Clone, Serialize, Type)]
+#[serde(rename_all = "camelCase")]
+#[specta(rename_all = "camelCase")]
+pub struct LockIntegrityReport {
+    /// 检查的 lock 文件路径
+    pub lock_path: String,
+    /// lock 能否解析（文件不存在也视为正常）
+    pub valid: bool,
+    /// 解析错误信息
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub parse_error: Option<String>,
+    /// 损坏文件的备份路径（`<lock>.bak`）
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub backup_path: Option<String>,
+    /// 重建 lock 时从 canonical 目录恢复的 skill（来源标记为 local）
+    pub recovered: Vec<String>,
+}
+
+/// 检查 lock 文件完整性，损坏时自动修复
+///
+/// 解析失败时先备份为 `<lock>.bak`，再根据 canonical 目录中的 SKILL.md 重建 lock。
+///
+/// # Arguments
+/// * `scope` - 范围（Global/Project）
+/// * `project_path` - Project scope 时的项目路径
+#[tauri::command]
+#[specta::specta]
+pub fn check_lock_integrity(
+    scope: Scope,
+    project_path: Option<String>,
+) -> Result<LockIntegrityReport, AppError> {
+    let result = lock_integrity::check_lock_integrity(&scope, project_path.as_deref())?;
+    Ok(LockIntegrityReport {
+        lock_path: result.lock_path.to_string_lossy().to_string(),
+        valid: result.parse_error.is_none(),
+        parse_error: result.parse_error,
+        backup_path: result.backup_path.map(|p| p.to_string_lossy().to_string()),
+        recovered: result.recovered,
+    })
+}
@@ -1,12 +1,30 @@
 // src-tauri/src/commands/mod.rs
 pub mod agents;
 pub mod audit;
+pub mod changelog;
+pub mod compare;
 pub mod config;
+pub mod dependencies;
+pub mod detach;
+pub mod diff;
+pub mod disk_usage;
+pub mod doctor;
+pub mod git;
+pub mod github;
 pub mod install;
+pub mod lock;
+pub mod move_scope;
+pub mod operation;
 pub mod overwrites;
+pub mod refs;
 pub mod remove;
 pub mod remove_details;
+pub mod rename;
+pub mod search;
 pub mod skills;
+pub mod state;
 pub mod update;
+pub mod validate;
+pub mod verify;
 pub mod wizard;
 
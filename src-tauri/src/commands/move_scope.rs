@@ -0,0 +1,44 @@
+//! Scope 迁移相关的 Tauri Command
+//!
+//! 提供命令：
+//! - move_skill_scope: 在 global 与 project scope 之间移动已安装的 skill
+//!
+//! GUI 增强：CLI 需要卸载后从来源重新安装，这里直接迁移本地文件和 lock 条目
+
+use crate::commands::operation::{Operation, OperationKind};
+use crate::core::scope_mover;
+use crate::error::AppError;
+use crate::models::{MoveScopeResult, Scope};
+use tauri::AppHandle;
+
+/// 在 global 与 project scope 之间移动已安装的 skill
+///
+/// 复制 canonical 目录到目标 scope，按原安装形态重新创建 agent 目录，
+/// 在 global lock 与项目 skills-lock.json 之间迁移条目，最后删除原 scope 的安装。
+/// 目标 scope 已存在同名 skill 时返回错误，不做任何修改。
+///
+/// # Arguments
+/// * `name` - skill 名称
+/// * `from_scope` - 当前 scope
+/// * `to_scope` - 目标 scope
+/// * `project_path` - 项目路径（两个方向都需要）
+/// * `op_id` - 前端指定的操作 id（用于 operation-event），未传时自动生成
+#[tauri::command]
+#[specta::specta]
+pub async fn move_skill_scope(
+    app: AppHandle,
+    name: String,
+    from_scope: Scope,
+    to_scope: Scope,
+    project_path: Option<String>,
+    op_id: Option<String>,
+) -> Result<MoveScopeResult, AppError> {
+    let op = Operation::start(&app, OperationKind::MoveScope, op_id);
+    let result = match project_path {
+        Some(project_path) => scope_mover::move_skill_scope(&name, &from_scope, &to_scope, &project_path),
+        None => Err(AppError::InvalidSource {
+            value: "Project path is required to move a skill between scopes".to_string(),
+        }),
+    };
+    op.finish(result, |_| format!("Moved {}", name))
+}
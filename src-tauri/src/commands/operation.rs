@@ -0,0 +1,157 @@
+//! 长时间操作的统一事件流
+//!
+//! 耗时命令（获取、安装、更新等）在 `operation-event` 上依次发送
+//! Started → Progress* → Completed / Failed，同一次操作共用一个 `opId`，
+//! 前端据此把进度和取消结果对应到具体操作。
+//! 命令可接受前端传入的 `op_id`（未传时自动生成），`clone-progress` 事件和
+//! `cancel_clone` 使用同一个 id。
+//! 原有的 `install-progress`、`update-all-progress` 事件保持不变。
+
+use crate::error::AppError;
+use serde::Serialize;
+use specta::Type;
+use std::sync::atomic::{AtomicU64, Ordering};
+use tauri::{AppHandle, Emitter};
+
+/// 事件名
+pub const OPERATION_EVENT: &str = "operation-event";
+
+/// 进程内递增的操作序号
+static NEXT_OPERATION_ID: AtomicU64 = AtomicU64::new(1);
+
+/// 操作类型
+#[derive(Debug, Clone, Copy, Serialize, Type)]
+#[serde(rename_all = "camelCase")]
+#[specta(rename_all = "camelCase")]
+pub enum OperationKind {
+    Fetch,
+    Install,
+    Update,
+    UpdateAll,
+    Reinstall,
+    ResolveDependencies,
+    Changelog,
+    MoveScope,
+    ExportState,
+    ImportState,
+    Search,
+    Lint,
+}
+
+/// 操作生命周期事件（发送到前端）
+#[derive(Debug, Serialize, Type)]
+#[serde(tag = "type", rename_all = "camelCase")]
+#[specta(tag = "type", rename_all = "camelCase")]
+pub enum OperationEvent<'a> {
+    Started {
+        #[serde(rename = "opId")]
+        op_id: &'a str,
+        kind: OperationKind,
+    },
+    Progress {
+        #[serde(rename = "opId")]
+        op_id: &'a str,
+        phase: &'a str,
+        completed: u32,
+        total: u32,
+    },
+    Completed {
+        #[serde(rename = "opId")]
+        op_id: &'a str,
+        summary: String,
+    },
+    Failed {
+        #[serde(rename = "opId")]
+        op_id: &'a str,
+        error: &'a AppError,
+    },
+}
+
+/// 一次进行中的操作，创建时发送 Started
+pub(crate) struct Operation {
+    app: AppHandle,
+    id: String,
+}
+
+impl Operation {
+    /// 开始操作：使用前端传入的 `op_id`，未传（或为空）时自动生成
+    pub(crate) fn start(app: &AppHandle, kind: OperationKind, op_id: Option<String>) -> Self {
+        let id = op_id
+            .filter(|id| !id.is_empty())
+            .unwrap_or_else(|| format!("op-{}", NEXT_OPERATION_ID.fetch_add(1, Ordering::Relaxed)));
+        let op = Self { app: app.clone(), id };
+        op.emit(OperationEvent::Started { op_id: &op.id, kind });
+        op
+    }
+
+    /// 操作 id（与 `clone-progress` 事件、`cancel_clone` 使用的 id 相同）
+    pub(crate) fn id(&self) -> &str {
+        &self.id
+    }
+
+    /// 发送进度
+    pub(crate) fn progress(&self, phase: &str, completed: usize, total: usize) {
+        self.emit(OperationEvent::Progress {
+            op_id: &self.id,
+            phase,
+            completed: completed as u32,
+            total: total as u32,
+        });
+    }
+
+    /// 按结果发送 Completed（`summary` 生成摘要）或 Failed，原样返回结果
+    pub(crate) fn finish<T>(
+        self,
+        result: Result<T, AppError>,
+        summary: impl FnOnce(&T) -> String,
+    ) -> Result<T, AppError> {
+        match &result {
+            Ok(value) => self.emit(OperationEvent::Completed {
+                op_id: &self.id,
+                summary: summary(value),
+            }),
+            Err(error) => self.emit(OperationEvent::Failed { op_id: &self.id, error }),
+        }
+        result
+    }
+
+    fn emit(&self, event: OperationEvent) {
+        let _ = self.app.emit(OPERATION_EVENT, &event);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_operation_event_serialization() {
+        let started = OperationEvent::Started {
+            op_id: "op-1",
+            kind: OperationKind::UpdateAll,
+        };
+        assert_eq!(
+            serde_json::to_value(&started).unwrap(),
+            serde_json::json!({ "type": "started", "opId": "op-1", "kind": "updateAll" })
+        );
+
+        let progress = OperationEvent::Progress {
+            op_id: "op-1",
+            phase: "installing",
+            completed: 1,
+            total: 3,
+        };
+        assert_eq!(
+            serde_json::to_value(&progress).unwrap(),
+            serde_json::json!({
+                "type": "progress", "opId": "op-1", "phase": "installing", "completed": 1, "total": 3
+            })
+        );
+
+        let error = AppError::Cancelled;
+        let failed = OperationEvent::Failed { op_id: "op-1", error: &error };
+        let value = serde_json::to_value(&failed).unwrap();
+        assert_eq!(value["type"], "failed");
+        assert_eq!(value["error"]["kind"], "cancelled");
+    }
+}
@@ -1,21 +1,28 @@
 //! 覆盖检测命令
 
 use crate::core::agents::AgentType;
-use crate::core::installer::is_skill_installed;
+use crate::core::installer::agent_skill_dir;
+use crate::core::local_lock::compute_skill_folder_hash;
 use crate::error::AppError;
-use crate::models::Scope;
+use crate::models::{OverwriteKind, Scope};
 use std::collections::HashMap;
+use std::path::Path;
 
 /// 检测哪些 skill × agent 组合会被覆盖
 ///
+/// 提供 content_hashes 时与已安装目录比对 hash，内容一致的标记为 Identical（无需提示）；
+/// 否则一律为 Modified。hash 来自 fetch_available 返回的 `AvailableSkill.contentHash`，
+/// 不会重新克隆来源。
+///
 /// # Arguments
 /// * `skills` - 要安装的 skill 名称列表
 /// * `agents` - 目标 agent 列表
 /// * `scope` - 安装范围
 /// * `project_path` - Project scope 时的项目路径
+/// * `content_hashes` - 可选的 { skill_name: 即将安装内容的 hash }
 ///
 /// # Returns
-/// * `HashMap<String, Vec<String>>` - { skill_name: [agent_ids that will be overwritten] }
+/// * `HashMap<String, Vec<(String, OverwriteKind)>>` - { skill_name: [(agent_id, kind)] }
 #[tauri::command]
 #[specta::specta]
 pub async fn check_overwrites(
@@ -23,8 +30,11 @@ pub async fn check_overwrites(
     agents: Vec<String>,
     scope: Scope,
     project_path: Option<String>,
-) -> Result<HashMap<String, Vec<String>>, AppError> {
-    let mut overwrites: HashMap<String, Vec<String>> = HashMap::new();
+    content_hashes: Option<HashMap<String, String>>,
+) -> Result<HashMap<String, Vec<(String, OverwriteKind)>>, AppError> {
+    let incoming_hashes = content_hashes.unwrap_or_default();
+
+    let mut overwrites: HashMap<String, Vec<(String, OverwriteKind)>> = HashMap::new();
 
     for skill_name in &skills {
         let mut overwritten_agents = Vec::new();
@@ -34,16 +44,16 @@ pub async fn check_overwrites(
                 .parse()
                 .map_err(|_| AppError::InvalidAgent { agent: agent_str.clone() })?;
 
-            let is_installed = is_skill_installed(
-                skill_name,
-                &agent,
-                &scope,
-                project_path.as_deref(),
-            );
+            let installed_dir = match agent_skill_dir(skill_name, &agent, &scope, project_path.as_deref()) {
+                Some(dir) if dir.exists() => dir,
+                _ => continue,
+            };
 
-            if is_installed {
-                overwritten_agents.push(agent_str.clone());
-            }
+            let kind = classify_overwrite(
+                &installed_dir,
+                incoming_hashes.get(skill_name).map(|h| h.as_str()),
+            );
+            overwritten_agents.push((agent_str.clone(), kind));
         }
 
         if !overwritten_agents.is_empty() {
@@ -53,3 +63,51 @@ pub async fn check_overwrites(
 
     Ok(overwrites)
 }
+
+/// 比对已安装目录与即将安装内容的 hash
+///
+/// 缺少即将安装内容的 hash 或已安装目录无法读取时视为 Modified
+fn classify_overwrite(installed_dir: &Path, incoming_hash: Option<&str>) -> OverwriteKind {
+    match (incoming_hash, compute_skill_folder_hash(installed_dir)) {
+        (Some(incoming), Ok(installed)) if incoming == installed => OverwriteKind::Identical,
+        _ => OverwriteKind::Modified,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_classify_overwrite() {
+        let src = tempdir().unwrap();
+        fs::write(src.path().join("SKILL.md"), "---\nname: demo\ndescription: Demo\n---\n").unwrap();
+        // 安装时会被排除的文件不影响比对
+        fs::write(src.path().join("metadata.json"), "{}").unwrap();
+
+        let installed = tempdir().unwrap();
+        fs::write(
+            installed.path().join("SKILL.md"),
+            "---\nname: demo\ndescription: Demo\n---\n",
+        )
+        .unwrap();
+
+        let incoming = crate::core::installer::compute_installable_hash(src.path()).unwrap();
+        assert_eq!(
+            classify_overwrite(installed.path(), Some(&incoming)),
+            OverwriteKind::Identical
+        );
+
+        // 本地修改后为 Modified
+        fs::write(installed.path().join("notes.md"), "local edit").unwrap();
+        assert_eq!(
+            classify_overwrite(installed.path(), Some(&incoming)),
+            OverwriteKind::Modified
+        );
+
+        // 无法获取来源内容时保守处理
+        assert_eq!(classify_overwrite(installed.path(), None), OverwriteKind::Modified);
+    }
+}
@@ -0,0 +1,83 @@
+//! 分支 / tag 列表命令
+//!
+//! 提供命令：
+//! - list_refs: 列出 GitHub / GitLab 来源的分支和 tags，供安装向导选择 git_ref
+
+use crate::core::github_api::list_github_refs;
+use crate::core::gitlab_api::{gitlab_api_base, list_gitlab_refs};
+use crate::core::{get_owner_repo, parse_source};
+use crate::error::AppError;
+use crate::models::{GitRefInfo, GitRefType, SourceType};
+
+/// 列出来源仓库的分支和 tags
+///
+/// - GitHub / GitLab：调用对应平台 API（分页获取）
+/// - 其他来源：返回空列表（无法选择 ref）
+///
+/// 排序：分支在前（按名称升序），tags 在后（按名称降序，新版本通常排在前面）
+///
+/// # Arguments
+/// * `source` - 来源字符串（支持 9 种格式）
+#[tauri::command]
+#[specta::specta]
+pub async fn list_refs(source: String) -> Result<Vec<GitRefInfo>, AppError> {
+    let parsed = parse_source(&source)?;
+
+    let repo = match get_owner_repo(&parsed) {
+        Some(r) => r,
+        None => return Ok(Vec::new()),
+    };
+
+    let mut refs = match parsed.source_type {
+        SourceType::GitHub => list_github_refs(&repo).await?,
+        SourceType::GitLab => list_gitlab_refs(&gitlab_api_base(&parsed.url), &repo).await?,
+        _ => Vec::new(),
+    };
+
+    sort_refs(&mut refs);
+    Ok(refs)
+}
+
+/// 分支在前按名称升序，tags 在后按名称降序
+fn sort_refs(refs: &mut [GitRefInfo]) {
+    refs.sort_by(|a, b| {
+        a.ref_type.cmp(&b.ref_type).then_with(|| match a.ref_type {
+            GitRefType::Branch => a.name.cmp(&b.name),
+            GitRefType::Tag => b.name.cmp(&a.name),
+        })
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn git_ref(name: &str, ref_type: GitRefType) -> GitRefInfo {
+        GitRefInfo {
+            name: name.to_string(),
+            ref_type,
+        }
+    }
+
+    #[test]
+    fn test_sort_refs() {
+        let mut refs = vec![
+            git_ref("v1.0.0", GitRefType::Tag),
+            git_ref("main", GitRefType::Branch),
+            git_ref("v2.0.0", GitRefType::Tag),
+            git_ref("dev", GitRefType::Branch),
+        ];
+        sort_refs(&mut refs);
+
+        let names: Vec<&str> = refs.iter().map(|r| r.name.as_str()).collect();
+        assert_eq!(names, vec!["dev", "main", "v2.0.0", "v1.0.0"]);
+    }
+
+    #[test]
+    fn test_list_refs_local_source_is_empty() {
+        let temp = tempfile::tempdir().unwrap();
+        let source = temp.path().to_string_lossy().to_string();
+        let refs = tauri::async_runtime::block_on(list_refs(source)).unwrap();
+        assert!(refs.is_empty());
+    }
+}
@@ -1,7 +1,8 @@
 //! 删除相关的 Tauri Command
 //!
 //! 提供命令：
-//! - remove_skill: 删除指定 skill（支持完全删除和部分移除）
+//! - remove_skill: 删除指定 skill（支持完全删除、部分移除和 dry run 预览）
+//! - cleanup_empty_skill_dirs: 清理删除后留下的空 agent skills 目录
 //!
 //! 对应 CLI: remove.ts 的 removeCommand()
 //! GUI 增强：支持 full_removal（完全删除）和 agents 指定（部分移除）
@@ -19,6 +20,7 @@ use crate::models::{RemoveResult, Scope};
 /// * `project_path` - Project scope 时的项目路径
 /// * `agents` - 部分移除时指定的 agent 列表（None 或空 = 完全删除）
 /// * `full_removal` - 是否完全删除（true = 删除一切，false = 仅删除指定 agents 的 symlink）
+/// * `dry_run` - 只返回将删除的路径，不实际删除（默认 false），用于确认对话框预览
 #[tauri::command]
 #[specta::specta]
 pub async fn remove_skill(
@@ -27,9 +29,45 @@ pub async fn remove_skill(
     project_path: Option<String>,
     agents: Option<Vec<AgentType>>,
     full_removal: Option<bool>,
+    dry_run: Option<bool>,
 ) -> Result<RemoveResult, AppError> {
     let full = full_removal.unwrap_or(true);
     let target_agents = agents.filter(|a| !a.is_empty());
 
-    uninstaller::remove_skill(&name, &scope, project_path.as_deref(), full, target_agents.as_deref())
+    uninstaller::remove_skill(
+        &name,
+        &scope,
+        project_path.as_deref(),
+        full,
+        target_agents.as_deref(),
+        dry_run.unwrap_or(false),
+    )
+}
+
+/// 清理空的 agent skills 目录
+///
+/// 只删除 skills 子目录（如 `.cursor/skills` 不会连带删除 `.cursor`），不影响 agent 检测。
+///
+/// # Arguments
+/// * `scope` - 范围（global/project）
+/// * `project_path` - Project scope 时的项目路径
+/// * `agents` - 要清理的 agent 列表（None 或空 = 全部 agents）
+///
+/// # Returns
+/// * 已删除的目录路径
+#[tauri::command]
+#[specta::specta]
+pub async fn cleanup_empty_skill_dirs(
+    scope: Scope,
+    project_path: Option<String>,
+    agents: Option<Vec<AgentType>>,
+) -> Result<Vec<String>, AppError> {
+    let agents = agents
+        .filter(|a| !a.is_empty())
+        .unwrap_or_else(|| AgentType::all().collect());
+
+    Ok(uninstaller::remove_empty_skill_dirs(&scope, project_path.as_deref(), &agents)
+        .into_iter()
+        .map(|p| p.to_string_lossy().to_string())
+        .collect())
 }
@@ -2,12 +2,11 @@
 //!
 //! 为智能删除对话框提供 agent 安装详情
 
-use crate::core::agents::AgentType;
-use crate::core::paths::canonical_skills_dir;
-use crate::core::skill::sanitize_name;
+use crate::core::agents::{AgentType, DetectionContext};
+use crate::core::paths::{agent_skills_dir, canonical_skills_dir, is_symlink_or_junction};
+use crate::core::skill::skill_dir_name;
 use crate::error::AppError;
 use crate::models::{IndependentAgentInfo, Scope, SkillAgentDetails};
-use std::path::PathBuf;
 
 /// 查询 skill 的 agent 安装详情
 ///
@@ -19,16 +18,25 @@ pub async fn get_skill_agent_details(
     name: String,
     project_path: Option<String>,
 ) -> Result<SkillAgentDetails, AppError> {
+    Ok(skill_agent_details(scope, name, project_path.as_deref()))
+}
+
+/// `get_skill_agent_details` 的实现，供 get_skill_info 复用
+pub(crate) fn skill_agent_details(
+    scope: Scope,
+    name: String,
+    project_path: Option<&str>,
+) -> SkillAgentDetails {
     let is_global = matches!(scope, Scope::Global);
-    let cwd = project_path.as_deref().unwrap_or(".");
-    let sanitized_name = sanitize_name(&name);
+    let cwd = project_path.unwrap_or(".");
+    let sanitized_name = skill_dir_name(&name, is_global, cwd);
 
     // 1. 计算 canonical 路径
     let canonical_path = canonical_skills_dir(is_global, cwd).join(&sanitized_name);
 
     // 2. 获取所有已安装的 agents（与当前 remove_skill 一致的逻辑）
     let detected_agents = {
-        let detected = AgentType::detect_installed();
+        let detected = AgentType::detect_installed(&DetectionContext::for_scope(is_global, cwd));
         if detected.is_empty() {
             AgentType::all().collect::<Vec<_>>()
         } else {
@@ -44,15 +52,9 @@ pub async fn get_skill_agent_details(
         let config = agent.config();
 
         // 计算该 agent 的 skill 路径
-        let skill_path = if is_global {
-            match &config.global_skills_dir {
-                Some(global_dir) => global_dir.join(&sanitized_name),
-                None => continue, // agent 不支持 global
-            }
-        } else {
-            PathBuf::from(cwd)
-                .join(&config.skills_dir)
-                .join(&sanitized_name)
+        let skill_path = match agent_skills_dir(agent, is_global, cwd) {
+            Some(base) => base.join(&sanitized_name),
+            None => continue, // agent 不支持 global
         };
 
         // 检查路径是否存在（包括 symlink）
@@ -61,7 +63,7 @@ pub async fn get_skill_agent_details(
             continue;
         }
 
-        if agent.is_universal() {
+        if agent.shares_canonical_dir(is_global) {
             // 仅展示 show_in_universal_list 的 agent，与安装向导保持一致
             // Replit/Universal 等隐藏的 universal agent 不参与展示
             if config.show_in_universal_list {
@@ -70,24 +72,8 @@ pub async fn get_skill_agent_details(
             // universal agent 不能放入 independent（删除其目录 = 删除 canonical）
             continue;
         } else {
-            // 检查是否是 symlink
-            let is_symlink = skill_path
-                .symlink_metadata()
-                .map(|m| m.file_type().is_symlink())
-                .unwrap_or(false);
-
-            // Windows junction 也需要检查
-            #[cfg(windows)]
-            let is_symlink = is_symlink || {
-                skill_path
-                    .symlink_metadata()
-                    .map(|m| {
-                        // Junction 在 Windows 上表现为 dir + reparse point
-                        m.file_type().is_dir()
-                            && std::os::windows::fs::MetadataExt::file_attributes(&m) & 0x400 != 0
-                    })
-                    .unwrap_or(false)
-            };
+            // 检查是否是 symlink（包括 Windows junction）
+            let is_symlink = is_symlink_or_junction(&skill_path);
 
             independent_agents.push(IndependentAgentInfo {
                 agent: *agent,
@@ -98,11 +84,11 @@ pub async fn get_skill_agent_details(
         }
     }
 
-    Ok(SkillAgentDetails {
+    SkillAgentDetails {
         skill_name: name,
         scope,
         canonical_path: canonical_path.to_string_lossy().to_string(),
         universal_agents,
         independent_agents,
-    })
+    }
 }
@@ -0,0 +1,38 @@
+//! 重命名相关的 Tauri Command
+//!
+//! 提供命令：
+//! - rename_skill: 重命名已安装的 skill（canonical 目录、agent 链接、lock 条目）
+//!
+//! GUI 增强：CLI 无对应命令，用于两个来源的 skill 同名时在本地区分
+
+use crate::core::renamer;
+use crate::error::AppError;
+use crate::models::{RenameResult, Scope};
+
+/// 重命名已安装的 skill
+///
+/// 新名称（sanitize 后）已存在时返回错误，不做任何修改。
+///
+/// # Arguments
+/// * `scope` - 范围（global/project）
+/// * `name` - 当前名称
+/// * `new_name` - 新名称
+/// * `project_path` - Project scope 时的项目路径
+/// * `rewrite_skill_md` - 是否同时改写 SKILL.md 中的 name（默认 false）
+#[tauri::command]
+#[specta::specta]
+pub async fn rename_skill(
+    scope: Scope,
+    name: String,
+    new_name: String,
+    project_path: Option<String>,
+    rewrite_skill_md: Option<bool>,
+) -> Result<RenameResult, AppError> {
+    renamer::rename_skill(
+        &name,
+        new_name.trim(),
+        &scope,
+        project_path.as_deref(),
+        rewrite_skill_md.unwrap_or(false),
+    )
+}
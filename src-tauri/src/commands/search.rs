@@ -0,0 +1,299 @@
+//! Skill 搜索命令
+//!
+//! 提供命令：
+//! - search_skills: 并发从多个来源获取可用 skills，按关键字过滤并排序
+//!
+//! 每个来源在独立线程中获取（克隆为阻塞操作），超过 `SEARCH_SOURCE_TIMEOUT_SECS`
+//! 仍未返回的来源记为失败，不阻塞其他来源的结果；超时后取消这些来源仍在进行的克隆。
+
+use crate::commands::install::fetch_available_inner;
+use crate::commands::operation::{Operation, OperationKind};
+use crate::core::cancel_clones;
+use crate::error::AppError;
+use crate::models::FetchResult;
+use serde::Serialize;
+use specta::Type;
+use std::sync::mpsc;
+use std::time::{Duration, Instant};
+use tauri::AppHandle;
+
+/// 单个来源的获取超时（秒）
+const SEARCH_SOURCE_TIMEOUT_SECS: u64 = 30;
+
+/// 搜索命中的 skill
+#[derive(Debug, Clone, Serialize, Type)]
+#[serde(rename_all = "camelCase")]
+#[specta(rename_all = "camelCase")]
+pub struct SkillSearchHit {
+    /// 用户传入的来源字符串（可直接用于 install_skills）
+    pub source: String,
+    /// 来源类型
+    pub source_type: String,
+    /// 规范化 URL
+    pub source_url: String,
+    pub name: String,
+    pub description: String,
+    /// 仓库内相对路径
+    pub relative_path: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub plugin_name: Option<String>,
+}
+
+/// 获取失败或超时的来源
+#[derive(Debug, Clone, Serialize, Type)]
+#[serde(rename_all = "camelCase")]
+#[specta(rename_all = "camelCase")]
+pub struct SourceSearchFailure {
+    pub source: String,
+    pub message: String,
+}
+
+/// 搜索结果
+#[derive(Debug, Clone, Serialize, Type)]
+#[serde(rename_all = "camelCase")]
+#[specta(rename_all = "camelCase")]
+pub struct SkillSearchResults {
+    /// 按相关度排序的命中列表
+    pub hits: Vec<SkillSearchHit>,
+    /// 获取失败的来源
+    pub failures: Vec<SourceSearchFailure>,
+}
+
+/// 在多个来源中搜索 skills
+///
+/// 对 name / description 做大小写不敏感的子串匹配，空关键字返回全部 skills。
+/// 排序：名称完全匹配 > 名称前缀匹配 > 名称包含 > 仅描述包含，同级按名称和来源顺序。
+///
+/// # Arguments
+/// * `query` - 搜索关键字
+/// * `sources` - 来源字符串列表（格式同 fetch_available）
+/// * `op_id` - 前端指定的操作 id（用于 operation-event、clone-progress 和 cancel_clone），未传时自动生成
+#[tauri::command]
+#[specta::specta]
+pub async fn search_skills(
+    app: AppHandle,
+    query: String,
+    sources: Vec<String>,
+    op_id: Option<String>,
+) -> Result<SkillSearchResults, AppError> {
+    let timeout = Duration::from_secs(SEARCH_SOURCE_TIMEOUT_SECS);
+    let op = Operation::start(&app, OperationKind::Search, op_id);
+    tauri::async_runtime::spawn_blocking(move || {
+        let fetched = fetch_all(&app, &sources, timeout, &op);
+        let results = build_search_results(&query, fetched);
+        op.finish(Ok(results), |r| format!("{} hits, {} sources failed", r.hits.len(), r.failures.len()))
+    })
+    .await
+    .map_err(|e| AppError::Custom { message: format!("Search task failed: {}", e) })?
+}
+
+/// 并发获取所有来源，返回与 sources 顺序一致的结果（每个来源返回时发送进度）
+fn fetch_all(
+    app: &AppHandle,
+    sources: &[String],
+    timeout: Duration,
+    op: &Operation,
+) -> Vec<(String, Result<FetchResult, AppError>)> {
+    let (tx, rx) = mpsc::channel();
+
+    for (index, source) in sources.iter().enumerate() {
+        let tx = tx.clone();
+        let app = app.clone();
+        let source = source.clone();
+        let op_id = op.id().to_string();
+        std::thread::spawn(move || {
+            let result = tauri::async_runtime::block_on(fetch_available_inner(
+                &app,
+                &source,
+                None,
+                None,
+                Some(&op_id),
+            ));
+            // 接收端可能已因超时停止等待
+            let _ = tx.send((index, result));
+        });
+    }
+    drop(tx);
+
+    let mut results: Vec<Option<Result<FetchResult, AppError>>> =
+        sources.iter().map(|_| None).collect();
+    let deadline = Instant::now() + timeout;
+    let mut completed = 0;
+    op.progress("fetching", completed, sources.len());
+
+    while results.iter().any(|r| r.is_none()) {
+        let remaining = deadline.saturating_duration_since(Instant::now());
+        match rx.recv_timeout(remaining) {
+            Ok((index, result)) => {
+                results[index] = Some(result);
+                completed += 1;
+                op.progress("fetching", completed, sources.len());
+            }
+            Err(_) => {
+                // 超时：未返回的来源都属于本次操作，终止它们仍在进行的克隆
+                let cancelled = cancel_clones(Some(op.id()));
+                if cancelled > 0 {
+                    log::info!("Cancelled {} search clones after timeout", cancelled);
+                }
+                break;
+            }
+        }
+    }
+
+    sources
+        .iter()
+        .cloned()
+        .zip(results)
+        .map(|(source, result)| {
+            let result = result.unwrap_or_else(|| {
+                Err(AppError::Custom {
+                    message: format!("Timed out after {}s", timeout.as_secs()),
+                })
+            });
+            (source, result)
+        })
+        .collect()
+}
+
+/// 过滤、排序并汇总各来源的获取结果
+fn build_search_results(
+    query: &str,
+    fetched: Vec<(String, Result<FetchResult, AppError>)>,
+) -> SkillSearchResults {
+    let query = query.trim().to_lowercase();
+    let mut ranked = Vec::new();
+    let mut failures = Vec::new();
+
+    for (source_index, (source, result)) in fetched.into_iter().enumerate() {
+        let fetch_result = match result {
+            Ok(r) => r,
+            Err(e) => {
+                failures.push(SourceSearchFailure {
+                    source,
+                    message: e.to_string(),
+                });
+                continue;
+            }
+        };
+
+        for skill in fetch_result.skills {
+            let Some(rank) = match_rank(&query, &skill.name, &skill.description) else {
+                continue;
+            };
+            ranked.push((
+                rank,
+                source_index,
+                SkillSearchHit {
+                    source: source.clone(),
+                    source_type: fetch_result.source_type.clone(),
+                    source_url: fetch_result.source_url.clone(),
+                    name: skill.name,
+                    description: skill.description,
+                    relative_path: skill.relative_path,
+                    plugin_name: skill.plugin_name,
+                },
+            ));
+        }
+    }
+
+    ranked.sort_by(|(rank_a, src_a, a), (rank_b, src_b, b)| {
+        rank_a
+            .cmp(rank_b)
+            .then_with(|| a.name.to_lowercase().cmp(&b.name.to_lowercase()))
+            .then_with(|| src_a.cmp(src_b))
+    });
+
+    SkillSearchResults {
+        hits: ranked.into_iter().map(|(_, _, hit)| hit).collect(),
+        failures,
+    }
+}
+
+/// 计算匹配等级（越小越相关），不匹配返回 None
+///
+/// `query` 需已转为小写
+fn match_rank(query: &str, name: &str, description: &str) -> Option<u8> {
+    let name = name.to_lowercase();
+    if name == query {
+        Some(0)
+    } else if name.starts_with(query) {
+        Some(1)
+    } else if name.contains(query) {
+        Some(2)
+    } else if description.to_lowercase().contains(query) {
+        Some(3)
+    } else {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::AvailableSkill;
+
+    fn fetch_result(skills: &[(&str, &str)]) -> FetchResult {
+        FetchResult {
+            source_type: "github".to_string(),
+            source_url: "https://github.com/owner/repo".to_string(),
+            skill_filter: None,
+            skills: skills
+                .iter()
+                .map(|(name, description)| AvailableSkill {
+                    name: name.to_string(),
+                    description: description.to_string(),
+                    relative_path: format!("skills/{}", name),
+                    plugin_name: None,
+                    version: None,
+                    author: None,
+                    license: None,
+                    tags: Vec::new(),
+                    category: None,
+                    icon_url: None,
+                    locales: Vec::new(),
+                    audit: None,
+                    content_hash: None,
+                })
+                .collect(),
+            warnings: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_match_rank() {
+        assert_eq!(match_rank("pdf", "PDF", ""), Some(0));
+        assert_eq!(match_rank("pdf", "pdf-tools", ""), Some(1));
+        assert_eq!(match_rank("pdf", "read-pdf", ""), Some(2));
+        assert_eq!(match_rank("pdf", "docs", "Handles PDF files"), Some(3));
+        assert_eq!(match_rank("pdf", "docs", "Word files"), None);
+        // 空关键字匹配全部
+        assert_eq!(match_rank("", "docs", ""), Some(1));
+    }
+
+    #[test]
+    fn test_build_search_results_ranks_and_reports_failures() {
+        let fetched = vec![
+            (
+                "owner/a".to_string(),
+                Ok(fetch_result(&[("read-pdf", ""), ("docs", "Convert PDF to text")])),
+            ),
+            (
+                "owner/slow".to_string(),
+                Err(AppError::Custom { message: "Timed out after 30s".to_string() }),
+            ),
+            (
+                "owner/b".to_string(),
+                Ok(fetch_result(&[("pdf", ""), ("xlsx", "Spreadsheets")])),
+            ),
+        ];
+
+        let results = build_search_results(" PDF ", fetched);
+        let names: Vec<_> = results.hits.iter().map(|h| h.name.as_str()).collect();
+        assert_eq!(names, vec!["pdf", "read-pdf", "docs"]);
+        assert_eq!(results.hits[0].source, "owner/b");
+        assert_eq!(results.hits[1].source, "owner/a");
+
+        assert_eq!(results.failures.len(), 1);
+        assert_eq!(results.failures[0].source, "owner/slow");
+    }
+}
@@ -1,10 +1,25 @@
 // list_skills command
 
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 use specta::Type;
 
-use crate::core::skill::{list_installed_skills, ListSkillsResult, SkillScope};
+use crate::commands::config::get_config;
+use crate::commands::remove_details::skill_agent_details;
+use crate::core::agents::AgentType;
+use crate::core::link_audit;
+use crate::core::local_lock::read_local_lock;
+use crate::core::orphans;
+use crate::core::shadowing;
+use crate::core::paths::canonical_skills_dir;
+use crate::core::skill::{
+    self, collect_skill_tags, compute_dir_size, filter_skills_by_tags, list_installed_skills, parse_skill_md,
+    skill_dir_name, ListSkillsResult, SkillFileEntry, SkillScope,
+};
+use crate::core::skill_lock::get_skill_from_lock;
+use crate::core::usage;
+use crate::core::visibility;
 use crate::error::AppError;
+use crate::models::{LinkMismatch, OrphanedSkill, Scope, ShadowedSkill, SkillAgentDetails, SkillUsage};
 
 /// list_skills 参数
 #[derive(Debug, Deserialize, Type)]
@@ -15,6 +30,22 @@ pub struct ListSkillsParams {
     pub scope: Option<String>,
     /// 项目路径（用于 project scope）
     pub project_path: Option<String>,
+    /// 是否统计每个 skill 的磁盘占用（默认 false，保持列表快速）
+    #[serde(default)]
+    pub include_size: bool,
+    /// 是否检查 Copy 模式的 agent 副本与 canonical 目录内容是否一致（默认 false，需要计算哈希）
+    #[serde(default)]
+    pub verify_copies: bool,
+    /// 要扫描的 agents；未提供时只扫描检测到的 agents
+    /// （预先安装到尚未检测到的 agent 时传入，以便列出这些 skills）
+    #[serde(default)]
+    pub agents: Option<Vec<AgentType>>,
+    /// 只返回带有这些 tags 的 skills（不区分大小写，为空时不过滤）
+    #[serde(default)]
+    pub tags: Vec<String>,
+    /// 为 true 时需包含全部 tags，否则包含任一即可
+    #[serde(default)]
+    pub match_all_tags: bool,
 }
 
 /// 列出已安装的 skills
@@ -40,14 +71,379 @@ pub fn list_skills(params: ListSkillsParams) -> Result<ListSkillsResult, AppErro
         _ => true, // global 始终为 true
     };
 
-    let skills = if path_exists {
-        list_installed_skills(scope, &cwd)?
+    let mut skills = if path_exists {
+        list_installed_skills(
+            scope,
+            &cwd,
+            params.include_size,
+            params.verify_copies,
+            params.agents.as_deref(),
+        )?
     } else {
         Vec::new()
     };
+    let all_tags = collect_skill_tags(&skills);
+    filter_skills_by_tags(&mut skills, &params.tags, params.match_all_tags);
 
     Ok(ListSkillsResult {
         skills,
         path_exists,
+        all_tags,
     })
 }
+
+/// lock 中记录的来源信息（Global 与 Project lock 的字段合集）
+#[derive(Debug, Clone, Serialize, Type)]
+#[serde(rename_all = "camelCase")]
+#[specta(rename_all = "camelCase")]
+pub struct SkillLockInfo {
+    pub source: String,
+    pub source_type: String,
+    /// 原始安装 URL（仅 Global lock 记录）
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub source_url: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub skill_path: Option<String>,
+    /// 安装 / 更新时间（旧版 Project lock 没有记录）
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub installed_at: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub updated_at: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub plugin_name: Option<String>,
+    /// 安装时 SKILL.md 中的 version
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub version: Option<String>,
+    /// 安装时记录的远程 tree SHA（Global: skillFolderHash，Project: remoteHash）
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub remote_hash: Option<String>,
+}
+
+/// 单个已安装 skill 的完整信息
+#[derive(Debug, Clone, Serialize, Type)]
+#[serde(rename_all = "camelCase")]
+#[specta(rename_all = "camelCase")]
+pub struct SkillInfo {
+    pub name: String,
+    pub scope: Scope,
+    pub canonical_path: String,
+    /// canonical 目录是否存在
+    pub canonical_exists: bool,
+    // 来自 SKILL.md frontmatter（解析失败时为空，原因见 skill_md_error）
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub version: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub author: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub license: Option<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<String>,
+    pub internal: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub skill_md_error: Option<String>,
+    /// lock 条目，未记录（如手动放入的目录）时为空
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub lock: Option<SkillLockInfo>,
+    /// 占用磁盘字节数（canonical 目录不存在时为空）
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[specta(type = Option<f64>)]
+    pub size_bytes: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub file_count: Option<u32>,
+    /// 各 agent 的安装详情（同 get_skill_agent_details）
+    pub agent_details: SkillAgentDetails,
+}
+
+/// 获取单个已安装 skill 的完整信息
+///
+/// 汇总 SKILL.md frontmatter、lock 条目、canonical 目录状态和各 agent 的安装详情。
+/// 只读取本地文件；是否有更新需要访问网络，请使用 check_updates。
+///
+/// # Arguments
+/// * `scope` - 安装范围
+/// * `name` - skill 名称（lock 中的 key）
+/// * `project_path` - Project scope 时的项目路径
+#[tauri::command]
+#[specta::specta]
+pub fn get_skill_info(
+    scope: Scope,
+    name: String,
+    project_path: Option<String>,
+) -> Result<SkillInfo, AppError> {
+    let is_global = matches!(scope, Scope::Global);
+    let cwd = project_path.as_deref().unwrap_or(".");
+    let canonical_dir = canonical_skills_dir(is_global, cwd).join(skill_dir_name(&name, is_global, cwd));
+    let canonical_exists = canonical_dir.is_dir();
+
+    let lock = if is_global {
+        get_skill_from_lock(&name)?.map(|entry| SkillLockInfo {
+            source: entry.source,
+            source_type: entry.source_type,
+            source_url: Some(entry.source_url).filter(|u| !u.is_empty()),
+            skill_path: entry.skill_path,
+            installed_at: Some(entry.installed_at),
+            updated_at: Some(entry.updated_at),
+            plugin_name: entry.plugin_name,
+            version: entry.version,
+            remote_hash: Some(entry.skill_folder_hash).filter(|h| !h.is_empty()),
+        })
+    } else {
+        read_local_lock(cwd)?.skills.remove(&name).map(|entry| SkillLockInfo {
+            source: entry.source,
+            source_type: entry.source_type,
+            source_url: None,
+            skill_path: entry.skill_path,
+            installed_at: entry.installed_at,
+            updated_at: entry.updated_at,
+            plugin_name: entry.plugin_name,
+            version: entry.version,
+            remote_hash: entry.remote_hash,
+        })
+    };
+
+    if !canonical_exists && lock.is_none() {
+        return Err(AppError::PathNotFound {
+            path: canonical_dir.to_string_lossy().to_string(),
+        });
+    }
+
+    let mut info = SkillInfo {
+        name: name.clone(),
+        scope: scope.clone(),
+        canonical_path: canonical_dir.to_string_lossy().to_string(),
+        canonical_exists,
+        description: None,
+        version: None,
+        author: None,
+        license: None,
+        tags: Vec::new(),
+        internal: false,
+        skill_md_error: None,
+        lock,
+        size_bytes: None,
+        file_count: None,
+        agent_details: skill_agent_details(scope, name, project_path.as_deref()),
+    };
+
+    if canonical_exists {
+        match parse_skill_md(&canonical_dir.join("SKILL.md")) {
+            Ok(frontmatter) => {
+                info.internal = frontmatter.metadata.as_ref().is_some_and(|m| m.internal);
+                info.description = Some(frontmatter.description);
+                info.version = frontmatter.version;
+                info.author = frontmatter.author;
+                info.license = frontmatter.license;
+                info.tags = frontmatter.tags;
+            }
+            Err(e) => info.skill_md_error = Some(e.to_string()),
+        }
+
+        let (size_bytes, file_count) = compute_dir_size(&canonical_dir);
+        info.size_bytes = Some(size_bytes);
+        info.file_count = Some(file_count);
+    }
+
+    Ok(info)
+}
+
+/// 列出已安装 skill 的文件树（canonical 目录）
+///
+/// 只读遍历，跳过 .git、node_modules；skill 内的符号链接不会被跟随到 skill 目录之外。
+///
+/// # Arguments
+/// * `scope` - 安装范围
+/// * `name` - skill 名称（lock 中的 key）
+/// * `project_path` - Project scope 时的项目路径
+#[tauri::command]
+#[specta::specta]
+pub fn list_skill_files(
+    scope: Scope,
+    name: String,
+    project_path: Option<String>,
+) -> Result<Vec<SkillFileEntry>, AppError> {
+    let is_global = matches!(scope, Scope::Global);
+    let cwd = project_path.as_deref().unwrap_or(".");
+    let canonical_dir = canonical_skills_dir(is_global, cwd).join(skill_dir_name(&name, is_global, cwd));
+    if !canonical_dir.is_dir() {
+        return Err(AppError::PathNotFound {
+            path: canonical_dir.to_string_lossy().to_string(),
+        });
+    }
+    skill::list_skill_files(&canonical_dir)
+}
+
+/// 设置 skill 是否为 internal（internal skill 不出现在 list_skills 结果中）
+///
+/// 改写 canonical 目录及 copy 模式 agent 目录中 SKILL.md 的 `metadata.internal`，
+/// 不卸载 skill。
+///
+/// # Arguments
+/// * `scope` - 安装范围
+/// * `name` - skill 名称
+/// * `project_path` - Project scope 时的项目路径
+/// * `internal` - true 隐藏，false 恢复显示
+#[tauri::command]
+#[specta::specta]
+pub fn set_skill_internal(
+    scope: Scope,
+    name: String,
+    project_path: Option<String>,
+    internal: bool,
+) -> Result<(), AppError> {
+    visibility::set_skill_internal(&name, &scope, project_path.as_deref(), internal)
+}
+
+/// 列出孤立的 canonical skills
+///
+/// 返回 canonical 目录中没有 lock 条目、或没有任何 agent 使用的 skill（含 internal 和
+/// SKILL.md 无效的目录），用于清理安装中断等原因留下的残留。
+///
+/// # Arguments
+/// * `scope` - 安装范围
+/// * `project_path` - Project scope 时的项目路径
+#[tauri::command]
+#[specta::specta]
+pub fn list_orphaned_skills(
+    scope: Scope,
+    project_path: Option<String>,
+) -> Result<Vec<OrphanedSkill>, AppError> {
+    orphans::list_orphaned_skills(&scope, project_path.as_deref())
+}
+
+/// 检查 agent 链接是否指向正确的 canonical 目录
+///
+/// 对 lock 中的每个 skill 读取各 agent 目录中的 symlink / junction 目标，
+/// 返回与期望 canonical 目录不一致的链接（实际目标与期望目标），供修复使用。
+///
+/// # Arguments
+/// * `scope` - 安装范围
+/// * `project_path` - Project scope 时的项目路径
+#[tauri::command]
+#[specta::specta]
+pub fn audit_links(scope: Scope, project_path: Option<String>) -> Result<Vec<LinkMismatch>, AppError> {
+    link_audit::audit_links(&scope, project_path.as_deref())
+}
+
+/// 检测同时安装在 global 和项目中的 skill
+///
+/// 对每个已检测到的 agent，比较其项目级和全局目录中同名（按 SKILL.md name）的 skill，
+/// 并按该 agent 的目录优先级给出实际生效的副本。
+///
+/// # Arguments
+/// * `project_path` - 项目路径
+#[tauri::command]
+#[specta::specta]
+pub fn detect_skill_shadowing(project_path: String) -> Result<Vec<ShadowedSkill>, AppError> {
+    shadowing::detect_skill_shadowing(&project_path)
+}
+
+/// 查找 skill 在 global 和所有已保存项目中的安装位置
+///
+/// 扫描全局 lock、每个项目的 skills-lock.json 以及对应的 canonical 目录；
+/// 已不存在的项目路径记入 `staleProjects`。
+///
+/// # Arguments
+/// * `name` - skill 名称（lock key）
+#[tauri::command]
+#[specta::specta]
+pub fn find_skill_usage(name: String) -> Result<SkillUsage, AppError> {
+    usage::find_skill_usage(&name, &get_config()?.projects)
+}
+
+/// 在系统默认编辑器中打开 skill 的 SKILL.md
+/// 路径解析与 remove_details 一致：canonical_skills_dir + skill_dir_name
+#[tauri::command]
+#[specta::specta]
+pub fn open_skill_file(
+    skill_name: String,
+    scope: Scope,
+    project_path: Option<String>,
+) -> Result<(), AppError> {
+    let is_global = matches!(scope, Scope::Global);
+    let cwd = project_path.as_deref().unwrap_or(".");
+    let skill_md = canonical_skills_dir(is_global, cwd)
+        .join(skill_dir_name(&skill_name, is_global, cwd))
+        .join("SKILL.md");
+
+    if !skill_md.is_file() {
+        return Err(AppError::PathNotFound {
+            path: skill_md.to_string_lossy().to_string(),
+        });
+    }
+
+    #[cfg(target_os = "windows")]
+    {
+        // start 的第一个带引号参数是窗口标题，需传空字符串
+        std::process::Command::new("cmd")
+            .args(["/c", "start", ""])
+            .arg(&skill_md)
+            .spawn()?;
+    }
+    #[cfg(target_os = "macos")]
+    {
+        std::process::Command::new("open")
+            .arg(&skill_md)
+            .spawn()?;
+    }
+    #[cfg(target_os = "linux")]
+    {
+        std::process::Command::new("xdg-open")
+            .arg(&skill_md)
+            .spawn()?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::local_lock::{write_local_lock, LocalSkillLockEntry, LocalSkillLockFile};
+    use std::fs;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_get_skill_info_project() {
+        let project = tempdir().unwrap();
+        let cwd = project.path().to_string_lossy().to_string();
+        let canonical = project.path().join(".agents/skills/demo");
+        fs::create_dir_all(&canonical).unwrap();
+        fs::write(
+            canonical.join("SKILL.md"),
+            "---\nname: demo\ndescription: Demo skill\nlicense: MIT\nmetadata:\n  internal: true\n  version: 1.2.0\n---\n",
+        )
+        .unwrap();
+
+        let mut lock = LocalSkillLockFile::empty();
+        lock.skills.insert(
+            "demo".to_string(),
+            LocalSkillLockEntry {
+                source: "owner/repo".to_string(),
+                source_type: "github".to_string(),
+                computed_hash: "abc".to_string(),
+                remote_hash: Some("def".to_string()),
+                skill_path: Some("skills/demo/SKILL.md".to_string()),
+                version: Some("1.2.0".to_string()),
+                ..Default::default()
+            },
+        );
+        write_local_lock(&lock, &cwd).unwrap();
+
+        let info = get_skill_info(Scope::Project, "demo".to_string(), Some(cwd.clone())).unwrap();
+        assert!(info.canonical_exists);
+        assert_eq!(info.description.as_deref(), Some("Demo skill"));
+        assert_eq!(info.version.as_deref(), Some("1.2.0"));
+        assert_eq!(info.license.as_deref(), Some("MIT"));
+        assert!(info.internal);
+        assert_eq!(info.file_count, Some(1));
+        let lock = info.lock.unwrap();
+        assert_eq!(lock.source, "owner/repo");
+        assert_eq!(lock.remote_hash.as_deref(), Some("def"));
+        assert_eq!(info.agent_details.skill_name, "demo");
+
+        // 既无目录也无 lock 条目
+        let err = get_skill_info(Scope::Project, "missing".to_string(), Some(cwd)).unwrap_err();
+        assert!(matches!(err, AppError::PathNotFound { .. }));
+    }
+}
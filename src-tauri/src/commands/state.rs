@@ -0,0 +1,191 @@
+//! 状态备份命令
+//!
+//! 提供命令：
+//! - export_state: 导出 global lock、config.json 和（可选）canonical skills 目录为 tar.gz
+//! - import_state: 从备份恢复（替换或合并），并为本机已安装的 agents 按导出时的模式重新安装
+//!
+//! 用于迁移到新机器。只处理 global scope，项目级 lock 随项目仓库迁移。
+
+use crate::commands::config::{get_config, save_config};
+use crate::commands::operation::{Operation, OperationKind};
+use crate::core::paths::{global_canonical_skills_dir, validate_canonical_dir_override};
+use crate::core::state_backup::{
+    export_state as export_archive, open_state_archive, restore_state, StatePaths,
+};
+use crate::error::AppError;
+use crate::models::SkillDeckConfig;
+use serde::Serialize;
+use specta::Type;
+use std::path::Path;
+use tauri::AppHandle;
+
+/// 导出结果
+#[derive(Debug, Clone, Serialize, Type)]
+#[serde(rename_all = "camelCase")]
+#[specta(rename_all = "camelCase")]
+pub struct StateExportReport {
+    /// 归档路径
+    pub archive_path: String,
+    /// lock 的 schema 版本（没有 lock 时为 None）
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub lock_version: Option<u32>,
+    /// lock 中的 skill 数量
+    pub locked_skills: u32,
+    /// 归档中包含的 canonical skill 目录数量
+    pub archived_skill_dirs: u32,
+}
+
+/// 导入结果
+#[derive(Debug, Clone, Serialize, Type)]
+#[serde(rename_all = "camelCase")]
+#[specta(rename_all = "camelCase")]
+pub struct StateImportReport {
+    /// 导出归档的应用版本
+    pub source_app_version: String,
+    /// 是否恢复了 config.json
+    pub config_restored: bool,
+    /// 写入 lock 的 skill
+    pub restored_skills: Vec<String>,
+    /// 合并时本机版本更新而保留的 skill
+    pub kept_skills: Vec<String>,
+    /// 重新创建的链接：(skill 目录名, agent)
+    pub relinked: Vec<(String, String)>,
+    /// 非致命问题（lock 版本迁移、跳过的条目、链接失败等）
+    pub warnings: Vec<String>,
+}
+
+/// 导出 global 状态
+///
+/// # Arguments
+/// * `out_path` - 输出的 tar.gz 路径
+/// * `include_skills` - 是否包含 canonical skills 目录（默认 true，新机器无需重新下载）
+/// * `op_id` - 前端指定的操作 id（用于 operation-event），未传时自动生成
+#[tauri::command]
+#[specta::specta]
+pub fn export_state(
+    app: AppHandle,
+    out_path: String,
+    include_skills: Option<bool>,
+    op_id: Option<String>,
+) -> Result<StateExportReport, AppError> {
+    let op = Operation::start(&app, OperationKind::ExportState, op_id);
+    let result = export_archive(
+        &StatePaths::global(),
+        Path::new(&out_path),
+        include_skills.unwrap_or(true),
+    )
+    .map(|export| StateExportReport {
+        archive_path: out_path,
+        lock_version: export.manifest.lock_version,
+        locked_skills: export.locked_skills as u32,
+        archived_skill_dirs: export.archived_skill_dirs as u32,
+    });
+    op.finish(result, |report| format!("{} skills exported", report.locked_skills))
+}
+
+/// 从备份恢复 global 状态
+///
+/// 先恢复 lock 和 skills（恢复位置按导入后配置的 canonicalDirOverride 计算），
+/// 全部成功后最后保存 config.json；恢复失败时本机配置保持不变。
+///
+/// # Arguments
+/// * `in_path` - export_state 生成的 tar.gz
+/// * `merge` - false（默认）: 替换本机 lock 和配置；
+///   true: 合并 lock 条目（按 updatedAt 取较新者），配置只补充本机没有的项目和 GitLab 主机
+/// * `op_id` - 前端指定的操作 id（用于 operation-event），未传时自动生成
+#[tauri::command]
+#[specta::specta]
+pub fn import_state(
+    app: AppHandle,
+    in_path: String,
+    merge: Option<bool>,
+    op_id: Option<String>,
+) -> Result<StateImportReport, AppError> {
+    let op = Operation::start(&app, OperationKind::ImportState, op_id);
+    let result = import_state_inner(&in_path, merge.unwrap_or(false));
+    op.finish(result, |report| format!("{} skills restored", report.restored_skills.len()))
+}
+
+fn import_state_inner(in_path: &str, merge: bool) -> Result<StateImportReport, AppError> {
+    let archive = open_state_archive(Path::new(in_path))?;
+
+    let config = match archive.config.clone() {
+        Some(imported) if merge => Some(merge_config(get_config()?, imported)),
+        imported => imported,
+    };
+    let mut paths = StatePaths::global();
+    if let Some(config) = &config {
+        let override_dir = match config.canonical_dir_override.as_deref() {
+            Some(value) => Some(validate_canonical_dir_override(value)?),
+            None => None,
+        };
+        paths.canonical_base = global_canonical_skills_dir(override_dir.as_deref());
+    }
+
+    let restored = restore_state(&archive, &paths, merge)?;
+    let config_restored = config.is_some();
+    if let Some(config) = config {
+        save_config(config)?;
+    }
+    Ok(StateImportReport {
+        source_app_version: archive.manifest.app_version.clone(),
+        config_restored,
+        restored_skills: restored.restored_skills,
+        kept_skills: restored.kept_skills,
+        relinked: restored.relinked,
+        warnings: restored.warnings,
+    })
+}
+
+/// 合并配置：保留本机设置，补充备份中本机没有的项目、项目默认 agents 和 GitLab 主机
+fn merge_config(mut local: SkillDeckConfig, imported: SkillDeckConfig) -> SkillDeckConfig {
+    for project in imported.projects {
+        if !local.projects.contains(&project) {
+            local.projects.push(project);
+        }
+    }
+    for (project, agents) in imported.project_default_agents {
+        local.project_default_agents.entry(project).or_insert(agents);
+    }
+    for host in imported.gitlab_hosts {
+        if !local.gitlab_hosts.contains(&host) {
+            local.gitlab_hosts.push(host);
+        }
+    }
+    local
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    #[test]
+    fn test_merge_config_keeps_local_settings() {
+        let local = SkillDeckConfig {
+            projects: vec!["/work/a".to_string()],
+            canonical_dir_override: Some("/data/skills".to_string()),
+            project_default_agents: HashMap::from([("/work/a".to_string(), vec!["cursor".to_string()])]),
+            ..Default::default()
+        };
+        let imported = SkillDeckConfig {
+            projects: vec!["/work/a".to_string(), "/work/b".to_string()],
+            canonical_dir_override: Some("/old/skills".to_string()),
+            project_default_agents: HashMap::from([
+                ("/work/a".to_string(), vec!["codex".to_string()]),
+                ("/work/b".to_string(), vec!["codex".to_string()]),
+            ]),
+            gitlab_hosts: vec!["code.mycorp.io".to_string()],
+            disable_audit: true,
+            ..Default::default()
+        };
+
+        let merged = merge_config(local, imported);
+        assert_eq!(merged.projects, vec!["/work/a", "/work/b"]);
+        assert_eq!(merged.canonical_dir_override.as_deref(), Some("/data/skills"));
+        assert_eq!(merged.project_default_agents["/work/a"], vec!["cursor"]);
+        assert_eq!(merged.project_default_agents["/work/b"], vec!["codex"]);
+        assert_eq!(merged.gitlab_hosts, vec!["code.mycorp.io"]);
+        assert!(!merged.disable_audit);
+    }
+}
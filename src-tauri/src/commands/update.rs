@@ -1,23 +1,37 @@
 //! 更新检测相关的 Tauri Commands
 //!
 //! 提供命令：
-//! - check_updates: 检测指定 scope 的 skills 是否有更新
+//! - check_updates: 检测指定 scope 的 skills 是否有更新（GitHub / GitLab 来源，Bitbucket 报告为不支持）
+//! - check_updates_all: 一次检测 global 和 project 两个 scope 的更新
+//! - update_skill: 更新单个 skill
+//! - update_all: 批量更新有更新的 skills（同一仓库只克隆一次）
+//! - reinstall_skill: 按 lock 中记录的来源重新安装单个 skill（修复被损坏的文件）
 
-use crate::core::agents::AgentType;
-use crate::core::fetch_skill_folder_hash;
+use crate::commands::install::prepare_skills_dir;
+use crate::commands::operation::{Operation, OperationKind};
+use crate::core::agents::{AgentType, DetectionContext};
+use crate::core::clone_cache::CheckoutDir;
+use crate::core::git_hash::compute_git_tree_hash;
 use crate::core::local_lock::{
-    add_skill_to_local_lock, compute_skill_folder_hash, read_local_lock, LocalSkillLockEntry,
+    add_skill_to_local_lock, compute_skill_folder_hash, read_local_lock, recorded_source_url,
+    LocalSkillLockEntry,
 };
-use crate::core::skill_lock::{add_skill_to_lock, read_scoped_lock, SkillLockFile};
+use crate::core::paths::{canonical_skills_dir, same_path};
+use crate::core::skill::skill_dir_name;
+use crate::core::remote_hash::RemoteHashHost;
+use crate::core::skill_lock::{add_skill_to_lock, read_scoped_lock, SkillLockEntry, SkillLockFile};
 use crate::core::{
-    clone_repo_with_progress, discover_skills, install_skill_for_agent, parse_source,
-    CloneProgress, DiscoverOptions,
+    agent_skill_dir, discover_skills, install_skill_for_agent, installed_agent_modes, parse_source,
+    refresh_canonical_dir, stage_localized, update_skill_for_agent, write_canonical_dir, DiscoverOptions,
+    DiscoveredSkill,
 };
 use crate::error::AppError;
 use crate::models::{InstallMode, Scope};
 use serde::Serialize;
 use specta::Type;
 use std::collections::HashMap;
+use std::path::Path;
+use tauri::Emitter;
 
 /// 更新检测结果
 #[derive(Debug, Clone, Serialize, Type)]
@@ -26,30 +40,144 @@ use std::collections::HashMap;
 pub struct SkillUpdateInfo {
     pub name: String,
     pub source: String,
+    /// skill 所在的安装范围
+    pub scope: Scope,
     pub has_update: bool,
+    /// lock 中记录的已安装版本
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub current_version: Option<String>,
+    /// 远程 SKILL.md 中的版本（仅 has_update 时获取）
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub latest_version: Option<String>,
+    /// 来源平台尚不支持更新检测时的原因（此时 has_update 恒为 false）
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub unsupported_reason: Option<String>,
+}
+
+/// 批量更新中单个 skill 的结果
+#[derive(Debug, Clone, Serialize, Type)]
+#[serde(rename_all = "camelCase")]
+#[specta(rename_all = "camelCase")]
+pub struct SkillUpdateResult {
+    pub name: String,
+    pub source: String,
+    pub success: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+impl SkillUpdateResult {
+    fn failed(name: &str, source: &str, error: &AppError) -> Self {
+        Self {
+            name: name.to_string(),
+            source: source.to_string(),
+            success: false,
+            error: Some(error.to_string()),
+        }
+    }
+}
+
+/// 批量更新进度事件（发送到前端）
+#[derive(serde::Serialize, Clone)]
+struct UpdateAllProgress {
+    /// 当前阶段: "fetching" | "installing"
+    phase: String,
+    /// 当前处理的仓库 URL
+    source: String,
+    /// 当前正在处理的 skill 名称（fetching 阶段为空）
+    current_skill: String,
+    /// 已完成的 skill 数量
+    completed: usize,
+    /// 总 skill 数量
+    total: usize,
+}
+
+/// 待检测更新的 lock 条目
+struct UpdateCandidate {
+    scope: Scope,
+    source: String,
+    name: String,
+    skill_path: String,
+    /// lock 中记录的远程 hash（可能为空，如 CLI 写入的项目 lock）
+    local_hash: String,
+    /// 按已安装文件计算的 git tree SHA（仅 Project scope 的 GitHub 来源）
+    local_tree_hash: Option<String>,
+    version: Option<String>,
+    /// 托管平台；尚不支持更新检测的平台为 Err(原因)
+    host: Result<RemoteHashHost, &'static str>,
+}
+
+impl UpdateCandidate {
+    /// 远程 hash 相对已安装版本是否有更新
+    ///
+    /// 本地 tree SHA 相同说明内容完全一致；否则（安装时排除了部分文件、本地有修改等）
+    /// 回退到与 lock 中记录的远程 hash 比较。lock 中的 hash 为空（安装时获取失败）时
+    /// 无法判断，返回 `None` 而不是误报为有更新
+    fn has_update(&self, remote_hash: &str) -> Option<bool> {
+        if self.local_tree_hash.as_deref() == Some(remote_hash) {
+            return Some(false);
+        }
+        if self.local_hash.is_empty() {
+            return None;
+        }
+        Some(self.local_hash != remote_hash)
+    }
+}
+
+/// 远程 hash 查询结果
+#[derive(Clone)]
+enum RemoteHash {
+    Found(String),
+    /// API 调用成功但远程找不到对应文件夹
+    Missing,
+    /// API 调用失败
+    Failed,
 }
 
 /// 检测指定 scope 的 skills 是否有更新
 ///
 /// 流程：
 /// 1. 读取对应 scope 的 .skill-lock.json
-/// 2. 过滤出 sourceType == "github" 且有 skillFolderHash 和 skillPath 的 skills
-/// 3. 按 source 分组，对每组调用 GitHub Trees API
-/// 4. 比对本地 hash 与远程 hash
+/// 2. 过滤出支持的托管平台（github / gitlab）且有 skillFolderHash 和 skillPath 的 skills；
+///    Project scope 的 GitHub 来源额外按已安装文件计算 git tree SHA（lock 没有 remoteHash 时也可检测）；
+///    尚不支持的已知平台（bitbucket）保留下来，结果中带 unsupportedReason
+/// 3. 按 source 分组，对每组调用对应平台的 Tree API
+/// 4. 比对本地 hash 与远程 hash，有更新时读取远程 SKILL.md 的 version
 #[tauri::command]
 #[specta::specta]
 pub async fn check_updates(
     scope: Scope,
     project_path: Option<String>,
 ) -> Result<Vec<SkillUpdateInfo>, AppError> {
-    check_updates_inner(scope, project_path.as_deref()).await
+    let candidates = collect_update_candidates(scope, project_path.as_deref())?;
+    check_candidates(candidates).await
 }
 
-async fn check_updates_inner(
+/// 一次检测 global 和 project 两个 scope 的更新
+///
+/// 结果中每项带有 scope；两个 scope 中来自同一来源、同一路径的 skill 只请求一次远程 API。
+/// 未提供 project_path 时只检测 global。
+///
+/// # Arguments
+/// * `project_path` - 项目路径
+#[tauri::command]
+#[specta::specta]
+pub async fn check_updates_all(
+    project_path: Option<String>,
+) -> Result<Vec<SkillUpdateInfo>, AppError> {
+    let mut candidates = collect_update_candidates(Scope::Global, None)?;
+    if let Some(pp) = project_path.as_deref() {
+        candidates.extend(collect_update_candidates(Scope::Project, Some(pp))?);
+    }
+    check_candidates(candidates).await
+}
+
+/// 读取 lock 文件，收集可检测更新的 skills（步骤 1-2）
+fn collect_update_candidates(
     scope: Scope,
     project_path: Option<&str>,
-) -> Result<Vec<SkillUpdateInfo>, AppError> {
-    // 1-2. 根据 scope 读取对应的 lock 文件
+) -> Result<Vec<UpdateCandidate>, AppError> {
+    // 1. 根据 scope 读取对应的 lock 文件
     let lock = match scope {
         Scope::Global => read_scoped_lock(None)?,
         Scope::Project => {
@@ -60,15 +188,19 @@ async fn check_updates_inner(
                 for (name, entry) in local_lock.skills {
                     skills.insert(
                         name,
-                        crate::core::skill_lock::SkillLockEntry {
+                        SkillLockEntry {
+                            source_url: entry.source_url(),
                             source: entry.source,
                             source_type: entry.source_type,
-                            source_url: String::new(),
                             skill_path: entry.skill_path,
                             skill_folder_hash: entry.remote_hash.unwrap_or_default(),
                             installed_at: String::new(),
                             updated_at: String::new(),
                             plugin_name: entry.plugin_name,
+                            version: entry.version,
+                            dir_name: entry.dir_name,
+                            locale: entry.locale,
+                            detached_agents: entry.detached_agents,
                         },
                     );
                 }
@@ -84,54 +216,146 @@ async fn check_updates_inner(
         }
     };
 
-    // 3. 过滤并按 source 分组
-    // value: Vec<(skill_name, skill_path, local_hash)>
-    let mut skills_by_source: HashMap<String, Vec<(String, String, String)>> = HashMap::new();
+    // 2. 过滤
+    let mut candidates = Vec::new();
 
-    for (name, entry) in &lock.skills {
-        if entry.source_type != "github" {
-            continue;
-        }
-        if entry.skill_folder_hash.is_empty() {
-            continue;
-        }
-        let skill_path = match &entry.skill_path {
-            Some(p) if !p.is_empty() => p.clone(),
+    for (name, entry) in lock.skills {
+        let host = match RemoteHashHost::from_source(&entry.source_type, &entry.source_url) {
+            Some(h) => h,
+            None => {
+                // 已知但尚不支持的平台报告原因，其余来源（local、git 等）静默跳过
+                if let Some(reason) = RemoteHashHost::unsupported_reason(&entry.source_type) {
+                    candidates.push(UpdateCandidate {
+                        scope: scope.clone(),
+                        source: entry.source,
+                        name,
+                        skill_path: entry.skill_path.unwrap_or_default(),
+                        local_hash: entry.skill_folder_hash,
+                        local_tree_hash: None,
+                        version: entry.version,
+                        host: Err(reason),
+                    });
+                }
+                continue;
+            }
+        };
+        let skill_path = match entry.skill_path {
+            Some(p) if !p.is_empty() => p,
             _ => continue,
         };
+        let local_tree_hash = match (&scope, &host, project_path) {
+            (Scope::Project, RemoteHashHost::GitHub, Some(pp)) => {
+                // 目录不存在时计算失败，视为无法比较
+                let installed_dir = canonical_skills_dir(false, pp).join(skill_dir_name(&name, false, pp));
+                compute_git_tree_hash(&installed_dir).ok()
+            }
+            _ => None,
+        };
+        if entry.skill_folder_hash.is_empty() && local_tree_hash.is_none() {
+            continue;
+        }
 
-        skills_by_source
-            .entry(entry.source.clone())
-            .or_default()
-            .push((name.clone(), skill_path, entry.skill_folder_hash.clone()));
+        candidates.push(UpdateCandidate {
+            scope: scope.clone(),
+            source: entry.source,
+            name,
+            skill_path,
+            local_hash: entry.skill_folder_hash,
+            local_tree_hash,
+            version: entry.version,
+            host: Ok(host),
+        });
     }
 
-    // 4. 对每组 source 调用 GitHub Trees API
+    Ok(candidates)
+}
+
+/// 按 source 分组调用对应平台的 Tree API 并比对 hash（步骤 3-4）
+///
+/// 远程结果按 (source, skillPath, 平台) 缓存，重复条目不会重复请求
+async fn check_candidates(
+    mut candidates: Vec<UpdateCandidate>,
+) -> Result<Vec<SkillUpdateInfo>, AppError> {
+    // 3. 按 source 分组（稳定排序，保持 scope 的先后顺序）
+    candidates.sort_by(|a, b| a.source.cmp(&b.source));
+
+    let mut remote_hashes: HashMap<(String, String, RemoteHashHost), RemoteHash> = HashMap::new();
+    let mut latest_versions: HashMap<(String, String, RemoteHashHost), Option<String>> =
+        HashMap::new();
     let mut results = Vec::new();
 
-    for (source, skills) in &skills_by_source {
-        for (name, skill_path, local_hash) in skills {
-            match fetch_skill_folder_hash(source, skill_path, None).await {
-                Ok(Some(remote_hash)) => {
-                    results.push(SkillUpdateInfo {
-                        name: name.clone(),
-                        source: source.clone(),
-                        has_update: remote_hash != *local_hash,
-                    });
-                }
-                Ok(None) => {
-                    // 远程找不到，不误报
-                    results.push(SkillUpdateInfo {
-                        name: name.clone(),
-                        source: source.clone(),
-                        has_update: false,
-                    });
-                }
-                Err(_) => {
-                    // API 失败，静默跳过
-                }
+    // 4. 比对本地与远程 hash
+    for skill in candidates {
+        let host = match &skill.host {
+            Ok(host) => host.clone(),
+            Err(reason) => {
+                results.push(SkillUpdateInfo {
+                    name: skill.name,
+                    source: skill.source,
+                    scope: skill.scope,
+                    has_update: false,
+                    current_version: skill.version,
+                    latest_version: None,
+                    unsupported_reason: Some(reason.to_string()),
+                });
+                continue;
             }
-        }
+        };
+        let key = (skill.source.clone(), skill.skill_path.clone(), host.clone());
+
+        let remote = match remote_hashes.get(&key) {
+            Some(cached) => cached.clone(),
+            None => {
+                let fetched = match host
+                    .fetch_folder_hash(&skill.source, &skill.skill_path, None)
+                    .await
+                {
+                    Ok(Some(hash)) => RemoteHash::Found(hash),
+                    Ok(None) => RemoteHash::Missing,
+                    Err(e @ AppError::GitHubRateLimited { .. }) => {
+                        // 限流后剩余请求也必然失败，直接上报而不是静默返回不完整结果
+                        return Err(e);
+                    }
+                    Err(_) => RemoteHash::Failed,
+                };
+                remote_hashes.insert(key.clone(), fetched.clone());
+                fetched
+            }
+        };
+
+        let (has_update, latest_version) = match remote {
+            RemoteHash::Found(remote_hash) if skill.has_update(&remote_hash) == Some(true) => {
+                // 版本号仅用于展示，获取失败不影响更新检测
+                let latest_version = match latest_versions.get(&key) {
+                    Some(cached) => cached.clone(),
+                    None => {
+                        let version = host
+                            .fetch_skill_version(&skill.source, &skill.skill_path, None)
+                            .await
+                            .unwrap_or(None);
+                        latest_versions.insert(key, version.clone());
+                        version
+                    }
+                };
+                (true, latest_version)
+            }
+            // 已是最新，或 lock 中没有可比较的 hash（视为未知，不误报）
+            RemoteHash::Found(_) => (false, skill.version.clone()),
+            // 远程找不到，不误报
+            RemoteHash::Missing => (false, None),
+            // API 失败，静默跳过
+            RemoteHash::Failed => continue,
+        };
+
+        results.push(SkillUpdateInfo {
+            name: skill.name,
+            source: skill.source,
+            scope: skill.scope,
+            has_update,
+            current_version: skill.version,
+            latest_version,
+            unsupported_reason: None,
+        });
     }
 
     Ok(results)
@@ -141,6 +365,9 @@ async fn check_updates_inner(
 ///
 /// 本质是"重新安装"：从 lock 文件读取来源信息，构造安装 URL，复用安装逻辑。
 /// 与 CLI update 命令行为一致。
+///
+/// # Arguments
+/// * `op_id` - 前端指定的操作 id（用于 operation-event、clone-progress 和 cancel_clone），未传时自动生成
 #[tauri::command]
 #[specta::specta]
 pub async fn update_skill(
@@ -148,8 +375,11 @@ pub async fn update_skill(
     scope: Scope,
     name: String,
     project_path: Option<String>,
+    op_id: Option<String>,
 ) -> Result<(), AppError> {
-    update_skill_inner(&app, scope, &name, project_path.as_deref()).await
+    let op = Operation::start(&app, OperationKind::Update, op_id);
+    let result = update_skill_inner(&app, scope, &name, project_path.as_deref(), op.id()).await;
+    op.finish(result, |_| format!("Updated {}", name))
 }
 
 async fn update_skill_inner(
@@ -157,23 +387,375 @@ async fn update_skill_inner(
     scope: Scope,
     skill_name: &str,
     project_path: Option<&str>,
+    op_id: &str,
 ) -> Result<(), AppError> {
-    use tauri::Emitter;
+    // 1. 根据 scope 读取 lock 中记录的来源信息
+    let locked = read_locked_source(&scope, skill_name, project_path)?;
 
-    // 1. 根据 scope 读取对应的 lock 文件
-    let (entry_source, entry_source_type, entry_source_url, entry_skill_path, entry_plugin_name) = match scope {
+    // 2-6. 重新获取来源并找到目标 skill（临时目录在函数结束前保持存活）
+    let (skill, _temp_dir) = fetch_locked_skill(app, &locked, skill_name, Some(op_id)).await?;
+
+    // 7-9. 重新安装并更新 lock
+    install_fetched_skill(&skill, &locked, &scope, skill_name, project_path, false).await
+}
+
+/// 按 lock 中记录的来源重新安装 skill（"从来源修复"）
+///
+/// 与 update_skill 一样重新获取来源，但不论是否有更新，都整目录替换 canonical 目录和
+/// 各 agent 的安装位置（而非增量同步），用于修复本地被损坏或误改的文件。
+/// 除已安装的 agents 外，还会安装到该 scope 下当前检测到的 agents（含 Universal Agents）。
+/// 完成后刷新 lock 中的 updatedAt 和 hash。
+///
+/// # Arguments
+/// * `scope` - skill 所在范围
+/// * `name` - skill 名称（lock 中的 key）
+/// * `project_path` - Project scope 时的项目路径
+/// * `op_id` - 前端指定的操作 id（用于 operation-event、clone-progress 和 cancel_clone），未传时自动生成
+#[tauri::command]
+#[specta::specta]
+pub async fn reinstall_skill(
+    app: tauri::AppHandle,
+    scope: Scope,
+    name: String,
+    project_path: Option<String>,
+    op_id: Option<String>,
+) -> Result<(), AppError> {
+    let op = Operation::start(&app, OperationKind::Reinstall, op_id);
+    let result = reinstall_skill_inner(&app, scope, &name, project_path.as_deref(), op.id()).await;
+    op.finish(result, |_| format!("Reinstalled {}", name))
+}
+
+async fn reinstall_skill_inner(
+    app: &tauri::AppHandle,
+    scope: Scope,
+    skill_name: &str,
+    project_path: Option<&str>,
+    op_id: &str,
+) -> Result<(), AppError> {
+    let locked = read_locked_source(&scope, skill_name, project_path)?;
+    let (skill, _temp_dir) = fetch_locked_skill(app, &locked, skill_name, Some(op_id)).await?;
+    install_fetched_skill(&skill, &locked, &scope, skill_name, project_path, true).await
+}
+
+/// 批量更新：检测指定 scope 中所有有更新的 skills 并逐一更新
+///
+/// 按仓库分组，每个仓库只克隆一次（仓库级 URL，默认分支），再从同一份 checkout
+/// 中按 skillPath 找到各个 skill。过程中发送 `update-all-progress` 和 `operation-event` 事件。
+/// 单个 skill 或单个仓库失败不影响其他 skill，取消克隆时整体返回 `AppError::Cancelled`。
+///
+/// # Arguments
+/// * `scope` - 更新范围
+/// * `project_path` - Project scope 时的项目路径
+/// * `op_id` - 前端指定的操作 id（用于 operation-event、clone-progress 和 cancel_clone），未传时自动生成
+///
+/// # Returns
+/// * 每个待更新 skill 的结果
+#[tauri::command]
+#[specta::specta]
+pub async fn update_all(
+    app: tauri::AppHandle,
+    scope: Scope,
+    project_path: Option<String>,
+    op_id: Option<String>,
+) -> Result<Vec<SkillUpdateResult>, AppError> {
+    let op = Operation::start(&app, OperationKind::UpdateAll, op_id);
+    let result = update_all_inner(&app, scope, project_path.as_deref(), &op).await;
+    op.finish(result, |results| {
+        let updated = results.iter().filter(|r| r.success).count();
+        format!("{} updated, {} failed", updated, results.len() - updated)
+    })
+}
+
+async fn update_all_inner(
+    app: &tauri::AppHandle,
+    scope: Scope,
+    project_path: Option<&str>,
+    op: &Operation,
+) -> Result<Vec<SkillUpdateResult>, AppError> {
+    // 1. 检测有更新的 skills
+    let candidates = collect_update_candidates(scope.clone(), project_path)?;
+    let updates = check_candidates(candidates).await?;
+
+    // 2. 按仓库分组（保持检测结果的顺序）
+    let mut results = Vec::new();
+    let mut groups: Vec<(String, Vec<(String, LockedSource)>)> = Vec::new();
+    for update in updates.into_iter().filter(|u| u.has_update) {
+        let locked = match read_locked_source(&scope, &update.name, project_path) {
+            Ok(locked) => locked,
+            Err(e) => {
+                results.push(SkillUpdateResult::failed(&update.name, &update.source, &e));
+                continue;
+            }
+        };
+        match groups.iter_mut().find(|(url, _)| *url == locked.source_url) {
+            Some((_, skills)) => skills.push((update.name, locked)),
+            None => groups.push((locked.source_url.clone(), vec![(update.name, locked)])),
+        }
+    }
+
+    let total: usize = groups.iter().map(|(_, skills)| skills.len()).sum();
+    let mut completed = 0;
+    let emit_progress = |phase: &str, source: &str, current_skill: &str, completed: usize| {
+        let _ = app.emit("update-all-progress", &UpdateAllProgress {
+            phase: phase.to_string(),
+            source: source.to_string(),
+            current_skill: current_skill.to_string(),
+            completed,
+            total,
+        });
+        op.progress(phase, completed, total);
+    };
+
+    // 3. 每个仓库克隆一次，依次更新其中的 skills
+    for (source_url, skills) in &groups {
+        emit_progress("fetching", source_url, "", completed);
+
+        let checkout = match parse_source(source_url) {
+            Ok(parsed) => prepare_skills_dir(app, &parsed, Some(op.id())).await,
+            Err(e) => Err(e),
+        };
+        let (skills_dir, _temp_dir) = match checkout {
+            Ok(checkout) => checkout,
+            Err(e @ AppError::Cancelled) => return Err(e),
+            Err(e) => {
+                for (name, locked) in skills {
+                    results.push(SkillUpdateResult::failed(name, &locked.source, &e));
+                }
+                completed += skills.len();
+                continue;
+            }
+        };
+
+        for (name, locked) in skills {
+            emit_progress("installing", source_url, name, completed);
+
+            let subpath = locked
+                .skill_path
+                .as_deref()
+                .map(skill_folder_from_path)
+                .filter(|f| !f.is_empty());
+            let outcome = match find_skill(&skills_dir, subpath.as_deref(), name) {
+                Ok(skill) => install_fetched_skill(&skill, locked, &scope, name, project_path, false).await,
+                Err(e) => Err(e),
+            };
+            results.push(match outcome {
+                Ok(()) => SkillUpdateResult {
+                    name: name.clone(),
+                    source: locked.source.clone(),
+                    success: true,
+                    error: None,
+                },
+                Err(e) => SkillUpdateResult::failed(name, &locked.source, &e),
+            });
+            completed += 1;
+        }
+    }
+
+    Ok(results)
+}
+
+/// 用获取到的新内容重新安装 skill，并更新 lock 中的 hash 和 version
+///
+/// 写入已安装该 skill 的 agents，并沿用各自的安装方式（Symlink / Copy），
+/// 不会把独立副本替换为 symlink；已脱离（detach_skill）且仍是独立副本的 agents 保持不动。`force` 为 true 时整目录替换，
+/// 并追加当前检测到的 agents（reinstall_skill）；否则增量同步且不新增安装位置（update）。
+/// 任一 agent 失败或没有任何已安装位置时返回错误且不更新 lock。
+async fn install_fetched_skill(
+    skill: &DiscoveredSkill,
+    locked: &LockedSource,
+    scope: &Scope,
+    skill_name: &str,
+    project_path: Option<&str>,
+    force: bool,
+) -> Result<(), AppError> {
+    // 7. 找出已安装的 agents 及各自的安装方式（跳过已脱离的独立副本）
+    //    reinstall 还会写入当前检测到的 agents（含 Universal Agents），见 `reinstall_targets`
+    let mut installed: Vec<(AgentType, InstallMode)> = installed_agent_modes(skill_name, scope, project_path)
+        .into_iter()
+        .filter(|(agent, mode)| {
+            !(*mode == InstallMode::Copy && locked.detached_agents.contains(&agent.to_string()))
+        })
+        .collect();
+    if force {
+        reinstall_targets(&mut installed, locked, scope, skill_name, project_path);
+    }
+
+    // 沿用安装时选择的本地化版本（新版本不再提供时回退到原 SKILL.md）
+    let localized = match locked.locale.as_deref() {
+        Some(locale) => stage_localized(&skill.path, locale)?,
+        None => None,
+    };
+    let source_path = localized.as_ref().map_or(skill.path.as_path(), |(_, staging)| staging.path());
+    let locale = localized.as_ref().map(|(locale, _)| locale.clone());
+
+    // 8. 执行安装（update 增量同步现有文件，reinstall 整目录替换）
+    //    Hardlink agent 链接到 canonical 目录，需先写入一次；
+    //    没有 Symlink agent 时 canonical 目录不会被写入，需单独刷新
+    let mut refreshed = false;
+    if installed.iter().any(|(_, mode)| *mode == InstallMode::Hardlink) {
+        let is_global = matches!(scope, Scope::Global);
+        let dir_name = skill_dir_name(skill_name, is_global, project_path.unwrap_or("."));
+        write_canonical_dir(source_path, &dir_name, scope, project_path, !force)?;
+        refreshed = true;
+    } else if !installed.iter().any(|(_, mode)| *mode == InstallMode::Symlink) {
+        refreshed = refresh_canonical_dir(source_path, skill_name, scope, project_path, !force)?;
+    }
+    if installed.is_empty() && !refreshed {
+        return Err(AppError::InstallFailed {
+            message: format!("Skill '{}' is not installed for any agent", skill_name),
+        });
+    }
+
+    let install = if force { install_skill_for_agent } else { update_skill_for_agent };
+    let failures: Vec<String> = installed
+        .iter()
+        .map(|(agent, mode)| install(source_path, skill_name, agent, scope, project_path, mode))
+        .filter(|result| !result.success)
+        .map(|result| format!("{}: {}", result.agent, result.error.unwrap_or_default()))
+        .collect();
+    if !failures.is_empty() {
+        return Err(AppError::InstallFailed {
+            message: failures.join("; "),
+        });
+    }
+
+    // 9. 更新 lock 文件（获取新的 hash，获取失败时保留原有 hash）
+    let fetched_hash = match RemoteHashHost::from_source(&locked.source_type, &locked.source_url) {
+        Some(host) => host
+            .fetch_folder_hash(&locked.source, locked.skill_path.as_deref().unwrap_or(""), None)
+            .await
+            .unwrap_or(None),
+        None => None,
+    };
+    let new_hash = fetched_hash.or_else(|| locked.folder_hash.clone());
+
+    match scope {
+        Scope::Global => {
+            let entry = SkillLockEntry {
+                source: locked.source.clone(),
+                source_type: locked.source_type.clone(),
+                source_url: locked.source_url.clone(),
+                skill_path: locked.skill_path.clone(),
+                skill_folder_hash: new_hash.unwrap_or_default(),
+                installed_at: String::new(),
+                updated_at: String::new(),
+                plugin_name: locked.plugin_name.clone(),
+                version: skill.version.clone(),
+                dir_name: locked.dir_name.clone(),
+                locale,
+                detached_agents: locked.detached_agents.clone(),
+            };
+            add_skill_to_lock(skill_name, entry)?;
+        }
+        Scope::Project => {
+            if let Some(pp) = project_path {
+                let install_dir = crate::core::paths::canonical_skills_dir(false, pp)
+                    .join(skill_dir_name(skill_name, false, pp));
+                let computed_hash = compute_skill_folder_hash(&install_dir).unwrap_or_default();
+                let entry = LocalSkillLockEntry {
+                    source: locked.source.clone(),
+                    source_type: locked.source_type.clone(),
+                    computed_hash,
+                    remote_hash: new_hash,
+                    skill_path: locked.skill_path.clone(),
+                    plugin_name: locked.plugin_name.clone(),
+                    version: skill.version.clone(),
+                    installed_at: None,
+                    updated_at: None,
+                    dir_name: locked.dir_name.clone(),
+                    locale,
+                    detached_agents: locked.detached_agents.clone(),
+                    source_url: recorded_source_url(&locked.source, &locked.source_type, &locked.source_url),
+                };
+                add_skill_to_local_lock(skill_name, entry, pp)?;
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// 追加 reinstall 的目标 agents：该 scope 下检测到的 agents 和 Universal Agents
+///
+/// 已安装的 agents 保留原安装方式，新增的 agents 使用 Symlink；
+/// 跳过已脱离的 agents、不支持该 scope 的 agents，以及与已有目标共用安装位置的 agents
+fn reinstall_targets(
+    targets: &mut Vec<(AgentType, InstallMode)>,
+    locked: &LockedSource,
+    scope: &Scope,
+    skill_name: &str,
+    project_path: Option<&str>,
+) {
+    let is_global = matches!(scope, Scope::Global);
+    let cwd = project_path.unwrap_or(".");
+    let detected = AgentType::detect_installed(&DetectionContext::for_scope(is_global, cwd));
+
+    let mut locations: Vec<_> = targets
+        .iter()
+        .filter_map(|(agent, _)| agent_skill_dir(skill_name, agent, scope, project_path))
+        .collect();
+    for agent in detected.into_iter().chain(AgentType::get_universal_agents()) {
+        if locked.detached_agents.contains(&agent.to_string()) {
+            continue;
+        }
+        let Some(dir) = agent_skill_dir(skill_name, &agent, scope, project_path) else {
+            continue;
+        };
+        if locations.iter().any(|existing| same_path(existing, &dir)) {
+            continue;
+        }
+        locations.push(dir);
+        targets.push((agent, InstallMode::Symlink));
+    }
+}
+
+/// lock 中记录的 skill 来源信息
+pub(crate) struct LockedSource {
+    pub source: String,
+    pub source_type: String,
+    pub source_url: String,
+    pub skill_path: Option<String>,
+    pub plugin_name: Option<String>,
+    /// 安装目录名（仅在与 sanitize_name 不同时记录）
+    pub dir_name: Option<String>,
+    /// 安装时 skill 目录的 tree hash（global skillFolderHash / 项目 remoteHash）
+    pub folder_hash: Option<String>,
+    /// 最近一次更新（或安装）时间
+    pub updated_at: Option<String>,
+    /// 安装的本地化版本
+    pub locale: Option<String>,
+    /// 已脱离 canonical 目录的 agents
+    pub detached_agents: Vec<String>,
+}
+
+/// 根据 scope 从对应的 lock 文件读取 skill 的来源信息
+pub(crate) fn read_locked_source(
+    scope: &Scope,
+    skill_name: &str,
+    project_path: Option<&str>,
+) -> Result<LockedSource, AppError> {
+    match scope {
         Scope::Global => {
             let lock = read_scoped_lock(None)?;
             let entry = lock.skills.get(skill_name).ok_or_else(|| AppError::InvalidSource {
                 value: format!("Skill '{}' not found in lock file", skill_name),
             })?;
-            (
-                entry.source.clone(),
-                entry.source_type.clone(),
-                entry.source_url.clone(),
-                entry.skill_path.clone(),
-                entry.plugin_name.clone(),
-            )
+            let updated_at = [&entry.updated_at, &entry.installed_at]
+                .into_iter()
+                .find(|t| !t.is_empty())
+                .cloned();
+            Ok(LockedSource {
+                source: entry.source.clone(),
+                source_type: entry.source_type.clone(),
+                source_url: entry.source_url.clone(),
+                skill_path: entry.skill_path.clone(),
+                plugin_name: entry.plugin_name.clone(),
+                dir_name: entry.dir_name.clone(),
+                folder_hash: Some(entry.skill_folder_hash.clone()).filter(|h| !h.is_empty()),
+                updated_at,
+                locale: entry.locale.clone(),
+                detached_agents: entry.detached_agents.clone(),
+            })
         }
         Scope::Project => {
             if let Some(pp) = project_path {
@@ -188,155 +770,80 @@ async fn update_skill_inner(
                                 skill_name
                             ),
                         })?;
-                // local lock 没有 source_url，从 source 构造
-                let source_url = if entry.source_type == "github" {
-                    format!("https://github.com/{}", entry.source)
-                } else {
-                    entry.source.clone()
-                };
-                (
-                    entry.source.clone(),
-                    entry.source_type.clone(),
-                    source_url,
-                    entry.skill_path.clone(),
-                    entry.plugin_name.clone(),
-                )
+                Ok(LockedSource {
+                    source: entry.source.clone(),
+                    source_type: entry.source_type.clone(),
+                    source_url: entry.source_url(),
+                    skill_path: entry.skill_path.clone(),
+                    plugin_name: entry.plugin_name.clone(),
+                    dir_name: entry.dir_name.clone(),
+                    folder_hash: entry.remote_hash.clone(),
+                    updated_at: entry.updated_at.clone().or_else(|| entry.installed_at.clone()),
+                    locale: entry.locale.clone(),
+                    detached_agents: entry.detached_agents.clone(),
+                })
             } else {
-                return Err(AppError::InvalidSource {
+                Err(AppError::InvalidSource {
                     value: "Project path is required for project scope".to_string(),
-                });
+                })
             }
         }
-    };
+    }
+}
 
-    // 2. 构造安装 URL（与 CLI runUpdate 逻辑一致）
+/// 按 lock 中的来源重新获取 skill
+///
+/// 返回的 CheckoutDir 需要在使用 skill 目录期间保持存活；`op_id` 见 `prepare_skills_dir`
+pub(crate) async fn fetch_locked_skill(
+    app: &tauri::AppHandle,
+    locked: &LockedSource,
+    skill_name: &str,
+    op_id: Option<&str>,
+) -> Result<(DiscoveredSkill, Option<CheckoutDir>), AppError> {
+    // 构造安装 URL（与 CLI runUpdate 逻辑一致）
     let install_url = build_install_url_from_parts(
-        &entry_source_url,
-        entry_skill_path.as_deref(),
+        &locked.source_url,
+        &locked.source_type,
+        locked.skill_path.as_deref(),
     );
 
-    // 3. 解析来源
+    // 解析来源
     let parsed = parse_source(&install_url)?;
 
-    // 4. 克隆仓库
-    let app_clone = app.clone();
-    let clone_result = clone_repo_with_progress(
-        &parsed.url,
-        parsed.git_ref.as_deref(),
-        move |progress: CloneProgress| {
-            let _ = app_clone.emit("clone-progress", &progress);
-        },
-    )?;
-
-    // 5. 发现 skills
+    // 克隆仓库或重新下载（direct-url / well-known）
+    let (skills_dir, temp_dir) = prepare_skills_dir(app, &parsed, op_id).await?;
+
+    let skill = find_skill(&skills_dir, parsed.subpath.as_deref(), skill_name)?;
+    Ok((skill, temp_dir))
+}
+
+/// 在已获取的 skills 目录中发现并找到目标 skill
+fn find_skill(
+    skills_dir: &Path,
+    subpath: Option<&str>,
+    skill_name: &str,
+) -> Result<DiscoveredSkill, AppError> {
     let options = DiscoverOptions {
         include_internal: true,
         full_depth: false,
+        max_depth: None,
     };
-    let discovered = discover_skills(&clone_result.repo_path, parsed.subpath.as_deref(), options)?;
-
-    // 6. 找到目标 skill
-    let skill = discovered
-        .iter()
+    discover_skills(skills_dir, subpath, options)?
+        .into_iter()
         .find(|s| s.name == skill_name)
-        .ok_or_else(|| AppError::NoSkillsFound)?;
-
-    // 7. 检测已安装的 agents + universal agents
-    let mut target_agents = AgentType::detect_installed();
-    let universal_agents = AgentType::get_universal_agents();
-    for ua in universal_agents {
-        if !target_agents.contains(&ua) {
-            target_agents.push(ua);
-        }
-    }
-
-    // 8. 执行安装（覆盖现有文件）
-    let install_scope = match scope {
-        Scope::Global => crate::models::Scope::Global,
-        Scope::Project => crate::models::Scope::Project,
-    };
-    for agent in &target_agents {
-        let _ = install_skill_for_agent(
-            &skill.path,
-            &skill.name,
-            agent,
-            &install_scope,
-            project_path,
-            &InstallMode::Symlink,
-        );
-    }
-
-    // 9. 更新 lock 文件（获取新的 hash）
-    let new_hash = if entry_source_type == "github" {
-        fetch_skill_folder_hash(
-            &entry_source,
-            entry_skill_path.as_deref().unwrap_or(""),
-            None,
-        )
-        .await
-        .unwrap_or(None)
-        .unwrap_or_default()
-    } else {
-        String::new()
-    };
-
-    match scope {
-        Scope::Global => {
-            let _ = add_skill_to_lock(
-                skill_name,
-                &entry_source,
-                &entry_source_type,
-                &entry_source_url,
-                entry_skill_path.as_deref(),
-                &new_hash,
-                entry_plugin_name.as_deref(),
-            );
-        }
-        Scope::Project => {
-            if let Some(pp) = project_path {
-                let install_dir = crate::core::paths::canonical_skills_dir(false, pp)
-                    .join(crate::core::skill::sanitize_name(skill_name));
-                let computed_hash = compute_skill_folder_hash(&install_dir).unwrap_or_default();
-                let entry = LocalSkillLockEntry {
-                    source: entry_source.clone(),
-                    source_type: entry_source_type.clone(),
-                    computed_hash,
-                    remote_hash: if new_hash.is_empty() {
-                        None
-                    } else {
-                        Some(new_hash.clone())
-                    },
-                    skill_path: entry_skill_path.clone(),
-                    plugin_name: entry_plugin_name.clone(),
-                };
-                let _ = add_skill_to_local_lock(skill_name, entry, pp);
-            }
-        }
-    }
-
-    Ok(())
+        .ok_or(AppError::NoSkillsFound)
 }
 
 /// 从来源信息构造安装 URL
 ///
 /// 与 CLI cli.ts runUpdate() 中构造 installUrl 的逻辑一致：
 /// 1. 基础 URL = source_url
-/// 2. 如果有 skillPath，去掉 SKILL.md 后缀，拼接为 GitHub tree URL
-fn build_install_url_from_parts(source_url: &str, skill_path: Option<&str>) -> String {
+/// 2. 如果有 skillPath，去掉 SKILL.md 后缀，拼接为 tree URL（GitLab 来源使用 `/-/tree/` 格式）
+fn build_install_url_from_parts(source_url: &str, source_type: &str, skill_path: Option<&str>) -> String {
     let mut install_url = source_url.to_string();
 
     if let Some(sp) = skill_path {
-        let mut skill_folder = sp.to_string();
-
-        // 去掉 /SKILL.md 或 SKILL.md 后缀
-        if skill_folder.ends_with("/SKILL.md") {
-            skill_folder = skill_folder[..skill_folder.len() - 9].to_string();
-        } else if skill_folder.ends_with("SKILL.md") {
-            skill_folder = skill_folder[..skill_folder.len() - 8].to_string();
-        }
-
-        // 去掉尾部斜杠
-        skill_folder = skill_folder.trim_end_matches('/').to_string();
+        let skill_folder = skill_folder_from_path(sp);
 
         if !skill_folder.is_empty() {
             // 去掉 sourceUrl 的 .git 后缀和尾部斜杠
@@ -345,10 +852,413 @@ fn build_install_url_from_parts(source_url: &str, skill_path: Option<&str>) -> S
                 .trim_end_matches('/')
                 .to_string();
 
-            // 拼接 GitHub tree URL（硬编码 main 分支，与 CLI 一致）
-            install_url = format!("{}/tree/main/{}", install_url, skill_folder);
+            // 拼接 tree URL（硬编码 main 分支，与 CLI 一致）
+            let tree = if source_type == "gitlab" { "-/tree" } else { "tree" };
+            install_url = format!("{}/{}/main/{}", install_url, tree, skill_folder);
         }
     }
 
     install_url
 }
+
+/// 从 lock 中的 skillPath 得到 skill 文件夹（仓库内相对路径，根目录为空串）
+pub(crate) fn skill_folder_from_path(skill_path: &str) -> String {
+    // 去掉 /SKILL.md 或 SKILL.md 后缀
+    let folder = skill_path
+        .strip_suffix("/SKILL.md")
+        .or_else(|| skill_path.strip_suffix("SKILL.md"))
+        .unwrap_or(skill_path);
+
+    // 去掉尾部斜杠
+    folder.trim_end_matches('/').to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::SourceType;
+    use crate::core::mock_http::{self, MockServer};
+
+    fn candidate(scope: Scope, local_hash: &str, api_base: &str) -> UpdateCandidate {
+        UpdateCandidate {
+            scope,
+            source: "group/repo".to_string(),
+            name: "demo".to_string(),
+            skill_path: "skills/demo/SKILL.md".to_string(),
+            local_hash: local_hash.to_string(),
+            local_tree_hash: None,
+            version: Some("1.0.0".to_string()),
+            host: Ok(RemoteHashHost::GitLab {
+                api_base: api_base.to_string(),
+            }),
+        }
+    }
+
+    #[test]
+    fn test_check_candidates_shares_remote_lookup_across_scopes() {
+        // 服务只接受一次连接：第二个 scope 若重复请求会失败并被跳过
+        let server = MockServer::start(vec![mock_http::response(
+            "200 OK",
+            "Content-Type: application/json\r\nX-Next-Page: \r\n",
+            r#"[{"id":"abc","name":"demo","type":"tree","path":"skills/demo","mode":"040000"}]"#,
+        )]);
+        let base = format!("{}/api/v4", server.url);
+        let candidates = vec![
+            candidate(Scope::Global, "abc", &base),
+            candidate(Scope::Project, "abc", &base),
+        ];
+
+        let results = tauri::async_runtime::block_on(check_candidates(candidates)).unwrap();
+
+        assert_eq!(results.len(), 2);
+        assert_eq!(results[0].scope, Scope::Global);
+        assert_eq!(results[1].scope, Scope::Project);
+        assert!(results.iter().all(|r| !r.has_update));
+        assert_eq!(results[0].latest_version.as_deref(), Some("1.0.0"));
+        server.next_request();
+        assert!(server.no_requests());
+    }
+
+    #[test]
+    fn test_check_candidates_reports_unsupported_host() {
+        // 不支持的平台不发起请求，直接报告原因
+        let mut skill = candidate(Scope::Global, "abc", "http://127.0.0.1:9/api/v4");
+        skill.host = Err("unsupported");
+
+        let results = tauri::async_runtime::block_on(check_candidates(vec![skill])).unwrap();
+
+        assert_eq!(results.len(), 1);
+        assert!(!results[0].has_update);
+        assert_eq!(results[0].unsupported_reason.as_deref(), Some("unsupported"));
+    }
+
+    #[test]
+    fn test_has_update_prefers_local_tree_hash() {
+        let mut skill = candidate(Scope::Project, "", "http://localhost/api/v4");
+        skill.local_tree_hash = Some("tree-local".to_string());
+        // lock 没有 remoteHash：tree SHA 相同即为最新，不同时无法判断
+        assert_eq!(skill.has_update("tree-local"), Some(false));
+        assert_eq!(skill.has_update("tree-remote"), None);
+
+        // 本地 tree SHA 不同（如安装时排除了文件）时回退到 lock 中的远程 hash
+        skill.local_hash = "tree-remote".to_string();
+        assert_eq!(skill.has_update("tree-remote"), Some(false));
+        assert_eq!(skill.has_update("tree-newer"), Some(true));
+    }
+
+    #[test]
+    fn test_empty_stored_hash_is_not_an_update() {
+        // 安装时 hash 获取失败写入的空 hash 不应被永久报告为有更新
+        let skill = candidate(Scope::Global, "", "http://localhost/api/v4");
+        assert_eq!(skill.has_update("tree-remote"), None);
+    }
+
+    #[test]
+    fn test_skill_folder_from_path() {
+        assert_eq!(skill_folder_from_path("skills/demo/SKILL.md"), "skills/demo");
+        assert_eq!(skill_folder_from_path("SKILL.md"), "");
+        assert_eq!(skill_folder_from_path("skills/demo/"), "skills/demo");
+        assert_eq!(
+            build_install_url_from_parts(
+                "https://github.com/owner/repo.git",
+                "github",
+                Some("skills/demo/SKILL.md")
+            ),
+            "https://github.com/owner/repo/tree/main/skills/demo"
+        );
+    }
+
+    #[test]
+    fn test_build_install_url_for_gitlab_subfolder() {
+        let install_url = build_install_url_from_parts(
+            "https://gitlab.com/group/sub/repo.git",
+            "gitlab",
+            Some("skills/x/SKILL.md"),
+        );
+        assert_eq!(install_url, "https://gitlab.com/group/sub/repo/-/tree/main/skills/x");
+
+        // 解析后仍为 GitLab 仓库，子路径指向 skill 目录
+        let parsed = parse_source(&install_url).unwrap();
+        assert_eq!(parsed.source_type, SourceType::GitLab);
+        assert_eq!(parsed.url, "https://gitlab.com/group/sub/repo");
+        assert_eq!(parsed.git_ref.as_deref(), Some("main"));
+        assert_eq!(parsed.subpath.as_deref(), Some("skills/x"));
+
+        // 根目录 skill 不拼接 tree
+        assert_eq!(
+            build_install_url_from_parts("https://gitlab.com/group/repo", "gitlab", Some("SKILL.md")),
+            "https://gitlab.com/group/repo"
+        );
+    }
+
+    #[test]
+    fn test_find_skill_in_shared_checkout() {
+        let repo = tempfile::tempdir().unwrap();
+        for name in ["alpha", "beta"] {
+            let dir = repo.path().join("skills").join(name);
+            std::fs::create_dir_all(&dir).unwrap();
+            std::fs::write(
+                dir.join("SKILL.md"),
+                format!("---\nname: {}\ndescription: Test\n---\n", name),
+            )
+            .unwrap();
+        }
+
+        // 同一份 checkout 中按各自的 skillPath 定位
+        let alpha = find_skill(repo.path(), Some("skills/alpha"), "alpha").unwrap();
+        let beta = find_skill(repo.path(), Some("skills/beta"), "beta").unwrap();
+        assert!(alpha.path.ends_with("skills/alpha"));
+        assert!(beta.path.ends_with("skills/beta"));
+
+        assert!(matches!(
+            find_skill(repo.path(), Some("skills/alpha"), "beta"),
+            Err(AppError::NoSkillsFound)
+        ));
+    }
+
+    #[test]
+    fn test_reinstall_restores_corrupted_files() {
+        use crate::core::local_lock::update_local_lock;
+
+        let src = tempfile::tempdir().unwrap();
+        std::fs::write(src.path().join("SKILL.md"), "---\nname: demo\ndescription: Test\n---\n").unwrap();
+        std::fs::write(src.path().join("notes.md"), "original").unwrap();
+        let project = tempfile::tempdir().unwrap();
+        let pp = project.path().to_string_lossy().to_string();
+
+        let result = install_skill_for_agent(
+            src.path(),
+            "demo",
+            &crate::core::agents::AgentType::ClaudeCode,
+            &Scope::Project,
+            Some(&pp),
+            &InstallMode::Symlink,
+        );
+        assert!(result.success, "{:?}", result.error);
+        let entry = LocalSkillLockEntry {
+            source: src.path().to_string_lossy().to_string(),
+            source_type: "local".to_string(),
+            remote_hash: Some("tree-1".to_string()),
+            ..Default::default()
+        };
+        add_skill_to_local_lock("demo", entry, &pp).unwrap();
+        let old = "2020-01-01T00:00:00.000Z".to_string();
+        update_local_lock(&pp, |lock| {
+            lock.skills.get_mut("demo").unwrap().updated_at = Some(old.clone());
+        })
+        .unwrap();
+
+        // 损坏 canonical 目录中的文件后从来源修复
+        let canonical = project.path().join(".agents/skills/demo");
+        std::fs::write(canonical.join("notes.md"), "corrupted").unwrap();
+        let locked = read_locked_source(&Scope::Project, "demo", Some(&pp)).unwrap();
+        let skill = find_skill(src.path(), None, "demo").unwrap();
+        tauri::async_runtime::block_on(install_fetched_skill(
+            &skill,
+            &locked,
+            &Scope::Project,
+            "demo",
+            Some(&pp),
+            true,
+        ))
+        .unwrap();
+
+        assert_eq!(std::fs::read_to_string(canonical.join("notes.md")).unwrap(), "original");
+        let entry = read_local_lock(&pp).unwrap().skills.remove("demo").unwrap();
+        assert_ne!(entry.updated_at, Some(old));
+        // 本地来源无法获取远程 hash，保留原有值
+        assert_eq!(entry.remote_hash.as_deref(), Some("tree-1"));
+
+        // 没有任何安装位置时 update 返回错误
+        std::fs::remove_dir_all(project.path().join(".agents")).unwrap();
+        let _ = std::fs::remove_file(project.path().join(".claude/skills/demo"));
+        let _ = std::fs::remove_dir_all(project.path().join(".claude/skills/demo"));
+        assert!(tauri::async_runtime::block_on(install_fetched_skill(
+            &skill,
+            &locked,
+            &Scope::Project,
+            "demo",
+            Some(&pp),
+            false,
+        ))
+        .is_err());
+
+        // reinstall 仍会安装到 Universal Agents（canonical 目录）
+        tauri::async_runtime::block_on(install_fetched_skill(
+            &skill,
+            &locked,
+            &Scope::Project,
+            "demo",
+            Some(&pp),
+            true,
+        ))
+        .unwrap();
+        assert_eq!(std::fs::read_to_string(canonical.join("notes.md")).unwrap(), "original");
+    }
+
+    #[test]
+    fn test_reinstall_targets_detected_agents() {
+        let src = tempfile::tempdir().unwrap();
+        std::fs::write(src.path().join("SKILL.md"), "---\nname: demo\ndescription: Test\n---\n").unwrap();
+        let project = tempfile::tempdir().unwrap();
+        // 项目级信号：Continue 在该项目中被检测到
+        std::fs::create_dir(project.path().join(".continue")).unwrap();
+        let pp = project.path().to_string_lossy().to_string();
+
+        let result = install_skill_for_agent(
+            src.path(),
+            "demo",
+            &AgentType::ClaudeCode,
+            &Scope::Project,
+            Some(&pp),
+            &InstallMode::Copy,
+        );
+        assert!(result.success, "{:?}", result.error);
+        let entry = LocalSkillLockEntry {
+            source: src.path().to_string_lossy().to_string(),
+            source_type: "local".to_string(),
+            ..Default::default()
+        };
+        add_skill_to_local_lock("demo", entry, &pp).unwrap();
+
+        let locked = read_locked_source(&Scope::Project, "demo", Some(&pp)).unwrap();
+        let skill = find_skill(src.path(), None, "demo").unwrap();
+        tauri::async_runtime::block_on(install_fetched_skill(
+            &skill,
+            &locked,
+            &Scope::Project,
+            "demo",
+            Some(&pp),
+            true,
+        ))
+        .unwrap();
+
+        // 检测到的 agent 和 Universal Agents 都已安装
+        assert!(project.path().join(".continue/skills/demo/SKILL.md").exists());
+        assert!(project.path().join(".agents/skills/demo/SKILL.md").exists());
+        // 已安装的 agent 沿用原安装方式
+        let claude = project.path().join(".claude/skills/demo");
+        assert!(!crate::core::paths::is_symlink_or_junction(&claude));
+        assert!(claude.join("SKILL.md").exists());
+        // update 不新增安装位置
+        std::fs::remove_dir_all(project.path().join(".continue/skills/demo")).unwrap();
+        tauri::async_runtime::block_on(install_fetched_skill(
+            &skill,
+            &locked,
+            &Scope::Project,
+            "demo",
+            Some(&pp),
+            false,
+        ))
+        .unwrap();
+        assert!(!project.path().join(".continue/skills/demo").exists());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_update_skips_detached_agents() {
+        use crate::core::agents::AgentType;
+        use crate::core::detacher::detach_skill;
+
+        let src = tempfile::tempdir().unwrap();
+        std::fs::write(src.path().join("SKILL.md"), "---\nname: demo\ndescription: Test\n---\n").unwrap();
+        std::fs::write(src.path().join("notes.md"), "v1").unwrap();
+        let project = tempfile::tempdir().unwrap();
+        let pp = project.path().to_string_lossy().to_string();
+
+        for agent in [AgentType::ClaudeCode, AgentType::Augment] {
+            let result =
+                install_skill_for_agent(src.path(), "demo", &agent, &Scope::Project, Some(&pp), &InstallMode::Symlink);
+            assert!(result.success, "{:?}", result.error);
+        }
+        let claude = project.path().join(".claude/skills/demo");
+        if !crate::core::paths::is_symlink_or_junction(&claude) {
+            // 当前文件系统不支持 symlink，已降级为 copy
+            return;
+        }
+        let entry = LocalSkillLockEntry {
+            source: src.path().to_string_lossy().to_string(),
+            source_type: "local".to_string(),
+            ..Default::default()
+        };
+        add_skill_to_local_lock("demo", entry, &pp).unwrap();
+
+        // 脱离 Claude Code 并在副本中修改
+        let result = detach_skill("demo", &Scope::Project, Some(&pp), Some(&[AgentType::ClaudeCode])).unwrap();
+        assert_eq!(result.detached, vec!["claude-code".to_string()]);
+        std::fs::write(claude.join("notes.md"), "local edit").unwrap();
+
+        // 来源更新后执行 update
+        std::fs::write(src.path().join("notes.md"), "v2").unwrap();
+        let locked = read_locked_source(&Scope::Project, "demo", Some(&pp)).unwrap();
+        assert_eq!(locked.detached_agents, vec!["claude-code".to_string()]);
+        let skill = find_skill(src.path(), None, "demo").unwrap();
+        tauri::async_runtime::block_on(install_fetched_skill(
+            &skill,
+            &locked,
+            &Scope::Project,
+            "demo",
+            Some(&pp),
+            false,
+        ))
+        .unwrap();
+
+        let canonical = project.path().join(".agents/skills/demo");
+        assert_eq!(std::fs::read_to_string(canonical.join("notes.md")).unwrap(), "v2");
+        assert_eq!(
+            std::fs::read_to_string(project.path().join(".augment/skills/demo/notes.md")).unwrap(),
+            "v2"
+        );
+        // 脱离的副本保持不动，lock 中的记录保留
+        assert!(!crate::core::paths::is_symlink_or_junction(&claude));
+        assert_eq!(std::fs::read_to_string(claude.join("notes.md")).unwrap(), "local edit");
+        let entry = read_local_lock(&pp).unwrap().skills.remove("demo").unwrap();
+        assert_eq!(entry.detached_agents, vec!["claude-code".to_string()]);
+    }
+
+    #[test]
+    fn test_update_keeps_installed_locale() {
+        let src = tempfile::tempdir().unwrap();
+        std::fs::write(src.path().join("SKILL.md"), "---\nname: demo\ndescription: Test\n---\nv1").unwrap();
+        std::fs::write(src.path().join("SKILL.zh.md"), "---\nname: demo\ndescription: 测试\n---\nv1").unwrap();
+        let project = tempfile::tempdir().unwrap();
+        let pp = project.path().to_string_lossy().to_string();
+
+        let (locale, staging) = stage_localized(src.path(), "zh").unwrap().unwrap();
+        let result = install_skill_for_agent(
+            staging.path(),
+            "demo",
+            &AgentType::Cursor,
+            &Scope::Project,
+            Some(&pp),
+            &InstallMode::Symlink,
+        );
+        assert!(result.success, "{:?}", result.error);
+        let entry = LocalSkillLockEntry {
+            source: src.path().to_string_lossy().to_string(),
+            source_type: "local".to_string(),
+            locale: Some(locale),
+            ..Default::default()
+        };
+        add_skill_to_local_lock("demo", entry, &pp).unwrap();
+
+        // 新版本仍按 zh 安装
+        std::fs::write(src.path().join("SKILL.zh.md"), "---\nname: demo\ndescription: 测试\n---\nv2").unwrap();
+        let locked = read_locked_source(&Scope::Project, "demo", Some(&pp)).unwrap();
+        let skill = find_skill(src.path(), None, "demo").unwrap();
+        tauri::async_runtime::block_on(install_fetched_skill(
+            &skill,
+            &locked,
+            &Scope::Project,
+            "demo",
+            Some(&pp),
+            false,
+        ))
+        .unwrap();
+
+        let skill_md = project.path().join(".agents/skills/demo/SKILL.md");
+        assert!(std::fs::read_to_string(skill_md).unwrap().ends_with("测试\n---\nv2"));
+        let entry = read_local_lock(&pp).unwrap().skills.remove("demo").unwrap();
+        assert_eq!(entry.locale.as_deref(), Some("zh"));
+    }
+}
@@ -0,0 +1,128 @@
+//! 来源校验命令
+//!
+//! 提供命令：
+//! - validate_source: 解析来源并检查能否访问（不克隆、不下载 skills）
+//! - lint_skill: 发布前检查本地 skill 目录（规则见 core/lint.rs）
+//!
+//! - GitHub / GitLab / Git：`git ls-remote` 确认仓库存在、ref 可解析
+//! - Local：目录或 bundle / 压缩包文件存在
+//! - WellKnown / DirectUrl：索引文件或 SKILL.md URL 可访问
+
+use crate::commands::operation::{Operation, OperationKind};
+use crate::core::git::check_remote;
+use crate::core::http_source::probe_http_source;
+use crate::core::lint::{self, LintDiagnostic};
+use crate::core::parse_source;
+use crate::error::AppError;
+use crate::models::{ParsedSource, SourceType};
+use serde::Serialize;
+use specta::Type;
+use std::path::Path;
+use tauri::AppHandle;
+
+/// 来源校验结果
+#[derive(Debug, Serialize, Type)]
+#[serde(rename_all = "camelCase")]
+#[specta(rename_all = "camelCase")]
+pub struct SourceValidation {
+    /// 解析后的来源
+    pub parsed: ParsedSource,
+    /// 是否可以访问
+    pub reachable: bool,
+    /// 无法访问的原因
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<AppError>,
+}
+
+/// 校验来源格式并检查能否访问
+///
+/// 来源格式无法解析时直接返回解析错误；能解析但无法访问时返回
+/// `reachable: false` 和分类后的错误（认证失败、仓库不存在、ref 不存在等）。
+///
+/// # Arguments
+/// * `source` - 来源字符串（格式同 fetch_available）
+#[tauri::command]
+#[specta::specta]
+pub async fn validate_source(source: String) -> Result<SourceValidation, AppError> {
+    let parsed = parse_source(&source)?;
+
+    let result = match parsed.source_type {
+        SourceType::GitHub | SourceType::GitLab | SourceType::Git => {
+            let url = parsed.url.clone();
+            let git_ref = parsed.git_ref.clone();
+            tauri::async_runtime::spawn_blocking(move || check_remote(&url, git_ref.as_deref()))
+                .await
+                .map_err(|e| AppError::Custom { message: format!("Validate task failed: {}", e) })?
+        }
+        SourceType::Local => check_local(&parsed),
+        SourceType::WellKnown => probe_http_source(&parsed.url, true).await,
+        SourceType::DirectUrl => probe_http_source(&parsed.url, false).await,
+    };
+
+    Ok(SourceValidation {
+        parsed,
+        reachable: result.is_ok(),
+        error: result.err(),
+    })
+}
+
+/// 检查本地 skill 目录能否正常安装
+///
+/// 复用安装时的 SKILL.md 解析、名称 sanitize 和复制排除规则，
+/// 把安装时静默处理的情况作为诊断返回（error / warning / info）。
+///
+/// # Arguments
+/// * `path` - skill 根目录
+/// * `op_id` - 前端指定的操作 id（用于 operation-event），未传时自动生成
+#[tauri::command]
+#[specta::specta]
+pub fn lint_skill(
+    app: AppHandle,
+    path: String,
+    op_id: Option<String>,
+) -> Result<Vec<LintDiagnostic>, AppError> {
+    let op = Operation::start(&app, OperationKind::Lint, op_id);
+    op.finish(lint::lint_skill(Path::new(&path)), |diagnostics| {
+        format!("{} diagnostics", diagnostics.len())
+    })
+}
+
+/// 本地来源：路径存在即可（目录或 bundle / 压缩包文件）
+fn check_local(parsed: &ParsedSource) -> Result<(), AppError> {
+    let path = parsed
+        .local_path
+        .as_ref()
+        .ok_or_else(|| AppError::InvalidSource { value: "Missing local path".to_string() })?;
+    if path.exists() {
+        Ok(())
+    } else {
+        Err(AppError::PathNotFound {
+            path: path.to_string_lossy().to_string(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_validate_local_source() {
+        let temp = tempfile::tempdir().unwrap();
+        let source = temp.path().to_string_lossy().to_string();
+        let result = tauri::async_runtime::block_on(validate_source(source)).unwrap();
+        assert_eq!(result.parsed.source_type, SourceType::Local);
+        assert!(result.reachable);
+        assert!(result.error.is_none());
+
+        let missing = temp.path().join("missing").to_string_lossy().to_string();
+        let result = tauri::async_runtime::block_on(validate_source(missing)).unwrap();
+        assert!(!result.reachable);
+        assert!(matches!(result.error, Some(AppError::PathNotFound { .. })));
+    }
+
+    #[test]
+    fn test_validate_invalid_source() {
+        assert!(tauri::async_runtime::block_on(validate_source("not-a-source".to_string())).is_err());
+    }
+}
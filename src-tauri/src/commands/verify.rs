@@ -0,0 +1,136 @@
+//! 本地改动检测命令
+//!
+//! 提供命令：
+//! - verify_skills: 对比项目 lock 中记录的 computedHash 与已安装内容，检测本地修改
+//!
+//! 与 check_updates 不同：check_updates 比对远程，verify_skills 只关心本地副本是否被改动。
+
+use crate::core::local_lock::{compute_skill_folder_hash, read_local_lock};
+use crate::core::paths::canonical_skills_dir;
+use crate::core::skill::locked_dir_name;
+use crate::error::AppError;
+use crate::models::Scope;
+use serde::Serialize;
+use specta::Type;
+
+/// 单个 skill 的校验结果
+#[derive(Debug, Clone, Serialize, Type)]
+#[serde(rename_all = "camelCase")]
+#[specta(rename_all = "camelCase")]
+pub struct SkillVerifyInfo {
+    pub name: String,
+    /// 安装后内容是否被修改（目录不存在时为 false，见 `missing`）
+    pub drifted: bool,
+    /// canonical 目录是否已不存在（被删除或移动）
+    pub missing: bool,
+    /// lock 文件中记录的 computedHash
+    pub expected_hash: String,
+    /// 当前 canonical 目录的 hash（目录不存在时为空）
+    pub actual_hash: String,
+}
+
+/// 检测指定 scope 已安装 skills 是否在安装后被本地修改
+///
+/// 只有项目级 skills-lock.json 记录 computedHash，因此 Global scope
+/// 或未提供 project_path 时返回空列表。
+///
+/// - computedHash 为空（旧版本 lock）的条目报告 `drifted: false`
+/// - canonical 目录不存在时报告 `missing: true`（不算作修改），`actual_hash` 为空
+#[tauri::command]
+#[specta::specta]
+pub async fn verify_skills(
+    scope: Scope,
+    project_path: Option<String>,
+) -> Result<Vec<SkillVerifyInfo>, AppError> {
+    verify_skills_inner(scope, project_path.as_deref())
+}
+
+fn verify_skills_inner(
+    scope: Scope,
+    project_path: Option<&str>,
+) -> Result<Vec<SkillVerifyInfo>, AppError> {
+    let project_path = match (scope, project_path) {
+        (Scope::Project, Some(pp)) => pp,
+        _ => return Ok(Vec::new()),
+    };
+
+    let lock = read_local_lock(project_path)?;
+    let skills_dir = canonical_skills_dir(false, project_path);
+
+    let results = lock
+        .skills
+        .into_iter()
+        .map(|(name, entry)| {
+            let expected_hash = entry.computed_hash;
+            let skill_dir = skills_dir.join(locked_dir_name(&name, entry.dir_name.as_deref()));
+            let missing = !skill_dir.is_dir();
+            let (drifted, actual_hash) = if expected_hash.is_empty() || missing {
+                (false, String::new())
+            } else {
+                let actual_hash = compute_skill_folder_hash(&skill_dir).unwrap_or_default();
+                (actual_hash != expected_hash, actual_hash)
+            };
+
+            SkillVerifyInfo {
+                name,
+                drifted,
+                missing,
+                expected_hash,
+                actual_hash,
+            }
+        })
+        .collect();
+
+    Ok(results)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::local_lock::{add_skill_to_local_lock, LocalSkillLockEntry};
+    use std::fs;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_verify_skills_detects_drift() {
+        let project = tempdir().unwrap();
+        let project_path = project.path().to_string_lossy().to_string();
+        let skills_dir = canonical_skills_dir(false, &project_path);
+
+        for name in ["clean", "edited", "legacy"] {
+            let dir = skills_dir.join(name);
+            fs::create_dir_all(&dir).unwrap();
+            fs::write(dir.join("SKILL.md"), format!("# {}", name)).unwrap();
+            let hash = if name == "legacy" {
+                String::new()
+            } else {
+                compute_skill_folder_hash(&dir).unwrap()
+            };
+            let entry = LocalSkillLockEntry { computed_hash: hash, ..Default::default() };
+            add_skill_to_local_lock(name, entry, &project_path).unwrap();
+        }
+        let entry = LocalSkillLockEntry { computed_hash: "abc".to_string(), ..Default::default() };
+        add_skill_to_local_lock("missing", entry, &project_path).unwrap();
+
+        // 安装后本地修改
+        fs::write(skills_dir.join("edited/SKILL.md"), "# edited locally").unwrap();
+        fs::write(skills_dir.join("legacy/SKILL.md"), "# legacy edited").unwrap();
+
+        let results = verify_skills_inner(Scope::Project, Some(&project_path)).unwrap();
+        let get = |name: &str| results.iter().find(|r| r.name == name).unwrap();
+
+        assert!(!get("clean").drifted);
+        assert_eq!(get("clean").expected_hash, get("clean").actual_hash);
+        assert!(get("edited").drifted);
+        assert!(!get("legacy").drifted);
+        assert!(results.iter().filter(|r| r.name != "missing").all(|r| !r.missing));
+        assert!(get("missing").missing);
+        assert!(!get("missing").drifted);
+        assert!(get("missing").actual_hash.is_empty());
+    }
+
+    #[test]
+    fn test_verify_skills_global_is_empty() {
+        assert!(verify_skills_inner(Scope::Global, None).unwrap().is_empty());
+    }
+}
@@ -0,0 +1,69 @@
+//! agent 目录中已安装 skill 的扫描
+//!
+//! 重命名（renamer）和 scope 迁移（scope_mover）都需要先找出各 agent 目录下的安装位置，
+//! 区分指向 canonical 目录的链接和 copy 模式的实体目录，再逐个迁移或删除。
+
+use crate::core::agents::AgentType;
+use crate::core::paths::{agent_skills_dir, is_symlink_or_junction, same_path};
+use crate::error::AppError;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// agent 目录下的安装形态
+pub enum AgentEntryKind {
+    /// 指向 canonical 目录的 symlink / junction
+    Link,
+    /// copy 模式的实体目录（可能已单独修改）
+    Copy,
+}
+
+/// agent 目录中的 skill 安装位置
+pub struct AgentEntry {
+    pub agent: AgentType,
+    pub path: PathBuf,
+    pub kind: AgentEntryKind,
+}
+
+impl AgentEntry {
+    /// 删除该安装位置：链接只删除链接本身，实体目录整体删除
+    pub fn remove(&self) -> std::io::Result<()> {
+        match self.kind {
+            AgentEntryKind::Link => fs::remove_file(&self.path).or_else(|_| fs::remove_dir(&self.path)),
+            AgentEntryKind::Copy => fs::remove_dir_all(&self.path),
+        }
+    }
+}
+
+/// 收集各 agent 目录下名为 `dir_name` 的安装位置（不修改任何文件）
+///
+/// Universal agents 直接使用 canonical 目录（`canonical_base`），不单独返回；
+/// 多个 agent 共用的目录只返回第一个 agent
+pub fn scan_agent_entries(global: bool, cwd: &str, canonical_base: &Path, dir_name: &str) -> Vec<AgentEntry> {
+    let mut entries: Vec<AgentEntry> = Vec::new();
+    for agent in AgentType::all() {
+        let Some(base) = agent_skills_dir(&agent, global, cwd) else {
+            continue;
+        };
+        let path = base.join(dir_name);
+        if same_path(&base, canonical_base) || entries.iter().any(|e| same_path(&e.path, &path)) {
+            continue;
+        }
+
+        let kind = if is_symlink_or_junction(&path) {
+            AgentEntryKind::Link
+        } else if path.is_dir() {
+            AgentEntryKind::Copy
+        } else {
+            continue;
+        };
+        entries.push(AgentEntry { agent, path, kind });
+    }
+    entries
+}
+
+/// 目标位置已存在时的错误
+pub fn already_exists(path: &Path) -> AppError {
+    AppError::Path {
+        message: format!("Skill already exists: {}", path.display()),
+    }
+}
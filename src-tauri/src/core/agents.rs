@@ -1,10 +1,15 @@
 // Agent 配置与检测
 // 完整对应 CLI: agents.ts
 
-use crate::core::paths::PATHS;
+use crate::core::paths::{read_skill_deck_config, PATHS};
+use crate::error::AppError;
+use crate::models::config::AgentDirOverride;
+use once_cell::sync::Lazy;
 use serde::{Deserialize, Serialize};
 use specta::Type;
-use std::path::PathBuf;
+use std::collections::HashMap;
+use std::path::{Component, Path, PathBuf};
+use std::sync::RwLock;
 
 /// Agent 配置
 /// 对应 CLI: AgentConfig (types.ts:51-60)
@@ -12,13 +17,132 @@ use std::path::PathBuf;
 pub struct AgentConfig {
     pub name: &'static str,
     pub display_name: &'static str,
-    pub skills_dir: &'static str,
+    pub skills_dir: String,
     pub global_skills_dir: Option<PathBuf>,
+    /// 是否支持项目级安装（仅云端使用、没有项目目录概念的 agent 为 false）
+    pub supports_project: bool,
     /// 是否在 Universal 列表显示（默认 true）
     /// 对应 CLI: showInUniversalList
     pub show_in_universal_list: bool,
 }
 
+/// config.json 中的 agentDirOverrides
+/// 首次访问时从 config.json 加载（跳过校验不通过的条目），save_config 时通过 set_agent_dir_overrides 同步
+static AGENT_DIR_OVERRIDES: Lazy<RwLock<HashMap<AgentType, AgentDirOverride>>> =
+    Lazy::new(|| RwLock::new(load_agent_dir_overrides()));
+
+fn load_agent_dir_overrides() -> HashMap<AgentType, AgentDirOverride> {
+    read_skill_deck_config()
+        .map(|c| c.agent_dir_overrides)
+        .unwrap_or_default()
+        .into_iter()
+        .filter(|(agent, dir_override)| match validate_agent_dir_override(agent, dir_override) {
+            Ok(()) => true,
+            Err(e) => {
+                log::warn!("Ignoring agent directory override: {}", e);
+                false
+            }
+        })
+        .collect()
+}
+
+/// 校验单个 agent 的目录覆盖
+///
+/// globalSkillsDir 必须为绝对路径；skillsDir 必须为相对项目根目录的路径，且不能包含 `..`
+pub fn validate_agent_dir_override(
+    agent: &AgentType,
+    dir_override: &AgentDirOverride,
+) -> Result<(), AppError> {
+    if let Some(global_dir) = dir_override.global_skills_dir.as_deref() {
+        let path = Path::new(global_dir);
+        if path.as_os_str().is_empty() || !path.is_absolute() {
+            return Err(AppError::Path {
+                message: format!(
+                    "Global skills directory override for {} must be an absolute path: {}",
+                    agent, global_dir
+                ),
+            });
+        }
+    }
+    if let Some(project_dir) = dir_override.skills_dir.as_deref() {
+        let path = Path::new(project_dir);
+        let relative = path
+            .components()
+            .all(|c| matches!(c, Component::Normal(_) | Component::CurDir));
+        if path.as_os_str().is_empty() || !relative {
+            return Err(AppError::Path {
+                message: format!(
+                    "Project skills directory override for {} must be a relative path inside the project: {}",
+                    agent, project_dir
+                ),
+            });
+        }
+    }
+    Ok(())
+}
+
+/// 更新内存中的 agent 目录覆盖（save_config 校验通过后调用）
+pub fn set_agent_dir_overrides(overrides: &HashMap<AgentType, AgentDirOverride>) {
+    if let Ok(mut guard) = AGENT_DIR_OVERRIDES.write() {
+        *guard = overrides.clone();
+    }
+}
+
+/// 用覆盖值替换内置目录（纯函数，未设置的字段保持内置值）
+fn apply_dir_override(mut config: AgentConfig, dir_override: &AgentDirOverride) -> AgentConfig {
+    if let Some(project_dir) = dir_override.skills_dir.as_deref() {
+        config.skills_dir = project_dir.trim_start_matches("./").trim_end_matches('/').to_string();
+    }
+    if let Some(global_dir) = dir_override.global_skills_dir.as_deref() {
+        config.global_skills_dir = Some(PathBuf::from(global_dir));
+    }
+    config
+}
+
+/// 覆盖是否设置了该范围的目录（global: globalSkillsDir，project: skillsDir）
+fn overrides_scope_dir(dir_override: Option<&AgentDirOverride>, global: bool) -> bool {
+    dir_override.is_some_and(|o| {
+        if global {
+            o.global_skills_dir.is_some()
+        } else {
+            o.skills_dir.is_some()
+        }
+    })
+}
+
+/// Agent 检测上下文：决定哪些信号参与检测
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DetectionContext {
+    /// 仅全局信号（如 `~/.cursor`），不受进程 cwd 影响
+    GlobalOnly,
+    /// 全局信号 + 指定项目目录下的信号（如 `<project>/.continue`）
+    WithProject(PathBuf),
+}
+
+impl DetectionContext {
+    /// 计入当前进程 cwd 下的项目信号（与 CLI 行为一致）
+    pub fn current_dir() -> Self {
+        Self::WithProject(std::env::current_dir().unwrap_or_default())
+    }
+
+    /// 按安装范围选择上下文：Global 仅看全局信号，Project 计入项目目录
+    pub fn for_scope(global: bool, cwd: &str) -> Self {
+        if global {
+            Self::GlobalOnly
+        } else {
+            Self::WithProject(PathBuf::from(cwd))
+        }
+    }
+
+    /// 项目目录下是否存在指定路径（GlobalOnly 时恒为 false）
+    fn project_has(&self, relative: &str) -> bool {
+        match self {
+            Self::GlobalOnly => false,
+            Self::WithProject(dir) => dir.join(relative).exists(),
+        }
+    }
+}
+
 /// Agent 信息（返回给前端）
 /// 对应 CLI: 综合 AgentConfig + detectInstalled 结果
 #[derive(Debug, Clone, Serialize, Type)]
@@ -38,6 +162,23 @@ pub struct AgentInfo {
     pub show_in_universal_list: bool,
 }
 
+/// Agent 实际路径信息（诊断用）
+///
+/// 与 AgentInfo 不同，路径已解析为绝对路径，并附带磁盘上是否存在
+#[derive(Debug, Clone, Serialize, Type)]
+#[serde(rename_all = "camelCase")]
+#[specta(rename_all = "camelCase")]
+pub struct AgentPathInfo {
+    pub id: AgentType,
+    pub name: String,
+    /// 项目级 skills 目录（基于 cwd 解析的绝对路径）
+    pub skills_dir: String,
+    pub skills_dir_exists: bool,
+    /// 全局 skills 目录（不支持全局安装时为 None）
+    pub global_skills_dir: Option<String>,
+    pub global_skills_dir_exists: bool,
+}
+
 /// Agent 类型枚举
 /// 完整对应 CLI: types.ts AgentType
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, Type)]
@@ -236,283 +377,336 @@ impl AgentType {
         .into_iter()
     }
 
-    /// 获取 Agent 配置
-    /// 完整对应 CLI: agents.ts 中每个 agent 的配置
+    /// 获取 Agent 配置（已应用 config.json 中的 agentDirOverrides）
     pub fn config(&self) -> AgentConfig {
+        let config = self.builtin_config();
+        match self.dir_override() {
+            Some(dir_override) => apply_dir_override(config, &dir_override),
+            None => config,
+        }
+    }
+
+    /// config.json 中该 agent 的目录覆盖
+    fn dir_override(&self) -> Option<AgentDirOverride> {
+        AGENT_DIR_OVERRIDES.read().ok().and_then(|g| g.get(self).cloned())
+    }
+
+    /// 内置 Agent 配置
+    /// 完整对应 CLI: agents.ts 中每个 agent 的配置
+    fn builtin_config(&self) -> AgentConfig {
         match self {
             Self::Amp => AgentConfig {
                 name: "amp",
                 display_name: "Amp",
-                skills_dir: ".agents/skills",
+                skills_dir: ".agents/skills".to_string(),
                 global_skills_dir: Some(PATHS.config_home.join("agents/skills")),
+                supports_project: true,
                 show_in_universal_list: true,
             },
             Self::Antigravity => AgentConfig {
                 name: "antigravity",
                 display_name: "Antigravity",
-                skills_dir: ".agent/skills",
+                skills_dir: ".agent/skills".to_string(),
                 global_skills_dir: Some(PATHS.home.join(".gemini/antigravity/skills")),
+                supports_project: true,
                 show_in_universal_list: true,
             },
             Self::Augment => AgentConfig {
                 name: "augment",
                 display_name: "Augment",
-                skills_dir: ".augment/skills",
+                skills_dir: ".augment/skills".to_string(),
                 global_skills_dir: Some(PATHS.home.join(".augment/skills")),
+                supports_project: true,
                 show_in_universal_list: true,
             },
             Self::ClaudeCode => AgentConfig {
                 name: "claude-code",
                 display_name: "Claude Code",
-                skills_dir: ".claude/skills",
+                skills_dir: ".claude/skills".to_string(),
                 global_skills_dir: Some(PATHS.claude_home.join("skills")),
+                supports_project: true,
                 show_in_universal_list: true,
             },
             Self::Openclaw => AgentConfig {
                 name: "openclaw",
                 display_name: "OpenClaw",
-                skills_dir: "skills",
+                skills_dir: "skills".to_string(),
                 global_skills_dir: Some(Self::openclaw_global_dir()),
+                supports_project: true,
                 show_in_universal_list: true,
             },
             Self::Cline => AgentConfig {
                 name: "cline",
                 display_name: "Cline",
-                skills_dir: ".agents/skills",
+                skills_dir: ".agents/skills".to_string(),
                 global_skills_dir: Some(PATHS.home.join(".agents").join("skills")),
+                supports_project: true,
                 show_in_universal_list: true,
             },
             Self::Codebuddy => AgentConfig {
                 name: "codebuddy",
                 display_name: "CodeBuddy",
-                skills_dir: ".codebuddy/skills",
+                skills_dir: ".codebuddy/skills".to_string(),
                 global_skills_dir: Some(PATHS.home.join(".codebuddy/skills")),
+                supports_project: true,
                 show_in_universal_list: true,
             },
             Self::Codex => AgentConfig {
                 name: "codex",
                 display_name: "Codex",
-                skills_dir: ".agents/skills",
+                skills_dir: ".agents/skills".to_string(),
                 global_skills_dir: Some(PATHS.codex_home.join("skills")),
+                supports_project: true,
                 show_in_universal_list: true,
             },
             Self::CommandCode => AgentConfig {
                 name: "command-code",
                 display_name: "Command Code",
-                skills_dir: ".commandcode/skills",
+                skills_dir: ".commandcode/skills".to_string(),
                 global_skills_dir: Some(PATHS.home.join(".commandcode/skills")),
+                supports_project: true,
                 show_in_universal_list: true,
             },
             Self::Continue => AgentConfig {
                 name: "continue",
                 display_name: "Continue",
-                skills_dir: ".continue/skills",
+                skills_dir: ".continue/skills".to_string(),
                 global_skills_dir: Some(PATHS.home.join(".continue/skills")),
+                supports_project: true,
                 show_in_universal_list: true,
             },
             Self::Crush => AgentConfig {
                 name: "crush",
                 display_name: "Crush",
-                skills_dir: ".crush/skills",
+                skills_dir: ".crush/skills".to_string(),
                 global_skills_dir: Some(PATHS.config_home.join("crush/skills")),
+                supports_project: true,
                 show_in_universal_list: true,
             },
             Self::Cursor => AgentConfig {
                 name: "cursor",
                 display_name: "Cursor",
-                skills_dir: ".agents/skills",
+                skills_dir: ".agents/skills".to_string(),
                 global_skills_dir: Some(PATHS.home.join(".cursor/skills")),
+                supports_project: true,
                 show_in_universal_list: true,
             },
             Self::Droid => AgentConfig {
                 name: "droid",
                 display_name: "Droid",
-                skills_dir: ".factory/skills",
+                skills_dir: ".factory/skills".to_string(),
                 global_skills_dir: Some(PATHS.home.join(".factory/skills")),
+                supports_project: true,
                 show_in_universal_list: true,
             },
             Self::GeminiCli => AgentConfig {
                 name: "gemini-cli",
                 display_name: "Gemini CLI",
-                skills_dir: ".agents/skills",
+                skills_dir: ".agents/skills".to_string(),
                 global_skills_dir: Some(PATHS.home.join(".gemini/skills")),
+                supports_project: true,
                 show_in_universal_list: true,
             },
             Self::GithubCopilot => AgentConfig {
                 name: "github-copilot",
                 display_name: "GitHub Copilot",
-                skills_dir: ".agents/skills",
+                skills_dir: ".agents/skills".to_string(),
                 global_skills_dir: Some(PATHS.home.join(".copilot/skills")),
+                supports_project: true,
                 show_in_universal_list: true,
             },
             Self::Goose => AgentConfig {
                 name: "goose",
                 display_name: "Goose",
-                skills_dir: ".goose/skills",
+                skills_dir: ".goose/skills".to_string(),
                 global_skills_dir: Some(PATHS.config_home.join("goose/skills")),
+                supports_project: true,
                 show_in_universal_list: true,
             },
             Self::IflowCli => AgentConfig {
                 name: "iflow-cli",
                 display_name: "iFlow CLI",
-                skills_dir: ".iflow/skills",
+                skills_dir: ".iflow/skills".to_string(),
                 global_skills_dir: Some(PATHS.home.join(".iflow/skills")),
+                supports_project: true,
                 show_in_universal_list: true,
             },
             Self::Junie => AgentConfig {
                 name: "junie",
                 display_name: "Junie",
-                skills_dir: ".junie/skills",
+                skills_dir: ".junie/skills".to_string(),
                 global_skills_dir: Some(PATHS.home.join(".junie/skills")),
+                supports_project: true,
                 show_in_universal_list: true,
             },
             Self::Kilo => AgentConfig {
                 name: "kilo",
                 display_name: "Kilo Code",
-                skills_dir: ".kilocode/skills",
+                skills_dir: ".kilocode/skills".to_string(),
                 global_skills_dir: Some(PATHS.home.join(".kilocode/skills")),
+                supports_project: true,
                 show_in_universal_list: true,
             },
             Self::KimiCli => AgentConfig {
                 name: "kimi-cli",
                 display_name: "Kimi Code CLI",
-                skills_dir: ".agents/skills",
+                skills_dir: ".agents/skills".to_string(),
                 global_skills_dir: Some(PATHS.config_home.join("agents/skills")),
+                supports_project: true,
                 show_in_universal_list: true,
             },
             Self::KiroCli => AgentConfig {
                 name: "kiro-cli",
                 display_name: "Kiro CLI",
-                skills_dir: ".kiro/skills",
+                skills_dir: ".kiro/skills".to_string(),
                 global_skills_dir: Some(PATHS.home.join(".kiro/skills")),
+                supports_project: true,
                 show_in_universal_list: true,
             },
             Self::Kode => AgentConfig {
                 name: "kode",
                 display_name: "Kode",
-                skills_dir: ".kode/skills",
+                skills_dir: ".kode/skills".to_string(),
                 global_skills_dir: Some(PATHS.home.join(".kode/skills")),
+                supports_project: true,
                 show_in_universal_list: true,
             },
             Self::Mcpjam => AgentConfig {
                 name: "mcpjam",
                 display_name: "MCPJam",
-                skills_dir: ".mcpjam/skills",
+                skills_dir: ".mcpjam/skills".to_string(),
                 global_skills_dir: Some(PATHS.home.join(".mcpjam/skills")),
+                supports_project: true,
                 show_in_universal_list: true,
             },
             Self::MistralVibe => AgentConfig {
                 name: "mistral-vibe",
                 display_name: "Mistral Vibe",
-                skills_dir: ".vibe/skills",
+                skills_dir: ".vibe/skills".to_string(),
                 global_skills_dir: Some(PATHS.home.join(".vibe/skills")),
+                supports_project: true,
                 show_in_universal_list: true,
             },
             Self::Mux => AgentConfig {
                 name: "mux",
                 display_name: "Mux",
-                skills_dir: ".mux/skills",
+                skills_dir: ".mux/skills".to_string(),
                 global_skills_dir: Some(PATHS.home.join(".mux/skills")),
+                supports_project: true,
                 show_in_universal_list: true,
             },
             Self::Neovate => AgentConfig {
                 name: "neovate",
                 display_name: "Neovate",
-                skills_dir: ".neovate/skills",
+                skills_dir: ".neovate/skills".to_string(),
                 global_skills_dir: Some(PATHS.home.join(".neovate/skills")),
+                supports_project: true,
                 show_in_universal_list: true,
             },
             Self::Opencode => AgentConfig {
                 name: "opencode",
                 display_name: "OpenCode",
-                skills_dir: ".agents/skills",
+                skills_dir: ".agents/skills".to_string(),
                 global_skills_dir: Some(PATHS.config_home.join("opencode/skills")),
+                supports_project: true,
                 show_in_universal_list: true,
             },
             Self::Openhands => AgentConfig {
                 name: "openhands",
                 display_name: "OpenHands",
-                skills_dir: ".openhands/skills",
+                skills_dir: ".openhands/skills".to_string(),
                 global_skills_dir: Some(PATHS.home.join(".openhands/skills")),
+                supports_project: true,
                 show_in_universal_list: true,
             },
             Self::Pi => AgentConfig {
                 name: "pi",
                 display_name: "Pi",
-                skills_dir: ".pi/skills",
+                skills_dir: ".pi/skills".to_string(),
                 global_skills_dir: Some(PATHS.home.join(".pi/agent/skills")),
+                supports_project: true,
                 show_in_universal_list: true,
             },
             Self::Qoder => AgentConfig {
                 name: "qoder",
                 display_name: "Qoder",
-                skills_dir: ".qoder/skills",
+                skills_dir: ".qoder/skills".to_string(),
                 global_skills_dir: Some(PATHS.home.join(".qoder/skills")),
+                supports_project: true,
                 show_in_universal_list: true,
             },
             Self::QwenCode => AgentConfig {
                 name: "qwen-code",
                 display_name: "Qwen Code",
-                skills_dir: ".qwen/skills",
+                skills_dir: ".qwen/skills".to_string(),
                 global_skills_dir: Some(PATHS.home.join(".qwen/skills")),
+                supports_project: true,
                 show_in_universal_list: true,
             },
-            // Replit: 使用 .agents/skills 但不显示在 Universal 列表
-            // 原因：Replit 是云端 IDE，不支持全局安装，且检测条件容易误判
+            // Replit: 云端 IDE，不支持本地项目级安装（supports_project 为 false）
+            // 使用 .agents/skills 但不显示在 Universal 列表，原因：检测条件容易误判
             Self::Replit => AgentConfig {
                 name: "replit",
                 display_name: "Replit",
-                skills_dir: ".agents/skills",
+                skills_dir: ".agents/skills".to_string(),
                 global_skills_dir: Some(PATHS.config_home.join("agents/skills")),
+                supports_project: false,
                 show_in_universal_list: false, // 关键：不显示在 Universal 列表
             },
             Self::Roo => AgentConfig {
                 name: "roo",
                 display_name: "Roo Code",
-                skills_dir: ".roo/skills",
+                skills_dir: ".roo/skills".to_string(),
                 global_skills_dir: Some(PATHS.home.join(".roo/skills")),
+                supports_project: true,
                 show_in_universal_list: true,
             },
             Self::Trae => AgentConfig {
                 name: "trae",
                 display_name: "Trae",
-                skills_dir: ".trae/skills",
+                skills_dir: ".trae/skills".to_string(),
                 global_skills_dir: Some(PATHS.home.join(".trae/skills")),
+                supports_project: true,
                 show_in_universal_list: true,
             },
             Self::TraeCn => AgentConfig {
                 name: "trae-cn",
                 display_name: "Trae CN",
-                skills_dir: ".trae/skills",
+                skills_dir: ".trae/skills".to_string(),
                 global_skills_dir: Some(PATHS.home.join(".trae-cn/skills")),
+                supports_project: true,
                 show_in_universal_list: true,
             },
             Self::Windsurf => AgentConfig {
                 name: "windsurf",
                 display_name: "Windsurf",
-                skills_dir: ".windsurf/skills",
+                skills_dir: ".windsurf/skills".to_string(),
                 global_skills_dir: Some(PATHS.home.join(".codeium/windsurf/skills")),
+                supports_project: true,
                 show_in_universal_list: true,
             },
             Self::Zencoder => AgentConfig {
                 name: "zencoder",
                 display_name: "Zencoder",
-                skills_dir: ".zencoder/skills",
+                skills_dir: ".zencoder/skills".to_string(),
                 global_skills_dir: Some(PATHS.home.join(".zencoder/skills")),
+                supports_project: true,
                 show_in_universal_list: true,
             },
             Self::Pochi => AgentConfig {
                 name: "pochi",
                 display_name: "Pochi",
-                skills_dir: ".pochi/skills",
+                skills_dir: ".pochi/skills".to_string(),
                 global_skills_dir: Some(PATHS.home.join(".pochi/skills")),
+                supports_project: true,
                 show_in_universal_list: true,
             },
             Self::Adal => AgentConfig {
                 name: "adal",
                 display_name: "AdaL",
-                skills_dir: ".adal/skills",
+                skills_dir: ".adal/skills".to_string(),
                 global_skills_dir: Some(PATHS.home.join(".adal/skills")),
+                supports_project: true,
                 show_in_universal_list: true,
             },
             // Cortex Code: Snowflake 的 AI 编码助手
@@ -520,8 +714,9 @@ impl AgentType {
             Self::Cortex => AgentConfig {
                 name: "cortex",
                 display_name: "Cortex Code",
-                skills_dir: ".cortex/skills",
+                skills_dir: ".cortex/skills".to_string(),
                 global_skills_dir: Some(PATHS.home.join(".snowflake/cortex/skills")),
+                supports_project: true,
                 show_in_universal_list: true,
             },
             // Universal: 统一的规范目录，不在列表中显示，不参与自动检测
@@ -529,8 +724,9 @@ impl AgentType {
             Self::Universal => AgentConfig {
                 name: "universal",
                 display_name: "Universal",
-                skills_dir: ".agents/skills",
+                skills_dir: ".agents/skills".to_string(),
                 global_skills_dir: Some(PATHS.config_home.join("agents/skills")),
+                supports_project: true,
                 show_in_universal_list: false,
             },
         }
@@ -552,9 +748,9 @@ impl AgentType {
 
     /// 检测 Agent 是否已安装
     /// 完整对应 CLI: 每个 agent 的 detectInstalled 函数
-    pub fn is_installed(&self) -> bool {
-        let cwd = std::env::current_dir().unwrap_or_default();
-
+    ///
+    /// 项目级信号（如 `.continue`、`.replit`）只在 `ctx` 包含项目目录时计入
+    pub fn is_installed(&self, ctx: &DetectionContext) -> bool {
         match self {
             Self::Amp => PATHS.config_home.join("amp").exists(),
             Self::Antigravity => PATHS.home.join(".gemini/antigravity").exists(),
@@ -567,13 +763,13 @@ impl AgentType {
             }
             Self::Cline => PATHS.home.join(".cline").exists(),
             Self::Codebuddy => {
-                cwd.join(".codebuddy").exists() || PATHS.home.join(".codebuddy").exists()
+                ctx.project_has(".codebuddy") || PATHS.home.join(".codebuddy").exists()
             }
             Self::Codex => {
                 PATHS.codex_home.exists() || std::path::Path::new("/etc/codex").exists()
             }
             Self::CommandCode => PATHS.home.join(".commandcode").exists(),
-            Self::Continue => cwd.join(".continue").exists() || PATHS.home.join(".continue").exists(),
+            Self::Continue => ctx.project_has(".continue") || PATHS.home.join(".continue").exists(),
             Self::Crush => PATHS.config_home.join("crush").exists(),
             Self::Cursor => PATHS.home.join(".cursor").exists(),
             Self::Droid => PATHS.home.join(".factory").exists(),
@@ -595,7 +791,7 @@ impl AgentType {
             Self::Pi => PATHS.home.join(".pi/agent").exists(),
             Self::Qoder => PATHS.home.join(".qoder").exists(),
             Self::QwenCode => PATHS.home.join(".qwen").exists(),
-            Self::Replit => cwd.join(".replit").exists(),
+            Self::Replit => ctx.project_has(".replit"),
             Self::Roo => PATHS.home.join(".roo").exists(),
             Self::Trae => PATHS.home.join(".trae").exists(),
             Self::TraeCn => PATHS.home.join(".trae-cn").exists(),
@@ -611,25 +807,29 @@ impl AgentType {
 
     /// 检测所有已安装的 Agent
     /// 对应 CLI: detectInstalledAgents (agents.ts:378-386)
-    pub fn detect_installed() -> Vec<AgentType> {
-        Self::all().filter(|agent| agent.is_installed()).collect()
+    pub fn detect_installed(ctx: &DetectionContext) -> Vec<AgentType> {
+        Self::all().filter(|agent| agent.is_installed(ctx)).collect()
     }
 
     /// 检查是否是 Universal Agent（使用 .agents/skills 目录）
     /// 对应 CLI: isUniversalAgent (agents.ts:418-420)
-    /// 用于安装逻辑判断是否跳过 symlink
+    /// 按内置定义判断，不受 agentDirOverrides 影响
     pub fn is_universal(&self) -> bool {
-        self.config().skills_dir == ".agents/skills"
+        self.builtin_config().skills_dir == ".agents/skills"
+    }
+
+    /// 该范围下是否直接读取 canonical 目录（用于安装逻辑判断是否跳过 symlink）
+    ///
+    /// Universal Agent 在对应范围设置了目录覆盖时，与普通 agent 一样安装到覆盖目录
+    pub fn shares_canonical_dir(&self, global: bool) -> bool {
+        self.is_universal() && !overrides_scope_dir(self.dir_override().as_ref(), global)
     }
 
     /// 获取 Universal Agents（用于 UI 显示）
     /// 对应 CLI: getUniversalAgents (agents.ts:397-403)
     pub fn get_universal_agents() -> Vec<AgentType> {
         Self::all()
-            .filter(|agent| {
-                let config = agent.config();
-                config.skills_dir == ".agents/skills" && config.show_in_universal_list
-            })
+            .filter(|agent| agent.is_universal() && agent.config().show_in_universal_list)
             .collect()
     }
 
@@ -637,14 +837,14 @@ impl AgentType {
     /// 对应 CLI: getNonUniversalAgents (agents.ts:409-413)
     pub fn get_non_universal_agents() -> Vec<AgentType> {
         Self::all()
-            .filter(|agent| agent.config().skills_dir != ".agents/skills")
+            .filter(|agent| !agent.is_universal())
             .collect()
     }
 
     /// 转换为 AgentInfo（前端使用）
     pub fn to_agent_info(&self) -> AgentInfo {
         let config = self.config();
-        let is_universal = config.skills_dir == ".agents/skills";
+        let is_universal = self.is_universal();
 
         AgentInfo {
             id: *self,
@@ -654,11 +854,32 @@ impl AgentType {
                 .global_skills_dir
                 .map(|p| p.to_string_lossy().to_string())
                 .unwrap_or_default(),
-            detected: self.is_installed(),
+            detected: self.is_installed(&DetectionContext::current_dir()),
             is_universal,
             show_in_universal_list: is_universal && config.show_in_universal_list,
         }
     }
+
+    /// 转换为 AgentPathInfo（项目级目录相对于 cwd 解析）
+    pub fn to_agent_path_info(&self, cwd: &Path) -> AgentPathInfo {
+        let config = self.config();
+        let skills_dir = cwd.join(config.skills_dir);
+
+        AgentPathInfo {
+            id: *self,
+            name: config.display_name.to_string(),
+            skills_dir_exists: skills_dir.is_dir(),
+            skills_dir: skills_dir.to_string_lossy().to_string(),
+            global_skills_dir_exists: config
+                .global_skills_dir
+                .as_ref()
+                .map(|p| p.is_dir())
+                .unwrap_or(false),
+            global_skills_dir: config
+                .global_skills_dir
+                .map(|p| p.to_string_lossy().to_string()),
+        }
+    }
 }
 
 #[cfg(test)]
@@ -725,9 +946,26 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_agent_path_info() {
+        let temp = tempfile::tempdir().unwrap();
+        std::fs::create_dir_all(temp.path().join(".claude/skills")).unwrap();
+
+        let info = AgentType::ClaudeCode.to_agent_path_info(temp.path());
+        assert_eq!(
+            info.skills_dir,
+            temp.path().join(".claude/skills").to_string_lossy()
+        );
+        assert!(info.skills_dir_exists);
+        assert!(info.global_skills_dir.is_some());
+
+        let info = AgentType::Cursor.to_agent_path_info(temp.path());
+        assert!(!info.skills_dir_exists);
+    }
+
     #[test]
     fn test_detect_installed_returns_vec() {
-        let installed = AgentType::detect_installed();
+        let installed = AgentType::detect_installed(&DetectionContext::current_dir());
         assert!(installed.len() <= 41);
     }
 
@@ -739,6 +977,84 @@ mod tests {
         assert!(!AgentType::Cortex.is_universal());
     }
 
+    #[test]
+    fn test_project_support_matches_skills_dir() {
+        // 项目目录由 supports_project 决定，Replit 是云端 IDE，不支持项目级安装
+        for agent in AgentType::all() {
+            let dir = crate::core::paths::agent_skills_dir(&agent, false, "/work");
+            assert_eq!(dir.is_some(), agent.config().supports_project, "{}", agent);
+        }
+        assert!(!AgentType::Replit.config().supports_project);
+        assert!(AgentType::Replit.config().global_skills_dir.is_some());
+    }
+
+    #[test]
+    fn test_apply_dir_override() {
+        let dir_override = AgentDirOverride {
+            skills_dir: Some("./.cursor/team-skills/".to_string()),
+            global_skills_dir: None,
+        };
+        let builtin = AgentType::Cursor.builtin_config();
+        let config = apply_dir_override(builtin.clone(), &dir_override);
+        assert_eq!(config.skills_dir, ".cursor/team-skills");
+        assert_eq!(config.global_skills_dir, builtin.global_skills_dir);
+
+        let dir_override = AgentDirOverride {
+            skills_dir: None,
+            global_skills_dir: Some("/data/cursor-skills".to_string()),
+        };
+        let config = apply_dir_override(builtin.clone(), &dir_override);
+        assert_eq!(config.skills_dir, builtin.skills_dir);
+        assert_eq!(config.global_skills_dir, Some(PathBuf::from("/data/cursor-skills")));
+    }
+
+    #[test]
+    fn test_overrides_scope_dir() {
+        let global_only = AgentDirOverride {
+            skills_dir: None,
+            global_skills_dir: Some("/data/cursor-skills".to_string()),
+        };
+        assert!(!overrides_scope_dir(None, true));
+        assert!(overrides_scope_dir(Some(&global_only), true));
+        assert!(!overrides_scope_dir(Some(&global_only), false));
+
+        // 项目目录覆盖不改变 Universal 判定（按内置定义）
+        let config = apply_dir_override(
+            AgentType::Cursor.builtin_config(),
+            &AgentDirOverride {
+                skills_dir: Some(".cursor/skills".to_string()),
+                global_skills_dir: None,
+            },
+        );
+        assert_ne!(config.skills_dir, ".agents/skills");
+        assert!(AgentType::Cursor.is_universal());
+    }
+
+    #[test]
+    fn test_validate_agent_dir_override() {
+        let valid = |skills_dir: Option<&str>, global_skills_dir: Option<&str>| {
+            validate_agent_dir_override(
+                &AgentType::Cursor,
+                &AgentDirOverride {
+                    skills_dir: skills_dir.map(String::from),
+                    global_skills_dir: global_skills_dir.map(String::from),
+                },
+            )
+            .is_ok()
+        };
+        let absolute = std::env::temp_dir().join("cursor-skills");
+        let absolute = absolute.to_str().unwrap();
+
+        assert!(valid(None, None));
+        assert!(valid(Some(".cursor/team-skills"), Some(absolute)));
+        assert!(valid(Some("./skills"), None));
+        assert!(!valid(None, Some("relative/skills")));
+        assert!(!valid(None, Some("")));
+        assert!(!valid(Some(absolute), None));
+        assert!(!valid(Some("../shared/skills"), None));
+        assert!(!valid(Some(""), None));
+    }
+
     #[test]
     fn test_universal_agent() {
         let config = AgentType::Universal.config();
@@ -747,7 +1063,7 @@ mod tests {
         assert!(AgentType::Universal.is_universal());
         assert!(!config.show_in_universal_list);
         // Universal 不参与自动检测
-        assert!(!AgentType::Universal.is_installed());
+        assert!(!AgentType::Universal.is_installed(&DetectionContext::current_dir()));
         // Universal 不在 universal list 中
         let universal_agents = AgentType::get_universal_agents();
         assert!(!universal_agents.contains(&AgentType::Universal));
@@ -757,6 +1073,20 @@ mod tests {
     fn test_replit_detection_changed() {
         // Replit 现在检查 .replit 而非 .agents
         // 这里只能验证不 panic
-        let _ = AgentType::Replit.is_installed();
+        let _ = AgentType::Replit.is_installed(&DetectionContext::current_dir());
+    }
+
+    #[test]
+    fn test_project_signals_require_project_context() {
+        let project = tempfile::tempdir().unwrap();
+        std::fs::create_dir(project.path().join(".replit")).unwrap();
+
+        // Replit 只有项目级信号
+        assert!(!AgentType::Replit.is_installed(&DetectionContext::GlobalOnly));
+        assert!(AgentType::Replit.is_installed(&DetectionContext::WithProject(
+            project.path().to_path_buf()
+        )));
+        assert!(!AgentType::detect_installed(&DetectionContext::GlobalOnly)
+            .contains(&AgentType::Replit));
     }
 }
@@ -0,0 +1,211 @@
+//! lock 文件的原子写入
+//!
+//! - 先写入同目录下的临时文件并 fsync，再 rename 覆盖目标文件：
+//!   写入中途崩溃时目标文件保持原样，不会出现被截断的 JSON
+//! - 写入期间持有 ~/.skill-deck/locks 下对应的文件锁（fs2），
+//!   串行化同时进行的安装 / 更新（含其他进程）对同一文件的写入；
+//!   `with_locked_file` 从读取到写回全程持锁，读-改-写不会丢失其他写入者的修改
+//! - 替换后的文件沿用原文件的权限（新文件为 0644），而不是临时文件的 0600
+
+use crate::core::paths::skill_deck_file_locks_dir;
+use crate::error::AppError;
+use fs2::FileExt;
+use sha2::{Digest, Sha256};
+use std::fs::{self, File, OpenOptions};
+use std::io::{self, ErrorKind, Write};
+use std::path::{Path, PathBuf};
+use tempfile::NamedTempFile;
+
+/// 原子写入文件（父目录不存在时创建）
+pub fn write_atomic(path: &Path, content: &[u8]) -> Result<(), AppError> {
+    write_atomic_with(path, &skill_deck_file_locks_dir(), |file| file.write_all(content))
+}
+
+/// 持有文件锁读取、修改并原子写回
+///
+/// `update` 收到当前内容（文件不存在时为 None），返回要写入的新内容（None 时不写入）和返回值。
+/// 读取到写回期间一直持有锁，同时进行的读-改-写（含其他进程）依次执行。
+pub fn with_locked_file<T>(
+    path: &Path,
+    update: impl FnOnce(Option<String>) -> Result<(Option<String>, T), AppError>,
+) -> Result<T, AppError> {
+    with_locked_file_in(path, &skill_deck_file_locks_dir(), update)
+}
+
+fn with_locked_file_in<T>(
+    path: &Path,
+    locks_dir: &Path,
+    update: impl FnOnce(Option<String>) -> Result<(Option<String>, T), AppError>,
+) -> Result<T, AppError> {
+    let lock = acquire_file_lock(path, locks_dir)?;
+    let current = match fs::read_to_string(path) {
+        Ok(content) => Some(content),
+        Err(e) if e.kind() == ErrorKind::NotFound => None,
+        Err(e) => return Err(e.into()),
+    };
+    let (content, value) = update(current)?;
+    if let Some(content) = content {
+        replace_file(path, |file| file.write_all(content.as_bytes()))?;
+    }
+    drop(lock);
+    Ok(value)
+}
+
+/// 原子写入，内容由 `write` 写入临时文件；`write` 失败时目标文件不变
+fn write_atomic_with(
+    path: &Path,
+    locks_dir: &Path,
+    write: impl FnOnce(&mut File) -> io::Result<()>,
+) -> Result<(), AppError> {
+    let lock = acquire_file_lock(path, locks_dir)?;
+    replace_file(path, write)?;
+    drop(lock);
+    Ok(())
+}
+
+/// 写入同目录下的临时文件后 rename 覆盖目标（调用方持有文件锁）
+fn replace_file(path: &Path, write: impl FnOnce(&mut File) -> io::Result<()>) -> Result<(), AppError> {
+    let parent = parent_dir(path);
+    fs::create_dir_all(parent)?;
+
+    // 临时文件与目标在同一目录，保证 rename 不跨文件系统；失败时随 drop 删除
+    let mut temp = NamedTempFile::new_in(parent)?;
+    write(temp.as_file_mut())?;
+    copy_permissions(temp.as_file(), path)?;
+    temp.as_file().sync_all()?;
+    temp.persist(path).map_err(|e| AppError::from(e.error))?;
+    Ok(())
+}
+
+/// 临时文件默认为 0600：沿用目标文件原有的权限，目标不存在时使用 0644
+fn copy_permissions(temp: &File, path: &Path) -> io::Result<()> {
+    match fs::metadata(path) {
+        Ok(metadata) => temp.set_permissions(metadata.permissions()),
+        #[cfg(unix)]
+        Err(_) => {
+            use std::os::unix::fs::PermissionsExt;
+            temp.set_permissions(fs::Permissions::from_mode(0o644))
+        }
+        #[cfg(not(unix))]
+        Err(_) => Ok(()),
+    }
+}
+
+fn parent_dir(path: &Path) -> &Path {
+    path.parent()
+        .filter(|p| !p.as_os_str().is_empty())
+        .unwrap_or(Path::new("."))
+}
+
+/// 获取 `path` 对应的排他文件锁（阻塞等待，文件关闭时释放）
+fn acquire_file_lock(path: &Path, locks_dir: &Path) -> Result<File, AppError> {
+    fs::create_dir_all(parent_dir(path))?;
+    fs::create_dir_all(locks_dir)?;
+    let file = OpenOptions::new()
+        .create(true)
+        .truncate(false)
+        .write(true)
+        .open(lock_file_path(path, locks_dir))?;
+    file.lock_exclusive()?;
+    Ok(file)
+}
+
+/// 锁文件路径：目标路径的 hash（同一文件的不同写法尽量规范化为同一把锁）
+fn lock_file_path(path: &Path, locks_dir: &Path) -> PathBuf {
+    let resolved = path
+        .parent()
+        .and_then(|p| p.canonicalize().ok())
+        .and_then(|p| path.file_name().map(|name| p.join(name)))
+        .unwrap_or_else(|| path.to_path_buf());
+    let hash = format!("{:x}", Sha256::digest(resolved.to_string_lossy().as_bytes()));
+    locks_dir.join(format!("{}.lock", &hash[..16]))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_partial_write_keeps_existing_file() {
+        let dir = tempdir().unwrap();
+        let locks = tempdir().unwrap();
+        let path = dir.path().join("skills-lock.json");
+
+        write_atomic_with(&path, locks.path(), |f| f.write_all(b"{\"version\": 1}\n")).unwrap();
+        assert_eq!(fs::read_to_string(&path).unwrap(), "{\"version\": 1}\n");
+
+        // 写到一半失败（模拟崩溃）：原文件不变，临时文件被清理
+        let result = write_atomic_with(&path, locks.path(), |f| {
+            f.write_all(b"{\"version\": 2, \"skil")?;
+            Err(io::Error::other("simulated crash"))
+        });
+        assert!(result.is_err());
+        assert_eq!(fs::read_to_string(&path).unwrap(), "{\"version\": 1}\n");
+        assert_eq!(fs::read_dir(dir.path()).unwrap().count(), 1);
+
+        write_atomic_with(&path, locks.path(), |f| f.write_all(b"{\"version\": 3}\n")).unwrap();
+        assert_eq!(fs::read_to_string(&path).unwrap(), "{\"version\": 3}\n");
+    }
+
+    #[test]
+    fn test_concurrent_writers_do_not_interleave() {
+        let dir = tempdir().unwrap();
+        let locks = tempdir().unwrap();
+        let path = dir.path().join(".skill-lock.json");
+
+        std::thread::scope(|scope| {
+            for i in 0..8 {
+                let (path, locks) = (&path, locks.path());
+                scope.spawn(move || {
+                    let content = format!("{{\"writer\": {}}}", i).repeat(1000);
+                    write_atomic_with(path, locks, |f| f.write_all(content.as_bytes())).unwrap();
+                });
+            }
+        });
+
+        let content = fs::read_to_string(&path).unwrap();
+        let first = &content[..content.find('}').unwrap() + 1];
+        assert_eq!(content, first.repeat(1000));
+    }
+
+    #[test]
+    fn test_locked_read_modify_write_keeps_all_updates() {
+        let dir = tempdir().unwrap();
+        let locks = tempdir().unwrap();
+        let path = dir.path().join("counter");
+
+        std::thread::scope(|scope| {
+            for _ in 0..8 {
+                let (path, locks) = (&path, locks.path());
+                scope.spawn(move || {
+                    with_locked_file_in(path, locks, |content| {
+                        let count: u32 = content.map(|c| c.parse().unwrap()).unwrap_or(0);
+                        Ok((Some((count + 1).to_string()), ()))
+                    })
+                    .unwrap();
+                });
+            }
+        });
+
+        assert_eq!(fs::read_to_string(&path).unwrap(), "8");
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_replace_keeps_permissions() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let dir = tempdir().unwrap();
+        let locks = tempdir().unwrap();
+        let path = dir.path().join("skills-lock.json");
+        let mode = |path: &Path| fs::metadata(path).unwrap().permissions().mode() & 0o777;
+
+        write_atomic_with(&path, locks.path(), |f| f.write_all(b"{}")).unwrap();
+        assert_eq!(mode(&path), 0o644);
+
+        fs::set_permissions(&path, fs::Permissions::from_mode(0o664)).unwrap();
+        write_atomic_with(&path, locks.path(), |f| f.write_all(b"{}")).unwrap();
+        assert_eq!(mode(&path), 0o664);
+    }
+}
@@ -2,14 +2,46 @@
 //!
 //! 对应 CLI: telemetry.ts fetchAuditData
 //! 调用 Vercel 的 audit API 获取 skill 风险等级
+//!
+//! 可通过 config.json 的 disableAudit 或 `SKILL_DECK_DISABLE_AUDIT` 环境变量完全关闭
+//! （离线环境下避免每次获取都等待超时）
 
+use crate::core::http_client::{api_client, with_user_agent};
+use crate::core::offline::is_offline;
+use crate::core::paths::read_skill_deck_config;
+use once_cell::sync::Lazy;
 use serde::{Deserialize, Serialize};
 use specta::Type;
 use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, Ordering};
 
-const AUDIT_URL: &str = "https://add-skill.vercel.sh/audit";
+pub(crate) const AUDIT_URL: &str = "https://add-skill.vercel.sh/audit";
 const AUDIT_TIMEOUT_SECS: u64 = 3;
 
+/// 关闭审计的环境变量（值为 1 / true / yes 时生效）
+const DISABLE_AUDIT_ENV: &str = "SKILL_DECK_DISABLE_AUDIT";
+
+/// config.json 中的 disableAudit
+/// 首次访问时从 config.json 加载，save_config 时通过 set_audit_disabled 同步
+static CONFIGURED_AUDIT_DISABLED: Lazy<AtomicBool> = Lazy::new(|| {
+    AtomicBool::new(read_skill_deck_config().is_some_and(|c| c.disable_audit))
+});
+
+/// 更新内存中的 disableAudit（save_config 后调用）
+pub fn set_audit_disabled(disabled: bool) {
+    CONFIGURED_AUDIT_DISABLED.store(disabled, Ordering::Relaxed);
+}
+
+/// 审计是否被关闭（配置或环境变量任一开启即关闭）
+pub fn is_audit_disabled() -> bool {
+    CONFIGURED_AUDIT_DISABLED.load(Ordering::Relaxed)
+        || std::env::var(DISABLE_AUDIT_ENV).is_ok_and(|v| is_truthy(&v))
+}
+
+fn is_truthy(value: &str) -> bool {
+    matches!(value.trim().to_lowercase().as_str(), "1" | "true" | "yes")
+}
+
 /// 风险等级
 #[derive(Debug, Clone, Serialize, Deserialize, Type)]
 #[serde(rename_all = "lowercase")]
@@ -39,21 +71,20 @@ pub struct SkillAuditData {
 /// 获取 skill 的安全审计数据
 ///
 /// 对应 CLI: fetchAuditData (telemetry.ts)
-/// 3 秒超时，失败返回 None（graceful degradation）
+/// 3 秒超时，失败返回 None（graceful degradation）；审计关闭或离线模式时不发起请求，直接返回 None
 pub async fn fetch_audit_data(
     source: &str,
     skills: &[String],
 ) -> Option<HashMap<String, SkillAuditData>> {
-    let client = reqwest::Client::builder()
-        .timeout(std::time::Duration::from_secs(AUDIT_TIMEOUT_SECS))
-        .build()
-        .ok()?;
+    if is_audit_disabled() || is_offline() {
+        return None;
+    }
 
     let skills_param = skills.join(",");
 
-    let response = client
-        .get(AUDIT_URL)
+    let response = with_user_agent(api_client().get(AUDIT_URL))
         .query(&[("source", source), ("skills", &skills_param)])
+        .timeout(std::time::Duration::from_secs(AUDIT_TIMEOUT_SECS))
         .send()
         .await
         .ok()?;
@@ -67,3 +98,31 @@ pub async fn fetch_audit_data(
         .await
         .ok()
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_is_truthy() {
+        assert!(is_truthy("1"));
+        assert!(is_truthy(" TRUE "));
+        assert!(is_truthy("yes"));
+        assert!(!is_truthy("0"));
+        assert!(!is_truthy(""));
+    }
+
+    #[test]
+    fn test_disabled_audit_skips_request() {
+        set_audit_disabled(true);
+        let disabled = is_audit_disabled();
+        let result = tauri::async_runtime::block_on(fetch_audit_data(
+            "owner/repo",
+            &["demo".to_string()],
+        ));
+        set_audit_disabled(false);
+
+        assert!(disabled);
+        assert!(result.is_none());
+    }
+}
@@ -0,0 +1,216 @@
+//! Skill 变更历史
+//!
+//! 在较深的克隆（`CloneDepth::Depth(CHANGELOG_CLONE_DEPTH)`）中用 `git log -- <skill 目录>`
+//! 列出修改过该 skill 的提交，截止到已安装的版本：
+//! - lock 中记录的目录 tree hash（global `skillFolderHash` / 项目 `remoteHash`）与某个提交中
+//!   该目录的 tree 相同时，该提交即已安装版本，只返回之后的提交
+//! - 找不到匹配的提交时按 lock 中的更新 / 安装时间过滤
+//!
+//! lock 不记录安装时的 commit，因此通过 tree hash 反查。
+
+use crate::core::git_binary::{git_not_found_error, git_program};
+use crate::error::AppError;
+use serde::Serialize;
+use specta::Type;
+use std::path::Path;
+use std::process::Command;
+
+/// 获取变更历史时的克隆深度（提交数）
+pub const CHANGELOG_CLONE_DEPTH: u32 = 500;
+
+/// 最多返回的提交数
+const MAX_CHANGELOG_COMMITS: usize = 200;
+
+/// git log 字段分隔符（%x1f）
+const FIELD_SEPARATOR: char = '\u{1f}';
+
+/// 修改过 skill 目录的提交
+#[derive(Debug, Clone, Serialize, Type)]
+#[serde(rename_all = "camelCase")]
+#[specta(rename_all = "camelCase")]
+pub struct SkillCommit {
+    pub sha: String,
+    pub author: String,
+    /// 提交时间（ISO 8601）
+    pub date: String,
+    /// 提交标题（第一行）
+    pub subject: String,
+}
+
+/// skill 自安装以来的变更历史
+#[derive(Debug, Clone, Serialize, Type)]
+#[serde(rename_all = "camelCase")]
+#[specta(rename_all = "camelCase")]
+pub struct SkillChangelog {
+    /// skill 在仓库中的目录（根目录为空串）
+    pub folder: String,
+    /// 与已安装版本对应的提交（按 tree hash 找到时）
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub installed_commit: Option<String>,
+    /// 安装之后的提交，最新的在前
+    pub commits: Vec<SkillCommit>,
+    /// 提交数超过上限或克隆深度不足以找到已安装版本，列表不完整
+    pub truncated: bool,
+}
+
+/// 列出仓库中 `folder` 自已安装版本以来的提交
+///
+/// # Arguments
+/// * `repo` - 克隆的仓库目录
+/// * `folder` - skill 目录（相对仓库根目录，根目录为空串）
+/// * `installed_hash` - lock 中记录的目录 tree hash
+/// * `installed_at` - lock 中记录的更新 / 安装时间（tree hash 找不到时使用）
+pub fn skill_changelog(
+    repo: &Path,
+    folder: &str,
+    installed_hash: Option<&str>,
+    installed_at: Option<&str>,
+) -> Result<SkillChangelog, AppError> {
+    let folder = folder.trim_matches('/');
+    let mut commits = folder_log(repo, folder, None)?;
+    let mut installed_commit = None;
+
+    if let Some(hash) = installed_hash.filter(|h| !h.is_empty()) {
+        if let Some(pos) = commits
+            .iter()
+            .position(|c| folder_tree(repo, &c.sha, folder).as_deref() == Some(hash))
+        {
+            installed_commit = Some(commits[pos].sha.clone());
+            commits.truncate(pos);
+        }
+    }
+    if installed_commit.is_none() {
+        if let Some(since) = installed_at.filter(|t| !t.is_empty()) {
+            commits = folder_log(repo, folder, Some(since))?;
+        }
+    }
+
+    // 克隆深度用尽时最早的提交可能并非该目录的第一次修改
+    let truncated = commits.len() > MAX_CHANGELOG_COMMITS
+        || (installed_commit.is_none() && installed_hash.is_some() && is_shallow(repo));
+    commits.truncate(MAX_CHANGELOG_COMMITS);
+
+    Ok(SkillChangelog {
+        folder: folder.to_string(),
+        installed_commit,
+        commits,
+        truncated,
+    })
+}
+
+/// `git log` 修改过 `folder` 的提交（最新的在前）
+fn folder_log(repo: &Path, folder: &str, since: Option<&str>) -> Result<Vec<SkillCommit>, AppError> {
+    let mut args = vec![
+        "log".to_string(),
+        "--format=%H%x1f%an%x1f%aI%x1f%s".to_string(),
+        format!("--max-count={}", MAX_CHANGELOG_COMMITS + 1),
+    ];
+    if let Some(since) = since {
+        args.push(format!("--since={}", since));
+    }
+    args.push("--".to_string());
+    args.push(if folder.is_empty() { ".".to_string() } else { folder.to_string() });
+
+    let stdout = run_git(repo, &args)?;
+    Ok(stdout
+        .lines()
+        .filter_map(|line| {
+            let mut fields = line.splitn(4, FIELD_SEPARATOR);
+            Some(SkillCommit {
+                sha: fields.next()?.to_string(),
+                author: fields.next()?.to_string(),
+                date: fields.next()?.to_string(),
+                subject: fields.next().unwrap_or_default().to_string(),
+            })
+        })
+        .collect())
+}
+
+/// 提交中 `folder` 的 tree hash
+fn folder_tree(repo: &Path, sha: &str, folder: &str) -> Option<String> {
+    let spec = if folder.is_empty() {
+        format!("{}^{{tree}}", sha)
+    } else {
+        format!("{}:{}", sha, folder)
+    };
+    run_git(repo, &["rev-parse".to_string(), spec])
+        .ok()
+        .map(|s| s.trim().to_string())
+}
+
+fn is_shallow(repo: &Path) -> bool {
+    run_git(repo, &["rev-parse".to_string(), "--is-shallow-repository".to_string()])
+        .is_ok_and(|s| s.trim() == "true")
+}
+
+fn run_git(repo: &Path, args: &[String]) -> Result<String, AppError> {
+    let output = Command::new(git_program())
+        .env("GIT_TERMINAL_PROMPT", "0")
+        .arg("-C")
+        .arg(repo)
+        .args(args)
+        .output()
+        .map_err(|e| match e.kind() {
+            std::io::ErrorKind::NotFound => git_not_found_error(),
+            _ => AppError::from(e),
+        })?;
+    if !output.status.success() {
+        return Err(AppError::Custom {
+            message: format!(
+                "git {} failed: {}",
+                args[0],
+                String::from_utf8_lossy(&output.stderr).trim()
+            ),
+        });
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[cfg(unix)]
+    #[test]
+    fn test_changelog_since_installed_tree() {
+        let repo = tempfile::tempdir().unwrap();
+        let git = |args: &[&str]| {
+            let output = Command::new("git")
+                .arg("-C")
+                .arg(repo.path())
+                .args(["-c", "user.name=test", "-c", "user.email=test@example.com"])
+                .args(args)
+                .output()
+                .unwrap();
+            assert!(output.status.success());
+            String::from_utf8_lossy(&output.stdout).trim().to_string()
+        };
+        let skill_dir = repo.path().join("skills/demo");
+        std::fs::create_dir_all(&skill_dir).unwrap();
+
+        git(&["init", "--quiet"]);
+        std::fs::write(skill_dir.join("SKILL.md"), "# v1").unwrap();
+        git(&["add", "."]);
+        git(&["commit", "--quiet", "-m", "demo v1"]);
+        let installed_tree = git(&["rev-parse", "HEAD:skills/demo"]);
+        let installed_sha = git(&["rev-parse", "HEAD"]);
+
+        std::fs::write(repo.path().join("README.md"), "unrelated").unwrap();
+        git(&["add", "."]);
+        git(&["commit", "--quiet", "-m", "readme"]);
+        std::fs::write(skill_dir.join("SKILL.md"), "# v2").unwrap();
+        git(&["commit", "--quiet", "-am", "demo v2"]);
+
+        let changelog = skill_changelog(repo.path(), "skills/demo/", Some(&installed_tree), None).unwrap();
+        assert_eq!(changelog.folder, "skills/demo");
+        assert_eq!(changelog.installed_commit.as_deref(), Some(installed_sha.as_str()));
+        let subjects: Vec<&str> = changelog.commits.iter().map(|c| c.subject.as_str()).collect();
+        assert_eq!(subjects, vec!["demo v2"]);
+        assert!(!changelog.truncated);
+
+        // 没有已安装版本信息：返回全部修改过该目录的提交
+        let all = skill_changelog(repo.path(), "skills/demo", None, None).unwrap();
+        assert_eq!(all.commits.len(), 2);
+        assert!(all.installed_commit.is_none());
+    }
+}
@@ -0,0 +1,316 @@
+//! Skill 依赖树解析
+//!
+//! SKILL.md frontmatter 的 `dependencies` 列出依赖的 skill，每项为：
+//! - 同一来源中的 skill 名称（如 `shared-utils`）
+//! - 来源字符串，用 `@skill` 指定 skill（如 `owner/repo@shared-utils`）；
+//!   来源中只有一个 skill 时可以省略 `@skill`
+//!
+//! 从根 skill 开始逐层加载依赖的来源（同一来源只加载一次），构建依赖图，
+//! 记录环和无法解析的依赖。只做分析，不安装任何内容。
+//! 递归深度和加载的来源数量都有上限，避免无限制地克隆仓库。
+
+use crate::core::source_parser::parse_source;
+use crate::error::AppError;
+use crate::models::{DependencyEdge, DependencyGraph, DependencyNode, ParsedSource, UnresolvedDependency};
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::future::Future;
+
+/// 默认最大依赖深度
+pub const DEFAULT_MAX_DEPENDENCY_DEPTH: u32 = 5;
+
+/// 最多加载的来源数量（含根来源）
+const MAX_DEPENDENCY_SOURCES: usize = 20;
+
+/// 来源中的一个 skill（名称与声明的依赖）
+#[derive(Debug, Clone)]
+pub struct SourceSkill {
+    pub name: String,
+    pub dependencies: Vec<String>,
+}
+
+/// 解析 `skill_name` 的依赖图
+///
+/// # Arguments
+/// * `root` - 根 skill 所在来源
+/// * `skill_name` - 根 skill 名称
+/// * `max_depth` - 最大依赖深度（根 skill 的直接依赖为第 1 层）
+/// * `load` - 加载来源中的全部 skill（通常需要克隆或下载）
+pub async fn resolve_dependency_graph<F, Fut>(
+    root: ParsedSource,
+    skill_name: &str,
+    max_depth: u32,
+    mut load: F,
+) -> Result<DependencyGraph, AppError>
+where
+    F: FnMut(ParsedSource) -> Fut,
+    Fut: Future<Output = Result<Vec<SourceSkill>, AppError>>,
+{
+    let root_label = source_label(&root);
+    let root_skills = load(root.clone()).await?;
+    let root_skill = root_skills
+        .iter()
+        .find(|s| s.name == skill_name)
+        .cloned()
+        .ok_or(AppError::NoSkillsFound)?;
+
+    let root_id = node_id(&root_label, &root_skill.name);
+    let mut graph = DependencyGraph {
+        root: root_id.clone(),
+        nodes: vec![DependencyNode {
+            id: root_id.clone(),
+            name: root_skill.name.clone(),
+            source: root_label.clone(),
+        }],
+        edges: Vec::new(),
+        cycles: Vec::new(),
+        unresolved: Vec::new(),
+        truncated: false,
+    };
+
+    // 来源 → 加载结果（失败原因同样缓存，避免重复克隆）
+    let mut loaded: HashMap<String, Result<Vec<SourceSkill>, String>> = HashMap::new();
+    loaded.insert(root_label, Ok(root_skills));
+
+    let mut queue = VecDeque::from([(root_id, root, root_skill.dependencies, 0u32)]);
+    while let Some((from, parent, dependencies, depth)) = queue.pop_front() {
+        for spec in dependencies {
+            let mut unresolved = |reason: String| {
+                graph.unresolved.push(UnresolvedDependency {
+                    from: from.clone(),
+                    dependency: spec.clone(),
+                    reason,
+                })
+            };
+
+            if depth >= max_depth {
+                unresolved(format!("Maximum dependency depth ({}) reached", max_depth));
+                graph.truncated = true;
+                continue;
+            }
+            let (target, wanted) = match dependency_target(&spec, &parent) {
+                Ok(t) => t,
+                Err(e) => {
+                    unresolved(e.to_string());
+                    continue;
+                }
+            };
+
+            let label = source_label(&target);
+            if !loaded.contains_key(&label) {
+                if loaded.len() >= MAX_DEPENDENCY_SOURCES {
+                    unresolved(format!("Too many dependency sources (limit {})", MAX_DEPENDENCY_SOURCES));
+                    graph.truncated = true;
+                    continue;
+                }
+                let result = load(target.clone()).await.map_err(|e| e.to_string());
+                loaded.insert(label.clone(), result);
+            }
+            let skill = match &loaded[&label] {
+                Ok(skills) => pick_skill(skills, wanted.as_deref()),
+                Err(reason) => Err(reason.clone()),
+            };
+            let skill = match skill {
+                Ok(s) => s.clone(),
+                Err(reason) => {
+                    unresolved(reason);
+                    continue;
+                }
+            };
+
+            let to = node_id(&label, &skill.name);
+            if !graph.nodes.iter().any(|n| n.id == to) {
+                graph.nodes.push(DependencyNode {
+                    id: to.clone(),
+                    name: skill.name.clone(),
+                    source: label,
+                });
+                queue.push_back((to.clone(), target, skill.dependencies, depth + 1));
+            }
+            if !graph.edges.iter().any(|e| e.from == from && e.to == to) {
+                graph.edges.push(DependencyEdge { from: from.clone(), to });
+            }
+        }
+    }
+
+    graph.cycles = find_cycles(&graph.edges);
+    Ok(graph)
+}
+
+/// 解析单个依赖声明，返回 (来源, 指定的 skill 名称)
+///
+/// 不含 `/`、`:`、`@` 且不以 `.`、`~` 开头的视为同一来源中的 skill 名称
+fn dependency_target(spec: &str, parent: &ParsedSource) -> Result<(ParsedSource, Option<String>), AppError> {
+    let spec = spec.trim();
+    let is_bare_name = !spec.is_empty()
+        && !spec.contains(['/', '\\', ':', '@'])
+        && !spec.starts_with(['.', '~']);
+    if is_bare_name {
+        let mut source = parent.clone();
+        source.skill_filter = None;
+        return Ok((source, Some(spec.to_string())));
+    }
+    let mut source = parse_source(spec)?;
+    let wanted = source.skill_filter.take();
+    Ok((source, wanted))
+}
+
+/// 在来源中选出依赖的 skill；未指定名称时来源必须只有一个 skill
+fn pick_skill<'a>(skills: &'a [SourceSkill], wanted: Option<&str>) -> Result<&'a SourceSkill, String> {
+    match wanted {
+        Some(name) => skills
+            .iter()
+            .find(|s| s.name == name)
+            .ok_or_else(|| format!("Skill '{}' not found in source", name)),
+        None => match skills {
+            [only] => Ok(only),
+            [] => Err("No skills found in source".to_string()),
+            _ => Err(format!(
+                "Source contains {} skills; specify one with @skill",
+                skills.len()
+            )),
+        },
+    }
+}
+
+/// 来源的显示名称，同时作为去重 key（含子路径和 ref）
+fn source_label(parsed: &ParsedSource) -> String {
+    let mut label = match &parsed.local_path {
+        Some(path) => path.to_string_lossy().to_string(),
+        None => parsed.url.clone(),
+    };
+    let subpaths = parsed.search_subpaths();
+    if !subpaths.is_empty() {
+        label = format!("{}/{}", label.trim_end_matches('/'), subpaths.join(","));
+    }
+    if let Some(r) = &parsed.git_ref {
+        label = format!("{}#{}", label, r);
+    }
+    label
+}
+
+fn node_id(source: &str, name: &str) -> String {
+    format!("{}@{}", source, name)
+}
+
+/// 找出依赖图中的环（每个环为节点 id 列表，首尾节点相同时省略结尾）
+fn find_cycles(edges: &[DependencyEdge]) -> Vec<Vec<String>> {
+    let mut adjacency: HashMap<&str, Vec<&str>> = HashMap::new();
+    for edge in edges {
+        adjacency.entry(edge.from.as_str()).or_default().push(edge.to.as_str());
+    }
+
+    fn visit<'a>(
+        node: &'a str,
+        adjacency: &HashMap<&'a str, Vec<&'a str>>,
+        stack: &mut Vec<&'a str>,
+        done: &mut HashSet<&'a str>,
+        cycles: &mut Vec<Vec<String>>,
+    ) {
+        stack.push(node);
+        for &next in adjacency.get(node).into_iter().flatten() {
+            if let Some(pos) = stack.iter().position(|n| *n == next) {
+                cycles.push(stack[pos..].iter().map(|n| n.to_string()).collect());
+            } else if !done.contains(next) {
+                visit(next, adjacency, stack, done, cycles);
+            }
+        }
+        stack.pop();
+        done.insert(node);
+    }
+
+    let mut cycles = Vec::new();
+    let mut done = HashSet::new();
+    for edge in edges {
+        if !done.contains(edge.from.as_str()) {
+            visit(edge.from.as_str(), &adjacency, &mut Vec::new(), &mut done, &mut cycles);
+        }
+    }
+    cycles
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn skill(name: &str, deps: &[&str]) -> SourceSkill {
+        SourceSkill {
+            name: name.to_string(),
+            dependencies: deps.iter().map(|d| d.to_string()).collect(),
+        }
+    }
+
+    fn resolve(max_depth: u32) -> DependencyGraph {
+        let sources: HashMap<String, Vec<SourceSkill>> = HashMap::from([
+            (
+                "https://github.com/owner/app".to_string(),
+                vec![
+                    skill("app", &["lib", "owner/util@util", "missing", "owner/multi"]),
+                    skill("lib", &["app"]),
+                ],
+            ),
+            ("https://github.com/owner/util".to_string(), vec![skill("util", &["owner/deep"])]),
+            ("https://github.com/owner/deep".to_string(), vec![skill("deep", &[])]),
+            (
+                "https://github.com/owner/multi".to_string(),
+                vec![skill("one", &[]), skill("two", &[])],
+            ),
+        ]);
+        let root = parse_source("owner/app").unwrap();
+        tauri::async_runtime::block_on(resolve_dependency_graph(root, "app", max_depth, |parsed| {
+            let result = sources.get(&parsed.url).cloned().ok_or(AppError::NoSkillsFound);
+            async move { result }
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_resolve_dependency_graph() {
+        let graph = resolve(DEFAULT_MAX_DEPENDENCY_DEPTH);
+        let ids: Vec<&str> = graph.nodes.iter().map(|n| n.id.as_str()).collect();
+        assert_eq!(graph.root, "https://github.com/owner/app@app");
+        assert_eq!(
+            ids,
+            vec![
+                "https://github.com/owner/app@app",
+                "https://github.com/owner/app@lib",
+                "https://github.com/owner/util@util",
+                "https://github.com/owner/deep@deep",
+            ]
+        );
+        assert_eq!(graph.edges.len(), 4);
+
+        // app → lib → app
+        assert_eq!(
+            graph.cycles,
+            vec![vec![
+                "https://github.com/owner/app@app".to_string(),
+                "https://github.com/owner/app@lib".to_string(),
+            ]]
+        );
+
+        let unresolved: Vec<&str> = graph.unresolved.iter().map(|u| u.dependency.as_str()).collect();
+        assert_eq!(unresolved, vec!["missing", "owner/multi"]);
+        assert!(graph.unresolved[1].reason.contains("specify one with @skill"));
+        assert!(!graph.truncated);
+    }
+
+    #[test]
+    fn test_resolve_dependency_graph_depth_limit() {
+        let graph = resolve(1);
+        assert!(graph.truncated);
+        assert!(!graph.nodes.iter().any(|n| n.name == "deep"));
+        assert!(graph
+            .unresolved
+            .iter()
+            .any(|u| u.dependency == "owner/deep" && u.reason.contains("depth")));
+    }
+
+    #[test]
+    fn test_root_skill_not_found() {
+        let root = parse_source("owner/app").unwrap();
+        let result = tauri::async_runtime::block_on(resolve_dependency_graph(root, "nope", 5, |_| async {
+            Ok(vec![skill("app", &[])])
+        }));
+        assert!(matches!(result, Err(AppError::NoSkillsFound)));
+    }
+}
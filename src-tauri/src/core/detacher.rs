@@ -0,0 +1,188 @@
+//! Skill 脱离 canonical 目录
+//!
+//! 将 symlink 模式的 agent 目录替换为 canonical 内容的独立副本，可单独修改。
+//! 脱离的 agents 记录在 lock 条目的 `detachedAgents` 中，update / reinstall 只更新
+//! canonical 目录和其他 agents，不会覆盖这些副本。
+//!
+//! 替换复用安装时的暂存流程：先复制到同级暂存目录，再移走 symlink 并 rename 到位，
+//! 失败时恢复原 symlink。直接读取 canonical 目录的 agent（Universal Agents）无法脱离。
+
+use crate::core::agents::AgentType;
+use crate::core::installer::stage_and_replace;
+use crate::core::local_lock::update_local_lock;
+use crate::core::paths::{agent_skills_dir, canonical_skills_dir, is_symlink_or_junction, same_path};
+use crate::core::skill::skill_dir_name;
+use crate::core::skill_lock::update_skill_lock;
+use crate::error::AppError;
+use crate::models::{DetachResult, Scope};
+use std::path::Path;
+
+/// 将指定 agents 的 symlink 替换为 canonical 目录的独立副本
+///
+/// # Arguments
+/// * `skill_name` - skill 名称
+/// * `scope` - 范围（Global/Project）
+/// * `project_path` - Project scope 时的项目路径
+/// * `agents` - 目标 agents（None = 所有已安装该 skill 的 agents，未安装的不出现在结果中）
+pub fn detach_skill(
+    skill_name: &str,
+    scope: &Scope,
+    project_path: Option<&str>,
+    agents: Option<&[AgentType]>,
+) -> Result<DetachResult, AppError> {
+    let is_global = matches!(scope, Scope::Global);
+    let cwd = project_path.unwrap_or(".");
+    let dir_name = skill_dir_name(skill_name, is_global, cwd);
+    let canonical_base = canonical_skills_dir(is_global, cwd);
+    let canonical_dir = canonical_base.join(&dir_name);
+
+    if !canonical_dir.join("SKILL.md").is_file() {
+        return Err(AppError::PathNotFound {
+            path: canonical_dir.to_string_lossy().to_string(),
+        });
+    }
+
+    let explicit = agents.is_some();
+    let targets: Vec<AgentType> = match agents {
+        Some(list) => list.to_vec(),
+        None => AgentType::all().collect(),
+    };
+
+    let mut result = DetachResult {
+        detached: Vec::new(),
+        already_standalone: Vec::new(),
+        skipped: Vec::new(),
+    };
+
+    for agent in targets {
+        match detach_agent(&agent, is_global, cwd, &canonical_base, &canonical_dir, &dir_name)? {
+            AgentOutcome::Detached => {
+                log::info!("Detached {} for {}", skill_name, agent);
+                result.detached.push(agent.to_string());
+            }
+            AgentOutcome::Standalone => result.already_standalone.push(agent.to_string()),
+            AgentOutcome::Skipped(reason) => {
+                if explicit {
+                    result.skipped.push((agent.to_string(), reason.to_string()));
+                }
+            }
+        }
+    }
+
+    if !result.detached.is_empty() {
+        record_detached(skill_name, is_global, cwd, &result.detached)?;
+    }
+
+    Ok(result)
+}
+
+/// 将脱离的 agents 记录到对应 scope 的 lock 条目（没有 lock 条目时不记录）
+fn record_detached(skill_name: &str, is_global: bool, cwd: &str, agents: &[String]) -> Result<(), AppError> {
+    let merge = |detached: &mut Vec<String>| {
+        for agent in agents {
+            if !detached.contains(agent) {
+                detached.push(agent.clone());
+            }
+        }
+    };
+    if is_global {
+        update_skill_lock(|lock| lock.skills.get_mut(skill_name).map(|e| merge(&mut e.detached_agents)))?;
+    } else {
+        update_local_lock(cwd, |lock| lock.skills.get_mut(skill_name).map(|e| merge(&mut e.detached_agents)))?;
+    }
+    Ok(())
+}
+
+/// 单个 agent 的处理结果
+enum AgentOutcome {
+    Detached,
+    Standalone,
+    Skipped(&'static str),
+}
+
+fn detach_agent(
+    agent: &AgentType,
+    is_global: bool,
+    cwd: &str,
+    canonical_base: &Path,
+    canonical_dir: &Path,
+    dir_name: &str,
+) -> Result<AgentOutcome, AppError> {
+    let Some(base) = agent_skills_dir(agent, is_global, cwd) else {
+        return Ok(AgentOutcome::Skipped("agent does not support this scope"));
+    };
+    if same_path(&base, canonical_base) {
+        return Ok(AgentOutcome::Skipped("agent reads the canonical directory directly"));
+    }
+
+    let agent_dir = base.join(dir_name);
+    if is_symlink_or_junction(&agent_dir) {
+        if !same_path(&agent_dir, canonical_dir) {
+            return Ok(AgentOutcome::Skipped("link does not point to the canonical directory"));
+        }
+        // 暂存副本就位前 symlink 保持不变；rename 失败时恢复原 symlink
+        stage_and_replace(canonical_dir, &agent_dir)?;
+        Ok(AgentOutcome::Detached)
+    } else if agent_dir.join("SKILL.md").is_file() {
+        Ok(AgentOutcome::Standalone)
+    } else {
+        Ok(AgentOutcome::Skipped("skill is not installed for this agent"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use tempfile::tempdir;
+
+    #[cfg(unix)]
+    #[test]
+    fn test_detach_project_skill() {
+        let project = tempdir().unwrap();
+        let cwd = project.path().to_string_lossy().to_string();
+        let canonical = project.path().join(".agents/skills/demo");
+        fs::create_dir_all(canonical.join("scripts")).unwrap();
+        fs::write(canonical.join("SKILL.md"), "---\nname: demo\ndescription: Demo\n---\n").unwrap();
+        fs::write(canonical.join("scripts/run.sh"), "echo hi\n").unwrap();
+
+        // Claude Code: symlink；Augment: copy
+        let claude = project.path().join(".claude/skills");
+        fs::create_dir_all(&claude).unwrap();
+        std::os::unix::fs::symlink("../../.agents/skills/demo", claude.join("demo")).unwrap();
+        let augment = project.path().join(".augment/skills/demo");
+        fs::create_dir_all(&augment).unwrap();
+        fs::write(augment.join("SKILL.md"), "---\nname: demo\ndescription: Demo\n---\n").unwrap();
+
+        let agents = [AgentType::ClaudeCode, AgentType::Augment, AgentType::Cursor];
+        let result = detach_skill("demo", &Scope::Project, Some(&cwd), Some(&agents)).unwrap();
+        assert_eq!(result.detached, vec!["claude-code".to_string()]);
+        assert_eq!(result.already_standalone, vec!["augment".to_string()]);
+        assert_eq!(result.skipped.len(), 1);
+        assert_eq!(result.skipped[0].0, "cursor");
+
+        let detached = claude.join("demo");
+        assert!(!is_symlink_or_junction(&detached));
+        assert_eq!(fs::read_to_string(detached.join("scripts/run.sh")).unwrap(), "echo hi\n");
+
+        // canonical 的后续修改不再影响脱离后的副本
+        fs::write(canonical.join("SKILL.md"), "---\nname: demo\ndescription: Changed\n---\n").unwrap();
+        assert!(fs::read_to_string(detached.join("SKILL.md")).unwrap().contains("description: Demo"));
+
+        // 再次执行时两者均已是独立副本
+        let result = detach_skill("demo", &Scope::Project, Some(&cwd), None).unwrap();
+        assert!(result.detached.is_empty());
+        assert!(result.already_standalone.contains(&"claude-code".to_string()));
+        assert!(result.skipped.is_empty());
+    }
+
+    #[test]
+    fn test_detach_missing_skill() {
+        let project = tempdir().unwrap();
+        let cwd = project.path().to_string_lossy().to_string();
+        assert!(matches!(
+            detach_skill("missing", &Scope::Project, Some(&cwd), None),
+            Err(AppError::PathNotFound { .. })
+        ));
+    }
+}
@@ -4,29 +4,38 @@
 //! - 扫描目录查找 SKILL.md 文件
 //! - 解析 frontmatter 获取 skill 信息
 //! - 支持 internal skills 过滤
+//! - 按名称 glob（globset 语法）过滤
+//! - 存在 .claude-plugin/ manifest 时优先使用其声明的 skill 目录，并标记所属 plugin
+//! - 记录被跳过的 SKILL.md 及原因（解析失败、缺少字段、internal）
+//! - 读取同级 metadata.json 中的 category / iconUrl（格式错误时忽略）
+//! - 记录 SKILL.md 旁的本地化版本（`SKILL.<locale>.md`）；skill 仍以 SKILL.md 识别
 //!
-//! 与 CLI skills.ts 行为一致
+//! 启发式搜索（优先目录、递归深度、跳过的目录）与 CLI skills.ts 一致；
+//! plugin manifest 优先、metadata.json 和本地化 SKILL.md 为本项目的扩展，CLI 没有对应行为
 
-use crate::core::skill::parse_skill_md;
+use crate::core::skill::{parse_skill_md, read_skill_sidecar, skill_md_locales};
 use crate::error::AppError;
-use crate::models::AvailableSkill;
-use std::collections::HashSet;
+use crate::models::{AvailableSkill, SkillWarning, SkillWarningReason};
+use globset::{GlobBuilder, GlobMatcher};
+use std::collections::{HashMap, HashSet};
 use std::path::{Path, PathBuf};
 use walkdir::WalkDir;
 
 /// 发现时跳过的目录（与 CLI 一致）
 const SKIP_DIRS: &[&str] = &["node_modules", ".git", "dist", "build", "__pycache__"];
 
-/// 最大递归深度（与 CLI 一致）
-const MAX_DEPTH: usize = 5;
+/// 默认最大递归深度（与 CLI 一致）
+pub const DEFAULT_MAX_DEPTH: usize = 5;
 
 /// 发现选项
-#[derive(Debug, Default)]
+#[derive(Debug, Default, Clone, Copy)]
 pub struct DiscoverOptions {
     /// 是否包含 internal skills
     pub include_internal: bool,
     /// 是否进行深度递归搜索（即使已找到 skills）
     pub full_depth: bool,
+    /// 递归搜索的最大深度，None 时使用 `DEFAULT_MAX_DEPTH`（不影响优先目录扫描）
+    pub max_depth: Option<usize>,
 }
 
 /// 发现的 Skill 信息
@@ -39,6 +48,16 @@ pub struct DiscoveredSkill {
     pub is_internal: bool,
     /// 所属 plugin 名称（来自 .claude-plugin/ manifest）
     pub plugin_name: Option<String>,
+    /// frontmatter 中的可选字段
+    pub version: Option<String>,
+    pub author: Option<String>,
+    pub license: Option<String>,
+    pub tags: Vec<String>,
+    /// 来自同级 metadata.json 的附加信息
+    pub category: Option<String>,
+    pub icon_url: Option<String>,
+    /// 本地化 SKILL.md 的 locale（`SKILL.<locale>.md`）
+    pub locales: Vec<String>,
 }
 
 impl From<DiscoveredSkill> for AvailableSkill {
@@ -48,6 +67,15 @@ impl From<DiscoveredSkill> for AvailableSkill {
             description: skill.description,
             relative_path: skill.relative_path,
             plugin_name: skill.plugin_name,
+            version: skill.version,
+            author: skill.author,
+            license: skill.license,
+            tags: skill.tags,
+            category: skill.category,
+            icon_url: skill.icon_url,
+            locales: skill.locales,
+            audit: None,
+            content_hash: None,
         }
     }
 }
@@ -59,16 +87,27 @@ impl From<DiscoveredSkill> for AvailableSkill {
 /// * `subpath` - 可选的子路径
 /// * `options` - 发现选项
 ///
-/// # 行为（与 CLI 一致）
+/// # 行为
 /// 1. 如果 searchPath 本身有 SKILL.md，添加它（除非 fullDepth，否则立即返回）
-/// 2. 搜索优先目录（skills/, .claude/skills/ 等）
-/// 3. 如果未找到或 fullDepth=true，进行递归搜索
-/// 4. 使用 seenNames 去重
+/// 2. 如果 searchPath 下有 .claude-plugin/ manifest，使用其声明的 skill 目录（CLI 没有这一步，
+///    同一来源可能比 CLI 发现的 skill 少）
+/// 3. manifest 不存在或未声明可用 skill 时，搜索优先目录（skills/, .claude/skills/ 等）
+/// 4. 如果仍未找到或 fullDepth=true，进行递归搜索
+/// 5. 使用 seenNames 去重，并按 manifest 填充 plugin_name
 pub fn discover_skills(
     base_path: &Path,
     subpath: Option<&str>,
     options: DiscoverOptions,
 ) -> Result<Vec<DiscoveredSkill>, AppError> {
+    discover_skills_with_warnings(base_path, subpath, options).map(|(skills, _)| skills)
+}
+
+/// 同 `discover_skills`，同时返回被跳过的 SKILL.md（同一文件只记录一次）
+pub fn discover_skills_with_warnings(
+    base_path: &Path,
+    subpath: Option<&str>,
+    options: DiscoverOptions,
+) -> Result<(Vec<DiscoveredSkill>, Vec<SkillWarning>), AppError> {
     let search_path = match subpath {
         Some(sub) => base_path.join(sub),
         None => base_path.to_path_buf(),
@@ -83,36 +122,44 @@ pub fn discover_skills(
     // 获取 plugin 分组映射
     let plugin_groupings = crate::core::plugin_manifest::get_plugin_groupings(&search_path);
 
-    let mut skills = Vec::new();
-    let mut seen_names: HashSet<String> = HashSet::new();
+    let mut found = Found::default();
 
     // 1. 检查 searchPath 本身是否是 skill
     let skill_md = search_path.join("SKILL.md");
     if skill_md.exists() {
-        if let Some(skill) = try_parse_skill(&skill_md, base_path, &options)? {
-            seen_names.insert(skill.name.clone());
-            skills.push(skill);
+        found.add(try_parse_skill(&skill_md, base_path, &options));
 
-            // 如果不是 fullDepth 模式，直接返回
-            if !options.full_depth {
-                return Ok(skills);
-            }
+        // 如果不是 fullDepth 模式，找到后直接返回
+        if !found.skills.is_empty() && !options.full_depth {
+            return Ok((found.skills, found.warnings));
         }
     }
 
-    // 2. 搜索优先目录
-    let priority_dirs = get_priority_search_dirs(&search_path);
-    for priority_dir in priority_dirs {
-        if priority_dir.exists() {
-            discover_in_dir(&priority_dir, base_path, &options, &mut skills, &mut seen_names)?;
+    // 2. 优先使用 plugin manifest 声明的 skill 目录
+    for skill_dir in declared_plugin_skill_dirs(&search_path, &plugin_groupings) {
+        let skill_md = skill_dir.join("SKILL.md");
+        if skill_md.is_file() {
+            found.add(try_parse_skill(&skill_md, base_path, &options));
         }
     }
 
-    // 3. 如果未找到或启用 fullDepth，进行递归搜索
-    if skills.is_empty() || options.full_depth {
-        discover_recursive(&search_path, base_path, &options, &mut skills, &mut seen_names)?;
+    if found.skills.is_empty() || options.full_depth {
+        // 3. 搜索优先目录
+        let priority_dirs = get_priority_search_dirs(&search_path);
+        for priority_dir in priority_dirs {
+            if priority_dir.exists() {
+                discover_in_dir(&priority_dir, base_path, &options, &mut found);
+            }
+        }
+
+        // 4. 如果未找到或启用 fullDepth，进行递归搜索
+        if found.skills.is_empty() || options.full_depth {
+            discover_recursive(&search_path, base_path, &options, &mut found);
+        }
     }
 
+    let Found { mut skills, warnings, .. } = found;
+
     // 为 skills 填充 plugin_name
     for skill in &mut skills {
         let normalized = crate::core::plugin_manifest::normalize_path(&skill.path);
@@ -121,7 +168,108 @@ pub fn discover_skills(
         }
     }
 
-    Ok(skills)
+    Ok((skills, warnings))
+}
+
+/// 在多个子路径中发现 skills
+///
+/// 没有或只有一个子路径时与 `discover_skills` 完全一致；多个子路径时依次发现，
+/// 按子路径顺序合并结果并按名称去重（先出现的优先）。任一子路径不存在时返回错误。
+pub fn discover_skills_in_subpaths(
+    base_path: &Path,
+    subpaths: &[&str],
+    options: DiscoverOptions,
+) -> Result<Vec<DiscoveredSkill>, AppError> {
+    discover_skills_in_subpaths_with_warnings(base_path, subpaths, options)
+        .map(|(skills, _)| skills)
+}
+
+/// 同 `discover_skills_in_subpaths`，同时返回被跳过的 SKILL.md
+pub fn discover_skills_in_subpaths_with_warnings(
+    base_path: &Path,
+    subpaths: &[&str],
+    options: DiscoverOptions,
+) -> Result<(Vec<DiscoveredSkill>, Vec<SkillWarning>), AppError> {
+    if subpaths.len() <= 1 {
+        return discover_skills_with_warnings(base_path, subpaths.first().copied(), options);
+    }
+
+    let mut skills = Vec::new();
+    let mut warnings: Vec<SkillWarning> = Vec::new();
+    let mut seen_names: HashSet<String> = HashSet::new();
+    for subpath in subpaths {
+        let (found, skipped) = discover_skills_with_warnings(base_path, Some(subpath), options)?;
+        for skill in found {
+            if seen_names.insert(skill.name.clone()) {
+                skills.push(skill);
+            }
+        }
+        for warning in skipped {
+            if !warnings.iter().any(|w| w.relative_path == warning.relative_path) {
+                warnings.push(warning);
+            }
+        }
+    }
+
+    Ok((skills, warnings))
+}
+
+/// 编译按 skill 名称过滤的 glob（忽略大小写）
+///
+/// 支持 `*`、`?`、`[abc]` 和 `{a,b}`（globset 语法），其他字符按字面匹配
+pub fn name_glob_matcher(pattern: &str) -> Result<GlobMatcher, AppError> {
+    GlobBuilder::new(pattern)
+        .case_insensitive(true)
+        .build()
+        .map(|glob| glob.compile_matcher())
+        .map_err(|e| AppError::Custom {
+            message: format!("Invalid name glob '{}': {}", pattern, e.kind()),
+        })
+}
+
+/// 发现过程中累积的结果
+#[derive(Default)]
+struct Found {
+    skills: Vec<DiscoveredSkill>,
+    seen_names: HashSet<String>,
+    warnings: Vec<SkillWarning>,
+    warned_paths: HashSet<String>,
+}
+
+impl Found {
+    /// 记录一次解析结果：同名 skill 只保留第一个，同一文件的警告只记录一次
+    fn add(&mut self, outcome: Result<DiscoveredSkill, SkillWarning>) {
+        match outcome {
+            Ok(skill) => {
+                if self.seen_names.insert(skill.name.clone()) {
+                    self.skills.push(skill);
+                }
+            }
+            Err(warning) => {
+                if self.warned_paths.insert(warning.relative_path.clone()) {
+                    self.warnings.push(warning);
+                }
+            }
+        }
+    }
+}
+
+/// plugin manifest 声明的 skill 目录（按路径排序）
+///
+/// groupings 的 key 是规范化后的绝对路径，这里换回以 search_path 为前缀的路径，
+/// 使 relative_path 的计算与启发式搜索一致（如 search_path 经过 symlink）
+fn declared_plugin_skill_dirs(
+    search_path: &Path,
+    plugin_groupings: &HashMap<PathBuf, String>,
+) -> Vec<PathBuf> {
+    let canonical_search = crate::core::plugin_manifest::normalize_path(search_path);
+    let mut dirs: Vec<PathBuf> = plugin_groupings
+        .keys()
+        .filter_map(|dir| dir.strip_prefix(&canonical_search).ok())
+        .map(|relative| search_path.join(relative))
+        .collect();
+    dirs.sort();
+    dirs
 }
 
 /// 获取优先搜索目录列表（与 CLI 一致）
@@ -161,16 +309,10 @@ fn get_priority_search_dirs(search_path: &Path) -> Vec<PathBuf> {
 }
 
 /// 在目录中发现 skills（搜索直接子目录）
-fn discover_in_dir(
-    dir: &Path,
-    root: &Path,
-    options: &DiscoverOptions,
-    skills: &mut Vec<DiscoveredSkill>,
-    seen_names: &mut HashSet<String>,
-) -> Result<(), AppError> {
+fn discover_in_dir(dir: &Path, root: &Path, options: &DiscoverOptions, found: &mut Found) {
     let entries = match std::fs::read_dir(dir) {
         Ok(e) => e,
-        Err(_) => return Ok(()), // 目录不存在或无权限
+        Err(_) => return, // 目录不存在或无权限
     };
 
     for entry in entries.filter_map(|e| e.ok()) {
@@ -178,29 +320,16 @@ fn discover_in_dir(
         if path.is_dir() {
             let skill_md = path.join("SKILL.md");
             if skill_md.exists() {
-                if let Some(skill) = try_parse_skill(&skill_md, root, options)? {
-                    if !seen_names.contains(&skill.name) {
-                        seen_names.insert(skill.name.clone());
-                        skills.push(skill);
-                    }
-                }
+                found.add(try_parse_skill(&skill_md, root, options));
             }
         }
     }
-
-    Ok(())
 }
 
 /// 递归发现 skills
-fn discover_recursive(
-    dir: &Path,
-    root: &Path,
-    options: &DiscoverOptions,
-    skills: &mut Vec<DiscoveredSkill>,
-    seen_names: &mut HashSet<String>,
-) -> Result<(), AppError> {
+fn discover_recursive(dir: &Path, root: &Path, options: &DiscoverOptions, found: &mut Found) {
     let walker = WalkDir::new(dir)
-        .max_depth(MAX_DEPTH)
+        .max_depth(options.max_depth.unwrap_or(DEFAULT_MAX_DEPTH))
         .follow_links(true)
         .into_iter()
         .filter_entry(|e| {
@@ -217,18 +346,11 @@ fn discover_recursive(
         if path.is_file() {
             if let Some(file_name) = path.file_name() {
                 if file_name.to_str() == Some("SKILL.md") {
-                    if let Some(skill) = try_parse_skill(path, root, options)? {
-                        if !seen_names.contains(&skill.name) {
-                            seen_names.insert(skill.name.clone());
-                            skills.push(skill);
-                        }
-                    }
+                    found.add(try_parse_skill(path, root, options));
                 }
             }
         }
     }
-
-    Ok(())
 }
 
 /// 检查是否应该安装 internal skills（与 CLI 一致）
@@ -238,16 +360,41 @@ fn should_install_internal_skills() -> bool {
         .unwrap_or(false)
 }
 
-/// 尝试解析 SKILL.md 文件
+/// 尝试解析 SKILL.md 文件，跳过时返回原因
 fn try_parse_skill(
     skill_md: &Path,
     root: &Path,
     options: &DiscoverOptions,
-) -> Result<Option<DiscoveredSkill>, AppError> {
+) -> Result<DiscoveredSkill, SkillWarning> {
+    // 计算相对路径
+    let skill_dir = skill_md.parent().unwrap_or(skill_md);
+    let relative_path = skill_dir
+        .strip_prefix(root)
+        .map(|p| p.to_string_lossy().replace('\\', "/"))
+        .unwrap_or_else(|_| skill_dir.to_string_lossy().to_string());
+
+    // 使用 SKILL.md 路径格式
+    let relative_skill_path = if relative_path.is_empty() {
+        "SKILL.md".to_string()
+    } else {
+        format!("{}/SKILL.md", relative_path)
+    };
+    let skipped = |reason, message: String| SkillWarning {
+        relative_path: relative_skill_path.clone(),
+        reason,
+        message,
+    };
+
     // 使用 skill.rs 中的 parse_skill_md 函数
     let parsed = match parse_skill_md(skill_md) {
         Ok(p) => p,
-        Err(_) => return Ok(None), // 解析失败，跳过
+        Err(e) => {
+            let reason = match e {
+                AppError::SkillMdMissingField { .. } => SkillWarningReason::MissingField,
+                _ => SkillWarningReason::ParseError,
+            };
+            return Err(skipped(reason, e.to_string()));
+        }
     };
 
     // 检查是否是 internal skill
@@ -259,31 +406,29 @@ fn try_parse_skill(
 
     // 如果是 internal 且未启用 include_internal 且环境变量未设置，跳过
     if is_internal && !options.include_internal && !should_install_internal_skills() {
-        return Ok(None);
+        return Err(skipped(
+            SkillWarningReason::Internal,
+            format!("'{}' is marked internal", parsed.name),
+        ));
     }
 
-    // 计算相对路径
-    let skill_dir = skill_md.parent().unwrap_or(skill_md);
-    let relative_path = skill_dir
-        .strip_prefix(root)
-        .map(|p| p.to_string_lossy().replace('\\', "/"))
-        .unwrap_or_else(|_| skill_dir.to_string_lossy().to_string());
-
-    // 使用 SKILL.md 路径格式
-    let relative_skill_path = if relative_path.is_empty() {
-        "SKILL.md".to_string()
-    } else {
-        format!("{}/SKILL.md", relative_path)
-    };
+    let sidecar = read_skill_sidecar(skill_dir);
 
-    Ok(Some(DiscoveredSkill {
+    Ok(DiscoveredSkill {
         name: parsed.name,
         description: parsed.description,
         path: skill_dir.to_path_buf(),
         relative_path: relative_skill_path,
         is_internal,
         plugin_name: None,
-    }))
+        version: parsed.version,
+        author: parsed.author,
+        license: parsed.license,
+        tags: parsed.tags,
+        category: sidecar.category,
+        icon_url: sidecar.icon_url,
+        locales: skill_md_locales(skill_dir),
+    })
 }
 
 #[cfg(test)]
@@ -329,6 +474,37 @@ mod tests {
         assert_eq!(skills[0].name, "nested-skill");
     }
 
+    #[test]
+    fn test_discover_skills_in_multiple_subpaths() {
+        let temp = tempdir().unwrap();
+        for (dir, name) in [("skills/a", "alpha"), ("skills/b", "beta"), ("skills/c", "gamma")] {
+            let skill_dir = temp.path().join(dir);
+            fs::create_dir_all(&skill_dir).unwrap();
+            fs::write(
+                skill_dir.join("SKILL.md"),
+                format!("---\nname: {}\ndescription: Test\n---\n", name),
+            )
+            .unwrap();
+        }
+
+        let options = DiscoverOptions::default();
+        // 重叠的子路径（skills 包含 skills/a）按名称去重
+        let skills =
+            discover_skills_in_subpaths(temp.path(), &["skills/b", "skills", "skills/a"], options)
+                .unwrap();
+        let names: Vec<_> = skills.iter().map(|s| s.name.as_str()).collect();
+        assert_eq!(names[0], "beta");
+        assert_eq!(names.len(), 3);
+        assert!(names.contains(&"alpha") && names.contains(&"gamma"));
+
+        // 单个子路径与 discover_skills 一致
+        let single = discover_skills_in_subpaths(temp.path(), &["skills/a"], options).unwrap();
+        assert_eq!(single.len(), 1);
+        assert_eq!(single[0].name, "alpha");
+
+        assert!(discover_skills_in_subpaths(temp.path(), &["skills/a", "missing"], options).is_err());
+    }
+
     #[test]
     fn test_skip_internal_skills_by_default() {
         let temp = tempdir().unwrap();
@@ -432,4 +608,207 @@ mod tests {
 
         assert_eq!(skills.len(), 0);
     }
+
+    #[test]
+    fn test_skipped_skills_reported_as_warnings() {
+        let temp = tempdir().unwrap();
+        let skills_dir = temp.path().join("skills");
+        let write = |name: &str, content: &str| {
+            let dir = skills_dir.join(name);
+            fs::create_dir_all(&dir).unwrap();
+            fs::write(dir.join("SKILL.md"), content).unwrap();
+        };
+        write("good", "---\nname: good\ndescription: Good\n---\n");
+        write("no-desc", "---\nname: no-desc\n---\n");
+        write("no-frontmatter", "# Just markdown\n");
+        write(
+            "hidden",
+            "---\nname: hidden\ndescription: Hidden\nmetadata:\n  internal: true\n---\n",
+        );
+
+        let options = DiscoverOptions {
+            full_depth: true,
+            ..Default::default()
+        };
+        let (skills, mut warnings) =
+            discover_skills_with_warnings(temp.path(), None, options).unwrap();
+        assert_eq!(skills.len(), 1);
+        assert_eq!(skills[0].name, "good");
+
+        // 优先目录和递归搜索都会访问到同一文件，但只记录一次
+        warnings.sort_by(|a, b| a.relative_path.cmp(&b.relative_path));
+        let summary: Vec<_> = warnings
+            .iter()
+            .map(|w| (w.relative_path.as_str(), w.reason))
+            .collect();
+        let mut expected = vec![
+            ("skills/no-desc/SKILL.md", SkillWarningReason::MissingField),
+            ("skills/no-frontmatter/SKILL.md", SkillWarningReason::ParseError),
+        ];
+        // 设置了 INSTALL_INTERNAL_SKILLS 时 internal skill 不会被过滤
+        if !should_install_internal_skills() {
+            expected.insert(0, ("skills/hidden/SKILL.md", SkillWarningReason::Internal));
+        }
+        assert_eq!(summary, expected);
+    }
+
+    #[test]
+    fn test_plugin_manifest_declared_skill_dirs() {
+        let temp = tempdir().unwrap();
+        let base = temp.path();
+        let write_skill = |dir: &str, name: &str| {
+            let skill_dir = base.join(dir);
+            fs::create_dir_all(&skill_dir).unwrap();
+            fs::write(
+                skill_dir.join("SKILL.md"),
+                format!("---\nname: {}\ndescription: {}\n---\n", name, name),
+            )
+            .unwrap();
+        };
+        // manifest 声明的目录不在优先目录中，skills/ 下的未声明 skill 被忽略
+        write_skill("plugins/docs/pdf", "pdf");
+        write_skill("plugins/docs/docx", "docx");
+        write_skill("skills/scratch", "scratch");
+
+        let plugin_dir = base.join(".claude-plugin");
+        fs::create_dir_all(&plugin_dir).unwrap();
+        fs::write(
+            plugin_dir.join("marketplace.json"),
+            r#"{"plugins": [{"name": "doc-skills", "source": "./plugins/docs", "skills": ["./pdf", "./docx", "./missing"]}]}"#,
+        )
+        .unwrap();
+
+        let skills = discover_skills(base, None, DiscoverOptions::default()).unwrap();
+        let names: Vec<_> = skills.iter().map(|s| s.name.as_str()).collect();
+        assert_eq!(names, vec!["docx", "pdf"]);
+        assert!(skills.iter().all(|s| s.plugin_name.as_deref() == Some("doc-skills")));
+        assert_eq!(skills[1].relative_path, "plugins/docs/pdf/SKILL.md");
+
+        // fullDepth 时仍会补充启发式搜索的结果
+        let options = DiscoverOptions { full_depth: true, ..Default::default() };
+        let skills = discover_skills(base, None, options).unwrap();
+        assert_eq!(skills.len(), 3);
+        let scratch = skills.iter().find(|s| s.name == "scratch").unwrap();
+        assert!(scratch.plugin_name.is_none());
+
+        // manifest 声明的目录都不可用时回退到启发式搜索
+        fs::write(
+            plugin_dir.join("marketplace.json"),
+            r#"{"plugins": [{"name": "doc-skills", "skills": ["./missing"]}]}"#,
+        )
+        .unwrap();
+        let skills = discover_skills(base, None, DiscoverOptions::default()).unwrap();
+        assert_eq!(skills[0].name, "scratch");
+    }
+
+    #[test]
+    fn test_name_glob_matcher() {
+        let matches_name_glob = |pattern: &str, name: &str| name_glob_matcher(pattern).unwrap().is_match(name);
+        assert!(matches_name_glob("python-*", "python-lint"));
+        assert!(matches_name_glob("python-*", "Python-"));
+        assert!(!matches_name_glob("python-*", "my-python-lint"));
+        assert!(matches_name_glob("*-lint", "python-lint"));
+        assert!(matches_name_glob("py*on-*t", "python-lint"));
+        assert!(matches_name_glob("doc?", "docx"));
+        assert!(!matches_name_glob("doc?", "doc"));
+        assert!(matches_name_glob("*", "anything"));
+        assert!(matches_name_glob("pdf", "pdf"));
+        assert!(!matches_name_glob("pdf", "pdf-tools"));
+        assert!(matches_name_glob("{pdf,docx}-*", "docx-tools"));
+        assert!(name_glob_matcher("[").is_err());
+    }
+
+    #[test]
+    fn test_configurable_max_depth() {
+        let temp = tempdir().unwrap();
+
+        // SKILL.md 位于递归根下第 6 层：a/b/c/d/e/SKILL.md
+        let skill_dir = temp.path().join("a/b/c/d/e");
+        fs::create_dir_all(&skill_dir).unwrap();
+        fs::write(
+            skill_dir.join("SKILL.md"),
+            "---\nname: deep-skill\ndescription: Deep\n---\n",
+        )
+        .unwrap();
+
+        // 默认深度 5 找不到
+        let skills = discover_skills(temp.path(), None, DiscoverOptions::default()).unwrap();
+        assert!(skills.is_empty());
+
+        // 提高深度后可以找到
+        let options = DiscoverOptions {
+            max_depth: Some(6),
+            ..Default::default()
+        };
+        let skills = discover_skills(temp.path(), None, options).unwrap();
+        assert_eq!(skills.len(), 1);
+        assert_eq!(skills[0].name, "deep-skill");
+    }
+
+    #[test]
+    fn test_localized_skill_md_variants() {
+        let temp = tempdir().unwrap();
+        let dir = temp.path().join("skills/pdf");
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(dir.join("SKILL.md"), "---\nname: pdf\ndescription: PDF tools\n---\n").unwrap();
+        fs::write(dir.join("SKILL.zh.md"), "---\nname: pdf\ndescription: PDF 工具\n---\n").unwrap();
+        fs::write(dir.join("SKILL.pt-BR.md"), "---\nname: pdf\ndescription: Ferramentas\n---\n").unwrap();
+        // 不是合法 locale 的文件名
+        fs::write(dir.join("SKILL..md"), "").unwrap();
+        fs::write(dir.join("SKILL.1x.md"), "").unwrap();
+        // 只有本地化版本、没有 SKILL.md 的目录不算 skill
+        let only_variant = temp.path().join("skills/orphan");
+        fs::create_dir_all(&only_variant).unwrap();
+        fs::write(only_variant.join("SKILL.zh.md"), "---\nname: orphan\ndescription: d\n---\n").unwrap();
+
+        let skills = discover_skills(temp.path(), None, DiscoverOptions::default()).unwrap();
+        assert_eq!(skills.len(), 1);
+        assert_eq!(skills[0].description, "PDF tools");
+        assert_eq!(skills[0].locales, vec!["pt-BR", "zh"]);
+        assert_eq!(AvailableSkill::from(skills[0].clone()).locales, vec!["pt-BR", "zh"]);
+    }
+
+    #[test]
+    fn test_metadata_json_sidecar() {
+        let temp = tempdir().unwrap();
+        let write_skill = |name: &str, sidecar: Option<&str>| {
+            let dir = temp.path().join("skills").join(name);
+            fs::create_dir_all(&dir).unwrap();
+            fs::write(
+                dir.join("SKILL.md"),
+                format!("---\nname: {}\ndescription: Test\n---\n", name),
+            )
+            .unwrap();
+            if let Some(content) = sidecar {
+                fs::write(dir.join("metadata.json"), content).unwrap();
+            }
+        };
+        write_skill(
+            "with-sidecar",
+            Some(r#"{"category": "Documents", "iconUrl": "https://example.com/pdf.png", "extra": 1}"#),
+        );
+        write_skill("without-sidecar", None);
+        write_skill("malformed", Some("{ not json"));
+        write_skill("wrong-types", Some(r#"{"category": 42, "icon": "icons/x.svg"}"#));
+
+        let skills = discover_skills(temp.path(), None, DiscoverOptions::default()).unwrap();
+        let find = |name: &str| skills.iter().find(|s| s.name == name).unwrap();
+        assert_eq!(skills.len(), 4);
+
+        let skill = find("with-sidecar");
+        assert_eq!(skill.category.as_deref(), Some("Documents"));
+        assert_eq!(skill.icon_url.as_deref(), Some("https://example.com/pdf.png"));
+
+        for name in ["without-sidecar", "malformed"] {
+            assert!(find(name).category.is_none());
+            assert!(find(name).icon_url.is_none());
+        }
+
+        let skill = find("wrong-types");
+        assert!(skill.category.is_none());
+        assert_eq!(skill.icon_url.as_deref(), Some("icons/x.svg"));
+
+        let available = AvailableSkill::from(find("with-sidecar").clone());
+        assert_eq!(available.category.as_deref(), Some("Documents"));
+    }
 }
@@ -0,0 +1,212 @@
+//! 磁盘占用统计
+//!
+//! - `measure_dir`：递归统计目录大小（跟随 symlink，按解析后的真实路径去重，避免链接成环时无限递归）；
+//!   无法读取的路径记入 `unreadable` 并跳过。单个 skill 的大小（`compute_dir_size`）也使用它
+//! - `disk_usage`：汇总 global canonical 目录、克隆缓存目录和各项目 canonical 目录的占用
+
+use crate::core::clone_cache::clone_retention_enabled;
+use crate::core::paths::{canonical_skills_dir, skill_deck_clone_cache_dir};
+use serde::Serialize;
+use specta::Type;
+use std::collections::HashSet;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// 目录统计结果
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct DirSize {
+    pub size_bytes: u64,
+    pub file_count: u32,
+    /// 无法读取的文件或目录
+    pub unreadable: Vec<PathBuf>,
+}
+
+/// 递归统计目录占用的字节数和文件数
+///
+/// # Arguments
+/// * `dir` - 目录
+/// * `skip_dirs` - 跳过的目录名（如 `.git`）
+pub fn measure_dir(dir: &Path, skip_dirs: &[&str]) -> DirSize {
+    let mut size = DirSize::default();
+    let mut visited = HashSet::new();
+    measure_into(dir, skip_dirs, &mut visited, &mut size);
+    size
+}
+
+fn measure_into(dir: &Path, skip_dirs: &[&str], visited: &mut HashSet<PathBuf>, size: &mut DirSize) {
+    // 同一真实目录只统计一次（symlink 成环或多个链接指向同一目录）
+    match dir.canonicalize() {
+        Ok(real) => {
+            if !visited.insert(real) {
+                return;
+            }
+        }
+        Err(_) => {
+            size.unreadable.push(dir.to_path_buf());
+            return;
+        }
+    }
+    let entries = match fs::read_dir(dir) {
+        Ok(entries) => entries,
+        Err(_) => {
+            size.unreadable.push(dir.to_path_buf());
+            return;
+        }
+    };
+
+    for entry in entries {
+        let Ok(entry) = entry else {
+            size.unreadable.push(dir.to_path_buf());
+            continue;
+        };
+        let path = entry.path();
+        if skip_dirs.iter().any(|d| entry.file_name() == *d) {
+            continue;
+        }
+        // 跟随 symlink；悬空链接不计入
+        match fs::metadata(&path) {
+            Ok(metadata) if metadata.is_dir() => measure_into(&path, skip_dirs, visited, size),
+            Ok(metadata) => {
+                size.size_bytes += metadata.len();
+                size.file_count += 1;
+            }
+            Err(_) if path.symlink_metadata().is_ok_and(|m| m.file_type().is_symlink()) => {}
+            Err(_) => size.unreadable.push(path),
+        }
+    }
+}
+
+/// 占用项类型
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Type)]
+#[serde(rename_all = "camelCase")]
+#[specta(rename_all = "camelCase")]
+pub enum DiskUsageKind {
+    /// ~/.agents/skills
+    GlobalSkills,
+    /// ~/.skill-deck/cache/clones
+    CloneCache,
+    /// <project>/.agents/skills
+    ProjectSkills,
+}
+
+/// 单项占用
+#[derive(Debug, Clone, Serialize, Type)]
+#[serde(rename_all = "camelCase")]
+#[specta(rename_all = "camelCase")]
+pub struct DiskUsageEntry {
+    pub kind: DiskUsageKind,
+    pub path: String,
+    /// ProjectSkills 时的项目路径
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub project_path: Option<String>,
+    #[specta(type = f64)]
+    pub size_bytes: u64,
+    pub file_count: u32,
+}
+
+/// skill-deck 的磁盘占用
+#[derive(Debug, Clone, Serialize, Type)]
+#[serde(rename_all = "camelCase")]
+#[specta(rename_all = "camelCase")]
+pub struct DiskUsage {
+    /// 各项占用（不存在的目录不列出）
+    pub entries: Vec<DiskUsageEntry>,
+    #[specta(type = f64)]
+    pub total_bytes: u64,
+    /// 无法读取而跳过的路径
+    pub unreadable: Vec<String>,
+}
+
+/// 统计 skill-deck 使用的磁盘空间
+///
+/// 克隆缓存目录在开启 `retainClones` 或目录已存在（之前开启过）时统计。
+///
+/// # Arguments
+/// * `projects` - 已保存的项目路径（config.json 的 projects）
+pub fn disk_usage(projects: &[String]) -> DiskUsage {
+    let mut targets = vec![(DiskUsageKind::GlobalSkills, canonical_skills_dir(true, "."), None)];
+    let cache_dir = skill_deck_clone_cache_dir();
+    if clone_retention_enabled() || cache_dir.exists() {
+        targets.push((DiskUsageKind::CloneCache, cache_dir, None));
+    }
+    for project in projects {
+        targets.push((
+            DiskUsageKind::ProjectSkills,
+            canonical_skills_dir(false, project),
+            Some(project.clone()),
+        ));
+    }
+    measure_targets(targets)
+}
+
+fn measure_targets(targets: Vec<(DiskUsageKind, PathBuf, Option<String>)>) -> DiskUsage {
+    let mut usage = DiskUsage {
+        entries: Vec::new(),
+        total_bytes: 0,
+        unreadable: Vec::new(),
+    };
+    for (kind, path, project_path) in targets {
+        if !path.is_dir() {
+            continue;
+        }
+        let size = measure_dir(&path, &[]);
+        usage.total_bytes += size.size_bytes;
+        usage
+            .unreadable
+            .extend(size.unreadable.iter().map(|p| p.to_string_lossy().to_string()));
+        usage.entries.push(DiskUsageEntry {
+            kind,
+            path: path.to_string_lossy().to_string(),
+            project_path,
+            size_bytes: size.size_bytes,
+            file_count: size.file_count,
+        });
+    }
+    usage
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    #[cfg(unix)]
+    #[test]
+    fn test_measure_dir_symlink_loop() {
+        let temp = tempdir().unwrap();
+        let dir = temp.path().join("skill");
+        fs::create_dir_all(dir.join("nested")).unwrap();
+        fs::write(dir.join("SKILL.md"), "12345").unwrap();
+        fs::write(dir.join("nested/a.txt"), "123").unwrap();
+        // 指向祖先目录的链接和悬空链接
+        std::os::unix::fs::symlink("..", dir.join("nested/loop")).unwrap();
+        std::os::unix::fs::symlink("missing", dir.join("broken")).unwrap();
+
+        let size = measure_dir(&dir, &[]);
+        assert_eq!((size.size_bytes, size.file_count), (8, 2));
+        assert!(size.unreadable.is_empty());
+    }
+
+    #[test]
+    fn test_measure_targets() {
+        let temp = tempdir().unwrap();
+        let global = temp.path().join("global");
+        let project = temp.path().join("project/.agents/skills");
+        fs::create_dir_all(global.join("demo")).unwrap();
+        fs::write(global.join("demo/SKILL.md"), "1234").unwrap();
+        fs::create_dir_all(project.join("demo")).unwrap();
+        fs::write(project.join("demo/SKILL.md"), "12").unwrap();
+
+        let usage = measure_targets(vec![
+            (DiskUsageKind::GlobalSkills, global, None),
+            (DiskUsageKind::CloneCache, temp.path().join("missing"), None),
+            (DiskUsageKind::ProjectSkills, project, Some("project".to_string())),
+        ]);
+        // 不存在的目录不列出
+        assert_eq!(usage.entries.len(), 2);
+        assert_eq!(usage.entries[1].kind, DiskUsageKind::ProjectSkills);
+        assert_eq!(usage.entries[1].size_bytes, 2);
+        assert_eq!(usage.total_bytes, 6);
+        assert!(usage.unreadable.is_empty());
+    }
+}
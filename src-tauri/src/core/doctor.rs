@@ -0,0 +1,244 @@
+//! 环境健康检查
+//!
+//! 汇总排查问题所需的环境信息，每项检查复用已有的检测函数：
+//! - git：`detect_git`
+//! - GitHub token：`get_github_token_with_source`（只报告来源，不返回 token）
+//! - config.json：能否解析
+//! - symlink：`symlink_unavailable_reason`（global canonical 目录）
+//! - 网络：github.com 与审计接口能否连接（离线模式下跳过）
+//! - agents：`AgentType::detect_installed`
+
+use crate::core::agents::{AgentType, DetectionContext};
+use crate::core::audit::{is_audit_disabled, AUDIT_URL};
+use crate::core::git_binary::{detect_git, GitInfo};
+use crate::core::github_api::{get_github_token_with_source, GithubTokenSource};
+use crate::core::http_client::{api_client, with_user_agent};
+use crate::core::installer::symlink_unavailable_reason;
+use crate::core::offline::is_offline;
+use crate::core::paths::{canonical_skills_dir, skill_deck_config_path, PATHS};
+use crate::models::SkillDeckConfig;
+use serde::Serialize;
+use specta::Type;
+use std::time::Duration;
+
+/// 网络检查超时（秒）
+const NETWORK_TIMEOUT_SECS: u64 = 5;
+
+/// GitHub 连通性检查地址
+const GITHUB_URL: &str = "https://github.com";
+
+/// 单项检查结果
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Type)]
+#[serde(rename_all = "lowercase")]
+#[specta(rename_all = "lowercase")]
+pub enum CheckStatus {
+    Ok,
+    Warn,
+    Fail,
+}
+
+/// 单项检查
+#[derive(Debug, Clone, Serialize, Type)]
+#[serde(rename_all = "camelCase")]
+#[specta(rename_all = "camelCase")]
+pub struct DoctorCheck {
+    /// 检查项标识（如 "git"、"githubToken"）
+    pub id: String,
+    pub status: CheckStatus,
+    pub message: String,
+}
+
+/// 解析后的路径
+#[derive(Debug, Clone, Serialize, Type)]
+#[serde(rename_all = "camelCase")]
+#[specta(rename_all = "camelCase")]
+pub struct DoctorPaths {
+    pub home: String,
+    pub config_home: String,
+    pub codex_home: String,
+    pub claude_home: String,
+    /// ~/.skill-deck/config.json
+    pub skill_deck_config: String,
+    /// global canonical skills 目录
+    pub canonical_skills_dir: String,
+}
+
+/// 健康检查报告
+#[derive(Debug, Clone, Serialize, Type)]
+#[serde(rename_all = "camelCase")]
+#[specta(rename_all = "camelCase")]
+pub struct DoctorReport {
+    /// 应用版本
+    pub app_version: String,
+    /// 操作系统（如 "macos"、"windows"）
+    pub os: String,
+    pub paths: DoctorPaths,
+    /// 检测到的 agents 数量（仅全局信号）
+    pub detected_agents: u32,
+    pub checks: Vec<DoctorCheck>,
+}
+
+/// 运行全部检查
+pub async fn run_doctor() -> DoctorReport {
+    let canonical = canonical_skills_dir(true, ".");
+    let config_path = skill_deck_config_path();
+    let detected = AgentType::detect_installed(&DetectionContext::GlobalOnly);
+
+    let mut checks = vec![
+        git_check(&detect_git()),
+        github_token_check(get_github_token_with_source("github.com").map(|(_, source)| source)),
+        config_check(std::fs::read_to_string(&config_path).ok().as_deref()),
+        symlink_check(symlink_unavailable_reason(&canonical)),
+    ];
+
+    if is_offline() {
+        checks.push(check("network", CheckStatus::Warn, "Offline mode is enabled; network checks skipped"));
+    } else {
+        checks.push(reachability_check("githubReachable", GITHUB_URL, probe(GITHUB_URL).await));
+        if is_audit_disabled() {
+            checks.push(check("auditReachable", CheckStatus::Ok, "Security audit is disabled"));
+        } else {
+            checks.push(reachability_check("auditReachable", AUDIT_URL, probe(AUDIT_URL).await));
+        }
+    }
+
+    checks.push(agents_check(detected.len()));
+
+    DoctorReport {
+        app_version: env!("CARGO_PKG_VERSION").to_string(),
+        os: std::env::consts::OS.to_string(),
+        paths: DoctorPaths {
+            home: PATHS.home.to_string_lossy().to_string(),
+            config_home: PATHS.config_home.to_string_lossy().to_string(),
+            codex_home: PATHS.codex_home.to_string_lossy().to_string(),
+            claude_home: PATHS.claude_home.to_string_lossy().to_string(),
+            skill_deck_config: config_path.to_string_lossy().to_string(),
+            canonical_skills_dir: canonical.to_string_lossy().to_string(),
+        },
+        detected_agents: detected.len() as u32,
+        checks,
+    }
+}
+
+fn check(id: &str, status: CheckStatus, message: impl Into<String>) -> DoctorCheck {
+    DoctorCheck {
+        id: id.to_string(),
+        status,
+        message: message.into(),
+    }
+}
+
+fn git_check(info: &GitInfo) -> DoctorCheck {
+    match (&info.path, &info.version) {
+        (Some(path), Some(version)) if info.found => {
+            check("git", CheckStatus::Ok, format!("git {} at {}", version, path))
+        }
+        _ => check(
+            "git",
+            CheckStatus::Fail,
+            info.error.clone().unwrap_or_else(|| "git was not found".to_string()),
+        ),
+    }
+}
+
+fn github_token_check(source: Option<GithubTokenSource>) -> DoctorCheck {
+    let from = match source {
+        Some(GithubTokenSource::GithubTokenEnv) => "the GITHUB_TOKEN environment variable",
+        Some(GithubTokenSource::GhTokenEnv) => "the GH_TOKEN environment variable",
+        Some(GithubTokenSource::CredentialsFile) => "~/.skill-deck/credentials.json",
+        Some(GithubTokenSource::GhCli) => "gh auth token",
+        None => {
+            return check(
+                "githubToken",
+                CheckStatus::Warn,
+                "No GitHub token configured; API requests are limited to 60 per hour and private repositories are unavailable",
+            )
+        }
+    };
+    check("githubToken", CheckStatus::Ok, format!("GitHub token found via {}", from))
+}
+
+fn config_check(content: Option<&str>) -> DoctorCheck {
+    match content {
+        None => check("config", CheckStatus::Ok, "config.json not found; using defaults"),
+        Some(content) => match serde_json::from_str::<SkillDeckConfig>(content) {
+            Ok(_) => check("config", CheckStatus::Ok, "config.json is valid"),
+            Err(e) => check(
+                "config",
+                CheckStatus::Warn,
+                format!("config.json could not be parsed and is ignored: {}", e),
+            ),
+        },
+    }
+}
+
+fn symlink_check(unavailable: Option<String>) -> DoctorCheck {
+    match unavailable {
+        None => check("symlink", CheckStatus::Ok, "Symlinks are supported"),
+        Some(reason) => check(
+            "symlink",
+            CheckStatus::Warn,
+            format!("Symlinks unavailable, skills will be copied: {}", reason),
+        ),
+    }
+}
+
+/// 发起一次请求，任何 HTTP 响应都视为可连接
+async fn probe(url: &str) -> Result<u16, String> {
+    with_user_agent(api_client().head(url))
+        .timeout(Duration::from_secs(NETWORK_TIMEOUT_SECS))
+        .send()
+        .await
+        .map(|response| response.status().as_u16())
+        .map_err(|e| e.to_string())
+}
+
+fn reachability_check(id: &str, url: &str, result: Result<u16, String>) -> DoctorCheck {
+    match result {
+        Ok(status) => check(id, CheckStatus::Ok, format!("{} reachable (HTTP {})", url, status)),
+        Err(e) => check(id, CheckStatus::Fail, format!("Cannot reach {}: {}", url, e)),
+    }
+}
+
+fn agents_check(count: usize) -> DoctorCheck {
+    if count == 0 {
+        check("agents", CheckStatus::Warn, "No agents detected")
+    } else {
+        check("agents", CheckStatus::Ok, format!("{} agent(s) detected", count))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_check_statuses() {
+        let missing = GitInfo {
+            found: false,
+            path: None,
+            version: None,
+            source: None,
+            error: Some("git was not found on PATH".to_string()),
+        };
+        assert_eq!(git_check(&missing).status, CheckStatus::Fail);
+        assert_eq!(git_check(&missing).message, "git was not found on PATH");
+
+        let token = github_token_check(Some(GithubTokenSource::GhCli));
+        assert_eq!(token.status, CheckStatus::Ok);
+        assert!(token.message.contains("gh auth token"));
+        assert_eq!(github_token_check(None).status, CheckStatus::Warn);
+
+        assert_eq!(config_check(None).status, CheckStatus::Ok);
+        assert_eq!(config_check(Some(r#"{"projects": []}"#)).status, CheckStatus::Ok);
+        assert_eq!(config_check(Some("{ broken")).status, CheckStatus::Warn);
+
+        assert_eq!(symlink_check(Some("os error 1314".to_string())).status, CheckStatus::Warn);
+        assert_eq!(
+            reachability_check("githubReachable", GITHUB_URL, Err("timed out".to_string())).status,
+            CheckStatus::Fail
+        );
+        assert_eq!(agents_check(0).status, CheckStatus::Warn);
+        assert_eq!(agents_check(3).status, CheckStatus::Ok);
+    }
+}
@@ -0,0 +1,332 @@
+//! git 可执行文件定位
+//!
+//! 查找顺序：
+//! 1. config.json 中的 gitPath（设置后只使用该路径，不再回退）
+//! 2. PATH 中的 git
+//! 3. 常见安装位置（Homebrew、Xcode Command Line Tools、Git for Windows 等）
+//!
+//! 从 Finder 启动的 macOS 应用不继承 shell 的 PATH，Homebrew 安装的 git 只能通过常见位置找到。
+//! 自动查找的结果在进程内只计算一次。
+
+use crate::core::paths::read_skill_deck_config;
+use crate::error::AppError;
+use once_cell::sync::Lazy;
+use serde::Serialize;
+use specta::Type;
+use std::ffi::OsStr;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::sync::RwLock;
+
+#[cfg(windows)]
+const GIT_EXE: &str = "git.exe";
+#[cfg(not(windows))]
+const GIT_EXE: &str = "git";
+
+/// config.json 中的 gitPath
+/// 首次访问时从 config.json 加载，save_config 时通过 set_git_path 同步
+static CONFIGURED_GIT_PATH: Lazy<RwLock<Option<PathBuf>>> = Lazy::new(|| {
+    RwLock::new(
+        read_skill_deck_config()
+            .and_then(|c| c.git_path)
+            .map(PathBuf::from)
+            .filter(|p| p.is_absolute()),
+    )
+});
+
+/// 未配置 gitPath 时自动找到的 git
+static DETECTED_GIT_PATH: Lazy<Option<PathBuf>> = Lazy::new(|| {
+    let path_env = std::env::var_os("PATH");
+    detect_git_with(None, path_env.as_deref(), &common_git_locations())
+        .path
+        .map(PathBuf::from)
+});
+
+/// git 的来源
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Type)]
+#[serde(rename_all = "camelCase")]
+#[specta(rename_all = "camelCase")]
+pub enum GitSource {
+    /// config.json 中的 gitPath
+    Config,
+    /// PATH 环境变量
+    Path,
+    /// 常见安装位置
+    CommonLocation,
+}
+
+/// detect_git 结果
+#[derive(Debug, Clone, Serialize, Type)]
+#[serde(rename_all = "camelCase")]
+#[specta(rename_all = "camelCase")]
+pub struct GitInfo {
+    /// 是否找到可用的 git
+    pub found: bool,
+    /// git 可执行文件路径（配置的路径不可用时也会返回，便于提示）
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub path: Option<String>,
+    /// `git --version` 输出中的版本号（如 "2.45.1"）
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub version: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub source: Option<GitSource>,
+    /// 未找到或无法运行时的原因
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+/// 校验 gitPath：空字符串视为未设置，其余必须为绝对路径
+pub fn validate_git_path(value: &str) -> Result<Option<PathBuf>, AppError> {
+    let value = value.trim();
+    if value.is_empty() {
+        return Ok(None);
+    }
+    let path = PathBuf::from(value);
+    if !path.is_absolute() {
+        return Err(AppError::Path {
+            message: format!("Git path must be an absolute path: {}", value),
+        });
+    }
+    Ok(Some(path))
+}
+
+/// 更新内存中的 gitPath（save_config 后调用）
+pub fn set_git_path(path: Option<PathBuf>) {
+    if let Ok(mut guard) = CONFIGURED_GIT_PATH.write() {
+        *guard = path;
+    }
+}
+
+fn configured_git_path() -> Option<PathBuf> {
+    CONFIGURED_GIT_PATH.read().ok().and_then(|g| g.clone())
+}
+
+/// 启动 git 时使用的程序路径
+///
+/// 都找不到时返回 "git"，由启动失败时的 `git_not_found_error` 给出提示
+pub fn git_program() -> PathBuf {
+    select_git_program(configured_git_path(), DETECTED_GIT_PATH.clone())
+}
+
+fn select_git_program(configured: Option<PathBuf>, detected: Option<PathBuf>) -> PathBuf {
+    configured
+        .or(detected)
+        .unwrap_or_else(|| PathBuf::from(GIT_EXE))
+}
+
+/// 启动 git 失败（程序不存在）时的错误
+pub fn git_not_found_error() -> AppError {
+    let message = match configured_git_path() {
+        Some(path) => format!(
+            "The configured git path {} does not exist or is not executable. \
+             Update gitPath in settings or clear it to search PATH.",
+            path.display()
+        ),
+        None => "git was not found on PATH or in common install locations. \
+                 Install git, or set gitPath in settings to the git executable."
+            .to_string(),
+    };
+    AppError::GitNotFound { message }
+}
+
+/// 检测 git 的位置和版本
+pub fn detect_git() -> GitInfo {
+    let path_env = std::env::var_os("PATH");
+    detect_git_with(
+        configured_git_path().as_deref(),
+        path_env.as_deref(),
+        &common_git_locations(),
+    )
+}
+
+/// 按查找顺序检测 git（纯函数，便于测试）
+///
+/// 自动查找时跳过无法运行的候选（如未安装 Command Line Tools 时的 /usr/bin/git）
+fn detect_git_with(configured: Option<&Path>, path_env: Option<&OsStr>, candidates: &[PathBuf]) -> GitInfo {
+    if let Some(path) = configured {
+        let (version, error) = match git_version(path) {
+            Ok(version) => (Some(version), None),
+            Err(e) => (None, Some(e)),
+        };
+        return GitInfo {
+            found: version.is_some(),
+            path: Some(path.to_string_lossy().to_string()),
+            version,
+            source: Some(GitSource::Config),
+            error,
+        };
+    }
+
+    let on_path = path_env
+        .map(|p| std::env::split_paths(p).map(|dir| dir.join(GIT_EXE)).collect::<Vec<_>>())
+        .unwrap_or_default();
+    let ordered = on_path
+        .into_iter()
+        .map(|p| (p, GitSource::Path))
+        .chain(candidates.iter().map(|p| (p.clone(), GitSource::CommonLocation)));
+
+    for (path, source) in ordered {
+        if !path.is_file() {
+            continue;
+        }
+        if let Ok(version) = git_version(&path) {
+            return GitInfo {
+                found: true,
+                path: Some(path.to_string_lossy().to_string()),
+                version: Some(version),
+                source: Some(source),
+                error: None,
+            };
+        }
+    }
+
+    GitInfo {
+        found: false,
+        path: None,
+        version: None,
+        source: None,
+        error: Some("git was not found on PATH or in common install locations".to_string()),
+    }
+}
+
+/// 运行 `git --version`，返回版本号
+fn git_version(path: &Path) -> Result<String, String> {
+    let mut cmd = Command::new(path);
+    cmd.arg("--version");
+
+    // Windows: 隐藏控制台窗口
+    #[cfg(windows)]
+    {
+        use std::os::windows::process::CommandExt;
+        const CREATE_NO_WINDOW: u32 = 0x08000000;
+        cmd.creation_flags(CREATE_NO_WINDOW);
+    }
+
+    let output = cmd
+        .output()
+        .map_err(|e| format!("Failed to run {}: {}", path.display(), e))?;
+    if !output.status.success() {
+        return Err(format!(
+            "{} --version failed: {}",
+            path.display(),
+            String::from_utf8_lossy(&output.stderr).trim()
+        ));
+    }
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    stdout
+        .trim()
+        .strip_prefix("git version ")
+        .map(|v| v.to_string())
+        .ok_or_else(|| format!("{} is not git: {}", path.display(), stdout.trim()))
+}
+
+/// 各平台常见的 git 安装位置
+fn common_git_locations() -> Vec<PathBuf> {
+    #[cfg(target_os = "macos")]
+    {
+        [
+            "/opt/homebrew/bin/git",
+            "/usr/local/bin/git",
+            "/opt/local/bin/git",
+            "/Library/Developer/CommandLineTools/usr/bin/git",
+            "/Applications/Xcode.app/Contents/Developer/usr/bin/git",
+            "/usr/bin/git",
+        ]
+        .iter()
+        .map(PathBuf::from)
+        .collect()
+    }
+    #[cfg(windows)]
+    {
+        let mut locations: Vec<PathBuf> = ["ProgramFiles", "ProgramFiles(x86)"]
+            .iter()
+            .filter_map(|var| std::env::var_os(var))
+            .map(|dir| PathBuf::from(dir).join("Git").join("cmd").join(GIT_EXE))
+            .collect();
+        if let Some(local) = std::env::var_os("LOCALAPPDATA") {
+            locations.push(PathBuf::from(local).join("Programs").join("Git").join("cmd").join(GIT_EXE));
+        }
+        locations
+    }
+    #[cfg(not(any(target_os = "macos", windows)))]
+    {
+        ["/usr/bin/git", "/usr/local/bin/git", "/bin/git", "/snap/bin/git"]
+            .iter()
+            .map(PathBuf::from)
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[cfg(unix)]
+    fn fake_git(dir: &Path, version: &str) -> PathBuf {
+        use std::os::unix::fs::PermissionsExt;
+
+        std::fs::create_dir_all(dir).unwrap();
+        let path = dir.join(GIT_EXE);
+        std::fs::write(&path, format!("#!/bin/sh\necho 'git version {}'\n", version)).unwrap();
+        std::fs::set_permissions(&path, std::fs::Permissions::from_mode(0o755)).unwrap();
+        path
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_detect_git_prefers_configured_path() {
+        let temp = tempfile::tempdir().unwrap();
+        let configured = fake_git(&temp.path().join("custom"), "9.9.9");
+        let on_path = fake_git(&temp.path().join("bin"), "2.40.0");
+        let common = fake_git(&temp.path().join("homebrew"), "2.45.1");
+        let path_env = temp.path().join("bin").into_os_string();
+        let path_env = path_env.as_os_str();
+
+        let info = detect_git_with(Some(configured.as_path()), Some(path_env), std::slice::from_ref(&common));
+        assert!(info.found);
+        assert_eq!(info.path.as_deref(), Some(configured.to_string_lossy().as_ref()));
+        assert_eq!(info.version.as_deref(), Some("9.9.9"));
+        assert_eq!(info.source, Some(GitSource::Config));
+
+        let info = detect_git_with(None, Some(path_env), std::slice::from_ref(&common));
+        assert_eq!(info.path.as_deref(), Some(on_path.to_string_lossy().as_ref()));
+        assert_eq!(info.source, Some(GitSource::Path));
+
+        // PATH 中没有 git（如从 Finder 启动）时回退到常见安装位置
+        let empty = temp.path().join("empty").into_os_string();
+        let empty = empty.as_os_str();
+        let info = detect_git_with(None, Some(empty), std::slice::from_ref(&common));
+        assert_eq!(info.version.as_deref(), Some("2.45.1"));
+        assert_eq!(info.source, Some(GitSource::CommonLocation));
+
+        // 配置的路径不存在时不回退，直接报告
+        let missing = temp.path().join("missing/git");
+        let info = detect_git_with(Some(missing.as_path()), Some(path_env), &[common]);
+        assert!(!info.found);
+        assert_eq!(info.source, Some(GitSource::Config));
+        assert!(info.error.is_some());
+
+        assert!(!detect_git_with(None, Some(empty), &[]).found);
+    }
+
+    #[test]
+    fn test_select_git_program() {
+        let configured = PathBuf::from("/opt/custom/bin/git");
+        let detected = PathBuf::from("/usr/bin/git");
+        assert_eq!(
+            select_git_program(Some(configured.clone()), Some(detected.clone())),
+            configured
+        );
+        assert_eq!(select_git_program(None, Some(detected.clone())), detected);
+        assert_eq!(select_git_program(None, None), PathBuf::from(GIT_EXE));
+    }
+
+    #[test]
+    fn test_validate_git_path() {
+        assert!(validate_git_path("").unwrap().is_none());
+        assert!(validate_git_path("  ").unwrap().is_none());
+        assert!(validate_git_path("git").is_err());
+        let abs = std::env::temp_dir().join("git");
+        assert_eq!(validate_git_path(&abs.to_string_lossy()).unwrap(), Some(abs));
+    }
+}
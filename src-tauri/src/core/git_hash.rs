@@ -0,0 +1,223 @@
+//! Git 对象 hash 模块
+//!
+//! 按 git 的对象格式在本地计算目录的 tree SHA，与 GitHub Trees API 返回的
+//! 文件夹 sha（lock 中的 skillFolderHash / remoteHash）处于同一 hash 空间，
+//! 可直接比较已安装文件与远程文件夹。
+//!
+//! 算法（与 `git hash-object` / `git write-tree` 一致）：
+//! - blob：`sha1("blob {字节数}\0" + 文件内容)`
+//! - tree：`sha1("tree {字节数}\0" + entries)`，每个 entry 为
+//!   `"{mode} {name}\0"` 加上子对象的 20 字节原始 sha
+//! - mode：普通文件 `100644`，可执行文件 `100755`，symlink `120000`
+//!   （blob 内容为链接目标），子目录 `40000`
+//! - entry 按名称的字节序排序，比较时目录名视为带末尾 `/`
+//! - 空目录不产生 entry（git 不记录空目录），`.git` 目录跳过
+//!
+//! 局限：安装时会排除 metadata.json、`_` 开头的文件和 `.skillignore` 匹配的文件，
+//! Windows 上也没有可执行位，这些情况下本地 tree SHA 与远程不同，调用方需要回退到
+//! lock 中记录的远程 hash 比较。
+
+use crate::error::AppError;
+use sha1::{Digest, Sha1};
+use std::fs;
+use std::path::Path;
+
+/// 空 tree 的 sha（`git hash-object -t tree /dev/null`）
+const EMPTY_TREE_SHA: &str = "4b825dc642cb6eb9a060e54bf8d69288fbee4904";
+
+/// 计算目录的 git tree SHA（40 位小写 hex）
+pub fn compute_git_tree_hash(dir: &Path) -> Result<String, AppError> {
+    Ok(match tree_hash(dir)? {
+        Some(sha) => to_hex(&sha),
+        None => EMPTY_TREE_SHA.to_string(),
+    })
+}
+
+/// 由子对象列表计算 tree SHA（如 GitLab Tree API 返回的根目录条目）
+///
+/// # Arguments
+/// * `entries` - (mode, 名称, 40 位 hex sha)；mode 可带前导 0（如 `040000`）
+///
+/// 条目为空时返回空 tree 的 sha，sha 不是合法 hex 时返回 None
+pub fn tree_hash_from_entries(entries: &[(&str, &str, &str)]) -> Option<String> {
+    let mut sorted: Vec<(Vec<u8>, &str, &str, [u8; 20])> = Vec::new();
+    for (mode, name, sha) in entries {
+        let mode = mode.trim_start_matches('0');
+        let mut key = name.as_bytes().to_vec();
+        if mode == "40000" {
+            key.push(b'/');
+        }
+        sorted.push((key, mode, name, from_hex(sha)?));
+    }
+    if sorted.is_empty() {
+        return Some(EMPTY_TREE_SHA.to_string());
+    }
+
+    sorted.sort_by(|a, b| a.0.cmp(&b.0));
+    let mut content = Vec::new();
+    for (_, mode, name, sha) in &sorted {
+        content.extend_from_slice(format!("{} {}\0", mode, name).as_bytes());
+        content.extend_from_slice(sha);
+    }
+    Some(to_hex(&object_hash("tree", &content)))
+}
+
+/// 40 位 hex 转为 20 字节 sha
+fn from_hex(hex: &str) -> Option<[u8; 20]> {
+    if hex.len() != 40 {
+        return None;
+    }
+    let mut bytes = [0u8; 20];
+    for (i, byte) in bytes.iter_mut().enumerate() {
+        *byte = u8::from_str_radix(hex.get(i * 2..i * 2 + 2)?, 16).ok()?;
+    }
+    Some(bytes)
+}
+
+/// 计算 blob 对象的 sha
+fn blob_hash(content: &[u8]) -> [u8; 20] {
+    object_hash("blob", content)
+}
+
+/// 递归计算 tree 对象的 sha，目录中没有任何文件时返回 None
+fn tree_hash(dir: &Path) -> Result<Option<[u8; 20]>, AppError> {
+    // (排序 key, mode, 名称, sha)
+    let mut entries: Vec<(Vec<u8>, &str, String, [u8; 20])> = Vec::new();
+
+    for entry in fs::read_dir(dir)? {
+        let entry = entry?;
+        let name = entry.file_name().to_string_lossy().to_string();
+        let path = entry.path();
+        let file_type = entry.file_type()?;
+
+        if file_type.is_symlink() {
+            let target = fs::read_link(&path)?;
+            let target = target.to_string_lossy().replace('\\', "/");
+            entries.push((name.clone().into_bytes(), "120000", name, blob_hash(target.as_bytes())));
+        } else if file_type.is_dir() {
+            if name == ".git" {
+                continue;
+            }
+            if let Some(sha) = tree_hash(&path)? {
+                let mut key = name.clone().into_bytes();
+                key.push(b'/');
+                entries.push((key, "40000", name, sha));
+            }
+        } else {
+            let mode = if is_executable(&path) { "100755" } else { "100644" };
+            let sha = blob_hash(&fs::read(&path)?);
+            entries.push((name.clone().into_bytes(), mode, name, sha));
+        }
+    }
+
+    if entries.is_empty() {
+        return Ok(None);
+    }
+
+    entries.sort_by(|a, b| a.0.cmp(&b.0));
+    let mut content = Vec::new();
+    for (_, mode, name, sha) in &entries {
+        content.extend_from_slice(format!("{} {}\0", mode, name).as_bytes());
+        content.extend_from_slice(sha);
+    }
+
+    Ok(Some(object_hash("tree", &content)))
+}
+
+/// `sha1("{kind} {len}\0" + content)`
+fn object_hash(kind: &str, content: &[u8]) -> [u8; 20] {
+    let mut hasher = Sha1::new();
+    hasher.update(format!("{} {}\0", kind, content.len()).as_bytes());
+    hasher.update(content);
+    hasher.finalize().into()
+}
+
+fn to_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+#[cfg(unix)]
+fn is_executable(path: &Path) -> bool {
+    use std::os::unix::fs::PermissionsExt;
+    fs::metadata(path)
+        .map(|m| m.permissions().mode() & 0o111 != 0)
+        .unwrap_or(false)
+}
+
+#[cfg(not(unix))]
+fn is_executable(_path: &Path) -> bool {
+    false
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_blob_hash_matches_git() {
+        // printf 'hello\n' | git hash-object --stdin
+        assert_eq!(
+            to_hex(&blob_hash(b"hello\n")),
+            "ce013625030ba8dba906f756967f9e9ca394464a"
+        );
+    }
+
+    #[test]
+    fn test_tree_hash_from_entries_matches_local() {
+        let dir = tempdir().unwrap();
+        let root = dir.path();
+        fs::create_dir_all(root.join("a")).unwrap();
+        fs::write(root.join("a/b.txt"), "inner\n").unwrap();
+        fs::write(root.join("a.md"), "x\n").unwrap();
+
+        // 远程 API 返回的条目顺序任意，目录 mode 带前导 0
+        let sub_tree = compute_git_tree_hash(&root.join("a")).unwrap();
+        let blob = to_hex(&blob_hash(b"x\n"));
+        let entries = [
+            ("040000", "a", sub_tree.as_str()),
+            ("100644", "a.md", blob.as_str()),
+        ];
+        assert_eq!(
+            tree_hash_from_entries(&entries),
+            Some(compute_git_tree_hash(root).unwrap())
+        );
+        assert_eq!(tree_hash_from_entries(&[]).as_deref(), Some(EMPTY_TREE_SHA));
+        assert_eq!(
+            tree_hash_from_entries(&[("100644", "a.md", "not-hex")]),
+            None
+        );
+    }
+
+    #[test]
+    fn test_empty_dir() {
+        let dir = tempdir().unwrap();
+        fs::create_dir_all(dir.path().join("empty")).unwrap();
+        assert_eq!(compute_git_tree_hash(dir.path()).unwrap(), EMPTY_TREE_SHA);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_tree_hash_matches_git_write_tree() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let dir = tempdir().unwrap();
+        let root = dir.path();
+        fs::create_dir_all(root.join("a")).unwrap();
+        fs::create_dir_all(root.join("scripts")).unwrap();
+        fs::write(root.join("SKILL.md"), "# Demo\n").unwrap();
+        // "a.md" 排在目录 "a" 之前（目录按 "a/" 比较）
+        fs::write(root.join("a.md"), "x\n").unwrap();
+        fs::write(root.join("a/b.txt"), "inner\n").unwrap();
+        fs::write(root.join("scripts/run.sh"), "echo hi\n").unwrap();
+        fs::set_permissions(root.join("scripts/run.sh"), fs::Permissions::from_mode(0o755)).unwrap();
+        fs::create_dir_all(root.join(".git")).unwrap();
+        fs::write(root.join(".git/HEAD"), "ref: refs/heads/main\n").unwrap();
+
+        // 同样的文件在 git 仓库中执行 git write-tree 的结果
+        assert_eq!(
+            compute_git_tree_hash(root).unwrap(),
+            "9bbbeb09008b794a8161f2a5d5bc06fb3874387f"
+        );
+    }
+}
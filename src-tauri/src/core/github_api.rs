@@ -1,19 +1,130 @@
 //! GitHub API 模块
 //!
 //! 功能：
-//! - 获取 GitHub token（环境变量 + gh CLI）
+//! - 获取 GitHub token（环境变量 + ~/.skill-deck/credentials.json + gh CLI）
 //! - 调用 GitHub Trees API 获取 skillFolderHash
+//! - 识别 API 限流（403/429 + X-RateLimit-Remaining: 0）
+//! - 分页获取仓库的分支和 tags
+//! - 获取远程 SKILL.md 内容（Contents API）
+//! - 查询当前 token（或未认证 IP）的限流额度（Rate Limit API）
 
+use crate::core::http_client::{api_client, with_github_headers};
+use crate::core::offline::ensure_online;
+use crate::core::paths::skill_deck_credentials_path;
 use crate::error::AppError;
-use reqwest::Client;
-use serde::Deserialize;
+use crate::models::{GitRefInfo, GitRefType};
+use reqwest::header::HeaderMap;
+use reqwest::{Client, StatusCode};
+use serde::{Deserialize, Serialize};
+use specta::Type;
+use std::collections::HashMap;
 use std::process::Command;
+use std::time::Duration;
+use url::Url;
+
+/// GitHub REST API 根地址
+const GITHUB_API_BASE: &str = "https://api.github.com";
+
+/// github.com 对应的凭据 host
+const DEFAULT_GITHUB_HOST: &str = "github.com";
+
+/// 列表 API 每页条目数（GitHub 上限为 100）
+const REFS_PER_PAGE: u32 = 100;
+
+/// 列表 API 最多翻页数，避免超大仓库耗尽限流额度
+pub(crate) const MAX_REF_PAGES: u32 = 10;
+
+/// Trees API 请求超时（秒），超大仓库的递归 tree 响应可能很慢
+const TREES_TIMEOUT_SECS: u64 = 20;
+
+/// 递归 tree 响应体上限（字节），超过时改用按路径的非递归查询
+const MAX_TREES_RESPONSE_BYTES: u64 = 20 * 1024 * 1024;
 
 /// GitHub Trees API 响应
 #[derive(Debug, Deserialize)]
 struct TreesResponse {
     sha: String,
     tree: Vec<TreeEntry>,
+    /// 递归结果超出 GitHub 的条目/大小上限时为 true，tree 只包含部分条目
+    #[serde(default)]
+    truncated: bool,
+}
+
+/// 单次 Trees API 请求的结果
+enum TreeFetch {
+    Tree(TreesResponse),
+    /// Content-Length 超过 `MAX_TREES_RESPONSE_BYTES`，未读取响应体
+    TooLarge,
+    /// 分支不存在、请求失败或响应无法解析
+    Unavailable,
+}
+
+/// Rate Limit API 响应（只取需要的桶）
+#[derive(Debug, Deserialize)]
+struct RateLimitResponse {
+    resources: RateLimitResources,
+}
+
+#[derive(Debug, Deserialize)]
+struct RateLimitResources {
+    core: RawRateLimitBucket,
+    search: RawRateLimitBucket,
+}
+
+#[derive(Debug, Deserialize)]
+struct RawRateLimitBucket {
+    limit: u32,
+    remaining: u32,
+    #[serde(default)]
+    used: u32,
+    /// Unix 秒
+    reset: i64,
+}
+
+/// 单个限流桶的额度
+#[derive(Debug, Clone, Serialize, Type)]
+#[serde(rename_all = "camelCase")]
+#[specta(rename_all = "camelCase")]
+pub struct RateLimitBucket {
+    pub limit: u32,
+    pub remaining: u32,
+    pub used: u32,
+    /// 额度重置时间（ISO 8601，UTC）
+    pub reset_at: String,
+}
+
+/// GitHub API 限流状态
+#[derive(Debug, Clone, Serialize, Type)]
+#[serde(rename_all = "camelCase")]
+#[specta(rename_all = "camelCase")]
+pub struct GitHubRateLimit {
+    /// 是否找到了 token（环境变量、凭据文件或 gh CLI）
+    pub token_found: bool,
+    /// 额度是否按 token 计算；false 时为按 IP 计算的未认证额度（core 每小时 60 次）
+    pub authenticated: bool,
+    /// 找到的 token 被 GitHub 拒绝（过期或已撤销），额度已回退为未认证查询
+    pub token_rejected: bool,
+    /// REST API（Trees / Contents / refs 等）
+    pub core: RateLimitBucket,
+    /// Search API
+    pub search: RateLimitBucket,
+}
+
+impl From<RawRateLimitBucket> for RateLimitBucket {
+    fn from(raw: RawRateLimitBucket) -> Self {
+        Self {
+            limit: raw.limit,
+            remaining: raw.remaining,
+            used: raw.used,
+            reset_at: format_reset_at(raw.reset),
+        }
+    }
+}
+
+/// 分支 / tag 列表条目（仅需 name）
+#[derive(Debug, Deserialize)]
+struct NamedRef {
+    name: String,
 }
 
 #[derive(Debug, Deserialize)]
@@ -24,34 +135,115 @@ struct TreeEntry {
     sha: String,
 }
 
-/// 获取 GitHub token
+/// GitHub token 的来源
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Type)]
+#[serde(rename_all = "camelCase")]
+#[specta(rename_all = "camelCase")]
+pub enum GithubTokenSource {
+    /// GITHUB_TOKEN 环境变量
+    GithubTokenEnv,
+    /// GH_TOKEN 环境变量
+    GhTokenEnv,
+    /// ~/.skill-deck/credentials.json
+    CredentialsFile,
+    /// `gh auth token`
+    GhCli,
+}
+
+/// 获取指定 host 的 GitHub token
 ///
 /// 优先级：
 /// 1. GITHUB_TOKEN 环境变量
 /// 2. GH_TOKEN 环境变量
-/// 3. gh auth token 命令
-pub fn get_github_token() -> Option<String> {
+/// 3. ~/.skill-deck/credentials.json 中匹配 host 的 token
+/// 4. gh auth token 命令
+///
+/// token 值不会写入日志
+pub fn get_github_token(host: &str) -> Option<String> {
+    get_github_token_with_source(host).map(|(token, _)| token)
+}
+
+/// 同 `get_github_token`，同时返回 token 的来源
+pub fn get_github_token_with_source(host: &str) -> Option<(String, GithubTokenSource)> {
     // 1. 检查 GITHUB_TOKEN
     if let Ok(token) = std::env::var("GITHUB_TOKEN") {
         if !token.is_empty() {
-            return Some(token);
+            return Some((token, GithubTokenSource::GithubTokenEnv));
         }
     }
 
     // 2. 检查 GH_TOKEN
     if let Ok(token) = std::env::var("GH_TOKEN") {
         if !token.is_empty() {
-            return Some(token);
+            return Some((token, GithubTokenSource::GhTokenEnv));
+        }
+    }
+
+    // 3. 凭据文件
+    if let Some(token) = get_credentials_token(host) {
+        return Some((token, GithubTokenSource::CredentialsFile));
+    }
+
+    // 4. 尝试 gh auth token
+    get_gh_cli_token(host).map(|token| (token, GithubTokenSource::GhCli))
+}
+
+/// 从凭据文件读取 host 对应的 token（文件不存在或无法解析时返回 None）
+pub(crate) fn get_credentials_token(host: &str) -> Option<String> {
+    let path = skill_deck_credentials_path();
+    let content = std::fs::read_to_string(&path).ok()?;
+    match serde_json::from_str::<HashMap<String, String>>(&content) {
+        Ok(credentials) => match_host_token(&credentials, host),
+        Err(e) => {
+            // serde_json 的错误信息只包含位置，不会带出 token 内容
+            log::warn!("Ignoring invalid credentials file {}: {}", path.display(), e);
+            None
         }
     }
+}
 
-    // 3. 尝试 gh auth token
-    get_gh_cli_token()
+/// 按 host 查找 token，取最具体的匹配
+///
+/// key 可以是完整 host（`ghe.example.com`）或上级域名（`example.com` 同时匹配其子域名），
+/// 大小写不敏感，允许带 scheme 和末尾 `/`。精确匹配优先，其次取最长的上级域名。
+fn match_host_token(credentials: &HashMap<String, String>, host: &str) -> Option<String> {
+    let host = host.trim().to_ascii_lowercase();
+    credentials
+        .iter()
+        .filter(|(_, token)| !token.trim().is_empty())
+        .filter_map(|(key, token)| {
+            let key = key.trim().to_ascii_lowercase();
+            let key = key
+                .trim_start_matches("https://")
+                .trim_start_matches("http://")
+                .trim_end_matches('/')
+                .to_string();
+            let matches = host == key || host.ends_with(&format!(".{}", key));
+            (matches && !key.is_empty()).then(|| (key.len(), token.trim().to_string()))
+        })
+        .max_by_key(|(len, _)| *len)
+        .map(|(_, token)| token)
+}
+
+/// API 根地址对应的凭据 host（api.github.com 归为 github.com）
+fn token_host(api_base: &str) -> String {
+    if api_base == GITHUB_API_BASE {
+        return DEFAULT_GITHUB_HOST.to_string();
+    }
+    Url::parse(api_base)
+        .ok()
+        .and_then(|u| u.host_str().map(|h| h.to_string()))
+        .unwrap_or_else(|| DEFAULT_GITHUB_HOST.to_string())
 }
 
 /// 通过 gh CLI 获取 token
-fn get_gh_cli_token() -> Option<String> {
-    let output = Command::new("gh").args(["auth", "token"]).output().ok()?;
+fn get_gh_cli_token(host: &str) -> Option<String> {
+    let mut command = Command::new("gh");
+    command.args(["auth", "token"]);
+    if host != DEFAULT_GITHUB_HOST {
+        command.args(["--hostname", host]);
+    }
+    let output = command.output().ok()?;
 
     if output.status.success() {
         let token = String::from_utf8_lossy(&output.stdout).trim().to_string();
@@ -73,77 +265,645 @@ fn get_gh_cli_token() -> Option<String> {
 /// # Returns
 /// * `Ok(Some(hash))` - 成功获取 hash
 /// * `Ok(None)` - API 调用成功但未找到对应文件夹
+/// * `Err(AppError::GitHubRateLimited)` - 触发 GitHub API 限流
 /// * `Err(_)` - API 调用失败
 pub async fn fetch_skill_folder_hash(
     owner_repo: &str,
     skill_path: &str,
     git_ref: Option<&str>,
 ) -> Result<Option<String>, AppError> {
-    // 规范化路径
-    let mut folder_path = skill_path.replace('\\', "/");
+    ensure_online(&format!("GitHub API request for {}", owner_repo))?;
+    fetch_skill_folder_hash_from(GITHUB_API_BASE, owner_repo, skill_path, git_ref).await
+}
 
-    // 移除 SKILL.md 后缀
-    if folder_path.ends_with("/SKILL.md") {
-        folder_path = folder_path[..folder_path.len() - 9].to_string();
-    } else if folder_path.ends_with("SKILL.md") {
-        folder_path = folder_path[..folder_path.len() - 8].to_string();
-    }
+/// `fetch_skill_folder_hash` 的实现，API 根地址可替换（便于测试）
+async fn fetch_skill_folder_hash_from(
+    api_base: &str,
+    owner_repo: &str,
+    skill_path: &str,
+    git_ref: Option<&str>,
+) -> Result<Option<String>, AppError> {
+    let mut hashes =
+        fetch_skill_folder_hashes_from(api_base, owner_repo, &[skill_path], git_ref).await?;
+    Ok(hashes.pop().flatten())
+}
 
-    // 移除尾部斜杠
-    folder_path = folder_path.trim_end_matches('/').to_string();
+/// 批量获取同一仓库内多个 skill 文件夹的 hash，每个分支只请求一次递归 tree
+///
+/// 返回值与 `skill_paths` 一一对应，含义同 `fetch_skill_folder_hash`
+pub async fn fetch_skill_folder_hashes(
+    owner_repo: &str,
+    skill_paths: &[&str],
+    git_ref: Option<&str>,
+) -> Result<Vec<Option<String>>, AppError> {
+    ensure_online(&format!("GitHub API request for {}", owner_repo))?;
+    fetch_skill_folder_hashes_from(GITHUB_API_BASE, owner_repo, skill_paths, git_ref).await
+}
 
-    let token = get_github_token();
-    let client = Client::new();
+/// `fetch_skill_folder_hashes` 的实现，API 根地址可替换（便于测试）
+async fn fetch_skill_folder_hashes_from(
+    api_base: &str,
+    owner_repo: &str,
+    skill_paths: &[&str],
+    git_ref: Option<&str>,
+) -> Result<Vec<Option<String>>, AppError> {
+    let folder_paths: Vec<String> = skill_paths.iter().map(|p| skill_folder_path(p)).collect();
+    let mut hashes: Vec<Option<String>> = vec![None; folder_paths.len()];
+    let token = get_github_token(&token_host(api_base));
+    let trees_url = format!("{}/repos/{}/git/trees", api_base, owner_repo);
 
-    // 如果指定了 git_ref，只尝试该分支；否则尝试 main 和 master
+    // 如果指定了 git_ref，只尝试该分支；否则尝试 main 和 master（已找到的文件夹不再查询）
     let branches: Vec<&str> = match git_ref {
         Some(r) => vec![r],
         None => vec!["main", "master"],
     };
 
     for branch in branches {
-        let url = format!(
-            "https://api.github.com/repos/{}/git/trees/{}?recursive=1",
-            owner_repo, branch
+        let pending: Vec<usize> = (0..hashes.len()).filter(|&i| hashes[i].is_none()).collect();
+        if pending.is_empty() {
+            break;
+        }
+
+        // 仓库根目录的 skill 只需要根 tree SHA，不必递归
+        if pending.iter().all(|&i| folder_paths[i].is_empty()) {
+            if let TreeFetch::Tree(data) =
+                fetch_tree(&format!("{}/{}", trees_url, branch), token.as_deref()).await?
+            {
+                for i in pending {
+                    hashes[i] = Some(data.sha.clone());
+                }
+            }
+            continue;
+        }
+
+        let recursive_url = format!("{}/{}?recursive=1", trees_url, branch);
+        let truncated = match fetch_tree(&recursive_url, token.as_deref()).await? {
+            TreeFetch::Tree(data) => {
+                for &i in &pending {
+                    hashes[i] = if folder_paths[i].is_empty() {
+                        Some(data.sha.clone())
+                    } else {
+                        find_tree_sha(&data.tree, &folder_paths[i])
+                    };
+                }
+                data.truncated
+            }
+            TreeFetch::TooLarge => true,
+            TreeFetch::Unavailable => continue,
+        };
+        if !truncated {
+            continue;
+        }
+
+        // 递归结果不完整时文件夹可能恰好不在其中，改为非递归地只列出父目录（同一父目录只请求一次）
+        let mut scoped: HashMap<String, Option<TreesResponse>> = HashMap::new();
+        for i in pending {
+            if hashes[i].is_some() {
+                continue;
+            }
+            let folder_path = folder_paths[i].as_str();
+            let (parent, name) = folder_path.rsplit_once('/').unwrap_or(("", folder_path));
+            if !scoped.contains_key(parent) {
+                let scoped_url = format!("{}/{}", trees_url, scoped_tree_ref(branch, parent));
+                let data = match fetch_tree(&scoped_url, token.as_deref()).await? {
+                    TreeFetch::Tree(data) => Some(data),
+                    _ => None,
+                };
+                scoped.insert(parent.to_string(), data);
+            }
+            hashes[i] = scoped[parent].as_ref().and_then(|data| {
+                if folder_path.is_empty() {
+                    Some(data.sha.clone())
+                } else {
+                    find_tree_sha(&data.tree, name)
+                }
+            });
+        }
+    }
+
+    Ok(hashes)
+}
+
+/// 请求 Trees API（带独立超时和响应体大小检查）
+///
+/// 限流时返回 `Err(AppError::GitHubRateLimited)`，其余失败返回 `TreeFetch::Unavailable`
+async fn fetch_tree(url: &str, token: Option<&str>) -> Result<TreeFetch, AppError> {
+    let request = api_client()
+        .get(url)
+        .timeout(Duration::from_secs(TREES_TIMEOUT_SECS));
+    let mut request = with_github_headers(request, None);
+    if let Some(t) = token {
+        request = request.header("Authorization", format!("Bearer {}", t));
+    }
+
+    let Ok(response) = request.send().await else {
+        return Ok(TreeFetch::Unavailable);
+    };
+    if is_rate_limited(response.status(), response.headers()) {
+        // 限流时换分支重试也没有意义，直接返回
+        return Err(rate_limit_error(response.headers(), token.is_some()));
+    }
+    if !response.status().is_success() {
+        return Ok(TreeFetch::Unavailable);
+    }
+    if response
+        .content_length()
+        .is_some_and(|len| len > MAX_TREES_RESPONSE_BYTES)
+    {
+        return Ok(TreeFetch::TooLarge);
+    }
+
+    Ok(match response.json::<TreesResponse>().await {
+        Ok(data) => TreeFetch::Tree(data),
+        Err(_) => TreeFetch::Unavailable,
+    })
+}
+
+/// 在 tree 条目中查找指定路径的子目录 SHA
+fn find_tree_sha(entries: &[TreeEntry], path: &str) -> Option<String> {
+    entries
+        .iter()
+        .find(|e| e.entry_type == "tree" && e.path == path)
+        .map(|e| e.sha.clone())
+}
+
+/// 构造指向分支下某个目录的 tree 引用（`{branch}:{path}`），路径各段做 URL 编码
+fn scoped_tree_ref(branch: &str, dir: &str) -> String {
+    if dir.is_empty() {
+        return branch.to_string();
+    }
+    let encoded: Vec<_> = dir.split('/').map(urlencoding::encode).collect();
+    format!("{}:{}", branch, encoded.join("/"))
+}
+
+/// 获取远程 SKILL.md 的原始内容（GitHub Contents API）
+///
+/// # Arguments
+/// * `owner_repo` - 格式为 "owner/repo"
+/// * `skill_path` - SKILL.md 路径，如 "skills/my-skill/SKILL.md"
+/// * `git_ref` - 可选的分支/tag，默认使用仓库默认分支
+///
+/// # Returns
+/// * `Ok(None)` - 文件不存在或 API 返回其他非成功状态
+pub async fn fetch_skill_md(
+    owner_repo: &str,
+    skill_path: &str,
+    git_ref: Option<&str>,
+) -> Result<Option<String>, AppError> {
+    ensure_online(&format!("GitHub API request for {}", owner_repo))?;
+    fetch_skill_md_from(GITHUB_API_BASE, owner_repo, skill_path, git_ref).await
+}
+
+/// `fetch_skill_md` 的实现，API 根地址可替换（便于测试）
+async fn fetch_skill_md_from(
+    api_base: &str,
+    owner_repo: &str,
+    skill_path: &str,
+    git_ref: Option<&str>,
+) -> Result<Option<String>, AppError> {
+    let token = get_github_token(&token_host(api_base));
+    let url = format!("{}/repos/{}/contents/{}", api_base, owner_repo, skill_path);
+
+    let mut request = with_github_headers(api_client().get(&url), Some("application/vnd.github.raw"));
+    if let Some(r) = git_ref {
+        request = request.query(&[("ref", r)]);
+    }
+    if let Some(ref t) = token {
+        request = request.header("Authorization", format!("Bearer {}", t));
+    }
+
+    let response = request.send().await?;
+    if is_rate_limited(response.status(), response.headers()) {
+        return Err(rate_limit_error(response.headers(), token.is_some()));
+    }
+    if !response.status().is_success() {
+        return Ok(None);
+    }
+
+    Ok(Some(response.text().await?))
+}
+
+/// 查询 GitHub API 限流额度
+///
+/// 查询本身不消耗额度。token 被拒绝（401）时改为未认证查询，并标记 `token_rejected`，
+/// 便于区分“没有配置 token”和“token 已失效”。
+pub async fn fetch_rate_limit() -> Result<GitHubRateLimit, AppError> {
+    ensure_online("GitHub API rate limit query")?;
+    fetch_rate_limit_from(GITHUB_API_BASE).await
+}
+
+/// `fetch_rate_limit` 的实现，API 根地址可替换（便于测试）
+async fn fetch_rate_limit_from(api_base: &str) -> Result<GitHubRateLimit, AppError> {
+    let token = get_github_token(&token_host(api_base));
+    let url = format!("{}/rate_limit", api_base);
+
+    let request = |token: Option<&str>| {
+        let mut request = with_github_headers(api_client().get(&url), None);
+        if let Some(t) = token {
+            request = request.header("Authorization", format!("Bearer {}", t));
+        }
+        request
+    };
+
+    let mut response = request(token.as_deref()).send().await?;
+    let mut authenticated = token.is_some();
+    let mut token_rejected = false;
+    if token.is_some() && response.status() == StatusCode::UNAUTHORIZED {
+        token_rejected = true;
+        authenticated = false;
+        response = request(None).send().await?;
+    }
+    if !response.status().is_success() {
+        return Err(AppError::GitNetworkError {
+            message: format!("GitHub rate limit query failed: HTTP {}", response.status()),
+        });
+    }
+
+    let data: RateLimitResponse = response.json().await?;
+    Ok(GitHubRateLimit {
+        token_found: token.is_some(),
+        authenticated,
+        token_rejected,
+        core: data.resources.core.into(),
+        search: data.resources.search.into(),
+    })
+}
+
+/// 获取仓库的分支和 tags
+///
+/// 分别调用 branches / tags API，按 Link header 翻页（最多 `MAX_REF_PAGES` 页）。
+/// 没有 tags 的仓库只返回分支。
+///
+/// # Arguments
+/// * `owner_repo` - 格式为 "owner/repo"
+pub async fn list_github_refs(owner_repo: &str) -> Result<Vec<GitRefInfo>, AppError> {
+    ensure_online(&format!("GitHub API request for {}", owner_repo))?;
+    list_github_refs_from(GITHUB_API_BASE, owner_repo).await
+}
+
+/// `list_github_refs` 的实现，API 根地址可替换（便于测试）
+async fn list_github_refs_from(
+    api_base: &str,
+    owner_repo: &str,
+) -> Result<Vec<GitRefInfo>, AppError> {
+    let token = get_github_token(&token_host(api_base));
+    let client = api_client();
+    let mut refs = Vec::new();
+
+    for (endpoint, ref_type) in [("branches", GitRefType::Branch), ("tags", GitRefType::Tag)] {
+        let first_page = format!(
+            "{}/repos/{}/{}?per_page={}",
+            api_base, owner_repo, endpoint, REFS_PER_PAGE
         );
+        let names =
+            fetch_paginated_names(client, owner_repo, first_page, token.as_deref()).await?;
+        refs.extend(names.into_iter().map(|name| GitRefInfo { name, ref_type }));
+    }
 
-        let mut request = client
-            .get(&url)
-            .header("Accept", "application/vnd.github.v3+json")
-            .header("User-Agent", "skill-deck");
+    Ok(refs)
+}
+
+/// 按 Link header 的 rel="next" 依次获取所有页的 name 字段
+async fn fetch_paginated_names(
+    client: &Client,
+    owner_repo: &str,
+    first_page: String,
+    token: Option<&str>,
+) -> Result<Vec<String>, AppError> {
+    let mut names = Vec::new();
+    let mut next_url = Some(first_page);
+    let mut pages = 0;
 
-        if let Some(ref t) = token {
+    while let Some(url) = next_url.take() {
+        if pages >= MAX_REF_PAGES {
+            break;
+        }
+        pages += 1;
+
+        let mut request = with_github_headers(client.get(&url), None);
+        if let Some(t) = token {
             request = request.header("Authorization", format!("Bearer {}", t));
         }
 
-        let response = request.send().await;
+        let resp = request.send().await?;
+        if is_rate_limited(resp.status(), resp.headers()) {
+            return Err(rate_limit_error(resp.headers(), token.is_some()));
+        }
+        if resp.status() == StatusCode::NOT_FOUND {
+            return Err(AppError::GitRepoNotFound {
+                repo: owner_repo.to_string(),
+            });
+        }
+        if !resp.status().is_success() {
+            return Err(AppError::GitNetworkError {
+                message: format!("GitHub API returned {}", resp.status()),
+            });
+        }
+
+        next_url = next_page_link(resp.headers());
+        let page: Vec<NamedRef> = resp.json().await?;
+        names.extend(page.into_iter().map(|r| r.name));
+    }
 
-        match response {
-            Ok(resp) if resp.status().is_success() => {
-                if let Ok(data) = resp.json::<TreesResponse>().await {
-                    // 如果 folder_path 为空，返回根 tree SHA
-                    if folder_path.is_empty() {
-                        return Ok(Some(data.sha));
-                    }
+    Ok(names)
+}
 
-                    // 查找对应的 tree entry
-                    for entry in data.tree {
-                        if entry.entry_type == "tree" && entry.path == folder_path {
-                            return Ok(Some(entry.sha));
-                        }
-                    }
-                }
-            }
-            _ => continue,
+/// 解析 Link header 中 rel="next" 的 URL
+///
+/// 格式：`<https://api.github.com/...&page=2>; rel="next", <...>; rel="last"`
+fn next_page_link(headers: &HeaderMap) -> Option<String> {
+    let link = headers.get("link")?.to_str().ok()?;
+    link.split(',').find_map(|part| {
+        let (url, params) = part.split_once(';')?;
+        if params.split(';').any(|p| p.trim() == "rel=\"next\"") {
+            Some(url.trim().trim_start_matches('<').trim_end_matches('>').to_string())
+        } else {
+            None
         }
+    })
+}
+
+/// 将 lock 中的 skillPath 规范化为仓库内的文件夹路径
+///
+/// `skills/my-skill/SKILL.md` → `skills/my-skill`；仓库根目录的 skill 返回空字符串
+pub(crate) fn skill_folder_path(skill_path: &str) -> String {
+    // 规范化路径
+    let mut folder_path = skill_path.replace('\\', "/");
+
+    // 移除 SKILL.md 后缀
+    if folder_path.ends_with("/SKILL.md") {
+        folder_path = folder_path[..folder_path.len() - 9].to_string();
+    } else if folder_path.ends_with("SKILL.md") {
+        folder_path = folder_path[..folder_path.len() - 8].to_string();
     }
 
-    Ok(None)
+    // 移除尾部斜杠
+    folder_path.trim_end_matches('/').to_string()
+}
+
+/// 判断响应是否为 GitHub API 限流
+///
+/// GitHub 在额度耗尽时返回 403（部分情况 429），并带 `X-RateLimit-Remaining: 0`。
+/// 普通的 403（如无权限）不带该 header 为 0，不视为限流。
+fn is_rate_limited(status: StatusCode, headers: &HeaderMap) -> bool {
+    (status == StatusCode::FORBIDDEN || status == StatusCode::TOO_MANY_REQUESTS)
+        && headers
+            .get("x-ratelimit-remaining")
+            .and_then(|v| v.to_str().ok())
+            .map(|v| v.trim() == "0")
+            .unwrap_or(false)
+}
+
+/// 根据限流响应构造 `AppError::GitHubRateLimited`
+///
+/// `X-RateLimit-Reset` 为 Unix 秒，转换为 ISO 8601（UTC）。
+fn rate_limit_error(headers: &HeaderMap, has_token: bool) -> AppError {
+    let reset_at = headers
+        .get("x-ratelimit-reset")
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| v.trim().parse::<i64>().ok())
+        .map(format_reset_at)
+        .unwrap_or_default();
+
+    let mut message = if reset_at.is_empty() {
+        "GitHub API rate limit exceeded".to_string()
+    } else {
+        format!("GitHub API rate limit exceeded, resets at {}", reset_at)
+    };
+
+    if has_token {
+        message
+            .push_str(". The configured GitHub token may be exhausted or lack the required scope");
+    } else {
+        message.push_str(". Set GITHUB_TOKEN or run `gh auth login` to raise the limit");
+    }
+
+    AppError::GitHubRateLimited { reset_at, message }
+}
+
+/// Unix 秒转换为 ISO 8601（UTC），超出范围时为空字符串
+fn format_reset_at(secs: i64) -> String {
+    chrono::DateTime::from_timestamp(secs, 0)
+        .map(|dt| dt.format("%Y-%m-%dT%H:%M:%SZ").to_string())
+        .unwrap_or_default()
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::core::mock_http::{self, MockServer};
+
+    #[test]
+    fn test_fetch_skill_md() {
+        let server = MockServer::start(vec![mock_http::ok(
+            "---\nname: demo\ndescription: d\nversion: 2.2.0\n---",
+        )]);
+
+        let content = tauri::async_runtime::block_on(fetch_skill_md_from(
+            &server.url,
+            "owner/repo",
+            "skills/demo/SKILL.md",
+            None,
+        ))
+        .unwrap()
+        .unwrap();
+
+        let frontmatter = crate::core::skill::parse_skill_md_content(&content).unwrap();
+        assert_eq!(frontmatter.version.as_deref(), Some("2.2.0"));
+    }
+
+    #[test]
+    fn test_fetch_rate_limit() {
+        let body = r#"{"resources":{"core":{"limit":60,"used":58,"remaining":2,"reset":1700000000},"search":{"limit":10,"used":0,"remaining":10,"reset":1700000060},"graphql":{"limit":0,"used":0,"remaining":0,"reset":1700000000}},"rate":{"limit":60,"used":58,"remaining":2,"reset":1700000000}}"#;
+        let server = MockServer::start(vec![mock_http::ok(body)]);
+
+        let status = tauri::async_runtime::block_on(fetch_rate_limit_from(&server.url)).unwrap();
+        assert!(!status.token_rejected);
+        assert_eq!(status.authenticated, status.token_found);
+        assert_eq!(status.core.limit, 60);
+        assert_eq!(status.core.remaining, 2);
+        assert_eq!(status.core.used, 58);
+        assert_eq!(status.core.reset_at, "2023-11-14T22:13:20Z");
+        assert_eq!(status.search.remaining, 10);
+    }
+
+    #[test]
+    fn test_fetch_skill_folder_hash_rate_limited() {
+        let server = MockServer::start(vec![mock_http::response(
+            "403 Forbidden",
+            "X-RateLimit-Remaining: 0\r\nX-RateLimit-Reset: 1700000000\r\n",
+            "{}",
+        )]);
+
+        let result = tauri::async_runtime::block_on(fetch_skill_folder_hash_from(
+            &server.url,
+            "owner/repo",
+            "skills/demo/SKILL.md",
+            None,
+        ));
+
+        match result {
+            Err(AppError::GitHubRateLimited { reset_at, message }) => {
+                assert_eq!(reset_at, "2023-11-14T22:13:20Z");
+                assert!(message.contains("2023-11-14T22:13:20Z"));
+            }
+            other => panic!("expected GitHubRateLimited, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_fetch_skill_folder_hash_truncated_falls_back_to_scoped_tree() {
+        // 递归结果被截断且不含目标文件夹；随后的父目录查询包含它
+        let server = MockServer::start(vec![
+            mock_http::ok(r#"{"sha":"root","truncated":true,"tree":[{"path":"a","type":"tree","sha":"aaa"}]}"#),
+            mock_http::ok(r#"{"sha":"skills","truncated":false,"tree":[{"path":"my skill","type":"tree","sha":"bbb"}]}"#),
+        ]);
+
+        let hash = tauri::async_runtime::block_on(fetch_skill_folder_hash_from(
+            &server.url,
+            "owner/repo",
+            "skills/my skill/SKILL.md",
+            Some("main"),
+        ))
+        .unwrap();
+
+        assert_eq!(hash, Some("bbb".to_string()));
+        assert!(server.next_request().contains("/git/trees/main?recursive=1"));
+        assert!(server.next_request().contains("/git/trees/main:skills "));
+    }
+
+    #[test]
+    fn test_fetch_skill_folder_hashes_uses_one_tree_request() {
+        // 只响应一次：多个 skill 共用同一个递归 tree
+        let server = MockServer::start(vec![mock_http::ok(
+            r#"{"sha":"root","truncated":false,"tree":[{"path":"skills/a","type":"tree","sha":"aaa"},{"path":"skills/b","type":"tree","sha":"bbb"}]}"#,
+        )]);
+
+        let hashes = tauri::async_runtime::block_on(fetch_skill_folder_hashes_from(
+            &server.url,
+            "owner/repo",
+            &["skills/a/SKILL.md", "SKILL.md", "skills/b/SKILL.md"],
+            Some("main"),
+        ))
+        .unwrap();
+
+        assert_eq!(
+            hashes,
+            vec![Some("aaa".to_string()), Some("root".to_string()), Some("bbb".to_string())]
+        );
+        assert!(server.next_request().contains("/git/trees/main?recursive=1"));
+        assert!(server.no_requests());
+    }
+
+    #[test]
+    fn test_scoped_tree_ref() {
+        assert_eq!(scoped_tree_ref("main", ""), "main");
+        assert_eq!(scoped_tree_ref("main", "skills/my skill"), "main:skills/my%20skill");
+    }
+
+    #[test]
+    fn test_list_github_refs_paginates() {
+        let server = MockServer::bind();
+        let page2 = format!("{}/repos/owner/repo/tags?per_page=100&page=2", server.url);
+
+        // 依次响应：branches、tags 第 1 页（带 next link）、tags 第 2 页
+        let server = server.serve(vec![
+            mock_http::ok(r#"[{"name":"main"}]"#),
+            mock_http::response(
+                "200 OK",
+                &format!("Link: <{}>; rel=\"next\", <{}>; rel=\"last\"\r\n", page2, page2),
+                r#"[{"name":"v2.0.0"}]"#,
+            ),
+            mock_http::ok(r#"[{"name":"v1.0.0"}]"#),
+        ]);
+
+        let refs =
+            tauri::async_runtime::block_on(list_github_refs_from(&server.url, "owner/repo")).unwrap();
+
+        let names: Vec<(&str, GitRefType)> =
+            refs.iter().map(|r| (r.name.as_str(), r.ref_type)).collect();
+        assert_eq!(
+            names,
+            vec![
+                ("main", GitRefType::Branch),
+                ("v2.0.0", GitRefType::Tag),
+                ("v1.0.0", GitRefType::Tag),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_next_page_link() {
+        let mut headers = HeaderMap::new();
+        assert_eq!(next_page_link(&headers), None);
+
+        headers.insert(
+            "link",
+            r#"<https://api.github.com/x?page=1>; rel="prev", <https://api.github.com/x?page=3>; rel="next""#
+                .parse()
+                .unwrap(),
+        );
+        assert_eq!(
+            next_page_link(&headers),
+            Some("https://api.github.com/x?page=3".to_string())
+        );
+    }
+
+    #[test]
+    fn test_is_rate_limited_requires_zero_remaining() {
+        let mut headers = HeaderMap::new();
+        assert!(!is_rate_limited(StatusCode::FORBIDDEN, &headers));
+
+        headers.insert("x-ratelimit-remaining", "12".parse().unwrap());
+        assert!(!is_rate_limited(StatusCode::FORBIDDEN, &headers));
+
+        headers.insert("x-ratelimit-remaining", "0".parse().unwrap());
+        assert!(is_rate_limited(StatusCode::FORBIDDEN, &headers));
+        assert!(!is_rate_limited(StatusCode::NOT_FOUND, &headers));
+    }
+
+    #[test]
+    fn test_rate_limit_error_token_hint() {
+        let headers = HeaderMap::new();
+        match rate_limit_error(&headers, true) {
+            AppError::GitHubRateLimited { reset_at, message } => {
+                assert!(reset_at.is_empty());
+                assert!(message.contains("scope"));
+            }
+            other => panic!("unexpected error: {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_match_host_token() {
+        let credentials: HashMap<String, String> = [
+            ("github.com", "public"),
+            ("https://Example.com/", "org"),
+            ("ghe.example.com", "enterprise"),
+            ("empty.example.com", " "),
+        ]
+        .into_iter()
+        .map(|(k, v)| (k.to_string(), v.to_string()))
+        .collect();
+
+        assert_eq!(match_host_token(&credentials, "github.com"), Some("public".to_string()));
+        // 精确匹配优先于上级域名
+        assert_eq!(
+            match_host_token(&credentials, "GHE.example.com"),
+            Some("enterprise".to_string())
+        );
+        assert_eq!(match_host_token(&credentials, "git.example.com"), Some("org".to_string()));
+        // 空 token 忽略，回退到上级域名
+        assert_eq!(match_host_token(&credentials, "empty.example.com"), Some("org".to_string()));
+        // 不做子串匹配
+        assert_eq!(match_host_token(&credentials, "notgithub.com"), None);
+    }
+
+    #[test]
+    fn test_token_host() {
+        assert_eq!(token_host(GITHUB_API_BASE), "github.com");
+        assert_eq!(token_host("https://ghe.example.com/api/v3"), "ghe.example.com");
+    }
 
     #[test]
     fn test_get_github_token_from_env() {
@@ -152,7 +912,7 @@ mod tests {
 
         // 设置测试值
         std::env::set_var("GITHUB_TOKEN", "test-token");
-        assert_eq!(get_github_token(), Some("test-token".to_string()));
+        assert_eq!(get_github_token(DEFAULT_GITHUB_HOST), Some("test-token".to_string()));
 
         // 恢复原始值
         match original {
@@ -0,0 +1,389 @@
+//! GitLab API 模块
+//!
+//! 功能：
+//! - 获取 GitLab token（环境变量）
+//! - 调用 GitLab Repository Tree API 获取 skill 文件夹的 tree id（等价于 GitHub 的 skillFolderHash）
+//! - 分页获取仓库的分支和 tags
+//! - 获取远程 SKILL.md 内容（Repository Files API）
+
+use crate::core::git_hash::tree_hash_from_entries;
+use crate::core::github_api::{skill_folder_path, MAX_REF_PAGES};
+use crate::core::http_client::{api_client, with_user_agent};
+use crate::core::offline::ensure_online;
+use crate::error::AppError;
+use crate::models::{GitRefInfo, GitRefType};
+use reqwest::RequestBuilder;
+use serde::Deserialize;
+use std::collections::HashMap;
+use url::Url;
+
+/// 未能从来源 URL 解析出主机时使用的默认主机
+const DEFAULT_GITLAB_HOST: &str = "gitlab.com";
+
+/// 列表 API 每页条目数（GitLab 上限为 100）
+const PER_PAGE: u32 = 100;
+
+/// Repository Tree API 条目
+#[derive(Debug, Deserialize)]
+struct TreeEntry {
+    id: String,
+    name: String,
+    path: String,
+    mode: String,
+    #[serde(rename = "type")]
+    entry_type: String,
+}
+
+/// 分支 / tag 列表条目（仅需 name）
+#[derive(Debug, Deserialize)]
+struct NamedRef {
+    name: String,
+}
+
+/// 获取 GitLab token（GITLAB_TOKEN 环境变量）
+pub fn get_gitlab_token() -> Option<String> {
+    std::env::var("GITLAB_TOKEN").ok().filter(|t| !t.is_empty())
+}
+
+/// 根据来源 URL 构造 GitLab API 根地址
+///
+/// 支持自托管实例：`https://git.example.com/group/repo` → `https://git.example.com/api/v4`。
+/// 项目 lock 不记录 sourceUrl，此时回退到 gitlab.com。
+pub fn gitlab_api_base(source_url: &str) -> String {
+    let parsed = Url::parse(source_url).ok();
+    let host = parsed
+        .as_ref()
+        .and_then(|u| u.host_str())
+        .unwrap_or(DEFAULT_GITLAB_HOST);
+    let scheme = match parsed.as_ref().map(|u| u.scheme()) {
+        Some("http") => "http",
+        _ => "https",
+    };
+    let port = parsed
+        .as_ref()
+        .and_then(|u| u.port())
+        .map(|p| format!(":{}", p))
+        .unwrap_or_default();
+
+    format!("{}://{}{}/api/v4", scheme, host, port)
+}
+
+/// 获取 skill 文件夹的 tree id（通过 GitLab Repository Tree API）
+///
+/// # Arguments
+/// * `api_base` - API 根地址，见 `gitlab_api_base`
+/// * `project_path` - 项目路径，如 "group/subgroup/repo"
+/// * `skill_path` - 文件夹路径，如 "skills/my-skill/SKILL.md"
+/// * `git_ref` - 可选的分支/tag，默认使用项目默认分支
+///
+/// # Returns
+/// * `Ok(Some(hash))` - 成功获取 tree id（仓库根目录的 skill 按根目录条目计算）
+/// * `Ok(None)` - API 调用成功但未找到对应文件夹
+/// * `Err(_)` - API 调用失败
+pub async fn fetch_gitlab_folder_hash(
+    api_base: &str,
+    project_path: &str,
+    skill_path: &str,
+    git_ref: Option<&str>,
+) -> Result<Option<String>, AppError> {
+    let mut hashes = fetch_gitlab_folder_hashes(api_base, project_path, &[skill_path], git_ref).await?;
+    Ok(hashes.pop().flatten())
+}
+
+/// 批量获取同一项目内多个 skill 文件夹的 tree id，同一父目录只列出一次
+///
+/// 返回值与 `skill_paths` 一一对应，含义同 `fetch_gitlab_folder_hash`
+pub async fn fetch_gitlab_folder_hashes(
+    api_base: &str,
+    project_path: &str,
+    skill_paths: &[&str],
+    git_ref: Option<&str>,
+) -> Result<Vec<Option<String>>, AppError> {
+    ensure_online(&format!("GitLab API request for {}", project_path))?;
+    let project_url = format!(
+        "{}/projects/{}",
+        api_base.trim_end_matches('/'),
+        urlencoding::encode(project_path)
+    );
+    let client = api_client();
+    let token = get_gitlab_token();
+    let mut listings: HashMap<String, Option<Vec<TreeEntry>>> = HashMap::new();
+    let mut hashes = Vec::with_capacity(skill_paths.len());
+
+    for skill_path in skill_paths {
+        let folder_path = skill_folder_path(skill_path);
+
+        // 列出父目录，找到目标文件夹对应的 tree 条目
+        let parent = folder_path
+            .rsplit_once('/')
+            .map(|(parent, _)| parent)
+            .unwrap_or("");
+        if !listings.contains_key(parent) {
+            let entries = list_tree(client, &project_url, token.as_deref(), parent, git_ref).await?;
+            listings.insert(parent.to_string(), entries);
+        }
+        let Some(entries) = &listings[parent] else {
+            hashes.push(None);
+            continue;
+        };
+
+        // Tree API 不返回根目录自身的 id：由根目录条目按 git 的 tree 格式计算（与 git 中的 tree id 相同）
+        if folder_path.is_empty() {
+            let objects: Vec<(&str, &str, &str)> = entries
+                .iter()
+                .map(|e| (e.mode.as_str(), e.name.as_str(), e.id.as_str()))
+                .collect();
+            hashes.push(tree_hash_from_entries(&objects));
+            continue;
+        }
+
+        hashes.push(
+            entries
+                .iter()
+                .find(|e| e.entry_type == "tree" && e.path == folder_path)
+                .map(|e| e.id.clone()),
+        );
+    }
+
+    Ok(hashes)
+}
+
+/// 分页列出目录的全部条目（`path` 为空时为根目录），请求失败或超出页数上限时返回 `Ok(None)`
+async fn list_tree(
+    client: &reqwest::Client,
+    project_url: &str,
+    token: Option<&str>,
+    path: &str,
+    git_ref: Option<&str>,
+) -> Result<Option<Vec<TreeEntry>>, AppError> {
+    let mut entries = Vec::new();
+    let mut page: u32 = 1;
+
+    loop {
+        let mut request = client
+            .get(format!("{}/repository/tree", project_url))
+            .query(&[("per_page", PER_PAGE), ("page", page)]);
+        if !path.is_empty() {
+            request = request.query(&[("path", path)]);
+        }
+        if let Some(r) = git_ref {
+            request = request.query(&[("ref", r)]);
+        }
+
+        let response = with_headers(request, token).send().await?;
+        if !response.status().is_success() {
+            return Ok(None);
+        }
+
+        // X-Next-Page 为空表示最后一页
+        let next_page = response
+            .headers()
+            .get("x-next-page")
+            .and_then(|v| v.to_str().ok())
+            .and_then(|v| v.trim().parse::<u32>().ok());
+
+        entries.extend(response.json::<Vec<TreeEntry>>().await?);
+
+        match next_page {
+            // 超出页数上限时条目不完整，无法可靠地定位或计算 tree id
+            Some(_) if page >= MAX_REF_PAGES => return Ok(None),
+            Some(next) if next > page => page = next,
+            _ => return Ok(Some(entries)),
+        }
+    }
+}
+
+/// 获取远程 SKILL.md 的原始内容（Repository Files API）
+///
+/// 未指定 git_ref 时使用 `HEAD`（项目默认分支）。文件不存在时返回 `Ok(None)`。
+pub async fn fetch_gitlab_skill_md(
+    api_base: &str,
+    project_path: &str,
+    skill_path: &str,
+    git_ref: Option<&str>,
+) -> Result<Option<String>, AppError> {
+    ensure_online(&format!("GitLab API request for {}", project_path))?;
+    let url = format!(
+        "{}/projects/{}/repository/files/{}/raw",
+        api_base.trim_end_matches('/'),
+        urlencoding::encode(project_path),
+        urlencoding::encode(skill_path)
+    );
+    let request = api_client()
+        .get(url)
+        .query(&[("ref", git_ref.unwrap_or("HEAD"))]);
+
+    let response = with_headers(request, get_gitlab_token().as_deref()).send().await?;
+    if !response.status().is_success() {
+        return Ok(None);
+    }
+
+    Ok(Some(response.text().await?))
+}
+
+/// 获取项目的分支和 tags
+///
+/// 分别调用 repository/branches 和 repository/tags API，按 X-Next-Page 翻页（最多 `MAX_REF_PAGES` 页）。
+pub async fn list_gitlab_refs(
+    api_base: &str,
+    project_path: &str,
+) -> Result<Vec<GitRefInfo>, AppError> {
+    ensure_online(&format!("GitLab API request for {}", project_path))?;
+    let project_url = format!(
+        "{}/projects/{}",
+        api_base.trim_end_matches('/'),
+        urlencoding::encode(project_path)
+    );
+    let client = api_client();
+    let token = get_gitlab_token();
+    let mut refs = Vec::new();
+
+    for (endpoint, ref_type) in [("branches", GitRefType::Branch), ("tags", GitRefType::Tag)] {
+        let mut page: u32 = 1;
+        for _ in 0..MAX_REF_PAGES {
+            let request = client
+                .get(format!("{}/repository/{}", project_url, endpoint))
+                .query(&[("per_page", PER_PAGE), ("page", page)]);
+            let response = with_headers(request, token.as_deref()).send().await?;
+
+            if response.status() == reqwest::StatusCode::NOT_FOUND {
+                return Err(AppError::GitRepoNotFound {
+                    repo: project_path.to_string(),
+                });
+            }
+            if !response.status().is_success() {
+                return Err(AppError::GitNetworkError {
+                    message: format!("GitLab API returned {}", response.status()),
+                });
+            }
+
+            let next_page = response
+                .headers()
+                .get("x-next-page")
+                .and_then(|v| v.to_str().ok())
+                .and_then(|v| v.trim().parse::<u32>().ok());
+
+            let names: Vec<NamedRef> = response.json().await?;
+            refs.extend(names.into_iter().map(|r| GitRefInfo { name: r.name, ref_type }));
+
+            match next_page {
+                Some(next) if next > page => page = next,
+                _ => break,
+            }
+        }
+    }
+
+    Ok(refs)
+}
+
+/// 添加通用请求头（有 token 时附带 PRIVATE-TOKEN）
+fn with_headers(request: RequestBuilder, token: Option<&str>) -> RequestBuilder {
+    let request = with_user_agent(request);
+    match token {
+        Some(t) => request.header("PRIVATE-TOKEN", t),
+        None => request,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::mock_http::{self, MockServer};
+
+    /// 启动只响应一次的本地 GitLab API 服务
+    fn serve_once(body: &str) -> MockServer {
+        MockServer::start(vec![mock_http::response(
+            "200 OK",
+            "Content-Type: application/json\r\nX-Next-Page: \r\n",
+            body,
+        )])
+    }
+
+    fn api_base(server: &MockServer) -> String {
+        format!("{}/api/v4", server.url)
+    }
+
+    #[test]
+    fn test_gitlab_api_base() {
+        assert_eq!(
+            gitlab_api_base("https://gitlab.com/group/repo"),
+            "https://gitlab.com/api/v4"
+        );
+        assert_eq!(
+            gitlab_api_base("http://git.example.com:8080/group/sub/repo.git"),
+            "http://git.example.com:8080/api/v4"
+        );
+        // 项目 lock 没有 sourceUrl
+        assert_eq!(gitlab_api_base(""), "https://gitlab.com/api/v4");
+    }
+
+    #[test]
+    fn test_fetch_gitlab_folder_hash() {
+        let server = serve_once(
+            r#"[{"id":"aaa","name":"other","type":"tree","path":"skills/other","mode":"040000"},
+                {"id":"bbb","name":"demo","type":"tree","path":"skills/demo","mode":"040000"}]"#,
+        );
+
+        let hash = tauri::async_runtime::block_on(fetch_gitlab_folder_hash(
+            &api_base(&server),
+            "group/repo",
+            "skills/demo/SKILL.md",
+            None,
+        ))
+        .unwrap();
+
+        assert_eq!(hash, Some("bbb".to_string()));
+        let request_line = server.next_request();
+        assert!(request_line.contains("/projects/group%2Frepo/repository/tree"));
+        assert!(request_line.contains("path=skills"));
+    }
+
+    #[test]
+    fn test_fetch_gitlab_folder_hashes_lists_parent_once() {
+        let server = serve_once(
+            r#"[{"id":"aaa","name":"a","type":"tree","path":"skills/a","mode":"040000"},
+                {"id":"bbb","name":"b","type":"tree","path":"skills/b","mode":"040000"}]"#,
+        );
+
+        let hashes = tauri::async_runtime::block_on(fetch_gitlab_folder_hashes(
+            &api_base(&server),
+            "group/repo",
+            &["skills/b/SKILL.md", "skills/missing/SKILL.md", "skills/a/SKILL.md"],
+            None,
+        ))
+        .unwrap();
+
+        assert_eq!(hashes, vec![Some("bbb".to_string()), None, Some("aaa".to_string())]);
+        assert!(server.next_request().contains("path=skills"));
+        assert!(server.no_requests());
+    }
+
+    #[test]
+    fn test_fetch_gitlab_root_folder_hash() {
+        // 根目录 skill 没有自身的 tree 条目，由根目录条目计算 tree id
+        let server = serve_once(
+            r#"[{"id":"6b6d2bf1b8e6cbc6c28e8ca4b7d0a7d4ba11e8f7","name":"SKILL.md","type":"blob","path":"SKILL.md","mode":"100644"}]"#,
+        );
+
+        let hash = tauri::async_runtime::block_on(fetch_gitlab_folder_hash(
+            &api_base(&server),
+            "group/repo",
+            "SKILL.md",
+            None,
+        ))
+        .unwrap();
+
+        assert_eq!(
+            hash,
+            tree_hash_from_entries(&[(
+                "100644",
+                "SKILL.md",
+                "6b6d2bf1b8e6cbc6c28e8ca4b7d0a7d4ba11e8f7"
+            )])
+        );
+        assert!(hash.is_some());
+        let request_line = server.next_request();
+        assert!(request_line.contains("/repository/tree"));
+        assert!(!request_line.contains("path="));
+    }
+}
@@ -0,0 +1,249 @@
+//! 共享 HTTP 客户端模块
+//!
+//! 功能：
+//! - 为 GitHub / GitLab / 审计 API 提供进程内共享的 reqwest Client（延迟初始化，复用连接池）
+//! - 显式读取 HTTPS_PROXY / HTTP_PROXY / NO_PROXY（兼容小写形式），支持企业代理环境
+//! - 统一的请求超时
+//! - 统一的请求头：User-Agent 和 GitHub API 的 Accept 可在 config.json 中配置（企业代理按 UA 过滤等），
+//!   GitHub 请求同时附带 `X-GitHub-Api-Version`
+
+use crate::core::paths::read_skill_deck_config;
+use crate::error::AppError;
+use once_cell::sync::Lazy;
+use reqwest::header::HeaderValue;
+use reqwest::{Client, NoProxy, Proxy, RequestBuilder};
+use std::sync::RwLock;
+use std::time::Duration;
+
+/// API 请求超时（秒）
+const API_TIMEOUT_SECS: u64 = 30;
+
+/// 默认 User-Agent
+pub const DEFAULT_USER_AGENT: &str = "skill-deck";
+
+/// GitHub JSON API 默认的 Accept
+pub const DEFAULT_GITHUB_ACCEPT: &str = "application/vnd.github.v3+json";
+
+/// 请求的 GitHub REST API 版本
+const GITHUB_API_VERSION: &str = "2022-11-28";
+
+static API_CLIENT: Lazy<Client> = Lazy::new(|| {
+    build_api_client(&ProxyEnv::from_env(), Duration::from_secs(API_TIMEOUT_SECS))
+});
+
+/// 获取共享的 API 客户端
+///
+/// 需要更短超时的调用方可通过 `RequestBuilder::timeout` 单独覆盖
+pub fn api_client() -> &'static Client {
+    &API_CLIENT
+}
+
+/// 可配置的请求头
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ApiHeaders {
+    pub user_agent: String,
+    /// GitHub JSON API 的 Accept（下载原始文件等需要特定 Accept 的请求不使用）
+    pub github_accept: String,
+}
+
+impl Default for ApiHeaders {
+    fn default() -> Self {
+        Self::new(None, None)
+    }
+}
+
+impl ApiHeaders {
+    /// 未设置或为空时使用默认值
+    pub fn new(user_agent: Option<&str>, github_accept: Option<&str>) -> Self {
+        let or_default = |value: Option<&str>, default: &str| {
+            value
+                .map(str::trim)
+                .filter(|v| !v.is_empty())
+                .unwrap_or(default)
+                .to_string()
+        };
+        Self {
+            user_agent: or_default(user_agent, DEFAULT_USER_AGENT),
+            github_accept: or_default(github_accept, DEFAULT_GITHUB_ACCEPT),
+        }
+    }
+}
+
+/// 当前生效的请求头
+/// 首次访问时从 config.json 加载，save_config 时通过 set_api_headers 同步
+static API_HEADERS: Lazy<RwLock<ApiHeaders>> = Lazy::new(|| {
+    let config = read_skill_deck_config().unwrap_or_default();
+    RwLock::new(ApiHeaders::new(
+        config.user_agent.as_deref(),
+        config.github_accept.as_deref(),
+    ))
+});
+
+/// 校验配置的请求头值（不能包含换行等非法字符）
+pub fn validate_header_value(name: &str, value: &str) -> Result<(), AppError> {
+    HeaderValue::from_str(value.trim())
+        .map(|_| ())
+        .map_err(|_| AppError::Custom {
+            message: format!("Invalid {} header value: {}", name, value),
+        })
+}
+
+/// 更新内存中的请求头设置（save_config 后调用）
+pub fn set_api_headers(user_agent: Option<&str>, github_accept: Option<&str>) {
+    if let Ok(mut guard) = API_HEADERS.write() {
+        *guard = ApiHeaders::new(user_agent, github_accept);
+    }
+}
+
+/// 当前生效的请求头设置
+pub fn api_headers() -> ApiHeaders {
+    API_HEADERS.read().map(|g| g.clone()).unwrap_or_default()
+}
+
+/// 附加 User-Agent（GitLab、审计等非 GitHub API 请求）
+pub fn with_user_agent(request: RequestBuilder) -> RequestBuilder {
+    request.header("User-Agent", api_headers().user_agent)
+}
+
+/// 附加 GitHub API 请求头
+///
+/// `accept` 为 None 时使用配置的 JSON Accept；需要特定媒体类型（如 `application/vnd.github.raw`）时传入
+pub fn with_github_headers(request: RequestBuilder, accept: Option<&str>) -> RequestBuilder {
+    apply_github_headers(request, &api_headers(), accept)
+}
+
+fn apply_github_headers(request: RequestBuilder, headers: &ApiHeaders, accept: Option<&str>) -> RequestBuilder {
+    request
+        .header("User-Agent", &headers.user_agent)
+        .header("Accept", accept.unwrap_or(&headers.github_accept))
+        .header("X-GitHub-Api-Version", GITHUB_API_VERSION)
+}
+
+/// 代理相关环境变量
+#[derive(Debug, Clone, Default)]
+pub struct ProxyEnv {
+    pub https_proxy: Option<String>,
+    pub http_proxy: Option<String>,
+    pub no_proxy: Option<String>,
+}
+
+impl ProxyEnv {
+    /// 从环境变量读取（大写优先，空值视为未设置）
+    pub fn from_env() -> Self {
+        Self {
+            https_proxy: read_env(&["HTTPS_PROXY", "https_proxy"]),
+            http_proxy: read_env(&["HTTP_PROXY", "http_proxy"]),
+            no_proxy: read_env(&["NO_PROXY", "no_proxy"]),
+        }
+    }
+}
+
+fn read_env(keys: &[&str]) -> Option<String> {
+    keys.iter()
+        .filter_map(|k| std::env::var(k).ok())
+        .find(|v| !v.trim().is_empty())
+}
+
+/// 按给定代理配置构造客户端
+///
+/// 关闭 reqwest 的系统代理自动探测，只使用 `proxy` 中的配置；
+/// 无法解析的代理地址会被忽略（记录警告），不影响直连。
+pub fn build_api_client(proxy: &ProxyEnv, timeout: Duration) -> Client {
+    let mut builder = Client::builder().timeout(timeout).no_proxy();
+
+    let proxies = [
+        proxy.https_proxy.as_deref().map(|url| (url, Proxy::https(url))),
+        proxy.http_proxy.as_deref().map(|url| (url, Proxy::http(url))),
+    ];
+    for (url, result) in proxies.into_iter().flatten() {
+        match result {
+            Ok(p) => {
+                let no_proxy = proxy.no_proxy.as_deref().and_then(NoProxy::from_string);
+                builder = builder.proxy(p.no_proxy(no_proxy));
+            }
+            Err(e) => log::warn!("Ignoring invalid proxy {}: {}", url, e),
+        }
+    }
+
+    builder.build().unwrap_or_else(|e| {
+        log::warn!("Failed to build HTTP client, falling back to defaults: {}", e);
+        Client::new()
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::mock_http::{self, MockServer};
+
+    /// 启动响应一次的模拟 HTTP 服务（也用作代理）
+    fn spawn_server() -> MockServer {
+        MockServer::start(vec![mock_http::ok("{}")])
+    }
+
+    #[test]
+    fn test_client_routes_through_proxy() {
+        let proxy = spawn_server();
+        let client = build_api_client(
+            &ProxyEnv {
+                http_proxy: Some(proxy.url.clone()),
+                ..Default::default()
+            },
+            Duration::from_secs(5),
+        );
+
+        let response =
+            tauri::async_runtime::block_on(async { client.get("http://api.github.com/rate_limit").send().await })
+                .unwrap();
+
+        assert!(response.status().is_success());
+        let request = proxy.next_request();
+        assert!(request.lines().next().unwrap().contains("http://api.github.com/rate_limit"));
+    }
+
+    #[test]
+    fn test_client_honors_no_proxy() {
+        let proxy = spawn_server();
+        let server = spawn_server();
+        let client = build_api_client(
+            &ProxyEnv {
+                http_proxy: Some(proxy.url.clone()),
+                no_proxy: Some("localhost,127.0.0.1".to_string()),
+                ..Default::default()
+            },
+            Duration::from_secs(5),
+        );
+
+        // NO_PROXY 命中的地址直连本地服务，不经过代理
+        let response =
+            tauri::async_runtime::block_on(async { client.get(format!("{}/rate_limit", server.url)).send().await })
+                .unwrap();
+
+        assert!(response.status().is_success());
+        let request = server.next_request();
+        assert!(request.lines().next().unwrap().starts_with("GET /rate_limit "));
+        assert!(proxy.no_requests());
+    }
+
+    #[test]
+    fn test_github_headers_on_request() {
+        let server = spawn_server();
+        let client = build_api_client(&ProxyEnv::default(), Duration::from_secs(5));
+        let headers = ApiHeaders::new(Some("corp-agent/1.0"), Some(" "));
+
+        let request = apply_github_headers(client.get(format!("{}/rate_limit", server.url)), &headers, None);
+        tauri::async_runtime::block_on(async { request.send().await }).unwrap();
+
+        let request = server.next_request().to_lowercase();
+        assert!(request.contains("user-agent: corp-agent/1.0\r\n"));
+        // 空值回退到默认 Accept
+        assert!(request.contains(&format!("accept: {}\r\n", DEFAULT_GITHUB_ACCEPT)));
+        assert!(request.contains(&format!("x-github-api-version: {}\r\n", GITHUB_API_VERSION)));
+    }
+
+    #[test]
+    fn test_validate_header_value() {
+        assert!(validate_header_value("User-Agent", "corp-agent/1.0").is_ok());
+        assert!(validate_header_value("User-Agent", "bad\nvalue").is_err());
+    }
+}
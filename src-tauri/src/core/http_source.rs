@@ -0,0 +1,660 @@
+//! HTTP 来源模块
+//!
+//! 功能：
+//! - Well-known 来源：读取 `<url>/.well-known/skills.json` 索引，下载其中列出的 SKILL.md
+//! - Direct URL 来源：下载单个 SKILL.md（可选 SHA-256 校验）
+//! - 可续传的文件下载：先写入 `<dest>.partial`，连接中断时用 `Range` 请求从已下载的位置继续，
+//!   续传带 `If-Range` 并校验 `Content-Range` 起始位置，重试次数和文件大小有上限，
+//!   完成后按服务端声明的大小校验再改名为目标文件
+//!
+//! 下载的 SKILL.md 写入临时目录（`<sanitized-name>/SKILL.md`），
+//! 之后与 local/git 来源一样交给 discover_skills 处理
+
+use crate::core::http_client::api_headers;
+use crate::core::offline::ensure_online;
+use crate::core::skill::{parse_skill_md_content, sanitize_name};
+use crate::error::AppError;
+use reqwest::header::{HeaderMap, CONTENT_RANGE, ETAG, IF_RANGE, LAST_MODIFIED, RANGE};
+use reqwest::{Client, StatusCode};
+use serde::Deserialize;
+use sha2::{Digest, Sha256};
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+use tempfile::TempDir;
+use url::Url;
+
+/// HTTP 请求超时时间（秒）
+const HTTP_TIMEOUT_SECS: u64 = 10;
+
+/// 下载读取超时（秒）：大文件不设总超时，只限制两次读到数据之间的间隔
+const DOWNLOAD_READ_TIMEOUT_SECS: u64 = 30;
+
+/// 下载中断后的最大重试次数
+const MAX_DOWNLOAD_RETRIES: u32 = 3;
+
+/// 单个文件的最大下载大小（SKILL.md 不应接近这个量级）
+const MAX_DOWNLOAD_BYTES: u64 = 10 * 1024 * 1024;
+
+/// Well-known 索引文件相对路径
+const WELL_KNOWN_INDEX_PATH: &str = ".well-known/skills.json";
+
+/// `.well-known/skills.json` 结构
+/// skills 为 SKILL.md 的 URL 列表（支持相对于索引文件的路径）
+#[derive(Debug, Deserialize)]
+struct WellKnownIndex {
+    skills: Vec<String>,
+}
+
+/// 下载结果，包含临时目录和 skills 根目录
+pub struct DownloadResult {
+    /// 临时目录（drop 时自动清理）
+    pub temp_dir: TempDir,
+    /// skills 根目录（每个 skill 一个子目录）
+    pub skills_dir: PathBuf,
+}
+
+/// 创建带超时的 HTTP 客户端（reqwest 默认跟随重定向）
+fn http_client() -> Result<Client, AppError> {
+    Ok(Client::builder()
+        .timeout(Duration::from_secs(HTTP_TIMEOUT_SECS))
+        .user_agent(api_headers().user_agent)
+        .build()?)
+}
+
+/// 创建下载用的 HTTP 客户端（只限制连接和读取间隔，不限制总时长）
+fn download_client() -> Result<Client, AppError> {
+    Ok(Client::builder()
+        .connect_timeout(Duration::from_secs(HTTP_TIMEOUT_SECS))
+        .read_timeout(Duration::from_secs(DOWNLOAD_READ_TIMEOUT_SECS))
+        .user_agent(api_headers().user_agent)
+        .build()?)
+}
+
+/// 构造 well-known 索引 URL
+///
+/// `https://example.com` → `https://example.com/.well-known/skills.json`
+/// `https://example.com/docs` → `https://example.com/docs/.well-known/skills.json`
+fn well_known_index_url(base: &str) -> Result<Url, AppError> {
+    let mut url = Url::parse(base).map_err(|e| AppError::InvalidSource {
+        value: format!("Invalid URL: {}", e),
+    })?;
+
+    // 确保路径以 / 结尾，使 join 追加而非替换最后一段
+    if !url.path().ends_with('/') {
+        let path = format!("{}/", url.path());
+        url.set_path(&path);
+    }
+
+    url.join(WELL_KNOWN_INDEX_PATH).map_err(|e| AppError::InvalidSource {
+        value: format!("Invalid URL: {}", e),
+    })
+}
+
+/// 解析 well-known 索引，格式错误视为没有 skills
+fn parse_well_known_index(body: &str) -> Result<WellKnownIndex, AppError> {
+    serde_json::from_str(body).map_err(|e| {
+        log::warn!("Malformed well-known index: {}", e);
+        AppError::NoSkillsFound
+    })
+}
+
+/// 下载文本内容（非 2xx 视为失败）
+async fn download_text(client: &Client, url: &Url) -> Result<String, AppError> {
+    let response = client.get(url.clone()).send().await?.error_for_status()?;
+    Ok(response.text().await?)
+}
+
+/// 单次下载尝试的结果
+enum DownloadAttempt {
+    /// `.partial` 已包含完整内容
+    Complete,
+    /// 连接中断或服务端拒绝续传，可以重试
+    Interrupted(String),
+}
+
+/// `<dest>.partial`
+fn partial_path(dest: &Path) -> PathBuf {
+    let mut name = dest.file_name().unwrap_or_default().to_os_string();
+    name.push(".partial");
+    dest.with_file_name(name)
+}
+
+/// 从 `Content-Range: bytes <start>-<end>/<total>` 中读取总大小
+fn content_range_total(headers: &HeaderMap) -> Option<u64> {
+    headers
+        .get(CONTENT_RANGE)?
+        .to_str()
+        .ok()?
+        .rsplit_once('/')?
+        .1
+        .trim()
+        .parse()
+        .ok()
+}
+
+/// 从 `Content-Range: bytes <start>-<end>/<total>` 中读取起始位置
+fn content_range_start(headers: &HeaderMap) -> Option<u64> {
+    headers
+        .get(CONTENT_RANGE)?
+        .to_str()
+        .ok()?
+        .trim()
+        .strip_prefix("bytes ")?
+        .split_once('-')?
+        .0
+        .trim()
+        .parse()
+        .ok()
+}
+
+/// 用于 `If-Range` 的校验值：优先强 ETag，其次 Last-Modified
+///
+/// 弱 ETag 不能用于 `If-Range`，两者都没有时无法安全续传
+fn resume_validator(headers: &HeaderMap) -> Option<String> {
+    let header = |name| headers.get(name).and_then(|v| v.to_str().ok()).map(str::to_string);
+    header(ETAG)
+        .filter(|etag| !etag.starts_with("W/"))
+        .or_else(|| header(LAST_MODIFIED))
+}
+
+/// 超过 `MAX_DOWNLOAD_BYTES` 时的错误
+fn too_large(url: &Url, size: u64) -> AppError {
+    AppError::Custom {
+        message: format!(
+            "Download of {} exceeds the {} byte limit ({} bytes)",
+            url, MAX_DOWNLOAD_BYTES, size
+        ),
+    }
+}
+
+/// 可续传地下载文件到 dest
+///
+/// # 行为
+/// - 内容先写入 `<dest>.partial`，中断后用 `Range: bytes=<len>-` 加 `If-Range` 继续下载；
+///   首次响应没有强 ETag / Last-Modified 时无法确认远端未变化，改为从头下载
+/// - 服务端忽略 Range 或文件已变化（返回 200）时从头写入
+/// - 206 响应的 `Content-Range` 起始位置与请求不一致时丢弃 `.partial` 从头下载
+/// - 连接失败/中断时重试，最多 `MAX_DOWNLOAD_RETRIES` 次；非 2xx 状态直接返回错误
+/// - 服务端声明了大小（Content-Length / Content-Range）时，下载完成后校验文件大小
+/// - 声明或实际大小超过 `MAX_DOWNLOAD_BYTES` 时返回错误并删除 `.partial`
+pub async fn download_resumable(client: &Client, url: &Url, dest: &Path) -> Result<(), AppError> {
+    let partial = partial_path(dest);
+    // 上次残留的 .partial 没有校验值，无法确认与远端一致
+    if partial.exists() {
+        fs::remove_file(&partial)?;
+    }
+
+    let mut validator = None;
+    let mut attempt = 0;
+    loop {
+        match download_attempt(client, url, &partial, &mut validator).await? {
+            DownloadAttempt::Complete => break,
+            DownloadAttempt::Interrupted(reason) => {
+                if attempt >= MAX_DOWNLOAD_RETRIES {
+                    return Err(AppError::Custom {
+                        message: format!(
+                            "Download of {} failed after {} attempts: {}",
+                            url,
+                            attempt + 1,
+                            reason
+                        ),
+                    });
+                }
+                attempt += 1;
+                log::warn!(
+                    "Download of {} interrupted ({}), resuming (retry {}/{})",
+                    url,
+                    reason,
+                    attempt,
+                    MAX_DOWNLOAD_RETRIES
+                );
+            }
+        }
+    }
+
+    fs::rename(&partial, dest)?;
+    Ok(())
+}
+
+/// 发起一次（续传）请求并把响应追加到 `.partial`
+///
+/// `validator` 为首次完整响应的 ETag / Last-Modified，续传时作为 `If-Range` 发送
+async fn download_attempt(
+    client: &Client,
+    url: &Url,
+    partial: &Path,
+    validator: &mut Option<String>,
+) -> Result<DownloadAttempt, AppError> {
+    let mut offset = fs::metadata(partial).map(|m| m.len()).unwrap_or(0);
+    if offset > 0 && validator.is_none() {
+        fs::remove_file(partial)?;
+        offset = 0;
+    }
+    let mut request = client.get(url.clone());
+    if let (true, Some(value)) = (offset > 0, validator.as_deref()) {
+        request = request
+            .header(RANGE, format!("bytes={}-", offset))
+            .header(IF_RANGE, value);
+    }
+
+    let response = match request.send().await {
+        Ok(r) => r,
+        Err(e) => return Ok(DownloadAttempt::Interrupted(e.to_string())),
+    };
+
+    if response.status() == StatusCode::RANGE_NOT_SATISFIABLE && offset > 0 {
+        // 已下载到末尾，或远端文件已变化：后者丢弃 .partial 从头下载
+        if content_range_total(response.headers()) == Some(offset) {
+            return Ok(DownloadAttempt::Complete);
+        }
+        fs::remove_file(partial)?;
+        return Ok(DownloadAttempt::Interrupted(
+            "server rejected resume range".to_string(),
+        ));
+    }
+
+    let mut response = response.error_for_status()?;
+    let resumed = response.status() == StatusCode::PARTIAL_CONTENT;
+    if resumed {
+        let start = content_range_start(response.headers());
+        if start != Some(offset) {
+            fs::remove_file(partial)?;
+            return Ok(DownloadAttempt::Interrupted(format!(
+                "server resumed at {:?} instead of byte {}",
+                start, offset
+            )));
+        }
+    } else {
+        *validator = resume_validator(response.headers());
+    }
+
+    let (mut file, mut written) = if resumed {
+        (OpenOptions::new().append(true).open(partial)?, offset)
+    } else {
+        (fs::File::create(partial)?, 0)
+    };
+    let expected = if resumed {
+        content_range_total(response.headers())
+            .or_else(|| response.content_length().map(|len| offset + len))
+    } else {
+        response.content_length()
+    };
+    if let Some(total) = expected.filter(|&total| total > MAX_DOWNLOAD_BYTES) {
+        drop(file);
+        fs::remove_file(partial)?;
+        return Err(too_large(url, total));
+    }
+
+    loop {
+        match response.chunk().await {
+            Ok(Some(chunk)) => {
+                written += chunk.len() as u64;
+                if written > MAX_DOWNLOAD_BYTES {
+                    drop(file);
+                    fs::remove_file(partial)?;
+                    return Err(too_large(url, written));
+                }
+                file.write_all(&chunk)?;
+            }
+            Ok(None) => break,
+            Err(e) => {
+                file.flush()?;
+                return Ok(DownloadAttempt::Interrupted(e.to_string()));
+            }
+        }
+    }
+    file.flush()?;
+
+    match expected {
+        Some(total) if written < total => Ok(DownloadAttempt::Interrupted(format!(
+            "connection closed after {} of {} bytes",
+            written, total
+        ))),
+        Some(total) if written > total => {
+            fs::remove_file(partial)?;
+            Err(AppError::Custom {
+                message: format!(
+                    "Downloaded {} bytes from {}, but the server declared {}",
+                    written, url, total
+                ),
+            })
+        }
+        _ => Ok(DownloadAttempt::Complete),
+    }
+}
+
+/// 校验内容的 SHA-256（expected 为小写 hex）
+pub fn verify_sha256(content: &[u8], expected: &str) -> Result<(), AppError> {
+    let actual = format!("{:x}", Sha256::digest(content));
+    if actual != expected.to_ascii_lowercase() {
+        return Err(AppError::ChecksumMismatch {
+            expected: expected.to_string(),
+            actual,
+        });
+    }
+    Ok(())
+}
+
+/// 将 SKILL.md 内容写入 `<dir>/<sanitized-name>/SKILL.md`
+///
+/// 内容不是合法的 SKILL.md frontmatter 时返回错误
+pub fn stage_skill_md(dir: &Path, content: &str) -> Result<PathBuf, AppError> {
+    let frontmatter = parse_skill_md_content(content)?;
+    let skill_dir = dir.join(sanitize_name(&frontmatter.name));
+    fs::create_dir_all(&skill_dir)?;
+    fs::write(skill_dir.join("SKILL.md"), content)?;
+    Ok(skill_dir)
+}
+
+/// 获取 well-known 来源的 skills
+///
+/// # 行为
+/// - 读取 `<url>/.well-known/skills.json`
+/// - 逐个下载索引中列出的 SKILL.md，单个失败或格式不合法时跳过
+/// - 索引缺失/格式错误或没有任何可用 skill 时返回 `NoSkillsFound`
+pub async fn fetch_well_known_skills(url: &str) -> Result<DownloadResult, AppError> {
+    ensure_online(&format!("download {}", url))?;
+    let client = http_client()?;
+    let index_url = well_known_index_url(url)?;
+
+    let response = client.get(index_url).send().await?;
+    if !response.status().is_success() {
+        return Err(AppError::NoSkillsFound);
+    }
+
+    // 相对路径基于重定向后的最终 URL 解析
+    let final_url = response.url().clone();
+    let body = response.text().await?;
+    let index = parse_well_known_index(&body)?;
+
+    let temp_dir = TempDir::new()?;
+    let skills_dir = temp_dir.path().to_path_buf();
+    let mut staged = 0usize;
+
+    for entry in &index.skills {
+        let skill_url = match final_url.join(entry) {
+            Ok(u) => u,
+            Err(e) => {
+                log::warn!("Invalid skill URL in well-known index {}: {}", entry, e);
+                continue;
+            }
+        };
+
+        let content = match download_text(&client, &skill_url).await {
+            Ok(c) => c,
+            Err(e) => {
+                log::warn!("Failed to download {}: {}", skill_url, e);
+                continue;
+            }
+        };
+
+        match stage_skill_md(&skills_dir, &content) {
+            Ok(_) => staged += 1,
+            Err(e) => log::warn!("Skipping invalid SKILL.md at {}: {}", skill_url, e),
+        }
+    }
+
+    if staged == 0 {
+        return Err(AppError::NoSkillsFound);
+    }
+
+    Ok(DownloadResult {
+        temp_dir,
+        skills_dir,
+    })
+}
+
+/// 检查 HTTP 来源能否访问（不下载 skills）
+///
+/// - Well-known：索引文件存在且格式正确，否则返回 `NoSkillsFound`
+/// - Direct URL：URL 返回 2xx
+pub async fn probe_http_source(url: &str, well_known: bool) -> Result<(), AppError> {
+    ensure_online(&format!("request {}", url))?;
+    let client = http_client()?;
+    if !well_known {
+        let target = Url::parse(url).map_err(|e| AppError::InvalidSource {
+            value: format!("Invalid URL: {}", e),
+        })?;
+        client.get(target).send().await?.error_for_status()?;
+        return Ok(());
+    }
+
+    let response = client.get(well_known_index_url(url)?).send().await?;
+    if !response.status().is_success() {
+        return Err(AppError::NoSkillsFound);
+    }
+    parse_well_known_index(&response.text().await?)?;
+    Ok(())
+}
+
+/// 获取 direct URL 来源的 skill
+///
+/// 下载单个 SKILL.md 作为只包含一个 skill 的来源（可续传，见 `download_resumable`），
+/// 响应不是合法的 SKILL.md frontmatter 时返回错误。
+/// 提供 expected_sha256 时先校验下载的原始字节，不一致返回 `ChecksumMismatch`
+pub async fn fetch_direct_url_skill(
+    url: &str,
+    expected_sha256: Option<&str>,
+) -> Result<DownloadResult, AppError> {
+    ensure_online(&format!("download {}", url))?;
+    let client = download_client()?;
+    let skill_url = Url::parse(url).map_err(|e| AppError::InvalidSource {
+        value: format!("Invalid URL: {}", e),
+    })?;
+
+    let temp_dir = TempDir::new()?;
+    let skills_dir = temp_dir.path().to_path_buf();
+    let download = skills_dir.join(".download");
+    download_resumable(&client, &skill_url, &download).await?;
+    let bytes = fs::read(&download)?;
+    fs::remove_file(&download)?;
+
+    if let Some(expected) = expected_sha256 {
+        verify_sha256(&bytes, expected)?;
+    }
+    let content = String::from_utf8(bytes).map_err(|e| AppError::InvalidSkillMd {
+        message: format!("SKILL.md is not valid UTF-8: {}", e),
+    })?;
+
+    stage_skill_md(&skills_dir, &content)?;
+
+    Ok(DownloadResult {
+        temp_dir,
+        skills_dir,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::mock_http::MockServer;
+    use tempfile::tempdir;
+
+    /// 启动本地 HTTP 服务，按顺序为每个连接返回一个原始响应，返回下载地址
+    fn serve_sequence(responses: Vec<&str>) -> (String, MockServer) {
+        let server = MockServer::start(responses.into_iter().map(String::from).collect());
+        (format!("{}/SKILL.md", server.url), server)
+    }
+
+    #[test]
+    fn test_well_known_index_url() {
+        let url = well_known_index_url("https://example.com").unwrap();
+        assert_eq!(url.as_str(), "https://example.com/.well-known/skills.json");
+
+        let url = well_known_index_url("https://example.com/docs").unwrap();
+        assert_eq!(url.as_str(), "https://example.com/docs/.well-known/skills.json");
+
+        let url = well_known_index_url("https://example.com/docs/").unwrap();
+        assert_eq!(url.as_str(), "https://example.com/docs/.well-known/skills.json");
+    }
+
+    #[test]
+    fn test_parse_well_known_index() {
+        let index = parse_well_known_index(r#"{"skills": ["a/SKILL.md", "https://x.dev/b/SKILL.md"]}"#)
+            .unwrap();
+        assert_eq!(index.skills.len(), 2);
+
+        let err = parse_well_known_index("not json").unwrap_err();
+        assert!(matches!(err, AppError::NoSkillsFound));
+
+        let err = parse_well_known_index(r#"{"other": []}"#).unwrap_err();
+        assert!(matches!(err, AppError::NoSkillsFound));
+    }
+
+    #[test]
+    fn test_verify_sha256() {
+        // echo -n "hello" | sha256sum
+        let expected = "2cf24dba5fb0a30e26e83b2ac5b9e29e1b161e5c1fa7425e73043362938b9824";
+        assert!(verify_sha256(b"hello", expected).is_ok());
+        assert!(verify_sha256(b"hello", &expected.to_uppercase()).is_ok());
+
+        match verify_sha256(b"hello!", expected) {
+            Err(AppError::ChecksumMismatch { expected: e, actual }) => {
+                assert_eq!(e, expected);
+                assert_ne!(actual, expected);
+            }
+            other => panic!("unexpected result: {:?}", other.err()),
+        }
+    }
+
+    #[test]
+    fn test_stage_skill_md() {
+        let temp = tempdir().unwrap();
+        let content = "---\nname: My Skill\ndescription: Remote skill\n---\nBody";
+
+        let skill_dir = stage_skill_md(temp.path(), content).unwrap();
+        assert_eq!(skill_dir, temp.path().join("my-skill"));
+        assert_eq!(fs::read_to_string(skill_dir.join("SKILL.md")).unwrap(), content);
+    }
+
+    #[test]
+    fn test_content_range_total() {
+        let mut headers = HeaderMap::new();
+        assert_eq!(content_range_total(&headers), None);
+        headers.insert(CONTENT_RANGE, "bytes 4-9/10".parse().unwrap());
+        assert_eq!(content_range_total(&headers), Some(10));
+        headers.insert(CONTENT_RANGE, "bytes */*".parse().unwrap());
+        assert_eq!(content_range_total(&headers), None);
+    }
+
+    #[test]
+    fn test_content_range_start_and_validator() {
+        let mut headers = HeaderMap::new();
+        assert_eq!(content_range_start(&headers), None);
+        assert_eq!(resume_validator(&headers), None);
+        headers.insert(CONTENT_RANGE, "bytes 4-9/10".parse().unwrap());
+        assert_eq!(content_range_start(&headers), Some(4));
+
+        headers.insert(LAST_MODIFIED, "Wed, 21 Oct 2015 07:28:00 GMT".parse().unwrap());
+        headers.insert(ETAG, "W/\"weak\"".parse().unwrap());
+        assert_eq!(resume_validator(&headers).as_deref(), Some("Wed, 21 Oct 2015 07:28:00 GMT"));
+        headers.insert(ETAG, "\"v1\"".parse().unwrap());
+        assert_eq!(resume_validator(&headers).as_deref(), Some("\"v1\""));
+    }
+
+    #[test]
+    fn test_download_resumes_after_connection_drop() {
+        // 第一次只发送 10 字节中的前 4 字节就断开，第二次应带 Range 续传
+        let (url, server) = serve_sequence(vec![
+            "HTTP/1.1 200 OK\r\nETag: \"v1\"\r\nContent-Length: 10\r\nConnection: close\r\n\r\nhell",
+            "HTTP/1.1 206 Partial Content\r\n\
+             Content-Range: bytes 4-9/10\r\n\
+             Content-Length: 6\r\n\
+             Connection: close\r\n\r\no worl",
+        ]);
+        let temp = tempdir().unwrap();
+        let dest = temp.path().join("file.txt");
+
+        let client = download_client().unwrap();
+        tauri::async_runtime::block_on(download_resumable(
+            &client,
+            &Url::parse(&url).unwrap(),
+            &dest,
+        ))
+        .unwrap();
+
+        assert_eq!(fs::read_to_string(&dest).unwrap(), "hello worl");
+        assert!(!partial_path(&dest).exists());
+        assert!(!server.next_request().to_lowercase().contains("range:"));
+        let resumed = server.next_request().to_lowercase();
+        assert!(resumed.contains("range: bytes=4-"));
+        assert!(resumed.contains("if-range: \"v1\""));
+    }
+
+    #[test]
+    fn test_download_restarts_when_resume_offset_mismatches() {
+        // 服务端返回的 Content-Range 不是从请求的位置开始，应丢弃 .partial 从头下载
+        let (url, server) = serve_sequence(vec![
+            "HTTP/1.1 200 OK\r\nETag: \"v1\"\r\nContent-Length: 10\r\nConnection: close\r\n\r\nhell",
+            "HTTP/1.1 206 Partial Content\r\n\
+             Content-Range: bytes 0-9/10\r\n\
+             Content-Length: 10\r\n\
+             Connection: close\r\n\r\nhello worl",
+            "HTTP/1.1 200 OK\r\nContent-Length: 10\r\nConnection: close\r\n\r\nhello worl",
+        ]);
+        let temp = tempdir().unwrap();
+        let dest = temp.path().join("file.txt");
+
+        let client = download_client().unwrap();
+        tauri::async_runtime::block_on(download_resumable(
+            &client,
+            &Url::parse(&url).unwrap(),
+            &dest,
+        ))
+        .unwrap();
+
+        assert_eq!(fs::read_to_string(&dest).unwrap(), "hello worl");
+        server.next_request();
+        assert!(server.next_request().to_lowercase().contains("range: bytes=4-"));
+        assert!(!server.next_request().to_lowercase().contains("range:"));
+    }
+
+    #[test]
+    fn test_download_rejects_oversized_file() {
+        let (url, _server) = serve_sequence(vec![
+            "HTTP/1.1 200 OK\r\nContent-Length: 999999999\r\nConnection: close\r\n\r\nhell",
+        ]);
+        let temp = tempdir().unwrap();
+        let dest = temp.path().join("file.txt");
+
+        let client = download_client().unwrap();
+        let err = tauri::async_runtime::block_on(download_resumable(
+            &client,
+            &Url::parse(&url).unwrap(),
+            &dest,
+        ))
+        .unwrap_err();
+
+        assert!(err.to_string().contains("byte limit"));
+        assert!(!partial_path(&dest).exists());
+    }
+
+    #[test]
+    fn test_download_gives_up_after_retries() {
+        let truncated = "HTTP/1.1 200 OK\r\nContent-Length: 10\r\nConnection: close\r\n\r\nhell";
+        // 服务端忽略 Range，每次都从头返回同样的截断响应
+        let (url, _server) = serve_sequence(vec![truncated; MAX_DOWNLOAD_RETRIES as usize + 1]);
+        let temp = tempdir().unwrap();
+        let dest = temp.path().join("file.txt");
+
+        let client = download_client().unwrap();
+        let err = tauri::async_runtime::block_on(download_resumable(
+            &client,
+            &Url::parse(&url).unwrap(),
+            &dest,
+        ))
+        .unwrap_err();
+
+        assert!(matches!(err, AppError::Custom { .. }));
+        assert!(!dest.exists());
+    }
+
+    #[test]
+    fn test_stage_skill_md_rejects_invalid() {
+        let temp = tempdir().unwrap();
+        assert!(stage_skill_md(temp.path(), "<html>Not a skill</html>").is_err());
+        assert!(fs::read_dir(temp.path()).unwrap().next().is_none());
+    }
+}
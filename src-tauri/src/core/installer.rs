@@ -1,26 +1,142 @@
 //! 安装核心模块
 //!
 //! 功能：
-//! - 复制文件到 canonical 目录
+//! - 复制文件到 canonical 目录（先写入暂存目录，成功后再替换，失败时保留原安装）
+//! - 更新时增量同步：只写入变化的文件、删除已移除的文件，已有 symlink 保持不动
+//! - 复制时应用排除列表（默认 + config `copyExcludeFiles` / `copyExcludeDirs`）和 skill 根目录的 `.skillignore`
+//! - 复制时保留文件权限（Unix 可执行位）
+//! - Windows 上路径接近 MAX_PATH 时使用 `\\?\` 扩展长度路径（深层嵌套的 skill）
 //! - 创建 symlink/junction 到各 agent 目录
+//! - Hardlink 模式：agent 目录中的文件硬链接到 canonical 目录（跨文件系统等失败时逐个回退为复制）
+//! - 预先探测 canonical 目录所在位置能否创建 symlink（按目录缓存，进程内只探测一次）
 //! - 处理 fallback 到 copy 模式
+//! - 启动时清理中断安装残留的暂存目录
 //!
 //! 与 CLI installer.ts 行为一致
 
 use crate::core::agents::AgentType;
-use crate::core::paths::canonical_skills_dir;
-use crate::core::skill::sanitize_name;
+use crate::core::local_lock::{hash_skill_files, HASH_SKIP_DIRS};
+use crate::core::paths::{
+    agent_skills_dir, canonical_skills_dir, is_symlink_or_junction, long_path, read_skill_deck_config,
+    resolve_path, same_file, same_path,
+};
+use crate::core::skill::{
+    localized_skill_md, parse_skill_md, sanitize_name, skill_dir_name, skill_md_locales,
+};
 use crate::error::AppError;
-use crate::models::{InstallMode, InstallResult, Scope};
+use crate::models::{InstallMode, InstallResult, NameCollisionPolicy, Scope};
+use ignore::gitignore::{Gitignore, GitignoreBuilder};
+use once_cell::sync::Lazy;
+use sha2::{Digest, Sha256};
+use std::collections::{HashMap, HashSet};
 use std::fs;
 use std::path::{Path, PathBuf};
+use std::sync::{Mutex, RwLock};
+use std::time::Duration;
 
-/// 复制时排除的文件（与 CLI 一致）
+/// 复制时默认排除的文件（与 CLI 一致）
 const EXCLUDE_FILES: &[&str] = &["metadata.json"];
 
-/// 复制时排除的目录（与 CLI 一致）
+/// 复制时默认排除的目录（与 CLI 一致）
 const EXCLUDE_DIRS: &[&str] = &[".git"];
 
+/// 生效的复制排除列表
+/// 首次访问时从 config.json 加载，save_config 时通过 set_copy_excludes 同步
+static COPY_EXCLUDES: Lazy<RwLock<CopyExcludes>> = Lazy::new(|| {
+    let config = read_skill_deck_config().unwrap_or_default();
+    RwLock::new(CopyExcludes::new(
+        &config.copy_exclude_files,
+        &config.copy_exclude_dirs,
+        config.replace_copy_excludes,
+    ))
+});
+
+/// skill 根目录下的忽略规则文件（gitignore 语法），自身不会被复制
+const SKILLIGNORE_FILE: &str = ".skillignore";
+
+/// 暂存目录前缀（与目标目录同级，保证 rename 在同一文件系统内）
+const STAGING_PREFIX: &str = ".skill-deck-staging-";
+
+/// 超过这个时间未修改的暂存目录视为中断安装的残留
+const STALE_STAGING_AGE: Duration = Duration::from_secs(60 * 60);
+
+/// symlink 探测目录前缀
+const SYMLINK_PROBE_PREFIX: &str = ".skill-deck-symlink-probe-";
+
+/// 各目录的 symlink 探测结果（key 为规范化后的目录，Err 为失败原因）
+static SYMLINK_SUPPORT: Lazy<Mutex<HashMap<PathBuf, Result<(), String>>>> =
+    Lazy::new(|| Mutex::new(HashMap::new()));
+
+/// Windows ERROR_PRIVILEGE_NOT_HELD：未开启开发者模式且非管理员时创建 symlink 的错误
+const WINDOWS_PRIVILEGE_NOT_HELD: &str = "os error 1314";
+
+/// 复制 skill 时按名称排除的文件和目录
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CopyExcludes {
+    /// 排除的文件名（同名目录也会被排除，与 CLI 一致）
+    pub files: Vec<String>,
+    /// 排除的目录名
+    pub dirs: Vec<String>,
+}
+
+impl Default for CopyExcludes {
+    fn default() -> Self {
+        Self::new(&[], &[], false)
+    }
+}
+
+impl CopyExcludes {
+    /// 合并配置的排除项与默认值；`replace` 为 true 时只使用配置的排除项
+    pub fn new(extra_files: &[String], extra_dirs: &[String], replace: bool) -> Self {
+        let merge = |defaults: &[&str], extra: &[String]| {
+            let mut names: Vec<String> = if replace {
+                Vec::new()
+            } else {
+                defaults.iter().map(|s| s.to_string()).collect()
+            };
+            for name in extra.iter().map(|s| s.trim()).filter(|s| !s.is_empty()) {
+                if !names.iter().any(|n| n == name) {
+                    names.push(name.to_string());
+                }
+            }
+            names
+        };
+        Self {
+            files: merge(EXCLUDE_FILES, extra_files),
+            dirs: merge(EXCLUDE_DIRS, extra_dirs),
+        }
+    }
+
+    fn excludes(&self, file_name: &str, is_dir: bool) -> bool {
+        self.files.iter().any(|f| f == file_name) || (is_dir && self.dirs.iter().any(|d| d == file_name))
+    }
+}
+
+/// 校验配置的排除项：只能是文件名或目录名，不能包含路径分隔符
+pub fn validate_copy_excludes(names: &[String]) -> Result<(), AppError> {
+    for name in names {
+        let name = name.trim();
+        if name.contains(['/', '\\']) || name == "." || name == ".." {
+            return Err(AppError::Path {
+                message: format!("Copy exclude must be a file or directory name, not a path: {}", name),
+            });
+        }
+    }
+    Ok(())
+}
+
+/// 更新内存中的复制排除列表（save_config 后调用）
+pub fn set_copy_excludes(files: &[String], dirs: &[String], replace: bool) {
+    if let Ok(mut guard) = COPY_EXCLUDES.write() {
+        *guard = CopyExcludes::new(files, dirs, replace);
+    }
+}
+
+/// 当前生效的复制排除列表
+pub fn copy_excludes() -> CopyExcludes {
+    COPY_EXCLUDES.read().map(|g| g.clone()).unwrap_or_default()
+}
+
 /// 安装 skill 到指定 agent
 ///
 /// # Arguments
@@ -29,7 +145,7 @@ const EXCLUDE_DIRS: &[&str] = &[".git"];
 /// * `agent` - 目标 agent 类型
 /// * `scope` - 安装范围（Global/Project）
 /// * `project_path` - Project scope 时的项目路径
-/// * `mode` - 安装模式（Symlink/Copy）
+/// * `mode` - 安装模式（Symlink/Copy/Hardlink）
 ///
 /// # Returns
 /// * `InstallResult` - 安装结果（成功或失败信息）
@@ -40,14 +156,72 @@ pub fn install_skill_for_agent(
     scope: &Scope,
     project_path: Option<&str>,
     mode: &InstallMode,
+) -> InstallResult {
+    let dir_name = installed_dir_name(skill_name, scope, project_path);
+    install_skill_for_agent_with(skill_path, skill_name, &dir_name, agent, scope, project_path, mode, false)
+}
+
+/// 安装到指定目录名（参数同 `install_skill_for_agent`）
+///
+/// `dir_name` 通常来自 `resolve_install_dir_name`，调用方负责将其记录到 lock
+pub fn install_skill_for_agent_in_dir(
+    skill_path: &Path,
+    skill_name: &str,
+    dir_name: &str,
+    agent: &AgentType,
+    scope: &Scope,
+    project_path: Option<&str>,
+    mode: &InstallMode,
+) -> InstallResult {
+    install_skill_for_agent_with(skill_path, skill_name, dir_name, agent, scope, project_path, mode, false)
+}
+
+/// 更新已安装的 skill（参数同 `install_skill_for_agent`）
+///
+/// canonical 目录和 copy 安装的 agent 目录增量同步（见 `sync_skill_files`），
+/// 已指向 canonical 目录的 symlink 保持不动，避免整目录替换导致编辑器缓存失效。
+/// 尚未安装的位置按正常安装处理。
+pub fn update_skill_for_agent(
+    skill_path: &Path,
+    skill_name: &str,
+    agent: &AgentType,
+    scope: &Scope,
+    project_path: Option<&str>,
+    mode: &InstallMode,
+) -> InstallResult {
+    let dir_name = installed_dir_name(skill_name, scope, project_path);
+    install_skill_for_agent_with(skill_path, skill_name, &dir_name, agent, scope, project_path, mode, true)
+}
+
+/// lock 中记录的安装目录名（未记录时为 sanitize 后的名称）
+fn installed_dir_name(skill_name: &str, scope: &Scope, project_path: Option<&str>) -> String {
+    skill_dir_name(skill_name, matches!(scope, Scope::Global), project_path.unwrap_or("."))
+}
+
+#[allow(clippy::too_many_arguments)]
+fn install_skill_for_agent_with(
+    skill_path: &Path,
+    skill_name: &str,
+    dir_name: &str,
+    agent: &AgentType,
+    scope: &Scope,
+    project_path: Option<&str>,
+    mode: &InstallMode,
+    incremental: bool,
 ) -> InstallResult {
     let is_global = matches!(scope, Scope::Global);
     let cwd = project_path.unwrap_or(".");
-    let sanitized_name = sanitize_name(skill_name);
 
-    // 检查 agent 是否支持 global 安装
+    // 检查 agent 是否支持当前 scope 的安装
     let config = agent.config();
-    if is_global && config.global_skills_dir.is_none() {
+    let unsupported_scope = if is_global && config.global_skills_dir.is_none() {
+        Some("global")
+    } else if !is_global && !config.supports_project {
+        Some("project")
+    } else {
+        None
+    };
+    if let Some(scope_name) = unsupported_scope {
         return InstallResult {
             skill_name: skill_name.to_string(),
             agent: agent.to_string(),
@@ -56,33 +230,40 @@ pub fn install_skill_for_agent(
             canonical_path: None,
             mode: mode.clone(),
             symlink_failed: false,
+            symlink_error: None,
             error: Some(format!(
-                "{} does not support global skill installation",
-                config.display_name
+                "{} does not support {} skill installation",
+                config.display_name, scope_name
             )),
         };
     }
 
     let result = match mode {
         InstallMode::Symlink => {
-            install_with_symlink(skill_path, &sanitized_name, agent, is_global, cwd)
+            install_with_symlink(skill_path, dir_name, agent, is_global, cwd, incremental)
+        }
+        InstallMode::Copy => {
+            install_with_copy(skill_path, dir_name, agent, is_global, cwd, false, incremental)
+        }
+        InstallMode::Hardlink => {
+            install_with_copy(skill_path, dir_name, agent, is_global, cwd, true, incremental)
         }
-        InstallMode::Copy => install_with_copy(skill_path, &sanitized_name, agent, is_global, cwd),
     };
 
     match result {
-        Ok((path, canonical_path, symlink_failed)) => InstallResult {
+        Ok((path, canonical_path, symlink_error)) => InstallResult {
             skill_name: skill_name.to_string(),
             agent: agent.to_string(),
             success: true,
             path,
             canonical_path,
-            mode: if symlink_failed {
+            mode: if symlink_error.is_some() {
                 InstallMode::Copy
             } else {
                 mode.clone()
             },
-            symlink_failed,
+            symlink_failed: symlink_error.is_some(),
+            symlink_error,
             error: None,
         },
         Err(e) => InstallResult {
@@ -93,221 +274,993 @@ pub fn install_skill_for_agent(
             canonical_path: None,
             mode: mode.clone(),
             symlink_failed: false,
+            symlink_error: None,
             error: Some(e.to_string()),
         },
     }
 }
 
 /// Symlink 模式安装
+///
+/// 返回 (安装路径, canonical 路径, symlink 失败原因)，失败原因为 Some 时已降级为 copy
 fn install_with_symlink(
     skill_path: &Path,
     skill_name: &str,
     agent: &AgentType,
     is_global: bool,
     cwd: &str,
-) -> Result<(PathBuf, Option<PathBuf>, bool), AppError> {
+    incremental: bool,
+) -> Result<(PathBuf, Option<PathBuf>, Option<String>), AppError> {
     // 1. 确定 canonical 目录
     let canonical_base = canonical_skills_dir(is_global, cwd);
     let canonical_dir = canonical_base.join(skill_name);
 
     // 2. 复制到 canonical 目录
-    clean_and_create_directory(&canonical_dir)?;
-    copy_skill_files(skill_path, &canonical_dir)?;
+    write_skill_dir(skill_path, &canonical_dir, incremental)?;
 
-    // 3. 对于 Universal Agent 的 global 安装，跳过 symlink（已在 canonical 目录）
-    if is_global && agent.is_universal() {
-        return Ok((canonical_dir.clone(), Some(canonical_dir), false));
+    // 3. 对于 Universal Agent 的 global 安装（未覆盖 global 目录），跳过 symlink（已在 canonical 目录）
+    if is_global && agent.shares_canonical_dir(true) {
+        return Ok((canonical_dir.clone(), Some(canonical_dir), None));
     }
 
     // 4. 获取 agent 目录
-    let config = agent.config();
-    let agent_base = if is_global {
-        config.global_skills_dir.clone().unwrap()
-    } else {
-        PathBuf::from(cwd).join(&config.skills_dir)
-    };
-    let agent_dir = agent_base.join(skill_name);
+    let agent_dir = agent_install_dir(agent, is_global, cwd, skill_name)?;
+
+    // 5. 已知无法创建 symlink 时直接 copy，避免每个 agent 都先失败一次
+    if let Some(reason) = symlink_unavailable_reason(&canonical_base) {
+        write_skill_dir(skill_path, &agent_dir, incremental)?;
+        return Ok((agent_dir, Some(canonical_dir), Some(reason)));
+    }
 
-    // 5. 创建 symlink
-    let symlink_failed = match create_symlink(&canonical_dir, &agent_dir) {
-        Ok(_) => false,
-        Err(_) => {
+    // 6. 创建 symlink（更新时已有的链接保持不动）
+    if incremental && is_symlink_or_junction(&agent_dir) && same_path(&agent_dir, &canonical_dir) {
+        return Ok((agent_dir, Some(canonical_dir), None));
+    }
+    let symlink_error = match create_symlink(&canonical_dir, &agent_dir) {
+        Ok(_) => None,
+        Err(e) => {
             // Symlink 失败，fallback 到 copy
-            clean_and_create_directory(&agent_dir)?;
-            copy_skill_files(skill_path, &agent_dir)?;
-            true
+            stage_and_replace(skill_path, &agent_dir)?;
+            Some(describe_symlink_error(&e.to_string()))
         }
     };
 
-    Ok((agent_dir, Some(canonical_dir), symlink_failed))
+    Ok((agent_dir, Some(canonical_dir), symlink_error))
 }
 
-/// Copy 模式安装
+/// Copy / Hardlink 模式安装
+///
+/// `hardlink` 为 true 时 agent 目录中的文件逐个硬链接到 canonical 目录中的对应文件，
+/// 多个 agent 共享同一份数据。canonical 目录由调用方事先用 `write_canonical_dir` 写入一次，
+/// 这里不再写入，否则后安装的 agent 会替换掉先安装的 agent 所链接的 inode。
+/// 硬链接共享内容：修改任一位置的文件会影响所有链接（重新安装会替换为新文件，不受影响）。
 fn install_with_copy(
     skill_path: &Path,
     skill_name: &str,
     agent: &AgentType,
     is_global: bool,
     cwd: &str,
-) -> Result<(PathBuf, Option<PathBuf>, bool), AppError> {
-    let config = agent.config();
-    let agent_base = if is_global {
-        config.global_skills_dir.clone().unwrap()
-    } else {
-        PathBuf::from(cwd).join(&config.skills_dir)
-    };
-    let agent_dir = agent_base.join(skill_name);
+    hardlink: bool,
+    incremental: bool,
+) -> Result<(PathBuf, Option<PathBuf>, Option<String>), AppError> {
+    if !hardlink {
+        let agent_dir = agent_install_dir(agent, is_global, cwd, skill_name)?;
+        write_skill_dir(skill_path, &agent_dir, incremental)?;
+        return Ok((agent_dir, None, None));
+    }
+
+    // agent 目录始终整体替换为新的硬链接，canonical 目录增量同步后变化的文件已是新 inode
+    let canonical_dir = canonical_skills_dir(is_global, cwd).join(skill_name);
+    if !canonical_dir.is_dir() {
+        return Err(AppError::InstallFailed {
+            message: format!("Canonical directory not found: {}", canonical_dir.display()),
+        });
+    }
 
-    clean_and_create_directory(&agent_dir)?;
-    copy_skill_files(skill_path, &agent_dir)?;
+    // Universal Agent 的 global 安装（未覆盖 global 目录）直接使用 canonical 目录
+    if is_global && agent.shares_canonical_dir(true) {
+        return Ok((canonical_dir.clone(), Some(canonical_dir), None));
+    }
+
+    let agent_dir = agent_install_dir(agent, is_global, cwd, skill_name)?;
+    if same_path(&agent_dir, &canonical_dir) {
+        return Ok((agent_dir, Some(canonical_dir), None));
+    }
+    stage_and_replace_with(&canonical_dir, &agent_dir, FileTransfer::Hardlink)?;
 
-    Ok((agent_dir, None, false))
+    Ok((agent_dir, Some(canonical_dir), None))
 }
 
-/// 清理并创建目录（与 CLI cleanAndCreateDirectory 一致）
-fn clean_and_create_directory(path: &Path) -> Result<(), AppError> {
-    // 尝试删除现有目录/文件
-    if path.exists() || path.symlink_metadata().is_ok() {
-        let _ = fs::remove_dir_all(path);
-        let _ = fs::remove_file(path);
+/// 写入 skill 目录：更新时增量同步，否则暂存后整体替换
+fn write_skill_dir(src: &Path, target: &Path, incremental: bool) -> Result<(), AppError> {
+    if incremental {
+        sync_skill_files(src, target)
+    } else {
+        stage_and_replace(src, target)
     }
+}
+
+/// 写入文件的方式
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum FileTransfer {
+    Copy,
+    /// 优先硬链接，失败时（跨文件系统、不支持等）回退为复制
+    Hardlink,
+}
+
+/// agent 目录下的安装路径（调用方已检查 scope 支持，这里仅兜底）
+fn agent_install_dir(
+    agent: &AgentType,
+    is_global: bool,
+    cwd: &str,
+    skill_name: &str,
+) -> Result<PathBuf, AppError> {
+    agent_skills_dir(agent, is_global, cwd)
+        .map(|base| base.join(skill_name))
+        .ok_or_else(|| AppError::InstallFailed {
+            message: format!(
+                "{} does not support {} skill installation",
+                agent.config().display_name,
+                if is_global { "global" } else { "project" }
+            ),
+        })
+}
+
+/// 暂存后替换目标目录
+///
+/// 对应 CLI cleanAndCreateDirectory + copyDirectory，但不会先删除原安装：
+/// 1. 复制到同级暂存目录（失败时暂存目录自动清理，原安装不受影响）
+/// 2. 原安装存在时先 rename 到备份路径
+/// 3. 暂存目录 rename 到目标路径，失败时将备份 rename 回原位
+/// 4. 删除备份
+///
+/// rename 不能覆盖非空目录（Windows 上也不能覆盖已存在的目录），
+/// 因此先移走原安装，且只在暂存成功后才移动
+pub(crate) fn stage_and_replace(src: &Path, target: &Path) -> Result<(), AppError> {
+    stage_and_replace_with(src, target, FileTransfer::Copy)
+}
+
+/// 同 `stage_and_replace`，但原样复制全部内容
+///
+/// 不应用排除列表和 `.skillignore`，symlink 复制为 symlink。用于迁移 / 备份用户已有的目录，
+/// 其中可能包含安装时会被排除的文件（如用户添加的 `_notes.md`）
+pub(crate) fn stage_and_replace_all(src: &Path, target: &Path) -> Result<(), AppError> {
+    stage_and_replace_using(target, |staging| copy_dir_all(src, staging))
+}
 
-    // 创建目录
-    fs::create_dir_all(path)
+/// 同 `stage_and_replace`，可指定写入文件的方式
+fn stage_and_replace_with(src: &Path, target: &Path, transfer: FileTransfer) -> Result<(), AppError> {
+    stage_and_replace_using(target, |staging| {
+        copy_skill_files_with(src, staging, &copy_excludes(), transfer)
+    })
+}
+
+/// 暂存替换流程：`fill` 负责写入暂存目录
+fn stage_and_replace_using(
+    target: &Path,
+    fill: impl FnOnce(&Path) -> Result<(), AppError>,
+) -> Result<(), AppError> {
+    let parent = target.parent().ok_or_else(|| AppError::InstallFailed {
+        message: format!("Invalid install path: {}", target.display()),
+    })?;
+    fs::create_dir_all(long_path(parent))
         .map_err(|e| AppError::InstallFailed { message: format!("Failed to create dir: {}", e) })?;
 
+    // 1. 复制到暂存目录
+    let staging = tempfile::Builder::new()
+        .prefix(STAGING_PREFIX)
+        .tempdir_in(parent)
+        .map_err(|e| AppError::InstallFailed { message: format!("Failed to create staging dir: {}", e) })?;
+    fill(staging.path())?;
+    apply_install_dir_mode(staging.path(), target)
+        .map_err(|e| AppError::InstallFailed { message: format!("Failed to set directory permissions: {}", e) })?;
+
+    // 备份路径基于暂存目录名，保证唯一
+    let mut backup_name = staging.path().file_name().unwrap_or_default().to_os_string();
+    backup_name.push(".backup");
+    let backup_path = parent.join(backup_name);
+
+    // 2. 移走原安装（目录、symlink 或 junction）
+    let has_previous = target.symlink_metadata().is_ok();
+    if has_previous {
+        fs::rename(long_path(target), long_path(&backup_path)).map_err(|e| AppError::InstallFailed {
+            message: format!("Failed to move previous installation aside: {}", e),
+        })?;
+    }
+
+    // 3. 暂存目录移动到目标路径
+    if let Err(e) = fs::rename(long_path(staging.path()), long_path(target)) {
+        if has_previous {
+            let _ = fs::rename(long_path(&backup_path), long_path(target));
+        }
+        return Err(AppError::InstallFailed {
+            message: format!("Failed to move staged files into place: {}", e),
+        });
+    }
+    // 暂存目录已被 rename，drop 时路径不存在，清理为 no-op
+    drop(staging);
+
+    // 4. 删除备份（remove_dir_all 不会跟随 symlink）
+    if has_previous {
+        let _ = fs::remove_dir_all(long_path(&backup_path));
+        let _ = fs::remove_file(long_path(&backup_path));
+    }
+
+    Ok(())
+}
+
+/// 暂存目录由 tempfile 创建，权限为 0700：改为原安装的权限，没有原安装时为 0755
+#[cfg(unix)]
+fn apply_install_dir_mode(staging: &Path, target: &Path) -> std::io::Result<()> {
+    use std::os::unix::fs::PermissionsExt;
+    let mode = fs::metadata(target)
+        .ok()
+        .filter(|m| m.is_dir())
+        .map(|m| m.permissions().mode() & 0o7777)
+        .unwrap_or(0o755);
+    fs::set_permissions(staging, fs::Permissions::from_mode(mode))
+}
+
+#[cfg(not(unix))]
+fn apply_install_dir_mode(_staging: &Path, _target: &Path) -> std::io::Result<()> {
     Ok(())
 }
 
+/// 清理 global 和已知项目的 canonical / agent 目录中残留的暂存目录（启动时调用）
+///
+/// # Returns
+/// 删除的目录数量
+pub fn cleanup_stale_staging() -> usize {
+    let projects = read_skill_deck_config().map(|c| c.projects).unwrap_or_default();
+    let scopes = std::iter::once((true, ".")).chain(projects.iter().map(|p| (false, p.as_str())));
+
+    let mut bases: Vec<PathBuf> = Vec::new();
+    for (is_global, cwd) in scopes {
+        bases.push(canonical_skills_dir(is_global, cwd));
+        bases.extend(AgentType::all().filter_map(|agent| agent_skills_dir(&agent, is_global, cwd)));
+    }
+    bases.sort();
+    bases.dedup();
+    cleanup_stale_staging_dirs(&bases, STALE_STAGING_AGE)
+}
+
+/// 删除 `bases` 下超过 `max_age` 未修改的暂存目录
+///
+/// 暂存替换中断（崩溃、强制退出）时 tempfile 来不及清理。`.backup` 不删除：
+/// 中断发生在移走原安装之后时，它是原安装的唯一副本
+fn cleanup_stale_staging_dirs(bases: &[PathBuf], max_age: Duration) -> usize {
+    let mut removed = 0;
+    for base in bases {
+        let Ok(entries) = fs::read_dir(base) else {
+            continue;
+        };
+        for entry in entries.filter_map(|e| e.ok()) {
+            let name = entry.file_name().to_string_lossy().to_string();
+            if !name.starts_with(STAGING_PREFIX) || name.ends_with(".backup") {
+                continue;
+            }
+            // 正在进行的安装（包括其他进程）的暂存目录修改时间较新
+            let stale = entry
+                .metadata()
+                .and_then(|m| m.modified())
+                .ok()
+                .and_then(|t| t.elapsed().ok())
+                .is_some_and(|age| age >= max_age);
+            if !stale || !entry.file_type().is_ok_and(|t| t.is_dir()) {
+                continue;
+            }
+            match fs::remove_dir_all(long_path(&entry.path())) {
+                Ok(()) => removed += 1,
+                Err(e) => log::warn!("Failed to remove stale staging dir {:?}: {}", entry.path(), e),
+            }
+        }
+    }
+    removed
+}
+
 /// 复制 skill 文件（排除特定文件，与 CLI copyDirectory 一致）
-fn copy_skill_files(src: &Path, dst: &Path) -> Result<(), AppError> {
+///
+/// 除 `excludes` 中的排除项外，还会应用 skill 根目录下 `.skillignore` 中的规则（相对于根目录）。
+fn copy_skill_files(src: &Path, dst: &Path, excludes: &CopyExcludes) -> Result<(), AppError> {
+    copy_skill_files_with(src, dst, excludes, FileTransfer::Copy)
+}
+
+fn copy_skill_files_with(
+    src: &Path,
+    dst: &Path,
+    excludes: &CopyExcludes,
+    transfer: FileTransfer,
+) -> Result<(), AppError> {
+    let skillignore = load_skillignore(src);
+    let mut chain = DirChain::new(src);
+    copy_skill_files_filtered(src, dst, excludes, skillignore.as_ref(), true, transfer, &mut chain)
+}
+
+/// 正在遍历的目录链（真实路径）
+///
+/// 复制 / 同步会跟随指向目录的 symlink，指回当前路径上某个目录的链接会导致无限递归：
+/// 进入子目录前检查，成环的链接跳过不复制（与 `disk_usage::measure_dir` 的处理一致）
+struct DirChain(Vec<PathBuf>);
+
+impl DirChain {
+    fn new(root: &Path) -> Self {
+        Self(vec![real_dir(root)])
+    }
+
+    /// 进入目录；它已在链上（symlink 成环）时返回 false，返回 true 后需调用 `leave`
+    fn enter(&mut self, dir: &Path) -> bool {
+        let real = real_dir(dir);
+        if self.0.contains(&real) {
+            log::warn!("Skipping {:?}: symlink points back to a parent directory", dir);
+            return false;
+        }
+        self.0.push(real);
+        true
+    }
+
+    fn leave(&mut self) {
+        self.0.pop();
+    }
+}
+
+fn real_dir(dir: &Path) -> PathBuf {
+    fs::canonicalize(long_path(dir)).unwrap_or_else(|_| dir.to_path_buf())
+}
+
+/// 原样递归复制目录（不应用任何排除规则）
+///
+/// symlink 复制为指向相同目标的 symlink 而不跟随，链接成环时也不会无限递归
+pub(crate) fn copy_dir_all(src: &Path, dst: &Path) -> Result<(), AppError> {
+    fs::create_dir_all(long_path(dst))
+        .map_err(|e| AppError::InstallFailed { message: format!("Failed to create dir: {}", e) })?;
+    let entries = fs::read_dir(long_path(src))
+        .map_err(|e| AppError::InstallFailed { message: format!("Failed to read dir: {}", e) })?;
+
+    for entry in entries.filter_map(|e| e.ok()) {
+        let path = src.join(entry.file_name());
+        let dst_path = dst.join(entry.file_name());
+        let (long_src, long_dst) = (long_path(&path), long_path(&dst_path));
+        if is_symlink_or_junction(&path) {
+            copy_link(&long_src, &long_dst)?;
+        } else if long_src.is_dir() {
+            copy_dir_all(&path, &dst_path)?;
+        } else {
+            fs::copy(&long_src, &long_dst)
+                .map_err(|e| AppError::InstallFailed { message: format!("Failed to copy file: {}", e) })?;
+            preserve_permissions(&long_src, &long_dst)?;
+        }
+    }
+
+    Ok(())
+}
+
+/// 复制 symlink 本身（目标保持原样，相对链接仍相对于新位置解析）
+fn copy_link(src: &Path, dst: &Path) -> Result<(), AppError> {
+    let target = fs::read_link(src)
+        .map_err(|e| AppError::InstallFailed { message: format!("Failed to read link: {}", e) })?;
+
+    #[cfg(unix)]
+    let result = std::os::unix::fs::symlink(&target, dst);
+    #[cfg(windows)]
+    let result = if src.is_dir() {
+        std::os::windows::fs::symlink_dir(&target, dst)
+    } else {
+        std::os::windows::fs::symlink_file(&target, dst)
+    };
+
+    result.map_err(|e| AppError::InstallFailed { message: format!("Failed to copy link: {}", e) })
+}
+
+/// 读取 skill 根目录下的 `.skillignore`
+///
+/// 文件不存在时返回 None；个别无法解析的规则会被跳过，不影响其余规则。
+fn load_skillignore(root: &Path) -> Option<Gitignore> {
+    let path = root.join(SKILLIGNORE_FILE);
+    if !path.is_file() {
+        return None;
+    }
+
+    let mut builder = GitignoreBuilder::new(root);
+    let _ = builder.add(&path);
+    builder.build().ok()
+}
+
+fn copy_skill_files_filtered(
+    src: &Path,
+    dst: &Path,
+    excludes: &CopyExcludes,
+    skillignore: Option<&Gitignore>,
+    is_root: bool,
+    transfer: FileTransfer,
+    chain: &mut DirChain,
+) -> Result<(), AppError> {
     // 确保目标目录存在
-    fs::create_dir_all(dst)
+    fs::create_dir_all(long_path(dst))
         .map_err(|e| AppError::InstallFailed { message: format!("Failed to create dir: {}", e) })?;
 
-    // 遍历源目录
-    let entries = fs::read_dir(src)
+    // 遍历源目录（路径保持原样拼接，供 .skillignore 按相对根目录匹配）
+    let entries = fs::read_dir(long_path(src))
         .map_err(|e| AppError::InstallFailed { message: format!("Failed to read dir: {}", e) })?;
 
     for entry in entries.filter_map(|e| e.ok()) {
-        let path = entry.path();
+        let path = src.join(entry.file_name());
         let file_name = path.file_name().and_then(|n| n.to_str()).unwrap_or("");
-
-        // 跳过排除的文件
-        if EXCLUDE_FILES.contains(&file_name) {
-            continue;
-        }
-        // 跳过 _ 开头的文件/目录
-        if file_name.starts_with('_') {
+        let is_dir = long_path(&path).is_dir();
+        if is_excluded(&path, file_name, is_dir, is_root, excludes, skillignore) {
             continue;
         }
 
         let dst_path = dst.join(file_name);
-
-        if path.is_dir() {
-            // 跳过排除的目录
-            if EXCLUDE_DIRS.contains(&file_name) {
+        if is_dir {
+            // 递归复制目录
+            if !chain.enter(&path) {
                 continue;
             }
-            // 递归复制目录
-            copy_skill_files(&path, &dst_path)?;
+            copy_skill_files_filtered(&path, &dst_path, excludes, skillignore, false, transfer, chain)?;
+            chain.leave();
         } else {
+            // 硬链接与源文件共享权限位，无需再设置
+            let (long_src, long_dst) = (long_path(&path), long_path(&dst_path));
+            if transfer == FileTransfer::Hardlink && fs::hard_link(&long_src, &long_dst).is_ok() {
+                continue;
+            }
             // 复制文件（解引用 symlink）
-            fs::copy(&path, &dst_path)
+            fs::copy(&long_src, &long_dst)
                 .map_err(|e| AppError::InstallFailed { message: format!("Failed to copy file: {}", e) })?;
+            preserve_permissions(&long_src, &long_dst)?;
         }
     }
 
     Ok(())
 }
 
-/// 创建 symlink（跨平台，与 CLI createSymlink 一致）
-fn create_symlink(target: &Path, link: &Path) -> Result<(), AppError> {
-    // 确保父目录存在
-    if let Some(parent) = link.parent() {
-        fs::create_dir_all(parent)
-            .map_err(|e| AppError::InstallFailed { message: format!("Failed to create parent dir: {}", e) })?;
+/// 复制时是否跳过该文件/目录
+fn is_excluded(
+    path: &Path,
+    file_name: &str,
+    is_dir: bool,
+    is_root: bool,
+    excludes: &CopyExcludes,
+    skillignore: Option<&Gitignore>,
+) -> bool {
+    // 排除的文件/目录、_ 开头的文件/目录、根目录的 .skillignore 本身
+    if excludes.excludes(file_name, is_dir)
+        || file_name.starts_with('_')
+        || (is_root && file_name == SKILLIGNORE_FILE)
+    {
+        return true;
+    }
+    // .skillignore 匹配的文件/目录（被忽略的目录不再下探，与 git 语义一致）
+    skillignore.is_some_and(|matcher| matcher.matched(path, is_dir).is_ignore())
+}
+
+/// 安装时会被跳过的文件 / 目录（相对 `src` 的路径，规则同复制；被排除的目录不再下探）
+pub(crate) fn excluded_entries(src: &Path) -> Vec<PathBuf> {
+    fn walk(
+        dir: &Path,
+        relative: &Path,
+        excludes: &CopyExcludes,
+        skillignore: Option<&Gitignore>,
+        excluded: &mut Vec<PathBuf>,
+        chain: &mut DirChain,
+    ) {
+        let Ok(entries) = fs::read_dir(long_path(dir)) else {
+            return;
+        };
+        for entry in entries.filter_map(|e| e.ok()) {
+            let path = dir.join(entry.file_name());
+            let file_name = path.file_name().and_then(|n| n.to_str()).unwrap_or("");
+            let is_dir = long_path(&path).is_dir();
+            let rel_path = relative.join(file_name);
+            let is_root = relative.as_os_str().is_empty();
+            if is_excluded(&path, file_name, is_dir, is_root, excludes, skillignore) {
+                // 根目录的 .skillignore 是规则文件本身，不算被排除的内容
+                if !(is_root && file_name == SKILLIGNORE_FILE) {
+                    excluded.push(rel_path);
+                }
+            } else if is_dir && chain.enter(&path) {
+                walk(&path, &rel_path, excludes, skillignore, excluded, chain);
+                chain.leave();
+            }
+        }
     }
 
-    // 检查目标和链接是否相同
-    let resolved_target = target.canonicalize().unwrap_or_else(|_| target.to_path_buf());
-    let resolved_link_parent = link
-        .parent()
-        .and_then(|p| p.canonicalize().ok())
-        .unwrap_or_else(|| link.parent().unwrap_or(Path::new(".")).to_path_buf());
-    let resolved_link = resolved_link_parent.join(link.file_name().unwrap_or_default());
+    let skillignore = load_skillignore(src);
+    let mut excluded = Vec::new();
+    walk(src, Path::new(""), &copy_excludes(), skillignore.as_ref(), &mut excluded, &mut DirChain::new(src));
+    excluded.sort();
+    excluded
+}
 
-    if resolved_target == resolved_link {
-        // 相同路径，无需创建 symlink
-        return Ok(());
+/// 增量同步 skill 文件到已有目录
+///
+/// 只写入新增和内容变化的文件（按 SHA-256 比较），删除源中已不存在的文件，
+/// 未变化的文件保持原样（mtime 不变）。变化的文件先删除再写入新文件，
+/// 不会修改与 hardlink 安装共享的旧 inode。
+///
+/// 目标不是实体目录（不存在、symlink / junction）时回退为 `stage_and_replace`。
+/// 与暂存替换不同，同步中途失败时目录可能处于部分更新的状态，重新安装即可恢复。
+pub(crate) fn sync_skill_files(src: &Path, target: &Path) -> Result<(), AppError> {
+    if is_symlink_or_junction(target) || !target.is_dir() {
+        return stage_and_replace(src, target);
     }
 
-    // 如果已存在，先删除
-    if link.exists() || link.symlink_metadata().is_ok() {
-        if link.is_dir() {
-            fs::remove_dir_all(link).ok();
-        } else {
-            fs::remove_file(link).ok();
+    let skillignore = load_skillignore(src);
+    let excludes = copy_excludes();
+    let mut wanted = HashSet::new();
+    let mut chain = DirChain::new(src);
+    sync_dir(src, target, Path::new(""), &excludes, skillignore.as_ref(), &mut wanted, &mut chain)?;
+    prune_unwanted(target, Path::new(""), &wanted)
+}
+
+/// 同步一层目录，记录应保留的相对路径（文件和目录）
+fn sync_dir(
+    src: &Path,
+    dst: &Path,
+    relative: &Path,
+    excludes: &CopyExcludes,
+    skillignore: Option<&Gitignore>,
+    wanted: &mut HashSet<PathBuf>,
+    chain: &mut DirChain,
+) -> Result<(), AppError> {
+    let entries = fs::read_dir(long_path(src))
+        .map_err(|e| AppError::InstallFailed { message: format!("Failed to read dir: {}", e) })?;
+
+    for entry in entries.filter_map(|e| e.ok()) {
+        let path = src.join(entry.file_name());
+        let file_name = path.file_name().and_then(|n| n.to_str()).unwrap_or("");
+        let is_dir = long_path(&path).is_dir();
+        if is_excluded(&path, file_name, is_dir, relative.as_os_str().is_empty(), excludes, skillignore) {
+            continue;
         }
-    }
 
-    // 计算相对路径
-    let relative_target = pathdiff::diff_paths(&resolved_target, &resolved_link_parent)
-        .ok_or_else(|| AppError::InstallFailed { message: "Failed to compute relative path".to_string() })?;
+        if is_dir && !chain.enter(&path) {
+            continue;
+        }
+        let rel_path = relative.join(file_name);
+        let dst_path = long_path(&dst.join(&rel_path));
+        wanted.insert(rel_path.clone());
 
-    // 创建 symlink
-    #[cfg(unix)]
-    {
-        std::os::unix::fs::symlink(&relative_target, link)
-            .map_err(|e| AppError::InstallFailed { message: format!("Failed to create symlink: {}", e) })?;
-    }
+        if is_dir {
+            // 目标位置是文件或 symlink 时替换为目录
+            if dst_path.symlink_metadata().is_ok_and(|m| !m.is_dir()) {
+                remove_entry(&dst_path)?;
+            }
+            fs::create_dir_all(&dst_path)
+                .map_err(|e| AppError::InstallFailed { message: format!("Failed to create dir: {}", e) })?;
+            sync_dir(&path, dst, &rel_path, excludes, skillignore, wanted, chain)?;
+            chain.leave();
+            continue;
+        }
 
-    #[cfg(windows)]
-    {
-        // Windows 优先尝试 junction（不需要管理员权限）
-        if let Err(_) = junction::create(&resolved_target, link) {
-            // Junction 失败，尝试 symlink_dir
-            std::os::windows::fs::symlink_dir(&relative_target, link)
-                .map_err(|e| AppError::InstallFailed { message: format!("Failed to create symlink: {}", e) })?;
+        let long_src = long_path(&path);
+        let unchanged = dst_path.symlink_metadata().is_ok_and(|m| m.is_file())
+            && same_content(&long_src, &dst_path)?;
+        if !unchanged {
+            if dst_path.symlink_metadata().is_ok() {
+                remove_entry(&dst_path)?;
+            }
+            fs::copy(&long_src, &dst_path)
+                .map_err(|e| AppError::InstallFailed { message: format!("Failed to copy file: {}", e) })?;
         }
+        // 内容未变但权限可能变化（如脚本新增可执行位）
+        preserve_permissions(&long_src, &dst_path)?;
     }
 
     Ok(())
 }
 
-/// 检查 skill 是否已安装在指定 agent
-pub fn is_skill_installed(
-    skill_name: &str,
-    agent: &AgentType,
-    scope: &Scope,
-    project_path: Option<&str>,
-) -> bool {
-    let is_global = matches!(scope, Scope::Global);
-    let cwd = project_path.unwrap_or(".");
-    let sanitized_name = sanitize_name(skill_name);
+/// 删除目标目录中不在 `wanted` 里的文件和目录（不跟随 symlink）
+fn prune_unwanted(dst_root: &Path, relative: &Path, wanted: &HashSet<PathBuf>) -> Result<(), AppError> {
+    let dir = long_path(&dst_root.join(relative));
+    let entries = fs::read_dir(&dir)
+        .map_err(|e| AppError::InstallFailed { message: format!("Failed to read dir: {}", e) })?;
 
-    let config = agent.config();
+    for entry in entries.filter_map(|e| e.ok()) {
+        let rel_path = relative.join(entry.file_name());
+        if !wanted.contains(&rel_path) {
+            remove_entry(&entry.path())?;
+        } else if entry.file_type().is_ok_and(|t| t.is_dir()) {
+            prune_unwanted(dst_root, &rel_path, wanted)?;
+        }
+    }
+    Ok(())
+}
+
+/// 删除文件、symlink 或目录
+fn remove_entry(path: &Path) -> Result<(), AppError> {
+    let is_dir = path.symlink_metadata().is_ok_and(|m| m.is_dir());
+    let result = if is_dir { fs::remove_dir_all(path) } else { fs::remove_file(path) };
+    result.map_err(|e| AppError::InstallFailed {
+        message: format!("Failed to remove {}: {}", path.display(), e),
+    })
+}
 
-    // 检查 agent 是否支持 global 安装
-    if is_global && config.global_skills_dir.is_none() {
-        return false;
+/// 两个文件内容是否相同（先比较大小，再比较 SHA-256）
+fn same_content(a: &Path, b: &Path) -> Result<bool, AppError> {
+    let read_err = |e: std::io::Error| AppError::InstallFailed { message: format!("Failed to read file: {}", e) };
+    let (meta_a, meta_b) = (fs::metadata(a).map_err(read_err)?, fs::metadata(b).map_err(read_err)?);
+    if meta_a.len() != meta_b.len() {
+        return Ok(false);
     }
+    let digest = |path: &Path| fs::read(path).map(Sha256::digest).map_err(read_err);
+    Ok(digest(a)? == digest(b)?)
+}
 
-    let agent_base = if is_global {
-        config.global_skills_dir.clone().unwrap()
-    } else {
-        PathBuf::from(cwd).join(&config.skills_dir)
-    };
+/// 将源文件的权限位显式写入目标文件，保证 skill 附带的脚本保持可执行
+///
+/// `fs::copy` 在 Unix 上通常会复制权限，但不能依赖所有调用路径都如此，
+/// 因此复制后再按源文件 mode 设置一次。Windows 没有可执行位，此函数为空操作。
+#[cfg(unix)]
+fn preserve_permissions(src: &Path, dst: &Path) -> Result<(), AppError> {
+    use std::os::unix::fs::PermissionsExt;
 
-    let skill_dir = agent_base.join(&sanitized_name);
-    skill_dir.exists()
+    let mode = fs::metadata(src)
+        .map_err(|e| AppError::InstallFailed { message: format!("Failed to read permissions: {}", e) })?
+        .permissions()
+        .mode();
+    fs::set_permissions(dst, fs::Permissions::from_mode(mode & 0o7777))
+        .map_err(|e| AppError::InstallFailed { message: format!("Failed to set permissions: {}", e) })
 }
 
-#[cfg(test)]
+#[cfg(windows)]
+fn preserve_permissions(_src: &Path, _dst: &Path) -> Result<(), AppError> {
+    Ok(())
+}
+
+/// 能否在指定目录下创建 symlink / junction
+///
+/// 首次调用时在目录内创建并删除一个测试链接（与实际安装使用同一套 `create_symlink`），
+/// 结果按目录缓存到进程结束。目录尚不存在时在最近的已存在上级目录中探测，不会创建目录；
+/// 无法探测时返回 true 且不缓存，交由实际安装时的 fallback 处理。
+pub fn can_symlink(dir: &Path) -> bool {
+    symlink_unavailable_reason(dir).is_none()
+}
+
+/// 指定目录下无法创建 symlink 的原因（探测逻辑与缓存同 [`can_symlink`]）
+pub fn symlink_unavailable_reason(dir: &Path) -> Option<String> {
+    let key = resolve_path(dir);
+    if let Some(probed) = SYMLINK_SUPPORT.lock().unwrap_or_else(|e| e.into_inner()).get(&key) {
+        return probed.clone().err();
+    }
+
+    let probed = probe_symlink(dir)?;
+    if let Err(reason) = &probed {
+        log::info!("Symlinks unavailable in {} ({}), installing with copy", dir.display(), reason);
+    }
+    SYMLINK_SUPPORT
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .insert(key, probed.clone());
+    probed.err()
+}
+
+/// 将 symlink 创建错误转为失败原因
+///
+/// Windows 权限不足（未开启开发者模式）时附加提示，以便与文件系统不支持链接区分
+fn describe_symlink_error(error: &str) -> String {
+    if error.contains(WINDOWS_PRIVILEGE_NOT_HELD) {
+        format!(
+            "{} (enable Windows Developer Mode or run as administrator to allow symlinks)",
+            error
+        )
+    } else {
+        error.to_string()
+    }
+}
+
+/// 创建测试链接，无法创建探测目录时返回 None（探测目录随 TempDir drop 删除）
+fn probe_symlink(dir: &Path) -> Option<Result<(), String>> {
+    let existing = dir.ancestors().find(|p| p.is_dir())?;
+    let probe = tempfile::Builder::new()
+        .prefix(SYMLINK_PROBE_PREFIX)
+        .tempdir_in(existing)
+        .ok()?;
+    let target = probe.path().join("target");
+    fs::create_dir(&target).ok()?;
+
+    Some(
+        create_symlink(&target, &probe.path().join("link"))
+            .map_err(|e| describe_symlink_error(&e.to_string())),
+    )
+}
+
+/// 创建 symlink（跨平台，与 CLI createSymlink 一致）
+pub(crate) fn create_symlink(target: &Path, link: &Path) -> Result<(), AppError> {
+    // 确保父目录存在
+    if let Some(parent) = link.parent() {
+        fs::create_dir_all(long_path(parent))
+            .map_err(|e| AppError::InstallFailed { message: format!("Failed to create parent dir: {}", e) })?;
+    }
+
+    // 检查目标和链接是否相同（两侧都解析 symlink，如 $HOME 本身是 symlink）
+    let resolved_target = resolve_path(target);
+    let resolved_link_parent = resolve_path(link.parent().unwrap_or(Path::new(".")));
+    let resolved_link = resolved_link_parent.join(link.file_name().unwrap_or_default());
+
+    if resolved_target == resolved_link {
+        // 相同路径，无需创建 symlink
+        return Ok(());
+    }
+
+    // 如果已存在，先删除
+    let long_link = long_path(link);
+    if long_link.exists() || long_link.symlink_metadata().is_ok() {
+        if long_link.is_dir() {
+            fs::remove_dir_all(&long_link).ok();
+        } else {
+            fs::remove_file(&long_link).ok();
+        }
+    }
+
+    // 计算相对路径
+    let relative_target = pathdiff::diff_paths(&resolved_target, &resolved_link_parent)
+        .ok_or_else(|| AppError::InstallFailed { message: "Failed to compute relative path".to_string() })?;
+
+    // 创建 symlink
+    #[cfg(unix)]
+    {
+        std::os::unix::fs::symlink(&relative_target, link)
+            .map_err(|e| AppError::InstallFailed { message: format!("Failed to create symlink: {}", e) })?;
+    }
+
+    #[cfg(windows)]
+    {
+        // Windows 优先尝试 junction（不需要管理员权限）
+        if let Err(_) = junction::create(&resolved_target, &long_link) {
+            // Junction 失败，尝试 symlink_dir
+            std::os::windows::fs::symlink_dir(&relative_target, &long_link)
+                .map_err(|e| AppError::InstallFailed { message: format!("Failed to create symlink: {}", e) })?;
+        }
+    }
+
+    Ok(())
+}
+
+/// 计算 skill 在指定 agent 下的安装目录（目录名见 `skill_dir_name`）
+///
+/// agent 不支持该 scope 的安装时返回 None
+pub fn agent_skill_dir(
+    skill_name: &str,
+    agent: &AgentType,
+    scope: &Scope,
+    project_path: Option<&str>,
+) -> Option<PathBuf> {
+    let is_global = matches!(scope, Scope::Global);
+    let cwd = project_path.unwrap_or(".");
+
+    agent_skills_dir(agent, is_global, cwd)
+        .map(|base| base.join(skill_dir_name(skill_name, is_global, cwd)))
+}
+
+/// 已安装该 skill 的 agents 及各自的实际安装方式
+///
+/// agent 目录存在（包括失效的 symlink）即视为已安装。symlink/junction 或就是 canonical 目录
+/// （Universal Agent）时为 Symlink，SKILL.md 与 canonical 目录中的是同一文件时为 Hardlink，
+/// 否则为 Copy。
+/// 多个 agent 共用同一安装位置时只保留第一个，避免重复写入
+pub fn installed_agent_modes(
+    skill_name: &str,
+    scope: &Scope,
+    project_path: Option<&str>,
+) -> Vec<(AgentType, InstallMode)> {
+    let is_global = matches!(scope, Scope::Global);
+    let cwd = project_path.unwrap_or(".");
+    let canonical_dir = canonical_skills_dir(is_global, cwd).join(skill_dir_name(skill_name, is_global, cwd));
+
+    // 只解析父目录：agent 目录本身是指向 canonical 的链接时不应与 universal agents 合并
+    let location = |dir: &Path| match (dir.parent(), dir.file_name()) {
+        (Some(parent), Some(name)) => resolve_path(parent).join(name),
+        _ => dir.to_path_buf(),
+    };
+
+    let mut seen: Vec<PathBuf> = Vec::new();
+    let mut installed = Vec::new();
+    for agent in AgentType::all() {
+        let Some(agent_dir) = agent_skill_dir(skill_name, &agent, scope, project_path) else {
+            continue;
+        };
+        let agent_location = location(&agent_dir);
+        if agent_dir.symlink_metadata().is_err() || seen.contains(&agent_location) {
+            continue;
+        }
+        let mode = if is_symlink_or_junction(&agent_dir) || same_path(&agent_dir, &canonical_dir) {
+            InstallMode::Symlink
+        } else if same_file(&agent_dir.join("SKILL.md"), &canonical_dir.join("SKILL.md")) {
+            InstallMode::Hardlink
+        } else {
+            InstallMode::Copy
+        };
+        seen.push(agent_location);
+        installed.push((agent, mode));
+    }
+    installed
+}
+
+/// 写入 canonical 目录（参数同 `install_skill_for_agent_in_dir`）
+///
+/// Hardlink 安装前每个 skill 调用一次，各 agent 再硬链接到其中的文件；
+/// `incremental` 为 true 时增量同步，否则整目录替换
+pub fn write_canonical_dir(
+    skill_path: &Path,
+    dir_name: &str,
+    scope: &Scope,
+    project_path: Option<&str>,
+    incremental: bool,
+) -> Result<(), AppError> {
+    let is_global = matches!(scope, Scope::Global);
+    let canonical_dir = canonical_skills_dir(is_global, project_path.unwrap_or(".")).join(dir_name);
+    write_skill_dir(skill_path, &canonical_dir, incremental)
+}
+
+/// 单独刷新已存在的 canonical 目录（参数同 `install_skill_for_agent`）
+///
+/// 各 agent 都是 Copy 安装时 canonical 目录不会随 agent 写入，需要单独同步；
+/// `incremental` 为 true 时增量同步，否则整目录替换。canonical 目录不存在时返回 false
+pub fn refresh_canonical_dir(
+    skill_path: &Path,
+    skill_name: &str,
+    scope: &Scope,
+    project_path: Option<&str>,
+    incremental: bool,
+) -> Result<bool, AppError> {
+    let is_global = matches!(scope, Scope::Global);
+    let cwd = project_path.unwrap_or(".");
+    let canonical_dir = canonical_skills_dir(is_global, cwd).join(skill_dir_name(skill_name, is_global, cwd));
+    if canonical_dir.symlink_metadata().is_err() {
+        return Ok(false);
+    }
+    write_skill_dir(skill_path, &canonical_dir, incremental)?;
+    Ok(true)
+}
+
+/// 选择 skill 的安装目录名
+///
+/// 默认为 lock 中已记录的目录名或 `sanitize_name(skill_name)`。
+/// `Suffix` 策略下，若该目录（canonical 目录或任一目标 agent 目录）已被 SKILL.md name
+/// 不同的 skill 占用，依次尝试 `<name>-2`、`<name>-3`…直到找到空闲或属于自己的目录。
+/// 没有 SKILL.md 或无法解析的目录不视为被占用（与原有的覆盖行为一致）。
+///
+/// 同一批次中已分配给其他 skill 的目录名（`claimed`）在任何策略下都视为被占用，
+/// 避免同批安装的 skill 互相覆盖。
+///
+/// # Arguments
+/// * `skill_name` - skill 名称（SKILL.md 中的 name）
+/// * `agents` - 目标 agents
+/// * `is_global` - 是否为 global scope
+/// * `cwd` - 项目路径
+/// * `policy` - 冲突处理方式
+/// * `claimed` - 本批次已分配的目录名
+pub fn resolve_install_dir_name(
+    skill_name: &str,
+    agents: &[AgentType],
+    is_global: bool,
+    cwd: &str,
+    policy: NameCollisionPolicy,
+    claimed: &HashSet<String>,
+) -> String {
+    let preferred = skill_dir_name(skill_name, is_global, cwd);
+    let mut bases = vec![canonical_skills_dir(is_global, cwd)];
+    if policy == NameCollisionPolicy::Overwrite {
+        bases.clear();
+    } else {
+        bases.extend(agents.iter().filter_map(|agent| agent_skills_dir(agent, is_global, cwd)));
+    }
+    let occupied = |dir_name: &str| {
+        claimed.contains(dir_name)
+            || bases
+                .iter()
+                .any(|base| occupied_by_other(&base.join(dir_name), skill_name))
+    };
+
+    if !occupied(&preferred) {
+        return preferred;
+    }
+    let base_name = sanitize_name(skill_name);
+    let mut n = 2;
+    loop {
+        let candidate = format!("{}-{}", base_name, n);
+        if !occupied(&candidate) {
+            return candidate;
+        }
+        n += 1;
+    }
+}
+
+/// 目录中是否安装了另一个 skill（SKILL.md 的 name 与 `skill_name` 不同）
+fn occupied_by_other(dir: &Path, skill_name: &str) -> bool {
+    parse_skill_md(&dir.join("SKILL.md")).is_ok_and(|frontmatter| frontmatter.name != skill_name)
+}
+
+/// 按安装规则（排除列表 + .skillignore）将 skill 复制到临时目录
+///
+/// 得到的内容与实际安装结果一致，可直接与已安装目录比较
+pub fn stage_installable(skill_path: &Path) -> Result<tempfile::TempDir, AppError> {
+    let staging = tempfile::tempdir()?;
+    copy_skill_files(skill_path, staging.path(), &copy_excludes())?;
+    Ok(staging)
+}
+
+/// 同 `stage_installable`，并将 `SKILL.<locale>.md` 作为暂存目录中的 SKILL.md
+///
+/// locale 按大小写不敏感匹配，返回实际使用的 locale（文件名中的写法）和暂存目录；
+/// skill 没有该本地化版本时返回 None（使用原 SKILL.md 安装）
+pub fn stage_localized(
+    skill_path: &Path,
+    locale: &str,
+) -> Result<Option<(String, tempfile::TempDir)>, AppError> {
+    let Some(locale) = skill_md_locales(skill_path)
+        .into_iter()
+        .find(|l| l.eq_ignore_ascii_case(locale.trim()))
+    else {
+        return Ok(None);
+    };
+
+    let staging = stage_installable(skill_path)?;
+    fs::rename(staging.path().join(localized_skill_md(&locale)), staging.path().join("SKILL.md"))
+        .map_err(|e| AppError::InstallFailed { message: format!("Failed to apply locale {}: {}", locale, e) })?;
+    Ok(Some((locale, staging)))
+}
+
+/// 计算 skill 安装后的内容 hash
+///
+/// 直接读取源目录中会被安装的文件（规则同复制），不做暂存复制；
+/// 结果可与已安装目录的 compute_skill_folder_hash 直接比较
+pub fn compute_installable_hash(skill_path: &Path) -> Result<String, AppError> {
+    fn walk(
+        dir: &Path,
+        relative: &str,
+        excludes: &CopyExcludes,
+        skillignore: Option<&Gitignore>,
+        files: &mut Vec<(String, Vec<u8>)>,
+        chain: &mut DirChain,
+    ) -> Result<(), AppError> {
+        for entry in fs::read_dir(long_path(dir))?.filter_map(|e| e.ok()) {
+            let path = dir.join(entry.file_name());
+            let file_name = path.file_name().and_then(|n| n.to_str()).unwrap_or("");
+            let is_dir = long_path(&path).is_dir();
+            if is_excluded(&path, file_name, is_dir, relative.is_empty(), excludes, skillignore)
+                || HASH_SKIP_DIRS.contains(&file_name)
+            {
+                continue;
+            }
+
+            let rel_path = if relative.is_empty() {
+                file_name.to_string()
+            } else {
+                format!("{}/{}", relative, file_name)
+            };
+            if is_dir {
+                if chain.enter(&path) {
+                    walk(&path, &rel_path, excludes, skillignore, files, chain)?;
+                    chain.leave();
+                }
+            } else {
+                files.push((rel_path, fs::read(long_path(&path))?));
+            }
+        }
+        Ok(())
+    }
+
+    let skillignore = load_skillignore(skill_path);
+    let mut files = Vec::new();
+    walk(
+        skill_path,
+        "",
+        &copy_excludes(),
+        skillignore.as_ref(),
+        &mut files,
+        &mut DirChain::new(skill_path),
+    )?;
+    Ok(hash_skill_files(files))
+}
+
+#[cfg(test)]
 mod tests {
     use super::*;
     use std::fs;
     use tempfile::tempdir;
+    use crate::core::local_lock::compute_skill_folder_hash;
 
     #[test]
     fn test_copy_skill_files_basic() {
@@ -318,7 +1271,7 @@ mod tests {
         fs::write(src.path().join("SKILL.md"), "# Test").unwrap();
         fs::write(src.path().join("config.json"), "{}").unwrap();
 
-        copy_skill_files(src.path(), dst.path()).unwrap();
+        copy_skill_files(src.path(), dst.path(), &CopyExcludes::default()).unwrap();
 
         assert!(dst.path().join("SKILL.md").exists());
         assert!(dst.path().join("config.json").exists());
@@ -337,7 +1290,7 @@ mod tests {
         fs::create_dir(src.path().join(".git")).unwrap();
         fs::write(src.path().join(".git/config"), "git config").unwrap();
 
-        copy_skill_files(src.path(), dst.path()).unwrap();
+        copy_skill_files(src.path(), dst.path(), &CopyExcludes::default()).unwrap();
 
         // SKILL.md 应该被复制
         assert!(dst.path().join("SKILL.md").exists());
@@ -349,6 +1302,45 @@ mod tests {
         assert!(!dst.path().join(".git").exists());
     }
 
+    #[test]
+    fn test_copy_skill_files_configured_excludes() {
+        let src = tempdir().unwrap();
+        fs::write(src.path().join("SKILL.md"), "# Test").unwrap();
+        fs::write(src.path().join("README.md"), "# README").unwrap();
+        fs::write(src.path().join("metadata.json"), "{}").unwrap();
+        fs::create_dir_all(src.path().join("node_modules/pkg")).unwrap();
+        fs::write(src.path().join("node_modules/pkg/index.js"), "").unwrap();
+        fs::create_dir_all(src.path().join(".git")).unwrap();
+        fs::create_dir_all(src.path().join("docs/node_modules")).unwrap();
+
+        // 配置的排除项与默认值合并
+        let extra_files = vec!["README.md".to_string(), " ".to_string()];
+        let extra_dirs = vec!["node_modules".to_string()];
+        let excludes = CopyExcludes::new(&extra_files, &extra_dirs, false);
+        assert_eq!(excludes.files, vec!["metadata.json", "README.md"]);
+        assert_eq!(excludes.dirs, vec![".git", "node_modules"]);
+
+        let dst = tempdir().unwrap();
+        copy_skill_files(src.path(), dst.path(), &excludes).unwrap();
+        assert!(dst.path().join("SKILL.md").exists());
+        assert!(!dst.path().join("README.md").exists());
+        assert!(!dst.path().join("metadata.json").exists());
+        assert!(!dst.path().join("node_modules").exists());
+        assert!(!dst.path().join("docs/node_modules").exists());
+        assert!(!dst.path().join(".git").exists());
+
+        // replace 时只使用配置的排除项
+        let excludes = CopyExcludes::new(&extra_files, &[], true);
+        let dst = tempdir().unwrap();
+        copy_skill_files(src.path(), dst.path(), &excludes).unwrap();
+        assert!(!dst.path().join("README.md").exists());
+        assert!(dst.path().join("metadata.json").exists());
+        assert!(dst.path().join(".git").exists());
+
+        assert!(validate_copy_excludes(&extra_files).is_ok());
+        assert!(validate_copy_excludes(&["docs/README.md".to_string()]).is_err());
+    }
+
     #[test]
     fn test_copy_skill_files_recursive() {
         let src = tempdir().unwrap();
@@ -359,27 +1351,645 @@ mod tests {
         fs::write(src.path().join("SKILL.md"), "# Test").unwrap();
         fs::write(src.path().join("scripts/helper.py"), "# Python").unwrap();
 
-        copy_skill_files(src.path(), dst.path()).unwrap();
+        copy_skill_files(src.path(), dst.path(), &CopyExcludes::default()).unwrap();
 
         assert!(dst.path().join("SKILL.md").exists());
         assert!(dst.path().join("scripts/helper.py").exists());
     }
 
     #[test]
-    fn test_clean_and_create_directory() {
+    fn test_copy_skill_files_skillignore_negation() {
+        let src = tempdir().unwrap();
+        let dst = tempdir().unwrap();
+
+        fs::write(src.path().join(SKILLIGNORE_FILE), "*.log\n!keep.log\n").unwrap();
+        fs::write(src.path().join("SKILL.md"), "# Test").unwrap();
+        fs::write(src.path().join("debug.log"), "debug").unwrap();
+        fs::write(src.path().join("keep.log"), "keep").unwrap();
+        fs::create_dir(src.path().join("scripts")).unwrap();
+        fs::write(src.path().join("scripts/run.log"), "run").unwrap();
+
+        copy_skill_files(src.path(), dst.path(), &CopyExcludes::default()).unwrap();
+
+        assert!(dst.path().join("SKILL.md").exists());
+        assert!(dst.path().join("keep.log").exists());
+        assert!(!dst.path().join("debug.log").exists());
+        assert!(!dst.path().join("scripts/run.log").exists());
+        // .skillignore 本身不复制
+        assert!(!dst.path().join(SKILLIGNORE_FILE).exists());
+    }
+
+    #[test]
+    fn test_compute_installable_hash_matches_installed_copy() {
+        let src = tempdir().unwrap();
+        fs::write(src.path().join(SKILLIGNORE_FILE), "*.log\n").unwrap();
+        fs::write(src.path().join("SKILL.md"), "# Test").unwrap();
+        fs::write(src.path().join("metadata.json"), "{}").unwrap();
+        fs::write(src.path().join("_draft.md"), "draft").unwrap();
+        fs::write(src.path().join("debug.log"), "debug").unwrap();
+        fs::create_dir_all(src.path().join("scripts/lib")).unwrap();
+        fs::write(src.path().join("scripts/lib/run.sh"), "run").unwrap();
+        fs::create_dir_all(src.path().join("node_modules/dep")).unwrap();
+        fs::write(src.path().join("node_modules/dep/index.js"), "dep").unwrap();
+
+        // 不复制也应与按安装规则复制后的目录 hash 相同
+        let staged = stage_installable(src.path()).unwrap();
+        assert_eq!(
+            compute_installable_hash(src.path()).unwrap(),
+            compute_skill_folder_hash(staged.path()).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_copy_skill_files_skillignore_directories() {
+        let src = tempdir().unwrap();
+        let dst = tempdir().unwrap();
+
+        // `build/` 只匹配目录；`/docs` 只匹配根目录下的 docs
+        fs::write(src.path().join(SKILLIGNORE_FILE), "build/\n/docs\n").unwrap();
+        fs::write(src.path().join("SKILL.md"), "# Test").unwrap();
+        fs::create_dir_all(src.path().join("build")).unwrap();
+        fs::write(src.path().join("build/out.txt"), "out").unwrap();
+        fs::create_dir_all(src.path().join("docs")).unwrap();
+        fs::write(src.path().join("docs/guide.md"), "guide").unwrap();
+        fs::create_dir_all(src.path().join("ref/docs")).unwrap();
+        fs::write(src.path().join("ref/docs/api.md"), "api").unwrap();
+        fs::create_dir_all(src.path().join("ref/build")).unwrap();
+        fs::write(src.path().join("ref/build/out.txt"), "out").unwrap();
+        // 名为 build 的文件不受目录规则影响
+        fs::create_dir_all(src.path().join("scripts")).unwrap();
+        fs::write(src.path().join("scripts/build"), "#!/bin/sh").unwrap();
+
+        copy_skill_files(src.path(), dst.path(), &CopyExcludes::default()).unwrap();
+
+        assert!(dst.path().join("SKILL.md").exists());
+        assert!(!dst.path().join("build").exists());
+        assert!(!dst.path().join("docs").exists());
+        assert!(!dst.path().join("ref/build").exists());
+        assert!(dst.path().join("ref/docs/api.md").exists());
+        assert!(dst.path().join("scripts/build").exists());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_copy_skill_files_skips_symlink_cycles() {
+        let src = tempdir().unwrap();
+        fs::write(src.path().join("SKILL.md"), "# Test").unwrap();
+        fs::create_dir_all(src.path().join("refs")).unwrap();
+        fs::write(src.path().join("refs/api.md"), "api").unwrap();
+        // 指回上级目录的链接，跟随会无限递归
+        std::os::unix::fs::symlink("..", src.path().join("refs/loop")).unwrap();
+        std::os::unix::fs::symlink(src.path(), src.path().join("self")).unwrap();
+
+        let dst = tempdir().unwrap();
+        copy_skill_files(src.path(), dst.path(), &CopyExcludes::default()).unwrap();
+        assert!(dst.path().join("refs/api.md").exists());
+        assert!(!dst.path().join("refs/loop").exists());
+        assert!(!dst.path().join("self").exists());
+
+        // 增量同步和排除项列举同样不会陷入循环
+        sync_skill_files(src.path(), dst.path()).unwrap();
+        assert!(dst.path().join("refs/api.md").exists());
+        assert!(excluded_entries(src.path()).is_empty());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_copy_skill_files_preserves_executable_bit() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let src = tempdir().unwrap();
+        let dst = tempdir().unwrap();
+
+        fs::write(src.path().join("SKILL.md"), "# Test").unwrap();
+        fs::create_dir_all(src.path().join("scripts")).unwrap();
+        let script = src.path().join("scripts/run.sh");
+        fs::write(&script, "#!/bin/sh\necho ok\n").unwrap();
+        fs::set_permissions(&script, fs::Permissions::from_mode(0o755)).unwrap();
+
+        copy_skill_files(src.path(), dst.path(), &CopyExcludes::default()).unwrap();
+
+        let mode = fs::metadata(dst.path().join("scripts/run.sh"))
+            .unwrap()
+            .permissions()
+            .mode();
+        assert_eq!(mode & 0o777, 0o755);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_install_hardlink_links_to_canonical() {
+        use std::os::unix::fs::MetadataExt;
+
+        let src = tempdir().unwrap();
+        fs::write(src.path().join("SKILL.md"), "---\nname: demo\ndescription: Demo\n---\n").unwrap();
+        fs::write(src.path().join("metadata.json"), "{}").unwrap();
+        let project = tempdir().unwrap();
+        let project_path = project.path().to_string_lossy().to_string();
+
+        // canonical 目录写入前无法硬链接
+        let result = install_skill_for_agent(
+            src.path(),
+            "demo",
+            &AgentType::ClaudeCode,
+            &Scope::Project,
+            Some(&project_path),
+            &InstallMode::Hardlink,
+        );
+        assert!(!result.success);
+
+        write_canonical_dir(src.path(), "demo", &Scope::Project, Some(&project_path), false).unwrap();
+        let canonical = project.path().join(".agents/skills/demo");
+        let inode = |p: &Path| fs::metadata(p).unwrap().ino();
+        for agent in [AgentType::ClaudeCode, AgentType::Windsurf] {
+            let result = install_skill_for_agent(
+                src.path(),
+                "demo",
+                &agent,
+                &Scope::Project,
+                Some(&project_path),
+                &InstallMode::Hardlink,
+            );
+            assert!(result.success, "{:?}", result.error);
+            assert_eq!(result.mode, InstallMode::Hardlink);
+            assert_eq!(result.canonical_path.as_deref(), Some(canonical.as_path()));
+        }
+
+        // 两个 agent 目录中的文件都与 canonical 共享 inode，排除规则仍然生效
+        for agent_dir in [project.path().join(".claude/skills/demo"), project.path().join(".windsurf/skills/demo")] {
+            assert!(!agent_dir.symlink_metadata().unwrap().file_type().is_symlink());
+            assert_eq!(inode(&agent_dir.join("SKILL.md")), inode(&canonical.join("SKILL.md")));
+            assert!(!agent_dir.join("metadata.json").exists());
+        }
+
+        // 更新时按 Hardlink 识别原安装方式
+        let modes = installed_agent_modes("demo", &Scope::Project, Some(&project_path));
+        assert!(modes.contains(&(AgentType::ClaudeCode, InstallMode::Hardlink)));
+        assert!(modes.contains(&(AgentType::Windsurf, InstallMode::Hardlink)));
+    }
+
+    #[test]
+    fn test_symlink_fallback_records_reason() {
+        let src = tempdir().unwrap();
+        fs::write(src.path().join("SKILL.md"), "---\nname: demo\ndescription: Demo\n---\n").unwrap();
+        let project = tempdir().unwrap();
+        let project_path = project.path().to_string_lossy().to_string();
+
+        // 模拟探测失败（如 Windows 未开启开发者模式）
+        let reason = describe_symlink_error("A required privilege is not held by the client. (os error 1314)");
+        assert!(reason.contains("Developer Mode"));
+        let canonical_base = project.path().join(".agents/skills");
+        SYMLINK_SUPPORT
+            .lock()
+            .unwrap()
+            .insert(resolve_path(&canonical_base), Err(reason.clone()));
+
+        let result = install_skill_for_agent(
+            src.path(),
+            "demo",
+            &AgentType::ClaudeCode,
+            &Scope::Project,
+            Some(&project_path),
+            &InstallMode::Symlink,
+        );
+        assert!(result.success, "{:?}", result.error);
+        assert!(result.symlink_failed);
+        assert_eq!(result.mode, InstallMode::Copy);
+        assert_eq!(result.symlink_error, Some(reason));
+        assert!(project.path().join(".claude/skills/demo/SKILL.md").is_file());
+
+        // 文件系统限制等其他错误原样保留
+        assert_eq!(
+            describe_symlink_error("Operation not permitted (os error 1)"),
+            "Operation not permitted (os error 1)"
+        );
+    }
+
+    #[test]
+    fn test_sync_skill_files_only_writes_changes() {
+        let src = tempdir().unwrap();
+        fs::create_dir_all(src.path().join("scripts")).unwrap();
+        fs::write(src.path().join("SKILL.md"), "# V1").unwrap();
+        fs::write(src.path().join("scripts/run.sh"), "echo run").unwrap();
+        fs::write(src.path().join("scripts/old.sh"), "echo old").unwrap();
+        fs::create_dir_all(src.path().join("legacy")).unwrap();
+        fs::write(src.path().join("legacy/notes.md"), "notes").unwrap();
+
         let temp = tempdir().unwrap();
-        let dir = temp.path().join("test-dir");
+        let target = temp.path().join("my-skill");
+        sync_skill_files(src.path(), &target).unwrap();
+        assert!(target.join("scripts/old.sh").is_file());
+
+        // 把未变化文件的 mtime 调到过去，便于判断是否被重写
+        let unchanged = target.join("scripts/run.sh");
+        let past = std::time::SystemTime::now() - std::time::Duration::from_secs(3600);
+        fs::File::options().write(true).open(&unchanged).unwrap().set_modified(past).unwrap();
+        let mtime_before = fs::metadata(&unchanged).unwrap().modified().unwrap();
+
+        // 新版本：修改 SKILL.md、删除 old.sh 和 legacy/、新增 README.md
+        fs::write(src.path().join("SKILL.md"), "# V2").unwrap();
+        fs::remove_file(src.path().join("scripts/old.sh")).unwrap();
+        fs::remove_dir_all(src.path().join("legacy")).unwrap();
+        fs::write(src.path().join("README.md"), "readme").unwrap();
+        sync_skill_files(src.path(), &target).unwrap();
+
+        assert_eq!(fs::metadata(&unchanged).unwrap().modified().unwrap(), mtime_before);
+        assert_eq!(fs::read_to_string(target.join("SKILL.md")).unwrap(), "# V2");
+        assert_eq!(fs::read_to_string(target.join("README.md")).unwrap(), "readme");
+        assert!(!target.join("scripts/old.sh").exists());
+        assert!(!target.join("legacy").exists());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_update_keeps_existing_symlink() {
+        let src = tempdir().unwrap();
+        fs::write(src.path().join("SKILL.md"), "---\nname: demo\ndescription: d\n---\n").unwrap();
+        let project = tempdir().unwrap();
+        let project_path = project.path().to_string_lossy().to_string();
+
+        let install = |update: bool| {
+            let result = install_skill_for_agent_with(
+                src.path(),
+                "demo",
+                "demo",
+                &AgentType::ClaudeCode,
+                &Scope::Project,
+                Some(&project_path),
+                &InstallMode::Symlink,
+                update,
+            );
+            assert!(result.success, "{:?}", result.error);
+        };
+        install(false);
+        let link = project.path().join(".claude/skills/demo");
+        if !is_symlink_or_junction(&link) {
+            // 当前文件系统不支持 symlink，已降级为 copy
+            return;
+        }
+        let link_before = fs::symlink_metadata(&link).unwrap().modified().unwrap();
+
+        std::thread::sleep(std::time::Duration::from_millis(20));
+        fs::write(src.path().join("extra.md"), "extra").unwrap();
+        install(true);
+
+        assert_eq!(fs::symlink_metadata(&link).unwrap().modified().unwrap(), link_before);
+        assert!(link.join("extra.md").is_file());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_installed_agent_modes() {
+        let src = tempdir().unwrap();
+        fs::write(src.path().join("SKILL.md"), "---\nname: demo\ndescription: d\n---\n").unwrap();
+        let project = tempdir().unwrap();
+        let project_path = project.path().to_string_lossy().to_string();
+
+        for (agent, mode) in [
+            (AgentType::ClaudeCode, InstallMode::Symlink),
+            (AgentType::Windsurf, InstallMode::Copy),
+        ] {
+            let result =
+                install_skill_for_agent(src.path(), "demo", &agent, &Scope::Project, Some(&project_path), &mode);
+            assert!(result.success, "{:?}", result.error);
+        }
+        if !is_symlink_or_junction(&project.path().join(".claude/skills/demo")) {
+            // 当前文件系统不支持 symlink，已降级为 copy
+            return;
+        }
+
+        let modes = installed_agent_modes("demo", &Scope::Project, Some(&project_path));
+        assert!(modes.contains(&(AgentType::ClaudeCode, InstallMode::Symlink)));
+        assert!(modes.contains(&(AgentType::Windsurf, InstallMode::Copy)));
+        // Universal agents 共用 canonical 目录，只保留一个
+        let universal: Vec<_> = modes.iter().filter(|(agent, _)| agent.is_universal()).collect();
+        assert_eq!(universal.len(), 1);
+        assert_eq!(universal[0].1, InstallMode::Symlink);
+        // 未安装的 agent 不出现在结果中
+        assert!(!modes.iter().any(|(agent, _)| *agent == AgentType::Roo));
+    }
+
+    #[test]
+    fn test_install_rejects_unsupported_project_agent() {
+        let src = tempdir().unwrap();
+        fs::write(src.path().join("SKILL.md"), "---\nname: demo\ndescription: d\n---\n").unwrap();
+        let project = tempdir().unwrap();
+        let project_path = project.path().to_string_lossy().to_string();
+
+        let result = install_skill_for_agent(
+            src.path(),
+            "demo",
+            &AgentType::Replit,
+            &Scope::Project,
+            Some(&project_path),
+            &InstallMode::Symlink,
+        );
+        assert!(!result.success);
+        assert_eq!(
+            result.error.as_deref(),
+            Some("Replit does not support project skill installation")
+        );
+        assert!(!project.path().join(".agents/skills/demo").exists());
+
+        // 不支持项目级安装的 agent 不视为已安装（即使共用的 canonical 目录存在）
+        let result = install_skill_for_agent(
+            src.path(),
+            "demo",
+            &AgentType::Amp,
+            &Scope::Project,
+            Some(&project_path),
+            &InstallMode::Symlink,
+        );
+        assert!(result.success, "{:?}", result.error);
+        assert!(agent_skill_dir("demo", &AgentType::Replit, &Scope::Project, Some(&project_path)).is_none());
+        let modes = installed_agent_modes("demo", &Scope::Project, Some(&project_path));
+        assert!(!modes.iter().any(|(agent, _)| *agent == AgentType::Replit));
+    }
+
+    #[test]
+    fn test_stage_localized() {
+        let src = tempdir().unwrap();
+        fs::write(src.path().join("SKILL.md"), "# English").unwrap();
+        fs::write(src.path().join("SKILL.zh.md"), "# 中文").unwrap();
+        fs::write(src.path().join("SKILL.ja.md"), "# 日本語").unwrap();
+
+        let (locale, staging) = stage_localized(src.path(), "ZH").unwrap().unwrap();
+        assert_eq!(locale, "zh");
+        assert_eq!(fs::read_to_string(staging.path().join("SKILL.md")).unwrap(), "# 中文");
+        assert!(!staging.path().join("SKILL.zh.md").exists());
+        assert!(staging.path().join("SKILL.ja.md").exists());
+        // 源目录不变
+        assert_eq!(fs::read_to_string(src.path().join("SKILL.md")).unwrap(), "# English");
+
+        // 没有该本地化版本时使用原 SKILL.md
+        assert!(stage_localized(src.path(), "fr").unwrap().is_none());
+    }
+
+    #[test]
+    fn test_stage_and_replace_replaces_previous() {
+        let src = tempdir().unwrap();
+        fs::write(src.path().join("SKILL.md"), "# New").unwrap();
+
+        let temp = tempdir().unwrap();
+        let target = temp.path().join("test-dir");
+
+        // 首次安装
+        stage_and_replace(src.path(), &target).unwrap();
+        assert!(target.join("SKILL.md").exists());
+
+        // 添加旧文件
+        fs::write(target.join("file.txt"), "content").unwrap();
+
+        // 再次安装应替换整个目录
+        stage_and_replace(src.path(), &target).unwrap();
+        assert_eq!(fs::read_to_string(target.join("SKILL.md")).unwrap(), "# New");
+        assert!(!target.join("file.txt").exists());
+
+        // 不应残留暂存或备份目录
+        let entries: Vec<_> = fs::read_dir(temp.path()).unwrap().collect();
+        assert_eq!(entries.len(), 1);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_stage_and_replace_sets_dir_mode() {
+        use std::os::unix::fs::PermissionsExt;
+        let src = tempdir().unwrap();
+        fs::write(src.path().join("SKILL.md"), "# New").unwrap();
+        let temp = tempdir().unwrap();
+        let target = temp.path().join("test-dir");
+        let mode = |p: &Path| fs::metadata(p).unwrap().permissions().mode() & 0o777;
+
+        // 新安装为 0755，而不是暂存目录的 0700
+        stage_and_replace(src.path(), &target).unwrap();
+        assert_eq!(mode(&target), 0o755);
+
+        // 重新安装沿用原安装的权限
+        fs::set_permissions(&target, fs::Permissions::from_mode(0o750)).unwrap();
+        stage_and_replace(src.path(), &target).unwrap();
+        assert_eq!(mode(&target), 0o750);
+    }
+
+    #[test]
+    fn test_cleanup_stale_staging_dirs() {
+        let temp = tempdir().unwrap();
+        let staging = temp.path().join(format!("{}abc", STAGING_PREFIX));
+        let backup = temp.path().join(format!("{}def.backup", STAGING_PREFIX));
+        let skill = temp.path().join("my-skill");
+        for dir in [&staging, &backup, &skill] {
+            fs::create_dir_all(dir.join("nested")).unwrap();
+        }
+        let bases = vec![temp.path().to_path_buf(), temp.path().join("missing")];
+
+        // 未超过时限的暂存目录可能属于正在进行的安装
+        assert_eq!(cleanup_stale_staging_dirs(&bases, Duration::from_secs(3600)), 0);
+        assert!(staging.exists());
+
+        assert_eq!(cleanup_stale_staging_dirs(&bases, Duration::ZERO), 1);
+        assert!(!staging.exists());
+        assert!(backup.exists());
+        assert!(skill.exists());
+    }
+
+    #[cfg(windows)]
+    #[test]
+    fn test_stage_and_replace_long_paths() {
+        // 嵌套目录使目标文件路径超过 MAX_PATH（260）
+        let nested: PathBuf = (0..12).map(|i| format!("nested-directory-{:02}", i)).collect();
+        let src = tempdir().unwrap();
+        fs::create_dir_all(long_path(&src.path().join(&nested))).unwrap();
+        fs::write(src.path().join("SKILL.md"), "# Deep").unwrap();
+        fs::write(long_path(&src.path().join(&nested).join("reference.md")), "deep").unwrap();
+
+        let temp = tempdir().unwrap();
+        let target = temp.path().join(".agents").join("skills").join("deep-skill");
+        let nested_file = target.join(&nested).join("reference.md");
+        assert!(nested_file.to_string_lossy().len() > 260);
+
+        stage_and_replace(src.path(), &target).unwrap();
+        assert!(long_path(&nested_file).is_file());
+
+        // 再次安装（替换原安装并删除备份）同样成功
+        sync_skill_files(src.path(), &target).unwrap();
+        stage_and_replace(src.path(), &target).unwrap();
+        assert_eq!(fs::read_to_string(long_path(&nested_file)).unwrap(), "deep");
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_stage_and_replace_keeps_previous_on_copy_error() {
+        use std::os::unix::fs::symlink;
+
+        let temp = tempdir().unwrap();
+        let target = temp.path().join("my-skill");
+        fs::create_dir_all(&target).unwrap();
+        fs::write(target.join("SKILL.md"), "# Old").unwrap();
+
+        // 源目录中的悬空 symlink 会使复制中途失败
+        let src = tempdir().unwrap();
+        fs::write(src.path().join("SKILL.md"), "# New").unwrap();
+        fs::create_dir(src.path().join("scripts")).unwrap();
+        fs::write(src.path().join("scripts/helper.py"), "# Python").unwrap();
+        symlink(src.path().join("missing"), src.path().join("broken.md")).unwrap();
+
+        let result = stage_and_replace(src.path(), &target);
+        assert!(result.is_err());
+
+        // 原安装保持不变
+        assert_eq!(fs::read_to_string(target.join("SKILL.md")).unwrap(), "# Old");
+        assert!(!target.join("scripts").exists());
+
+        // 暂存目录已清理
+        let entries: Vec<_> = fs::read_dir(temp.path()).unwrap().collect();
+        assert_eq!(entries.len(), 1);
+    }
+
+    #[test]
+    fn test_can_symlink_cleans_up_probe() {
+        let temp = tempdir().unwrap();
+        let base = temp.path().join("skills");
+
+        #[cfg(unix)]
+        assert!(can_symlink(&base));
+        #[cfg(not(unix))]
+        let _ = can_symlink(&base);
+
+        // 不创建目标目录，探测目录已删除，且结果已缓存
+        assert!(!base.exists());
+        assert_eq!(fs::read_dir(temp.path()).unwrap().count(), 0);
+        assert!(SYMLINK_SUPPORT.lock().unwrap().contains_key(&resolve_path(&base)));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_create_symlink_through_symlinked_home() {
+        use std::os::unix::fs::symlink;
+
+        let temp = tempdir().unwrap();
+        let real_home = temp.path().join("real-home");
+        let linked_home = temp.path().join("home");
+        fs::create_dir_all(&real_home).unwrap();
+        symlink(&real_home, &linked_home).unwrap();
+
+        // canonical 目录经 symlink home 访问
+        let canonical = linked_home.join(".agents/skills/demo");
+        fs::create_dir_all(&canonical).unwrap();
+        fs::write(canonical.join("SKILL.md"), "# Demo").unwrap();
+
+        // agent 目录经真实路径访问且与 canonical 相同：不能删除 canonical
+        create_symlink(&canonical, &real_home.join(".agents/skills/demo")).unwrap();
+        assert!(!is_symlink_or_junction(&canonical));
+        assert_eq!(fs::read_to_string(canonical.join("SKILL.md")).unwrap(), "# Demo");
+
+        // 独立 agent 目录：symlink 可从两条路径访问到内容
+        let agent_dir = real_home.join(".claude/skills/demo");
+        create_symlink(&canonical, &agent_dir).unwrap();
+        assert!(is_symlink_or_junction(&agent_dir));
+        assert_eq!(fs::read_to_string(agent_dir.join("SKILL.md")).unwrap(), "# Demo");
+        assert_eq!(
+            fs::read_to_string(linked_home.join(".claude/skills/demo/SKILL.md")).unwrap(),
+            "# Demo"
+        );
+    }
+
+    #[test]
+    fn test_resolve_install_dir_name_suffix_on_collision() {
+        use crate::core::local_lock::{add_skill_to_local_lock, LocalSkillLockEntry};
+        use crate::core::skill::{list_installed_skills, SkillScope};
+        use crate::core::uninstaller::remove_skill;
+
+        let write_src = |name: &str| {
+            let src = tempdir().unwrap();
+            fs::write(
+                src.path().join("SKILL.md"),
+                format!("---\nname: {}\ndescription: d\n---\n", name),
+            )
+            .unwrap();
+            src
+        };
+        let project = tempdir().unwrap();
+        let cwd = project.path().to_string_lossy().to_string();
+        let agents = [AgentType::ClaudeCode];
+        let claimed = HashSet::new();
+
+        let existing = write_src("my-skill");
+        let result = install_skill_for_agent(
+            existing.path(),
+            "my-skill",
+            &AgentType::ClaudeCode,
+            &Scope::Project,
+            Some(&cwd),
+            &InstallMode::Symlink,
+        );
+        assert!(result.success, "{:?}", result.error);
+
+        // `My Skill!` 也 sanitize 为 my-skill：默认覆盖，Suffix 时追加后缀
+        let overwrite =
+            resolve_install_dir_name("My Skill!", &agents, false, &cwd, NameCollisionPolicy::Overwrite, &claimed);
+        assert_eq!(overwrite, "my-skill");
+        let dir_name =
+            resolve_install_dir_name("My Skill!", &agents, false, &cwd, NameCollisionPolicy::Suffix, &claimed);
+        assert_eq!(dir_name, "my-skill-2");
+        // 同名 skill 重新安装不算冲突
+        assert_eq!(
+            resolve_install_dir_name("my-skill", &agents, false, &cwd, NameCollisionPolicy::Suffix, &claimed),
+            "my-skill"
+        );
+
+        let incoming = write_src("My Skill!");
+        let result = install_skill_for_agent_in_dir(
+            incoming.path(),
+            "My Skill!",
+            &dir_name,
+            &AgentType::ClaudeCode,
+            &Scope::Project,
+            Some(&cwd),
+            &InstallMode::Symlink,
+        );
+        assert!(result.success, "{:?}", result.error);
+        add_skill_to_local_lock(
+            "My Skill!",
+            LocalSkillLockEntry {
+                source: "owner/repo".to_string(),
+                source_type: "github".to_string(),
+                dir_name: Some(dir_name.clone()),
+                ..Default::default()
+            },
+            &cwd,
+        )
+        .unwrap();
 
-        // 首次创建
-        clean_and_create_directory(&dir).unwrap();
-        assert!(dir.exists());
+        // 原 skill 未被覆盖，记录的目录名可被后续操作找到
+        let md = fs::read_to_string(project.path().join(".agents/skills/my-skill/SKILL.md")).unwrap();
+        assert!(md.contains("name: my-skill"));
+        assert_eq!(
+            agent_skill_dir("My Skill!", &AgentType::ClaudeCode, &Scope::Project, Some(&cwd)),
+            Some(project.path().join(".claude/skills/my-skill-2"))
+        );
+        assert_eq!(
+            resolve_install_dir_name("My Skill!", &agents, false, &cwd, NameCollisionPolicy::Suffix, &claimed),
+            "my-skill-2"
+        );
+        // 第三个同目录名的 skill 继续递增
+        assert_eq!(
+            resolve_install_dir_name("my skill", &agents, false, &cwd, NameCollisionPolicy::Suffix, &claimed),
+            "my-skill-3"
+        );
 
-        // 添加文件
-        fs::write(dir.join("file.txt"), "content").unwrap();
+        let mut names: Vec<String> = list_installed_skills(Some(SkillScope::Project), &cwd, false, false, None)
+            .unwrap()
+            .into_iter()
+            .map(|s| s.name)
+            .collect();
+        names.sort();
+        assert_eq!(names, vec!["My Skill!", "my-skill"]);
 
-        // 再次调用应该清理并重建
-        clean_and_create_directory(&dir).unwrap();
-        assert!(dir.exists());
-        assert!(!dir.join("file.txt").exists());
+        // universal agents 共用 canonical 目录，需一并移除才会删除它
+        let all_agents: Vec<AgentType> = AgentType::all().collect();
+        remove_skill("My Skill!", &Scope::Project, Some(&cwd), true, Some(&all_agents), false).unwrap();
+        assert!(!project.path().join(".agents/skills/my-skill-2").exists());
+        assert!(!project.path().join(".claude/skills/my-skill-2").exists());
+        assert!(project.path().join(".agents/skills/my-skill/SKILL.md").is_file());
+        assert!(project.path().join(".claude/skills/my-skill").exists());
     }
 }
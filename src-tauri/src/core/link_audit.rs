@@ -0,0 +1,153 @@
+//! Agent 链接检查模块
+//!
+//! canonical 目录在应用外被重命名或移动后，agent 目录中的 symlink / junction
+//! 可能仍指向旧路径。对 lock 中的每个 skill 和每个 agent：
+//! - 读取链接目标（Unix `read_link`；Windows junction 读取 reparse 目标）
+//! - 与期望的 canonical 目录（`canonical_skills_dir` + lock 记录的目录名）比较
+//!
+//! 直接读取 canonical 目录的 Universal agents、copy 模式的实体目录以及不存在的链接不做检查。
+
+use crate::core::agents::AgentType;
+use crate::core::local_lock::read_local_lock;
+use crate::core::paths::{agent_skills_dir, canonical_skills_dir, is_symlink_or_junction, resolve_path, same_path};
+use crate::core::skill::locked_dir_name;
+use crate::core::skill_lock::read_skill_lock;
+use crate::error::AppError;
+use crate::models::{LinkMismatch, Scope};
+use std::path::{Path, PathBuf};
+
+/// 列出目标与期望 canonical 目录不一致的 agent 链接
+///
+/// # Arguments
+/// * `scope` - 范围（Global/Project）
+/// * `project_path` - Project scope 时的项目路径
+///
+/// # Returns
+/// 按 skill 名称排序的不一致链接列表
+pub fn audit_links(scope: &Scope, project_path: Option<&str>) -> Result<Vec<LinkMismatch>, AppError> {
+    let is_global = matches!(scope, Scope::Global);
+    let cwd = project_path.unwrap_or(".");
+    let canonical_base = canonical_skills_dir(is_global, cwd);
+
+    let dir_names: Vec<(String, String)> = if is_global {
+        read_skill_lock()?
+            .skills
+            .into_iter()
+            .map(|(k, e)| {
+                let dir = locked_dir_name(&k, e.dir_name.as_deref());
+                (k, dir)
+            })
+            .collect()
+    } else {
+        read_local_lock(cwd)?
+            .skills
+            .into_iter()
+            .map(|(k, e)| {
+                let dir = locked_dir_name(&k, e.dir_name.as_deref());
+                (k, dir)
+            })
+            .collect()
+    };
+
+    let mut mismatches = Vec::new();
+    for (skill_name, dir_name) in &dir_names {
+        let expected = canonical_base.join(dir_name);
+        for agent in AgentType::all() {
+            let Some(base) = agent_skills_dir(&agent, is_global, cwd) else {
+                continue;
+            };
+            if same_path(&base, &canonical_base) {
+                continue;
+            }
+            let link_path = base.join(dir_name);
+            if !is_symlink_or_junction(&link_path) {
+                continue;
+            }
+
+            let actual = link_target(&link_path);
+            if actual.as_deref().is_some_and(|target| same_path(target, &expected)) {
+                continue;
+            }
+            mismatches.push(LinkMismatch {
+                skill_name: skill_name.clone(),
+                agent,
+                link_path: link_path.to_string_lossy().to_string(),
+                actual_target: actual.as_ref().map(|t| t.to_string_lossy().to_string()),
+                expected_target: expected.to_string_lossy().to_string(),
+                target_exists: actual.is_some_and(|t| t.exists()),
+            });
+        }
+    }
+
+    mismatches.sort_by_key(|m| m.skill_name.to_lowercase());
+    Ok(mismatches)
+}
+
+/// 读取 symlink / junction 的目标（相对目标按链接所在目录解析）
+fn link_target(link: &Path) -> Option<PathBuf> {
+    #[cfg(windows)]
+    {
+        if let Ok(target) = junction::get_target(link) {
+            return Some(resolve_path(&target));
+        }
+    }
+
+    let target = std::fs::read_link(link).ok()?;
+    let absolute = match link.parent() {
+        Some(parent) if target.is_relative() => parent.join(target),
+        _ => target,
+    };
+    Some(resolve_path(&absolute))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::local_lock::{write_local_lock, LocalSkillLockEntry, LocalSkillLockFile};
+    use std::fs;
+    use tempfile::tempdir;
+
+    #[cfg(unix)]
+    #[test]
+    fn test_audit_project_links() {
+        let project = tempdir().unwrap();
+        let cwd = project.path().to_string_lossy().to_string();
+        let skills = project.path().join(".agents/skills");
+        for name in ["good", "renamed"] {
+            fs::create_dir_all(skills.join(name)).unwrap();
+        }
+
+        // good 指向正确的 canonical 目录；stale 指向已在应用外改名的旧目录
+        let claude = project.path().join(".claude/skills");
+        fs::create_dir_all(&claude).unwrap();
+        std::os::unix::fs::symlink("../../.agents/skills/good", claude.join("good")).unwrap();
+        std::os::unix::fs::symlink(skills.join("renamed"), claude.join("stale")).unwrap();
+        // copy 模式的实体目录不检查
+        fs::create_dir_all(claude.join("copied")).unwrap();
+
+        let mut lock = LocalSkillLockFile::empty();
+        for name in ["good", "stale", "copied"] {
+            lock.skills.insert(name.to_string(), LocalSkillLockEntry::default());
+        }
+        write_local_lock(&lock, &cwd).unwrap();
+
+        let mismatches = audit_links(&Scope::Project, Some(&cwd)).unwrap();
+        assert_eq!(mismatches.len(), 1);
+        let stale = &mismatches[0];
+        assert_eq!(stale.skill_name, "stale");
+        assert_eq!(stale.agent, AgentType::ClaudeCode);
+        assert_eq!(
+            stale.actual_target.as_deref().map(PathBuf::from),
+            Some(skills.join("renamed").canonicalize().unwrap())
+        );
+        assert!(stale.expected_target.ends_with("stale"));
+        assert!(stale.target_exists);
+    }
+
+    #[test]
+    fn test_audit_without_lock() {
+        let project = tempdir().unwrap();
+        let cwd = project.path().to_string_lossy().to_string();
+        assert!(audit_links(&Scope::Project, Some(&cwd)).unwrap().is_empty());
+    }
+}
@@ -0,0 +1,288 @@
+//! 发布前检查本地 skill 目录
+//!
+//! 与安装时使用同一套规则，但把安装时静默处理的情况报告为诊断：
+//! - SKILL.md 缺失、frontmatter 无法解析或缺少必填字段（error）
+//! - name / description 超出 Agent Skills 规范的长度（warning）
+//! - name 经 `sanitize_name` 后与原值不同，安装目录名会改变（warning）
+//! - SKILL.md 正文引用了安装时会被排除的文件（warning），其余被排除的内容（info）
+//! - symlink 指向 skill 根目录之外（error，安装时会复制目标内容）或目标不存在（warning）
+
+use crate::core::installer::excluded_entries;
+use crate::core::paths::resolve_path;
+use crate::core::preview::skill_md_body;
+use crate::core::skill::{parse_skill_md_content, sanitize_name};
+use crate::error::AppError;
+use serde::Serialize;
+use specta::Type;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// name 的最大长度（Agent Skills 规范）
+const MAX_NAME_LENGTH: usize = 64;
+
+/// description 的最大长度（Agent Skills 规范）
+const MAX_DESCRIPTION_LENGTH: usize = 1024;
+
+/// 诊断级别
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Type)]
+#[serde(rename_all = "lowercase")]
+#[specta(rename_all = "lowercase")]
+pub enum LintSeverity {
+    Error,
+    Warning,
+    Info,
+}
+
+/// 单条诊断
+#[derive(Debug, Clone, Serialize, Type)]
+#[serde(rename_all = "camelCase")]
+#[specta(rename_all = "camelCase")]
+pub struct LintDiagnostic {
+    pub severity: LintSeverity,
+    /// 诊断标识（如 "invalidFrontmatter"、"symlinkEscapesRoot"）
+    pub code: String,
+    pub message: String,
+    /// 相关文件（相对 skill 根目录）
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub path: Option<String>,
+}
+
+impl LintDiagnostic {
+    fn new(severity: LintSeverity, code: &str, message: String, path: Option<&Path>) -> Self {
+        Self {
+            severity,
+            code: code.to_string(),
+            message,
+            path: path.map(display_relative),
+        }
+    }
+}
+
+/// 检查本地 skill 目录
+///
+/// # Arguments
+/// * `skill_dir` - skill 根目录（包含 SKILL.md）
+///
+/// # Returns
+/// 诊断列表（error 在前）；没有问题时为空
+pub fn lint_skill(skill_dir: &Path) -> Result<Vec<LintDiagnostic>, AppError> {
+    if !skill_dir.is_dir() {
+        return Err(AppError::PathNotFound {
+            path: skill_dir.to_string_lossy().to_string(),
+        });
+    }
+
+    let mut diagnostics = Vec::new();
+    let skill_md = Path::new("SKILL.md");
+    let body = match fs::read_to_string(skill_dir.join(skill_md)) {
+        Ok(content) => {
+            lint_frontmatter(&content, &mut diagnostics);
+            skill_md_body(&content).to_string()
+        }
+        Err(_) => {
+            diagnostics.push(LintDiagnostic::new(
+                LintSeverity::Error,
+                "missingSkillMd",
+                "SKILL.md not found in skill root".to_string(),
+                Some(skill_md),
+            ));
+            String::new()
+        }
+    };
+
+    let excluded = excluded_entries(skill_dir);
+    for path in &excluded {
+        let relative = display_relative(path);
+        if body.contains(&relative) {
+            diagnostics.push(LintDiagnostic::new(
+                LintSeverity::Warning,
+                "excludedReference",
+                format!("SKILL.md references {}, which is excluded from installation", relative),
+                Some(path),
+            ));
+        } else {
+            diagnostics.push(LintDiagnostic::new(
+                LintSeverity::Info,
+                "excluded",
+                format!("{} will not be installed", relative),
+                Some(path),
+            ));
+        }
+    }
+
+    lint_symlinks(skill_dir, &resolve_path(skill_dir), Path::new(""), &excluded, &mut diagnostics);
+
+    diagnostics.sort_by_key(|d| d.severity as u8);
+    Ok(diagnostics)
+}
+
+/// 检查 frontmatter 能否解析、字段长度和 name 的 sanitize 结果
+fn lint_frontmatter(content: &str, diagnostics: &mut Vec<LintDiagnostic>) {
+    let skill_md = Some(Path::new("SKILL.md"));
+    let frontmatter = match parse_skill_md_content(content) {
+        Ok(f) => f,
+        Err(e) => {
+            let message = match e {
+                AppError::InvalidSkillMd { message } => message,
+                AppError::SkillMdMissingField { field } => format!("Missing {} field", field),
+                other => other.to_string(),
+            };
+            diagnostics.push(LintDiagnostic::new(LintSeverity::Error, "invalidFrontmatter", message, skill_md));
+            return;
+        }
+    };
+
+    if frontmatter.name.chars().count() > MAX_NAME_LENGTH {
+        diagnostics.push(LintDiagnostic::new(
+            LintSeverity::Warning,
+            "nameTooLong",
+            format!("name is longer than {} characters", MAX_NAME_LENGTH),
+            skill_md,
+        ));
+    }
+    if frontmatter.description.chars().count() > MAX_DESCRIPTION_LENGTH {
+        diagnostics.push(LintDiagnostic::new(
+            LintSeverity::Warning,
+            "descriptionTooLong",
+            format!("description is longer than {} characters", MAX_DESCRIPTION_LENGTH),
+            skill_md,
+        ));
+    }
+
+    let sanitized = sanitize_name(&frontmatter.name);
+    if sanitized != frontmatter.name {
+        diagnostics.push(LintDiagnostic::new(
+            LintSeverity::Warning,
+            "nameSanitized",
+            format!("name '{}' will be installed as '{}'", frontmatter.name, sanitized),
+            skill_md,
+        ));
+    }
+}
+
+/// 递归检查 symlink（不跟随链接，跳过安装时排除的内容）
+fn lint_symlinks(
+    dir: &Path,
+    root: &Path,
+    relative: &Path,
+    excluded: &[PathBuf],
+    diagnostics: &mut Vec<LintDiagnostic>,
+) {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return;
+    };
+    for entry in entries.filter_map(|e| e.ok()) {
+        let rel_path = relative.join(entry.file_name());
+        if excluded.contains(&rel_path) {
+            continue;
+        }
+        let Ok(file_type) = entry.file_type() else {
+            continue;
+        };
+
+        if file_type.is_symlink() {
+            let Ok(target) = fs::read_link(entry.path()) else {
+                continue;
+            };
+            let target = resolve_path(&dir.join(target));
+            if !target.starts_with(root) {
+                diagnostics.push(LintDiagnostic::new(
+                    LintSeverity::Error,
+                    "symlinkEscapesRoot",
+                    format!("Symlink points outside the skill: {}", target.display()),
+                    Some(&rel_path),
+                ));
+            } else if !target.exists() {
+                diagnostics.push(LintDiagnostic::new(
+                    LintSeverity::Warning,
+                    "brokenSymlink",
+                    "Symlink target does not exist".to_string(),
+                    Some(&rel_path),
+                ));
+            }
+        } else if file_type.is_dir() {
+            lint_symlinks(&entry.path(), root, &rel_path, excluded, diagnostics);
+        }
+    }
+}
+
+/// 相对路径统一用 `/` 分隔（与 SKILL.md 中的引用写法一致）
+fn display_relative(path: &Path) -> String {
+    path.components()
+        .map(|c| c.as_os_str().to_string_lossy())
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    fn codes(diagnostics: &[LintDiagnostic]) -> Vec<&str> {
+        diagnostics.iter().map(|d| d.code.as_str()).collect()
+    }
+
+    #[test]
+    fn test_lint_clean_skill() {
+        let dir = tempdir().unwrap();
+        fs::write(dir.path().join("SKILL.md"), "---\nname: clean\ndescription: d\n---\nSee scripts/run.sh").unwrap();
+        fs::create_dir(dir.path().join("scripts")).unwrap();
+        fs::write(dir.path().join("scripts/run.sh"), "echo").unwrap();
+        assert!(lint_skill(dir.path()).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_lint_frontmatter_and_excludes() {
+        let dir = tempdir().unwrap();
+        fs::write(
+            dir.path().join("SKILL.md"),
+            "---\nname: My Skill\ndescription: d\n---\nRead _notes/guide.md first.",
+        )
+        .unwrap();
+        fs::create_dir(dir.path().join("_notes")).unwrap();
+        fs::write(dir.path().join("_notes/guide.md"), "guide").unwrap();
+        fs::write(dir.path().join("metadata.json"), "{}").unwrap();
+
+        let diagnostics = lint_skill(dir.path()).unwrap();
+        assert_eq!(codes(&diagnostics), vec!["nameSanitized", "excludedReference", "excluded"]);
+        assert!(diagnostics[0].message.contains("'my-skill'"));
+        assert_eq!(diagnostics[1].path.as_deref(), Some("_notes"));
+        assert_eq!(diagnostics[2].path.as_deref(), Some("metadata.json"));
+        assert_eq!(diagnostics[2].severity, LintSeverity::Info);
+    }
+
+    #[test]
+    fn test_lint_invalid_frontmatter() {
+        let dir = tempdir().unwrap();
+        fs::write(dir.path().join("SKILL.md"), "---\nname: demo\n---\n").unwrap();
+        let diagnostics = lint_skill(dir.path()).unwrap();
+        assert_eq!(codes(&diagnostics), vec!["invalidFrontmatter"]);
+        assert_eq!(diagnostics[0].message, "Missing description field");
+
+        let empty = tempdir().unwrap();
+        assert_eq!(codes(&lint_skill(empty.path()).unwrap()), vec!["missingSkillMd"]);
+        assert!(lint_skill(&empty.path().join("missing")).is_err());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_lint_symlinks() {
+        let outside = tempdir().unwrap();
+        fs::write(outside.path().join("secret.txt"), "secret").unwrap();
+
+        let dir = tempdir().unwrap();
+        fs::write(dir.path().join("SKILL.md"), "---\nname: links\ndescription: d\n---\n").unwrap();
+        fs::create_dir(dir.path().join("docs")).unwrap();
+        fs::write(dir.path().join("docs/a.md"), "a").unwrap();
+        std::os::unix::fs::symlink("a.md", dir.path().join("docs/inside.md")).unwrap();
+        std::os::unix::fs::symlink(outside.path().join("secret.txt"), dir.path().join("docs/escape.txt")).unwrap();
+        std::os::unix::fs::symlink("missing.md", dir.path().join("docs/broken.md")).unwrap();
+
+        let mut diagnostics = lint_skill(dir.path()).unwrap();
+        diagnostics.sort_by(|a, b| a.path.cmp(&b.path));
+        assert_eq!(codes(&diagnostics), vec!["brokenSymlink", "symlinkEscapesRoot"]);
+        assert_eq!(diagnostics[0].path.as_deref(), Some("docs/broken.md"));
+        assert_eq!(diagnostics[1].severity, LintSeverity::Error);
+    }
+}
@@ -7,10 +7,11 @@
 //! - BTreeMap 按 key 排序，最小化 git diff
 //! - GUI 扩展字段 remote_hash 用于更新检测
 
+use crate::core::atomic_write::with_locked_file;
 use crate::error::AppError;
 use serde::{Deserialize, Serialize};
 use sha2::{Digest, Sha256};
-use std::collections::BTreeMap;
+use std::collections::{btree_map, BTreeMap};
 use std::fs;
 use std::path::{Path, PathBuf};
 
@@ -21,13 +22,17 @@ const LOCAL_LOCK_VERSION: u32 = 1;
 /// Local lock 文件名
 const LOCAL_LOCK_FILENAME: &str = "skills-lock.json";
 
+/// 计算哈希时跳过的目录
+/// 对应 CLI: collectFiles (local-lock.ts) 中的跳过条件
+pub const HASH_SKIP_DIRS: &[&str] = &[".git", "node_modules"];
+
 /// 旧版项目级 lock 路径（向后兼容读取）
 const LEGACY_PROJECT_LOCK_PATH: &str = ".agents/.skill-lock.json";
 
 /// Local Skill Lock 条目
 /// 对应 CLI: LocalSkillLockEntry (local-lock.ts:8-12)
 /// GUI 扩展了 remote_hash 和 skill_path 字段（CLI 会忽略未知字段）
-#[derive(Debug, Clone, Deserialize, Serialize)]
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct LocalSkillLockEntry {
     /// 来源标识符 (owner/repo, npm 包名, 本地路径)
@@ -51,6 +56,69 @@ pub struct LocalSkillLockEntry {
     /// 对应 CLI: SkillLockEntry.pluginName
     #[serde(skip_serializing_if = "Option::is_none")]
     pub plugin_name: Option<String>,
+
+    /// GUI 扩展字段：安装时 SKILL.md 中的 version
+    /// CLI 会忽略此字段
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub version: Option<String>,
+
+    /// GUI 扩展字段：首次安装时间 (ISO 格式)，重新安装时保留
+    /// CLI 会忽略此字段
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub installed_at: Option<String>,
+
+    /// GUI 扩展字段：最近一次安装/更新时间 (ISO 格式)
+    /// CLI 会忽略此字段
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub updated_at: Option<String>,
+
+    /// GUI 扩展字段：安装目录名，仅在与 `sanitize_name(name)` 不同时记录
+    /// （与其他 skill 的目录冲突时追加了数字后缀）
+    /// CLI 会忽略此字段
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub dir_name: Option<String>,
+
+    /// GUI 扩展字段：安装的本地化版本（`SKILL.<locale>.md` 的 locale），更新时沿用
+    /// CLI 会忽略此字段
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub locale: Option<String>,
+
+    /// GUI 扩展字段：已脱离 canonical 目录的 agents（见 detach_skill），
+    /// 更新和修复时不写入这些 agents 的独立副本
+    /// CLI 会忽略此字段
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub detached_agents: Vec<String>,
+
+    /// GUI 扩展字段：仓库 URL，仅在无法从 source 还原时记录
+    /// （如自建 GitLab 实例）
+    /// CLI 会忽略此字段
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub source_url: Option<String>,
+}
+
+impl LocalSkillLockEntry {
+    /// 来源 URL：优先使用记录的 source_url，旧条目从 source 构造
+    pub fn source_url(&self) -> String {
+        self.source_url
+            .clone()
+            .unwrap_or_else(|| default_source_url(&self.source, &self.source_type))
+    }
+}
+
+/// 从 source 构造来源 URL（local lock 的 CLI 字段中没有 source_url）
+fn default_source_url(source: &str, source_type: &str) -> String {
+    match source_type {
+        "github" => format!("https://github.com/{}", source),
+        "gitlab" => format!("https://gitlab.com/{}", source),
+        _ => source.to_string(),
+    }
+}
+
+/// 需要写入 `LocalSkillLockEntry.source_url` 的值：
+/// 仅在与从 source 构造的 URL 不同时记录
+pub fn recorded_source_url(source: &str, source_type: &str, source_url: &str) -> Option<String> {
+    (!source_url.is_empty() && source_url != default_source_url(source, source_type))
+        .then(|| source_url.to_string())
 }
 
 /// Local Skill Lock 文件
@@ -73,7 +141,7 @@ impl LocalSkillLockFile {
 
 /// 获取项目级 lock 文件路径
 /// 优先使用新格式 skills-lock.json
-fn get_local_lock_path(project_path: &str) -> PathBuf {
+pub fn get_local_lock_path(project_path: &str) -> PathBuf {
     PathBuf::from(project_path).join(LOCAL_LOCK_FILENAME)
 }
 
@@ -92,10 +160,7 @@ pub fn read_local_lock(project_path: &str) -> Result<LocalSkillLockFile, AppErro
     // 优先读新格式
     if new_path.exists() {
         let content = fs::read_to_string(&new_path)?;
-        return match serde_json::from_str::<LocalSkillLockFile>(&content) {
-            Ok(lock) if lock.version >= LOCAL_LOCK_VERSION => Ok(lock),
-            _ => Ok(LocalSkillLockFile::empty()),
-        };
+        return Ok(parse_local_lock(&content));
     }
 
     // 回退读旧格式并转换
@@ -107,6 +172,14 @@ pub fn read_local_lock(project_path: &str) -> Result<LocalSkillLockFile, AppErro
     Ok(LocalSkillLockFile::empty())
 }
 
+/// 解析 skills-lock.json 内容（无法解析或版本过旧时返回空 lock）
+fn parse_local_lock(content: &str) -> LocalSkillLockFile {
+    match serde_json::from_str::<LocalSkillLockFile>(content) {
+        Ok(lock) if lock.version >= LOCAL_LOCK_VERSION => lock,
+        _ => LocalSkillLockFile::empty(),
+    }
+}
+
 /// 读取旧版 lock 文件并转换为新格式
 /// 旧版使用 SkillLockFile 格式（GitHub tree SHA），需要转换
 fn read_and_convert_legacy_lock(path: &Path) -> Result<LocalSkillLockFile, AppError> {
@@ -118,8 +191,14 @@ fn read_and_convert_legacy_lock(path: &Path) -> Result<LocalSkillLockFile, AppEr
         Err(_) => return Ok(LocalSkillLockFile::empty()),
     };
 
+    Ok(convert_legacy_lock(old_lock))
+}
+
+/// 将旧版 SkillLockFile 转换为新格式
+fn convert_legacy_lock(old_lock: crate::core::skill_lock::SkillLockFile) -> LocalSkillLockFile {
     let mut new_lock = LocalSkillLockFile::empty();
     for (name, entry) in old_lock.skills {
+        let source_url = recorded_source_url(&entry.source, &entry.source_type, &entry.source_url);
         new_lock.skills.insert(
             name,
             LocalSkillLockEntry {
@@ -133,11 +212,88 @@ fn read_and_convert_legacy_lock(path: &Path) -> Result<LocalSkillLockFile, AppEr
                 },
                 skill_path: entry.skill_path,
                 plugin_name: entry.plugin_name,
+                version: entry.version,
+                installed_at: Some(entry.installed_at),
+                updated_at: Some(entry.updated_at),
+                dir_name: entry.dir_name,
+                locale: entry.locale,
+                detached_agents: entry.detached_agents,
+                source_url,
             },
         );
     }
 
-    Ok(new_lock)
+    new_lock
+}
+
+/// 旧版 lock 迁移结果
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LegacyLockMigration {
+    /// 写入 skills-lock.json 的条目数
+    pub migrated: usize,
+    /// 是否已删除旧版 lock 文件
+    pub legacy_deleted: bool,
+}
+
+/// 将旧版 `.agents/.skill-lock.json` 迁移为 `skills-lock.json`
+///
+/// - 旧版文件不存在时不做任何修改
+/// - skills-lock.json 已存在时只补充其中缺少的条目，已有条目保持不变
+/// - 仅在新文件写入成功后才删除旧版文件（`delete_legacy`）
+/// - 旧版文件无法解析时返回错误，不会写入或删除任何文件
+pub fn migrate_legacy_lock(
+    project_path: &str,
+    delete_legacy: bool,
+) -> Result<LegacyLockMigration, AppError> {
+    use crate::core::skill_lock::SkillLockFile;
+
+    let legacy_path = get_legacy_lock_path(project_path);
+    if !legacy_path.exists() {
+        return Ok(LegacyLockMigration {
+            migrated: 0,
+            legacy_deleted: false,
+        });
+    }
+
+    let content = fs::read_to_string(&legacy_path)?;
+    let legacy: SkillLockFile = serde_json::from_str(&content)?;
+
+    // 持有 skills-lock.json 的文件锁合并，不覆盖同时进行的安装 / 更新写入的条目。
+    // 不用 update_local_lock：新文件不存在时它会读取旧版 lock，合并结果相同而不会写入
+    let migrated = with_locked_file(&get_local_lock_path(project_path), |content| {
+        let exists = content.is_some();
+        let mut lock = content
+            .as_deref()
+            .map(parse_local_lock)
+            .unwrap_or_else(LocalSkillLockFile::empty);
+
+        let mut migrated = 0;
+        for (name, entry) in convert_legacy_lock(legacy).skills {
+            if let btree_map::Entry::Vacant(slot) = lock.skills.entry(name) {
+                slot.insert(entry);
+                migrated += 1;
+            }
+        }
+
+        let content = if migrated > 0 || !exists {
+            Some(serde_json::to_string_pretty(&lock)? + "\n")
+        } else {
+            None
+        };
+        Ok((content, migrated))
+    })?;
+
+    let legacy_deleted = if delete_legacy {
+        fs::remove_file(&legacy_path)?;
+        true
+    } else {
+        false
+    };
+
+    Ok(LegacyLockMigration {
+        migrated,
+        legacy_deleted,
+    })
 }
 
 /// 写入项目级 lock 文件
@@ -145,29 +301,61 @@ fn read_and_convert_legacy_lock(path: &Path) -> Result<LocalSkillLockFile, AppEr
 ///
 /// - BTreeMap 自动按 key 排序
 /// - 尾部添加换行符
+/// - 原子写入（临时文件 + rename），并发写入通过文件锁串行化
+///
+/// 只用于测试准备 lock 文件：整体覆盖会丢失读取之后其他写入者的修改，
+/// 其余代码通过 `update_local_lock` 在文件锁内读-改-写
+#[cfg(test)]
 pub fn write_local_lock(
     lock: &LocalSkillLockFile,
     project_path: &str,
 ) -> Result<(), AppError> {
     let lock_path = get_local_lock_path(project_path);
-    if let Some(parent) = lock_path.parent() {
-        fs::create_dir_all(parent)?;
-    }
     let content = serde_json::to_string_pretty(lock)? + "\n";
-    fs::write(&lock_path, content)?;
+    crate::core::atomic_write::write_atomic(&lock_path, content.as_bytes())?;
     Ok(())
 }
 
+/// 在文件锁内读取、修改并写回项目级 lock 文件
+///
+/// 读取到写回期间持有文件锁，同时进行的安装 / 更新不会覆盖彼此的条目。
+/// skills-lock.json 不存在时从旧版 lock 读取；`update` 没有修改 lock 时不写入
+pub fn update_local_lock<T>(
+    project_path: &str,
+    update: impl FnOnce(&mut LocalSkillLockFile) -> T,
+) -> Result<T, AppError> {
+    with_locked_file(&get_local_lock_path(project_path), |content| {
+        let mut lock = match content {
+            Some(content) => parse_local_lock(&content),
+            None => read_local_lock(project_path)?,
+        };
+        let before = serde_json::to_string_pretty(&lock)? + "\n";
+        let value = update(&mut lock);
+        let after = serde_json::to_string_pretty(&lock)? + "\n";
+        Ok(((after != before).then_some(after), value))
+    })
+}
+
 /// 添加 skill 到项目级 lock 文件
 /// 对应 CLI: addSkillToLocalLock (local-lock.ts:55-68)
 pub fn add_skill_to_local_lock(
     skill_name: &str,
-    entry: LocalSkillLockEntry,
+    mut entry: LocalSkillLockEntry,
     project_path: &str,
 ) -> Result<(), AppError> {
-    let mut lock = read_local_lock(project_path)?;
-    lock.skills.insert(skill_name.to_string(), entry);
-    write_local_lock(&lock, project_path)
+    let now = chrono::Utc::now().format("%Y-%m-%dT%H:%M:%S%.3fZ").to_string();
+
+    update_local_lock(project_path, |lock| {
+        // 保留原有的 installed_at（与全局 lock 一致）
+        entry.installed_at = lock
+            .skills
+            .get(skill_name)
+            .and_then(|e| e.installed_at.clone())
+            .or_else(|| Some(now.clone()));
+        entry.updated_at = Some(now);
+
+        lock.skills.insert(skill_name.to_string(), entry);
+    })
 }
 
 /// 从项目级 lock 文件移除 skill
@@ -176,12 +364,7 @@ pub fn remove_skill_from_local_lock(
     skill_name: &str,
     project_path: &str,
 ) -> Result<bool, AppError> {
-    let mut lock = read_local_lock(project_path)?;
-    if lock.skills.remove(skill_name).is_none() {
-        return Ok(false);
-    }
-    write_local_lock(&lock, project_path)?;
-    Ok(true)
+    update_local_lock(project_path, |lock| lock.skills.remove(skill_name).is_some())
 }
 
 /// 计算 skill 文件夹的 SHA-256 哈希
@@ -194,7 +377,11 @@ pub fn remove_skill_from_local_lock(
 pub fn compute_skill_folder_hash(skill_dir: &Path) -> Result<String, AppError> {
     let mut files: Vec<(String, Vec<u8>)> = Vec::new();
     collect_files(skill_dir, skill_dir, &mut files)?;
+    Ok(hash_skill_files(files))
+}
 
+/// 按 `compute_skill_folder_hash` 的算法对（相对路径, 文件内容）列表计算哈希
+pub(crate) fn hash_skill_files(mut files: Vec<(String, Vec<u8>)>) -> String {
     // 按相对路径排序确保确定性
     files.sort_by(|a, b| a.0.cmp(&b.0));
 
@@ -204,7 +391,7 @@ pub fn compute_skill_folder_hash(skill_dir: &Path) -> Result<String, AppError> {
         hasher.update(content);
     }
 
-    Ok(format!("{:x}", hasher.finalize()))
+    format!("{:x}", hasher.finalize())
 }
 
 /// 递归收集目录下所有文件
@@ -222,7 +409,7 @@ fn collect_files(
         let file_name = entry.file_name().to_string_lossy().to_string();
 
         // 跳过 .git 和 node_modules
-        if file_name == ".git" || file_name == "node_modules" {
+        if HASH_SKIP_DIRS.contains(&file_name.as_str()) {
             continue;
         }
 
@@ -264,9 +451,7 @@ mod tests {
                 source: "owner/z".to_string(),
                 source_type: "github".to_string(),
                 computed_hash: "hash-z".to_string(),
-                remote_hash: None,
-                skill_path: None,
-                plugin_name: None,
+                ..Default::default()
             },
         );
         lock.skills.insert(
@@ -275,9 +460,7 @@ mod tests {
                 source: "owner/a".to_string(),
                 source_type: "github".to_string(),
                 computed_hash: "hash-a".to_string(),
-                remote_hash: None,
-                skill_path: None,
-                plugin_name: None,
+                ..Default::default()
             },
         );
 
@@ -293,9 +476,7 @@ mod tests {
             source: "owner/repo".to_string(),
             source_type: "github".to_string(),
             computed_hash: "abc123".to_string(),
-            remote_hash: None,
-            skill_path: None,
-            plugin_name: None,
+            ..Default::default()
         };
         let json = serde_json::to_string(&entry).unwrap();
         assert!(!json.contains("remoteHash"), "None remote_hash should not be serialized");
@@ -311,6 +492,30 @@ mod tests {
         assert!(json.contains("skillPath"), "Some skill_path should be serialized");
     }
 
+    #[test]
+    fn test_source_url_prefers_recorded_url() {
+        let url = "https://code.mycorp.io:8443/group/repo";
+        let recorded = recorded_source_url("group/repo", "gitlab", url);
+        assert_eq!(recorded.as_deref(), Some(url));
+        // 与从 source 构造的 URL 相同时不记录
+        assert!(recorded_source_url("group/repo", "gitlab", "https://gitlab.com/group/repo").is_none());
+        assert!(recorded_source_url("owner/repo", "github", "https://github.com/owner/repo").is_none());
+
+        let entry = LocalSkillLockEntry {
+            source: "group/repo".to_string(),
+            source_type: "gitlab".to_string(),
+            source_url: recorded,
+            ..Default::default()
+        };
+        assert_eq!(entry.source_url(), url);
+        let json = serde_json::to_string(&entry).unwrap();
+        assert!(json.contains(r#""sourceUrl":"https://code.mycorp.io:8443/group/repo""#));
+
+        // 旧条目没有 sourceUrl，回退为从 source 构造
+        let legacy = LocalSkillLockEntry { source_url: None, ..entry };
+        assert_eq!(legacy.source_url(), "https://gitlab.com/group/repo");
+    }
+
     #[test]
     fn test_compute_skill_folder_hash() {
         let temp = tempdir().unwrap();
@@ -358,7 +563,7 @@ mod tests {
                 computed_hash: "abc123".to_string(),
                 remote_hash: Some("tree-sha".to_string()),
                 skill_path: Some("skills/test/SKILL.md".to_string()),
-                plugin_name: None,
+                ..Default::default()
             },
         );
 
@@ -382,6 +587,112 @@ mod tests {
         );
     }
 
+    fn write_legacy_lock(project_path: &str, skills: &[&str]) {
+        let entries: Vec<String> = skills
+            .iter()
+            .map(|name| {
+                format!(
+                    r#""{}": {{"source": "owner/repo", "sourceType": "github", "sourceUrl": "https://github.com/owner/repo.git", "skillFolderHash": "tree-{}", "installedAt": "", "updatedAt": ""}}"#,
+                    name, name
+                )
+            })
+            .collect();
+        let legacy_path = get_legacy_lock_path(project_path);
+        fs::create_dir_all(legacy_path.parent().unwrap()).unwrap();
+        fs::write(
+            &legacy_path,
+            format!(r#"{{"version": 3, "skills": {{{}}}}}"#, entries.join(",")),
+        )
+        .unwrap();
+    }
+
+    #[test]
+    fn test_migrate_legacy_lock() {
+        let temp = tempdir().unwrap();
+        let project_path = temp.path().to_string_lossy().to_string();
+        write_legacy_lock(&project_path, &["alpha", "beta"]);
+
+        let result = migrate_legacy_lock(&project_path, false).unwrap();
+        assert_eq!(result.migrated, 2);
+        assert!(!result.legacy_deleted);
+        assert!(get_legacy_lock_path(&project_path).exists());
+
+        let lock = read_local_lock(&project_path).unwrap();
+        assert!(get_local_lock_path(&project_path).exists());
+        assert_eq!(lock.skills["alpha"].remote_hash, Some("tree-alpha".to_string()));
+
+        // 再次迁移不会重复写入，删除旧文件
+        let result = migrate_legacy_lock(&project_path, true).unwrap();
+        assert_eq!(result.migrated, 0);
+        assert!(result.legacy_deleted);
+        assert!(!get_legacy_lock_path(&project_path).exists());
+        assert_eq!(read_local_lock(&project_path).unwrap().skills.len(), 2);
+    }
+
+    #[test]
+    fn test_migrate_legacy_lock_keeps_existing_entries() {
+        let temp = tempdir().unwrap();
+        let project_path = temp.path().to_string_lossy().to_string();
+        write_legacy_lock(&project_path, &["alpha", "beta"]);
+
+        // 直接写入新格式：add_skill_to_local_lock 会回退读旧格式，把 beta 一并写入
+        let mut existing = LocalSkillLockFile::empty();
+        existing.skills.insert(
+            "alpha".to_string(),
+            LocalSkillLockEntry {
+                source: "other/repo".to_string(),
+                source_type: "github".to_string(),
+                computed_hash: "hash1".to_string(),
+                ..Default::default()
+            },
+        );
+        write_local_lock(&existing, &project_path).unwrap();
+
+        let result = migrate_legacy_lock(&project_path, false).unwrap();
+        assert_eq!(result.migrated, 1);
+
+        let lock = read_local_lock(&project_path).unwrap();
+        assert_eq!(lock.skills["alpha"].source, "other/repo");
+        assert_eq!(lock.skills["beta"].source, "owner/repo");
+    }
+
+    #[test]
+    fn test_migrate_legacy_lock_invalid_keeps_files() {
+        let temp = tempdir().unwrap();
+        let project_path = temp.path().to_string_lossy().to_string();
+        let legacy_path = get_legacy_lock_path(&project_path);
+        fs::create_dir_all(legacy_path.parent().unwrap()).unwrap();
+        fs::write(&legacy_path, "not json").unwrap();
+
+        assert!(migrate_legacy_lock(&project_path, true).is_err());
+        assert!(legacy_path.exists());
+        assert!(!get_local_lock_path(&project_path).exists());
+    }
+
+    #[test]
+    fn test_reinstall_keeps_installed_at() {
+        let temp = tempdir().unwrap();
+        let project_path = temp.path().to_string_lossy().to_string();
+        let entry = |hash: &str| LocalSkillLockEntry {
+            source: "owner/repo".to_string(),
+            source_type: "github".to_string(),
+            computed_hash: hash.to_string(),
+            ..Default::default()
+        };
+
+        add_skill_to_local_lock("my-skill", entry("hash1"), &project_path).unwrap();
+        let first = read_local_lock(&project_path).unwrap().skills["my-skill"].clone();
+        assert!(first.installed_at.is_some());
+        assert_eq!(first.installed_at, first.updated_at);
+
+        std::thread::sleep(std::time::Duration::from_millis(5));
+        add_skill_to_local_lock("my-skill", entry("hash2"), &project_path).unwrap();
+        let second = read_local_lock(&project_path).unwrap().skills["my-skill"].clone();
+        assert_eq!(second.computed_hash, "hash2");
+        assert_eq!(second.installed_at, first.installed_at);
+        assert_ne!(second.updated_at, first.updated_at);
+    }
+
     #[test]
     fn test_add_remove_local_lock() {
         let temp = tempdir().unwrap();
@@ -394,9 +705,7 @@ mod tests {
                 source: "owner/repo".to_string(),
                 source_type: "github".to_string(),
                 computed_hash: "hash1".to_string(),
-                remote_hash: None,
-                skill_path: None,
-                plugin_name: None,
+                ..Default::default()
             },
             &project_path,
         )
@@ -0,0 +1,177 @@
+//! 本地来源模块
+//!
+//! 功能：
+//! - 识别本地来源的形态：目录、git bundle（`.bundle`）、压缩包（`.tar.gz` / `.tgz` / `.zip`）
+//! - 解压压缩包到临时目录
+//!
+//! git bundle 由调用方交给 `clone_repo_with_progress` 克隆（`git clone` 直接支持 bundle 文件）。
+//! 解压与克隆一样调用外部命令：优先 `tar`（Windows 10+ 与 macOS 自带的 bsdtar 也能解 zip），
+//! zip 解压失败时回退到 `unzip`。tar / unzip 默认拒绝解出 `..` 和绝对路径成员。
+
+use crate::error::AppError;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use tempfile::TempDir;
+
+/// 本地来源的形态
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LocalSourceKind {
+    /// 普通目录（直接扫描）
+    Directory,
+    /// git bundle 文件（`git clone <bundle>`）
+    GitBundle,
+    /// tar.gz / zip 压缩包
+    Archive(ArchiveFormat),
+}
+
+/// 支持的压缩包格式
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ArchiveFormat {
+    TarGz,
+    Zip,
+}
+
+/// 解压结果
+pub struct ExtractResult {
+    /// 临时目录（drop 时自动清理）
+    pub temp_dir: TempDir,
+    /// skills 根目录：压缩包只有一个顶层目录时为该目录，否则为临时目录本身
+    pub root: PathBuf,
+}
+
+/// 根据路径判断本地来源形态
+///
+/// 只有存在的文件才按扩展名识别，其他情况（包括不存在的路径）按目录处理，
+/// 由后续扫描报告路径错误。
+pub fn local_source_kind(path: &Path) -> LocalSourceKind {
+    if !path.is_file() {
+        return LocalSourceKind::Directory;
+    }
+
+    let name = path
+        .file_name()
+        .map(|n| n.to_string_lossy().to_lowercase())
+        .unwrap_or_default();
+    if name.ends_with(".bundle") {
+        LocalSourceKind::GitBundle
+    } else if name.ends_with(".tar.gz") || name.ends_with(".tgz") {
+        LocalSourceKind::Archive(ArchiveFormat::TarGz)
+    } else if name.ends_with(".zip") {
+        LocalSourceKind::Archive(ArchiveFormat::Zip)
+    } else {
+        LocalSourceKind::Directory
+    }
+}
+
+/// 解压压缩包到临时目录
+pub fn extract_archive(archive: &Path, format: ArchiveFormat) -> Result<ExtractResult, AppError> {
+    let temp_dir = TempDir::new()?;
+    let dest = temp_dir.path();
+
+    let mut tar = Command::new("tar");
+    tar.arg("-xf").arg(archive).arg("-C").arg(dest);
+    let mut result = run_extract(&mut tar);
+
+    // GNU tar 不支持 zip
+    if result.is_err() && format == ArchiveFormat::Zip {
+        let mut unzip = Command::new("unzip");
+        unzip.arg("-q").arg("-o").arg(archive).arg("-d").arg(dest);
+        result = run_extract(&mut unzip);
+    }
+
+    if let Err(message) = result {
+        return Err(AppError::InvalidSource {
+            value: format!("Failed to extract {}: {}", archive.display(), message),
+        });
+    }
+
+    let root = single_top_level_dir(dest).unwrap_or_else(|| dest.to_path_buf());
+    Ok(ExtractResult { temp_dir, root })
+}
+
+/// 执行解压命令，失败时返回 stderr
+fn run_extract(cmd: &mut Command) -> Result<(), String> {
+    let output = cmd.output().map_err(|e| e.to_string())?;
+    if output.status.success() {
+        Ok(())
+    } else {
+        Err(String::from_utf8_lossy(&output.stderr).trim().to_string())
+    }
+}
+
+/// 目录下只有一个子目录（常见的 `repo-main/` 包装）时返回该目录
+fn single_top_level_dir(dir: &Path) -> Option<PathBuf> {
+    let mut entries = fs::read_dir(dir).ok()?.filter_map(|e| e.ok());
+    let first = entries.next()?;
+    if entries.next().is_some() || !first.file_type().ok()?.is_dir() {
+        return None;
+    }
+    Some(first.path())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_local_source_kind() {
+        let dir = tempdir().unwrap();
+        for name in ["skills.bundle", "skills.tar.gz", "skills.TGZ", "skills.zip", "notes.txt"] {
+            fs::write(dir.path().join(name), "").unwrap();
+        }
+
+        assert_eq!(local_source_kind(dir.path()), LocalSourceKind::Directory);
+        assert_eq!(local_source_kind(&dir.path().join("skills.bundle")), LocalSourceKind::GitBundle);
+        assert_eq!(
+            local_source_kind(&dir.path().join("skills.tar.gz")),
+            LocalSourceKind::Archive(ArchiveFormat::TarGz)
+        );
+        assert_eq!(
+            local_source_kind(&dir.path().join("skills.TGZ")),
+            LocalSourceKind::Archive(ArchiveFormat::TarGz)
+        );
+        assert_eq!(
+            local_source_kind(&dir.path().join("skills.zip")),
+            LocalSourceKind::Archive(ArchiveFormat::Zip)
+        );
+        assert_eq!(local_source_kind(&dir.path().join("notes.txt")), LocalSourceKind::Directory);
+        // 不存在的 .zip 路径按目录处理
+        assert_eq!(local_source_kind(&dir.path().join("missing.zip")), LocalSourceKind::Directory);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_extract_tar_gz_unwraps_single_top_level_dir() {
+        let src = tempdir().unwrap();
+        let skill_dir = src.path().join("repo-main/skills/demo");
+        fs::create_dir_all(&skill_dir).unwrap();
+        fs::write(skill_dir.join("SKILL.md"), "---\nname: demo\ndescription: Demo\n---\n").unwrap();
+
+        let archive = src.path().join("repo.tar.gz");
+        let status = Command::new("tar")
+            .arg("-czf")
+            .arg(&archive)
+            .arg("-C")
+            .arg(src.path())
+            .arg("repo-main")
+            .status()
+            .unwrap();
+        assert!(status.success());
+
+        let extracted = extract_archive(&archive, ArchiveFormat::TarGz).unwrap();
+        assert!(extracted.root.ends_with("repo-main"));
+        assert!(extracted.root.join("skills/demo/SKILL.md").is_file());
+    }
+
+    #[test]
+    fn test_extract_invalid_archive() {
+        let dir = tempdir().unwrap();
+        let archive = dir.path().join("broken.tar.gz");
+        fs::write(&archive, "not an archive").unwrap();
+
+        let err = extract_archive(&archive, ArchiveFormat::TarGz).err().unwrap();
+        assert!(matches!(err, AppError::InvalidSource { .. }));
+    }
+}
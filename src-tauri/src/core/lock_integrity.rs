@@ -0,0 +1,236 @@
+//! Lock 文件完整性检查模块
+//!
+//! read_skill_lock / read_local_lock 在 JSON 解析失败时静默返回空 lock，
+//! 一个损坏的字符就会让所有已安装 skill 从 lock 中“消失”（文件仍在磁盘上）。
+//!
+//! 功能：
+//! - 检查指定 scope 的 lock 文件能否解析（文件不存在视为正常）
+//! - 解析失败时先备份为 `<lock>.bak`，再根据 canonical 目录中的 SKILL.md 重建 lock
+//!
+//! 原来源信息无法恢复：重建的条目标记为 `local` 来源并指向 canonical 目录，
+//! 需要重新从原来源安装才能恢复更新检测。
+
+use crate::core::atomic_write::with_locked_file;
+use crate::core::local_lock::{
+    compute_skill_folder_hash, get_local_lock_path, LocalSkillLockEntry, LocalSkillLockFile,
+};
+use crate::core::paths::canonical_skills_dir;
+use crate::core::skill::{parse_skill_md, SkillFrontmatter};
+use crate::core::skill_lock::{get_skill_lock_path, SkillLockEntry, SkillLockFile};
+use crate::error::AppError;
+use crate::models::Scope;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// 完整性检查结果
+#[derive(Debug, Clone)]
+pub struct LockIntegrity {
+    /// 检查的 lock 文件路径
+    pub lock_path: PathBuf,
+    /// JSON 解析错误（None 表示 lock 正常或不存在）
+    pub parse_error: Option<String>,
+    /// 损坏文件的备份路径
+    pub backup_path: Option<PathBuf>,
+    /// 重建 lock 时恢复的 skill 名称
+    pub recovered: Vec<String>,
+}
+
+/// 检查 lock 文件能否解析，不能时备份并从 canonical 目录重建
+///
+/// # Arguments
+/// * `scope` - 范围（Global: `~/.agents/.skill-lock.json`，Project: `skills-lock.json`）
+/// * `project_path` - Project scope 时的项目路径
+pub fn check_lock_integrity(
+    scope: &Scope,
+    project_path: Option<&str>,
+) -> Result<LockIntegrity, AppError> {
+    let is_global = matches!(scope, Scope::Global);
+    let cwd = project_path.unwrap_or(".");
+    let lock_path = if is_global {
+        get_skill_lock_path()
+    } else {
+        get_local_lock_path(cwd)
+    };
+
+    let mut result = LockIntegrity {
+        lock_path: lock_path.clone(),
+        parse_error: None,
+        backup_path: None,
+        recovered: Vec::new(),
+    };
+    // 持有文件锁检查和重建：重建的 lock 不会覆盖同时进行的安装 / 更新写入的条目。
+    // 不用 update_skill_lock / update_local_lock：它们把无法解析的内容当作空 lock，
+    // 这里需要原始内容判断是否损坏，且重建结果为空时也要写回
+    with_locked_file(&lock_path, |content| {
+        let Some(content) = content else {
+            return Ok((None, ()));
+        };
+        let parsed = if is_global {
+            serde_json::from_str::<SkillLockFile>(&content).map(|_| ())
+        } else {
+            serde_json::from_str::<LocalSkillLockFile>(&content).map(|_| ())
+        };
+        let Err(e) = parsed else {
+            return Ok((None, ()));
+        };
+        result.parse_error = Some(e.to_string());
+
+        let backup_path = backup_path(&lock_path);
+        fs::write(&backup_path, &content)?;
+        result.backup_path = Some(backup_path);
+
+        let skills = scan_canonical_skills(&canonical_skills_dir(is_global, cwd));
+        let rebuilt = if is_global {
+            let now = chrono::Utc::now().format("%Y-%m-%dT%H:%M:%S%.3fZ").to_string();
+            let mut lock = SkillLockFile::empty();
+            for (dir, frontmatter) in skills {
+                let source = dir.to_string_lossy().to_string();
+                lock.skills.insert(
+                    frontmatter.name,
+                    SkillLockEntry {
+                        source: source.clone(),
+                        source_type: "local".to_string(),
+                        source_url: source,
+                        skill_path: None,
+                        skill_folder_hash: String::new(),
+                        installed_at: now.clone(),
+                        updated_at: now.clone(),
+                        plugin_name: None,
+                        version: frontmatter.version,
+                        dir_name: None,
+                        locale: None,
+                        detached_agents: Vec::new(),
+                    },
+                );
+            }
+            result.recovered = lock.skills.keys().cloned().collect();
+            result.recovered.sort();
+            serde_json::to_string_pretty(&lock)?
+        } else {
+            let mut lock = LocalSkillLockFile::empty();
+            for (dir, frontmatter) in skills {
+                lock.skills.insert(
+                    frontmatter.name,
+                    LocalSkillLockEntry {
+                        source: dir.to_string_lossy().to_string(),
+                        source_type: "local".to_string(),
+                        computed_hash: compute_skill_folder_hash(&dir)?,
+                        version: frontmatter.version,
+                        ..Default::default()
+                    },
+                );
+            }
+            result.recovered = lock.skills.keys().cloned().collect();
+            serde_json::to_string_pretty(&lock)? + "\n"
+        };
+        Ok((Some(rebuilt), ()))
+    })?;
+
+    Ok(result)
+}
+
+/// `<lock>.bak`
+fn backup_path(lock_path: &Path) -> PathBuf {
+    let mut name = lock_path.file_name().unwrap_or_default().to_os_string();
+    name.push(".bak");
+    lock_path.with_file_name(name)
+}
+
+/// 读取 canonical 目录下各 skill 的 SKILL.md，跳过隐藏目录和无法解析的 SKILL.md
+fn scan_canonical_skills(canonical_base: &Path) -> Vec<(PathBuf, SkillFrontmatter)> {
+    let Ok(entries) = fs::read_dir(canonical_base) else {
+        return Vec::new();
+    };
+
+    let mut skills = Vec::new();
+    for entry in entries.filter_map(|e| e.ok()) {
+        if !entry.file_type().map(|t| t.is_dir()).unwrap_or(false)
+            || entry.file_name().to_string_lossy().starts_with('.')
+        {
+            continue;
+        }
+        let dir = entry.path();
+        match parse_skill_md(&dir.join("SKILL.md")) {
+            Ok(frontmatter) => skills.push((dir, frontmatter)),
+            Err(e) => log::warn!("Skipping {} while rebuilding lock: {}", dir.display(), e),
+        }
+    }
+    skills
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::local_lock::{read_local_lock, write_local_lock};
+    use tempfile::tempdir;
+
+    fn write_skill(project: &Path, dir: &str, content: &str) {
+        let skill_dir = project.join(".agents/skills").join(dir);
+        fs::create_dir_all(&skill_dir).unwrap();
+        fs::write(skill_dir.join("SKILL.md"), content).unwrap();
+    }
+
+    #[test]
+    fn test_valid_or_missing_lock_untouched() {
+        let project = tempdir().unwrap();
+        let cwd = project.path().to_string_lossy().to_string();
+
+        let result = check_lock_integrity(&Scope::Project, Some(&cwd)).unwrap();
+        assert!(result.parse_error.is_none());
+        assert!(!result.lock_path.exists());
+
+        write_local_lock(&LocalSkillLockFile::empty(), &cwd).unwrap();
+        let result = check_lock_integrity(&Scope::Project, Some(&cwd)).unwrap();
+        assert!(result.parse_error.is_none());
+        assert!(result.backup_path.is_none());
+    }
+
+    #[test]
+    fn test_repair_corrupt_project_lock() {
+        let project = tempdir().unwrap();
+        let cwd = project.path().to_string_lossy().to_string();
+        write_skill(project.path(), "pdf", "---\nname: pdf\ndescription: PDF\nversion: 1.2.0\n---\n");
+        write_skill(project.path(), "docx", "---\nname: docx\ndescription: Word\n---\n");
+        write_skill(project.path(), "broken", "no frontmatter");
+
+        let lock_path = project.path().join("skills-lock.json");
+        let corrupt = "{\"version\": 1, \"skills\": {\"pdf\": {\"source\": \"owner/repo\",";
+        fs::write(&lock_path, corrupt).unwrap();
+
+        let result = check_lock_integrity(&Scope::Project, Some(&cwd)).unwrap();
+        assert!(result.parse_error.is_some());
+        assert_eq!(result.recovered, vec!["docx", "pdf"]);
+
+        let backup = result.backup_path.unwrap();
+        assert_eq!(backup, project.path().join("skills-lock.json.bak"));
+        assert_eq!(fs::read_to_string(backup).unwrap(), corrupt);
+
+        let lock = read_local_lock(&cwd).unwrap();
+        assert_eq!(lock.skills.len(), 2);
+        assert_eq!(lock.skills["pdf"].source_type, "local");
+        assert_eq!(lock.skills["pdf"].version.as_deref(), Some("1.2.0"));
+        assert_eq!(
+            lock.skills["pdf"].computed_hash,
+            compute_skill_folder_hash(&project.path().join(".agents/skills/pdf")).unwrap()
+        );
+
+        // 修复后再次检查为正常
+        let result = check_lock_integrity(&Scope::Project, Some(&cwd)).unwrap();
+        assert!(result.parse_error.is_none());
+    }
+
+    #[test]
+    fn test_repair_corrupt_lock_without_skills() {
+        let project = tempdir().unwrap();
+        let cwd = project.path().to_string_lossy().to_string();
+        let lock_path = project.path().join("skills-lock.json");
+        fs::write(&lock_path, "{").unwrap();
+
+        // 没有可恢复的 skill 时也写回合法的空 lock
+        let result = check_lock_integrity(&Scope::Project, Some(&cwd)).unwrap();
+        assert!(result.parse_error.is_some());
+        assert!(result.recovered.is_empty());
+        let content = fs::read_to_string(&lock_path).unwrap();
+        assert!(serde_json::from_str::<LocalSkillLockFile>(&content).is_ok());
+    }
+}
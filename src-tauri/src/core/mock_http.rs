@@ -0,0 +1,80 @@
+//! 测试用本地 HTTP 模拟服务
+//!
+//! 按顺序为每个连接返回一个预设的原始 HTTP 响应，并记录收到的原始请求，
+//! 供 GitHub / GitLab API、代理和下载相关的测试使用。
+
+use std::io::{Read, Write};
+use std::net::TcpListener;
+use std::sync::mpsc::{channel, Receiver, Sender};
+use std::time::Duration;
+
+/// 本地 HTTP 模拟服务（监听 127.0.0.1 的随机端口）
+pub struct MockServer {
+    /// 根地址（`http://127.0.0.1:<port>`）
+    pub url: String,
+    listener: Option<TcpListener>,
+    tx: Sender<String>,
+    rx: Receiver<String>,
+}
+
+impl MockServer {
+    /// 绑定端口但暂不响应；响应内容需要用到 `url` 时先绑定再 `serve`
+    pub fn bind() -> Self {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        let (tx, rx) = channel();
+        Self {
+            url,
+            listener: Some(listener),
+            tx,
+            rx,
+        }
+    }
+
+    /// 启动服务：按顺序为每个连接返回 `responses` 中的一个原始响应，全部返回后不再接受连接
+    pub fn start(responses: Vec<String>) -> Self {
+        Self::bind().serve(responses)
+    }
+
+    /// 见 `start`
+    pub fn serve(mut self, responses: Vec<String>) -> Self {
+        let listener = self.listener.take().expect("MockServer already serving");
+        let tx = self.tx.clone();
+        std::thread::spawn(move || {
+            for response in responses {
+                let Ok((mut stream, _)) = listener.accept() else { break };
+                let mut buf = [0u8; 4096];
+                let n = stream.read(&mut buf).unwrap_or(0);
+                let _ = tx.send(String::from_utf8_lossy(&buf[..n]).to_string());
+                let _ = stream.write_all(response.as_bytes());
+            }
+        });
+        self
+    }
+
+    /// 下一个收到的原始请求（请求行 + 请求头），1 秒内没有请求时 panic
+    pub fn next_request(&self) -> String {
+        self.rx.recv_timeout(Duration::from_secs(1)).expect("no request received")
+    }
+
+    /// 目前是否没有收到任何（未读取的）请求
+    pub fn no_requests(&self) -> bool {
+        self.rx.try_recv().is_err()
+    }
+}
+
+/// 原始 HTTP 响应：`headers` 为额外的响应头（每行以 `\r\n` 结尾），自动添加 Content-Length
+pub fn response(status: &str, headers: &str, body: &str) -> String {
+    format!(
+        "HTTP/1.1 {}\r\n{}Content-Length: {}\r\nConnection: close\r\n\r\n{}",
+        status,
+        headers,
+        body.len(),
+        body
+    )
+}
+
+/// `200 OK` 响应
+pub fn ok(body: &str) -> String {
+    response("200 OK", "", body)
+}
@@ -1,20 +1,48 @@
 // src-tauri/src/core/mod.rs
+pub mod agent_entries;
 pub mod agents;
+pub mod atomic_write;
 pub mod audit;
+pub mod changelog;
+pub mod clone_cache;
+pub mod dependencies;
+pub mod detacher;
 pub mod discovery;
+pub mod disk_usage;
+pub mod doctor;
 pub mod git;
+pub mod git_binary;
+pub mod git_hash;
 pub mod github_api;
+pub mod gitlab_api;
+pub mod http_client;
+pub mod http_source;
 pub mod installer;
+pub mod link_audit;
+pub mod lint;
 pub mod paths;
 pub mod skill;
 pub mod local_lock;
+pub mod local_source;
+pub mod lock_integrity;
+#[cfg(test)]
+pub mod mock_http;
+pub mod offline;
+pub mod orphans;
 pub mod plugin_manifest;
+pub mod preview;
+pub mod remote_hash;
+pub mod renamer;
+pub mod scope_mover;
+pub mod shadowing;
 pub mod skill_lock;
 pub mod source_parser;
+pub mod state_backup;
 pub mod uninstaller;
+pub mod usage;
+pub mod visibility;
 
 pub use discovery::*;
 pub use git::*;
-pub use github_api::*;
 pub use installer::*;
 pub use source_parser::*;
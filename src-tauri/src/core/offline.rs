@@ -0,0 +1,84 @@
+//! 离线模式
+//!
+//! 开启后所有联网操作不再发起请求：
+//! - 需要网络才能完成的操作（远程克隆、`git ls-remote`、well-known / direct URL 下载、
+//!   列出远程 refs）返回 `AppError::OfflineMode`
+//! - 可选的联网操作静默跳过：审计返回 None，安装时 skillFolderHash 记为空，更新检测跳过远程比对
+//!
+//! 本地目录、git bundle 和压缩包来源不受影响。
+
+use crate::core::paths::read_skill_deck_config;
+use crate::error::AppError;
+use once_cell::sync::Lazy;
+use std::path::Path;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+/// 开启离线模式的环境变量（值为 1 / true / yes 时生效）
+const OFFLINE_ENV: &str = "SKILL_DECK_OFFLINE";
+
+/// config.json 中的 offline
+/// 首次访问时从 config.json 加载，save_config 时通过 set_offline 同步
+static CONFIGURED_OFFLINE: Lazy<AtomicBool> =
+    Lazy::new(|| AtomicBool::new(read_skill_deck_config().is_some_and(|c| c.offline)));
+
+/// 更新内存中的 offline（save_config 后调用）
+pub fn set_offline(offline: bool) {
+    CONFIGURED_OFFLINE.store(offline, Ordering::Relaxed);
+}
+
+/// 是否处于离线模式（配置或环境变量任一开启即离线）
+pub fn is_offline() -> bool {
+    offline_from(
+        CONFIGURED_OFFLINE.load(Ordering::Relaxed),
+        std::env::var(OFFLINE_ENV).ok().as_deref(),
+    )
+}
+
+fn offline_from(configured: bool, env: Option<&str>) -> bool {
+    configured || env.is_some_and(|v| matches!(v.trim().to_lowercase().as_str(), "1" | "true" | "yes"))
+}
+
+/// 离线时拒绝需要网络的操作
+///
+/// # Arguments
+/// * `operation` - 操作描述，用于错误信息（如 "git clone https://github.com/owner/repo"）
+pub fn ensure_online(operation: &str) -> Result<(), AppError> {
+    if is_offline() {
+        return Err(AppError::OfflineMode {
+            operation: operation.to_string(),
+        });
+    }
+    Ok(())
+}
+
+/// git URL 是否指向远程（本地存在的路径和 `file://` URL 不需要网络）
+pub fn is_remote_git_url(url: &str) -> bool {
+    !(url.starts_with("file://") || Path::new(url).exists())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_offline_from() {
+        assert!(!offline_from(false, None));
+        assert!(offline_from(true, None));
+        assert!(offline_from(false, Some("1")));
+        assert!(offline_from(false, Some(" TRUE ")));
+        assert!(!offline_from(false, Some("0")));
+        assert!(!offline_from(false, Some("")));
+    }
+
+    #[test]
+    fn test_is_remote_git_url() {
+        let dir = tempfile::tempdir().unwrap();
+        let bundle = dir.path().join("repo.bundle");
+        std::fs::write(&bundle, "").unwrap();
+
+        assert!(!is_remote_git_url(&bundle.to_string_lossy()));
+        assert!(!is_remote_git_url("file:///srv/git/repo.git"));
+        assert!(is_remote_git_url("https://github.com/owner/repo.git"));
+        assert!(is_remote_git_url("git@github.com:owner/repo.git"));
+    }
+}
@@ -0,0 +1,208 @@
+//! 孤立 skill 检测模块
+//!
+//! canonical 目录（`.agents/skills/<name>`）可能在安装中断、手动删除 lock 等情况下残留。
+//! 扫描方式与 list_installed_skills 相同（逐个读取子目录的 SKILL.md），但不跳过
+//! internal skill 和无法解析的 SKILL.md，并额外检查：
+//! - lock 中是否有条目（按 lock 记录的 dirName 或 `sanitize_name(key)` 与目录名匹配）
+//! - 是否有 agent 在使用：已检测到的 Universal agents 直接读取 canonical 目录，
+//!   其他 agent 需要有指向该目录的 symlink / junction，或同名（目录名或 sanitize 后的
+//!   skill 名称）的 Copy 安装目录
+
+use crate::core::agents::{AgentType, DetectionContext};
+use crate::core::local_lock::read_local_lock;
+use crate::core::paths::{agent_skills_dir, canonical_skills_dir, is_symlink_or_junction, same_path};
+use crate::core::skill::{locked_dir_name, parse_skill_md, sanitize_name};
+use crate::core::skill_lock::read_skill_lock;
+use crate::error::AppError;
+use crate::models::{OrphanedSkill, Scope};
+use std::collections::HashSet;
+use std::fs;
+use std::path::Path;
+
+/// 列出没有 lock 条目或没有任何 agent 使用的 canonical skill
+///
+/// # Arguments
+/// * `scope` - 范围（Global/Project）
+/// * `project_path` - Project scope 时的项目路径
+///
+/// # Returns
+/// 按名称排序的孤立 skill 列表；canonical 目录不存在时返回空列表
+pub fn list_orphaned_skills(
+    scope: &Scope,
+    project_path: Option<&str>,
+) -> Result<Vec<OrphanedSkill>, AppError> {
+    let is_global = matches!(scope, Scope::Global);
+    let cwd = project_path.unwrap_or(".");
+    let canonical_base = canonical_skills_dir(is_global, cwd);
+    if !canonical_base.is_dir() {
+        return Ok(Vec::new());
+    }
+
+    let locked_dirs: HashSet<String> = if is_global {
+        read_skill_lock()?
+            .skills
+            .into_iter()
+            .map(|(k, e)| locked_dir_name(&k, e.dir_name.as_deref()))
+            .collect()
+    } else {
+        read_local_lock(cwd)?
+            .skills
+            .into_iter()
+            .map(|(k, e)| locked_dir_name(&k, e.dir_name.as_deref()))
+            .collect()
+    };
+    let detected = AgentType::detect_installed(&DetectionContext::for_scope(is_global, cwd));
+    Ok(find_orphans(&canonical_base, &locked_dirs, &detected, is_global, cwd))
+}
+
+/// 扫描 canonical 目录，`detected` 为本机检测到的 agents（决定 Universal agents 是否算在使用）
+fn find_orphans(
+    canonical_base: &Path,
+    locked_dirs: &HashSet<String>,
+    detected: &[AgentType],
+    is_global: bool,
+    cwd: &str,
+) -> Vec<OrphanedSkill> {
+    let entries = match fs::read_dir(canonical_base) {
+        Ok(entries) => entries,
+        Err(_) => return Vec::new(),
+    };
+
+    let mut orphans = Vec::new();
+    for entry in entries.filter_map(|e| e.ok()) {
+        let canonical_dir = entry.path();
+        // 只检查实体目录；canonical 目录下的 symlink 不是本工具创建的
+        if !entry.file_type().map(|t| t.is_dir()).unwrap_or(false) {
+            continue;
+        }
+        let dir_name = entry.file_name().to_string_lossy().to_string();
+        if dir_name.starts_with('.') {
+            continue;
+        }
+
+        let (name, skill_md_error) = match parse_skill_md(&canonical_dir.join("SKILL.md")) {
+            Ok(frontmatter) => (frontmatter.name, None),
+            Err(e) => (dir_name.clone(), Some(e.to_string())),
+        };
+
+        let mut referenced_by = Vec::new();
+        for agent in AgentType::all() {
+            let Some(base) = agent_skills_dir(&agent, is_global, cwd) else {
+                continue;
+            };
+            let used = if same_path(&base, canonical_base) {
+                detected.contains(&agent)
+            } else {
+                [dir_name.clone(), sanitize_name(&name)].iter().any(|candidate| {
+                    let agent_path = base.join(candidate);
+                    if is_symlink_or_junction(&agent_path) {
+                        same_path(&agent_path, &canonical_dir)
+                    } else {
+                        // Copy 安装：agent 目录中有同名的实体 skill 目录
+                        agent_path.join("SKILL.md").is_file()
+                    }
+                })
+            };
+            if used {
+                referenced_by.push(agent);
+            }
+        }
+
+        let in_lock = locked_dirs.contains(&dir_name);
+        if in_lock && !referenced_by.is_empty() {
+            continue;
+        }
+
+        orphans.push(OrphanedSkill {
+            name,
+            canonical_path: canonical_dir.to_string_lossy().to_string(),
+            skill_md_error,
+            in_lock,
+            referenced_by,
+        });
+    }
+
+    orphans.sort_by_key(|o| o.name.to_lowercase());
+    orphans
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::local_lock::{write_local_lock, LocalSkillLockEntry, LocalSkillLockFile};
+    use tempfile::tempdir;
+
+    #[cfg(unix)]
+    #[test]
+    fn test_list_orphaned_project_skills() {
+        let project = tempdir().unwrap();
+        let cwd = project.path().to_string_lossy().to_string();
+        let skills = project.path().join(".agents/skills");
+        for name in ["linked", "unlocked", "unused"] {
+            fs::create_dir_all(skills.join(name)).unwrap();
+            fs::write(
+                skills.join(name).join("SKILL.md"),
+                format!("---\nname: {}\ndescription: d\n---\n", name),
+            )
+            .unwrap();
+        }
+        // SKILL.md 缺失的残留目录
+        fs::create_dir_all(skills.join("broken")).unwrap();
+
+        // linked / unused 在 lock 中；linked 和 unlocked 有 Claude Code symlink
+        let claude = project.path().join(".claude/skills");
+        fs::create_dir_all(&claude).unwrap();
+        for name in ["linked", "unlocked"] {
+            std::os::unix::fs::symlink(skills.join(name), claude.join(name)).unwrap();
+        }
+        // Copy 安装：Windsurf 目录中有 unused 的实体副本
+        let windsurf = agent_skills_dir(&AgentType::Windsurf, false, &cwd).unwrap();
+        fs::create_dir_all(windsurf.join("copied")).unwrap();
+        fs::write(windsurf.join("copied/SKILL.md"), "---\nname: copied\ndescription: d\n---\n").unwrap();
+        fs::create_dir_all(skills.join("copied")).unwrap();
+        fs::write(skills.join("copied/SKILL.md"), "---\nname: copied\ndescription: d\n---\n").unwrap();
+
+        let locked_dirs: HashSet<String> =
+            ["linked", "unused", "copied"].iter().map(|s| s.to_string()).collect();
+
+        // 只检测到 Claude Code：Universal agents 不算在使用
+        let orphans = find_orphans(&skills, &locked_dirs, &[AgentType::ClaudeCode], false, &cwd);
+        let names: Vec<_> = orphans.iter().map(|o| o.name.as_str()).collect();
+        assert_eq!(names, vec!["broken", "unlocked", "unused"]);
+        assert!(orphans[0].skill_md_error.is_some());
+        assert!(!orphans[0].in_lock);
+        assert!(!orphans[1].in_lock);
+        assert!(orphans[1].referenced_by.contains(&AgentType::ClaudeCode));
+        assert!(orphans[2].in_lock);
+        assert!(orphans[2].referenced_by.is_empty());
+
+        // 检测到 Universal agent 时，它们直接读取 canonical 目录，unused 不算孤立
+        let universal = AgentType::all().find(|a| a.is_universal()).unwrap();
+        let orphans = find_orphans(&skills, &locked_dirs, &[universal], false, &cwd);
+        let names: Vec<_> = orphans.iter().map(|o| o.name.as_str()).collect();
+        assert_eq!(names, vec!["broken", "unlocked"]);
+    }
+
+    #[test]
+    fn test_list_orphaned_reads_project_lock() {
+        let project = tempdir().unwrap();
+        let cwd = project.path().to_string_lossy().to_string();
+        let skills = project.path().join(".agents/skills");
+        fs::create_dir_all(skills.join("unlocked")).unwrap();
+        fs::write(skills.join("unlocked/SKILL.md"), "---\nname: unlocked\ndescription: d\n---\n").unwrap();
+        let mut lock = LocalSkillLockFile::empty();
+        lock.skills.insert("other".to_string(), LocalSkillLockEntry::default());
+        write_local_lock(&lock, &cwd).unwrap();
+
+        let orphans = list_orphaned_skills(&Scope::Project, Some(&cwd)).unwrap();
+        assert_eq!(orphans.len(), 1);
+        assert!(!orphans[0].in_lock);
+    }
+
+    #[test]
+    fn test_missing_canonical_dir() {
+        let project = tempdir().unwrap();
+        let cwd = project.path().to_string_lossy().to_string();
+        assert!(list_orphaned_skills(&Scope::Project, Some(&cwd)).unwrap().is_empty());
+    }
+}
@@ -1,8 +1,12 @@
 // 路径工具函数
 // 对应 CLI: agents.ts 顶层常量 (home, configHome, codexHome, claudeHome)
 
+use crate::core::agents::AgentType;
+use crate::error::AppError;
+use crate::models::SkillDeckConfig;
 use once_cell::sync::Lazy;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+use std::sync::RwLock;
 
 /// 路径上下文（与 CLI 顶层常量对应）
 /// 使用 Lazy 单例，只初始化一次
@@ -66,17 +70,236 @@ impl PathContext {
     }
 }
 
+/// Global canonical 目录覆盖
+/// 首次访问时从 config.json 加载，save_config 时通过 set_canonical_dir_override 同步
+static CANONICAL_DIR_OVERRIDE: Lazy<RwLock<Option<PathBuf>>> =
+    Lazy::new(|| RwLock::new(load_canonical_dir_override()));
+
+/// 获取 Skill Deck 配置文件路径: ~/.skill-deck/config.json
+pub fn skill_deck_config_path() -> PathBuf {
+    PATHS.home.join(".skill-deck").join("config.json")
+}
+
+/// 获取凭据文件路径: ~/.skill-deck/credentials.json（host → token，可选）
+pub fn skill_deck_credentials_path() -> PathBuf {
+    PATHS.home.join(".skill-deck").join("credentials.json")
+}
+
+/// 获取保留的克隆目录: ~/.skill-deck/cache/clones（config.json 开启 retainClones 时使用）
+pub fn skill_deck_clone_cache_dir() -> PathBuf {
+    PATHS.home.join(".skill-deck").join("cache").join("clones")
+}
+
+/// 获取文件锁目录: ~/.skill-deck/locks（写入 lock 文件时用于串行化，避免在项目目录中留下锁文件）
+pub fn skill_deck_file_locks_dir() -> PathBuf {
+    PATHS.home.join(".skill-deck").join("locks")
+}
+
+/// 读取 config.json（文件不存在或解析失败时返回 None）
+///
+/// 供常驻内存的配置项在首次访问时加载
+pub fn read_skill_deck_config() -> Option<SkillDeckConfig> {
+    let content = std::fs::read_to_string(skill_deck_config_path()).ok()?;
+    serde_json::from_str(&content).ok()
+}
+
+/// 从配置文件读取 canonical 目录覆盖（读取失败或非绝对路径时忽略）
+fn load_canonical_dir_override() -> Option<PathBuf> {
+    read_skill_deck_config()?
+        .canonical_dir_override
+        .map(PathBuf::from)
+        .filter(|p| p.is_absolute())
+}
+
+/// 校验 canonical 目录覆盖：必须为非空绝对路径
+pub fn validate_canonical_dir_override(value: &str) -> Result<PathBuf, AppError> {
+    let path = PathBuf::from(value);
+    if path.as_os_str().is_empty() || !path.is_absolute() {
+        return Err(AppError::Path {
+            message: format!("Canonical directory override must be an absolute path: {}", value),
+        });
+    }
+    Ok(path)
+}
+
+/// 更新内存中的 canonical 目录覆盖（save_config 后调用）
+pub fn set_canonical_dir_override(path: Option<PathBuf>) {
+    if let Ok(mut guard) = CANONICAL_DIR_OVERRIDE.write() {
+        *guard = path;
+    }
+}
+
 /// 获取 canonical skills 目录
 /// 对应 CLI: getCanonicalSkillsDir (installer.ts:74-77)
-/// Global: ~/.agents/skills/
+/// Global: ~/.agents/skills/（可通过 canonicalDirOverride 覆盖）
 /// Project: ./.agents/skills/
 pub fn canonical_skills_dir(global: bool, cwd: &str) -> PathBuf {
-    let base = if global {
-        PATHS.home.clone()
+    let override_dir = if global {
+        CANONICAL_DIR_OVERRIDE.read().ok().and_then(|g| g.clone())
     } else {
-        PathBuf::from(cwd)
+        None
     };
-    base.join(".agents").join("skills")
+    resolve_canonical_skills_dir(global, cwd, override_dir.as_deref())
+}
+
+/// global canonical 目录（使用给定的覆盖而非当前配置，如导入配置前计算恢复位置）
+pub fn global_canonical_skills_dir(override_dir: Option<&Path>) -> PathBuf {
+    resolve_canonical_skills_dir(true, ".", override_dir)
+}
+
+/// canonical 目录解析（纯函数，override 仅对 global 生效）
+fn resolve_canonical_skills_dir(global: bool, cwd: &str, override_dir: Option<&Path>) -> PathBuf {
+    if global {
+        if let Some(dir) = override_dir {
+            return dir.to_path_buf();
+        }
+        PATHS.home.join(".agents").join("skills")
+    } else {
+        PathBuf::from(cwd).join(".agents").join("skills")
+    }
+}
+
+/// 获取 agent 的 skills 目录
+///
+/// Global: agent 的 global 目录（不支持 global 安装时返回 None）；
+/// Project: `cwd/<skills_dir>`（`supports_project` 为 false 时返回 None）。
+/// 两者都已应用 agentDirOverrides（Universal Agent 同样适用，见 `AgentType::shares_canonical_dir`）。
+/// 返回值不做规范化，比较路径时使用 `same_path`
+pub fn agent_skills_dir(agent: &AgentType, global: bool, cwd: &str) -> Option<PathBuf> {
+    let config = agent.config();
+    if global {
+        config.global_skills_dir
+    } else if config.supports_project {
+        Some(PathBuf::from(cwd).join(config.skills_dir))
+    } else {
+        None
+    }
+}
+
+/// 规范化路径，用于判断两个路径是否指向同一位置
+///
+/// 对已存在的最长前缀做 canonicalize（解析 symlink，如 `$HOME` 本身是 symlink），
+/// 再拼接尚不存在的部分；整条路径都不存在时原样返回
+pub fn resolve_path(path: &Path) -> PathBuf {
+    let mut existing = path;
+    let mut missing = Vec::new();
+    loop {
+        if let Ok(resolved) = existing.canonicalize() {
+            return missing.iter().rev().fold(resolved, |acc, name| acc.join(name));
+        }
+        match (existing.parent(), existing.file_name()) {
+            (Some(parent), Some(name)) => {
+                missing.push(name.to_os_string());
+                existing = parent;
+            }
+            _ => return path.to_path_buf(),
+        }
+    }
+}
+
+/// 判断两个路径在解析 symlink 后是否相同
+pub fn same_path(a: &Path, b: &Path) -> bool {
+    a == b || resolve_path(a) == resolve_path(b)
+}
+
+/// 判断两个路径是否为同一文件（硬链接），任一不存在时为 false
+///
+/// 比较设备号和 inode；Windows 上稳定版标准库无法取得文件 ID，始终返回 false
+pub fn same_file(a: &Path, b: &Path) -> bool {
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::MetadataExt;
+
+        match (std::fs::metadata(a), std::fs::metadata(b)) {
+            (Ok(ma), Ok(mb)) => ma.dev() == mb.dev() && ma.ino() == mb.ino(),
+            _ => false,
+        }
+    }
+    #[cfg(not(unix))]
+    {
+        let _ = (a, b);
+        false
+    }
+}
+
+/// 判断路径是否为 symlink 或 Windows junction（不跟随链接）
+///
+/// Junction 在 Windows 上表现为带 reparse point 属性的目录，`is_symlink()` 不会识别
+pub fn is_symlink_or_junction(path: &Path) -> bool {
+    let metadata = match path.symlink_metadata() {
+        Ok(m) => m,
+        Err(_) => return false,
+    };
+
+    if metadata.file_type().is_symlink() {
+        return true;
+    }
+
+    #[cfg(windows)]
+    {
+        // FILE_ATTRIBUTE_REPARSE_POINT = 0x400
+        metadata.file_type().is_dir()
+            && std::os::windows::fs::MetadataExt::file_attributes(&metadata) & 0x400 != 0
+    }
+    #[cfg(not(windows))]
+    {
+        false
+    }
+}
+
+/// 路径长度达到该值（UTF-16 单元）时转为扩展长度形式
+///
+/// Windows MAX_PATH 为 260，CreateDirectory 要求不超过 248，再为后续拼接的短文件名留出余量
+#[cfg(windows)]
+const WINDOWS_LONG_PATH_THRESHOLD: usize = 240;
+
+/// 路径接近 Windows MAX_PATH 时转为 `\\?\` 扩展长度形式，其他平台原样返回
+///
+/// 用于写入 agent 目录的文件操作（深层嵌套的 skill + 较长的 agent 目录可能超过 260 字符）。
+/// 扩展长度路径不会被系统规范化，因此先转为绝对路径并处理 `.` / `..`；
+/// UNC 路径转为 `\\?\UNC\server\share\...`。
+pub fn long_path(path: &Path) -> PathBuf {
+    #[cfg(windows)]
+    {
+        extended_length_path(path).unwrap_or_else(|| path.to_path_buf())
+    }
+    #[cfg(not(windows))]
+    {
+        path.to_path_buf()
+    }
+}
+
+/// 需要时返回 `path` 的扩展长度形式（已是扩展长度、长度未达阈值或无法转换时返回 None）
+#[cfg(windows)]
+fn extended_length_path(path: &Path) -> Option<PathBuf> {
+    use std::path::Component;
+
+    let raw = path.to_str()?;
+    if raw.starts_with(r"\\?\") || raw.encode_utf16().count() < WINDOWS_LONG_PATH_THRESHOLD {
+        return None;
+    }
+
+    let absolute = if path.is_absolute() {
+        path.to_path_buf()
+    } else {
+        std::env::current_dir().ok()?.join(path)
+    };
+    let mut normalized = PathBuf::new();
+    for component in absolute.components() {
+        match component {
+            Component::CurDir => {}
+            Component::ParentDir => {
+                normalized.pop();
+            }
+            other => normalized.push(other.as_os_str()),
+        }
+    }
+
+    let normalized = normalized.to_str()?;
+    Some(PathBuf::from(match normalized.strip_prefix(r"\\") {
+        Some(unc) => format!(r"\\?\UNC\{}", unc),
+        None => format!(r"\\?\{}", normalized),
+    }))
 }
 
 #[cfg(test)]
@@ -112,4 +335,83 @@ mod tests {
         assert!(dir_str.contains(".agents"), "Should contain .agents");
         assert!(dir_str.contains("skills"), "Should contain skills");
     }
+
+    #[test]
+    fn test_resolve_canonical_skills_dir_override() {
+        let override_dir = std::env::temp_dir().join("synced-skills");
+
+        // global 使用 override
+        let dir = resolve_canonical_skills_dir(true, "/some/project", Some(&override_dir));
+        assert_eq!(dir, override_dir);
+
+        // project 忽略 override
+        let dir = resolve_canonical_skills_dir(false, "/some/project", Some(&override_dir));
+        assert_eq!(dir, PathBuf::from("/some/project").join(".agents").join("skills"));
+    }
+
+    #[test]
+    fn test_validate_canonical_dir_override() {
+        let abs = std::env::temp_dir().join("skills");
+        assert!(validate_canonical_dir_override(&abs.to_string_lossy()).is_ok());
+        assert!(validate_canonical_dir_override("relative/skills").is_err());
+        assert!(validate_canonical_dir_override("").is_err());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_is_symlink_or_junction() {
+        let temp = tempfile::tempdir().unwrap();
+        let real = temp.path().join("real");
+        let link = temp.path().join("link");
+        std::fs::create_dir(&real).unwrap();
+        std::os::unix::fs::symlink(&real, &link).unwrap();
+
+        assert!(is_symlink_or_junction(&link));
+        assert!(!is_symlink_or_junction(&real));
+        assert!(!is_symlink_or_junction(&temp.path().join("missing")));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_same_path_through_symlinked_home() {
+        let temp = tempfile::tempdir().unwrap();
+        let real_home = temp.path().join("real-home");
+        let linked_home = temp.path().join("home");
+        std::fs::create_dir_all(real_home.join(".agents/skills/demo")).unwrap();
+        std::os::unix::fs::symlink(&real_home, &linked_home).unwrap();
+
+        // 已存在的目录：经 symlink home 和真实路径到达的是同一位置
+        let via_link = linked_home.join(".agents/skills/demo");
+        let via_real = real_home.join(".agents/skills/demo");
+        assert!(same_path(&via_link, &via_real));
+
+        // 尚不存在的 agent 目录也能解析到真实路径
+        let missing = linked_home.join(".claude/skills/demo");
+        assert_eq!(
+            resolve_path(&missing),
+            real_home.canonicalize().unwrap().join(".claude/skills/demo")
+        );
+        assert!(same_path(&missing, &real_home.join(".claude/skills/demo")));
+        assert!(!same_path(&via_link, &missing));
+    }
+
+    #[cfg(windows)]
+    #[test]
+    fn test_long_path() {
+        let short = Path::new(r"C:\Users\me\.claude\skills\demo");
+        assert_eq!(long_path(short), short);
+
+        let deep = "d".repeat(250);
+        assert_eq!(
+            long_path(&Path::new(r"C:\skills\.\x\..").join(&deep)),
+            PathBuf::from(format!(r"\\?\C:\skills\{}", deep))
+        );
+        assert_eq!(
+            long_path(&Path::new(r"\\server\share").join(&deep)),
+            PathBuf::from(format!(r"\\?\UNC\server\share\{}", deep))
+        );
+
+        let extended = PathBuf::from(format!(r"\\?\C:\{}", deep));
+        assert_eq!(long_path(&extended), extended);
+    }
 }
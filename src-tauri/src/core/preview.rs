@@ -0,0 +1,352 @@
+//! 安装前预览 SKILL.md
+//!
+//! 只获取单个 SKILL.md，尽量避免完整克隆：
+//! - GitHub: raw.githubusercontent.com
+//! - GitLab: Repository Files API
+//! - 其他 git 仓库: `git archive --remote`，服务端不支持时回退为浅克隆
+//! - 本地目录直接读取；压缩包解压、git bundle 克隆后读取
+//! - well-known / direct URL: 下载后读取（本身只包含 SKILL.md 等少量文件）
+//!
+//! 内容超过 `MAX_PREVIEW_BYTES` 时截断并标记 `truncated`。
+
+use crate::core::git::{archive_remote_file, clone_repo_with_progress, CloneDepth, CloneProgress};
+use crate::core::github_api::get_github_token;
+use crate::core::gitlab_api::{fetch_gitlab_skill_md, gitlab_api_base};
+use crate::core::http_client::{api_client, with_user_agent};
+use crate::core::http_source::{fetch_direct_url_skill, fetch_well_known_skills};
+use crate::core::local_source::{extract_archive, local_source_kind, LocalSourceKind};
+use crate::core::offline::ensure_online;
+use crate::core::skill::parse_skill_md_content;
+use crate::core::source_parser::get_owner_repo;
+use crate::error::AppError;
+use crate::models::{ParsedSource, SourceType};
+use serde::Serialize;
+use specta::Type;
+use std::io::Read;
+use std::path::{Component, Path};
+use std::process::Command;
+
+/// 预览内容上限（字节）
+const MAX_PREVIEW_BYTES: usize = 256 * 1024;
+
+/// raw.githubusercontent.com 地址
+const GITHUB_RAW_BASE: &str = "https://raw.githubusercontent.com";
+
+/// SKILL.md 预览
+#[derive(Debug, Clone, Serialize, Type)]
+#[serde(rename_all = "camelCase")]
+#[specta(rename_all = "camelCase")]
+pub struct SkillPreview {
+    pub name: String,
+    pub description: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub version: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub author: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub license: Option<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<String>,
+    /// frontmatter 之后的 markdown 正文
+    pub body: String,
+    /// 内容超过上限被截断
+    pub truncated: bool,
+}
+
+/// 获取来源中某个 skill 的 SKILL.md 并解析
+///
+/// # Arguments
+/// * `parsed` - 解析后的来源
+/// * `relative_path` - skill 目录相对来源根目录的路径（fetch_available 返回的 relativePath），
+///   也可以直接给出 SKILL.md 路径
+pub async fn preview_skill(parsed: &ParsedSource, relative_path: &str) -> Result<SkillPreview, AppError> {
+    let skill_md = skill_md_path(relative_path)?;
+    let (content, truncated) = fetch_skill_md(parsed, &skill_md).await?;
+    build_preview(&content, truncated)
+}
+
+/// 规范化为 SKILL.md 的相对路径，拒绝绝对路径和 `..`
+fn skill_md_path(relative_path: &str) -> Result<String, AppError> {
+    let trimmed = relative_path.trim().trim_matches('/');
+    let invalid = Path::new(trimmed)
+        .components()
+        .any(|c| !matches!(c, Component::Normal(_) | Component::CurDir));
+    if invalid {
+        return Err(AppError::InvalidSource {
+            value: format!("Invalid skill path: {}", relative_path),
+        });
+    }
+    Ok(match trimmed {
+        "" | "." => "SKILL.md".to_string(),
+        p if p.ends_with("SKILL.md") => p.to_string(),
+        p => format!("{}/SKILL.md", p),
+    })
+}
+
+/// 按来源类型获取 SKILL.md，返回 (内容, 是否截断)
+async fn fetch_skill_md(parsed: &ParsedSource, skill_md: &str) -> Result<(String, bool), AppError> {
+    match parsed.source_type {
+        SourceType::Local => {
+            let path = parsed
+                .local_path
+                .as_ref()
+                .ok_or_else(|| AppError::InvalidSource { value: "Missing local path".to_string() })?;
+            match local_source_kind(path) {
+                LocalSourceKind::Directory => read_capped_file(path, skill_md),
+                LocalSourceKind::GitBundle => {
+                    read_from_clone(&path.to_string_lossy(), parsed.git_ref.as_deref(), skill_md)
+                }
+                LocalSourceKind::Archive(format) => {
+                    let extracted = extract_archive(path, format)?;
+                    read_capped_file(&extracted.root, skill_md)
+                }
+            }
+        }
+        SourceType::GitHub => {
+            let owner_repo = get_owner_repo(parsed).ok_or_else(|| AppError::InvalidSource {
+                value: parsed.url.clone(),
+            })?;
+            fetch_github_raw(&owner_repo, parsed.git_ref.as_deref(), skill_md).await
+        }
+        SourceType::GitLab => {
+            let project = get_owner_repo(parsed).ok_or_else(|| AppError::InvalidSource {
+                value: parsed.url.clone(),
+            })?;
+            let api_base = gitlab_api_base(&parsed.url);
+            let content = fetch_gitlab_skill_md(&api_base, &project, skill_md, parsed.git_ref.as_deref())
+                .await?
+                .ok_or_else(|| AppError::PathNotFound {
+                    path: format!("{}/{}", parsed.url, skill_md),
+                })?;
+            Ok(cap_text(content.into_bytes()))
+        }
+        SourceType::Git => read_via_archive(&parsed.url, parsed.git_ref.as_deref(), skill_md),
+        SourceType::WellKnown => {
+            let download = fetch_well_known_skills(&parsed.url).await?;
+            read_capped_file(&download.skills_dir, skill_md)
+        }
+        SourceType::DirectUrl => {
+            let download = fetch_direct_url_skill(&parsed.url, parsed.sha256.as_deref()).await?;
+            read_capped_file(&download.skills_dir, skill_md)
+        }
+    }
+}
+
+/// 通过 raw.githubusercontent.com 获取文件（不消耗 API 额度），超过上限时停止读取
+async fn fetch_github_raw(
+    owner_repo: &str,
+    git_ref: Option<&str>,
+    skill_md: &str,
+) -> Result<(String, bool), AppError> {
+    ensure_online(&format!("download {} from {}", skill_md, owner_repo))?;
+    let encoded: Vec<_> = skill_md.split('/').map(urlencoding::encode).collect();
+    let url = format!(
+        "{}/{}/{}/{}",
+        GITHUB_RAW_BASE,
+        owner_repo,
+        git_ref.unwrap_or("HEAD"),
+        encoded.join("/")
+    );
+
+    let mut request = with_user_agent(api_client().get(&url));
+    if let Some(token) = get_github_token("github.com") {
+        request = request.header("Authorization", format!("Bearer {}", token));
+    }
+    let mut response = request.send().await?;
+    if response.status() == reqwest::StatusCode::NOT_FOUND {
+        return Err(AppError::PathNotFound {
+            path: format!("{}/{}", owner_repo, skill_md),
+        });
+    }
+    if !response.status().is_success() {
+        return Err(AppError::GitNetworkError {
+            message: format!("Failed to download {}: HTTP {}", url, response.status()),
+        });
+    }
+
+    let mut bytes = Vec::new();
+    while let Some(chunk) = response.chunk().await? {
+        bytes.extend_from_slice(&chunk);
+        if bytes.len() > MAX_PREVIEW_BYTES {
+            break;
+        }
+    }
+    Ok(cap_text(bytes))
+}
+
+/// `git archive --remote` 获取单个文件，服务端不支持时回退为浅克隆
+fn read_via_archive(url: &str, git_ref: Option<&str>, skill_md: &str) -> Result<(String, bool), AppError> {
+    let temp = tempfile::tempdir()?;
+    let tar_path = temp.path().join("skill.tar");
+    match archive_remote_file(url, git_ref, skill_md, &tar_path) {
+        Ok(()) => {
+            let extracted = temp.path().join("files");
+            std::fs::create_dir_all(&extracted)?;
+            let output = Command::new("tar")
+                .arg("-xf")
+                .arg(&tar_path)
+                .arg("-C")
+                .arg(&extracted)
+                .output()?;
+            if !output.status.success() {
+                return Err(AppError::GitCloneFailed {
+                    message: format!(
+                        "Failed to extract git archive: {}",
+                        String::from_utf8_lossy(&output.stderr).trim()
+                    ),
+                });
+            }
+            read_capped_file(&extracted, skill_md)
+        }
+        // upload-archive 不可用（或路径不存在）时克隆后再判断
+        Err(AppError::GitCloneFailed { .. } | AppError::GitRefNotFound { .. }) => {
+            log::info!("git archive unavailable for {}, falling back to clone", url);
+            read_from_clone(url, git_ref, skill_md)
+        }
+        Err(e) => Err(e),
+    }
+}
+
+/// 浅克隆后读取文件
+fn read_from_clone(url: &str, git_ref: Option<&str>, skill_md: &str) -> Result<(String, bool), AppError> {
+    let clone = clone_repo_with_progress(url, git_ref, CloneDepth::Shallow, None, |_: CloneProgress| {})?;
+    read_capped_file(&clone.repo_path, skill_md)
+}
+
+/// 读取 `root` 下的文件，最多读取上限 + 1 字节用于判断截断
+///
+/// 文件经符号链接解析到 `root` 之外时拒绝读取
+fn read_capped_file(root: &Path, relative: &str) -> Result<(String, bool), AppError> {
+    let path = root.join(relative);
+    let not_found = || AppError::PathNotFound {
+        path: path.to_string_lossy().to_string(),
+    };
+    let resolved = std::fs::canonicalize(&path).map_err(|_| not_found())?;
+    let root = std::fs::canonicalize(root)?;
+    if !resolved.starts_with(&root) || !resolved.is_file() {
+        return Err(not_found());
+    }
+
+    let mut bytes = Vec::new();
+    std::fs::File::open(&resolved)?
+        .take(MAX_PREVIEW_BYTES as u64 + 1)
+        .read_to_end(&mut bytes)?;
+    Ok(cap_text(bytes))
+}
+
+/// 截断到上限（按 UTF-8 字符边界），返回 (文本, 是否截断)
+fn cap_text(mut bytes: Vec<u8>) -> (String, bool) {
+    let truncated = bytes.len() > MAX_PREVIEW_BYTES;
+    if truncated {
+        bytes.truncate(MAX_PREVIEW_BYTES);
+    }
+    let text = match String::from_utf8(bytes) {
+        Ok(text) => text,
+        Err(e) => {
+            // 截断点落在多字节字符中间时丢弃不完整的字符
+            let valid = e.utf8_error().valid_up_to();
+            let mut bytes = e.into_bytes();
+            bytes.truncate(valid);
+            String::from_utf8_lossy(&bytes).to_string()
+        }
+    };
+    (text, truncated)
+}
+
+/// 解析 frontmatter 并拆出正文
+fn build_preview(content: &str, truncated: bool) -> Result<SkillPreview, AppError> {
+    let frontmatter = parse_skill_md_content(content)?;
+    Ok(SkillPreview {
+        name: frontmatter.name,
+        description: frontmatter.description,
+        version: frontmatter.version,
+        author: frontmatter.author,
+        license: frontmatter.license,
+        tags: frontmatter.tags,
+        body: skill_md_body(content).to_string(),
+        truncated,
+    })
+}
+
+/// frontmatter 之后的正文（与 parse_skill_md_content 的分隔规则一致，支持 `---` 和 `+++`）
+pub(crate) fn skill_md_body(content: &str) -> &str {
+    let Some(delimiter) = ["---", "+++"].into_iter().find(|d| content.starts_with(d)) else {
+        return content;
+    };
+    let rest = &content[3..];
+    match rest.find(delimiter) {
+        Some(end) => rest[end + 3..].trim_start_matches(['\r', '\n']),
+        None => "",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_skill_md_path() {
+        assert_eq!(skill_md_path("").unwrap(), "SKILL.md");
+        assert_eq!(skill_md_path("skills/pdf").unwrap(), "skills/pdf/SKILL.md");
+        assert_eq!(skill_md_path("skills/pdf/").unwrap(), "skills/pdf/SKILL.md");
+        assert_eq!(skill_md_path("skills/pdf/SKILL.md").unwrap(), "skills/pdf/SKILL.md");
+        assert!(skill_md_path("../secret").is_err());
+        assert!(skill_md_path("skills/../../secret").is_err());
+    }
+
+    #[test]
+    fn test_preview_local_skill() {
+        let root = tempdir().unwrap();
+        let skill_dir = root.path().join("skills/pdf");
+        std::fs::create_dir_all(&skill_dir).unwrap();
+        std::fs::write(
+            skill_dir.join("SKILL.md"),
+            "---\nname: pdf\ndescription: Work with PDFs\nversion: 1.2\n---\n\n# PDF\n\nUse pdftotext.\n",
+        )
+        .unwrap();
+
+        let parsed = ParsedSource::local(root.path().to_path_buf());
+        let preview = tauri::async_runtime::block_on(preview_skill(&parsed, "skills/pdf")).unwrap();
+        assert_eq!(preview.name, "pdf");
+        assert_eq!(preview.description, "Work with PDFs");
+        assert_eq!(preview.version.as_deref(), Some("1.2"));
+        assert_eq!(preview.body, "# PDF\n\nUse pdftotext.\n");
+        assert!(!preview.truncated);
+
+        let missing = tauri::async_runtime::block_on(preview_skill(&parsed, "skills/docx"));
+        assert!(matches!(missing, Err(AppError::PathNotFound { .. })));
+    }
+
+    #[test]
+    fn test_preview_truncates_large_body() {
+        let root = tempdir().unwrap();
+        // 多字节字符跨越截断点
+        let body = "预览".repeat(MAX_PREVIEW_BYTES / 3);
+        std::fs::write(
+            root.path().join("SKILL.md"),
+            format!("---\nname: big\ndescription: Large\n---\n{}", body),
+        )
+        .unwrap();
+
+        let parsed = ParsedSource::local(root.path().to_path_buf());
+        let preview = tauri::async_runtime::block_on(preview_skill(&parsed, "")).unwrap();
+        assert!(preview.truncated);
+        assert!(preview.body.len() < body.len());
+        assert!(body.starts_with(&preview.body));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_read_capped_file_rejects_symlink_escape() {
+        let outside = tempdir().unwrap();
+        std::fs::write(outside.path().join("SKILL.md"), "---\nname: x\ndescription: y\n---\n").unwrap();
+        let root = tempdir().unwrap();
+        std::os::unix::fs::symlink(outside.path(), root.path().join("linked")).unwrap();
+
+        assert!(matches!(
+            read_capped_file(root.path(), "linked/SKILL.md"),
+            Err(AppError::PathNotFound { .. })
+        ));
+    }
+}
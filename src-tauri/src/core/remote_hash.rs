@@ -0,0 +1,133 @@
+//! 远程 skill 文件夹 hash 模块
+//!
+//! 按 lock 条目的 sourceType 分发到对应托管平台的 API。
+//! 每个平台返回可跨次比较的稳定 hash（GitHub tree SHA / GitLab tree id），
+//! 用于安装时记录 skillFolderHash 以及 check_updates 比对。
+//! 同时提供远程 SKILL.md 的读取，用于展示更新后的版本号。
+
+use crate::core::github_api::{fetch_skill_folder_hash, fetch_skill_folder_hashes, fetch_skill_md};
+use crate::core::gitlab_api::{
+    fetch_gitlab_folder_hash, fetch_gitlab_folder_hashes, fetch_gitlab_skill_md, gitlab_api_base,
+};
+use crate::core::skill::parse_skill_md_content;
+use crate::error::AppError;
+
+/// 支持获取文件夹 hash 的托管平台
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum RemoteHashHost {
+    GitHub,
+    GitLab {
+        /// API 根地址（支持自托管实例）
+        api_base: String,
+    },
+}
+
+impl RemoteHashHost {
+    /// 根据 sourceType 和 sourceUrl 确定托管平台
+    ///
+    /// 不支持的平台返回 None；其中已知但尚未支持的平台见 `unsupported_reason`
+    pub fn from_source(source_type: &str, source_url: &str) -> Option<Self> {
+        match source_type {
+            "github" => Some(Self::GitHub),
+            "gitlab" => Some(Self::GitLab {
+                api_base: gitlab_api_base(source_url),
+            }),
+            _ => None,
+        }
+    }
+
+    /// 已知托管平台但尚不支持更新检测时的原因（其余来源类型返回 None，调用方静默跳过）
+    pub fn unsupported_reason(source_type: &str) -> Option<&'static str> {
+        match source_type {
+            "bitbucket" => Some("Update checks are not supported for Bitbucket sources yet"),
+            _ => None,
+        }
+    }
+
+    /// 获取 skill 文件夹 hash
+    ///
+    /// # Arguments
+    /// * `repo` - lock 中的 source（GitHub 为 "owner/repo"，GitLab 为项目路径）
+    /// * `skill_path` - 文件夹路径，如 "skills/my-skill/SKILL.md"
+    /// * `git_ref` - 可选的分支/tag
+    pub async fn fetch_folder_hash(
+        &self,
+        repo: &str,
+        skill_path: &str,
+        git_ref: Option<&str>,
+    ) -> Result<Option<String>, AppError> {
+        match self {
+            Self::GitHub => fetch_skill_folder_hash(repo, skill_path, git_ref).await,
+            Self::GitLab { api_base } => {
+                fetch_gitlab_folder_hash(api_base, repo, skill_path, git_ref).await
+            }
+        }
+    }
+
+    /// 批量获取同一仓库内多个 skill 文件夹的 hash，返回值与 `skill_paths` 一一对应
+    ///
+    /// 远程目录只请求一次（GitHub 每个分支一次递归 tree，GitLab 每个父目录一次），
+    /// 适合一次安装多个 skill 时使用
+    pub async fn fetch_folder_hashes(
+        &self,
+        repo: &str,
+        skill_paths: &[&str],
+        git_ref: Option<&str>,
+    ) -> Result<Vec<Option<String>>, AppError> {
+        match self {
+            Self::GitHub => fetch_skill_folder_hashes(repo, skill_paths, git_ref).await,
+            Self::GitLab { api_base } => {
+                fetch_gitlab_folder_hashes(api_base, repo, skill_paths, git_ref).await
+            }
+        }
+    }
+
+    /// 获取远程 SKILL.md frontmatter 中的 version
+    ///
+    /// 文件不存在、无法解析或未声明 version 时返回 `Ok(None)`
+    pub async fn fetch_skill_version(
+        &self,
+        repo: &str,
+        skill_path: &str,
+        git_ref: Option<&str>,
+    ) -> Result<Option<String>, AppError> {
+        let content = match self {
+            Self::GitHub => fetch_skill_md(repo, skill_path, git_ref).await?,
+            Self::GitLab { api_base } => {
+                fetch_gitlab_skill_md(api_base, repo, skill_path, git_ref).await?
+            }
+        };
+
+        Ok(content
+            .and_then(|c| parse_skill_md_content(&c).ok())
+            .and_then(|f| f.version))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from_source() {
+        assert_eq!(
+            RemoteHashHost::from_source("github", "https://github.com/owner/repo"),
+            Some(RemoteHashHost::GitHub)
+        );
+        assert_eq!(
+            RemoteHashHost::from_source("gitlab", "https://git.example.com/group/repo"),
+            Some(RemoteHashHost::GitLab {
+                api_base: "https://git.example.com/api/v4".to_string()
+            })
+        );
+        assert_eq!(RemoteHashHost::from_source("git", "https://example.com/repo.git"), None);
+        assert_eq!(RemoteHashHost::from_source("local", "/tmp/skills"), None);
+    }
+
+    #[test]
+    fn test_unsupported_reason() {
+        assert!(RemoteHashHost::unsupported_reason("bitbucket").is_some());
+        assert_eq!(RemoteHashHost::unsupported_reason("github"), None);
+        assert_eq!(RemoteHashHost::unsupported_reason("local"), None);
+    }
+}
@@ -0,0 +1,337 @@
+//! 重命名核心模块
+//!
+//! 功能：
+//! - 重命名 canonical 目录（从 lock 记录的目录名改为 `sanitize_name(new_name)`）
+//! - 各 agent 目录下的 symlink 重新指向新目录，copy 模式的实体目录直接 rename
+//! - 迁移 lock 条目的 key（Global: ~/.agents/.skill-lock.json，Project: skills-lock.json）
+//! - 可选改写 SKILL.md frontmatter 中的 name
+//!
+//! 已安装 skill 的名称以 SKILL.md 中的 name 为准（列表和 lock 查找都使用它），因此只有 name
+//! 被改写时才迁移 lock key；未改写时只重命名目录，lock key 保持原名称并通过 `dirName`
+//! 记录新目录名。
+//!
+//! 所有冲突检查在修改任何文件之前完成，目标名称已存在时直接返回错误。
+
+use crate::core::agent_entries::{already_exists, scan_agent_entries, AgentEntry, AgentEntryKind};
+use crate::core::installer::create_symlink;
+use crate::core::local_lock::{compute_skill_folder_hash, read_local_lock, update_local_lock};
+use crate::core::paths::canonical_skills_dir;
+use crate::core::skill::{sanitize_name, skill_dir_name};
+use crate::core::skill_lock::{read_skill_lock, update_skill_lock};
+use crate::error::AppError;
+use crate::models::{RenameResult, Scope};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// 重命名已安装的 skill
+///
+/// # Arguments
+/// * `skill_name` - 当前名称（lock 中的 key）
+/// * `new_name` - 新名称
+/// * `scope` - 范围（Global/Project）
+/// * `project_path` - Project scope 时的项目路径
+/// * `rewrite_name` - 是否同时改写 SKILL.md 中的 name；为 false（或 SKILL.md 中没有 name）时
+///   skill 名称不变，只把安装目录改为 `sanitize_name(new_name)`
+pub fn rename_skill(
+    skill_name: &str,
+    new_name: &str,
+    scope: &Scope,
+    project_path: Option<&str>,
+    rewrite_name: bool,
+) -> Result<RenameResult, AppError> {
+    if new_name.trim().is_empty() {
+        return Err(AppError::Path {
+            message: "Skill name cannot be empty".to_string(),
+        });
+    }
+
+    let is_global = matches!(scope, Scope::Global);
+    let cwd = project_path.unwrap_or(".");
+    let old_sanitized = skill_dir_name(skill_name, is_global, cwd);
+    let new_sanitized = sanitize_name(new_name);
+
+    let canonical_base = canonical_skills_dir(is_global, cwd);
+    let old_canonical = canonical_base.join(&old_sanitized);
+    let new_canonical = canonical_base.join(&new_sanitized);
+
+    if !old_canonical.is_dir() {
+        return Err(AppError::PathNotFound {
+            path: old_canonical.to_string_lossy().to_string(),
+        });
+    }
+    if new_sanitized == old_sanitized || new_canonical.symlink_metadata().is_ok() {
+        return Err(already_exists(&new_canonical));
+    }
+
+    // 1. 收集需要迁移的 agent 目录并检查冲突（不修改任何文件）
+    let mut entries: Vec<(AgentEntry, PathBuf)> = Vec::new();
+    for entry in scan_agent_entries(is_global, cwd, &canonical_base, &old_sanitized) {
+        let new_path = entry.path.with_file_name(&new_sanitized);
+        if new_path.symlink_metadata().is_ok() {
+            return Err(already_exists(&new_path));
+        }
+        entries.push((entry, new_path));
+    }
+
+    // 只有改写 name 时 lock key 才会变化
+    let lock_key_taken = rewrite_name && if is_global {
+        read_skill_lock()?.skills.contains_key(new_name)
+    } else {
+        match project_path {
+            Some(dir) => read_local_lock(dir)?.skills.contains_key(new_name),
+            None => false,
+        }
+    };
+    if lock_key_taken {
+        return Err(AppError::Path {
+            message: format!("A skill named '{}' is already recorded in the lock file", new_name),
+        });
+    }
+
+    // 2. 重命名 canonical 目录
+    fs::rename(&old_canonical, &new_canonical)?;
+
+    let name_rewritten = rewrite_name && rewrite_skill_md_name(&new_canonical, new_name)?;
+
+    // 3. 迁移 agent 目录（单个 agent 失败仅 warn，与删除流程一致）
+    let mut relinked_paths = Vec::new();
+    for (entry, new_path) in entries {
+        let old_path = &entry.path;
+        let result = match entry.kind {
+            AgentEntryKind::Link => create_symlink(&new_canonical, &new_path)
+                .and_then(|_| entry.remove().map_err(AppError::from)),
+            AgentEntryKind::Copy => fs::rename(old_path, &new_path)
+                .map_err(AppError::from)
+                .and_then(|_| {
+                    if name_rewritten {
+                        rewrite_skill_md_name(&new_path, new_name)?;
+                    }
+                    Ok(())
+                }),
+        };
+
+        match result {
+            Ok(()) => relinked_paths.push(new_path.to_string_lossy().to_string()),
+            Err(e) => log::warn!("Could not move {} to new name: {}", old_path.display(), e),
+        }
+    }
+
+    // 4. 迁移 lock 条目：name 已改写时 key 改为新名称（目录名即 sanitize_name(new_name)，
+    //    不再需要记录）；否则 key 仍与 SKILL.md 中的 name 一致，记录新目录名
+    let (lock_key, dir_name) = if name_rewritten {
+        (new_name, None)
+    } else {
+        (skill_name, Some(new_sanitized.clone()).filter(|d| *d != sanitize_name(skill_name)))
+    };
+    if is_global {
+        update_skill_lock(|lock| {
+            if let Some(mut entry) = lock.skills.remove(skill_name) {
+                entry.dir_name = dir_name;
+                lock.skills.insert(lock_key.to_string(), entry);
+            }
+        })?;
+    } else if let Some(dir) = project_path {
+        // 改写 SKILL.md 后内容已变化，同步 computedHash 避免 verify 误报
+        let computed_hash = if name_rewritten {
+            Some(compute_skill_folder_hash(&new_canonical)?)
+        } else {
+            None
+        };
+        update_local_lock(dir, |lock| {
+            if let Some(mut entry) = lock.skills.remove(skill_name) {
+                entry.dir_name = dir_name;
+                if let Some(hash) = computed_hash {
+                    entry.computed_hash = hash;
+                }
+                lock.skills.insert(lock_key.to_string(), entry);
+            }
+        })?;
+    }
+
+    Ok(RenameResult {
+        old_name: skill_name.to_string(),
+        new_name: new_name.to_string(),
+        canonical_path: new_canonical.to_string_lossy().to_string(),
+        relinked_paths,
+        name_rewritten,
+    })
+}
+
+/// 改写目录下 SKILL.md frontmatter 中的 name
+///
+/// # Returns
+/// * `Ok(true)` - 已改写
+/// * `Ok(false)` - SKILL.md 不存在或 frontmatter 中没有 name 字段
+fn rewrite_skill_md_name(skill_dir: &Path, new_name: &str) -> Result<bool, AppError> {
+    let skill_md = skill_dir.join("SKILL.md");
+    if !skill_md.is_file() {
+        return Ok(false);
+    }
+
+    let content = fs::read_to_string(&skill_md)?;
+    match replace_frontmatter_name(&content, new_name)? {
+        Some(updated) => {
+            fs::write(&skill_md, updated)?;
+            Ok(true)
+        }
+        None => Ok(false),
+    }
+}
+
+/// 替换 frontmatter 中顶层的 `name:` 行，其余内容（包括换行风格）保持不变
+///
+/// 新名称经 YAML 序列化，含特殊字符时会自动加引号
+fn replace_frontmatter_name(content: &str, new_name: &str) -> Result<Option<String>, AppError> {
+    let mut lines = content.split_inclusive('\n');
+    let Some(first) = lines.next() else {
+        return Ok(None);
+    };
+    if first.trim_end() != "---" {
+        return Ok(None);
+    }
+
+    let value = serde_yaml::to_string(new_name)?;
+    let mut result = String::from(first);
+    let mut replaced = false;
+    let mut in_frontmatter = true;
+
+    for line in lines {
+        if in_frontmatter && line.trim_end() == "---" {
+            in_frontmatter = false;
+        } else if in_frontmatter && !replaced && line.starts_with("name:") {
+            let ending = &line[line.trim_end_matches(['\r', '\n']).len()..];
+            result.push_str(&format!("name: {}{}", value.trim_end(), ending));
+            replaced = true;
+            continue;
+        }
+        result.push_str(line);
+    }
+
+    Ok(replaced.then_some(result))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::local_lock::{write_local_lock, LocalSkillLockEntry};
+    use crate::core::paths::{is_symlink_or_junction, same_path};
+    use crate::core::skill::parse_skill_md;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_replace_frontmatter_name() {
+        let content = "---\r\nname: demo\r\ndescription: Demo\r\n---\r\nname: body stays\r\n";
+        let updated = replace_frontmatter_name(content, "demo-fork").unwrap().unwrap();
+        assert_eq!(
+            updated,
+            "---\r\nname: demo-fork\r\ndescription: Demo\r\n---\r\nname: body stays\r\n"
+        );
+
+        // 需要引号的名称
+        let updated = replace_frontmatter_name("---\nname: demo\n---\n", "a: b").unwrap().unwrap();
+        assert_eq!(updated, "---\nname: 'a: b'\n---\n");
+
+        assert!(replace_frontmatter_name("# No frontmatter\n", "x").unwrap().is_none());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_rename_project_skill() {
+        let project = tempdir().unwrap();
+        let cwd = project.path().to_string_lossy().to_string();
+        let canonical = project.path().join(".agents/skills/demo");
+        fs::create_dir_all(&canonical).unwrap();
+        fs::write(canonical.join("SKILL.md"), "---\nname: demo\ndescription: Demo\n---\n").unwrap();
+
+        // Claude Code: symlink；Augment: copy
+        let claude = project.path().join(".claude/skills");
+        fs::create_dir_all(&claude).unwrap();
+        std::os::unix::fs::symlink("../../.agents/skills/demo", claude.join("demo")).unwrap();
+        let augment = project.path().join(".augment/skills/demo");
+        fs::create_dir_all(&augment).unwrap();
+        fs::write(augment.join("SKILL.md"), "---\nname: demo\ndescription: Demo\n---\n").unwrap();
+
+        let mut lock = crate::core::local_lock::LocalSkillLockFile::empty();
+        lock.skills.insert(
+            "demo".to_string(),
+            LocalSkillLockEntry {
+                computed_hash: compute_skill_folder_hash(&canonical).unwrap(),
+                ..Default::default()
+            },
+        );
+        write_local_lock(&lock, &cwd).unwrap();
+
+        let result = rename_skill("demo", "demo-fork", &Scope::Project, Some(&cwd), true).unwrap();
+        assert!(result.name_rewritten);
+        assert_eq!(result.relinked_paths.len(), 2);
+
+        let new_canonical = project.path().join(".agents/skills/demo-fork");
+        assert!(!canonical.exists());
+        assert_eq!(parse_skill_md(&new_canonical.join("SKILL.md")).unwrap().name, "demo-fork");
+
+        let link = claude.join("demo-fork");
+        assert!(is_symlink_or_junction(&link));
+        assert!(same_path(&link, &new_canonical));
+        assert!(claude.join("demo").symlink_metadata().is_err());
+        assert_eq!(
+            parse_skill_md(&project.path().join(".augment/skills/demo-fork/SKILL.md")).unwrap().name,
+            "demo-fork"
+        );
+
+        let lock = read_local_lock(&cwd).unwrap();
+        assert!(!lock.skills.contains_key("demo"));
+        assert_eq!(
+            lock.skills["demo-fork"].computed_hash,
+            compute_skill_folder_hash(&new_canonical).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_rename_without_rewrite_keeps_lock_key() {
+        let project = tempdir().unwrap();
+        let cwd = project.path().to_string_lossy().to_string();
+        let canonical = project.path().join(".agents/skills/demo");
+        fs::create_dir_all(&canonical).unwrap();
+        fs::write(canonical.join("SKILL.md"), "---\nname: demo\ndescription: Demo\n---\n").unwrap();
+
+        let mut lock = crate::core::local_lock::LocalSkillLockFile::empty();
+        lock.skills.insert(
+            "demo".to_string(),
+            LocalSkillLockEntry {
+                computed_hash: compute_skill_folder_hash(&canonical).unwrap(),
+                ..Default::default()
+            },
+        );
+        write_local_lock(&lock, &cwd).unwrap();
+
+        let result = rename_skill("demo", "demo-fork", &Scope::Project, Some(&cwd), false).unwrap();
+        assert!(!result.name_rewritten);
+
+        // SKILL.md 中的 name 未变，lock key 保持一致，新目录名记录在 dirName
+        let new_canonical = project.path().join(".agents/skills/demo-fork");
+        assert_eq!(parse_skill_md(&new_canonical.join("SKILL.md")).unwrap().name, "demo");
+        let lock = read_local_lock(&cwd).unwrap();
+        assert!(!lock.skills.contains_key("demo-fork"));
+        assert_eq!(lock.skills["demo"].dir_name.as_deref(), Some("demo-fork"));
+        assert_eq!(skill_dir_name("demo", false, &cwd), "demo-fork");
+
+        // 改回原目录名时不再需要记录 dirName
+        rename_skill("demo", "demo", &Scope::Project, Some(&cwd), false).unwrap();
+        assert!(canonical.is_dir());
+        assert_eq!(read_local_lock(&cwd).unwrap().skills["demo"].dir_name, None);
+    }
+
+    #[test]
+    fn test_rename_fails_when_target_exists() {
+        let project = tempdir().unwrap();
+        let cwd = project.path().to_string_lossy().to_string();
+        let skills = project.path().join(".agents/skills");
+        fs::create_dir_all(skills.join("demo")).unwrap();
+        fs::create_dir_all(skills.join("other")).unwrap();
+
+        let err = rename_skill("demo", "Other", &Scope::Project, Some(&cwd), false).unwrap_err();
+        assert!(matches!(err, AppError::Path { .. }));
+        // 未做任何修改
+        assert!(skills.join("demo").is_dir());
+    }
+}
@@ -0,0 +1,404 @@
+//! Scope 迁移核心模块
+//!
+//! 功能：
+//! - 将 canonical 目录复制到目标 scope 的 canonical 目录（目录名为 `sanitize_name(name)`）
+//! - 原 scope 中以 symlink 安装的 agent 在目标 scope 重新创建 symlink，copy 模式的目录原样复制
+//!   （不应用安装时的排除规则，保留用户添加的文件）
+//! - 在 ~/.agents/.skill-lock.json 与项目 skills-lock.json 之间迁移 lock 条目
+//! - 删除原 scope 中已迁移的 agent 目录；所有 agent 都迁移成功时再删除原 canonical 目录
+//!
+//! 冲突检查在修改任何文件之前完成；canonical 目录复制失败时原安装保持不变。
+//! 未能迁移的 agent 保持原样，仍有 symlink 指向原 canonical 目录时保留该目录，避免链接失效。
+//! 两个 scope 可能位于不同文件系统，因此复制而非 rename。
+
+use crate::core::agent_entries::{already_exists, scan_agent_entries, AgentEntry, AgentEntryKind};
+use crate::core::installer::{create_symlink, stage_and_replace_all};
+use crate::core::local_lock::{
+    compute_skill_folder_hash, read_local_lock, recorded_source_url, update_local_lock,
+    LocalSkillLockEntry,
+};
+use crate::core::paths::{agent_skills_dir, canonical_skills_dir, same_path};
+use crate::core::skill::{sanitize_name, skill_dir_name};
+use crate::core::skill_lock::{read_skill_lock, update_skill_lock, SkillLockEntry};
+use crate::error::AppError;
+use crate::models::{MoveScopeResult, Scope};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// 待迁移的 agent 目录（copy 模式的实体目录整体复制）
+struct AgentMove {
+    entry: AgentEntry,
+    /// 目标 scope 下的路径；目标 scope 没有该 agent 的 skills 目录时为 None
+    new_path: Option<PathBuf>,
+}
+
+/// 在 global / project scope 之间移动已安装的 skill
+///
+/// # Arguments
+/// * `skill_name` - skill 名称（lock 中的 key）
+/// * `from` - 当前 scope
+/// * `to` - 目标 scope
+/// * `project_path` - 项目路径（两个方向都需要）
+pub fn move_skill_scope(
+    skill_name: &str,
+    from: &Scope,
+    to: &Scope,
+    project_path: &str,
+) -> Result<MoveScopeResult, AppError> {
+    if from == to {
+        return Err(AppError::Path {
+            message: "Source and target scope are the same".to_string(),
+        });
+    }
+
+    let from_global = matches!(from, Scope::Global);
+    let to_global = matches!(to, Scope::Global);
+    let old_dir_name = skill_dir_name(skill_name, from_global, project_path);
+    let new_dir_name = sanitize_name(skill_name);
+
+    let old_base = canonical_skills_dir(from_global, project_path);
+    let new_base = canonical_skills_dir(to_global, project_path);
+    let old_canonical = old_base.join(&old_dir_name);
+    let new_canonical = new_base.join(&new_dir_name);
+
+    if !old_canonical.join("SKILL.md").is_file() {
+        return Err(AppError::PathNotFound {
+            path: old_canonical.to_string_lossy().to_string(),
+        });
+    }
+    if new_canonical.symlink_metadata().is_ok() {
+        return Err(already_exists(&new_canonical));
+    }
+
+    // 1. 收集需要迁移的 agent 目录并检查冲突（不修改任何文件）
+    let mut moves: Vec<AgentMove> = Vec::new();
+    for entry in scan_agent_entries(from_global, project_path, &old_base, &old_dir_name) {
+        let new_path = agent_skills_dir(&entry.agent, to_global, project_path)
+            .filter(|target_base| !same_path(target_base, &new_base))
+            .map(|target_base| target_base.join(&new_dir_name));
+        if let Some(path) = &new_path {
+            if path.symlink_metadata().is_ok() {
+                return Err(already_exists(path));
+            }
+        }
+        moves.push(AgentMove { entry, new_path });
+    }
+
+    let lock_key_taken = if to_global {
+        read_skill_lock()?.skills.contains_key(skill_name)
+    } else {
+        read_local_lock(project_path)?.skills.contains_key(skill_name)
+    };
+    if lock_key_taken {
+        return Err(AppError::Path {
+            message: format!(
+                "A skill named '{}' is already recorded in the target scope's lock file",
+                skill_name
+            ),
+        });
+    }
+
+    // 2-3. 复制 canonical 目录（失败时不做任何修改），在目标 scope 重新创建 agent 目录
+    let copied = copy_to_target(&old_canonical, &new_canonical, &moves)?;
+
+    // 4. 迁移 lock 条目（先写入目标，再从原 scope 移除）
+    let lock_moved = if from_global {
+        match read_skill_lock()?.skills.remove(skill_name) {
+            Some(entry) => {
+                let computed_hash = compute_skill_folder_hash(&new_canonical)?;
+                update_local_lock(project_path, |local| {
+                    local
+                        .skills
+                        .insert(skill_name.to_string(), to_local_entry(entry, computed_hash))
+                })?;
+                update_skill_lock(|global| global.skills.remove(skill_name))?;
+                true
+            }
+            None => false,
+        }
+    } else {
+        match read_local_lock(project_path)?.skills.remove(skill_name) {
+            Some(entry) => {
+                update_skill_lock(|global| {
+                    global.skills.insert(skill_name.to_string(), to_global_entry(entry))
+                })?;
+                update_local_lock(project_path, |local| local.skills.remove(skill_name))?;
+                true
+            }
+            None => false,
+        }
+    };
+
+    // 5. 删除原 scope 中已迁移的 agent 目录和 canonical 目录
+    remove_moved(&old_canonical, &moves, &copied.moved)?;
+
+    Ok(MoveScopeResult {
+        canonical_path: new_canonical.to_string_lossy().to_string(),
+        relinked_paths: copied.relinked_paths,
+        skipped_agents: copied.skipped_agents,
+        lock_moved,
+    })
+}
+
+/// 复制到目标 scope 的结果
+struct Copied {
+    relinked_paths: Vec<String>,
+    skipped_agents: Vec<String>,
+    /// 与 moves 一一对应：该 agent 目录是否已在目标 scope 创建
+    moved: Vec<bool>,
+}
+
+/// 复制 canonical 目录并在目标 scope 重新创建 agent 目录
+///
+/// canonical 目录复制失败时直接返回错误；单个 agent 失败仅 warn 并记入 skipped（与重命名流程一致）
+fn copy_to_target(old_canonical: &Path, new_canonical: &Path, moves: &[AgentMove]) -> Result<Copied, AppError> {
+    stage_and_replace_all(old_canonical, new_canonical)?;
+
+    let mut copied = Copied {
+        relinked_paths: Vec::new(),
+        skipped_agents: Vec::new(),
+        moved: Vec::new(),
+    };
+    for m in moves {
+        let Some(new_path) = &m.new_path else {
+            copied.skipped_agents.push(m.entry.agent.to_string());
+            copied.moved.push(false);
+            continue;
+        };
+        let result = match m.entry.kind {
+            AgentEntryKind::Link => create_symlink(new_canonical, new_path),
+            AgentEntryKind::Copy => stage_and_replace_all(&m.entry.path, new_path),
+        };
+        let moved = match result {
+            Ok(()) => {
+                copied.relinked_paths.push(new_path.to_string_lossy().to_string());
+                true
+            }
+            Err(e) => {
+                log::warn!("Could not move {} to {}: {}", m.entry.path.display(), new_path.display(), e);
+                copied.skipped_agents.push(m.entry.agent.to_string());
+                false
+            }
+        };
+        copied.moved.push(moved);
+    }
+    Ok(copied)
+}
+
+/// 删除原 scope 中已迁移的 agent 目录，未迁移的保持原样
+///
+/// 未迁移的 symlink 仍指向原 canonical 目录，此时保留 canonical 目录避免链接失效
+fn remove_moved(old_canonical: &Path, moves: &[AgentMove], moved: &[bool]) -> Result<(), AppError> {
+    let mut keep_canonical = false;
+    for (m, moved) in moves.iter().zip(moved) {
+        if !moved {
+            keep_canonical |= matches!(m.entry.kind, AgentEntryKind::Link);
+            continue;
+        }
+        if let Err(e) = m.entry.remove() {
+            log::warn!("Could not remove {}: {}", m.entry.path.display(), e);
+        }
+    }
+
+    if keep_canonical {
+        log::warn!(
+            "Keeping {} because some agents still link to it",
+            old_canonical.display()
+        );
+        return Ok(());
+    }
+    fs::remove_dir_all(old_canonical)?;
+    Ok(())
+}
+
+/// global lock 条目转为项目 lock 条目
+///
+/// 目录名重置为 `sanitize_name(name)`，skillFolderHash 作为 remoteHash 保留用于更新检测
+fn to_local_entry(entry: SkillLockEntry, computed_hash: String) -> LocalSkillLockEntry {
+    let source_url = recorded_source_url(&entry.source, &entry.source_type, &entry.source_url);
+    LocalSkillLockEntry {
+        source: entry.source,
+        source_type: entry.source_type,
+        computed_hash,
+        remote_hash: Some(entry.skill_folder_hash).filter(|h| !h.is_empty()),
+        skill_path: entry.skill_path,
+        plugin_name: entry.plugin_name,
+        version: entry.version,
+        installed_at: Some(entry.installed_at).filter(|t| !t.is_empty()),
+        updated_at: Some(entry.updated_at).filter(|t| !t.is_empty()),
+        dir_name: None,
+        locale: entry.locale,
+        detached_agents: entry.detached_agents,
+        source_url,
+    }
+}
+
+/// 项目 lock 条目转为 global lock 条目（旧条目的 source_url 从 source 构造）
+fn to_global_entry(entry: LocalSkillLockEntry) -> SkillLockEntry {
+    let now = chrono::Utc::now().format("%Y-%m-%dT%H:%M:%S%.3fZ").to_string();
+    SkillLockEntry {
+        source_url: entry.source_url(),
+        source: entry.source,
+        source_type: entry.source_type,
+        skill_path: entry.skill_path,
+        skill_folder_hash: entry.remote_hash.unwrap_or_default(),
+        installed_at: entry.installed_at.unwrap_or_else(|| now.clone()),
+        updated_at: entry.updated_at.unwrap_or(now),
+        plugin_name: entry.plugin_name,
+        version: entry.version,
+        dir_name: None,
+        locale: entry.locale,
+        detached_agents: entry.detached_agents,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::agents::AgentType;
+    use crate::core::paths::is_symlink_or_junction;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_lock_entry_round_trip() {
+        let global = SkillLockEntry {
+            source: "owner/repo".to_string(),
+            source_type: "github".to_string(),
+            source_url: "https://github.com/owner/repo".to_string(),
+            skill_path: Some("skills/demo/SKILL.md".to_string()),
+            skill_folder_hash: "abc123".to_string(),
+            installed_at: "2026-01-01T00:00:00.000Z".to_string(),
+            updated_at: "2026-02-01T00:00:00.000Z".to_string(),
+            plugin_name: Some("tools".to_string()),
+            version: Some("1.0".to_string()),
+            dir_name: Some("demo-2".to_string()),
+            locale: None,
+            detached_agents: Vec::new(),
+        };
+
+        let local = to_local_entry(global.clone(), "sha".to_string());
+        assert_eq!(local.computed_hash, "sha");
+        assert_eq!(local.remote_hash.as_deref(), Some("abc123"));
+        assert_eq!(local.installed_at.as_deref(), Some("2026-01-01T00:00:00.000Z"));
+        assert!(local.dir_name.is_none());
+
+        let back = to_global_entry(local);
+        assert_eq!(back.source_url, global.source_url);
+        assert_eq!(back.skill_path, global.skill_path);
+        assert_eq!(back.skill_folder_hash, "abc123");
+        assert_eq!(back.installed_at, global.installed_at);
+        assert_eq!(back.updated_at, global.updated_at);
+        assert_eq!(back.plugin_name, global.plugin_name);
+        assert!(back.dir_name.is_none());
+    }
+
+    #[test]
+    fn test_move_rejects_same_scope_and_missing_skill() {
+        let project = tempdir().unwrap();
+        let cwd = project.path().to_string_lossy().to_string();
+
+        let err = move_skill_scope("demo", &Scope::Project, &Scope::Project, &cwd).unwrap_err();
+        assert!(matches!(err, AppError::Path { .. }));
+
+        let err = move_skill_scope("demo", &Scope::Project, &Scope::Global, &cwd).unwrap_err();
+        assert!(matches!(err, AppError::PathNotFound { .. }));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_move_files_between_scopes() {
+        let root = tempdir().unwrap();
+        let from = root.path().join("project");
+        let to = root.path().join("home");
+        let old_canonical = from.join(".agents/skills/demo");
+        let new_canonical = to.join(".agents/skills/demo");
+        fs::create_dir_all(&old_canonical).unwrap();
+        fs::write(old_canonical.join("SKILL.md"), "---\nname: demo\ndescription: Demo\n---\n").unwrap();
+        // 安装时会被排除的文件也要原样迁移
+        fs::write(old_canonical.join("_notes.md"), "notes").unwrap();
+        fs::write(old_canonical.join("metadata.json"), "{}").unwrap();
+
+        // Claude Code: symlink；Augment: copy；Windsurf: copy 且目标 scope 没有对应目录
+        let claude = from.join(".claude/skills");
+        fs::create_dir_all(&claude).unwrap();
+        std::os::unix::fs::symlink("../../.agents/skills/demo", claude.join("demo")).unwrap();
+        for agent in [".augment", ".windsurf"] {
+            let dir = from.join(agent).join("skills/demo");
+            fs::create_dir_all(&dir).unwrap();
+            fs::write(dir.join("SKILL.md"), "---\nname: demo\ndescription: Demo\n---\n").unwrap();
+            fs::write(dir.join("_local.md"), "local").unwrap();
+        }
+        let moves = vec![
+            AgentMove {
+                entry: AgentEntry {
+                    agent: AgentType::ClaudeCode,
+                    path: claude.join("demo"),
+                    kind: AgentEntryKind::Link,
+                },
+                new_path: Some(to.join(".claude/skills/demo")),
+            },
+            AgentMove {
+                entry: AgentEntry {
+                    agent: AgentType::Augment,
+                    path: from.join(".augment/skills/demo"),
+                    kind: AgentEntryKind::Copy,
+                },
+                new_path: Some(to.join(".augment/skills/demo")),
+            },
+            AgentMove {
+                entry: AgentEntry {
+                    agent: AgentType::Windsurf,
+                    path: from.join(".windsurf/skills/demo"),
+                    kind: AgentEntryKind::Copy,
+                },
+                new_path: None,
+            },
+        ];
+
+        let copied = copy_to_target(&old_canonical, &new_canonical, &moves).unwrap();
+        assert_eq!(copied.relinked_paths.len(), 2);
+        assert_eq!(copied.skipped_agents, vec!["windsurf".to_string()]);
+        remove_moved(&old_canonical, &moves, &copied.moved).unwrap();
+
+        assert_eq!(fs::read_to_string(new_canonical.join("_notes.md")).unwrap(), "notes");
+        assert!(new_canonical.join("metadata.json").is_file());
+        let new_link = to.join(".claude/skills/demo");
+        assert!(is_symlink_or_junction(&new_link));
+        assert!(same_path(&new_link, &new_canonical));
+        assert_eq!(fs::read_to_string(to.join(".augment/skills/demo/_local.md")).unwrap(), "local");
+
+        // 已迁移的目录被删除，跳过的 agent 保持原样
+        assert!(claude.join("demo").symlink_metadata().is_err());
+        assert!(!from.join(".augment/skills/demo").exists());
+        assert!(!old_canonical.exists());
+        assert_eq!(fs::read_to_string(from.join(".windsurf/skills/demo/_local.md")).unwrap(), "local");
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_skipped_link_keeps_old_canonical() {
+        let root = tempdir().unwrap();
+        let old_canonical = root.path().join("project/.agents/skills/demo");
+        fs::create_dir_all(&old_canonical).unwrap();
+        fs::write(old_canonical.join("SKILL.md"), "---\nname: demo\ndescription: Demo\n---\n").unwrap();
+        let claude = root.path().join("project/.claude/skills");
+        fs::create_dir_all(&claude).unwrap();
+        std::os::unix::fs::symlink("../../.agents/skills/demo", claude.join("demo")).unwrap();
+        let moves = vec![AgentMove {
+            entry: AgentEntry {
+                agent: AgentType::ClaudeCode,
+                path: claude.join("demo"),
+                kind: AgentEntryKind::Link,
+            },
+            new_path: None,
+        }];
+
+        let new_canonical = root.path().join("home/.agents/skills/demo");
+        let copied = copy_to_target(&old_canonical, &new_canonical, &moves).unwrap();
+        remove_moved(&old_canonical, &moves, &copied.moved).unwrap();
+
+        // 原 symlink 仍可用
+        assert!(claude.join("demo/SKILL.md").is_file());
+        assert!(new_canonical.join("SKILL.md").is_file());
+    }
+}
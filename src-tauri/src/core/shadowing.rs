@@ -0,0 +1,200 @@
+//! 跨 scope 重名 skill 检测模块
+//!
+//! 同名 skill 同时安装在 global 和项目中时，agent 只会加载其中一份。
+//! 判断哪一份生效需要逐个 agent 分析：
+//! - 只看该 agent 自己的目录（项目级 `<project>/<skills_dir>` 与全局 `global_skills_dir`），
+//!   canonical 目录中存在但没有链接给该 agent 的 skill 不参与比较
+//! - 按 SKILL.md 的 name 匹配（agent 按 name 加载），目录名不同也视为重名
+//! - 两个目录解析后是同一位置时（如项目就是 home 目录）不存在遮蔽
+//! - 按 agent 的目录优先级决定生效的副本：多数 agent 项目级优先，
+//!   Claude Code 的个人 skills（`~/.claude/skills`）优先于项目 skills
+
+use crate::core::agents::{AgentType, DetectionContext};
+use crate::core::local_lock::compute_skill_folder_hash;
+use crate::core::paths::{agent_skills_dir, same_path};
+use crate::core::skill::parse_skill_md;
+use crate::error::AppError;
+use crate::models::{Scope, ShadowedSkill, SkillResolution};
+use std::collections::{BTreeMap, HashMap};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// 列出同时存在于 global 和项目中的 skill，以及每个已检测到的 agent 实际使用的副本
+///
+/// # Arguments
+/// * `project_path` - 项目路径
+///
+/// # Returns
+/// 按名称排序的重名 skill；每个 skill 只列出两个 scope 都能看到它的 agent
+pub fn detect_skill_shadowing(project_path: &str) -> Result<Vec<ShadowedSkill>, AppError> {
+    let detected = AgentType::detect_installed(&DetectionContext::for_scope(false, project_path));
+    let agent_dirs: Vec<(AgentType, PathBuf, PathBuf)> = detected
+        .into_iter()
+        .filter_map(|agent| {
+            let project_base = agent_skills_dir(&agent, false, project_path)?;
+            let global_base = agent_skills_dir(&agent, true, project_path)?;
+            Some((agent, project_base, global_base))
+        })
+        .collect();
+    Ok(find_shadowing(&agent_dirs))
+}
+
+/// 同名 skill 的生效 scope
+///
+/// Claude Code 文档约定个人 skills 覆盖同名项目 skills，其余 agent 项目级优先
+fn preferred_scope(agent: &AgentType) -> Scope {
+    match agent {
+        AgentType::ClaudeCode => Scope::Global,
+        _ => Scope::Project,
+    }
+}
+
+/// 按 agent 比较项目级与全局目录中的 skill（纯函数，便于测试）
+///
+/// # Arguments
+/// * `agent_dirs` - (agent, 项目级 skills 目录, 全局 skills 目录)
+fn find_shadowing(agent_dirs: &[(AgentType, PathBuf, PathBuf)]) -> Vec<ShadowedSkill> {
+    // 多个 agent 共享同一目录（如 .agents/skills），每个目录只扫描一次
+    let mut scanned: HashMap<PathBuf, BTreeMap<String, PathBuf>> = HashMap::new();
+    let mut hashes: HashMap<PathBuf, Option<String>> = HashMap::new();
+    let mut shadowed: BTreeMap<String, Vec<SkillResolution>> = BTreeMap::new();
+
+    for (agent, project_base, global_base) in agent_dirs {
+        if same_path(project_base, global_base) {
+            continue;
+        }
+        let project_skills = scanned
+            .entry(project_base.clone())
+            .or_insert_with(|| scan_skills(project_base))
+            .clone();
+        let global_skills = scanned
+            .entry(global_base.clone())
+            .or_insert_with(|| scan_skills(global_base));
+
+        for (name, project_dir) in &project_skills {
+            let Some(global_dir) = global_skills.get(name) else {
+                continue;
+            };
+            // 项目内的 symlink 指向全局副本时，两边是同一份内容
+            let identical = same_path(project_dir, global_dir) || {
+                let project_hash = folder_hash(&mut hashes, project_dir);
+                project_hash.is_some() && project_hash == folder_hash(&mut hashes, global_dir)
+            };
+            shadowed.entry(name.clone()).or_default().push(SkillResolution {
+                agent: *agent,
+                effective_scope: preferred_scope(agent),
+                project_path: project_dir.to_string_lossy().to_string(),
+                global_path: global_dir.to_string_lossy().to_string(),
+                identical,
+            });
+        }
+    }
+
+    shadowed
+        .into_iter()
+        .map(|(name, agents)| ShadowedSkill { name, agents })
+        .collect()
+}
+
+/// 读取目录下各 skill 的 SKILL.md，返回 name → skill 目录
+///
+/// 跳过隐藏目录和无法解析的 SKILL.md；目录不存在时返回空
+fn scan_skills(base: &Path) -> BTreeMap<String, PathBuf> {
+    let Ok(entries) = fs::read_dir(base) else {
+        return BTreeMap::new();
+    };
+
+    let mut skills = BTreeMap::new();
+    for entry in entries.filter_map(|e| e.ok()) {
+        let dir = entry.path();
+        if !dir.is_dir() || entry.file_name().to_string_lossy().starts_with('.') {
+            continue;
+        }
+        if let Ok(frontmatter) = parse_skill_md(&dir.join("SKILL.md")) {
+            skills.entry(frontmatter.name).or_insert(dir);
+        }
+    }
+    skills
+}
+
+/// 计算（并缓存）skill 目录的内容 hash，失败时为 None
+fn folder_hash(cache: &mut HashMap<PathBuf, Option<String>>, dir: &Path) -> Option<String> {
+    cache
+        .entry(dir.to_path_buf())
+        .or_insert_with(|| compute_skill_folder_hash(dir).ok())
+        .clone()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    fn write_skill(base: &Path, dir: &str, name: &str, body: &str) {
+        let skill_dir = base.join(dir);
+        fs::create_dir_all(&skill_dir).unwrap();
+        fs::write(
+            skill_dir.join("SKILL.md"),
+            format!("---\nname: {}\ndescription: d\n---\n{}", name, body),
+        )
+        .unwrap();
+    }
+
+    #[test]
+    fn test_find_shadowing_per_agent_precedence() {
+        let temp = tempdir().unwrap();
+        let project = temp.path().join("project");
+        let home = temp.path().join("home");
+
+        let claude_project = project.join(".claude/skills");
+        let claude_global = home.join(".claude/skills");
+        let cursor_project = project.join(".cursor/skills");
+        let cursor_global = home.join(".cursor/skills");
+
+        // pdf: Claude Code 两边都有且内容相同；docx: 目录名不同但 name 相同
+        write_skill(&claude_project, "pdf", "pdf", "same");
+        write_skill(&claude_global, "pdf", "pdf", "same");
+        write_skill(&cursor_project, "docx-local", "docx", "project");
+        write_skill(&cursor_global, "docx", "docx", "global");
+        // 只存在于一个 scope
+        write_skill(&claude_project, "xlsx", "xlsx", "");
+        write_skill(&cursor_global, "pptx", "pptx", "");
+        // pdf 没有链接给 Cursor 的全局目录，Cursor 不受影响
+        write_skill(&cursor_project, "pdf", "pdf", "same");
+
+        let result = find_shadowing(&[
+            (AgentType::ClaudeCode, claude_project.clone(), claude_global),
+            (AgentType::Cursor, cursor_project.clone(), cursor_global),
+        ]);
+
+        let names: Vec<&str> = result.iter().map(|s| s.name.as_str()).collect();
+        assert_eq!(names, vec!["docx", "pdf"]);
+
+        let docx = &result[0].agents;
+        assert_eq!(docx.len(), 1);
+        assert_eq!(docx[0].agent, AgentType::Cursor);
+        assert_eq!(docx[0].effective_scope, Scope::Project);
+        assert_eq!(
+            docx[0].project_path,
+            cursor_project.join("docx-local").to_string_lossy()
+        );
+        assert!(!docx[0].identical);
+
+        let pdf = &result[1].agents;
+        assert_eq!(pdf.len(), 1);
+        assert_eq!(pdf[0].agent, AgentType::ClaudeCode);
+        assert_eq!(pdf[0].effective_scope, Scope::Global);
+        assert!(pdf[0].identical);
+    }
+
+    #[test]
+    fn test_find_shadowing_same_directory() {
+        // 项目即 home 目录时，两个 scope 指向同一目录
+        let home = tempdir().unwrap();
+        let skills = home.path().join(".claude/skills");
+        write_skill(&skills, "pdf", "pdf", "");
+
+        let result = find_shadowing(&[(AgentType::ClaudeCode, skills.clone(), skills)]);
+        assert!(result.is_empty());
+    }
+}
@@ -2,74 +2,234 @@
 
 use serde::{Deserialize, Serialize};
 use specta::Type;
-use std::collections::HashMap;
-use std::path::Path;
-
-use super::agents::AgentType;
-use super::paths::canonical_skills_dir;
-use super::local_lock::{read_local_lock, LocalSkillLockEntry};
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use walkdir::WalkDir;
+
+use super::agents::{AgentType, DetectionContext};
+use super::disk_usage::measure_dir;
+use super::paths::{agent_skills_dir, canonical_skills_dir, is_symlink_or_junction, same_path};
+use super::local_lock::{compute_skill_folder_hash, read_local_lock, LocalSkillLockEntry, HASH_SKIP_DIRS};
 use super::skill_lock::{get_skill_from_lock, SkillLockEntry};
 use crate::error::AppError;
+use crate::models::InstallMode;
 
 /// Skill 元数据
 /// 对应 CLI: Skill (types.ts:42-49)
+///
+/// version / author / tags 也常写在 metadata 下，作为顶层字段的回退
 #[derive(Debug, Clone, Deserialize)]
 pub struct SkillMetadata {
     #[serde(default)]
     pub internal: bool,
+    #[serde(default, deserialize_with = "de_lenient_string")]
+    pub version: Option<String>,
+    #[serde(default, deserialize_with = "de_lenient_string")]
+    pub author: Option<String>,
+    #[serde(default, deserialize_with = "de_lenient_tags")]
+    pub tags: Vec<String>,
 }
 
 /// SKILL.md frontmatter 结构
 /// 对应 CLI: parseSkillMd 返回的数据结构
+///
+/// version / author / license / tags 为可选的描述性字段，
+/// 格式不符合预期时视为缺省，不会导致解析失败
 #[derive(Debug, Clone, Deserialize)]
 pub struct SkillFrontmatter {
+    // 缺失与为空统一在解析后校验，报告 SkillMdMissingField
+    #[serde(default)]
     pub name: String,
+    #[serde(default)]
     pub description: String,
     #[serde(default)]
     pub metadata: Option<SkillMetadata>,
+    #[serde(default, deserialize_with = "de_lenient_string")]
+    pub version: Option<String>,
+    #[serde(default, deserialize_with = "de_lenient_string")]
+    pub author: Option<String>,
+    #[serde(default, deserialize_with = "de_lenient_string")]
+    pub license: Option<String>,
+    #[serde(default, deserialize_with = "de_lenient_tags")]
+    pub tags: Vec<String>,
+    /// 依赖的 skills（同一来源中的 skill 名称或 `owner/repo@skill` 形式的来源），见 core/dependencies.rs
+    #[serde(default, deserialize_with = "de_lenient_tags")]
+    pub dependencies: Vec<String>,
+}
+
+/// 宽松解析字符串字段
+///
+/// - 字符串 / 数字 / 布尔值转为字符串（`version: 1.0` 会被 YAML 解析为数字）
+/// - 对象取 `name` 字段（如 `author: { name: ..., email: ... }`）
+/// - 其他格式视为缺省
+fn de_lenient_string<'de, D>(deserializer: D) -> Result<Option<String>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    let value = Option::<serde_yaml::Value>::deserialize(deserializer)?;
+    Ok(value.as_ref().and_then(|v| {
+        yaml_scalar_to_string(v).or_else(|| v.get("name").and_then(yaml_scalar_to_string))
+    }))
+}
+
+/// 宽松解析 tags：支持字符串列表或逗号分隔的字符串
+fn de_lenient_tags<'de, D>(deserializer: D) -> Result<Vec<String>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    let value = Option::<serde_yaml::Value>::deserialize(deserializer)?;
+    let tags = match value {
+        Some(serde_yaml::Value::Sequence(items)) => {
+            items.iter().filter_map(yaml_scalar_to_string).collect()
+        }
+        Some(serde_yaml::Value::String(s)) => s
+            .split(',')
+            .map(|t| t.trim().to_string())
+            .filter(|t| !t.is_empty())
+            .collect(),
+        _ => Vec::new(),
+    };
+    Ok(tags)
+}
+
+fn yaml_scalar_to_string(value: &serde_yaml::Value) -> Option<String> {
+    let s = match value {
+        serde_yaml::Value::String(s) => s.trim().to_string(),
+        serde_yaml::Value::Number(n) => n.to_string(),
+        serde_yaml::Value::Bool(b) => b.to_string(),
+        _ => return None,
+    };
+    (!s.is_empty()).then_some(s)
 }
 
 /// 解析 SKILL.md 文件
 /// 对应 CLI: parseSkillMd (skills.ts:28-58)
 pub fn parse_skill_md(path: &Path) -> Result<SkillFrontmatter, AppError> {
     let content = std::fs::read_to_string(path)?;
+    parse_skill_md_content(&content)
+}
 
-    // 检查是否以 --- 开头
-    if !content.starts_with("---") {
+/// 解析 SKILL.md 内容（不读取文件，用于远程下载的 SKILL.md）
+///
+/// frontmatter 以 `---` 包围时按 YAML 解析，以 `+++` 包围时按 TOML 解析
+pub fn parse_skill_md_content(content: &str) -> Result<SkillFrontmatter, AppError> {
+    let (delimiter, is_toml) = if content.starts_with("---") {
+        ("---", false)
+    } else if content.starts_with("+++") {
+        ("+++", true)
+    } else {
         return Err(AppError::InvalidSkillMd {
             message: "Missing frontmatter delimiter".to_string(),
         });
-    }
+    };
 
-    // 找到第二个 ---
+    // 找到第二个分隔符
     let rest = &content[3..];
-    let end_pos = rest.find("---").ok_or_else(|| {
+    let end_pos = rest.find(delimiter).ok_or_else(|| {
         AppError::InvalidSkillMd {
             message: "Unclosed frontmatter delimiter".to_string(),
         }
     })?;
 
-    // 提取 YAML 部分（跳过开头的换行符）
-    let yaml_content = rest[..end_pos].trim();
+    // 提取 frontmatter 部分（跳过开头的换行符）
+    let raw = rest[..end_pos].trim();
 
-    // 解析 YAML
-    let frontmatter: SkillFrontmatter = serde_yaml::from_str(yaml_content)?;
+    let mut frontmatter: SkillFrontmatter = if is_toml {
+        toml::from_str(raw).map_err(|e| AppError::InvalidSkillMd {
+            message: format!("Invalid TOML frontmatter: {}", e.message()),
+        })?
+    } else {
+        serde_yaml::from_str(raw)?
+    };
+
+    // 顶层缺省时回退到 metadata 下的同名字段
+    if let Some(metadata) = frontmatter.metadata.clone() {
+        frontmatter.version = frontmatter.version.or(metadata.version);
+        frontmatter.author = frontmatter.author.or(metadata.author);
+        if frontmatter.tags.is_empty() {
+            frontmatter.tags = metadata.tags;
+        }
+    }
 
     // 验证必填字段
     if frontmatter.name.is_empty() {
-        return Err(AppError::InvalidSkillMd {
-            message: "Missing name field".to_string(),
+        return Err(AppError::SkillMdMissingField {
+            field: "name".to_string(),
         });
     }
     if frontmatter.description.is_empty() {
-        return Err(AppError::InvalidSkillMd {
-            message: "Missing description field".to_string(),
+        return Err(AppError::SkillMdMissingField {
+            field: "description".to_string(),
         });
     }
 
     Ok(frontmatter)
 }
 
+/// SKILL.md 同级 metadata.json 中的附加信息（供 UI 展示，文件本身不会被安装）
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SkillSidecar {
+    pub category: Option<String>,
+    pub icon_url: Option<String>,
+}
+
+/// skill 目录中本地化 SKILL.md（`SKILL.<locale>.md`，如 `SKILL.zh.md`、`SKILL.pt-BR.md`）的 locale 列表
+///
+/// locale 需以字母开头，只含字母、数字、`-`、`_`；按字母排序
+pub fn skill_md_locales(skill_dir: &Path) -> Vec<String> {
+    let Ok(entries) = std::fs::read_dir(skill_dir) else {
+        return Vec::new();
+    };
+    let mut locales: Vec<String> = entries
+        .filter_map(|e| e.ok())
+        .filter(|e| e.file_type().is_ok_and(|t| t.is_file()))
+        .filter_map(|e| {
+            let file_name = e.file_name().to_str()?.to_string();
+            let locale = file_name.strip_prefix("SKILL.")?.strip_suffix(".md")?;
+            let valid = locale.len() <= 35
+                && locale.starts_with(|c: char| c.is_ascii_alphabetic())
+                && locale.chars().all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_');
+            valid.then(|| locale.to_string())
+        })
+        .collect();
+    locales.sort();
+    locales
+}
+
+/// locale 对应的本地化 SKILL.md 文件名
+pub fn localized_skill_md(locale: &str) -> String {
+    format!("SKILL.{}.md", locale)
+}
+
+/// 读取 skill 目录下的 metadata.json
+///
+/// 文件不存在、JSON 格式错误或字段类型不符时视为缺省，不影响 skill 发现。
+/// `iconUrl` 也接受 `icon_url` / `icon` 写法。
+pub fn read_skill_sidecar(skill_dir: &Path) -> SkillSidecar {
+    let path = skill_dir.join("metadata.json");
+    let Ok(content) = std::fs::read_to_string(&path) else {
+        return SkillSidecar::default();
+    };
+    let value: serde_json::Value = match serde_json::from_str(&content) {
+        Ok(v) => v,
+        Err(e) => {
+            log::warn!("Ignoring malformed {}: {}", path.display(), e);
+            return SkillSidecar::default();
+        }
+    };
+    let field = |keys: &[&str]| {
+        keys.iter()
+            .find_map(|k| value.get(k).and_then(|v| v.as_str()))
+            .map(str::trim)
+            .filter(|s| !s.is_empty())
+            .map(str::to_string)
+    };
+    SkillSidecar {
+        category: field(&["category"]),
+        icon_url: field(&["iconUrl", "icon_url", "icon"]),
+    }
+}
+
 /// Sanitize skill 名称
 /// 对应 CLI: sanitizeName (installer.ts:39-54)
 pub fn sanitize_name(name: &str) -> String {
@@ -110,8 +270,36 @@ pub fn sanitize_name(name: &str) -> String {
     }
 }
 
-/// Skill 范围
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Type)]
+/// skill 的安装目录名
+///
+/// 安装时目录与其他 skill 冲突而追加了后缀的，lock 中记录了 `dir_name`，
+/// 其余情况为 `sanitize_name(skill_name)`。lock 中的值不是合法目录名时忽略。
+///
+/// # Arguments
+/// * `skill_name` - skill 名称（lock key）
+/// * `is_global` - true 读取全局 lock，false 读取 `cwd` 下的项目 lock
+/// * `cwd` - 项目路径
+pub fn skill_dir_name(skill_name: &str, is_global: bool, cwd: &str) -> String {
+    let recorded = if is_global {
+        get_skill_from_lock(skill_name).ok().flatten().and_then(|e| e.dir_name)
+    } else {
+        read_local_lock(cwd)
+            .ok()
+            .and_then(|lock| lock.skills.get(skill_name).and_then(|e| e.dir_name.clone()))
+    };
+    locked_dir_name(skill_name, recorded.as_deref())
+}
+
+/// 由 lock 条目的 `dir_name` 得到安装目录名（已读取 lock 时使用，见 `skill_dir_name`）
+pub fn locked_dir_name(skill_name: &str, recorded: Option<&str>) -> String {
+    match recorded {
+        Some(d) if sanitize_name(d) == d => d.to_string(),
+        _ => sanitize_name(skill_name),
+    }
+}
+
+/// Skill 范围（排序时 Global 在前）
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize, Type)]
 #[serde(rename_all = "lowercase")]
 #[specta(rename_all = "lowercase")]
 pub enum SkillScope {
@@ -145,6 +333,34 @@ pub struct InstalledSkill {
     /// 所属 plugin 名称
     #[serde(skip_serializing_if = "Option::is_none")]
     pub plugin_name: Option<String>,
+    // 来自 SKILL.md frontmatter 的可选字段
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub version: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub author: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub license: Option<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<String>,
+    /// 占用磁盘字节数（仅 include_size 时计算）
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[specta(type = Option<f64>)]
+    pub size_bytes: Option<u64>,
+    /// 文件数量（仅 include_size 时计算）
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub file_count: Option<u32>,
+    /// 各 agent 的实际安装方式
+    /// symlink/junction 或直接读取 canonical 目录为 Symlink，独立副本为 Copy（修改不会同步）
+    pub agent_install_modes: HashMap<AgentType, InstallMode>,
+    /// 内容与 canonical 目录不一致的 Copy 模式 agents（仅 verify_copies 时计算）
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub out_of_sync_agents: Option<Vec<AgentType>>,
+    /// 安装的本地化版本（来自 lock，未选择时为 None）
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub locale: Option<String>,
+    /// 可切换的本地化版本：已安装目录中的 `SKILL.<locale>.md` 加上当前安装的 locale（按字母排序）
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub available_locales: Vec<String>,
 }
 
 impl InstalledSkill {
@@ -156,6 +372,7 @@ impl InstalledSkill {
             self.installed_at = Some(e.installed_at.clone());
             self.updated_at = Some(e.updated_at.clone());
             self.plugin_name = e.plugin_name.clone();
+            self.locale = e.locale.clone();
         }
         self
     }
@@ -164,13 +381,9 @@ impl InstalledSkill {
     pub fn with_local_lock_entry(mut self, entry: Option<&LocalSkillLockEntry>) -> Self {
         if let Some(e) = entry {
             self.source = Some(e.source.clone());
-            // local lock 没有 source_url，从 source 构造
-            self.source_url = if e.source_type == "github" {
-                Some(format!("https://github.com/{}", e.source))
-            } else {
-                Some(e.source.clone())
-            };
+            self.source_url = Some(e.source_url());
             self.plugin_name = e.plugin_name.clone();
+            self.locale = e.locale.clone();
         }
         self
     }
@@ -185,6 +398,45 @@ pub struct ListSkillsResult {
     pub skills: Vec<InstalledSkill>,
     /// 项目目录是否存在（project scope 时有意义，global 始终为 true）
     pub path_exists: bool,
+    /// 过滤前所有已安装 skills 的 tags 并集（按字母排序，供前端构建过滤菜单）
+    pub all_tags: Vec<String>,
+}
+
+/// 按 tags 过滤已安装的 skills（不区分大小写）
+///
+/// `tags` 为空时不过滤；`match_all` 为 true 时需包含全部 tags，否则包含任一即可。
+/// 没有 tags 的 skill 在指定过滤条件时不会被返回。
+pub fn filter_skills_by_tags(skills: &mut Vec<InstalledSkill>, tags: &[String], match_all: bool) {
+    let wanted: Vec<String> = tags
+        .iter()
+        .map(|t| t.trim().to_lowercase())
+        .filter(|t| !t.is_empty())
+        .collect();
+    if wanted.is_empty() {
+        return;
+    }
+    skills.retain(|skill| {
+        let has = |tag: &String| skill.tags.iter().any(|t| t.trim().to_lowercase() == *tag);
+        if match_all {
+            wanted.iter().all(has)
+        } else {
+            wanted.iter().any(has)
+        }
+    });
+}
+
+/// 所有 skills 的 tags 并集（大小写不同的 tag 合并为首次出现的写法）
+pub fn collect_skill_tags(skills: &[InstalledSkill]) -> Vec<String> {
+    let mut seen = HashSet::new();
+    let mut tags: Vec<String> = skills
+        .iter()
+        .flat_map(|s| s.tags.iter())
+        .map(|t| t.trim())
+        .filter(|t| !t.is_empty() && seen.insert(t.to_lowercase()))
+        .map(|t| t.to_string())
+        .collect();
+    tags.sort_by_key(|t| t.to_lowercase());
+    tags
 }
 
 /// 扫描目录信息
@@ -194,11 +446,202 @@ struct ScanScope {
     agent_type: Option<AgentType>,
 }
 
+/// 统计目录占用的字节数和文件数
+/// 跳过与 compute_skill_folder_hash 相同的目录（.git、node_modules）
+pub fn compute_dir_size(dir: &Path) -> (u64, u32) {
+    let size = measure_dir(dir, HASH_SKIP_DIRS);
+    (size.size_bytes, size.file_count)
+}
+
+/// skill 目录中的一个文件或子目录
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Type)]
+#[serde(rename_all = "camelCase")]
+#[specta(rename_all = "camelCase")]
+pub struct SkillFileEntry {
+    /// 相对 skill 根目录的路径（`/` 分隔）
+    pub path: String,
+    /// 文件字节数（目录为 0）
+    #[specta(type = f64)]
+    pub size_bytes: u64,
+    pub is_dir: bool,
+    /// 是否为符号链接（不会展开链接指向的目录）
+    pub is_symlink: bool,
+}
+
+/// 列出 skill 目录的文件树（按路径排序，父目录在子项之前）
+///
+/// 跳过与 compute_skill_folder_hash 相同的目录（.git、node_modules）。
+/// 符号链接不跟随：指向 skill 目录内的链接按目标类型和大小记录，
+/// 指向目录外或失效的链接只记录自身（大小为 0），不读取外部内容。
+pub fn list_skill_files(dir: &Path) -> Result<Vec<SkillFileEntry>, AppError> {
+    let root = std::fs::canonicalize(dir)?;
+    let mut files = Vec::new();
+
+    let walker = WalkDir::new(&root)
+        .min_depth(1)
+        .sort_by_file_name()
+        .into_iter()
+        .filter_entry(|e| {
+            !(e.file_type().is_dir() && HASH_SKIP_DIRS.contains(&e.file_name().to_string_lossy().as_ref()))
+        })
+        .filter_map(|e| e.ok());
+    for entry in walker {
+        let Ok(relative) = entry.path().strip_prefix(&root) else {
+            continue;
+        };
+        let path = relative.to_string_lossy().replace('\\', "/");
+
+        let is_symlink = entry.path_is_symlink();
+        let (is_dir, size_bytes) = if is_symlink {
+            match std::fs::canonicalize(entry.path()) {
+                Ok(target) if target.starts_with(&root) => {
+                    let metadata = std::fs::metadata(&target)?;
+                    (metadata.is_dir(), if metadata.is_dir() { 0 } else { metadata.len() })
+                }
+                _ => (false, 0),
+            }
+        } else if entry.file_type().is_dir() {
+            (true, 0)
+        } else {
+            (false, entry.metadata().map(|m| m.len()).unwrap_or(0))
+        };
+
+        files.push(SkillFileEntry {
+            path,
+            size_bytes,
+            is_dir,
+            is_symlink,
+        });
+    }
+
+    Ok(files)
+}
+
+/// 当前平台的文件系统是否大小写不敏感（macOS / Windows 默认如此）
+const CASE_INSENSITIVE_FS: bool = cfg!(any(target_os = "macos", target_os = "windows"));
+
+/// 已安装 skill 的去重 key
+///
+/// 大小写不敏感的文件系统上，`PDF-Tools` 和 `pdf-tools` 落在同一目录，
+/// 因此按小写 name 去重；其余平台保持按 frontmatter name 去重。
+/// 不按 sanitized name 去重：`My Skill!` 和 `my-skill` 可以通过目录后缀共存。
+fn skill_dedup_key(scope_key: &str, name: &str, case_insensitive: bool) -> String {
+    if case_insensitive {
+        format!("{}:{}", scope_key, name.to_lowercase())
+    } else {
+        format!("{}:{}", scope_key, name)
+    }
+}
+
+/// 按去重 key 合并 skill：已存在时合并 agents（去重），否则插入 `make(agents)` 构造的新 skill
+fn upsert_skill(
+    skills_map: &mut HashMap<String, InstalledSkill>,
+    key: String,
+    agents: Vec<AgentType>,
+    make: impl FnOnce(Vec<AgentType>) -> InstalledSkill,
+) {
+    match skills_map.get_mut(&key) {
+        Some(existing) => {
+            for agent in agents {
+                if !existing.agents.contains(&agent) {
+                    existing.agents.push(agent);
+                }
+            }
+        }
+        None => {
+            skills_map.insert(key, make(agents));
+        }
+    }
+}
+
+/// 查找 agent 目录中该 skill 的安装位置（依次尝试 canonical 目录名和 sanitize 后的名称）
+fn find_agent_skill_dir(skill: &InstalledSkill, agent: &AgentType, cwd: &str) -> Option<PathBuf> {
+    let is_global = matches!(skill.scope, SkillScope::Global);
+    let agent_base = agent_skills_dir(agent, is_global, cwd)?;
+    let dir_name = Path::new(&skill.canonical_path)
+        .file_name()
+        .map(|n| n.to_string_lossy().to_string())
+        .unwrap_or_default();
+    let sanitized_name = sanitize_name(&skill.name);
+
+    [&dir_name, &sanitized_name]
+        .iter()
+        .map(|name| agent_base.join(name))
+        .find(|p| p.symlink_metadata().is_ok())
+}
+
+/// 检测每个 agent 的实际安装方式
+///
+/// agent 目录是 symlink/junction 或就是 canonical 目录（Universal Agent）时为 Symlink，
+/// 否则为 Copy；找不到 agent 目录的 agent 不出现在结果中。
+fn detect_agent_install_modes(skill: &InstalledSkill, cwd: &str) -> HashMap<AgentType, InstallMode> {
+    let canonical_path = Path::new(&skill.canonical_path);
+
+    let mut modes = HashMap::new();
+    for agent in &skill.agents {
+        let Some(agent_dir) = find_agent_skill_dir(skill, agent, cwd) else {
+            continue;
+        };
+
+        let mode = if is_symlink_or_junction(&agent_dir) || same_path(&agent_dir, canonical_path) {
+            InstallMode::Symlink
+        } else {
+            InstallMode::Copy
+        };
+        modes.insert(*agent, mode);
+    }
+
+    modes
+}
+
+/// 可切换的本地化版本
+///
+/// 安装本地化版本时 `SKILL.<locale>.md` 已被替换为 SKILL.md，因此补上 lock 中记录的 locale
+fn available_locales(skill: &InstalledSkill) -> Vec<String> {
+    let mut locales = skill_md_locales(Path::new(&skill.canonical_path));
+    if let Some(locale) = &skill.locale {
+        if !locales.contains(locale) {
+            locales.push(locale.clone());
+            locales.sort();
+        }
+    }
+    locales
+}
+
+/// 找出内容已与 canonical 目录不一致的 Copy 模式 agents
+///
+/// 依据 `agent_install_modes`，对 canonical 目录和每个副本分别计算 `compute_skill_folder_hash`；
+/// 无法计算哈希的目录不做判断。
+fn detect_out_of_sync_agents(skill: &InstalledSkill, cwd: &str) -> Vec<AgentType> {
+    let Ok(canonical_hash) = compute_skill_folder_hash(Path::new(&skill.canonical_path)) else {
+        return Vec::new();
+    };
+
+    skill
+        .agents
+        .iter()
+        .filter(|agent| skill.agent_install_modes.get(*agent) == Some(&InstallMode::Copy))
+        .filter(|agent| {
+            find_agent_skill_dir(skill, agent, cwd)
+                .and_then(|dir| compute_skill_folder_hash(&dir).ok())
+                .is_some_and(|hash| hash != canonical_hash)
+        })
+        .copied()
+        .collect()
+}
+
 /// 列出已安装的 skills
 /// 对应 CLI: listInstalledSkills (installer.ts:797-1007)
+///
+/// `include_size` 为 true 时统计每个 skill canonical 目录的大小和文件数（额外 IO）；
+/// `verify_copies` 为 true 时比较 Copy 模式 agent 副本与 canonical 目录的内容哈希（额外 IO）；
+/// `agents` 指定时扫描这些 agents 的目录而不做检测（用于预先安装到尚未检测到的 agent）
 pub fn list_installed_skills(
     scope: Option<SkillScope>,
     cwd: &str,
+    include_size: bool,
+    verify_copies: bool,
+    agents: Option<&[AgentType]>,
 ) -> Result<Vec<InstalledSkill>, AppError> {
     let mut skills_map: HashMap<String, InstalledSkill> = HashMap::new();
     let mut scopes: Vec<ScanScope> = Vec::new();
@@ -206,8 +649,21 @@ pub fn list_installed_skills(
     // 预读项目级 local lock（如果存在）
     let local_lock = read_local_lock(cwd).ok();
 
-    // 检测已安装的 agents
-    let detected_agents = AgentType::detect_installed();
+    // 检测已安装的 agents：global 只看全局信号，避免进程 cwd 下的项目目录造成误判
+    let (detected_global, detected_project) = match agents {
+        Some(agents) => (agents.to_vec(), agents.to_vec()),
+        None => (
+            AgentType::detect_installed(&DetectionContext::GlobalOnly),
+            AgentType::detect_installed(&DetectionContext::for_scope(false, cwd)),
+        ),
+    };
+    let detected_agents = |global: bool| {
+        if global {
+            &detected_global
+        } else {
+            &detected_project
+        }
+    };
 
     // 确定要扫描的 scope 类型
     let scope_types: Vec<bool> = match scope {
@@ -227,22 +683,17 @@ pub fn list_installed_skills(
         });
 
         // 添加每个已安装 agent 的 skills 目录
-        for agent_type in &detected_agents {
-            let config = agent_type.config();
-
+        for agent_type in detected_agents(*is_global) {
             // 跳过不支持 global 安装的 agent
-            if *is_global && config.global_skills_dir.is_none() {
+            let Some(agent_dir) = agent_skills_dir(agent_type, *is_global, cwd) else {
                 continue;
-            }
-
-            let agent_dir = if *is_global {
-                config.global_skills_dir.clone().unwrap()
-            } else {
-                std::path::PathBuf::from(cwd).join(config.skills_dir)
             };
 
-            // 避免重复路径
-            if !scopes.iter().any(|s| s.path == agent_dir && s.global == *is_global) {
+            // 避免重复路径（解析 symlink 后比较，如 $HOME 本身是 symlink）
+            if !scopes
+                .iter()
+                .any(|s| s.global == *is_global && same_path(&s.path, &agent_dir))
+            {
                 scopes.push(ScanScope {
                     global: *is_global,
                     path: agent_dir,
@@ -292,15 +743,11 @@ pub fn list_installed_skills(
             }
 
             let scope_key = if scope_info.global { "global" } else { "project" };
-            let skill_key = format!("{}:{}", scope_key, frontmatter.name);
+            let skill_key = skill_dedup_key(scope_key, &frontmatter.name, CASE_INSENSITIVE_FS);
 
             // 如果是 agent 特定目录，直接归属于该 agent
             if let Some(agent_type) = scope_info.agent_type {
-                if let Some(existing) = skills_map.get_mut(&skill_key) {
-                    if !existing.agents.contains(&agent_type) {
-                        existing.agents.push(agent_type);
-                    }
-                } else {
+                upsert_skill(&mut skills_map, skill_key, vec![agent_type], |agents| {
                     let skill = InstalledSkill {
                         name: frontmatter.name.clone(),
                         description: frontmatter.description,
@@ -311,27 +758,35 @@ pub fn list_installed_skills(
                         } else {
                             SkillScope::Project
                         },
-                        agents: vec![agent_type],
+                        agents,
                         source: None,
                         source_url: None,
                         installed_at: None,
                         updated_at: None,
                         has_update: None,
                         plugin_name: None,
+                        version: frontmatter.version,
+                        author: frontmatter.author,
+                        license: frontmatter.license,
+                        tags: frontmatter.tags,
+                        size_bytes: None,
+                        file_count: None,
+                        agent_install_modes: HashMap::new(),
+                        out_of_sync_agents: None,
+                        locale: None,
+                        available_locales: Vec::new(),
                     };
 
                     // 根据 scope 从对应的 lock 文件填充元数据
-                    let skill = if scope_info.global {
+                    if scope_info.global {
                         let lock_entry = get_skill_from_lock(&frontmatter.name).ok().flatten();
                         skill.with_lock_entry(lock_entry.as_ref())
                     } else {
                         let local_entry = local_lock.as_ref()
                             .and_then(|l| l.skills.get(&frontmatter.name));
                         skill.with_local_lock_entry(local_entry)
-                    };
-
-                    skills_map.insert(skill_key, skill);
-                }
+                    }
+                });
                 continue;
             }
 
@@ -341,17 +796,9 @@ pub fn list_installed_skills(
             let dir_name = path.file_name().unwrap().to_string_lossy().to_string();
             let mut installed_agents: Vec<AgentType> = Vec::new();
 
-            for agent_type in &detected_agents {
-                let config = agent_type.config();
-
-                if scope_info.global && config.global_skills_dir.is_none() {
+            for agent_type in detected_agents(scope_info.global) {
+                let Some(agent_base) = agent_skills_dir(agent_type, scope_info.global, cwd) else {
                     continue;
-                }
-
-                let agent_base = if scope_info.global {
-                    config.global_skills_dir.clone().unwrap()
-                } else {
-                    std::path::PathBuf::from(cwd).join(config.skills_dir)
                 };
 
                 // 尝试多种目录名匹配
@@ -397,13 +844,7 @@ pub fn list_installed_skills(
             }
 
             // 更新或插入 skill
-            if let Some(existing) = skills_map.get_mut(&skill_key) {
-                for agent in installed_agents {
-                    if !existing.agents.contains(&agent) {
-                        existing.agents.push(agent);
-                    }
-                }
-            } else {
+            upsert_skill(&mut skills_map, skill_key, installed_agents, |agents| {
                 let skill = InstalledSkill {
                     name: frontmatter.name.clone(),
                     description: frontmatter.description,
@@ -414,31 +855,67 @@ pub fn list_installed_skills(
                     } else {
                         SkillScope::Project
                     },
-                    agents: installed_agents,
+                    agents,
                     source: None,
                     source_url: None,
                     installed_at: None,
                     updated_at: None,
                     has_update: None,
                     plugin_name: None,
+                    version: frontmatter.version,
+                    author: frontmatter.author,
+                    license: frontmatter.license,
+                    tags: frontmatter.tags,
+                    size_bytes: None,
+                    file_count: None,
+                    agent_install_modes: HashMap::new(),
+                    out_of_sync_agents: None,
+                    locale: None,
+                    available_locales: Vec::new(),
                 };
 
                 // 根据 scope 从对应的 lock 文件填充元数据
-                let skill = if scope_info.global {
+                if scope_info.global {
                     let lock_entry = get_skill_from_lock(&frontmatter.name).ok().flatten();
                     skill.with_lock_entry(lock_entry.as_ref())
                 } else {
                     let local_entry = local_lock.as_ref()
                         .and_then(|l| l.skills.get(&frontmatter.name));
                     skill.with_local_lock_entry(local_entry)
-                };
+                }
+            });
+        }
+    }
 
-                skills_map.insert(skill_key, skill);
-            }
+    // HashMap 遍历顺序不固定，按 scope、名称排序保证每次刷新顺序一致
+    let mut skills: Vec<InstalledSkill> = skills_map.into_values().collect();
+    skills.sort_by(|a, b| {
+        a.scope
+            .cmp(&b.scope)
+            .then_with(|| a.name.to_lowercase().cmp(&b.name.to_lowercase()))
+            .then_with(|| a.name.cmp(&b.name))
+    });
+
+    for skill in &mut skills {
+        skill.agent_install_modes = detect_agent_install_modes(skill, cwd);
+        skill.available_locales = available_locales(skill);
+    }
+
+    if include_size {
+        for skill in &mut skills {
+            let (size_bytes, file_count) = compute_dir_size(Path::new(&skill.canonical_path));
+            skill.size_bytes = Some(size_bytes);
+            skill.file_count = Some(file_count);
+        }
+    }
+
+    if verify_copies {
+        for skill in &mut skills {
+            skill.out_of_sync_agents = Some(detect_out_of_sync_agents(skill, cwd));
         }
     }
 
-    Ok(skills_map.into_values().collect())
+    Ok(skills)
 }
 
 #[cfg(test)]
@@ -466,6 +943,48 @@ Content here.
         assert_eq!(result.description, "A test skill");
     }
 
+    #[test]
+    fn test_parse_toml_frontmatter() {
+        let content = r#"+++
+name = "toml-skill"
+description = "A TOML skill"
+version = "1.0"
+tags = ["a", "b"]
++++
+
+# TOML Skill
+"#;
+        let mut file = NamedTempFile::new().unwrap();
+        file.write_all(content.as_bytes()).unwrap();
+
+        let result = parse_skill_md(file.path()).unwrap();
+        assert_eq!(result.name, "toml-skill");
+        assert_eq!(result.description, "A TOML skill");
+        assert_eq!(result.version.as_deref(), Some("1.0"));
+        assert_eq!(result.tags, vec!["a".to_string(), "b".to_string()]);
+
+        let err = parse_skill_md_content("+++\nname = \n+++\n").unwrap_err();
+        assert!(matches!(err, AppError::InvalidSkillMd { .. }));
+
+        // 缺少必填字段报告为 SkillMdMissingField（与 YAML 一致）
+        let err = parse_skill_md_content("+++\nname = \"no-description\"\n+++\n").unwrap_err();
+        assert!(matches!(err, AppError::SkillMdMissingField { ref field } if field == "description"));
+        let err = parse_skill_md_content("---\ndescription: d\n---\n").unwrap_err();
+        assert!(matches!(err, AppError::SkillMdMissingField { ref field } if field == "name"));
+    }
+
+    #[test]
+    fn test_parse_dependencies() {
+        let list = parse_skill_md_content(
+            "---\nname: app\ndescription: d\ndependencies:\n  - lib\n  - owner/repo@util\n---\n",
+        )
+        .unwrap();
+        assert_eq!(list.dependencies, vec!["lib", "owner/repo@util"]);
+
+        let inline = parse_skill_md_content("---\nname: app\ndescription: d\ndependencies: lib, util\n---\n").unwrap();
+        assert_eq!(inline.dependencies, vec!["lib", "util"]);
+    }
+
     #[test]
     fn test_parse_skill_md_with_metadata() {
         let content = r#"---
@@ -485,6 +1004,49 @@ Content.
         assert!(result.metadata.unwrap().internal);
     }
 
+    #[test]
+    fn test_parse_skill_md_optional_fields() {
+        let content = r#"---
+name: pdf
+description: PDF tools
+version: 2.1
+author:
+  name: Jane Doe
+  email: jane@example.com
+license: MIT
+tags: [documents, pdf]
+homepage: https://example.com
+---
+"#;
+        let result = parse_skill_md_content(content).unwrap();
+        assert_eq!(result.version.as_deref(), Some("2.1"));
+        assert_eq!(result.author.as_deref(), Some("Jane Doe"));
+        assert_eq!(result.license.as_deref(), Some("MIT"));
+        assert_eq!(result.tags, vec!["documents", "pdf"]);
+
+        // metadata 下的字段作为回退；格式异常的字段视为缺省
+        let content = r#"---
+name: pdf
+description: PDF tools
+license: [MIT, Apache-2.0]
+tags: "documents, pdf"
+metadata:
+  version: "1.0.0"
+  author: Acme
+---
+"#;
+        let result = parse_skill_md_content(content).unwrap();
+        assert_eq!(result.version.as_deref(), Some("1.0.0"));
+        assert_eq!(result.author.as_deref(), Some("Acme"));
+        assert_eq!(result.license, None);
+        assert_eq!(result.tags, vec!["documents", "pdf"]);
+
+        // 没有可选字段的 SKILL.md 保持不变
+        let result = parse_skill_md_content("---\nname: a\ndescription: b\n---\n").unwrap();
+        assert_eq!(result.version, None);
+        assert!(result.tags.is_empty());
+    }
+
     #[test]
     fn test_parse_missing_frontmatter() {
         let content = "# No frontmatter\n\nJust content.";
@@ -495,6 +1057,312 @@ Content.
         assert!(result.is_err());
     }
 
+    #[test]
+    fn test_case_variants_collapse_on_case_insensitive_fs() {
+        let installed = |name: &str, agents: Vec<AgentType>| InstalledSkill {
+            name: name.to_string(),
+            description: "PDF tools".to_string(),
+            path: "/skills/pdf-tools".to_string(),
+            canonical_path: "/skills/pdf-tools".to_string(),
+            scope: SkillScope::Global,
+            agents,
+            source: None,
+            source_url: None,
+            installed_at: None,
+            updated_at: None,
+            has_update: None,
+            plugin_name: None,
+            version: None,
+            author: None,
+            license: None,
+            tags: Vec::new(),
+            size_bytes: None,
+            file_count: None,
+            agent_install_modes: HashMap::new(),
+            out_of_sync_agents: None,
+            locale: None,
+            available_locales: Vec::new(),
+        };
+
+        let mut skills_map: HashMap<String, InstalledSkill> = HashMap::new();
+        for (name, agent) in [
+            ("PDF-Tools", AgentType::ClaudeCode),
+            ("pdf-tools", AgentType::Cursor),
+            ("pdf-tools", AgentType::ClaudeCode),
+        ] {
+            let key = skill_dedup_key("global", name, true);
+            upsert_skill(&mut skills_map, key, vec![agent], |agents| installed(name, agents));
+        }
+
+        assert_eq!(skills_map.len(), 1);
+        let skill = skills_map.values().next().unwrap();
+        assert_eq!(skill.agents, vec![AgentType::ClaudeCode, AgentType::Cursor]);
+
+        // 大小写敏感的文件系统上仍是两个不同的 skill
+        assert_ne!(
+            skill_dedup_key("global", "PDF-Tools", false),
+            skill_dedup_key("global", "pdf-tools", false)
+        );
+        // 不同 scope 不会合并
+        assert_ne!(
+            skill_dedup_key("global", "pdf-tools", true),
+            skill_dedup_key("project", "pdf-tools", true)
+        );
+        // sanitize 后相同但 name 不同的 skill 安装在不同目录，不合并
+        assert_ne!(
+            skill_dedup_key("global", "My Skill!", true),
+            skill_dedup_key("global", "my-skill", true)
+        );
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_available_locales_include_installed_locale() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("SKILL.md"), "---\nname: demo\ndescription: d\n---\n").unwrap();
+        std::fs::write(dir.path().join("SKILL.ja.md"), "---\nname: demo\ndescription: d\n---\n").unwrap();
+        let mut skill = InstalledSkill {
+            name: "demo".to_string(),
+            description: "d".to_string(),
+            path: dir.path().to_string_lossy().to_string(),
+            canonical_path: dir.path().to_string_lossy().to_string(),
+            scope: SkillScope::Project,
+            agents: Vec::new(),
+            source: None,
+            source_url: None,
+            installed_at: None,
+            updated_at: None,
+            has_update: None,
+            plugin_name: None,
+            version: None,
+            author: None,
+            license: None,
+            tags: Vec::new(),
+            size_bytes: None,
+            file_count: None,
+            agent_install_modes: HashMap::new(),
+            out_of_sync_agents: None,
+            locale: None,
+            available_locales: Vec::new(),
+        };
+        assert_eq!(available_locales(&skill), vec!["ja".to_string()]);
+
+        // 安装 zh 版本后 SKILL.zh.md 已替换为 SKILL.md，仍应可选
+        skill.locale = Some("zh".to_string());
+        assert_eq!(available_locales(&skill), vec!["ja".to_string(), "zh".to_string()]);
+    }
+
+    #[test]
+    fn test_detect_agent_install_modes() {
+        let project = tempfile::tempdir().unwrap();
+        let cwd = project.path().to_string_lossy().to_string();
+        let canonical = project.path().join(".agents/skills/demo");
+        std::fs::create_dir_all(&canonical).unwrap();
+
+        // Claude Code: symlink 到 canonical
+        std::fs::create_dir_all(project.path().join(".claude/skills")).unwrap();
+        std::os::unix::fs::symlink(&canonical, project.path().join(".claude/skills/demo")).unwrap();
+        // Augment: 独立副本
+        std::fs::create_dir_all(project.path().join(".augment/skills/demo")).unwrap();
+
+        let skill = InstalledSkill {
+            name: "demo".to_string(),
+            description: "Demo".to_string(),
+            path: canonical.to_string_lossy().to_string(),
+            canonical_path: canonical.to_string_lossy().to_string(),
+            scope: SkillScope::Project,
+            agents: vec![AgentType::ClaudeCode, AgentType::Augment, AgentType::Cursor],
+            source: None,
+            source_url: None,
+            installed_at: None,
+            updated_at: None,
+            has_update: None,
+            plugin_name: None,
+            version: None,
+            author: None,
+            license: None,
+            tags: Vec::new(),
+            size_bytes: None,
+            file_count: None,
+            agent_install_modes: HashMap::new(),
+            out_of_sync_agents: None,
+            locale: None,
+            available_locales: Vec::new(),
+        };
+
+        let modes = detect_agent_install_modes(&skill, &cwd);
+        assert_eq!(modes.get(&AgentType::ClaudeCode), Some(&InstallMode::Symlink));
+        assert_eq!(modes.get(&AgentType::Augment), Some(&InstallMode::Copy));
+        // Universal Agent 直接读取 canonical 目录
+        assert_eq!(modes.get(&AgentType::Cursor), Some(&InstallMode::Symlink));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_list_installed_skills_verify_copies() {
+        let project = tempfile::tempdir().unwrap();
+        let cwd = project.path().to_string_lossy().to_string();
+        let content = "---\nname: demo\ndescription: d\n---\nBody";
+        let canonical = project.path().join(".agents/skills/demo");
+        std::fs::create_dir_all(&canonical).unwrap();
+        std::fs::write(canonical.join("SKILL.md"), content).unwrap();
+
+        // Claude Code: symlink；Augment / Windsurf: 独立副本，其中 Windsurf 的副本被修改过
+        std::fs::create_dir_all(project.path().join(".claude/skills")).unwrap();
+        std::os::unix::fs::symlink(&canonical, project.path().join(".claude/skills/demo")).unwrap();
+        for agent_dir in [".augment/skills/demo", ".windsurf/skills/demo"] {
+            let dir = project.path().join(agent_dir);
+            std::fs::create_dir_all(&dir).unwrap();
+            std::fs::write(dir.join("SKILL.md"), content).unwrap();
+        }
+        std::fs::write(project.path().join(".windsurf/skills/demo/extra.md"), "edited").unwrap();
+
+        let agents = [AgentType::ClaudeCode, AgentType::Augment, AgentType::Windsurf];
+        let skills =
+            list_installed_skills(Some(SkillScope::Project), &cwd, false, true, Some(&agents)).unwrap();
+        assert_eq!(skills.len(), 1);
+        assert_eq!(skills[0].out_of_sync_agents, Some(vec![AgentType::Windsurf]));
+
+        // 未开启时不计算
+        let skills =
+            list_installed_skills(Some(SkillScope::Project), &cwd, false, false, Some(&agents)).unwrap();
+        assert_eq!(skills[0].out_of_sync_agents, None);
+    }
+
+    #[test]
+    fn test_list_installed_skills_stable_order() {
+        let project = tempfile::tempdir().unwrap();
+        let cwd = project.path().to_string_lossy().to_string();
+        let canonical = project.path().join(".agents/skills");
+        for name in ["zeta", "Alpha", "mike", "bravo", "echo", "kilo", "delta"] {
+            let dir = canonical.join(name.to_lowercase());
+            std::fs::create_dir_all(&dir).unwrap();
+            std::fs::write(
+                dir.join("SKILL.md"),
+                format!("---\nname: {}\ndescription: d\n---\n", name),
+            )
+            .unwrap();
+        }
+
+        let names = || -> Vec<String> {
+            list_installed_skills(Some(SkillScope::Project), &cwd, false, false, None)
+                .unwrap()
+                .into_iter()
+                .map(|s| s.name)
+                .collect()
+        };
+        let first = names();
+        assert_eq!(first, vec!["Alpha", "bravo", "delta", "echo", "kilo", "mike", "zeta"]);
+        assert_eq!(names(), first);
+    }
+
+    #[test]
+    fn test_list_installed_skills_with_given_agents() {
+        let project = tempfile::tempdir().unwrap();
+        let cwd = project.path().to_string_lossy().to_string();
+        let dir = project.path().join(".augment/skills/pre");
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(dir.join("SKILL.md"), "---\nname: pre\ndescription: d\n---\n").unwrap();
+
+        let names = |agents: &[AgentType]| -> Vec<String> {
+            list_installed_skills(Some(SkillScope::Project), &cwd, false, false, Some(agents))
+                .unwrap()
+                .into_iter()
+                .map(|s| s.name)
+                .collect()
+        };
+        // 传入 agents 时不做检测，只扫描给定 agents 的目录
+        assert!(names(&[]).is_empty());
+        assert_eq!(names(&[AgentType::Augment]), vec!["pre"]);
+    }
+
+    #[test]
+    fn test_filter_skills_by_tags() {
+        let project = tempfile::tempdir().unwrap();
+        let cwd = project.path().to_string_lossy().to_string();
+        let canonical = project.path().join(".agents/skills");
+        for (name, tags) in [
+            ("pdf", "tags: [documents, PDF]"),
+            ("docx", "tags: documents, office"),
+            ("untagged", ""),
+        ] {
+            let dir = canonical.join(name);
+            std::fs::create_dir_all(&dir).unwrap();
+            std::fs::write(
+                dir.join("SKILL.md"),
+                format!("---\nname: {}\ndescription: d\n{}\n---\n", name, tags),
+            )
+            .unwrap();
+        }
+
+        let skills = list_installed_skills(Some(SkillScope::Project), &cwd, false, false, None).unwrap();
+        assert_eq!(collect_skill_tags(&skills), vec!["documents", "office", "PDF"]);
+
+        let filtered = |tags: &[&str], match_all: bool| -> Vec<String> {
+            let mut skills = skills.clone();
+            let tags: Vec<String> = tags.iter().map(|t| t.to_string()).collect();
+            filter_skills_by_tags(&mut skills, &tags, match_all);
+            skills.into_iter().map(|s| s.name).collect()
+        };
+        assert_eq!(filtered(&[], false), vec!["docx", "pdf", "untagged"]);
+        assert_eq!(filtered(&["Documents"], false), vec!["docx", "pdf"]);
+        assert_eq!(filtered(&["pdf", "office"], false), vec!["docx", "pdf"]);
+        assert_eq!(filtered(&["documents", "pdf"], true), vec!["pdf"]);
+        assert!(filtered(&["missing"], false).is_empty());
+    }
+
+    #[test]
+    fn test_compute_dir_size() {
+        let temp = tempfile::tempdir().unwrap();
+        let dir = temp.path();
+        std::fs::write(dir.join("SKILL.md"), "12345").unwrap();
+        std::fs::create_dir_all(dir.join("scripts")).unwrap();
+        std::fs::write(dir.join("scripts/run.sh"), "123").unwrap();
+        // 与哈希计算一致，跳过 .git 和 node_modules
+        std::fs::create_dir_all(dir.join(".git")).unwrap();
+        std::fs::write(dir.join(".git/config"), "ignored").unwrap();
+        std::fs::create_dir_all(dir.join("node_modules/pkg")).unwrap();
+        std::fs::write(dir.join("node_modules/pkg/index.js"), "ignored").unwrap();
+
+        assert_eq!(compute_dir_size(dir), (8, 2));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_list_skill_files() {
+        use std::os::unix::fs::symlink;
+
+        let temp = tempfile::tempdir().unwrap();
+        let outside = temp.path().join("secret.txt");
+        std::fs::write(&outside, "outside the skill").unwrap();
+        let dir = temp.path().join("skill");
+        std::fs::create_dir_all(dir.join("scripts")).unwrap();
+        std::fs::write(dir.join("SKILL.md"), "12345").unwrap();
+        std::fs::write(dir.join("scripts/run.sh"), "123").unwrap();
+        std::fs::create_dir_all(dir.join("node_modules/pkg")).unwrap();
+        std::fs::write(dir.join("node_modules/pkg/index.js"), "ignored").unwrap();
+        // 目录内的链接按目标记录但不展开；目录外的链接不读取目标
+        symlink(dir.join("scripts"), dir.join("bin")).unwrap();
+        symlink(&outside, dir.join("leak.txt")).unwrap();
+
+        let entry = |path: &str, size_bytes: u64, is_dir: bool, is_symlink: bool| SkillFileEntry {
+            path: path.to_string(),
+            size_bytes,
+            is_dir,
+            is_symlink,
+        };
+        assert_eq!(
+            list_skill_files(&dir).unwrap(),
+            vec![
+                entry("SKILL.md", 5, false, false),
+                entry("bin", 0, true, true),
+                entry("leak.txt", 0, false, true),
+                entry("scripts", 0, true, false),
+                entry("scripts/run.sh", 3, false, false),
+            ]
+        );
+    }
+
     #[test]
     fn test_sanitize_name_basic() {
         assert_eq!(sanitize_name("Hello World"), "hello-world");
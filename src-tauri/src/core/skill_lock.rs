@@ -2,13 +2,15 @@
 
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
+use std::path::Path;
 
+use super::atomic_write::with_locked_file;
 use super::paths::PATHS;
 use crate::error::AppError;
 
 /// Lock 文件版本号
 /// 对应 CLI: CURRENT_VERSION = 3 (skill-lock.ts:9)
-const CURRENT_VERSION: u32 = 3;
+pub const CURRENT_VERSION: u32 = 3;
 
 /// Skill Lock 条目
 /// 对应 CLI: SkillLockEntry (skill-lock.ts:14-33)
@@ -33,6 +35,20 @@ pub struct SkillLockEntry {
     /// 所属 plugin 名称
     #[serde(skip_serializing_if = "Option::is_none")]
     pub plugin_name: Option<String>,
+    /// GUI 扩展字段：安装时 SKILL.md 中的 version（CLI 会忽略此字段）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub version: Option<String>,
+    /// GUI 扩展字段：安装目录名，仅在与 `sanitize_name(name)` 不同时记录
+    /// （与其他 skill 的目录冲突时追加了数字后缀，CLI 会忽略此字段）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub dir_name: Option<String>,
+    /// GUI 扩展字段：安装的本地化版本（`SKILL.<locale>.md` 的 locale），更新时沿用（CLI 会忽略此字段）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub locale: Option<String>,
+    /// GUI 扩展字段：已脱离 canonical 目录的 agents（见 detach_skill），
+    /// 更新和修复时不写入这些 agents 的独立副本（CLI 会忽略此字段）
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub detached_agents: Vec<String>,
 }
 
 /// 已忽略的提示
@@ -89,6 +105,16 @@ pub fn get_scoped_lock_path(project_path: Option<&str>) -> std::path::PathBuf {
     }
 }
 
+/// 解析 skill-lock.json 内容（无法解析时返回空 lock）
+fn parse_skill_lock(content: &str) -> SkillLockFile {
+    match serde_json::from_str::<SkillLockFile>(content) {
+        // 版本检查：旧版本返回空（与 CLI 行为一致）
+        // 对应 CLI: skill-lock.ts 第 84-86 行
+        Ok(lock) if lock.version >= CURRENT_VERSION => lock,
+        _ => SkillLockFile::empty(),
+    }
+}
+
 /// 读取 skill-lock.json
 /// 对应 CLI: readSkillLock (skill-lock.ts:70-93)
 pub fn read_skill_lock() -> Result<SkillLockFile, AppError> {
@@ -99,16 +125,7 @@ pub fn read_skill_lock() -> Result<SkillLockFile, AppError> {
     }
 
     let content = std::fs::read_to_string(&path)?;
-    let lock: SkillLockFile = match serde_json::from_str(&content) {
-        Ok(l) => l,
-        Err(_) => return Ok(SkillLockFile::empty()),
-    };
-
-    // 版本检查：旧版本返回空（与 CLI 行为一致）
-    // 对应 CLI: skill-lock.ts 第 84-86 行
-    if lock.version < CURRENT_VERSION {
-        return Ok(SkillLockFile::empty());
-    }
+    let lock = parse_skill_lock(&content);
 
     Ok(lock)
 }
@@ -120,14 +137,7 @@ pub fn read_scoped_lock(project_path: Option<&str>) -> Result<SkillLockFile, App
         return Ok(SkillLockFile::empty());
     }
     let content = std::fs::read_to_string(&path)?;
-    let lock: SkillLockFile = match serde_json::from_str(&content) {
-        Ok(l) => l,
-        Err(_) => return Ok(SkillLockFile::empty()),
-    };
-    if lock.version < CURRENT_VERSION {
-        return Ok(SkillLockFile::empty());
-    }
-    Ok(lock)
+    Ok(parse_skill_lock(&content))
 }
 
 /// 获取指定 skill 的 lock 条目
@@ -144,86 +154,63 @@ pub fn get_all_locked_skills() -> Result<HashMap<String, SkillLockEntry>, AppErr
     Ok(lock.skills)
 }
 
-/// 写入 skill-lock.json
-/// 对应 CLI: writeSkillLock (skill-lock.ts:99-108)
-pub fn write_skill_lock(lock: &SkillLockFile) -> Result<(), AppError> {
-    let lock_path = get_skill_lock_path();
-
-    // 确保目录存在
-    if let Some(parent) = lock_path.parent() {
-        std::fs::create_dir_all(parent)?;
-    }
-
-    // 序列化为 pretty JSON 并写入
-    let content = serde_json::to_string_pretty(lock)?;
-    std::fs::write(&lock_path, content)?;
-
-    Ok(())
+/// 在文件锁内读取、修改并写回 skill-lock.json
+///
+/// 读取到写回期间持有文件锁，同时进行的安装 / 更新不会覆盖彼此的条目；`update` 没有修改 lock 时不写入
+pub fn update_skill_lock<T>(update: impl FnOnce(&mut SkillLockFile) -> T) -> Result<T, AppError> {
+    update_scoped_lock(None, update)
 }
 
-/// 写入指定 scope 的 skill-lock.json
-pub fn write_scoped_lock(
-    lock: &SkillLockFile,
+/// 同 `update_skill_lock`，作用于指定 scope 的 skill-lock.json
+pub fn update_scoped_lock<T>(
     project_path: Option<&str>,
-) -> Result<(), AppError> {
-    let lock_path = get_scoped_lock_path(project_path);
-    if let Some(parent) = lock_path.parent() {
-        std::fs::create_dir_all(parent)?;
-    }
-    let content = serde_json::to_string_pretty(lock)?;
-    std::fs::write(&lock_path, content)?;
-    Ok(())
+    update: impl FnOnce(&mut SkillLockFile) -> T,
+) -> Result<T, AppError> {
+    update_lock_file(&get_scoped_lock_path(project_path), update)
+}
+
+/// 同 `update_skill_lock`，作用于指定路径的 lock 文件
+pub(crate) fn update_lock_file<T>(
+    path: &Path,
+    update: impl FnOnce(&mut SkillLockFile) -> T,
+) -> Result<T, AppError> {
+    with_locked_file(path, |content| {
+        let mut lock = content
+            .as_deref()
+            .map(parse_skill_lock)
+            .unwrap_or_else(SkillLockFile::empty);
+        let before = serde_json::to_string_pretty(&lock)?;
+        let value = update(&mut lock);
+        let after = serde_json::to_string_pretty(&lock)?;
+        Ok(((after != before).then_some(after), value))
+    })
 }
 
 /// 添加或更新 skill 到 lock 文件
 /// 对应 CLI: addSkillToLock (skill-lock.ts:227-242)
-pub fn add_skill_to_lock(
-    skill_name: &str,
-    source: &str,
-    source_type: &str,
-    source_url: &str,
-    skill_path: Option<&str>,
-    skill_folder_hash: &str,
-    plugin_name: Option<&str>,
-) -> Result<(), AppError> {
-    let mut lock = read_skill_lock().unwrap_or_else(|_| SkillLockFile::empty());
-
+///
+/// `entry.installed_at` / `entry.updated_at` 由此函数填写（保留原有的 installed_at）；
+/// `entry.dir_name` 仅在实际安装目录名与 `sanitize_name(skill_name)` 不同时记录
+pub fn add_skill_to_lock(skill_name: &str, mut entry: SkillLockEntry) -> Result<(), AppError> {
     let now = chrono::Utc::now().format("%Y-%m-%dT%H:%M:%S%.3fZ").to_string();
 
-    // 保留原有的 installed_at
-    let installed_at = lock
-        .skills
-        .get(skill_name)
-        .map(|e| e.installed_at.clone())
-        .unwrap_or_else(|| now.clone());
-
-    let entry = SkillLockEntry {
-        source: source.to_string(),
-        source_type: source_type.to_string(),
-        source_url: source_url.to_string(),
-        skill_path: skill_path.map(|s| s.to_string()),
-        skill_folder_hash: skill_folder_hash.to_string(),
-        installed_at,
-        updated_at: now,
-        plugin_name: plugin_name.map(|s| s.to_string()),
-    };
-
-    lock.skills.insert(skill_name.to_string(), entry);
-
-    write_skill_lock(&lock)
+    update_skill_lock(|lock| {
+        // 保留原有的 installed_at
+        entry.installed_at = lock
+            .skills
+            .get(skill_name)
+            .map(|e| e.installed_at.clone())
+            .unwrap_or_else(|| now.clone());
+        entry.updated_at = now;
+
+        lock.skills.insert(skill_name.to_string(), entry);
+    })
 }
 
 /// 从 lock 文件移除 skill
 /// 对应 CLI: removeSkillFromLock (skill-lock.ts:247-254)
 pub fn remove_skill_from_lock(skill_name: &str) -> Result<bool, AppError> {
-    let mut lock = read_skill_lock()?;
-
-    if lock.skills.remove(skill_name).is_none() {
-        return Ok(false);
-    }
-
-    write_skill_lock(&lock)?;
-    Ok(true)
+    update_skill_lock(|lock| lock.skills.remove(skill_name).is_some())
 }
 
 /// 添加或更新 skill 到指定 scope 的 lock 文件
@@ -237,29 +224,32 @@ pub fn add_skill_to_scoped_lock(
     project_path: Option<&str>,
     plugin_name: Option<&str>,
 ) -> Result<(), AppError> {
-    let mut lock =
-        read_scoped_lock(project_path).unwrap_or_else(|_| SkillLockFile::empty());
     let now = chrono::Utc::now().format("%Y-%m-%dT%H:%M:%S%.3fZ").to_string();
 
-    let installed_at = lock
-        .skills
-        .get(skill_name)
-        .map(|e| e.installed_at.clone())
-        .unwrap_or_else(|| now.clone());
-
-    let entry = SkillLockEntry {
-        source: source.to_string(),
-        source_type: source_type.to_string(),
-        source_url: source_url.to_string(),
-        skill_path: skill_path.map(|s| s.to_string()),
-        skill_folder_hash: skill_folder_hash.to_string(),
-        installed_at,
-        updated_at: now,
-        plugin_name: plugin_name.map(|s| s.to_string()),
-    };
-
-    lock.skills.insert(skill_name.to_string(), entry);
-    write_scoped_lock(&lock, project_path)
+    update_scoped_lock(project_path, |lock| {
+        let installed_at = lock
+            .skills
+            .get(skill_name)
+            .map(|e| e.installed_at.clone())
+            .unwrap_or_else(|| now.clone());
+
+        let entry = SkillLockEntry {
+            source: source.to_string(),
+            source_type: source_type.to_string(),
+            source_url: source_url.to_string(),
+            skill_path: skill_path.map(|s| s.to_string()),
+            skill_folder_hash: skill_folder_hash.to_string(),
+            installed_at,
+            updated_at: now,
+            plugin_name: plugin_name.map(|s| s.to_string()),
+            version: None,
+            dir_name: None,
+            locale: None,
+            detached_agents: Vec::new(),
+        };
+
+        lock.skills.insert(skill_name.to_string(), entry);
+    })
 }
 
 /// 从指定 scope 的 lock 文件移除 skill
@@ -267,20 +257,13 @@ pub fn remove_skill_from_scoped_lock(
     skill_name: &str,
     project_path: Option<&str>,
 ) -> Result<bool, AppError> {
-    let mut lock = read_scoped_lock(project_path)?;
-    if lock.skills.remove(skill_name).is_none() {
-        return Ok(false);
-    }
-    write_scoped_lock(&lock, project_path)?;
-    Ok(true)
+    update_scoped_lock(project_path, |lock| lock.skills.remove(skill_name).is_some())
 }
 
 /// 保存最后选择的 agents
 /// 对应 CLI: saveLastSelectedAgents (skill-lock.ts:282-287)
 pub fn save_selected_agents(agents: &[String]) -> Result<(), AppError> {
-    let mut lock = read_skill_lock().unwrap_or_else(|_| SkillLockFile::empty());
-    lock.last_selected_agents = Some(agents.to_vec());
-    write_skill_lock(&lock)
+    update_skill_lock(|lock| lock.last_selected_agents = Some(agents.to_vec()))
 }
 
 /// 获取最后选择的 agents
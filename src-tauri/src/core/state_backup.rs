@@ -0,0 +1,617 @@
+//! 状态备份模块
+//!
+//! 功能：
+//! - 导出 global lock、config.json 和（可选）global canonical skills 目录为单个 tar.gz
+//! - 从备份恢复：替换或合并 lock 条目，旧版 lock schema 逐条补全后导入
+//! - 为目标机器上已安装的 agents 按导出时的安装模式重新创建 symlink 或副本
+//!
+//! 归档结构（顶层目录 `skill-deck-state/`）：
+//! - `manifest.json`：归档格式版本、应用版本、lock 版本、各 skill 安装到的 agents 及安装模式
+//! - `skill-lock.json`：`~/.agents/.skill-lock.json` 原样复制
+//! - `config.json`：`~/.skill-deck/config.json` 原样复制
+//! - `skills/<name>/`：canonical skills 目录（完整复制，不应用复制排除规则）
+//! - `agent-copies/<agent>/<name>/`：copy 模式安装的 agent 目录（可能与 canonical 不同）
+//!
+//! 打包与解压一样调用外部 `tar` 命令。
+
+use crate::core::agents::{AgentType, DetectionContext};
+use crate::core::installer::{agent_skill_dir, create_symlink, stage_and_replace_all};
+use crate::core::local_source::{extract_archive, ArchiveFormat};
+use crate::core::paths::{
+    canonical_skills_dir, is_symlink_or_junction, same_path, skill_deck_config_path,
+};
+use crate::core::skill::skill_dir_name;
+use crate::core::skill_lock::{
+    get_skill_lock_path, update_lock_file, SkillLockEntry, SkillLockFile, CURRENT_VERSION,
+};
+use crate::error::AppError;
+use crate::models::{InstallMode, Scope, SkillDeckConfig};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use tempfile::TempDir;
+
+/// 归档格式版本（结构变化时递增）
+pub const STATE_FORMAT_VERSION: u32 = 1;
+
+/// 归档内的顶层目录
+const ARCHIVE_ROOT: &str = "skill-deck-state";
+
+const MANIFEST_FILE: &str = "manifest.json";
+const LOCK_FILE: &str = "skill-lock.json";
+const CONFIG_FILE: &str = "config.json";
+const SKILLS_DIR: &str = "skills";
+const AGENT_COPIES_DIR: &str = "agent-copies";
+
+/// 归档 manifest
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StateManifest {
+    /// 归档格式版本
+    pub format_version: u32,
+    /// 导出时的应用版本
+    pub app_version: String,
+    /// 导出时 lock 文件的 schema 版本（没有 lock 时为 None）
+    #[serde(default)]
+    pub lock_version: Option<u32>,
+    /// 导出时间 (ISO 格式)
+    pub exported_at: String,
+    /// 是否包含 canonical skills 目录
+    #[serde(default)]
+    pub includes_skills: bool,
+    /// canonical 目录名 → 导出时安装了该 skill 的非 Universal agents
+    #[serde(default)]
+    pub agent_links: BTreeMap<String, Vec<String>>,
+    /// canonical 目录名 → agent → 安装模式（旧版归档没有此字段，按 symlink 恢复）
+    #[serde(default)]
+    pub agent_modes: BTreeMap<String, BTreeMap<String, InstallMode>>,
+}
+
+/// 导出 / 恢复涉及的本机路径
+#[derive(Debug, Clone)]
+pub struct StatePaths {
+    /// global skill-lock.json
+    pub lock_path: PathBuf,
+    /// config.json
+    pub config_path: PathBuf,
+    /// global canonical skills 目录
+    pub canonical_base: PathBuf,
+}
+
+impl StatePaths {
+    /// 当前配置下的 global 路径
+    pub fn global() -> Self {
+        Self {
+            lock_path: get_skill_lock_path(),
+            config_path: skill_deck_config_path(),
+            canonical_base: canonical_skills_dir(true, "."),
+        }
+    }
+}
+
+/// 导出结果
+#[derive(Debug, Clone)]
+pub struct StateExport {
+    pub manifest: StateManifest,
+    /// lock 中的 skill 数量
+    pub locked_skills: usize,
+    /// 归档中包含的 canonical skill 目录数量
+    pub archived_skill_dirs: usize,
+}
+
+/// 已解压的备份归档（drop 时清理临时目录）
+pub struct StateArchive {
+    _temp_dir: TempDir,
+    root: PathBuf,
+    pub manifest: StateManifest,
+    /// 归档中的 config.json
+    pub config: Option<SkillDeckConfig>,
+}
+
+/// 恢复结果
+#[derive(Debug, Clone, Default)]
+pub struct StateImport {
+    /// 写入 lock 的 skill
+    pub restored_skills: Vec<String>,
+    /// 合并时本机条目更新而保留本机版本的 skill
+    pub kept_skills: Vec<String>,
+    /// 重新创建的安装：(canonical 目录名, agent)
+    pub relinked: Vec<(String, String)>,
+    /// 非致命问题（lock 版本迁移、跳过的条目、链接失败等）
+    pub warnings: Vec<String>,
+}
+
+/// 导出 global 状态到 tar.gz
+///
+/// # Arguments
+/// * `paths` - 要导出的 lock、config 和 canonical 目录（通常为 `StatePaths::global()`）
+/// * `out_path` - 输出文件路径（父目录不存在时创建）
+/// * `include_skills` - 是否包含 global canonical skills 目录和 copy 模式的 agent 目录
+pub fn export_state(
+    paths: &StatePaths,
+    out_path: &Path,
+    include_skills: bool,
+) -> Result<StateExport, AppError> {
+    let staging = TempDir::new()?;
+    let root = staging.path().join(ARCHIVE_ROOT);
+    fs::create_dir_all(&root)?;
+
+    let lock_path = &paths.lock_path;
+    let (lock_version, locked_skills) = if lock_path.is_file() {
+        fs::copy(lock_path, root.join(LOCK_FILE))?;
+        let lock: serde_json::Value = serde_json::from_str(&fs::read_to_string(lock_path)?)?;
+        let skills = lock.get("skills").and_then(|s| s.as_object()).map_or(0, |s| s.len());
+        (lock_version(&lock), skills)
+    } else {
+        (None, 0)
+    };
+
+    if paths.config_path.is_file() {
+        fs::copy(&paths.config_path, root.join(CONFIG_FILE))?;
+    }
+
+    let canonical_base = &paths.canonical_base;
+    let skill_dirs = list_skill_dirs(canonical_base);
+    let mut agent_links = BTreeMap::new();
+    let mut agent_modes = BTreeMap::new();
+    for name in &skill_dirs {
+        if include_skills {
+            stage_and_replace_all(&canonical_base.join(name), &root.join(SKILLS_DIR).join(name))?;
+        }
+        let agents = installed_agents(name, &canonical_base.join(name));
+        if agents.is_empty() {
+            continue;
+        }
+        for (agent, (agent_dir, mode)) in &agents {
+            if include_skills && *mode == InstallMode::Copy {
+                let target = root.join(AGENT_COPIES_DIR).join(agent).join(name);
+                stage_and_replace_all(agent_dir, &target)?;
+            }
+        }
+        agent_links.insert(name.clone(), agents.keys().cloned().collect());
+        agent_modes.insert(
+            name.clone(),
+            agents.into_iter().map(|(agent, (_, mode))| (agent, mode)).collect(),
+        );
+    }
+    let manifest = StateManifest {
+        format_version: STATE_FORMAT_VERSION,
+        app_version: env!("CARGO_PKG_VERSION").to_string(),
+        lock_version,
+        exported_at: chrono::Utc::now().format("%Y-%m-%dT%H:%M:%S%.3fZ").to_string(),
+        includes_skills: include_skills,
+        agent_links,
+        agent_modes,
+    };
+    fs::write(root.join(MANIFEST_FILE), serde_json::to_string_pretty(&manifest)?)?;
+
+    if let Some(parent) = out_path.parent().filter(|p| !p.as_os_str().is_empty()) {
+        fs::create_dir_all(parent)?;
+    }
+    let output = Command::new("tar")
+        .arg("-czf")
+        .arg(out_path)
+        .arg("-C")
+        .arg(staging.path())
+        .arg(ARCHIVE_ROOT)
+        .output()?;
+    if !output.status.success() {
+        return Err(AppError::Custom {
+            message: format!(
+                "Failed to create {}: {}",
+                out_path.display(),
+                String::from_utf8_lossy(&output.stderr).trim()
+            ),
+        });
+    }
+
+    Ok(StateExport {
+        manifest,
+        locked_skills,
+        archived_skill_dirs: if include_skills { skill_dirs.len() } else { 0 },
+    })
+}
+
+/// 解压并校验备份归档
+///
+/// 归档格式版本高于当前支持的版本时返回错误（需要先升级应用）
+pub fn open_state_archive(in_path: &Path) -> Result<StateArchive, AppError> {
+    if !in_path.is_file() {
+        return Err(AppError::PathNotFound {
+            path: in_path.display().to_string(),
+        });
+    }
+
+    let extracted = extract_archive(in_path, ArchiveFormat::TarGz)?;
+    let root = extracted.root;
+    let manifest: StateManifest = fs::read_to_string(root.join(MANIFEST_FILE))
+        .ok()
+        .and_then(|content| serde_json::from_str(&content).ok())
+        .ok_or_else(|| AppError::InvalidSource {
+            value: format!("{} is not a skill-deck state archive", in_path.display()),
+        })?;
+    if manifest.format_version > STATE_FORMAT_VERSION {
+        return Err(AppError::Custom {
+            message: format!(
+                "State archive format {} (from Skill Deck {}) is newer than supported format {}",
+                manifest.format_version, manifest.app_version, STATE_FORMAT_VERSION
+            ),
+        });
+    }
+
+    let config = match fs::read_to_string(root.join(CONFIG_FILE)) {
+        Ok(content) => Some(serde_json::from_str(&content)?),
+        Err(_) => None,
+    };
+
+    Ok(StateArchive {
+        _temp_dir: extracted.temp_dir,
+        root,
+        manifest,
+        config,
+    })
+}
+
+/// 恢复 lock 和 canonical skills，并为本机已安装的 agents 重新创建安装
+///
+/// config.json 由调用方在恢复成功后保存；`paths.canonical_base` 应按将要保存的配置
+/// （canonicalDirOverride）计算，`paths.config_path` 不会被使用。
+///
+/// # Arguments
+/// * `merge` - false: 用归档中的 lock 替换本机 lock；
+///   true: 保留本机 lock，按 updatedAt 取较新的条目，本机较新的 skill 目录不被覆盖
+///
+/// 归档中没有的本机 canonical skill 目录始终保留在磁盘上。
+pub fn restore_state(
+    archive: &StateArchive,
+    paths: &StatePaths,
+    merge: bool,
+) -> Result<StateImport, AppError> {
+    let mut result = StateImport::default();
+
+    // 1. lock
+    if let Ok(content) = fs::read_to_string(archive.root.join(LOCK_FILE)) {
+        let imported = migrate_lock(&content, archive.manifest.lock_version, &mut result.warnings)?;
+        let (mut restored, mut kept) = update_lock_file(&paths.lock_path, |lock| {
+            if !merge {
+                *lock = SkillLockFile {
+                    dismissed: imported.dismissed.clone(),
+                    last_selected_agents: imported.last_selected_agents.clone(),
+                    ..SkillLockFile::empty()
+                };
+            }
+            let (mut restored, mut kept) = (Vec::new(), Vec::new());
+            for (name, entry) in imported.skills {
+                let local_is_newer = lock
+                    .skills
+                    .get(&name)
+                    .is_some_and(|local| local.updated_at > entry.updated_at);
+                if local_is_newer {
+                    kept.push(name);
+                } else {
+                    lock.skills.insert(name.clone(), entry);
+                    restored.push(name);
+                }
+            }
+            (restored, kept)
+        })?;
+        restored.sort();
+        kept.sort();
+        result.restored_skills = restored;
+        result.kept_skills = kept;
+    }
+
+    // 2. canonical skills
+    let canonical_base = &paths.canonical_base;
+    let kept_dirs: HashSet<String> = result.kept_skills.iter().map(|n| skill_dir_name(n, true, ".")).collect();
+    let archived_skills = archive.root.join(SKILLS_DIR);
+    for name in list_skill_dirs(&archived_skills) {
+        let target = canonical_base.join(&name);
+        if merge && target.exists() && kept_dirs.contains(&name) {
+            continue;
+        }
+        stage_and_replace_all(&archived_skills.join(&name), &target)?;
+    }
+
+    // 3. 为本机已安装的 agents 按导出时的模式重新安装（已存在的安装不动）
+    let detected = AgentType::detect_installed(&DetectionContext::GlobalOnly);
+    for (name, agents) in &archive.manifest.agent_links {
+        let canonical_dir = canonical_base.join(name);
+        if !canonical_dir.is_dir() {
+            continue;
+        }
+        for agent_id in agents {
+            let Ok(agent) = agent_id.parse::<AgentType>() else {
+                result.warnings.push(format!("Unknown agent '{}' for {}", agent_id, name));
+                continue;
+            };
+            if !detected.contains(&agent) {
+                continue;
+            }
+            let Some(agent_dir) = agent_skill_dir(name, &agent, &Scope::Global, None) else {
+                continue;
+            };
+            if agent_dir.symlink_metadata().is_ok() {
+                continue;
+            }
+            let mode = archive
+                .manifest
+                .agent_modes
+                .get(name)
+                .and_then(|modes| modes.get(agent_id))
+                .cloned()
+                .unwrap_or(InstallMode::Symlink);
+            let installed = if mode == InstallMode::Copy {
+                let archived = archive.root.join(AGENT_COPIES_DIR).join(agent_id).join(name);
+                let src = if archived.is_dir() { archived } else { canonical_dir.clone() };
+                stage_and_replace_all(&src, &agent_dir)
+            } else {
+                create_symlink(&canonical_dir, &agent_dir)
+            };
+            match installed {
+                Ok(()) => result.relinked.push((name.clone(), agent_id.clone())),
+                Err(e) => result
+                    .warnings
+                    .push(format!("Failed to install {} for {}: {}", name, agent_id, e)),
+            }
+        }
+    }
+
+    Ok(result)
+}
+
+/// 将归档中的 lock 转为当前 schema
+///
+/// read_skill_lock 会直接丢弃旧版本的 lock，这里改为逐条导入：
+/// - 旧版本：补全当前版本新增的字段（skillFolderHash 为空，更新检测会重新获取）
+/// - 更新的版本：按当前 schema 解析，丢弃不认识的字段
+/// - 无法解析的条目跳过并记录警告
+fn migrate_lock(
+    content: &str,
+    manifest_version: Option<u32>,
+    warnings: &mut Vec<String>,
+) -> Result<SkillLockFile, AppError> {
+    let mut value: serde_json::Value = serde_json::from_str(content)?;
+    let version = lock_version(&value).or(manifest_version).unwrap_or(0);
+    if version > CURRENT_VERSION {
+        warnings.push(format!(
+            "Lock version {} is newer than supported version {}; unknown fields were dropped",
+            version, CURRENT_VERSION
+        ));
+    } else if version < CURRENT_VERSION {
+        warnings.push(format!(
+            "Migrated lock from version {} to {}; restored skills will re-check for updates",
+            version, CURRENT_VERSION
+        ));
+    }
+
+    let mut lock = SkillLockFile::empty();
+    lock.dismissed = value
+        .get("dismissed")
+        .and_then(|v| serde_json::from_value(v.clone()).ok());
+    lock.last_selected_agents = value
+        .get("lastSelectedAgents")
+        .and_then(|v| serde_json::from_value(v.clone()).ok());
+
+    let now = chrono::Utc::now().format("%Y-%m-%dT%H:%M:%S%.3fZ").to_string();
+    let skills = value
+        .get_mut("skills")
+        .and_then(|s| s.as_object_mut())
+        .map(std::mem::take)
+        .unwrap_or_default();
+    for (name, mut entry) in skills {
+        if let Some(fields) = entry.as_object_mut() {
+            if let Some(source) = fields.get("source").cloned() {
+                fields.entry("sourceUrl").or_insert(source);
+            }
+            fields.entry("skillFolderHash").or_insert_with(|| "".into());
+            fields.entry("installedAt").or_insert_with(|| now.clone().into());
+            fields.entry("updatedAt").or_insert_with(|| now.clone().into());
+        }
+        match serde_json::from_value::<SkillLockEntry>(entry) {
+            Ok(entry) => {
+                lock.skills.insert(name, entry);
+            }
+            Err(e) => warnings.push(format!("Skipped lock entry '{}': {}", name, e)),
+        }
+    }
+
+    Ok(lock)
+}
+
+fn lock_version(lock: &serde_json::Value) -> Option<u32> {
+    lock.get("version")?.as_u64().map(|v| v as u32)
+}
+
+/// canonical 目录下的 skill 目录名（跳过隐藏目录，按名称排序）
+fn list_skill_dirs(base: &Path) -> Vec<String> {
+    let Ok(entries) = fs::read_dir(base) else {
+        return Vec::new();
+    };
+    let mut names: Vec<String> = entries
+        .filter_map(|e| e.ok())
+        .filter(|e| e.file_type().map(|t| t.is_dir()).unwrap_or(false))
+        .map(|e| e.file_name().to_string_lossy().to_string())
+        .filter(|name| !name.starts_with('.'))
+        .collect();
+    names.sort();
+    names
+}
+
+/// 在 global scope 安装了该 skill 的非 Universal agents：agent → (agent 目录, 安装模式)
+///
+/// 链接（symlink / junction）或指向 canonical 目录本身时为 symlink 模式，其余为 copy 模式
+fn installed_agents(name: &str, canonical_dir: &Path) -> BTreeMap<String, (PathBuf, InstallMode)> {
+    AgentType::get_non_universal_agents()
+        .into_iter()
+        .filter_map(|agent| {
+            let dir = agent_skill_dir(name, &agent, &Scope::Global, None)?;
+            dir.symlink_metadata().ok()?;
+            let mode = if is_symlink_or_junction(&dir) || same_path(&dir, canonical_dir) {
+                InstallMode::Symlink
+            } else {
+                InstallMode::Copy
+            };
+            Some((agent.to_string(), (dir, mode)))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_migrate_old_lock_version() {
+        let content = r#"{
+            "version": 2,
+            "skills": {
+                "pdf": { "source": "owner/repo", "sourceType": "github", "installedAt": "2024-01-01T00:00:00.000Z" },
+                "broken": { "skillPath": "skills/broken/SKILL.md" }
+            },
+            "lastSelectedAgents": ["cursor"]
+        }"#;
+        let mut warnings = Vec::new();
+        let lock = migrate_lock(content, None, &mut warnings).unwrap();
+
+        assert_eq!(lock.version, CURRENT_VERSION);
+        assert_eq!(lock.skills.len(), 1);
+        let pdf = &lock.skills["pdf"];
+        assert_eq!(pdf.source_url, "owner/repo");
+        assert_eq!(pdf.skill_folder_hash, "");
+        assert_eq!(pdf.installed_at, "2024-01-01T00:00:00.000Z");
+        assert_eq!(lock.last_selected_agents, Some(vec!["cursor".to_string()]));
+        assert!(warnings[0].contains("from version 2"));
+        assert!(warnings.iter().any(|w| w.contains("'broken'")));
+    }
+
+    #[test]
+    fn test_migrate_newer_lock_version() {
+        let content = r#"{
+            "version": 9,
+            "skills": {
+                "pdf": {
+                    "source": "owner/repo", "sourceType": "github", "sourceUrl": "https://github.com/owner/repo",
+                    "skillFolderHash": "abc", "installedAt": "t", "updatedAt": "t", "futureField": true
+                }
+            }
+        }"#;
+        let mut warnings = Vec::new();
+        let lock = migrate_lock(content, None, &mut warnings).unwrap();
+
+        assert_eq!(lock.version, CURRENT_VERSION);
+        assert_eq!(lock.skills["pdf"].skill_folder_hash, "abc");
+        assert_eq!(warnings.len(), 1);
+        assert!(warnings[0].contains("newer"));
+
+        // 当前版本无警告
+        let mut warnings = Vec::new();
+        migrate_lock(&content.replace("\"version\": 9", "\"version\": 3"), None, &mut warnings).unwrap();
+        assert!(warnings.is_empty());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_export_import_round_trip() {
+        let temp = tempfile::tempdir().unwrap();
+        let machine = |name: &str| StatePaths {
+            lock_path: temp.path().join(name).join(".skill-lock.json"),
+            config_path: temp.path().join(name).join("config.json"),
+            canonical_base: temp.path().join(name).join("skills"),
+        };
+        let lock = |updated_at: &str| {
+            format!(
+                r#"{{"version": {}, "skills": {{"state-round-trip": {{"source": "owner/repo",
+                "sourceType": "github", "sourceUrl": "https://github.com/owner/repo",
+                "skillFolderHash": "abc", "installedAt": "t", "updatedAt": "{}"}}}}}}"#,
+                CURRENT_VERSION, updated_at
+            )
+        };
+
+        // 导出端：复制排除项（.git、.skillignore）也应完整保留
+        let old = machine("old");
+        let skill = old.canonical_base.join("state-round-trip");
+        fs::create_dir_all(skill.join(".git")).unwrap();
+        fs::write(skill.join("SKILL.md"), "---\nname: state-round-trip\n---\n").unwrap();
+        fs::write(skill.join(".git/HEAD"), "ref: refs/heads/main\n").unwrap();
+        fs::write(skill.join(".skillignore"), "*.log\n").unwrap();
+        fs::write(&old.lock_path, lock("2024-02-01T00:00:00.000Z")).unwrap();
+        fs::write(&old.config_path, r#"{"projects": ["/work/app"]}"#).unwrap();
+
+        let archive_path = temp.path().join("out/state.tar.gz");
+        let export = export_state(&old, &archive_path, true).unwrap();
+        assert_eq!(export.locked_skills, 1);
+        assert_eq!(export.archived_skill_dirs, 1);
+
+        // 导入端：替换
+        let new = machine("new");
+        let archive = open_state_archive(&archive_path).unwrap();
+        assert_eq!(archive.config.as_ref().unwrap().projects, vec!["/work/app"]);
+        let restored = restore_state(&archive, &new, false).unwrap();
+        assert_eq!(restored.restored_skills, vec!["state-round-trip"]);
+        let restored_skill = new.canonical_base.join("state-round-trip");
+        assert_eq!(fs::read_to_string(restored_skill.join(".git/HEAD")).unwrap(), "ref: refs/heads/main\n");
+        assert!(restored_skill.join(".skillignore").is_file());
+        let lock_file: SkillLockFile =
+            serde_json::from_str(&fs::read_to_string(&new.lock_path).unwrap()).unwrap();
+        assert_eq!(lock_file.skills["state-round-trip"].skill_folder_hash, "abc");
+        // config.json 由调用方在恢复后保存
+        assert!(!new.config_path.exists());
+
+        // 合并：本机条目更新时保留本机 lock 条目和目录
+        fs::write(&new.lock_path, lock("2025-01-01T00:00:00.000Z")).unwrap();
+        fs::write(restored_skill.join("SKILL.md"), "local").unwrap();
+        let merged = restore_state(&archive, &new, true).unwrap();
+        assert_eq!(merged.kept_skills, vec!["state-round-trip"]);
+        assert!(merged.restored_skills.is_empty());
+        assert_eq!(fs::read_to_string(restored_skill.join("SKILL.md")).unwrap(), "local");
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_open_state_archive() {
+        let src = tempfile::tempdir().unwrap();
+        let root = src.path().join(ARCHIVE_ROOT);
+        fs::create_dir_all(root.join("skills/pdf")).unwrap();
+        fs::write(root.join("config.json"), r#"{"projects": ["/work/app"]}"#).unwrap();
+        let tar = |manifest: &str| {
+            fs::write(root.join(MANIFEST_FILE), manifest).unwrap();
+            let archive = src.path().join("state.tar.gz");
+            let status = Command::new("tar")
+                .arg("-czf")
+                .arg(&archive)
+                .arg("-C")
+                .arg(src.path())
+                .arg(ARCHIVE_ROOT)
+                .status()
+                .unwrap();
+            assert!(status.success());
+            archive
+        };
+
+        let archive = tar(r#"{"formatVersion": 1, "appVersion": "0.1.0", "exportedAt": "t",
+            "agentLinks": {"pdf": ["cursor", "windsurf"]}, "agentModes": {"pdf": {"windsurf": "copy"}}}"#);
+        let opened = open_state_archive(&archive).unwrap();
+        assert_eq!(opened.manifest.agent_links["pdf"], vec!["cursor", "windsurf"]);
+        assert_eq!(opened.manifest.agent_modes["pdf"]["windsurf"], InstallMode::Copy);
+        assert_eq!(opened.config.as_ref().unwrap().projects, vec!["/work/app"]);
+        assert_eq!(list_skill_dirs(&opened.root.join(SKILLS_DIR)), vec!["pdf"]);
+
+        let archive = tar(r#"{"formatVersion": 99, "appVersion": "9.0.0", "exportedAt": "t"}"#);
+        assert!(matches!(open_state_archive(&archive), Err(AppError::Custom { .. })));
+
+        fs::remove_file(root.join(MANIFEST_FILE)).unwrap();
+        let archive = src.path().join("plain.tar.gz");
+        Command::new("tar")
+            .arg("-czf")
+            .arg(&archive)
+            .arg("-C")
+            .arg(src.path())
+            .arg(ARCHIVE_ROOT)
+            .status()
+            .unwrap();
+        assert!(matches!(open_state_archive(&archive), Err(AppError::InvalidSource { .. })));
+    }
+}
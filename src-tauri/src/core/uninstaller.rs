@@ -9,16 +9,18 @@
 //! - agent 检测 fallback：CLI 用 `Object.keys(agents)` 全部 agents，Rust 用 `AgentType::all()` 枚举迭代（等价）
 //! - 路径安全检查：CLI 有独立 `isPathSafe()` 函数，Rust 的 `sanitize_name()` 已移除路径穿越字符，无需二次检查
 //! - 错误收集：CLI 用 `results` 数组收集批量结果，GUI 是单个删除返回 `RemoveResult`
+//! - GUI 增强：可清理删除后留下的空 agent skills 目录（`remove_empty_skill_dirs`）
+//! - GUI 增强：dry run 模式只计算将删除的路径，供确认对话框预览
 
-use crate::core::agents::AgentType;
-use crate::core::paths::canonical_skills_dir;
-use crate::core::skill::sanitize_name;
+use crate::core::agents::{AgentType, DetectionContext};
+use crate::core::paths::{agent_skills_dir, canonical_skills_dir, is_symlink_or_junction, same_path};
+use crate::core::skill::skill_dir_name;
 use crate::core::local_lock::remove_skill_from_local_lock;
 use crate::core::skill_lock::{get_skill_from_lock, remove_skill_from_lock};
 use crate::error::AppError;
-use crate::models::{RemoveResult, Scope};
+use crate::models::{RemoveResult, RemovedPathInfo, RemovedPathKind, Scope};
 use std::fs;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
 /// 删除 skill
 ///
@@ -31,26 +33,28 @@ use std::path::PathBuf;
 /// * `project_path` - Project scope 时的项目路径
 /// * `full_removal` - 是否完全删除（true = 删除一切，false = 仅删除指定 agents 的 symlink）
 /// * `target_agents` - 部分移除时指定的 agent 列表（None = 自动检测）
+/// * `dry_run` - 只计算将删除的路径（含 canonical 共享保护判断），不删除任何内容、不修改 lock
 ///
 /// # Returns
-/// * `RemoveResult` - 删除结果
+/// * `RemoveResult` - 删除结果（removed_paths 只包含实际存在的路径）
 pub fn remove_skill(
     skill_name: &str,
     scope: &Scope,
     project_path: Option<&str>,
     full_removal: bool,
     target_agents: Option<&[AgentType]>,
+    dry_run: bool,
 ) -> Result<RemoveResult, AppError> {
     let is_global = matches!(scope, Scope::Global);
     let cwd = project_path.unwrap_or(".");
-    let sanitized_name = sanitize_name(skill_name);
+    let sanitized_name = skill_dir_name(skill_name, is_global, cwd);
 
     // 1. 确定要操作的 agents
     let agents_to_remove: Vec<AgentType> = if let Some(specified) = target_agents {
         specified.to_vec()
     } else {
         // 自动检测（向后兼容原始行为）
-        let detected = AgentType::detect_installed();
+        let detected = AgentType::detect_installed(&DetectionContext::for_scope(is_global, cwd));
         if detected.is_empty() {
             AgentType::all().collect::<Vec<_>>()
         } else {
@@ -58,7 +62,12 @@ pub fn remove_skill(
         }
     };
 
+    let canonical_path = canonical_skills_dir(is_global, cwd).join(&sanitized_name);
+    // Universal agents 的目录即 canonical 目录，需在遍历 agents 前记录
+    let canonical_existed = canonical_path.symlink_metadata().is_ok();
+
     let mut removed_paths = Vec::new();
+    let mut path_details = Vec::new();
 
     // 2. 遍历 agents 删除 skill 目录
     // 对应 CLI: remove.ts:152-168
@@ -67,17 +76,24 @@ pub fn remove_skill(
 
         // 计算 agent 目录下的 skill 路径
         // 对应 CLI: installer.ts:367-389 getInstallPath()
-        let skill_path = if is_global {
-            match &config.global_skills_dir {
-                Some(global_dir) => global_dir.join(&sanitized_name),
-                // agent 不支持 global 安装，跳过
-                None => continue,
-            }
-        } else {
-            PathBuf::from(cwd)
-                .join(&config.skills_dir)
-                .join(&sanitized_name)
+        let skill_path = match agent_skills_dir(agent, is_global, cwd) {
+            Some(base) => base.join(&sanitized_name),
+            // agent 不支持 global 安装，跳过
+            None => continue,
+        };
+
+        let Some(kind) = path_kind(&skill_path) else {
+            continue;
         };
+        // 多个 agent 共用同一目录时只记录一次
+        if removed_paths.contains(&skill_path.to_string_lossy().to_string()) {
+            continue;
+        }
+
+        if dry_run {
+            push_removed(&mut removed_paths, &mut path_details, &skill_path, kind);
+            continue;
+        }
 
         // 删除 agent 目录下的 skill（可能是 symlink 或实体目录）
         // 对应 CLI: remove.ts:156-167
@@ -94,44 +110,35 @@ pub fn remove_skill(
         } else if skill_path.exists() || skill_path.symlink_metadata().is_ok() {
             // 路径存在但删除后仍然存在，说明删除失败
         } else {
-            removed_paths.push(skill_path.to_string_lossy().to_string());
+            push_removed(&mut removed_paths, &mut path_details, &skill_path, kind);
         }
     }
 
     // 3. 完全删除模式：清理 canonical 目录 + lock file
+    let mut removed_canonical = None;
     let (source, source_type) = if full_removal {
         // 删除 canonical 目录（带共享保护）
-        let canonical_path = canonical_skills_dir(is_global, cwd).join(&sanitized_name);
-        let should_remove_canonical = if is_global {
-            let still_used = AgentType::all().any(|agent| {
-                if agents_to_remove.contains(&agent) {
-                    return false;
-                }
-                let config = agent.config();
-                if let Some(global_dir) = &config.global_skills_dir {
-                    let agent_skill_path = global_dir.join(&sanitized_name);
-                    agent_skill_path.symlink_metadata().is_ok()
-                } else {
-                    false
-                }
-            });
-            !still_used
-        } else {
-            let still_used = AgentType::all().any(|agent| {
-                if agents_to_remove.contains(&agent) {
-                    return false;
-                }
-                let config = agent.config();
-                let agent_skill_path = PathBuf::from(cwd)
-                    .join(&config.skills_dir)
-                    .join(&sanitized_name);
-                agent_skill_path.symlink_metadata().is_ok()
-            });
-            !still_used
-        };
+        let still_used = AgentType::all().any(|agent| {
+            if agents_to_remove.contains(&agent) {
+                return false;
+            }
+            // 已删除（dry run 时为将删除）的路径不再视为占用
+            agent_skills_dir(&agent, is_global, cwd)
+                .map(|base| base.join(&sanitized_name))
+                .map(|path| {
+                    path.symlink_metadata().is_ok()
+                        && !removed_paths.contains(&path.to_string_lossy().to_string())
+                })
+                .unwrap_or(false)
+        });
 
-        if should_remove_canonical {
-            let _ = remove_path(&canonical_path);
+        if !still_used && canonical_existed {
+            if !dry_run {
+                let _ = remove_path(&canonical_path);
+            }
+            if dry_run || canonical_path.symlink_metadata().is_err() {
+                removed_canonical = Some(canonical_path.to_string_lossy().to_string());
+            }
         }
 
         // 更新 lock file
@@ -145,7 +152,9 @@ pub fn remove_skill(
                 .as_ref()
                 .map(|e| e.source_type.clone())
                 .unwrap_or_else(|| "local".to_string());
-            let _ = remove_skill_from_lock(skill_name);
+            if !dry_run {
+                let _ = remove_skill_from_lock(skill_name);
+            }
             (Some(effective_source), Some(effective_source_type))
         } else {
             if let Some(project_dir) = project_path {
@@ -153,7 +162,9 @@ pub fn remove_skill(
                 let lock_entry = local_lock.and_then(|l| l.skills.get(skill_name).cloned());
                 let effective_source = lock_entry.as_ref().map(|e| e.source.clone());
                 let effective_source_type = lock_entry.as_ref().map(|e| e.source_type.clone());
-                let _ = remove_skill_from_local_lock(skill_name, project_dir);
+                if !dry_run {
+                    let _ = remove_skill_from_local_lock(skill_name, project_dir);
+                }
                 (effective_source, effective_source_type)
             } else {
                 (None, None)
@@ -168,12 +179,80 @@ pub fn remove_skill(
         skill_name: skill_name.to_string(),
         success: true,
         removed_paths,
+        path_details,
+        canonical_path: removed_canonical,
+        dry_run,
         source,
         source_type,
         error: None,
     })
 }
 
+/// 路径类型（不跟随 symlink），路径不存在时返回 None
+fn path_kind(path: &Path) -> Option<RemovedPathKind> {
+    path.symlink_metadata().ok()?;
+    if is_symlink_or_junction(path) {
+        Some(RemovedPathKind::Symlink)
+    } else {
+        Some(RemovedPathKind::Directory)
+    }
+}
+
+fn push_removed(
+    removed_paths: &mut Vec<String>,
+    path_details: &mut Vec<RemovedPathInfo>,
+    path: &Path,
+    kind: RemovedPathKind,
+) {
+    let path = path.to_string_lossy().to_string();
+    removed_paths.push(path.clone());
+    path_details.push(RemovedPathInfo { path, kind });
+}
+
+/// 删除空的 agent skills 目录
+///
+/// 只删除 skills 目录本身（如 `.claude/skills`），不会删除 agent 检测依赖的父目录（如 `.claude`）。
+/// 非空目录、symlink 以及不存在的目录都会跳过；多个 agent 共用的目录只处理一次。
+///
+/// # Returns
+/// * 实际删除的目录列表
+pub fn remove_empty_skill_dirs(
+    scope: &Scope,
+    project_path: Option<&str>,
+    agents: &[AgentType],
+) -> Vec<PathBuf> {
+    let is_global = matches!(scope, Scope::Global);
+    let cwd = project_path.unwrap_or(".");
+    let mut removed: Vec<PathBuf> = Vec::new();
+
+    for agent in agents {
+        let Some(dir) = agent_skills_dir(agent, is_global, cwd) else {
+            continue;
+        };
+        if removed.iter().any(|r| same_path(r, &dir)) || !is_empty_real_dir(&dir) {
+            continue;
+        }
+
+        // remove_dir 只能删除空目录，检查后被写入也不会误删内容
+        match fs::remove_dir(&dir) {
+            Ok(()) => removed.push(dir),
+            Err(e) => log::warn!("Could not remove empty skills dir {}: {}", dir.display(), e),
+        }
+    }
+
+    removed
+}
+
+/// 是否为空的实体目录（symlink / junction 不算）
+fn is_empty_real_dir(path: &Path) -> bool {
+    if is_symlink_or_junction(path) || !path.is_dir() {
+        return false;
+    }
+    fs::read_dir(path)
+        .map(|mut entries| entries.next().is_none())
+        .unwrap_or(false)
+}
+
 /// 删除路径（目录或 symlink）
 ///
 /// 对应 CLI: remove.ts:156-161
@@ -283,4 +362,79 @@ mod tests {
         // 目标目录不受影响
         assert!(target.exists());
     }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_remove_skill_dry_run() {
+        let project = tempdir().unwrap();
+        let cwd = project.path().to_string_lossy().to_string();
+        let canonical = project.path().join(".agents/skills/demo");
+        fs::create_dir_all(&canonical).unwrap();
+        fs::write(canonical.join("SKILL.md"), "# Demo").unwrap();
+        fs::create_dir_all(project.path().join(".claude/skills")).unwrap();
+        std::os::unix::fs::symlink(
+            "../../.agents/skills/demo",
+            project.path().join(".claude/skills/demo"),
+        )
+        .unwrap();
+        fs::create_dir_all(project.path().join(".augment/skills/demo")).unwrap();
+
+        // 只移除 Claude Code：Augment 仍在使用，canonical 受共享保护
+        let partial = remove_skill(
+            "demo",
+            &Scope::Project,
+            Some(&cwd),
+            true,
+            Some(&[AgentType::ClaudeCode]),
+            true,
+        )
+        .unwrap();
+        assert!(partial.dry_run);
+        assert_eq!(partial.path_details.len(), 1);
+        assert_eq!(partial.path_details[0].kind, RemovedPathKind::Symlink);
+        assert!(partial.canonical_path.is_none());
+
+        let all: Vec<AgentType> = AgentType::all().collect();
+        let full = remove_skill("demo", &Scope::Project, Some(&cwd), true, Some(&all), true).unwrap();
+        let kind_of = |path: PathBuf| {
+            let path = path.to_string_lossy().to_string();
+            full.path_details.iter().find(|d| d.path == path).map(|d| d.kind)
+        };
+        assert_eq!(
+            kind_of(project.path().join(".claude/skills/demo")),
+            Some(RemovedPathKind::Symlink)
+        );
+        assert_eq!(
+            kind_of(project.path().join(".augment/skills/demo")),
+            Some(RemovedPathKind::Directory)
+        );
+        assert_eq!(full.removed_paths.len(), full.path_details.len());
+        assert_eq!(full.canonical_path, Some(canonical.to_string_lossy().to_string()));
+
+        // 未删除任何内容
+        assert!(canonical.join("SKILL.md").is_file());
+        assert!(project.path().join(".claude/skills/demo").symlink_metadata().is_ok());
+        assert!(project.path().join(".augment/skills/demo").is_dir());
+    }
+
+    #[test]
+    fn test_remove_empty_skill_dirs() {
+        let project = tempdir().unwrap();
+        let cwd = project.path().to_string_lossy().to_string();
+
+        // 空目录：删除 skills 子目录，保留 agent 目录
+        fs::create_dir_all(project.path().join(".claude/skills")).unwrap();
+        // 仍有 skill：保留
+        fs::create_dir_all(project.path().join(".augment/skills/demo")).unwrap();
+
+        let removed = remove_empty_skill_dirs(
+            &Scope::Project,
+            Some(&cwd),
+            &[AgentType::ClaudeCode, AgentType::Augment, AgentType::Cline],
+        );
+
+        assert_eq!(removed, vec![project.path().join(".claude/skills")]);
+        assert!(project.path().join(".claude").is_dir());
+        assert!(project.path().join(".augment/skills/demo").is_dir());
+    }
 }
@@ -0,0 +1,133 @@
+//! 跨项目查找 skill 的安装位置
+//!
+//! 检查范围：
+//! - global：~/.agents/.skill-lock.json 与 global canonical 目录
+//! - 项目：config.json 中记录的每个项目的 skills-lock.json 与项目 canonical 目录
+//!
+//! 按 lock key / 目录名匹配；lock 中有记录或 canonical 目录下存在 SKILL.md 即视为已安装。
+//! 磁盘上已不存在的项目路径记为 stale，不做扫描；lock 无法读取的项目记入
+//! unreadable_projects 并继续扫描其余项目。
+
+use crate::core::local_lock::read_local_lock;
+use crate::core::paths::canonical_skills_dir;
+use crate::core::skill::locked_dir_name;
+use crate::core::skill_lock::get_skill_from_lock;
+use crate::error::AppError;
+use crate::models::{Scope, SkillLocation, SkillUsage, UnreadableProject};
+use std::path::Path;
+
+/// 查找名为 `skill_name` 的 skill 在 global 和已知项目中的安装位置
+///
+/// # Arguments
+/// * `skill_name` - skill 名称（lock key）
+/// * `projects` - 已知项目路径（config.json 的 projects）
+pub fn find_skill_usage(skill_name: &str, projects: &[String]) -> Result<SkillUsage, AppError> {
+    let mut usage = SkillUsage {
+        locations: Vec::new(),
+        stale_projects: Vec::new(),
+        unreadable_projects: Vec::new(),
+    };
+
+    let global_entry = get_skill_from_lock(skill_name)?;
+    let dir_name = locked_dir_name(skill_name, global_entry.as_ref().and_then(|e| e.dir_name.as_deref()));
+    let canonical = canonical_skills_dir(true, ".").join(dir_name);
+    if global_entry.is_some() || canonical.join("SKILL.md").is_file() {
+        usage.locations.push(SkillLocation {
+            scope: Scope::Global,
+            project_path: None,
+            path: canonical.to_string_lossy().to_string(),
+            source: global_entry.as_ref().map(|e| e.source.clone()),
+            source_type: global_entry.as_ref().map(|e| e.source_type.clone()),
+            in_lock: global_entry.is_some(),
+        });
+    }
+
+    add_project_locations(skill_name, projects, &mut usage);
+    Ok(usage)
+}
+
+/// 依次检查已知项目，结果追加到 `usage`（单个项目失败不影响其他项目）
+fn add_project_locations(skill_name: &str, projects: &[String], usage: &mut SkillUsage) {
+    for project in projects {
+        if !Path::new(project).is_dir() {
+            usage.stale_projects.push(project.clone());
+            continue;
+        }
+        match project_location(skill_name, project) {
+            Ok(Some(location)) => usage.locations.push(location),
+            Ok(None) => {}
+            Err(e) => {
+                log::warn!("Skipping project {} while looking up {}: {}", project, skill_name, e);
+                usage.unreadable_projects.push(UnreadableProject {
+                    project_path: project.clone(),
+                    message: e.to_string(),
+                });
+            }
+        }
+    }
+}
+
+/// 单个项目中的安装位置（未安装时返回 None）
+fn project_location(skill_name: &str, project_path: &str) -> Result<Option<SkillLocation>, AppError> {
+    let lock = read_local_lock(project_path)?;
+    let entry = lock.skills.get(skill_name);
+    let dir_name = locked_dir_name(skill_name, entry.and_then(|e| e.dir_name.as_deref()));
+    let canonical = canonical_skills_dir(false, project_path).join(dir_name);
+    if entry.is_none() && !canonical.join("SKILL.md").is_file() {
+        return Ok(None);
+    }
+    Ok(Some(SkillLocation {
+        scope: Scope::Project,
+        project_path: Some(project_path.to_string()),
+        path: canonical.to_string_lossy().to_string(),
+        source: entry.map(|e| e.source.clone()),
+        source_type: entry.map(|e| e.source_type.clone()),
+        in_lock: entry.is_some(),
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_project_usage_and_stale_paths() {
+        let with_skill = tempdir().unwrap();
+        let without_skill = tempdir().unwrap();
+        let skill_dir = with_skill.path().join(".agents").join("skills").join("demo");
+        fs::create_dir_all(&skill_dir).unwrap();
+        fs::write(skill_dir.join("SKILL.md"), "---\nname: demo\ndescription: d\n---\n").unwrap();
+
+        let with_path = with_skill.path().to_string_lossy().to_string();
+        let without_path = without_skill.path().to_string_lossy().to_string();
+        let missing = with_skill.path().join("gone").to_string_lossy().to_string();
+
+        let location = project_location("demo", &with_path).unwrap().unwrap();
+        assert_eq!(location.scope, Scope::Project);
+        assert_eq!(location.project_path.as_deref(), Some(with_path.as_str()));
+        assert!(!location.in_lock);
+        assert!(location.source.is_none());
+        assert!(project_location("demo", &without_path).unwrap().is_none());
+
+        // lock 无法读取（这里是同名目录）的项目被跳过并记录，不影响其他项目
+        let unreadable = tempdir().unwrap();
+        fs::create_dir(unreadable.path().join("skills-lock.json")).unwrap();
+        let unreadable_path = unreadable.path().to_string_lossy().to_string();
+
+        // 只扫描项目，不读取本机的 global lock
+        let mut usage = SkillUsage {
+            locations: Vec::new(),
+            stale_projects: Vec::new(),
+            unreadable_projects: Vec::new(),
+        };
+        let projects = [unreadable_path.clone(), with_path.clone(), without_path, missing.clone()];
+        add_project_locations("demo", &projects, &mut usage);
+        assert_eq!(usage.stale_projects, vec![missing]);
+        assert_eq!(usage.locations.len(), 1);
+        assert_eq!(usage.locations[0].project_path.as_deref(), Some(with_path.as_str()));
+        assert_eq!(usage.unreadable_projects.len(), 1);
+        assert_eq!(usage.unreadable_projects[0].project_path, unreadable_path);
+    }
+}
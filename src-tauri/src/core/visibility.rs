@@ -0,0 +1,294 @@
+//! Skill 可见性模块
+//!
+//! 功能：
+//! - 改写 SKILL.md frontmatter 中的 `metadata.internal`（list_installed_skills 会跳过 internal skill）
+//! - 同步到 copy 模式的 agent 目录（symlink 指向 canonical 目录，无需单独处理）
+//! - Project scope 下同步 skills-lock.json 的 computedHash
+//!
+//! 优先逐行修改 frontmatter，保留注释、字段顺序和正文；`metadata` 为 flow 样式等
+//! 无法逐行处理的写法时，回退为经 serde_yaml 重新序列化 frontmatter（正文仍保持不变）。
+
+use crate::core::agents::AgentType;
+use crate::core::local_lock::{compute_skill_folder_hash, update_local_lock};
+use crate::core::paths::{agent_skills_dir, canonical_skills_dir, is_symlink_or_junction, same_path};
+use crate::core::skill::{parse_skill_md_content, skill_dir_name};
+use crate::error::AppError;
+use crate::models::Scope;
+use serde_yaml::{Mapping, Value};
+use std::fs;
+use std::path::Path;
+
+/// 设置已安装 skill 的 `metadata.internal`
+///
+/// # Arguments
+/// * `skill_name` - skill 名称
+/// * `scope` - 范围（Global/Project）
+/// * `project_path` - Project scope 时的项目路径
+/// * `internal` - true 时隐藏，false 时移除该字段（恢复默认可见）
+pub fn set_skill_internal(
+    skill_name: &str,
+    scope: &Scope,
+    project_path: Option<&str>,
+    internal: bool,
+) -> Result<(), AppError> {
+    let is_global = matches!(scope, Scope::Global);
+    let cwd = project_path.unwrap_or(".");
+    let sanitized = skill_dir_name(skill_name, is_global, cwd);
+    let canonical_base = canonical_skills_dir(is_global, cwd);
+    let canonical_dir = canonical_base.join(&sanitized);
+
+    let skill_md = canonical_dir.join("SKILL.md");
+    if !skill_md.is_file() {
+        return Err(AppError::PathNotFound {
+            path: skill_md.to_string_lossy().to_string(),
+        });
+    }
+    rewrite_internal(&canonical_dir, internal)?;
+
+    // copy 模式的 agent 目录各自持有 SKILL.md 副本
+    for agent in AgentType::all() {
+        let Some(base) = agent_skills_dir(&agent, is_global, cwd) else {
+            continue;
+        };
+        let agent_dir = base.join(&sanitized);
+        if same_path(&base, &canonical_base)
+            || is_symlink_or_junction(&agent_dir)
+            || !agent_dir.join("SKILL.md").is_file()
+        {
+            continue;
+        }
+        if let Err(e) = rewrite_internal(&agent_dir, internal) {
+            log::warn!("Could not update {}: {}", agent_dir.display(), e);
+        }
+    }
+
+    // SKILL.md 内容已变化，同步 computedHash 避免 verify 误报
+    if let (false, Some(dir)) = (is_global, project_path) {
+        let computed_hash = compute_skill_folder_hash(&canonical_dir)?;
+        update_local_lock(dir, |lock| {
+            if let Some(entry) = lock.skills.get_mut(skill_name) {
+                entry.computed_hash = computed_hash;
+            }
+        })?;
+    }
+
+    Ok(())
+}
+
+/// 改写目录下 SKILL.md 的 internal 标记，内容未变化时不写入
+fn rewrite_internal(skill_dir: &Path, internal: bool) -> Result<(), AppError> {
+    let skill_md = skill_dir.join("SKILL.md");
+    let content = fs::read_to_string(&skill_md)?;
+    let updated = set_frontmatter_internal(&content, internal)?;
+    if updated != content {
+        fs::write(&skill_md, updated)?;
+    }
+    Ok(())
+}
+
+/// 设置 frontmatter 中的 `metadata.internal`，frontmatter 之外的内容原样保留
+fn set_frontmatter_internal(content: &str, internal: bool) -> Result<String, AppError> {
+    let lines: Vec<&str> = content.split_inclusive('\n').collect();
+    if lines.first().map(|l| l.trim_end()) != Some("---") {
+        return Err(AppError::InvalidSkillMd {
+            message: "Missing frontmatter delimiter".to_string(),
+        });
+    }
+    let close = (1..lines.len())
+        .find(|&i| lines[i].trim_end() == "---")
+        .ok_or_else(|| AppError::InvalidSkillMd {
+            message: "Unclosed frontmatter delimiter".to_string(),
+        })?;
+    let eol = if lines[0].ends_with("\r\n") { "\r\n" } else { "\n" };
+
+    if let Some(edited) = edit_lines(&lines, close, internal, eol) {
+        let edited = edited.concat();
+        // 缩进不规则等情况下逐行修改可能不生效，校验后再采用
+        if parsed_internal(&edited) == Some(internal) {
+            return Ok(edited);
+        }
+    }
+
+    reserialize(&lines, close, internal, eol)
+}
+
+/// 逐行修改 frontmatter（`lines[0]` 与 `lines[close]` 为分隔线）
+///
+/// `metadata` 不是块样式 mapping 时返回 None
+fn edit_lines(lines: &[&str], close: usize, internal: bool, eol: &str) -> Option<Vec<String>> {
+    let mut out: Vec<String> = lines.iter().map(|l| l.to_string()).collect();
+
+    let Some(meta) = (1..close).find(|&i| lines[i].starts_with("metadata:")) else {
+        if internal {
+            out.insert(close, format!("metadata:{eol}  internal: true{eol}"));
+        }
+        return Some(out);
+    };
+
+    let rest = lines[meta]["metadata:".len()..].trim();
+    if !rest.is_empty() && !rest.starts_with('#') {
+        return None;
+    }
+
+    // metadata 的子项：紧随其后的缩进行或空行
+    let mut end = meta + 1;
+    while end < close && (lines[end].trim().is_empty() || lines[end].starts_with([' ', '\t'])) {
+        end += 1;
+    }
+    let children: Vec<usize> = (meta + 1..end).filter(|&i| !lines[i].trim().is_empty()).collect();
+    let indent = children
+        .first()
+        .map(|&i| &lines[i][..lines[i].len() - lines[i].trim_start().len()])
+        .unwrap_or("  ");
+    let existing = children.iter().copied().find(|&i| {
+        lines[i]
+            .strip_prefix(indent)
+            .is_some_and(|r| r.starts_with("internal:"))
+    });
+
+    match (existing, internal) {
+        (Some(i), true) => out[i] = format!("{indent}internal: true{eol}"),
+        (Some(i), false) => {
+            out.remove(i);
+            // internal 是唯一子项时连同 metadata 一起移除
+            if children.len() == 1 {
+                out.remove(meta);
+            }
+        }
+        (None, true) => out.insert(meta + 1, format!("{indent}internal: true{eol}")),
+        (None, false) => {}
+    }
+    Some(out)
+}
+
+/// 经 serde_yaml 重新序列化 frontmatter（会丢失注释和原有格式）
+fn reserialize(lines: &[&str], close: usize, internal: bool, eol: &str) -> Result<String, AppError> {
+    let mut frontmatter: Mapping = serde_yaml::from_str(&lines[1..close].concat())?;
+    let metadata = frontmatter
+        .entry(Value::from("metadata"))
+        .or_insert_with(|| Value::Mapping(Mapping::new()));
+    if !metadata.is_mapping() {
+        *metadata = Value::Mapping(Mapping::new());
+    }
+    if let Value::Mapping(map) = metadata {
+        if internal {
+            map.insert(Value::from("internal"), Value::from(true));
+        } else {
+            map.remove("internal");
+        }
+    }
+
+    let mut yaml = serde_yaml::to_string(&frontmatter)?;
+    if eol == "\r\n" {
+        yaml = yaml.replace('\n', "\r\n");
+    }
+    Ok(format!("{}{}{}", lines[0], yaml, lines[close..].concat()))
+}
+
+/// 解析后的 internal 值，SKILL.md 无效时返回 None
+fn parsed_internal(content: &str) -> Option<bool> {
+    parse_skill_md_content(content)
+        .ok()
+        .map(|f| f.metadata.is_some_and(|m| m.internal))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::local_lock::{
+        read_local_lock, write_local_lock, LocalSkillLockEntry, LocalSkillLockFile,
+    };
+    use tempfile::tempdir;
+
+    const BODY: &str = "# Demo\n\nmetadata:\n  internal: true\n---\nBody stays.\n";
+
+    #[test]
+    fn test_round_trip_without_metadata() {
+        let original = format!("---\nname: demo\ndescription: Demo # note\n---\n{}", BODY);
+
+        let hidden = set_frontmatter_internal(&original, true).unwrap();
+        assert_eq!(
+            hidden,
+            format!("---\nname: demo\ndescription: Demo # note\nmetadata:\n  internal: true\n---\n{}", BODY)
+        );
+        assert_eq!(parsed_internal(&hidden), Some(true));
+
+        let shown = set_frontmatter_internal(&hidden, false).unwrap();
+        assert_eq!(shown, original);
+        // 已经可见时不做修改
+        assert_eq!(set_frontmatter_internal(&original, false).unwrap(), original);
+    }
+
+    #[test]
+    fn test_existing_metadata_block() {
+        let original = "---\r\nname: demo\r\ndescription: Demo\r\nmetadata:\r\n    version: 1.0.0\r\n    internal: false\r\n---\r\nBody\r\n";
+
+        let hidden = set_frontmatter_internal(original, true).unwrap();
+        assert_eq!(
+            hidden,
+            "---\r\nname: demo\r\ndescription: Demo\r\nmetadata:\r\n    version: 1.0.0\r\n    internal: true\r\n---\r\nBody\r\n"
+        );
+
+        let shown = set_frontmatter_internal(&hidden, false).unwrap();
+        assert_eq!(
+            shown,
+            "---\r\nname: demo\r\ndescription: Demo\r\nmetadata:\r\n    version: 1.0.0\r\n---\r\nBody\r\n"
+        );
+        assert_eq!(parsed_internal(&shown), Some(false));
+    }
+
+    #[test]
+    fn test_flow_metadata_falls_back_to_yaml() {
+        let original = format!("---\nname: demo\ndescription: Demo\nmetadata: {{version: 1.0.0}}\n---\n{}", BODY);
+
+        let hidden = set_frontmatter_internal(&original, true).unwrap();
+        assert_eq!(parsed_internal(&hidden), Some(true));
+        assert!(hidden.ends_with(&format!("---\n{}", BODY)));
+
+        let shown = set_frontmatter_internal(&hidden, false).unwrap();
+        assert_eq!(parsed_internal(&shown), Some(false));
+        assert!(shown.ends_with(&format!("---\n{}", BODY)));
+    }
+
+    #[test]
+    fn test_set_skill_internal_project() {
+        let project = tempdir().unwrap();
+        let cwd = project.path().to_string_lossy().to_string();
+        let content = "---\nname: demo\ndescription: Demo\n---\n# Demo\n";
+
+        let canonical = project.path().join(".agents/skills/demo");
+        fs::create_dir_all(&canonical).unwrap();
+        fs::write(canonical.join("SKILL.md"), content).unwrap();
+        // Augment: copy 模式
+        let augment = project.path().join(".augment/skills/demo");
+        fs::create_dir_all(&augment).unwrap();
+        fs::write(augment.join("SKILL.md"), content).unwrap();
+
+        let mut lock = LocalSkillLockFile::empty();
+        lock.skills.insert(
+            "demo".to_string(),
+            LocalSkillLockEntry {
+                source: "owner/repo".to_string(),
+                source_type: "github".to_string(),
+                computed_hash: compute_skill_folder_hash(&canonical).unwrap(),
+                ..Default::default()
+            },
+        );
+        write_local_lock(&lock, &cwd).unwrap();
+
+        set_skill_internal("demo", &Scope::Project, Some(&cwd), true).unwrap();
+        for dir in [&canonical, &augment] {
+            let updated = fs::read_to_string(dir.join("SKILL.md")).unwrap();
+            assert_eq!(parsed_internal(&updated), Some(true));
+            assert!(updated.ends_with("---\n# Demo\n"));
+        }
+        assert_eq!(
+            read_local_lock(&cwd).unwrap().skills["demo"].computed_hash,
+            compute_skill_folder_hash(&canonical).unwrap()
+        );
+
+        set_skill_internal("demo", &Scope::Project, Some(&cwd), false).unwrap();
+        assert_eq!(fs::read_to_string(canonical.join("SKILL.md")).unwrap(), content);
+        assert_eq!(fs::read_to_string(augment.join("SKILL.md")).unwrap(), content);
+    }
+}
@@ -19,6 +19,9 @@ pub enum AppError {
     #[error("Invalid SKILL.md: {message}")]
     InvalidSkillMd { message: String },
 
+    #[error("Invalid SKILL.md: Missing {field} field")]
+    SkillMdMissingField { field: String },
+
     #[error("Path error: {message}")]
     Path { message: String },
 
@@ -40,12 +43,21 @@ pub enum AppError {
         ref_name: String,
     },
 
+    #[error("Commit {sha} could not be fetched: {message}")]
+    GitCommitUnavailable { sha: String, message: String },
+
     #[error("Git operation timed out")]
     GitTimeout,
 
     #[error("Git network error: {message}")]
     GitNetworkError { message: String },
 
+    #[error("Git not found: {message}")]
+    GitNotFound { message: String },
+
+    #[error("SSH host key verification failed for {host}: {message}")]
+    GitSshHostKeyFailed { host: String, message: String },
+
     #[error("Path not found: {path}")]
     PathNotFound { path: String },
 
@@ -58,6 +70,22 @@ pub enum AppError {
     #[error("Invalid agent: {agent}")]
     InvalidAgent { agent: String },
 
+    #[error("{message}")]
+    GitHubRateLimited {
+        #[serde(rename = "resetAt")]
+        reset_at: String,
+        message: String,
+    },
+
+    #[error("Operation cancelled")]
+    Cancelled,
+
+    #[error("Checksum mismatch: expected {expected}, got {actual}")]
+    ChecksumMismatch { expected: String, actual: String },
+
+    #[error("Offline mode: {operation} requires network access")]
+    OfflineMode { operation: String },
+
     #[error("{message}")]
     Custom { message: String },
 }
@@ -13,8 +13,19 @@ pub fn run() {
     let builder = Builder::<tauri::Wry>::new()
         .commands(collect_commands![
             commands::agents::list_agents,
+            commands::agents::refresh_agents,
+            commands::agents::get_agent_paths,
             commands::skills::list_skills,
+            commands::skills::open_skill_file,
+            commands::skills::get_skill_info,
+            commands::skills::list_skill_files,
+            commands::skills::set_skill_internal,
+            commands::skills::list_orphaned_skills,
+            commands::skills::audit_links,
+            commands::skills::detect_skill_shadowing,
+            commands::skills::find_skill_usage,
             commands::config::get_config,
+            commands::config::get_source_history,
             commands::config::save_config,
             commands::config::get_last_selected_agents,
             commands::config::save_last_selected_agents,
@@ -24,15 +35,45 @@ pub fn run() {
             commands::config::open_in_explorer,
             commands::install::fetch_available,
             commands::install::install_skills,
+            commands::install::cancel_clone,
+            commands::install::plan_install,
+            commands::install::preview_skill,
+            commands::install::check_symlink_support,
+            commands::github::github_rate_limit,
+            commands::git::detect_git,
+            commands::doctor::doctor,
+            commands::disk_usage::disk_usage,
+            commands::dependencies::resolve_dependencies,
+            commands::lock::migrate_project_lock,
+            commands::lock::check_lock_integrity,
+            commands::state::export_state,
+            commands::state::import_state,
             commands::overwrites::check_overwrites,
+            commands::refs::list_refs,
+            commands::validate::validate_source,
+            commands::validate::lint_skill,
             commands::remove::remove_skill,
+            commands::remove::cleanup_empty_skill_dirs,
             commands::remove_details::get_skill_agent_details,
+            commands::rename::rename_skill,
+            commands::detach::detach_skill,
+            commands::move_scope::move_skill_scope,
+            commands::search::search_skills,
+            commands::compare::compare_sources,
             commands::update::check_updates,
+            commands::update::check_updates_all,
             commands::update::update_skill,
+            commands::update::update_all,
+            commands::update::reinstall_skill,
+            commands::diff::diff_skill,
+            commands::changelog::skill_changelog,
+            commands::verify::verify_skills,
             commands::wizard::open_install_wizard,
             commands::audit::check_skill_audit,
         ])
-        .events(collect_events![]);
+        .events(collect_events![])
+        // operation-event 的负载类型（事件由 Operation 直接 emit）
+        .typ::<commands::operation::OperationEvent<'static>>();
 
     // Debug 模式下导出 TypeScript 绑定
     #[cfg(debug_assertions)]
@@ -55,6 +96,14 @@ pub fn run() {
         .setup(move |app| {
             builder.mount_events(app);
 
+            // 清理上次中断安装残留的暂存目录（不阻塞启动）
+            std::thread::spawn(|| {
+                let removed = core::installer::cleanup_stale_staging();
+                if removed > 0 {
+                    log::info!("Removed {} stale staging directories", removed);
+                }
+            });
+
             #[cfg(debug_assertions)]
             app.handle().plugin(
                 tauri_plugin_log::Builder::default()
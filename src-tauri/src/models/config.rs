@@ -1,5 +1,7 @@
+use crate::core::agents::AgentType;
 use serde::{Deserialize, Serialize};
 use specta::Type;
+use std::collections::HashMap;
 
 /// Skill Deck 应用配置
 /// 持久化到 ~/.skill-deck/config.json
@@ -10,4 +12,85 @@ pub struct SkillDeckConfig {
     /// 已保存的项目路径列表
     #[serde(default)]
     pub projects: Vec<String>,
+    /// 自定义 global canonical skills 目录（必须为绝对路径）
+    /// 未设置时使用 ~/.agents/skills
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub canonical_dir_override: Option<String>,
+    /// 每个项目默认选中的 agents（key 为项目路径）
+    /// 未记录的项目回退到 skill-lock 中的全局 lastSelectedAgents
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub project_default_agents: HashMap<String, Vec<String>>,
+    /// 额外识别为 GitLab 的主机（自托管实例，域名不含 "gitlab" 时使用）
+    /// 格式为 `host` 或 `host:port`
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub gitlab_hosts: Vec<String>,
+    /// 关闭安全审计查询（不再请求 audit API）
+    /// 也可通过 SKILL_DECK_DISABLE_AUDIT 环境变量开启
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub disable_audit: bool,
+    /// 最大并发 git 克隆数（搜索、对比等批量获取共享），未设置时为 4
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_concurrent_clones: Option<u32>,
+    /// 离线模式：不发起任何网络请求，只能使用本地来源
+    /// 也可通过 SKILL_DECK_OFFLINE 环境变量开启
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub offline: bool,
+    /// 按 agent 覆盖 skills 目录（key 为 agent id，如 "cursor"）
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub agent_dir_overrides: HashMap<AgentType, AgentDirOverride>,
+    /// git 可执行文件的绝对路径（git 不在 PATH 中时使用，如从 Finder 启动的 macOS 应用）
+    /// 未设置时依次查找 PATH 和常见安装位置
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub git_path: Option<String>,
+    /// 克隆到 ~/.skill-deck/cache/clones 下按 URL 命名的目录，用完后不删除（便于排查克隆问题）
+    /// 未开启时克隆到随机命名、用完即删的临时目录
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub retain_clones: bool,
+    /// retainClones 开启时最多保留的克隆目录数，超出时删除最久未使用的，未设置时为 10
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_retained_clones: Option<u32>,
+    /// 安装时额外排除的文件名（与默认的 metadata.json 合并）
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub copy_exclude_files: Vec<String>,
+    /// 安装时额外排除的目录名（与默认的 .git 合并）
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub copy_exclude_dirs: Vec<String>,
+    /// 为 true 时 copyExcludeFiles / copyExcludeDirs 替换默认排除项而非合并
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub replace_copy_excludes: bool,
+    /// API 请求的 User-Agent（GitHub、GitLab、审计及 HTTP 下载共用），未设置时为 "skill-deck"
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub user_agent: Option<String>,
+    /// GitHub JSON API 请求的 Accept，未设置时为 "application/vnd.github.v3+json"
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub github_accept: Option<String>,
+    /// 最近安装过的来源（最近使用的在前，按原始来源字符串去重，最多 20 条）
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub sources: Vec<SourceHistoryEntry>,
+}
+
+/// 来源使用记录（install_skills 成功安装后写入）
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Type)]
+#[serde(rename_all = "camelCase")]
+#[specta(rename_all = "camelCase")]
+pub struct SourceHistoryEntry {
+    /// 用户输入的原始来源字符串
+    pub source: String,
+    /// 最近一次安装时间（ISO 8601）
+    pub last_used_at: String,
+    /// 最近一次安装成功的 skill 数量
+    pub skill_count: u32,
+}
+
+/// 单个 agent 的 skills 目录覆盖，未设置的字段沿用内置目录
+#[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq, Eq, Type)]
+#[serde(rename_all = "camelCase")]
+#[specta(rename_all = "camelCase")]
+pub struct AgentDirOverride {
+    /// 项目级 skills 目录（相对项目根目录，如 ".cursor/my-skills"）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub skills_dir: Option<String>,
+    /// 全局 skills 目录（必须为绝对路径）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub global_skills_dir: Option<String>,
 }
@@ -5,6 +5,7 @@ use specta::Type;
 use std::path::PathBuf;
 
 use crate::core::agents::AgentType;
+use crate::core::audit::SkillAuditData;
 
 /// 安装范围
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Type)]
@@ -22,6 +23,23 @@ pub enum Scope {
 pub enum InstallMode {
     Symlink,
     Copy,
+    /// 复制到 canonical 目录，agent 目录中的文件硬链接到 canonical（同一文件系统时节省空间）。
+    /// 修改硬链接文件会影响所有链接；无法硬链接的文件回退为复制
+    Hardlink,
+}
+
+/// 安装目录名冲突时的处理方式
+///
+/// 不同的 skill name 可能 sanitize 成同一目录名（如 `My Skill!` 和 `my-skill`）
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, Type)]
+#[serde(rename_all = "lowercase")]
+#[specta(rename_all = "lowercase")]
+pub enum NameCollisionPolicy {
+    /// 覆盖已有目录（与 CLI 一致）
+    #[default]
+    Overwrite,
+    /// 目录被其他 skill 占用时追加数字后缀（`my-skill-2`），实际目录名记录在 lock 中
+    Suffix,
 }
 
 /// 安装参数
@@ -41,6 +59,38 @@ pub struct InstallParams {
     pub project_path: Option<String>,
     /// 安装模式
     pub mode: InstallMode,
+    /// 为 true 时不自动追加 Universal Agents，只安装到显式选择的 agents
+    ///
+    /// 注意：Symlink 模式仍会写入 canonical 目录（`.agents/skills`）作为 symlink 目标，
+    /// 而 Universal Agents 读取的正是该目录，因此只有 Copy 模式能完全避开它们。
+    #[serde(default)]
+    pub skip_universal: bool,
+    /// 发现 skills 时的最大递归深度，需与 fetch_available 时一致（默认 5）
+    #[serde(default)]
+    pub max_depth: Option<u32>,
+    /// 覆盖来源中的分支/tag（来自 list_refs 的选择）
+    #[serde(default)]
+    pub git_ref: Option<String>,
+    /// 安装目录被其他 skill 占用时的处理方式（默认覆盖）
+    #[serde(default)]
+    pub name_collision: Option<NameCollisionPolicy>,
+    /// 未检测到也强制安装的 agents（预先为尚未启动过的 agent 安装，目录不存在时自动创建）
+    #[serde(default)]
+    pub force_agents: Vec<AgentType>,
+    /// 安装的本地化版本：`SKILL.<locale>.md` 作为目标目录中的 SKILL.md
+    /// （未指定或 skill 没有该版本时使用 SKILL.md）
+    #[serde(default)]
+    pub locale: Option<String>,
+}
+
+/// 覆盖类型（与即将安装的内容相比）
+/// Identical: 内容完全一致；Modified: 内容不同或无法比较
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Type)]
+#[serde(rename_all = "camelCase")]
+#[specta(rename_all = "camelCase")]
+pub enum OverwriteKind {
+    Identical,
+    Modified,
 }
 
 /// 单个 skill 的安装结果
@@ -62,6 +112,9 @@ pub struct InstallResult {
     pub mode: InstallMode,
     /// symlink 是否失败并降级为 copy
     pub symlink_failed: bool,
+    /// symlink 失败原因（捕获的链接创建错误）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub symlink_error: Option<String>,
     /// 错误信息
     pub error: Option<String>,
 }
@@ -77,6 +130,9 @@ pub struct InstallResults {
     pub failed: Vec<InstallResult>,
     /// symlink 失败降级为 copy 的 agents
     pub symlink_fallback_agents: Vec<String>,
+    /// 各降级 agent 的失败原因：(agent, reason)，区分权限问题与文件系统限制
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub symlink_fallback_reasons: Vec<(String, String)>,
 }
 
 /// 可用的 Skill 信息（fetch_available 返回）
@@ -93,6 +149,33 @@ pub struct AvailableSkill {
     /// 所属 plugin 名称（来自 .claude-plugin/ manifest）
     #[serde(skip_serializing_if = "Option::is_none")]
     pub plugin_name: Option<String>,
+    /// 版本号（来自 SKILL.md frontmatter）
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub version: Option<String>,
+    /// 作者
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub author: Option<String>,
+    /// 许可证
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub license: Option<String>,
+    /// 标签
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<String>,
+    /// 分类（来自同级 metadata.json）
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub category: Option<String>,
+    /// 图标地址（来自同级 metadata.json）
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub icon_url: Option<String>,
+    /// 可选的本地化版本（`SKILL.<locale>.md` 的 locale），安装时通过 InstallParams.locale 选择
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub locales: Vec<String>,
+    /// 安全审计数据（仅 with_audit 时填充，审计失败为 None）
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub audit: Option<SkillAuditData>,
+    /// 安装后的内容 hash（fetch 时按克隆内容计算，传给 check_overwrites 比对，无需再次克隆）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub content_hash: Option<String>,
 }
 
 /// 非 Universal Agent 的安装详情
@@ -128,6 +211,26 @@ pub struct SkillAgentDetails {
     // 注意：不设 has_independent_agents 字段，前端直接用 independent_agents.length > 0 推导（YAGNI）
 }
 
+/// 删除（或 dry run 时将删除）的路径类型
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Type)]
+#[serde(rename_all = "lowercase")]
+#[specta(rename_all = "lowercase")]
+pub enum RemovedPathKind {
+    /// symlink / junction（只删除链接本身）
+    Symlink,
+    /// 实体目录（copy 模式安装或 canonical 目录）
+    Directory,
+}
+
+/// 删除路径详情
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+#[serde(rename_all = "camelCase")]
+#[specta(rename_all = "camelCase")]
+pub struct RemovedPathInfo {
+    pub path: String,
+    pub kind: RemovedPathKind,
+}
+
 /// 单个 skill 的删除结果
 /// 对应 CLI: remove.ts 第 148-195 行的 results 数组元素
 #[derive(Debug, Clone, Serialize, Deserialize, Type)]
@@ -138,8 +241,17 @@ pub struct RemoveResult {
     pub skill_name: String,
     /// 是否成功
     pub success: bool,
-    /// 删除的 agent 目录路径列表
+    /// 删除的 agent 目录路径列表（dry run 时为将删除的路径）
     pub removed_paths: Vec<String>,
+    /// removed_paths 对应的类型详情（顺序一致）
+    #[serde(default)]
+    pub path_details: Vec<RemovedPathInfo>,
+    /// 删除（或将删除）的 canonical 目录；仍被其他 agent 使用时为 None
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub canonical_path: Option<String>,
+    /// 是否为 dry run（未实际删除任何内容）
+    #[serde(default)]
+    pub dry_run: bool,
     /// 来源信息（从 lock file 读取，仅 Global）
     pub source: Option<String>,
     /// 来源类型
@@ -148,6 +260,216 @@ pub struct RemoveResult {
     pub error: Option<String>,
 }
 
+/// 重命名 skill 的结果
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+#[serde(rename_all = "camelCase")]
+#[specta(rename_all = "camelCase")]
+pub struct RenameResult {
+    /// 原名称
+    pub old_name: String,
+    /// 新名称（name_rewritten 为 false 时只用作目录名，skill 名称和 lock key 仍为 old_name）
+    pub new_name: String,
+    /// 新的 canonical 目录
+    pub canonical_path: String,
+    /// 已迁移到新名称的 agent 目录路径列表
+    pub relinked_paths: Vec<String>,
+    /// 是否改写了 SKILL.md 中的 name
+    pub name_rewritten: bool,
+}
+
+/// 在 global / project scope 之间移动 skill 的结果
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+#[serde(rename_all = "camelCase")]
+#[specta(rename_all = "camelCase")]
+pub struct MoveScopeResult {
+    /// 目标 scope 的 canonical 目录
+    pub canonical_path: String,
+    /// 在目标 scope 重新创建的 agent 目录路径列表
+    pub relinked_paths: Vec<String>,
+    /// 目标 scope 下没有 skills 目录或迁移失败的 agents（原 scope 的安装仍会被删除）
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub skipped_agents: Vec<String>,
+    /// 是否迁移了 lock 条目（原 scope 没有记录时为 false）
+    pub lock_moved: bool,
+}
+
+/// 将 agent 目录从 symlink 转为独立副本的结果
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+#[serde(rename_all = "camelCase")]
+#[specta(rename_all = "camelCase")]
+pub struct DetachResult {
+    /// 本次从 symlink 转为独立副本的 agents
+    pub detached: Vec<String>,
+    /// 已经是独立副本（copy 模式）的 agents
+    pub already_standalone: Vec<String>,
+    /// 无法转换的 agents：(agent, 原因)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub skipped: Vec<(String, String)>,
+}
+
+/// 孤立的 canonical skill（没有 lock 条目或没有任何 agent 使用）
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+#[serde(rename_all = "camelCase")]
+#[specta(rename_all = "camelCase")]
+pub struct OrphanedSkill {
+    /// SKILL.md 中的 name（无法解析时为目录名）
+    pub name: String,
+    /// canonical 目录
+    pub canonical_path: String,
+    /// SKILL.md 缺失或解析失败的原因
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub skill_md_error: Option<String>,
+    /// lock 中是否有对应条目
+    pub in_lock: bool,
+    /// 使用该目录的 agents（已检测到的 Universal agents + symlink 指向它的 agents）
+    pub referenced_by: Vec<AgentType>,
+}
+
+/// 目标与期望 canonical 目录不一致的 agent 链接
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+#[serde(rename_all = "camelCase")]
+#[specta(rename_all = "camelCase")]
+pub struct LinkMismatch {
+    /// skill 名称（lock key）
+    pub skill_name: String,
+    pub agent: AgentType,
+    /// agent 目录中的 symlink / junction
+    pub link_path: String,
+    /// 链接实际指向的路径（无法读取时为空）
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub actual_target: Option<String>,
+    /// 期望的 canonical 目录
+    pub expected_target: String,
+    /// 实际目标是否存在（false 表示悬空链接）
+    pub target_exists: bool,
+}
+
+/// 同时安装在 global 和项目中的 skill
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+#[serde(rename_all = "camelCase")]
+#[specta(rename_all = "camelCase")]
+pub struct ShadowedSkill {
+    /// SKILL.md 中的 name
+    pub name: String,
+    /// 两个 scope 都能看到该 skill 的 agents
+    pub agents: Vec<SkillResolution>,
+}
+
+/// 单个 agent 对重名 skill 的解析结果
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+#[serde(rename_all = "camelCase")]
+#[specta(rename_all = "camelCase")]
+pub struct SkillResolution {
+    pub agent: AgentType,
+    /// 实际生效的副本所在 scope（按该 agent 的目录优先级）
+    pub effective_scope: Scope,
+    /// 项目级副本目录
+    pub project_path: String,
+    /// 全局副本目录
+    pub global_path: String,
+    /// 两份副本内容是否相同（相同时遮蔽不影响行为）
+    pub identical: bool,
+}
+
+/// 某个 skill 在 global 和已知项目中的安装位置
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+#[serde(rename_all = "camelCase")]
+#[specta(rename_all = "camelCase")]
+pub struct SkillUsage {
+    /// 安装位置（global 在前，项目按 config.json 中的顺序）
+    pub locations: Vec<SkillLocation>,
+    /// config.json 中记录但磁盘上已不存在的项目路径
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub stale_projects: Vec<String>,
+    /// lock 无法读取而跳过的项目
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub unreadable_projects: Vec<UnreadableProject>,
+}
+
+/// 扫描时无法读取的项目
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+#[serde(rename_all = "camelCase")]
+#[specta(rename_all = "camelCase")]
+pub struct UnreadableProject {
+    pub project_path: String,
+    /// 读取失败的原因
+    pub message: String,
+}
+
+/// 单个安装位置
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+#[serde(rename_all = "camelCase")]
+#[specta(rename_all = "camelCase")]
+pub struct SkillLocation {
+    pub scope: Scope,
+    /// 项目路径（global 为 None）
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub project_path: Option<String>,
+    /// canonical 目录
+    pub path: String,
+    /// lock 中记录的来源（未记录时为 None）
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub source: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub source_type: Option<String>,
+    /// lock 中是否有记录（false 表示只在 canonical 目录中找到）
+    pub in_lock: bool,
+}
+
+/// skill 依赖图（resolve_dependencies 返回）
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+#[serde(rename_all = "camelCase")]
+#[specta(rename_all = "camelCase")]
+pub struct DependencyGraph {
+    /// 根 skill 的节点 id
+    pub root: String,
+    /// 节点（根节点在前，其余按解析顺序）
+    pub nodes: Vec<DependencyNode>,
+    /// 依赖边（from 依赖 to）
+    pub edges: Vec<DependencyEdge>,
+    /// 依赖环，每个环为按依赖顺序排列的节点 id
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub cycles: Vec<Vec<String>>,
+    /// 无法解析的依赖
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub unresolved: Vec<UnresolvedDependency>,
+    /// 达到深度或来源数量上限，依赖图不完整
+    pub truncated: bool,
+}
+
+/// 依赖图节点
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+#[serde(rename_all = "camelCase")]
+#[specta(rename_all = "camelCase")]
+pub struct DependencyNode {
+    /// 节点 id（`<source>@<name>`）
+    pub id: String,
+    pub name: String,
+    /// 来源（URL 或本地路径，含子路径和 `#ref`）
+    pub source: String,
+}
+
+/// 依赖边
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+#[serde(rename_all = "camelCase")]
+#[specta(rename_all = "camelCase")]
+pub struct DependencyEdge {
+    pub from: String,
+    pub to: String,
+}
+
+/// 无法解析的依赖
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+#[serde(rename_all = "camelCase")]
+#[specta(rename_all = "camelCase")]
+pub struct UnresolvedDependency {
+    /// 声明该依赖的节点 id
+    pub from: String,
+    /// frontmatter 中的依赖声明
+    pub dependency: String,
+    pub reason: String,
+}
+
 /// fetch_available 返回结果
 #[derive(Debug, Clone, Serialize, Deserialize, Type)]
 #[serde(rename_all = "camelCase")]
@@ -161,4 +483,62 @@ pub struct FetchResult {
     pub skill_filter: Option<String>,
     /// 可用的 skills 列表
     pub skills: Vec<AvailableSkill>,
+    /// 发现过程中被跳过的 SKILL.md
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub warnings: Vec<SkillWarning>,
+}
+
+/// SKILL.md 被跳过的原因
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Type)]
+#[serde(rename_all = "camelCase")]
+#[specta(rename_all = "camelCase")]
+pub enum SkillWarningReason {
+    /// frontmatter 缺失或 YAML 无法解析
+    ParseError,
+    /// 缺少 name / description
+    MissingField,
+    /// internal skill 未被请求
+    Internal,
+}
+
+/// 发现 skills 时被跳过的候选 SKILL.md
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+#[serde(rename_all = "camelCase")]
+#[specta(rename_all = "camelCase")]
+pub struct SkillWarning {
+    /// SKILL.md 相对来源根目录的路径
+    pub relative_path: String,
+    pub reason: SkillWarningReason,
+    /// 具体原因（解析错误信息等）
+    pub message: String,
+}
+
+/// plan_install 返回的安装计划（安装前预览，不克隆）
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+#[serde(rename_all = "camelCase")]
+#[specta(rename_all = "camelCase")]
+pub struct InstallPlan {
+    /// 来源类型
+    pub source_type: String,
+    /// 规范化 URL（本地来源为路径）
+    pub source_url: String,
+    /// 将使用的分支/tag
+    pub git_ref: Option<String>,
+    /// 仓库内子路径
+    pub subpath: Option<String>,
+    /// 逗号分隔的多个子路径（只有一个子路径时为空）
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub subpaths: Vec<String>,
+    /// 安装时是否需要克隆仓库
+    pub requires_clone: bool,
+    /// 安装范围
+    pub scope: Scope,
+    /// 安装模式
+    pub mode: InstallMode,
+    /// 最终安装的 agents（含自动追加的 Universal Agents）
+    pub agents: Vec<String>,
+    /// 自动追加的 Universal Agents
+    pub universal_agents_added: Vec<String>,
+    /// 将安装的 skills（仅本地来源可知；远程来源需克隆后才能确定，为 None）
+    pub skills: Option<Vec<String>>,
 }
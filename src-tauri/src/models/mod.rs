@@ -2,6 +2,6 @@ pub mod config;
 mod source;
 mod install;
 
-pub use config::SkillDeckConfig;
+pub use config::{SkillDeckConfig, SourceHistoryEntry};
 pub use source::*;
 pub use install::*;
@@ -39,14 +39,20 @@ pub struct ParsedSource {
     pub source_type: SourceType,
     /// 规范化后的 URL
     pub url: String,
-    /// 仓库内子路径
+    /// 仓库内子路径（逗号分隔的多个子路径时为第一个）
     pub subpath: Option<String>,
+    /// 逗号分隔的多个子路径（如 `owner/repo/skills/a,skills/b`），只有一个子路径时为空
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub subpaths: Vec<String>,
     /// 本地路径（仅 Local 类型）
     pub local_path: Option<PathBuf>,
     /// Git 分支/tag
     pub git_ref: Option<String>,
     /// @skill 语法提取的 skill 名称
     pub skill_filter: Option<String>,
+    /// 期望的内容 SHA-256（小写 hex，来自 `#sha256=` fragment，仅 DirectUrl）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sha256: Option<String>,
 }
 
 impl ParsedSource {
@@ -56,9 +62,11 @@ impl ParsedSource {
             source_type: SourceType::GitHub,
             url,
             subpath: None,
+            subpaths: Vec::new(),
             local_path: None,
             git_ref: None,
             skill_filter: None,
+            sha256: None,
         }
     }
 
@@ -68,9 +76,11 @@ impl ParsedSource {
             source_type: SourceType::Local,
             url: String::new(),
             subpath: None,
+            subpaths: Vec::new(),
             local_path: Some(path),
             git_ref: None,
             skill_filter: None,
+            sha256: None,
         }
     }
 
@@ -80,6 +90,15 @@ impl ParsedSource {
         self
     }
 
+    /// 需要搜索的全部子路径（没有子路径时为空）
+    pub fn search_subpaths(&self) -> Vec<&str> {
+        if self.subpaths.is_empty() {
+            self.subpath.as_deref().into_iter().collect()
+        } else {
+            self.subpaths.iter().map(String::as_str).collect()
+        }
+    }
+
     /// 设置 Git ref
     pub fn with_ref(mut self, git_ref: String) -> Self {
         self.git_ref = Some(git_ref);
@@ -92,3 +111,23 @@ impl ParsedSource {
         self
     }
 }
+
+/// Git 引用类型
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize, Type)]
+#[serde(rename_all = "lowercase")]
+#[specta(rename_all = "lowercase")]
+pub enum GitRefType {
+    Branch,
+    Tag,
+}
+
+/// 远程仓库的分支/tag（用于安装时选择 git_ref）
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Type)]
+#[serde(rename_all = "camelCase")]
+#[specta(rename_all = "camelCase")]
+pub struct GitRefInfo {
+    /// 分支名或 tag 名
+    pub name: String,
+    /// 引用类型
+    pub ref_type: GitRefType,
+}
@@ -19,6 +19,37 @@ async listAgents() : Promise<Result<AgentInfo[], AppError>> {
     else return { status: "error", error: e  as any };
 }
 },
+/**
+ * 重新检测 agents，并与调用方之前的检测结果对比
+ * 
+ * 前端缓存 list_agents 结果时，可用此命令发现运行期间新安装/卸载的 agent，
+ * 只更新变化的部分。
+ * 
+ * # Arguments
+ * * `previous_detected` - 之前检测到的 agent id 列表（None 视为空）
+ */
+async refreshAgents(previousDetected: AgentType[] | null) : Promise<Result<AgentRefresh, AppError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("refresh_agents", { previousDetected }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * 列出所有 Agents 实际解析出的 skills 目录及其是否存在（诊断用）
+ * 
+ * # Arguments
+ * * `project_path` - 解析项目级目录的基准路径，默认当前工作目录
+ */
+async getAgentPaths(projectPath: string | null) : Promise<Result<AgentPathInfo[], AppError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("get_agent_paths", { projectPath }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
 /**
  * 列出已安装的 skills
  * 对应前端调用: invoke('list_skills', { params })
@@ -31,6 +62,145 @@ async listSkills(params: ListSkillsParams) : Promise<Result<ListSkillsResult, Ap
     else return { status: "error", error: e  as any };
 }
 },
+/**
+ * 在系统默认编辑器中打开 skill 的 SKILL.md
+ * 路径解析与 remove_details 一致：canonical_skills_dir + skill_dir_name
+ */
+async openSkillFile(skillName: string, scope: Scope, projectPath: string | null) : Promise<Result<null, AppError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("open_skill_file", { skillName, scope, projectPath }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * 获取单个已安装 skill 的完整信息
+ * 
+ * 汇总 SKILL.md frontmatter、lock 条目、canonical 目录状态和各 agent 的安装详情。
+ * 只读取本地文件；是否有更新需要访问网络，请使用 check_updates。
+ * 
+ * # Arguments
+ * * `scope` - 安装范围
+ * * `name` - skill 名称（lock 中的 key）
+ * * `project_path` - Project scope 时的项目路径
+ */
+async getSkillInfo(scope: Scope, name: string, projectPath: string | null) : Promise<Result<SkillInfo, AppError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("get_skill_info", { scope, name, projectPath }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * 列出已安装 skill 的文件树（canonical 目录）
+ * 
+ * 只读遍历，跳过 .git、node_modules；skill 内的符号链接不会被跟随到 skill 目录之外。
+ * 
+ * # Arguments
+ * * `scope` - 安装范围
+ * * `name` - skill 名称（lock 中的 key）
+ * * `project_path` - Project scope 时的项目路径
+ */
+async listSkillFiles(scope: Scope, name: string, projectPath: string | null) : Promise<Result<SkillFileEntry[], AppError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("list_skill_files", { scope, name, projectPath }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * 设置 skill 是否为 internal（internal skill 不出现在 list_skills 结果中）
+ * 
+ * 改写 canonical 目录及 copy 模式 agent 目录中 SKILL.md 的 `metadata.internal`，
+ * 不卸载 skill。
+ * 
+ * # Arguments
+ * * `scope` - 安装范围
+ * * `name` - skill 名称
+ * * `project_path` - Project scope 时的项目路径
+ * * `internal` - true 隐藏，false 恢复显示
+ */
+async setSkillInternal(scope: Scope, name: string, projectPath: string | null, internal: boolean) : Promise<Result<null, AppError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("set_skill_internal", { scope, name, projectPath, internal }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * 列出孤立的 canonical skills
+ * 
+ * 返回 canonical 目录中没有 lock 条目、或没有任何 agent 使用的 skill（含 internal 和
+ * SKILL.md 无效的目录），用于清理安装中断等原因留下的残留。
+ * 
+ * # Arguments
+ * * `scope` - 安装范围
+ * * `project_path` - Project scope 时的项目路径
+ */
+async listOrphanedSkills(scope: Scope, projectPath: string | null) : Promise<Result<OrphanedSkill[], AppError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("list_orphaned_skills", { scope, projectPath }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * 检查 agent 链接是否指向正确的 canonical 目录
+ * 
+ * 对 lock 中的每个 skill 读取各 agent 目录中的 symlink / junction 目标，
+ * 返回与期望 canonical 目录不一致的链接（实际目标与期望目标），供修复使用。
+ * 
+ * # Arguments
+ * * `scope` - 安装范围
+ * * `project_path` - Project scope 时的项目路径
+ */
+async auditLinks(scope: Scope, projectPath: string | null) : Promise<Result<LinkMismatch[], AppError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("audit_links", { scope, projectPath }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * 检测同时安装在 global 和项目中的 skill
+ * 
+ * 对每个已检测到的 agent，比较其项目级和全局目录中同名（按 SKILL.md name）的 skill，
+ * 并按该 agent 的目录优先级给出实际生效的副本。
+ * 
+ * # Arguments
+ * * `project_path` - 项目路径
+ */
+async detectSkillShadowing(projectPath: string) : Promise<Result<ShadowedSkill[], AppError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("detect_skill_shadowing", { projectPath }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * 查找 skill 在 global 和所有已保存项目中的安装位置
+ * 
+ * 扫描全局 lock、每个项目的 skills-lock.json 以及对应的 canonical 目录；
+ * 已不存在的项目路径记入 `staleProjects`。
+ * 
+ * # Arguments
+ * * `name` - skill 名称（lock key）
+ */
+async findSkillUsage(name: string) : Promise<Result<SkillUsage, AppError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("find_skill_usage", { name }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
 /**
  * 获取配置
  * 文件不存在或解析失败时返回默认配置
@@ -43,9 +213,22 @@ async getConfig() : Promise<Result<SkillDeckConfig, AppError>> {
     else return { status: "error", error: e  as any };
 }
 },
+/**
+ * 获取最近安装过的来源（最近使用的在前），供安装向导快速选择
+ */
+async getSourceHistory() : Promise<Result<SourceHistoryEntry[], AppError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("get_source_history") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
 /**
  * 保存配置
- * 目录不存在时自动创建
+ * 目录不存在时自动创建；原子写入并持有与 record_source_usage 相同的文件锁
+ * canonicalDirOverride 和 gitPath 必须为绝对路径，agentDirOverrides 中全局目录必须为绝对路径、项目目录必须为相对路径，
+ * 保存后立即生效（gitlabHosts、disableAudit、maxConcurrentClones、offline、userAgent、githubAccept 同样立即生效）
  */
 async saveConfig(config: SkillDeckConfig) : Promise<Result<null, AppError>> {
     try {
@@ -57,18 +240,20 @@ async saveConfig(config: SkillDeckConfig) : Promise<Result<null, AppError>> {
 },
 /**
  * 获取上次选择的 agents
- * 读取 ~/.agents/.skill-lock.json 中的 lastSelectedAgents
+ * 提供 project_path 且该项目有记录时返回项目级默认 agents，
+ * 否则读取 ~/.agents/.skill-lock.json 中的 lastSelectedAgents
  */
-async getLastSelectedAgents() : Promise<string[]> {
-    return await TAURI_INVOKE("get_last_selected_agents");
+async getLastSelectedAgents(projectPath: string | null) : Promise<string[]> {
+    return await TAURI_INVOKE("get_last_selected_agents", { projectPath });
 },
 /**
  * 保存选择的 agents
- * 写入 ~/.agents/.skill-lock.json 中的 lastSelectedAgents
+ * 提供 project_path 时写入 config.json 的 projectDefaultAgents，
+ * 否则写入 ~/.agents/.skill-lock.json 中的 lastSelectedAgents
  */
-async saveLastSelectedAgents(agents: string[]) : Promise<Result<null, AppError>> {
+async saveLastSelectedAgents(agents: string[], projectPath: string | null) : Promise<Result<null, AppError>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("save_last_selected_agents", { agents }) };
+    return { status: "ok", data: await TAURI_INVOKE("save_last_selected_agents", { agents, projectPath }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
@@ -120,13 +305,19 @@ async openInExplorer(path: string) : Promise<Result<null, AppError>> {
  * 
  * # Arguments
  * * `source` - 来源字符串（支持 9 种格式）
+ * * `with_audit` - 是否同时获取安全审计数据（默认 false，避免离线时等待 3 秒超时）
+ * * `max_depth` - 递归搜索的最大深度（默认 5，不影响优先目录扫描）
+ * * `git_ref` - 覆盖来源中的分支/tag（来自 list_refs 的选择）
+ * * `name_glob` - 按名称过滤 skills 的 glob（如 `python-*`，忽略大小写）；
+ * 与 `@skill` 同时使用时，`@skill` 只在过滤后的结果中生效；无匹配时返回空列表，glob 无效时返回错误
+ * * `op_id` - 前端指定的操作 id（用于 operation-event、clone-progress 和 cancel_clone），未传时自动生成
  * 
  * # Returns
  * * `FetchResult` - 包含来源信息和可用 skills 列表
  */
-async fetchAvailable(source: string) : Promise<Result<FetchResult, AppError>> {
+async fetchAvailable(source: string, withAudit: boolean | null, maxDepth: number | null, gitRef: string | null, nameGlob: string | null, opId: string | null) : Promise<Result<FetchResult, AppError>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("fetch_available", { source }) };
+    return { status: "ok", data: await TAURI_INVOKE("fetch_available", { source, withAudit, maxDepth, gitRef, nameGlob, opId }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
@@ -137,415 +328,2120 @@ async fetchAvailable(source: string) : Promise<Result<FetchResult, AppError>> {
  * 
  * # Arguments
  * * `params` - 安装参数（来源、选中的 skills、agents、scope、mode）
+ * * `op_id` - 前端指定的操作 id（用于 operation-event、clone-progress 和 cancel_clone），未传时自动生成
  * 
  * # Returns
  * * `InstallResults` - 安装结果汇总
  */
-async installSkills(params: InstallParams) : Promise<Result<InstallResults, AppError>> {
+async installSkills(params: InstallParams, opId: string | null) : Promise<Result<InstallResults, AppError>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("install_skills", { params }) };
+    return { status: "ok", data: await TAURI_INVOKE("install_skills", { params, opId }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
 /**
- * 检测哪些 skill × agent 组合会被覆盖
+ * 取消进行中的克隆
+ * 
+ * 终止对应的 git clone 进程，所属操作（fetch_available / install_skills 等）返回 `AppError::Cancelled`
  * 
  * # Arguments
- * * `skills` - 要安装的 skill 名称列表
- * * `agents` - 目标 agent 列表
- * * `scope` - 安装范围
- * * `project_path` - Project scope 时的项目路径
+ * * `op_id` - 只取消该操作的克隆（与发起命令时传入或 operation-event 中的 opId 相同）；
+ * 未传时取消所有进行中的克隆
+ */
+async cancelClone(opId: string | null) : Promise<CancelCloneResult> {
+    return await TAURI_INVOKE("cancel_clone", { opId });
+},
+/**
+ * 预览安装计划
  * 
- * # Returns
- * * `HashMap<String, Vec<String>>` - { skill_name: [agent_ids that will be overwritten] }
+ * 解析来源并确定最终的目标 agents（含 Universal Agents 自动追加和 skip_universal 逻辑），
+ * 不会克隆仓库或下载文件。远程来源在克隆前无法知道 skill 数量，此时只返回来源、
+ * agent 和 scope 信息（`skills` 为 None）；本地来源会做一次快速发现。
+ * 
+ * # Arguments
+ * * `params` - 与 install_skills 相同的安装参数
  */
-async checkOverwrites(skills: string[], agents: string[], scope: Scope, projectPath: string | null) : Promise<Result<Partial<{ [key in string]: string[] }>, AppError>> {
+async planInstall(params: InstallParams) : Promise<Result<InstallPlan, AppError>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("check_overwrites", { skills, agents, scope, projectPath }) };
+    return { status: "ok", data: await TAURI_INVOKE("plan_install", { params }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
 /**
- * 删除指定 skill
+ * 安装前预览某个 skill 的 SKILL.md
+ * 
+ * 只获取单个文件（GitHub raw / GitLab Files API / `git archive --remote`），
+ * 不完整克隆仓库。内容超过上限时截断，`truncated` 为 true。
  * 
  * # Arguments
- * * `scope` - 删除范围（global/project）
- * * `name` - skill 名称
- * * `project_path` - Project scope 时的项目路径
- * * `agents` - 部分移除时指定的 agent 列表（None 或空 = 完全删除）
- * * `full_removal` - 是否完全删除（true = 删除一切，false = 仅删除指定 agents 的 symlink）
+ * * `source` - 来源（同 fetch_available）
+ * * `relative_path` - skill 目录相对来源根目录的路径（AvailableSkill.relativePath）
+ * * `git_ref` - 可选的分支/标签/提交，优先于来源中的 ref
  */
-async removeSkill(scope: Scope, name: string, projectPath: string | null, agents: AgentType[] | null, fullRemoval: boolean | null) : Promise<Result<RemoveResult, AppError>> {
+async previewSkill(source: string, relativePath: string, gitRef: string | null) : Promise<Result<SkillPreview, AppError>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("remove_skill", { scope, name, projectPath, agents, fullRemoval }) };
+    return { status: "ok", data: await TAURI_INVOKE("preview_skill", { source, relativePath, gitRef }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
 /**
- * 查询 skill 的 agent 安装详情
+ * 检测指定范围的 canonical 目录能否创建 symlink
  * 
- * 对话框挂载时调用，返回 universal/non-universal 分组信息
+ * 结果在进程内缓存。返回 false 时 Symlink 模式安装会直接复制到各 agent 目录，
+ * 前端可据此提示或默认选择 Copy 模式。
+ * 
+ * # Arguments
+ * * `scope` - 安装范围
+ * * `project_path` - Project scope 时的项目路径
  */
-async getSkillAgentDetails(scope: Scope, name: string, projectPath: string | null) : Promise<Result<SkillAgentDetails, AppError>> {
+async checkSymlinkSupport(scope: Scope, projectPath: string | null) : Promise<boolean> {
+    return await TAURI_INVOKE("check_symlink_support", { scope, projectPath });
+},
+/**
+ * 查询 GitHub API 限流额度
+ * 
+ * 返回 core（REST API）和 search 两个桶的 limit / remaining / resetAt，以及是否找到 token。
+ * 未认证时按 IP 计算额度（core 每小时 60 次），配置 token 后提升到 5000 次。
+ */
+async githubRateLimit() : Promise<Result<GitHubRateLimit, AppError>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("get_skill_agent_details", { scope, name, projectPath }) };
+    return { status: "ok", data: await TAURI_INVOKE("github_rate_limit") };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
 /**
- * 检测指定 scope 的 skills 是否有更新
+ * 检测 git
  * 
- * 流程：
- * 1. 读取对应 scope 的 .skill-lock.json
- * 2. 过滤出 sourceType == "github" 且有 skillFolderHash 和 skillPath 的 skills
- * 3. 按 source 分组，对每组调用 GitHub Trees API
- * 4. 比对本地 hash 与远程 hash
+ * 配置了 gitPath 时只检查该路径；否则依次查找 PATH 和常见安装位置，
+ * 返回第一个能运行 `git --version` 的 git。未找到时 `found` 为 false，原因见 `error`。
  */
-async checkUpdates(scope: Scope, projectPath: string | null) : Promise<Result<SkillUpdateInfo[], AppError>> {
+async detectGit() : Promise<Result<GitInfo, AppError>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("check_updates", { scope, projectPath }) };
+    return { status: "ok", data: await TAURI_INVOKE("detect_git") };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
 /**
- * 更新指定 skill
+ * 运行环境健康检查
  * 
- * 本质是"重新安装"：从 lock 文件读取来源信息，构造安装 URL，复用安装逻辑。
- * 与 CLI update 命令行为一致。
+ * 每项检查返回 ok / warn / fail 及说明；检查本身失败不会导致命令失败。
+ * 网络检查各有 5 秒超时，离线模式下跳过。
  */
-async updateSkill(scope: Scope, name: string, projectPath: string | null) : Promise<Result<null, AppError>> {
+async doctor() : Promise<Result<DoctorReport, AppError>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("update_skill", { scope, name, projectPath }) };
+    return { status: "ok", data: await TAURI_INVOKE("doctor") };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
 /**
- * 打开安装向导独立窗口
+ * 统计 skill-deck 使用的磁盘空间
  * 
- * 必须为 async —— 同步 command 在主线程执行，
- * 而 WebviewWindowBuilder::build() 也需要主线程，会导致死锁。
- * async command 在异步线程执行，build() 可以安全回调主线程。
+ * 汇总 `~/.agents/skills`、克隆缓存目录（开启 retainClones 时）和已保存项目的
+ * canonical 目录，返回各项大小及无法读取而跳过的路径。
  */
-async openInstallWizard(entryPoint: string, scope: string, projectPath: string | null, prefillSource: string | null, prefillSkillName: string | null) : Promise<Result<null, AppError>> {
+async diskUsage() : Promise<Result<DiskUsage, AppError>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("open_install_wizard", { entryPoint, scope, projectPath, prefillSource, prefillSkillName }) };
+    return { status: "ok", data: await TAURI_INVOKE("disk_usage") };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
 /**
- * 检查 skill 的安全审计数据
+ * 解析 skill 的依赖图
+ * 
+ * 克隆（或读取）来源，按 SKILL.md frontmatter 的 `dependencies` 逐层加载依赖所在的来源，
+ * 返回节点、边、依赖环和无法解析的依赖，供安装前展示。
+ * 
+ * # Arguments
+ * * `source` - 来源字符串
+ * * `skill_name` - 根 skill 名称
+ * * `max_depth` - 最大依赖深度（默认 5）
+ * * `op_id` - 前端指定的操作 id（用于 operation-event、clone-progress 和 cancel_clone），未传时自动生成
  */
-async checkSkillAudit(source: string, skills: string[]) : Promise<Result<Partial<{ [key in string]: SkillAuditData }> | null, AppError>> {
+async resolveDependencies(source: string, skillName: string, maxDepth: number | null, opId: string | null) : Promise<Result<DependencyGraph, AppError>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("check_skill_audit", { source, skills }) };
+    return { status: "ok", data: await TAURI_INVOKE("resolve_dependencies", { source, skillName, maxDepth, opId }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
-}
-}
-
-/** user-defined events **/
-
-
-
-/** user-defined constants **/
-
-
-
-/** user-defined types **/
-
+},
 /**
- * Agent 信息（返回给前端）
- * 对应 CLI: 综合 AgentConfig + detectInstalled 结果
+ * 将项目的旧版 lock 文件迁移为 skills-lock.json
+ * 
+ * # Arguments
+ * * `project_path` - 项目路径
+ * * `delete_legacy` - 迁移成功后是否删除旧版文件（默认 false，保留以便回退）
  */
-export type AgentInfo = { id: AgentType; name: string; skillsDir: string; globalSkillsDir: string; detected: boolean; 
+async migrateProjectLock(projectPath: string, deleteLegacy: boolean | null) : Promise<Result<LockMigrationResult, AppError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("migrate_project_lock", { projectPath, deleteLegacy }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
 /**
- * 是否是 Universal Agent（安装逻辑用）
- * 对应 CLI: isUniversalAgent()
+ * 检查 lock 文件完整性，损坏时自动修复
+ * 
+ * 解析失败时先备份为 `<lock>.bak`，再根据 canonical 目录中的 SKILL.md 重建 lock。
+ * 
+ * # Arguments
+ * * `scope` - 范围（Global/Project）
+ * * `project_path` - Project scope 时的项目路径
  */
-isUniversal: boolean; 
+async checkLockIntegrity(scope: Scope, projectPath: string | null) : Promise<Result<LockIntegrityReport, AppError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("check_lock_integrity", { scope, projectPath }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
 /**
- * 是否在 Universal 列表显示（UI 显示用）
- * 对应 CLI: getUniversalAgents() 的过滤条件
+ * 导出 global 状态
+ * 
+ * # Arguments
+ * * `out_path` - 输出的 tar.gz 路径
+ * * `include_skills` - 是否包含 canonical skills 目录（默认 true，新机器无需重新下载）
+ * * `op_id` - 前端指定的操作 id（用于 operation-event），未传时自动生成
  */
-showInUniversalList: boolean }
+async exportState(outPath: string, includeSkills: boolean | null, opId: string | null) : Promise<Result<StateExportReport, AppError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("export_state", { outPath, includeSkills, opId }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
 /**
- * Agent 类型枚举
- * 完整对应 CLI: types.ts AgentType
+ * 从备份恢复 global 状态
+ * 
+ * 先恢复 lock 和 skills（恢复位置按导入后配置的 canonicalDirOverride 计算），
+ * 全部成功后最后保存 config.json；恢复失败时本机配置保持不变。
+ * 
+ * # Arguments
+ * * `in_path` - export_state 生成的 tar.gz
+ * * `merge` - false（默认）: 替换本机 lock 和配置；
+ * true: 合并 lock 条目（按 updatedAt 取较新者），配置只补充本机没有的项目和 GitLab 主机
+ * * `op_id` - 前端指定的操作 id（用于 operation-event），未传时自动生成
  */
-export type AgentType = "amp" | "antigravity" | "augment" | "claude-code" | "openclaw" | "cline" | "codebuddy" | "codex" | "command-code" | "continue" | "crush" | "cursor" | "droid" | "gemini-cli" | "github-copilot" | "goose" | "iflow-cli" | "junie" | "kilo" | "kimi-cli" | "kiro-cli" | "kode" | "mcpjam" | "mistral-vibe" | "mux" | "neovate" | "opencode" | "openhands" | "pi" | "qoder" | "qwen-code" | "replit" | "roo" | "trae" | "trae-cn" | "windsurf" | "zencoder" | "pochi" | "adal" | "cortex" | "universal"
-export type AppError = { kind: "io"; data: { message: string } } | { kind: "yaml"; data: { message: string } } | { kind: "json"; data: { message: string } } | { kind: "invalidSkillMd"; data: { message: string } } | { kind: "path"; data: { message: string } } | { kind: "invalidSource"; data: { value: string } } | { kind: "gitCloneFailed"; data: { message: string } } | { kind: "gitAuthFailed"; data: { message: string } } | { kind: "gitRepoNotFound"; data: { repo: string } } | { kind: "gitRefNotFound"; data: { refName: string } } | { kind: "gitTimeout" } | { kind: "gitNetworkError"; data: { message: string } } | { kind: "pathNotFound"; data: { path: string } } | { kind: "installFailed"; data: { message: string } } | { kind: "noSkillsFound" } | { kind: "invalidAgent"; data: { agent: string } } | { kind: "custom"; data: { message: string } }
+async importState(inPath: string, merge: boolean | null, opId: string | null) : Promise<Result<StateImportReport, AppError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("import_state", { inPath, merge, opId }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
 /**
- * 可用的 Skill 信息（fetch_available 返回）
+ * 检测哪些 skill × agent 组合会被覆盖
+ * 
+ * 提供 content_hashes 时与已安装目录比对 hash，内容一致的标记为 Identical（无需提示）；
+ * 否则一律为 Modified。hash 来自 fetch_available 返回的 `AvailableSkill.contentHash`，
+ * 不会重新克隆来源。
+ * 
+ * # Arguments
+ * * `skills` - 要安装的 skill 名称列表
+ * * `agents` - 目标 agent 列表
+ * * `scope` - 安装范围
+ * * `project_path` - Project scope 时的项目路径
+ * * `content_hashes` - 可选的 { skill_name: 即将安装内容的 hash }
+ * 
+ * # Returns
+ * * `HashMap<String, Vec<(String, OverwriteKind)>>` - { skill_name: [(agent_id, kind)] }
  */
-export type AvailableSkill = { 
+async checkOverwrites(skills: string[], agents: string[], scope: Scope, projectPath: string | null, contentHashes: Partial<{ [key in string]: string }> | null) : Promise<Result<Partial<{ [key in string]: ([string, OverwriteKind])[] }>, AppError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("check_overwrites", { skills, agents, scope, projectPath, contentHashes }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
 /**
- * Skill 名称
+ * 列出来源仓库的分支和 tags
+ * 
+ * - GitHub / GitLab：调用对应平台 API（分页获取）
+ * - 其他来源：返回空列表（无法选择 ref）
+ * 
+ * 排序：分支在前（按名称升序），tags 在后（按名称降序，新版本通常排在前面）
+ * 
+ * # Arguments
+ * * `source` - 来源字符串（支持 9 种格式）
+ */
+async listRefs(source: string) : Promise<Result<GitRefInfo[], AppError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("list_refs", { source }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * 校验来源格式并检查能否访问
+ * 
+ * 来源格式无法解析时直接返回解析错误；能解析但无法访问时返回
+ * `reachable: false` 和分类后的错误（认证失败、仓库不存在、ref 不存在等）。
+ * 
+ * # Arguments
+ * * `source` - 来源字符串（格式同 fetch_available）
+ */
+async validateSource(source: string) : Promise<Result<SourceValidation, AppError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("validate_source", { source }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * 检查本地 skill 目录能否正常安装
+ * 
+ * 复用安装时的 SKILL.md 解析、名称 sanitize 和复制排除规则，
+ * 把安装时静默处理的情况作为诊断返回（error / warning / info）。
+ * 
+ * # Arguments
+ * * `path` - skill 根目录
+ * * `op_id` - 前端指定的操作 id（用于 operation-event），未传时自动生成
+ */
+async lintSkill(path: string, opId: string | null) : Promise<Result<LintDiagnostic[], AppError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("lint_skill", { path, opId }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * 删除指定 skill
+ * 
+ * # Arguments
+ * * `scope` - 删除范围（global/project）
+ * * `name` - skill 名称
+ * * `project_path` - Project scope 时的项目路径
+ * * `agents` - 部分移除时指定的 agent 列表（None 或空 = 完全删除）
+ * * `full_removal` - 是否完全删除（true = 删除一切，false = 仅删除指定 agents 的 symlink）
+ * * `dry_run` - 只返回将删除的路径，不实际删除（默认 false），用于确认对话框预览
+ */
+async removeSkill(scope: Scope, name: string, projectPath: string | null, agents: AgentType[] | null, fullRemoval: boolean | null, dryRun: boolean | null) : Promise<Result<RemoveResult, AppError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("remove_skill", { scope, name, projectPath, agents, fullRemoval, dryRun }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * 清理空的 agent skills 目录
+ * 
+ * 只删除 skills 子目录（如 `.cursor/skills` 不会连带删除 `.cursor`），不影响 agent 检测。
+ * 
+ * # Arguments
+ * * `scope` - 范围（global/project）
+ * * `project_path` - Project scope 时的项目路径
+ * * `agents` - 要清理的 agent 列表（None 或空 = 全部 agents）
+ * 
+ * # Returns
+ * * 已删除的目录路径
+ */
+async cleanupEmptySkillDirs(scope: Scope, projectPath: string | null, agents: AgentType[] | null) : Promise<Result<string[], AppError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("cleanup_empty_skill_dirs", { scope, projectPath, agents }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * 查询 skill 的 agent 安装详情
+ * 
+ * 对话框挂载时调用，返回 universal/non-universal 分组信息
+ */
+async getSkillAgentDetails(scope: Scope, name: string, projectPath: string | null) : Promise<Result<SkillAgentDetails, AppError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("get_skill_agent_details", { scope, name, projectPath }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * 重命名已安装的 skill
+ * 
+ * 新名称（sanitize 后）已存在时返回错误，不做任何修改。
+ * 
+ * # Arguments
+ * * `scope` - 范围（global/project）
+ * * `name` - 当前名称
+ * * `new_name` - 新名称
+ * * `project_path` - Project scope 时的项目路径
+ * * `rewrite_skill_md` - 是否同时改写 SKILL.md 中的 name（默认 false）
+ */
+async renameSkill(scope: Scope, name: string, newName: string, projectPath: string | null, rewriteSkillMd: boolean | null) : Promise<Result<RenameResult, AppError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("rename_skill", { scope, name, newName, projectPath, rewriteSkillMd }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * 将 skill 在指定 agents 下的 symlink 替换为 canonical 目录的独立副本
+ * 
+ * 脱离的 agents 记录在 lock 中，之后 update_skill / reinstall_skill 不会覆盖这些副本。
+ * 
+ * # Arguments
+ * * `scope` - 范围（global/project）
+ * * `name` - skill 名称
+ * * `project_path` - Project scope 时的项目路径
+ * * `agents` - 目标 agents（None 或空列表 = 所有以 symlink 安装了该 skill 的 agents）
+ */
+async detachSkill(scope: Scope, name: string, projectPath: string | null, agents: AgentType[] | null) : Promise<Result<DetachResult, AppError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("detach_skill", { scope, name, projectPath, agents }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * 在 global 与 project scope 之间移动已安装的 skill
+ * 
+ * 复制 canonical 目录到目标 scope，按原安装形态重新创建 agent 目录，
+ * 在 global lock 与项目 skills-lock.json 之间迁移条目，最后删除原 scope 的安装。
+ * 目标 scope 已存在同名 skill 时返回错误，不做任何修改。
+ * 
+ * # Arguments
+ * * `name` - skill 名称
+ * * `from_scope` - 当前 scope
+ * * `to_scope` - 目标 scope
+ * * `project_path` - 项目路径（两个方向都需要）
+ * * `op_id` - 前端指定的操作 id（用于 operation-event），未传时自动生成
+ */
+async moveSkillScope(name: string, fromScope: Scope, toScope: Scope, projectPath: string | null, opId: string | null) : Promise<Result<MoveScopeResult, AppError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("move_skill_scope", { name, fromScope, toScope, projectPath, opId }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * 在多个来源中搜索 skills
+ * 
+ * 对 name / description 做大小写不敏感的子串匹配，空关键字返回全部 skills。
+ * 排序：名称完全匹配 > 名称前缀匹配 > 名称包含 > 仅描述包含，同级按名称和来源顺序。
+ * 
+ * # Arguments
+ * * `query` - 搜索关键字
+ * * `sources` - 来源字符串列表（格式同 fetch_available）
+ * * `op_id` - 前端指定的操作 id（用于 operation-event、clone-progress 和 cancel_clone），未传时自动生成
+ */
+async searchSkills(query: string, sources: string[], opId: string | null) : Promise<Result<SkillSearchResults, AppError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("search_skills", { query, sources, opId }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * 对比两个来源的 skills 列表
+ * 
+ * 按 skill 名称匹配，各列表按名称排序。
+ * 
+ * # Arguments
+ * * `source_a` - 基准来源（格式同 fetch_available）
+ * * `source_b` - 对比来源
+ */
+async compareSources(sourceA: string, sourceB: string) : Promise<Result<SourceComparison, AppError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("compare_sources", { sourceA, sourceB }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * 检测指定 scope 的 skills 是否有更新
+ * 
+ * 流程：
+ * 1. 读取对应 scope 的 .skill-lock.json
+ * 2. 过滤出支持的托管平台（github / gitlab）且有 skillFolderHash 和 skillPath 的 skills；
+ * Project scope 的 GitHub 来源额外按已安装文件计算 git tree SHA（lock 没有 remoteHash 时也可检测）；
+ * 尚不支持的已知平台（bitbucket）保留下来，结果中带 unsupportedReason
+ * 3. 按 source 分组，对每组调用对应平台的 Tree API
+ * 4. 比对本地 hash 与远程 hash，有更新时读取远程 SKILL.md 的 version
+ */
+async checkUpdates(scope: Scope, projectPath: string | null) : Promise<Result<SkillUpdateInfo[], AppError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("check_updates", { scope, projectPath }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * 一次检测 global 和 project 两个 scope 的更新
+ * 
+ * 结果中每项带有 scope；两个 scope 中来自同一来源、同一路径的 skill 只请求一次远程 API。
+ * 未提供 project_path 时只检测 global。
+ * 
+ * # Arguments
+ * * `project_path` - 项目路径
+ */
+async checkUpdatesAll(projectPath: string | null) : Promise<Result<SkillUpdateInfo[], AppError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("check_updates_all", { projectPath }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * 更新指定 skill
+ * 
+ * 本质是"重新安装"：从 lock 文件读取来源信息，构造安装 URL，复用安装逻辑。
+ * 与 CLI update 命令行为一致。
+ * 
+ * # Arguments
+ * * `op_id` - 前端指定的操作 id（用于 operation-event、clone-progress 和 cancel_clone），未传时自动生成
+ */
+async updateSkill(scope: Scope, name: string, projectPath: string | null, opId: string | null) : Promise<Result<null, AppError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("update_skill", { scope, name, projectPath, opId }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * 批量更新：检测指定 scope 中所有有更新的 skills 并逐一更新
+ * 
+ * 按仓库分组，每个仓库只克隆一次（仓库级 URL，默认分支），再从同一份 checkout
+ * 中按 skillPath 找到各个 skill。过程中发送 `update-all-progress` 和 `operation-event` 事件。
+ * 单个 skill 或单个仓库失败不影响其他 skill，取消克隆时整体返回 `AppError::Cancelled`。
+ * 
+ * # Arguments
+ * * `scope` - 更新范围
+ * * `project_path` - Project scope 时的项目路径
+ * * `op_id` - 前端指定的操作 id（用于 operation-event、clone-progress 和 cancel_clone），未传时自动生成
+ * 
+ * # Returns
+ * * 每个待更新 skill 的结果
+ */
+async updateAll(scope: Scope, projectPath: string | null, opId: string | null) : Promise<Result<SkillUpdateResult[], AppError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("update_all", { scope, projectPath, opId }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * 按 lock 中记录的来源重新安装 skill（"从来源修复"）
+ * 
+ * 与 update_skill 一样重新获取来源，但不论是否有更新，都整目录替换 canonical 目录和
+ * 各 agent 的安装位置（而非增量同步），用于修复本地被损坏或误改的文件。
+ * 完成后刷新 lock 中的 updatedAt 和 hash。
+ * 
+ * # Arguments
+ * * `scope` - skill 所在范围
+ * * `name` - skill 名称（lock 中的 key）
+ * * `project_path` - Project scope 时的项目路径
+ * * `op_id` - 前端指定的操作 id（用于 operation-event、clone-progress 和 cancel_clone），未传时自动生成
+ */
+async reinstallSkill(scope: Scope, name: string, projectPath: string | null, opId: string | null) : Promise<Result<null, AppError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("reinstall_skill", { scope, name, projectPath, opId }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * 对比已安装 skill 与来源中的最新内容
+ * 
+ * 按 lock 中记录的来源重新克隆/下载，定位 skill 后与 canonical 目录逐文件比较。
+ * 内容相同的文件不出现在结果中；二进制或超过 `MAX_TEXT_DIFF_BYTES` 的文件
+ * 只报告 `binary: true`。
+ * 
+ * # Arguments
+ * * `scope` - 安装范围
+ * * `name` - skill 名称
+ * * `project_path` - Project scope 时的项目路径
+ */
+async diffSkill(scope: Scope, name: string, projectPath: string | null) : Promise<Result<SkillFileDiff[], AppError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("diff_skill", { scope, name, projectPath }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * 列出已安装 skill 自安装以来的提交
+ * 
+ * 按 lock 中记录的来源克隆较深的历史（最多 500 个提交），用 lock 中的目录 tree hash
+ * 定位已安装的版本；只支持 git 来源（GitHub / GitLab / 其他 git 仓库）。
+ * 
+ * # Arguments
+ * * `scope` - 安装范围
+ * * `name` - skill 名称
+ * * `project_path` - Project scope 时的项目路径
+ * * `op_id` - 前端指定的操作 id（用于 operation-event、clone-progress 和 cancel_clone），未传时自动生成
+ */
+async skillChangelog(scope: Scope, name: string, projectPath: string | null, opId: string | null) : Promise<Result<SkillChangelog, AppError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("skill_changelog", { scope, name, projectPath, opId }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * 检测指定 scope 已安装 skills 是否在安装后被本地修改
+ * 
+ * 只有项目级 skills-lock.json 记录 computedHash，因此 Global scope
+ * 或未提供 project_path 时返回空列表。
+ * 
+ * - computedHash 为空（旧版本 lock）的条目报告 `drifted: false`
+ * - canonical 目录不存在时报告 `missing: true`（不算作修改），`actual_hash` 为空
+ */
+async verifySkills(scope: Scope, projectPath: string | null) : Promise<Result<SkillVerifyInfo[], AppError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("verify_skills", { scope, projectPath }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * 打开安装向导独立窗口
+ * 
+ * 必须为 async —— 同步 command 在主线程执行，
+ * 而 WebviewWindowBuilder::build() 也需要主线程，会导致死锁。
+ * async command 在异步线程执行，build() 可以安全回调主线程。
+ */
+async openInstallWizard(entryPoint: string, scope: string, projectPath: string | null, prefillSource: string | null, prefillSkillName: string | null) : Promise<Result<null, AppError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("open_install_wizard", { entryPoint, scope, projectPath, prefillSource, prefillSkillName }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * 检查 skill 的安全审计数据
+ * 
+ * 审计已关闭（disableAudit / SKILL_DECK_DISABLE_AUDIT）时直接返回 None
+ */
+async checkSkillAudit(source: string, skills: string[]) : Promise<Result<Partial<{ [key in string]: SkillAuditData }> | null, AppError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("check_skill_audit", { source, skills }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+}
+}
+
+/** user-defined events **/
+
+
+
+/** user-defined constants **/
+
+
+
+/** user-defined types **/
+
+/**
+ * 单个 agent 的 skills 目录覆盖，未设置的字段沿用内置目录
+ */
+export type AgentDirOverride = { 
+/**
+ * 项目级 skills 目录（相对项目根目录，如 ".cursor/my-skills"）
+ */
+skillsDir?: string | null; 
+/**
+ * 全局 skills 目录（必须为绝对路径）
+ */
+globalSkillsDir?: string | null }
+/**
+ * Agent 信息（返回给前端）
+ * 对应 CLI: 综合 AgentConfig + detectInstalled 结果
+ */
+export type AgentInfo = { id: AgentType; name: string; skillsDir: string; globalSkillsDir: string; detected: boolean; 
+/**
+ * 是否是 Universal Agent（安装逻辑用）
+ * 对应 CLI: isUniversalAgent()
+ */
+isUniversal: boolean; 
+/**
+ * 是否在 Universal 列表显示（UI 显示用）
+ * 对应 CLI: getUniversalAgents() 的过滤条件
+ */
+showInUniversalList: boolean }
+/**
+ * Agent 实际路径信息（诊断用）
+ * 
+ * 与 AgentInfo 不同，路径已解析为绝对路径，并附带磁盘上是否存在
+ */
+export type AgentPathInfo = { id: AgentType; name: string; 
+/**
+ * 项目级 skills 目录（基于 cwd 解析的绝对路径）
+ */
+skillsDir: string; skillsDirExists: boolean; 
+/**
+ * 全局 skills 目录（不支持全局安装时为 None）
+ */
+globalSkillsDir: string | null; globalSkillsDirExists: boolean }
+/**
+ * 重新检测 agents 的结果
+ */
+export type AgentRefresh = { 
+/**
+ * 所有 agents（与 list_agents 相同）
+ */
+agents: AgentInfo[]; 
+/**
+ * 本次检测到、但不在 previous_detected 中的 agents
+ */
+newlyDetected: AgentType[]; 
+/**
+ * 在 previous_detected 中、但本次未检测到的 agents
+ */
+newlyMissing: AgentType[] }
+/**
+ * Agent 类型枚举
+ * 完整对应 CLI: types.ts AgentType
+ */
+export type AgentType = "amp" | "antigravity" | "augment" | "claude-code" | "openclaw" | "cline" | "codebuddy" | "codex" | "command-code" | "continue" | "crush" | "cursor" | "droid" | "gemini-cli" | "github-copilot" | "goose" | "iflow-cli" | "junie" | "kilo" | "kimi-cli" | "kiro-cli" | "kode" | "mcpjam" | "mistral-vibe" | "mux" | "neovate" | "opencode" | "openhands" | "pi" | "qoder" | "qwen-code" | "replit" | "roo" | "trae" | "trae-cn" | "windsurf" | "zencoder" | "pochi" | "adal" | "cortex" | "universal"
+export type AppError = { kind: "io"; data: { message: string } } | { kind: "yaml"; data: { message: string } } | { kind: "json"; data: { message: string } } | { kind: "invalidSkillMd"; data: { message: string } } | { kind: "skillMdMissingField"; data: { field: string } } | { kind: "path"; data: { message: string } } | { kind: "invalidSource"; data: { value: string } } | { kind: "gitCloneFailed"; data: { message: string } } | { kind: "gitAuthFailed"; data: { message: string } } | { kind: "gitRepoNotFound"; data: { repo: string } } | { kind: "gitRefNotFound"; data: { refName: string } } | { kind: "gitCommitUnavailable"; data: { sha: string; message: string } } | { kind: "gitTimeout" } | { kind: "gitNetworkError"; data: { message: string } } | { kind: "gitNotFound"; data: { message: string } } | { kind: "gitSshHostKeyFailed"; data: { host: string; message: string } } | { kind: "pathNotFound"; data: { path: string } } | { kind: "installFailed"; data: { message: string } } | { kind: "noSkillsFound" } | { kind: "invalidAgent"; data: { agent: string } } | { kind: "gitHubRateLimited"; data: { resetAt: string; message: string } } | { kind: "cancelled" } | { kind: "checksumMismatch"; data: { expected: string; actual: string } } | { kind: "offlineMode"; data: { operation: string } } | { kind: "custom"; data: { message: string } }
+/**
+ * 可用的 Skill 信息（fetch_available 返回）
+ */
+export type AvailableSkill = { 
+/**
+ * Skill 名称
+ */
+name: string; 
+/**
+ * 描述
+ */
+description: string; 
+/**
+ * 仓库内相对路径
+ */
+relativePath: string; 
+/**
+ * 所属 plugin 名称（来自 .claude-plugin/ manifest）
+ */
+pluginName?: string | null; 
+/**
+ * 版本号（来自 SKILL.md frontmatter）
+ */
+version?: string | null; 
+/**
+ * 作者
+ */
+author?: string | null; 
+/**
+ * 许可证
+ */
+license?: string | null; 
+/**
+ * 标签
+ */
+tags: string[]; 
+/**
+ * 分类（来自同级 metadata.json）
+ */
+category?: string | null; 
+/**
+ * 图标地址（来自同级 metadata.json）
+ */
+iconUrl?: string | null; 
+/**
+ * 可选的本地化版本（`SKILL.<locale>.md` 的 locale），安装时通过 InstallParams.locale 选择
+ */
+locales: string[]; 
+/**
+ * 安全审计数据（仅 with_audit 时填充，审计失败为 None）
+ */
+audit?: SkillAuditData | null; 
+/**
+ * 安装后的内容 hash（fetch 时按克隆内容计算，传给 check_overwrites 比对，无需再次克隆）
+ */
+contentHash?: string | null }
+/**
+ * 取消克隆的结果
+ */
+export type CancelCloneResult = { 
+/**
+ * 已发出取消信号的克隆数（0 表示没有匹配的进行中克隆）
+ */
+cancelled: number; 
+/**
+ * 取消后仍在进行中的克隆所属的操作 id（可据此改为取消其他操作）
+ */
+activeOpIds: string[] }
+/**
+ * 单项检查结果
+ */
+export type CheckStatus = "ok" | "warn" | "fail"
+/**
+ * 两侧都存在的 skill
+ */
+export type CommonSkill = { name: string; 
+/**
+ * source_a 中的描述
+ */
+descriptionA: string; 
+/**
+ * source_b 中的描述
+ */
+descriptionB: string }
+/**
+ * 只存在于一侧的 skill
+ */
+export type ComparedSkill = { name: string; description: string }
+/**
+ * 依赖边
+ */
+export type DependencyEdge = { from: string; to: string }
+/**
+ * skill 依赖图（resolve_dependencies 返回）
+ */
+export type DependencyGraph = { 
+/**
+ * 根 skill 的节点 id
+ */
+root: string; 
+/**
+ * 节点（根节点在前，其余按解析顺序）
+ */
+nodes: DependencyNode[]; 
+/**
+ * 依赖边（from 依赖 to）
+ */
+edges: DependencyEdge[]; 
+/**
+ * 依赖环，每个环为按依赖顺序排列的节点 id
+ */
+cycles: string[][]; 
+/**
+ * 无法解析的依赖
+ */
+unresolved: UnresolvedDependency[]; 
+/**
+ * 达到深度或来源数量上限，依赖图不完整
+ */
+truncated: boolean }
+/**
+ * 依赖图节点
+ */
+export type DependencyNode = { 
+/**
+ * 节点 id（`<source>@<name>`）
+ */
+id: string; name: string; 
+/**
+ * 来源（URL 或本地路径，含子路径和 `#ref`）
+ */
+source: string }
+/**
+ * 将 agent 目录从 symlink 转为独立副本的结果
+ */
+export type DetachResult = { 
+/**
+ * 本次从 symlink 转为独立副本的 agents
+ */
+detached: string[]; 
+/**
+ * 已经是独立副本（copy 模式）的 agents
+ */
+alreadyStandalone: string[]; 
+/**
+ * 无法转换的 agents：(agent, 原因)
+ */
+skipped: ([string, string])[] }
+/**
+ * skill-deck 的磁盘占用
+ */
+export type DiskUsage = { 
+/**
+ * 各项占用（不存在的目录不列出）
+ */
+entries: DiskUsageEntry[]; totalBytes: number; 
+/**
+ * 无法读取而跳过的路径
+ */
+unreadable: string[] }
+/**
+ * 单项占用
+ */
+export type DiskUsageEntry = { kind: DiskUsageKind; path: string; 
+/**
+ * ProjectSkills 时的项目路径
+ */
+projectPath?: string | null; sizeBytes: number; fileCount: number }
+/**
+ * 占用项类型
+ */
+export type DiskUsageKind = 
+/**
+ * ~/.agents/skills
+ */
+"globalSkills" | 
+/**
+ * ~/.skill-deck/cache/clones
+ */
+"cloneCache" | 
+/**
+ * <project>/.agents/skills
+ */
+"projectSkills"
+/**
+ * 单项检查
+ */
+export type DoctorCheck = { 
+/**
+ * 检查项标识（如 "git"、"githubToken"）
+ */
+id: string; status: CheckStatus; message: string }
+/**
+ * 解析后的路径
+ */
+export type DoctorPaths = { home: string; configHome: string; codexHome: string; claudeHome: string; 
+/**
+ * ~/.skill-deck/config.json
+ */
+skillDeckConfig: string; 
+/**
+ * global canonical skills 目录
+ */
+canonicalSkillsDir: string }
+/**
+ * 健康检查报告
+ */
+export type DoctorReport = { 
+/**
+ * 应用版本
+ */
+appVersion: string; 
+/**
+ * 操作系统（如 "macos"、"windows"）
+ */
+os: string; paths: DoctorPaths; 
+/**
+ * 检测到的 agents 数量（仅全局信号）
+ */
+detectedAgents: number; checks: DoctorCheck[] }
+/**
+ * fetch_available 返回结果
+ */
+export type FetchResult = { 
+/**
+ * 来源类型
+ */
+sourceType: string; 
+/**
+ * 规范化 URL
+ */
+sourceUrl: string; 
+/**
+ * @skill 语法提取的名称（用于预选）
+ */
+skillFilter: string | null; 
+/**
+ * 可用的 skills 列表
+ */
+skills: AvailableSkill[]; 
+/**
+ * 发现过程中被跳过的 SKILL.md
+ */
+warnings: SkillWarning[] }
+/**
+ * 文件改动类型
+ */
+export type FileChangeKind = "added" | "removed" | "modified"
+/**
+ * GitHub API 限流状态
+ */
+export type GitHubRateLimit = { 
+/**
+ * 是否找到了 token（环境变量、凭据文件或 gh CLI）
+ */
+tokenFound: boolean; 
+/**
+ * 额度是否按 token 计算；false 时为按 IP 计算的未认证额度（core 每小时 60 次）
+ */
+authenticated: boolean; 
+/**
+ * 找到的 token 被 GitHub 拒绝（过期或已撤销），额度已回退为未认证查询
+ */
+tokenRejected: boolean; 
+/**
+ * REST API（Trees / Contents / refs 等）
+ */
+core: RateLimitBucket; 
+/**
+ * Search API
+ */
+search: RateLimitBucket }
+/**
+ * detect_git 结果
+ */
+export type GitInfo = { 
+/**
+ * 是否找到可用的 git
+ */
+found: boolean; 
+/**
+ * git 可执行文件路径（配置的路径不可用时也会返回，便于提示）
+ */
+path?: string | null; 
+/**
+ * `git --version` 输出中的版本号（如 "2.45.1"）
+ */
+version?: string | null; source?: GitSource | null; 
+/**
+ * 未找到或无法运行时的原因
+ */
+error?: string | null }
+/**
+ * 远程仓库的分支/tag（用于安装时选择 git_ref）
+ */
+export type GitRefInfo = { 
+/**
+ * 分支名或 tag 名
+ */
+name: string; 
+/**
+ * 引用类型
+ */
+refType: GitRefType }
+/**
+ * Git 引用类型
+ */
+export type GitRefType = "branch" | "tag"
+/**
+ * git 的来源
+ */
+export type GitSource = 
+/**
+ * config.json 中的 gitPath
+ */
+"config" | 
+/**
+ * PATH 环境变量
+ */
+"path" | 
+/**
+ * 常见安装位置
+ */
+"commonLocation"
+/**
+ * 非 Universal Agent 的安装详情
+ */
+export type IndependentAgentInfo = { 
+/**
+ * Agent 类型
+ */
+agent: AgentType; 
+/**
+ * Agent 显示名称
+ */
+displayName: string; 
+/**
+ * 安装路径
+ */
+path: string; 
+/**
+ * 是否是 symlink（false 表示 copy 模式安装）
+ */
+isSymlink: boolean }
+/**
+ * 安装模式
+ */
+export type InstallMode = "symlink" | "copy" | 
+/**
+ * 复制到 canonical 目录，agent 目录中的文件硬链接到 canonical（同一文件系统时节省空间）。
+ * 修改硬链接文件会影响所有链接；无法硬链接的文件回退为复制
+ */
+"hardlink"
+/**
+ * 安装参数
+ */
+export type InstallParams = { 
+/**
+ * 原始来源字符串
+ */
+source: string; 
+/**
+ * 选中的 skill 名称列表
+ */
+skills: string[]; 
+/**
+ * 目标 agents
+ */
+agents: string[]; 
+/**
+ * 安装范围
+ */
+scope: Scope; 
+/**
+ * Project scope 时的项目路径
+ */
+projectPath: string | null; 
+/**
+ * 安装模式
+ */
+mode: InstallMode; 
+/**
+ * 为 true 时不自动追加 Universal Agents，只安装到显式选择的 agents
+ * 
+ * 注意：Symlink 模式仍会写入 canonical 目录（`.agents/skills`）作为 symlink 目标，
+ * 而 Universal Agents 读取的正是该目录，因此只有 Copy 模式能完全避开它们。
+ */
+skipUniversal?: boolean; 
+/**
+ * 发现 skills 时的最大递归深度，需与 fetch_available 时一致（默认 5）
+ */
+maxDepth?: number | null; 
+/**
+ * 覆盖来源中的分支/tag（来自 list_refs 的选择）
+ */
+gitRef?: string | null; 
+/**
+ * 安装目录被其他 skill 占用时的处理方式（默认覆盖）
+ */
+nameCollision?: NameCollisionPolicy | null; 
+/**
+ * 未检测到也强制安装的 agents（预先为尚未启动过的 agent 安装，目录不存在时自动创建）
+ */
+forceAgents?: AgentType[]; 
+/**
+ * 安装的本地化版本：`SKILL.<locale>.md` 作为目标目录中的 SKILL.md
+ * （未指定或 skill 没有该版本时使用 SKILL.md）
+ */
+locale?: string | null }
+/**
+ * plan_install 返回的安装计划（安装前预览，不克隆）
+ */
+export type InstallPlan = { 
+/**
+ * 来源类型
+ */
+sourceType: string; 
+/**
+ * 规范化 URL（本地来源为路径）
+ */
+sourceUrl: string; 
+/**
+ * 将使用的分支/tag
+ */
+gitRef: string | null; 
+/**
+ * 仓库内子路径
+ */
+subpath: string | null; 
+/**
+ * 逗号分隔的多个子路径（只有一个子路径时为空）
+ */
+subpaths: string[]; 
+/**
+ * 安装时是否需要克隆仓库
+ */
+requiresClone: boolean; 
+/**
+ * 安装范围
+ */
+scope: Scope; 
+/**
+ * 安装模式
+ */
+mode: InstallMode; 
+/**
+ * 最终安装的 agents（含自动追加的 Universal Agents）
+ */
+agents: string[]; 
+/**
+ * 自动追加的 Universal Agents
+ */
+universalAgentsAdded: string[]; 
+/**
+ * 将安装的 skills（仅本地来源可知；远程来源需克隆后才能确定，为 None）
+ */
+skills: string[] | null }
+/**
+ * 单个 skill 的安装结果
+ */
+export type InstallResult = { 
+/**
+ * Skill 名称
+ */
+skillName: string; 
+/**
+ * Agent 名称
+ */
+agent: string; 
+/**
+ * 是否成功
+ */
+success: boolean; 
+/**
+ * 安装路径
+ */
+path: string; 
+/**
+ * Canonical 路径（symlink 模式）
+ */
+canonicalPath: string | null; 
+/**
+ * 实际使用的安装模式
+ */
+mode: InstallMode; 
+/**
+ * symlink 是否失败并降级为 copy
+ */
+symlinkFailed: boolean; 
+/**
+ * symlink 失败原因（捕获的链接创建错误）
+ */
+symlinkError?: string | null; 
+/**
+ * 错误信息
+ */
+error: string | null }
+/**
+ * 安装结果汇总
+ */
+export type InstallResults = { 
+/**
+ * 成功的安装
+ */
+successful: InstallResult[]; 
+/**
+ * 失败的安装
+ */
+failed: InstallResult[]; 
+/**
+ * symlink 失败降级为 copy 的 agents
+ */
+symlinkFallbackAgents: string[]; 
+/**
+ * 各降级 agent 的失败原因：(agent, reason)，区分权限问题与文件系统限制
+ */
+symlinkFallbackReasons: ([string, string])[] }
+/**
+ * 已安装的 Skill 信息
+ * 对应 CLI: InstalledSkill (installer.ts:783-790)
+ */
+export type InstalledSkill = { name: string; description: string; path: string; canonicalPath: string; scope: SkillScope; agents: AgentType[]; source?: string | null; sourceUrl?: string | null; installedAt?: string | null; updatedAt?: string | null; hasUpdate?: boolean | null; 
+/**
+ * 所属 plugin 名称
+ */
+pluginName?: string | null; version?: string | null; author?: string | null; license?: string | null; tags: string[]; 
+/**
+ * 占用磁盘字节数（仅 include_size 时计算）
+ */
+sizeBytes?: number | null; 
+/**
+ * 文件数量（仅 include_size 时计算）
+ */
+fileCount?: number | null; 
+/**
+ * 各 agent 的实际安装方式
+ * symlink/junction 或直接读取 canonical 目录为 Symlink，独立副本为 Copy（修改不会同步）
+ */
+agentInstallModes: Partial<{ [key in AgentType]: InstallMode }>; 
+/**
+ * 内容与 canonical 目录不一致的 Copy 模式 agents（仅 verify_copies 时计算）
+ */
+outOfSyncAgents?: AgentType[] | null; 
+/**
+ * 安装的本地化版本（来自 lock，未选择时为 None）
+ */
+locale?: string | null; 
+/**
+ * 可切换的本地化版本：已安装目录中的 `SKILL.<locale>.md` 加上当前安装的 locale（按字母排序）
+ */
+availableLocales: string[] }
+/**
+ * 目标与期望 canonical 目录不一致的 agent 链接
+ */
+export type LinkMismatch = { 
+/**
+ * skill 名称（lock key）
+ */
+skillName: string; agent: AgentType; 
+/**
+ * agent 目录中的 symlink / junction
+ */
+linkPath: string; 
+/**
+ * 链接实际指向的路径（无法读取时为空）
+ */
+actualTarget?: string | null; 
+/**
+ * 期望的 canonical 目录
+ */
+expectedTarget: string; 
+/**
+ * 实际目标是否存在（false 表示悬空链接）
+ */
+targetExists: boolean }
+/**
+ * 单条诊断
+ */
+export type LintDiagnostic = { severity: LintSeverity; 
+/**
+ * 诊断标识（如 "invalidFrontmatter"、"symlinkEscapesRoot"）
+ */
+code: string; message: string; 
+/**
+ * 相关文件（相对 skill 根目录）
+ */
+path?: string | null }
+/**
+ * 诊断级别
+ */
+export type LintSeverity = "error" | "warning" | "info"
+/**
+ * list_skills 参数
+ */
+export type ListSkillsParams = { 
+/**
+ * 范围: "global" | "project" | null (返回全部)
+ */
+scope: string | null; 
+/**
+ * 项目路径（用于 project scope）
+ */
+projectPath: string | null; 
+/**
+ * 是否统计每个 skill 的磁盘占用（默认 false，保持列表快速）
+ */
+includeSize?: boolean; 
+/**
+ * 是否检查 Copy 模式的 agent 副本与 canonical 目录内容是否一致（默认 false，需要计算哈希）
+ */
+verifyCopies?: boolean; 
+/**
+ * 要扫描的 agents；未提供时只扫描检测到的 agents
+ * （预先安装到尚未检测到的 agent 时传入，以便列出这些 skills）
+ */
+agents?: AgentType[] | null; 
+/**
+ * 只返回带有这些 tags 的 skills（不区分大小写，为空时不过滤）
+ */
+tags?: string[]; 
+/**
+ * 为 true 时需包含全部 tags，否则包含任一即可
+ */
+matchAllTags?: boolean }
+/**
+ * list_skills 返回结果
+ * 包含 skills 列表和路径存在性信息
+ */
+export type ListSkillsResult = { skills: InstalledSkill[]; 
+/**
+ * 项目目录是否存在（project scope 时有意义，global 始终为 true）
+ */
+pathExists: boolean; 
+/**
+ * 过滤前所有已安装 skills 的 tags 并集（按字母排序，供前端构建过滤菜单）
+ */
+allTags: string[] }
+/**
+ * lock 完整性检查结果
+ */
+export type LockIntegrityReport = { 
+/**
+ * 检查的 lock 文件路径
+ */
+lockPath: string; 
+/**
+ * lock 能否解析（文件不存在也视为正常）
+ */
+valid: boolean; 
+/**
+ * 解析错误信息
+ */
+parseError?: string | null; 
+/**
+ * 损坏文件的备份路径（`<lock>.bak`）
+ */
+backupPath?: string | null; 
+/**
+ * 重建 lock 时从 canonical 目录恢复的 skill（来源标记为 local）
+ */
+recovered: string[] }
+/**
+ * 迁移结果
+ */
+export type LockMigrationResult = { 
+/**
+ * 写入 skills-lock.json 的条目数（已存在的条目不计入）
+ */
+migrated: number; 
+/**
+ * 是否已删除旧版 lock 文件
+ */
+legacyDeleted: boolean }
+/**
+ * 在 global / project scope 之间移动 skill 的结果
+ */
+export type MoveScopeResult = { 
+/**
+ * 目标 scope 的 canonical 目录
+ */
+canonicalPath: string; 
+/**
+ * 在目标 scope 重新创建的 agent 目录路径列表
+ */
+relinkedPaths: string[]; 
+/**
+ * 目标 scope 下没有 skills 目录或迁移失败的 agents（原 scope 的安装仍会被删除）
+ */
+skippedAgents: string[]; 
+/**
+ * 是否迁移了 lock 条目（原 scope 没有记录时为 false）
+ */
+lockMoved: boolean }
+/**
+ * 安装目录名冲突时的处理方式
+ * 
+ * 不同的 skill name 可能 sanitize 成同一目录名（如 `My Skill!` 和 `my-skill`）
+ */
+export type NameCollisionPolicy = 
+/**
+ * 覆盖已有目录（与 CLI 一致）
+ */
+"overwrite" | 
+/**
+ * 目录被其他 skill 占用时追加数字后缀（`my-skill-2`），实际目录名记录在 lock 中
+ */
+"suffix"
+/**
+ * 操作生命周期事件（发送到前端）
+ */
+export type OperationEvent = { type: "started"; opId: string; kind: OperationKind } | { type: "progress"; opId: string; phase: string; completed: number; total: number } | { type: "completed"; opId: string; summary: string } | { type: "failed"; opId: string; error: AppError }
+/**
+ * 操作类型
+ */
+export type OperationKind = "fetch" | "install" | "update" | "updateAll" | "reinstall" | "resolveDependencies" | "changelog" | "moveScope" | "exportState" | "importState" | "search" | "lint"
+/**
+ * 孤立的 canonical skill（没有 lock 条目或没有任何 agent 使用）
+ */
+export type OrphanedSkill = { 
+/**
+ * SKILL.md 中的 name（无法解析时为目录名）
+ */
+name: string; 
+/**
+ * canonical 目录
+ */
+canonicalPath: string; 
+/**
+ * SKILL.md 缺失或解析失败的原因
+ */
+skillMdError?: string | null; 
+/**
+ * lock 中是否有对应条目
+ */
+inLock: boolean; 
+/**
+ * 使用该目录的 agents（已检测到的 Universal agents + symlink 指向它的 agents）
+ */
+referencedBy: AgentType[] }
+/**
+ * 覆盖类型（与即将安装的内容相比）
+ * Identical: 内容完全一致；Modified: 内容不同或无法比较
+ */
+export type OverwriteKind = "identical" | "modified"
+/**
+ * 解析后的来源信息
+ */
+export type ParsedSource = { 
+/**
+ * 来源类型
+ */
+sourceType: SourceType; 
+/**
+ * 规范化后的 URL
+ */
+url: string; 
+/**
+ * 仓库内子路径（逗号分隔的多个子路径时为第一个）
+ */
+subpath: string | null; 
+/**
+ * 逗号分隔的多个子路径（如 `owner/repo/skills/a,skills/b`），只有一个子路径时为空
+ */
+subpaths: string[]; 
+/**
+ * 本地路径（仅 Local 类型）
+ */
+localPath: string | null; 
+/**
+ * Git 分支/tag
+ */
+gitRef: string | null; 
+/**
+ * @skill 语法提取的 skill 名称
+ */
+skillFilter: string | null; 
+/**
+ * 期望的内容 SHA-256（小写 hex，来自 `#sha256=` fragment，仅 DirectUrl）
+ */
+sha256?: string | null }
+/**
+ * 单个限流桶的额度
+ */
+export type RateLimitBucket = { limit: number; remaining: number; used: number; 
+/**
+ * 额度重置时间（ISO 8601，UTC）
+ */
+resetAt: string }
+/**
+ * 单个 skill 的删除结果
+ * 对应 CLI: remove.ts 第 148-195 行的 results 数组元素
+ */
+export type RemoveResult = { 
+/**
+ * Skill 名称
+ */
+skillName: string; 
+/**
+ * 是否成功
+ */
+success: boolean; 
+/**
+ * 删除的 agent 目录路径列表（dry run 时为将删除的路径）
+ */
+removedPaths: string[]; 
+/**
+ * removed_paths 对应的类型详情（顺序一致）
+ */
+pathDetails?: RemovedPathInfo[]; 
+/**
+ * 删除（或将删除）的 canonical 目录；仍被其他 agent 使用时为 None
+ */
+canonicalPath?: string | null; 
+/**
+ * 是否为 dry run（未实际删除任何内容）
+ */
+dryRun?: boolean; 
+/**
+ * 来源信息（从 lock file 读取，仅 Global）
+ */
+source: string | null; 
+/**
+ * 来源类型
+ */
+sourceType: string | null; 
+/**
+ * 错误信息
+ */
+error: string | null }
+/**
+ * 删除路径详情
+ */
+export type RemovedPathInfo = { path: string; kind: RemovedPathKind }
+/**
+ * 删除（或 dry run 时将删除）的路径类型
+ */
+export type RemovedPathKind = 
+/**
+ * symlink / junction（只删除链接本身）
+ */
+"symlink" | 
+/**
+ * 实体目录（copy 模式安装或 canonical 目录）
+ */
+"directory"
+/**
+ * 重命名 skill 的结果
+ */
+export type RenameResult = { 
+/**
+ * 原名称
+ */
+oldName: string; 
+/**
+ * 新名称（name_rewritten 为 false 时只用作目录名，skill 名称和 lock key 仍为 old_name）
+ */
+newName: string; 
+/**
+ * 新的 canonical 目录
+ */
+canonicalPath: string; 
+/**
+ * 已迁移到新名称的 agent 目录路径列表
+ */
+relinkedPaths: string[]; 
+/**
+ * 是否改写了 SKILL.md 中的 name
+ */
+nameRewritten: boolean }
+/**
+ * 风险等级
+ */
+export type RiskLevel = "safe" | "low" | "medium" | "high" | "critical" | "unknown"
+/**
+ * 安装范围
+ */
+export type Scope = "global" | "project"
+/**
+ * 同时安装在 global 和项目中的 skill
+ */
+export type ShadowedSkill = { 
+/**
+ * SKILL.md 中的 name
+ */
+name: string; 
+/**
+ * 两个 scope 都能看到该 skill 的 agents
+ */
+agents: SkillResolution[] }
+/**
+ * Skill 的 Agent 安装详情（用于智能删除对话框）
+ */
+export type SkillAgentDetails = { 
+/**
+ * Skill 名称
+ */
+skillName: string; 
+/**
+ * 安装范围
+ */
+scope: Scope; 
+/**
+ * Canonical 目录路径
+ */
+canonicalPath: string; 
+/**
+ * 共享 canonical 的 Universal Agents（带显示名称）
  */
-name: string; 
+universalAgents: ([AgentType, string])[]; 
 /**
- * 描述
+ * 有独立 symlink 的 Non-Universal Agents
  */
-description: string; 
+independentAgents: IndependentAgentInfo[] }
 /**
- * 仓库内相对路径
+ * Skill 审计数据
  */
-relativePath: string; 
+export type SkillAuditData = { risk: RiskLevel; alerts?: number | null; score?: number | null; analyzedAt: string }
 /**
- * 所属 plugin 名称（来自 .claude-plugin/ manifest）
+ * skill 自安装以来的变更历史
  */
-pluginName?: string | null }
+export type SkillChangelog = { 
 /**
- * fetch_available 返回结果
+ * skill 在仓库中的目录（根目录为空串）
  */
-export type FetchResult = { 
+folder: string; 
 /**
- * 来源类型
+ * 与已安装版本对应的提交（按 tree hash 找到时）
  */
-sourceType: string; 
+installedCommit?: string | null; 
 /**
- * 规范化 URL
+ * 安装之后的提交，最新的在前
  */
-sourceUrl: string; 
+commits: SkillCommit[]; 
 /**
- * @skill 语法提取的名称（用于预选）
+ * 提交数超过上限或克隆深度不足以找到已安装版本，列表不完整
  */
-skillFilter: string | null; 
+truncated: boolean }
 /**
- * 可用的 skills 列表
+ * 修改过 skill 目录的提交
  */
-skills: AvailableSkill[] }
+export type SkillCommit = { sha: string; author: string; 
 /**
- * 非 Universal Agent 的安装详情
+ * 提交时间（ISO 8601）
  */
-export type IndependentAgentInfo = { 
+date: string; 
 /**
- * Agent 类型
+ * 提交标题（第一行）
  */
-agent: AgentType; 
+subject: string }
 /**
- * Agent 显示名称
+ * Skill Deck 应用配置
+ * 持久化到 ~/.skill-deck/config.json
  */
-displayName: string; 
+export type SkillDeckConfig = { 
 /**
- * 安装路径
+ * 已保存的项目路径列表
+ */
+projects?: string[]; 
+/**
+ * 自定义 global canonical skills 目录（必须为绝对路径）
+ * 未设置时使用 ~/.agents/skills
+ */
+canonicalDirOverride?: string | null; 
+/**
+ * 每个项目默认选中的 agents（key 为项目路径）
+ * 未记录的项目回退到 skill-lock 中的全局 lastSelectedAgents
+ */
+projectDefaultAgents: Partial<{ [key in string]: string[] }>; 
+/**
+ * 额外识别为 GitLab 的主机（自托管实例，域名不含 "gitlab" 时使用）
+ * 格式为 `host` 或 `host:port`
+ */
+gitlabHosts: string[]; 
+/**
+ * 关闭安全审计查询（不再请求 audit API）
+ * 也可通过 SKILL_DECK_DISABLE_AUDIT 环境变量开启
+ */
+disableAudit: boolean; 
+/**
+ * 最大并发 git 克隆数（搜索、对比等批量获取共享），未设置时为 4
+ */
+maxConcurrentClones?: number | null; 
+/**
+ * 离线模式：不发起任何网络请求，只能使用本地来源
+ * 也可通过 SKILL_DECK_OFFLINE 环境变量开启
+ */
+offline: boolean; 
+/**
+ * 按 agent 覆盖 skills 目录（key 为 agent id，如 "cursor"）
+ */
+agentDirOverrides: Partial<{ [key in AgentType]: AgentDirOverride }>; 
+/**
+ * git 可执行文件的绝对路径（git 不在 PATH 中时使用，如从 Finder 启动的 macOS 应用）
+ * 未设置时依次查找 PATH 和常见安装位置
+ */
+gitPath?: string | null; 
+/**
+ * 克隆到 ~/.skill-deck/cache/clones 下按 URL 命名的目录，用完后不删除（便于排查克隆问题）
+ * 未开启时克隆到随机命名、用完即删的临时目录
+ */
+retainClones: boolean; 
+/**
+ * retainClones 开启时最多保留的克隆目录数，超出时删除最久未使用的，未设置时为 10
+ */
+maxRetainedClones?: number | null; 
+/**
+ * 安装时额外排除的文件名（与默认的 metadata.json 合并）
+ */
+copyExcludeFiles: string[]; 
+/**
+ * 安装时额外排除的目录名（与默认的 .git 合并）
+ */
+copyExcludeDirs: string[]; 
+/**
+ * 为 true 时 copyExcludeFiles / copyExcludeDirs 替换默认排除项而非合并
+ */
+replaceCopyExcludes: boolean; 
+/**
+ * API 请求的 User-Agent（GitHub、GitLab、审计及 HTTP 下载共用），未设置时为 "skill-deck"
+ */
+userAgent?: string | null; 
+/**
+ * GitHub JSON API 请求的 Accept，未设置时为 "application/vnd.github.v3+json"
+ */
+githubAccept?: string | null; 
+/**
+ * 最近安装过的来源（最近使用的在前，按原始来源字符串去重，最多 20 条）
+ */
+sources: SourceHistoryEntry[] }
+/**
+ * 单个文件的差异
+ */
+export type SkillFileDiff = { 
+/**
+ * 相对 skill 目录的路径（`/` 分隔）
+ */
+path: string; change: FileChangeKind; 
+/**
+ * 是否为二进制或超出大小上限的文件（此时不提供 diff 文本）
+ */
+binary: boolean; 
+/**
+ * unified diff 文本（installed → source）
+ */
+diff?: string | null }
+/**
+ * skill 目录中的一个文件或子目录
+ */
+export type SkillFileEntry = { 
+/**
+ * 相对 skill 根目录的路径（`/` 分隔）
  */
 path: string; 
 /**
- * 是否是 symlink（false 表示 copy 模式安装）
+ * 文件字节数（目录为 0）
+ */
+sizeBytes: number; isDir: boolean; 
+/**
+ * 是否为符号链接（不会展开链接指向的目录）
  */
 isSymlink: boolean }
 /**
- * 安装模式
+ * 单个已安装 skill 的完整信息
  */
-export type InstallMode = "symlink" | "copy"
+export type SkillInfo = { name: string; scope: Scope; canonicalPath: string; 
 /**
- * 安装参数
+ * canonical 目录是否存在
  */
-export type InstallParams = { 
+canonicalExists: boolean; description?: string | null; version?: string | null; author?: string | null; license?: string | null; tags: string[]; internal: boolean; skillMdError?: string | null; 
 /**
- * 原始来源字符串
+ * lock 条目，未记录（如手动放入的目录）时为空
+ */
+lock?: SkillLockInfo | null; 
+/**
+ * 占用磁盘字节数（canonical 目录不存在时为空）
+ */
+sizeBytes?: number | null; fileCount?: number | null; 
+/**
+ * 各 agent 的安装详情（同 get_skill_agent_details）
+ */
+agentDetails: SkillAgentDetails }
+/**
+ * 单个安装位置
+ */
+export type SkillLocation = { scope: Scope; 
+/**
+ * 项目路径（global 为 None）
+ */
+projectPath?: string | null; 
+/**
+ * canonical 目录
+ */
+path: string; 
+/**
+ * lock 中记录的来源（未记录时为 None）
+ */
+source?: string | null; sourceType?: string | null; 
+/**
+ * lock 中是否有记录（false 表示只在 canonical 目录中找到）
+ */
+inLock: boolean }
+/**
+ * lock 中记录的来源信息（Global 与 Project lock 的字段合集）
+ */
+export type SkillLockInfo = { source: string; sourceType: string; 
+/**
+ * 原始安装 URL（仅 Global lock 记录）
+ */
+sourceUrl?: string | null; skillPath?: string | null; 
+/**
+ * 安装 / 更新时间（旧版 Project lock 没有记录）
+ */
+installedAt?: string | null; updatedAt?: string | null; pluginName?: string | null; 
+/**
+ * 安装时 SKILL.md 中的 version
+ */
+version?: string | null; 
+/**
+ * 安装时记录的远程 tree SHA（Global: skillFolderHash，Project: remoteHash）
+ */
+remoteHash?: string | null }
+/**
+ * SKILL.md 预览
+ */
+export type SkillPreview = { name: string; description: string; version?: string | null; author?: string | null; license?: string | null; tags: string[]; 
+/**
+ * frontmatter 之后的 markdown 正文
+ */
+body: string; 
+/**
+ * 内容超过上限被截断
+ */
+truncated: boolean }
+/**
+ * 单个 agent 对重名 skill 的解析结果
+ */
+export type SkillResolution = { agent: AgentType; 
+/**
+ * 实际生效的副本所在 scope（按该 agent 的目录优先级）
+ */
+effectiveScope: Scope; 
+/**
+ * 项目级副本目录
+ */
+projectPath: string; 
+/**
+ * 全局副本目录
+ */
+globalPath: string; 
+/**
+ * 两份副本内容是否相同（相同时遮蔽不影响行为）
+ */
+identical: boolean }
+/**
+ * Skill 范围（排序时 Global 在前）
+ */
+export type SkillScope = "global" | "project"
+/**
+ * 搜索命中的 skill
+ */
+export type SkillSearchHit = { 
+/**
+ * 用户传入的来源字符串（可直接用于 install_skills）
  */
 source: string; 
 /**
- * 选中的 skill 名称列表
+ * 来源类型
  */
-skills: string[]; 
+sourceType: string; 
 /**
- * 目标 agents
+ * 规范化 URL
  */
-agents: string[]; 
+sourceUrl: string; name: string; description: string; 
 /**
- * 安装范围
+ * 仓库内相对路径
  */
-scope: Scope; 
+relativePath: string; pluginName?: string | null }
 /**
- * Project scope 时的项目路径
+ * 搜索结果
  */
-projectPath: string | null; 
+export type SkillSearchResults = { 
 /**
- * 安装模式
+ * 按相关度排序的命中列表
  */
-mode: InstallMode }
+hits: SkillSearchHit[]; 
 /**
- * 单个 skill 的安装结果
+ * 获取失败的来源
  */
-export type InstallResult = { 
+failures: SourceSearchFailure[] }
 /**
- * Skill 名称
+ * 更新检测结果
  */
-skillName: string; 
+export type SkillUpdateInfo = { name: string; source: string; 
 /**
- * Agent 名称
+ * skill 所在的安装范围
  */
-agent: string; 
+scope: Scope; hasUpdate: boolean; 
 /**
- * 是否成功
+ * lock 中记录的已安装版本
  */
-success: boolean; 
+currentVersion?: string | null; 
 /**
- * 安装路径
+ * 远程 SKILL.md 中的版本（仅 has_update 时获取）
  */
-path: string; 
+latestVersion?: string | null; 
 /**
- * Canonical 路径（symlink 模式）
+ * 来源平台尚不支持更新检测时的原因（此时 has_update 恒为 false）
  */
-canonicalPath: string | null; 
+unsupportedReason?: string | null }
 /**
- * 实际使用的安装模式
+ * 批量更新中单个 skill 的结果
  */
-mode: InstallMode; 
+export type SkillUpdateResult = { name: string; source: string; success: boolean; error?: string | null }
 /**
- * symlink 是否失败并降级为 copy
+ * 某个 skill 在 global 和已知项目中的安装位置
  */
-symlinkFailed: boolean; 
+export type SkillUsage = { 
 /**
- * 错误信息
+ * 安装位置（global 在前，项目按 config.json 中的顺序）
  */
-error: string | null }
+locations: SkillLocation[]; 
 /**
- * 安装结果汇总
+ * config.json 中记录但磁盘上已不存在的项目路径
  */
-export type InstallResults = { 
+staleProjects: string[]; 
 /**
- * 成功的安装
+ * lock 无法读取而跳过的项目
  */
-successful: InstallResult[]; 
+unreadableProjects: UnreadableProject[] }
 /**
- * 失败的安装
+ * 单个 skill 的校验结果
  */
-failed: InstallResult[]; 
+export type SkillVerifyInfo = { name: string; 
 /**
- * symlink 失败降级为 copy 的 agents
+ * 安装后内容是否被修改（目录不存在时为 false，见 `missing`）
  */
-symlinkFallbackAgents: string[] }
+drifted: boolean; 
 /**
- * 已安装的 Skill 信息
- * 对应 CLI: InstalledSkill (installer.ts:783-790)
+ * canonical 目录是否已不存在（被删除或移动）
  */
-export type InstalledSkill = { name: string; description: string; path: string; canonicalPath: string; scope: SkillScope; agents: AgentType[]; source?: string | null; sourceUrl?: string | null; installedAt?: string | null; updatedAt?: string | null; hasUpdate?: boolean | null; 
+missing: boolean; 
 /**
- * 所属 plugin 名称
+ * lock 文件中记录的 computedHash
  */
-pluginName?: string | null }
+expectedHash: string; 
 /**
- * list_skills 参数
+ * 当前 canonical 目录的 hash（目录不存在时为空）
  */
-export type ListSkillsParams = { 
+actualHash: string }
 /**
- * 范围: "global" | "project" | null (返回全部)
+ * 发现 skills 时被跳过的候选 SKILL.md
  */
-scope: string | null; 
+export type SkillWarning = { 
 /**
- * 项目路径（用于 project scope）
+ * SKILL.md 相对来源根目录的路径
  */
-projectPath: string | null }
+relativePath: string; reason: SkillWarningReason; 
 /**
- * list_skills 返回结果
- * 包含 skills 列表和路径存在性信息
+ * 具体原因（解析错误信息等）
  */
-export type ListSkillsResult = { skills: InstalledSkill[]; 
+message: string }
 /**
- * 项目目录是否存在（project scope 时有意义，global 始终为 true）
+ * SKILL.md 被跳过的原因
  */
-pathExists: boolean }
+export type SkillWarningReason = 
 /**
- * 单个 skill 的删除结果
- * 对应 CLI: remove.ts 第 148-195 行的 results 数组元素
+ * frontmatter 缺失或 YAML 无法解析
  */
-export type RemoveResult = { 
+"parseError" | 
 /**
- * Skill 名称
+ * 缺少 name / description
  */
-skillName: string; 
+"missingField" | 
 /**
- * 是否成功
+ * internal skill 未被请求
  */
-success: boolean; 
+"internal"
 /**
- * 删除的 agent 目录路径列表
+ * 来源对比结果（以 source_a 为基准）
  */
-removedPaths: string[]; 
+export type SourceComparison = { 
 /**
- * 来源信息（从 lock file 读取，仅 Global）
+ * 只在 source_b 中存在
  */
-source: string | null; 
+added: ComparedSkill[]; 
 /**
- * 来源类型
+ * 只在 source_a 中存在
  */
-sourceType: string | null; 
+removed: ComparedSkill[]; 
 /**
- * 错误信息
+ * 两侧都存在
  */
-error: string | null }
+common: CommonSkill[]; 
 /**
- * 风险等级
+ * source_a 获取失败时的错误信息
  */
-export type RiskLevel = "safe" | "low" | "medium" | "high" | "critical" | "unknown"
+errorA?: string | null; 
 /**
- * 安装范围
+ * source_b 获取失败时的错误信息
  */
-export type Scope = "global" | "project"
+errorB?: string | null }
 /**
- * Skill 的 Agent 安装详情（用于智能删除对话框）
+ * 来源使用记录（install_skills 成功安装后写入）
  */
-export type SkillAgentDetails = { 
+export type SourceHistoryEntry = { 
 /**
- * Skill 名称
+ * 用户输入的原始来源字符串
  */
-skillName: string; 
+source: string; 
 /**
- * 安装范围
+ * 最近一次安装时间（ISO 8601）
  */
-scope: Scope; 
+lastUsedAt: string; 
 /**
- * Canonical 目录路径
+ * 最近一次安装成功的 skill 数量
  */
-canonicalPath: string; 
+skillCount: number }
 /**
- * 共享 canonical 的 Universal Agents（带显示名称）
+ * 获取失败或超时的来源
  */
-universalAgents: ([AgentType, string])[]; 
+export type SourceSearchFailure = { source: string; message: string }
 /**
- * 有独立 symlink 的 Non-Universal Agents
+ * 来源类型枚举
  */
-independentAgents: IndependentAgentInfo[] }
+export type SourceType = "github" | "gitlab" | "git" | "local" | "directurl" | "wellknown"
 /**
- * Skill 审计数据
+ * 来源校验结果
  */
-export type SkillAuditData = { risk: RiskLevel; alerts?: number | null; score?: number | null; analyzedAt: string }
+export type SourceValidation = { 
 /**
- * Skill Deck 应用配置
- * 持久化到 ~/.skill-deck/config.json
+ * 解析后的来源
  */
-export type SkillDeckConfig = { 
+parsed: ParsedSource; 
 /**
- * 已保存的项目路径列表
+ * 是否可以访问
  */
-projects?: string[] }
+reachable: boolean; 
 /**
- * Skill 范围
+ * 无法访问的原因
  */
-export type SkillScope = "global" | "project"
+error?: AppError | null }
 /**
- * 更新检测结果
+ * 导出结果
+ */
+export type StateExportReport = { 
+/**
+ * 归档路径
+ */
+archivePath: string; 
+/**
+ * lock 的 schema 版本（没有 lock 时为 None）
+ */
+lockVersion?: number | null; 
+/**
+ * lock 中的 skill 数量
+ */
+lockedSkills: number; 
+/**
+ * 归档中包含的 canonical skill 目录数量
+ */
+archivedSkillDirs: number }
+/**
+ * 导入结果
+ */
+export type StateImportReport = { 
+/**
+ * 导出归档的应用版本
+ */
+sourceAppVersion: string; 
+/**
+ * 是否恢复了 config.json
+ */
+configRestored: boolean; 
+/**
+ * 写入 lock 的 skill
+ */
+restoredSkills: string[]; 
+/**
+ * 合并时本机版本更新而保留的 skill
+ */
+keptSkills: string[]; 
+/**
+ * 重新创建的链接：(skill 目录名, agent)
+ */
+relinked: ([string, string])[]; 
+/**
+ * 非致命问题（lock 版本迁移、跳过的条目、链接失败等）
+ */
+warnings: string[] }
+/**
+ * 扫描时无法读取的项目
+ */
+export type UnreadableProject = { projectPath: string; 
+/**
+ * 读取失败的原因
+ */
+message: string }
+/**
+ * 无法解析的依赖
+ */
+export type UnresolvedDependency = { 
+/**
+ * 声明该依赖的节点 id
+ */
+from: string; 
+/**
+ * frontmatter 中的依赖声明
  */
-export type SkillUpdateInfo = { name: string; source: string; hasUpdate: boolean }
+dependency: string; reason: string }
 
 /** tauri-specta globals **/
 
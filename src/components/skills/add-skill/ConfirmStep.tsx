@@ -37,11 +37,20 @@ export function ConfirmStep({ state, updateState, scope, projectPath }: ConfirmS
 
     updateStateRef.current({ confirmReady: false });
 
+    // 复用 fetch 时计算的内容 hash，无需再次克隆来源
+    const contentHashes: Record<string, string> = {};
+    for (const skill of state.availableSkills) {
+      if (skill.contentHash && state.selectedSkills.includes(skill.name)) {
+        contentHashes[skill.name] = skill.contentHash;
+      }
+    }
+
     const overwritePromise = checkOverwrites(
       state.selectedSkills,
       state.selectedAgents,
       scope,
-      scope === 'project' ? projectPath : undefined
+      scope === 'project' ? projectPath : undefined,
+      contentHashes
     );
 
     const auditPromise = state.source
@@ -51,7 +60,9 @@ export function ConfirmStep({ state, updateState, scope, projectPath }: ConfirmS
     Promise.all([overwritePromise, auditPromise]).then(([overwriteResult, auditResult]) => {
       const overwrites: Record<string, string[]> = {};
       for (const [key, value] of Object.entries(overwriteResult)) {
-        if (value) overwrites[key] = value;
+        // 内容完全一致的重装无需提示
+        const modified = value?.filter(([, kind]) => kind === 'modified').map(([agent]) => agent);
+        if (modified && modified.length > 0) overwrites[key] = modified;
       }
       updateStateRef.current({ overwrites, confirmReady: true });
 
@@ -62,7 +73,7 @@ export function ConfirmStep({ state, updateState, scope, projectPath }: ConfirmS
       console.error('Failed to check overwrites/audit:', error);
       updateStateRef.current({ overwrites: {}, confirmReady: true });
     });
-  }, [state.selectedSkills, state.selectedAgents, state.source, scope, projectPath]);
+  }, [state.selectedSkills, state.selectedAgents, state.availableSkills, state.source, scope, projectPath]);
 
   // 覆盖统计
   const overwriteCount = useMemo(
@@ -11,7 +11,7 @@ import type { WizardState } from './types';
 
 /** 克隆进度事件（与 SourceStep 共用后端事件） */
 interface CloneProgress {
-  phase: 'connecting' | 'cloning' | 'done' | 'error';
+  phase: 'queued' | 'connecting' | 'cloning' | 'done' | 'error';
   elapsed_secs: number;
   timeout_secs: number;
   message: string | null;
@@ -138,6 +138,7 @@ export function InstallingStep({ state, updateState, scope, projectPath }: Insta
             successful: [],
             failed: [],
             symlinkFallbackAgents: [],
+            symlinkFallbackReasons: [],
           },
           installError,
           step: 'error',
@@ -154,6 +155,9 @@ export function InstallingStep({ state, updateState, scope, projectPath }: Insta
       case 'preparing':
         return t('addSkill.installing.preparing');
       case 'cloning':
+        if (cloneProgress?.phase === 'queued') {
+          return t('addSkill.installing.queued');
+        }
         if (cloneProgress?.phase === 'connecting') {
           return t('addSkill.installing.connecting');
         }
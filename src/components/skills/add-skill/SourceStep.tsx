@@ -17,7 +17,7 @@ import type { WizardState } from './types';
 
 /** 克隆进度事件 */
 interface CloneProgress {
-  phase: 'connecting' | 'cloning' | 'done' | 'error';
+  phase: 'queued' | 'connecting' | 'cloning' | 'done' | 'error';
   elapsed_secs: number;
   timeout_secs: number;
   message: string | null;
@@ -162,6 +162,8 @@ export function SourceStep({ state, updateState, onNext, autoFetch }: SourceStep
   const getPhaseText = () => {
     if (!cloneProgress) return t('addSkill.source.cloning');
     switch (cloneProgress.phase) {
+      case 'queued':
+        return t('addSkill.source.queued');
       case 'connecting':
         return t('addSkill.source.connecting');
       case 'cloning':
@@ -6,6 +6,7 @@ const { mockCommands } = vi.hoisted(() => ({
     listAgents: vi.fn(),
     listSkills: vi.fn(),
     getConfig: vi.fn(),
+    fetchAvailable: vi.fn(),
   },
 }));
 
@@ -13,7 +14,7 @@ vi.mock('@/bindings', () => ({
   commands: mockCommands,
 }));
 
-import { listAgents, listSkills } from '../useTauriApi';
+import { listAgents, listSkills, fetchAvailable } from '../useTauriApi';
 
 describe('useTauriApi unwrap logic', () => {
   beforeEach(() => {
@@ -68,4 +69,27 @@ describe('useTauriApi unwrap logic', () => {
       projectPath: '/my/project',
     });
   });
+
+  it('passes tag filters to list_skills', async () => {
+    mockCommands.listSkills.mockResolvedValue({
+      status: 'ok',
+      data: { skills: [], pathExists: true, allTags: [] },
+    });
+    await listSkills({ scope: 'global', tags: ['docs'], matchAllTags: true });
+    expect(mockCommands.listSkills).toHaveBeenCalledWith({
+      scope: 'global',
+      projectPath: null,
+      tags: ['docs'],
+      matchAllTags: true,
+    });
+  });
+
+  it('passes fetch_available options positionally', async () => {
+    mockCommands.fetchAvailable.mockResolvedValue({ status: 'ok', data: { skills: [] } });
+    await fetchAvailable('owner/repo');
+    expect(mockCommands.fetchAvailable).toHaveBeenCalledWith('owner/repo', null, null, null, null, null);
+
+    await fetchAvailable('owner/repo', { gitRef: 'v1', opId: 'op-1' });
+    expect(mockCommands.fetchAvailable).toHaveBeenLastCalledWith('owner/repo', null, null, 'v1', null, 'op-1');
+  });
 });
@@ -3,18 +3,52 @@
 import { commands } from '@/bindings';
 import type {
   AgentInfo,
+  AgentPathInfo,
+  AgentRefresh,
   AgentType,
   ListSkillsResult,
   SkillScope,
   RemoveResult,
   SkillUpdateInfo,
+  SkillUpdateResult,
   FetchResult,
   InstallParams,
+  InstallPlan,
   InstallResults,
+  CancelCloneResult,
+  OverwriteKind,
   SkillDeckConfig,
+  SourceHistoryEntry,
   Scope,
   SkillAuditData,
   SkillAgentDetails,
+  SkillInfo,
+  SkillFileEntry,
+  OrphanedSkill,
+  LinkMismatch,
+  ShadowedSkill,
+  SkillUsage,
+  SkillPreview,
+  GitHubRateLimit,
+  GitInfo,
+  DoctorReport,
+  DiskUsage,
+  DependencyGraph,
+  LockMigrationResult,
+  LockIntegrityReport,
+  StateExportReport,
+  StateImportReport,
+  GitRefInfo,
+  SourceValidation,
+  LintDiagnostic,
+  RenameResult,
+  DetachResult,
+  MoveScopeResult,
+  SkillSearchResults,
+  SourceComparison,
+  SkillFileDiff,
+  SkillChangelog,
+  SkillVerifyInfo,
 } from '@/bindings';
 
 // 重导出类型供组件使用
@@ -30,6 +64,16 @@ function unwrap<T, E>(result: { status: "ok"; data: T } | { status: "error"; err
 interface ListSkillsParams {
   scope?: SkillScope;
   projectPath?: string;
+  /** 统计每个 skill 的磁盘占用 */
+  includeSize?: boolean;
+  /** 检查 Copy 模式的副本是否与 canonical 目录一致 */
+  verifyCopies?: boolean;
+  /** 要扫描的 agents，未提供时只扫描检测到的 agents */
+  agents?: AgentType[];
+  /** 只返回带有这些 tags 的 skills（不区分大小写） */
+  tags?: string[];
+  /** true 时需包含全部 tags，否则包含任一即可 */
+  matchAllTags?: boolean;
 }
 
 /**
@@ -40,6 +84,20 @@ export async function listAgents(): Promise<AgentInfo[]> {
   return unwrap(await commands.listAgents());
 }
 
+/**
+ * 重新检测 agents，并与之前的检测结果对比
+ */
+export async function refreshAgents(previousDetected?: AgentType[]): Promise<AgentRefresh> {
+  return unwrap(await commands.refreshAgents(previousDetected ?? null));
+}
+
+/**
+ * 列出各 agent 解析出的 skills 目录（诊断用）
+ */
+export async function getAgentPaths(projectPath?: string): Promise<AgentPathInfo[]> {
+  return unwrap(await commands.getAgentPaths(projectPath ?? null));
+}
+
 /**
  * 列出已安装的 Skills
  */
@@ -47,9 +105,91 @@ export async function listSkills(params?: ListSkillsParams): Promise<ListSkillsR
   return unwrap(await commands.listSkills({
     scope: params?.scope ?? null,
     projectPath: params?.projectPath ?? null,
+    includeSize: params?.includeSize,
+    verifyCopies: params?.verifyCopies,
+    agents: params?.agents,
+    tags: params?.tags,
+    matchAllTags: params?.matchAllTags,
   }));
 }
 
+// ============ Skill 详情 API ============
+
+/**
+ * 在系统默认编辑器中打开 skill 的 SKILL.md
+ */
+export async function openSkillFile(params: {
+  skillName: string;
+  scope: Scope;
+  projectPath?: string;
+}): Promise<void> {
+  unwrap(await commands.openSkillFile(params.skillName, params.scope, params.projectPath ?? null));
+}
+
+/**
+ * 获取单个已安装 skill 的完整信息
+ */
+export async function getSkillInfo(params: {
+  scope: Scope;
+  name: string;
+  projectPath?: string;
+}): Promise<SkillInfo> {
+  return unwrap(await commands.getSkillInfo(params.scope, params.name, params.projectPath ?? null));
+}
+
+/**
+ * 列出已安装 skill 的文件树
+ */
+export async function listSkillFiles(params: {
+  scope: Scope;
+  name: string;
+  projectPath?: string;
+}): Promise<SkillFileEntry[]> {
+  return unwrap(await commands.listSkillFiles(params.scope, params.name, params.projectPath ?? null));
+}
+
+/**
+ * 设置 skill 是否为 internal（internal skill 不出现在 listSkills 结果中）
+ */
+export async function setSkillInternal(params: {
+  scope: Scope;
+  name: string;
+  projectPath?: string;
+  internal: boolean;
+}): Promise<void> {
+  unwrap(
+    await commands.setSkillInternal(params.scope, params.name, params.projectPath ?? null, params.internal)
+  );
+}
+
+/**
+ * 列出孤立的 canonical skills（没有 lock 条目或没有 agent 使用）
+ */
+export async function listOrphanedSkills(scope: Scope, projectPath?: string): Promise<OrphanedSkill[]> {
+  return unwrap(await commands.listOrphanedSkills(scope, projectPath ?? null));
+}
+
+/**
+ * 检查 agent 链接是否指向正确的 canonical 目录
+ */
+export async function auditLinks(scope: Scope, projectPath?: string): Promise<LinkMismatch[]> {
+  return unwrap(await commands.auditLinks(scope, projectPath ?? null));
+}
+
+/**
+ * 检测同时安装在 global 和项目中的 skill
+ */
+export async function detectSkillShadowing(projectPath: string): Promise<ShadowedSkill[]> {
+  return unwrap(await commands.detectSkillShadowing(projectPath));
+}
+
+/**
+ * 查找 skill 在 global 和所有已保存项目中的安装位置
+ */
+export async function findSkillUsage(name: string): Promise<SkillUsage> {
+  return unwrap(await commands.findSkillUsage(name));
+}
+
 // ============ 配置相关 API ============
 
 /**
@@ -59,6 +199,13 @@ export async function getConfig(): Promise<SkillDeckConfig> {
   return unwrap(await commands.getConfig());
 }
 
+/**
+ * 获取来源历史记录
+ */
+export async function getSourceHistory(): Promise<SourceHistoryEntry[]> {
+  return unwrap(await commands.getSourceHistory());
+}
+
 /**
  * 保存应用配置
  */
@@ -70,44 +217,148 @@ export async function saveConfig(config: SkillDeckConfig): Promise<void> {
 
 /**
  * 获取上次选择的 agents
+ * 传入 projectPath 且该项目有记录时返回项目级默认 agents
  */
-export async function getLastSelectedAgents(): Promise<string[]> {
-  return await commands.getLastSelectedAgents();
+export async function getLastSelectedAgents(projectPath?: string): Promise<string[]> {
+  return await commands.getLastSelectedAgents(projectPath ?? null);
 }
 
 /**
  * 保存选择的 agents
+ * 传入 projectPath 时保存为该项目的默认 agents
  */
-export async function saveLastSelectedAgents(agents: string[]): Promise<void> {
-  unwrap(await commands.saveLastSelectedAgents(agents));
+export async function saveLastSelectedAgents(agents: string[], projectPath?: string): Promise<void> {
+  unwrap(await commands.saveLastSelectedAgents(agents, projectPath ?? null));
 }
 
 // ============ 安装相关 API ============
 
 /**
  * 从来源获取可用的 skills 列表
+ * @param options.opId - 操作 id（用于 operation-event、clone-progress 和 cancelClone）
  */
-export async function fetchAvailable(source: string): Promise<FetchResult> {
-  return unwrap(await commands.fetchAvailable(source));
+export async function fetchAvailable(
+  source: string,
+  options?: {
+    withAudit?: boolean;
+    maxDepth?: number;
+    gitRef?: string;
+    nameGlob?: string;
+    opId?: string;
+  }
+): Promise<FetchResult> {
+  return unwrap(
+    await commands.fetchAvailable(
+      source,
+      options?.withAudit ?? null,
+      options?.maxDepth ?? null,
+      options?.gitRef ?? null,
+      options?.nameGlob ?? null,
+      options?.opId ?? null
+    )
+  );
 }
 
 /**
  * 安装选中的 skills
  */
-export async function installSkills(params: InstallParams): Promise<InstallResults> {
-  return unwrap(await commands.installSkills(params));
+export async function installSkills(params: InstallParams, opId?: string): Promise<InstallResults> {
+  return unwrap(await commands.installSkills(params, opId ?? null));
+}
+
+/**
+ * 取消进行中的克隆
+ * 传入 opId 时只取消该操作的克隆，否则取消所有进行中的克隆
+ */
+export async function cancelClone(opId?: string): Promise<CancelCloneResult> {
+  return commands.cancelClone(opId ?? null);
+}
+
+/**
+ * 预览安装计划（不克隆仓库）
+ */
+export async function planInstall(params: InstallParams): Promise<InstallPlan> {
+  return unwrap(await commands.planInstall(params));
+}
+
+/**
+ * 安装前预览某个 skill 的 SKILL.md（不完整克隆仓库）
+ */
+export async function previewSkill(source: string, relativePath: string, gitRef?: string): Promise<SkillPreview> {
+  return unwrap(await commands.previewSkill(source, relativePath, gitRef ?? null));
+}
+
+/**
+ * 检测指定范围的 canonical 目录能否创建 symlink
+ */
+export async function checkSymlinkSupport(scope: Scope, projectPath?: string): Promise<boolean> {
+  return await commands.checkSymlinkSupport(scope, projectPath ?? null);
+}
+
+/**
+ * 解析 skill 的依赖图
+ */
+export async function resolveDependencies(
+  source: string,
+  skillName: string,
+  options?: { maxDepth?: number; opId?: string }
+): Promise<DependencyGraph> {
+  return unwrap(
+    await commands.resolveDependencies(source, skillName, options?.maxDepth ?? null, options?.opId ?? null)
+  );
 }
 
 /**
  * 检测覆盖情况
+ * 提供 contentHashes（来自 fetchAvailable 的 contentHash）时后端会比对内容，返回每个 agent 的覆盖类型
  */
 export async function checkOverwrites(
   skills: string[],
   agents: string[],
   scope: Scope,
-  projectPath?: string
-): Promise<Partial<Record<string, string[]>>> {
-  return unwrap(await commands.checkOverwrites(skills, agents, scope, projectPath ?? null));
+  projectPath?: string,
+  contentHashes?: Partial<Record<string, string>>
+): Promise<Partial<Record<string, [string, OverwriteKind][]>>> {
+  return unwrap(
+    await commands.checkOverwrites(skills, agents, scope, projectPath ?? null, contentHashes ?? null)
+  );
+}
+
+// ============ 来源相关 API ============
+
+/**
+ * 列出来源仓库的分支和 tags
+ */
+export async function listRefs(source: string): Promise<GitRefInfo[]> {
+  return unwrap(await commands.listRefs(source));
+}
+
+/**
+ * 校验来源格式并检查能否访问
+ */
+export async function validateSource(source: string): Promise<SourceValidation> {
+  return unwrap(await commands.validateSource(source));
+}
+
+/**
+ * 检查本地 skill 目录能否正常安装
+ */
+export async function lintSkill(path: string, opId?: string): Promise<LintDiagnostic[]> {
+  return unwrap(await commands.lintSkill(path, opId ?? null));
+}
+
+/**
+ * 在多个来源中搜索 skills
+ */
+export async function searchSkills(query: string, sources: string[], opId?: string): Promise<SkillSearchResults> {
+  return unwrap(await commands.searchSkills(query, sources, opId ?? null));
+}
+
+/**
+ * 对比两个来源的 skills 列表
+ */
+export async function compareSources(sourceA: string, sourceB: string): Promise<SourceComparison> {
+  return unwrap(await commands.compareSources(sourceA, sourceB));
 }
 
 // ============ 删除相关 API ============
@@ -116,6 +367,7 @@ export async function checkOverwrites(
  * 删除指定 skill
  * @param params.fullRemoval - true=完全删除，false=部分移除（仅删除指定 agents 的 symlink）
  * @param params.agents - 部分移除时指定的 agent 列表
+ * @param params.dryRun - 只返回将删除的路径，不删除任何内容
  */
 export async function removeSkill(params: {
   scope: Scope;
@@ -123,6 +375,7 @@ export async function removeSkill(params: {
   projectPath?: string;
   agents?: AgentType[];
   fullRemoval?: boolean;
+  dryRun?: boolean;
 }): Promise<RemoveResult> {
   return unwrap(
     await commands.removeSkill(
@@ -131,10 +384,24 @@ export async function removeSkill(params: {
       params.projectPath ?? null,
       params.agents ?? null,
       params.fullRemoval ?? null,
+      params.dryRun ?? null,
     )
   );
 }
 
+/**
+ * 清理删除后留下的空 agent skills 目录
+ */
+export async function cleanupEmptySkillDirs(params: {
+  scope: Scope;
+  projectPath?: string;
+  agents?: AgentType[];
+}): Promise<string[]> {
+  return unwrap(
+    await commands.cleanupEmptySkillDirs(params.scope, params.projectPath ?? null, params.agents ?? null)
+  );
+}
+
 /**
  * 查询 skill 的 agent 安装详情（智能删除对话框用）
  */
@@ -148,6 +415,65 @@ export async function getSkillAgentDetails(params: {
   );
 }
 
+// ============ Skill 管理 API ============
+
+/**
+ * 重命名 skill
+ * @param params.rewriteSkillMd - 同时改写 SKILL.md 中的 name 字段
+ */
+export async function renameSkill(params: {
+  scope: Scope;
+  name: string;
+  newName: string;
+  projectPath?: string;
+  rewriteSkillMd?: boolean;
+}): Promise<RenameResult> {
+  return unwrap(
+    await commands.renameSkill(
+      params.scope,
+      params.name,
+      params.newName,
+      params.projectPath ?? null,
+      params.rewriteSkillMd ?? null
+    )
+  );
+}
+
+/**
+ * 将 skill 在指定 agents 下的 symlink 替换为独立副本
+ */
+export async function detachSkill(params: {
+  scope: Scope;
+  name: string;
+  projectPath?: string;
+  agents?: AgentType[];
+}): Promise<DetachResult> {
+  return unwrap(
+    await commands.detachSkill(params.scope, params.name, params.projectPath ?? null, params.agents ?? null)
+  );
+}
+
+/**
+ * 在全局和项目之间移动 skill
+ */
+export async function moveSkillScope(params: {
+  name: string;
+  fromScope: Scope;
+  toScope: Scope;
+  projectPath?: string;
+  opId?: string;
+}): Promise<MoveScopeResult> {
+  return unwrap(
+    await commands.moveSkillScope(
+      params.name,
+      params.fromScope,
+      params.toScope,
+      params.projectPath ?? null,
+      params.opId ?? null
+    )
+  );
+}
+
 // ============ 项目管理 API ============
 
 /**
@@ -178,6 +504,72 @@ export async function openInExplorer(path: string): Promise<void> {
   unwrap(await commands.openInExplorer(path));
 }
 
+// ============ Lock 与状态 API ============
+
+/**
+ * 将项目的旧版 lock 文件迁移为 skills-lock.json
+ */
+export async function migrateProjectLock(projectPath: string, deleteLegacy?: boolean): Promise<LockMigrationResult> {
+  return unwrap(await commands.migrateProjectLock(projectPath, deleteLegacy ?? null));
+}
+
+/**
+ * 检查 lock 文件完整性，损坏时备份并重建
+ */
+export async function checkLockIntegrity(scope: Scope, projectPath?: string): Promise<LockIntegrityReport> {
+  return unwrap(await commands.checkLockIntegrity(scope, projectPath ?? null));
+}
+
+/**
+ * 导出 global 状态（tar.gz）
+ */
+export async function exportState(
+  outPath: string,
+  options?: { includeSkills?: boolean; opId?: string }
+): Promise<StateExportReport> {
+  return unwrap(await commands.exportState(outPath, options?.includeSkills ?? null, options?.opId ?? null));
+}
+
+/**
+ * 从备份恢复 global 状态
+ */
+export async function importState(
+  inPath: string,
+  options?: { merge?: boolean; opId?: string }
+): Promise<StateImportReport> {
+  return unwrap(await commands.importState(inPath, options?.merge ?? null, options?.opId ?? null));
+}
+
+// ============ 诊断 API ============
+
+/**
+ * 查询 GitHub API 限流状态
+ */
+export async function githubRateLimit(): Promise<GitHubRateLimit> {
+  return unwrap(await commands.githubRateLimit());
+}
+
+/**
+ * 检测 git 可执行文件
+ */
+export async function detectGit(): Promise<GitInfo> {
+  return unwrap(await commands.detectGit());
+}
+
+/**
+ * 运行环境健康检查
+ */
+export async function doctor(): Promise<DoctorReport> {
+  return unwrap(await commands.doctor());
+}
+
+/**
+ * 统计 skill-deck 使用的磁盘空间
+ */
+export async function diskUsage(): Promise<DiskUsage> {
+  return unwrap(await commands.diskUsage());
+}
+
 // ============ 更新检测 API ============
 
 /**
@@ -190,6 +582,13 @@ export async function checkUpdates(
   return unwrap(await commands.checkUpdates(scope, projectPath ?? null));
 }
 
+/**
+ * 同时检测全局和项目 skills 的更新
+ */
+export async function checkUpdatesAll(projectPath?: string): Promise<SkillUpdateInfo[]> {
+  return unwrap(await commands.checkUpdatesAll(projectPath ?? null));
+}
+
 /**
  * 更新指定 skill
  */
@@ -197,8 +596,68 @@ export async function updateSkill(params: {
   scope: Scope;
   name: string;
   projectPath?: string;
+  opId?: string;
+}): Promise<void> {
+  unwrap(
+    await commands.updateSkill(params.scope, params.name, params.projectPath ?? null, params.opId ?? null)
+  );
+}
+
+/**
+ * 更新指定 scope 下所有有更新的 skills
+ */
+export async function updateAll(params: {
+  scope: Scope;
+  projectPath?: string;
+  opId?: string;
+}): Promise<SkillUpdateResult[]> {
+  return unwrap(await commands.updateAll(params.scope, params.projectPath ?? null, params.opId ?? null));
+}
+
+/**
+ * 按 lock 记录重新安装 skill
+ */
+export async function reinstallSkill(params: {
+  scope: Scope;
+  name: string;
+  projectPath?: string;
+  opId?: string;
 }): Promise<void> {
-  unwrap(await commands.updateSkill(params.scope, params.name, params.projectPath ?? null));
+  unwrap(
+    await commands.reinstallSkill(params.scope, params.name, params.projectPath ?? null, params.opId ?? null)
+  );
+}
+
+/**
+ * 对比已安装 skill 与来源中的最新内容
+ */
+export async function diffSkill(params: {
+  scope: Scope;
+  name: string;
+  projectPath?: string;
+}): Promise<SkillFileDiff[]> {
+  return unwrap(await commands.diffSkill(params.scope, params.name, params.projectPath ?? null));
+}
+
+/**
+ * 列出已安装 skill 自安装以来的提交
+ */
+export async function skillChangelog(params: {
+  scope: Scope;
+  name: string;
+  projectPath?: string;
+  opId?: string;
+}): Promise<SkillChangelog> {
+  return unwrap(
+    await commands.skillChangelog(params.scope, params.name, params.projectPath ?? null, params.opId ?? null)
+  );
+}
+
+/**
+ * 检测已安装 skills 是否在安装后被本地修改
+ */
+export async function verifySkills(scope: Scope, projectPath?: string): Promise<SkillVerifyInfo[]> {
+  return unwrap(await commands.verifySkills(scope, projectPath ?? null));
 }
 
 // ============ 安全审计 API ============
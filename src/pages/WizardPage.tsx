@@ -18,6 +18,7 @@ import { InstallingStep } from '@/components/skills/add-skill/InstallingStep';
 import { CompleteStep } from '@/components/skills/add-skill/CompleteStep';
 import { ErrorStep } from '@/components/skills/add-skill/ErrorStep';
 import { getStepFlow } from '@/components/skills/add-skill/types';
+import { cancelClone } from '@/hooks/useTauriApi';
 import type {
   EntryPoint,
   CoreStep,
@@ -127,6 +128,8 @@ export function WizardPage() {
 
   // 关闭窗口
   const closeWizard = useCallback(async () => {
+    // 立即终止进行中的克隆，避免窗口关闭后 git 进程继续运行
+    await cancelClone().catch(() => false);
     await getCurrentWebviewWindow().close();
   }, []);
 
@@ -37,6 +37,9 @@ const makeSkill = (name: string, overrides: Partial<InstalledSkill> = {}): Insta
   agents: ['claude-code'],
   source: `https://github.com/test/${name}`,
   hasUpdate: false,
+  tags: [],
+  agentInstallModes: {},
+  availableLocales: [],
   ...overrides,
 });
 
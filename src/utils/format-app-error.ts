@@ -18,6 +18,8 @@ export function formatAppError(error: AppError, t: TFunction): string {
       return t('addSkill.source.error.notFound');
     case 'gitRefNotFound':
       return t('addSkill.source.error.refNotFound');
+    case 'gitCommitUnavailable':
+      return t('addSkill.source.error.commitUnavailable');
     case 'gitNetworkError':
     case 'gitCloneFailed':
       return t('addSkill.source.error.network');
@@ -25,10 +27,23 @@ export function formatAppError(error: AppError, t: TFunction): string {
       return t('addSkill.source.error.invalidSource', { value: error.data.value });
     case 'invalidAgent':
       return t('addSkill.error.invalidAgent', { agent: error.data.agent });
+    case 'skillMdMissingField':
+      return t('addSkill.error.missingField', { field: error.data.field });
     case 'pathNotFound':
       return t('addSkill.error.pathNotFound', { path: error.data.path });
     case 'installFailed':
+    case 'gitHubRateLimited':
       return error.data.message;
+    case 'cancelled':
+      return t('addSkill.source.error.cancelled');
+    case 'checksumMismatch':
+      return t('addSkill.source.error.checksumMismatch');
+    case 'offlineMode':
+      return t('addSkill.source.error.offline');
+    case 'gitNotFound':
+      return t('addSkill.source.error.gitNotFound');
+    case 'gitSshHostKeyFailed':
+      return t('addSkill.source.error.sshHostKey', { host: error.data.host });
     case 'io':
     case 'yaml':
     case 'json':
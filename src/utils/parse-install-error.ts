@@ -77,6 +77,16 @@ export function parseInstallError(
         ],
       };
 
+    case 'gitCommitUnavailable':
+      return {
+        message: t('addSkill.error.commitUnavailable', { sha: error.data.sha.slice(0, 7) }),
+        details: error.data.message,
+        suggestions: [
+          t('addSkill.error.suggestion.checkRef'),
+          t('addSkill.error.suggestion.useDefaultBranch'),
+        ],
+      };
+
     case 'gitTimeout':
       return {
         message: t('addSkill.error.cloneTimeout'),
@@ -86,6 +96,47 @@ export function parseInstallError(
         ],
       };
 
+    case 'gitHubRateLimited':
+      return {
+        message: t('addSkill.error.rateLimited'),
+        details: error.data.message,
+        suggestions: [],
+      };
+
+    case 'cancelled':
+      return {
+        message: t('addSkill.error.cancelled'),
+        suggestions: [],
+      };
+
+    case 'checksumMismatch':
+      return {
+        message: t('addSkill.error.checksumMismatch'),
+        details: `expected ${error.data.expected}\nactual   ${error.data.actual}`,
+        suggestions: [],
+      };
+
+    case 'offlineMode':
+      return {
+        message: t('addSkill.error.offline'),
+        details: error.data.operation,
+        suggestions: [],
+      };
+
+    case 'gitNotFound':
+      return {
+        message: t('addSkill.error.gitNotFound'),
+        details: error.data.message,
+        suggestions: [],
+      };
+
+    case 'gitSshHostKeyFailed':
+      return {
+        message: t('addSkill.error.sshHostKey', { host: error.data.host }),
+        details: error.data.message,
+        suggestions: [],
+      };
+
     case 'io':
       return {
         message: t('addSkill.error.ioFailed'),
@@ -138,6 +189,15 @@ export function parseInstallError(
         ],
       };
 
+    case 'skillMdMissingField':
+      return {
+        message: t('addSkill.error.parseFailed'),
+        details: t('addSkill.error.missingField', { field: error.data.field }),
+        suggestions: [
+          t('addSkill.error.suggestion.retryOrContact'),
+        ],
+      };
+
     case 'yaml':
     case 'json':
     case 'invalidSkillMd':